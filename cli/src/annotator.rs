@@ -0,0 +1,352 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External commands that annotate commits with short status strings, such as
+//! the CI status or the review state of a commit.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::Read as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetError;
+use jj_lib::content_hash::blake2b_hash;
+use jj_lib::hex_util;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::settings::UserSettings;
+use thiserror::Error;
+
+use crate::config::CommandNameAndArgs;
+
+/// Maximum number of annotators run at the same time by
+/// [`Annotators::prefetch()`].
+const MAX_CONCURRENT_ANNOTATORS: usize = 16;
+
+/// Error from running an annotator.
+#[derive(Clone, Debug, Error)]
+pub enum AnnotatorError {
+    #[error("Failed to run annotator {name}")]
+    Io {
+        name: String,
+        #[source]
+        source: Arc<io::Error>,
+    },
+    #[error("Annotator {name} timed out")]
+    Timeout { name: String },
+    #[error("Annotator {name} failed")]
+    Failed { name: String },
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawAnnotatorConfig {
+    command: CommandNameAndArgs,
+    #[serde(default = "default_timeout_secs")]
+    timeout_secs: u64,
+    #[serde(default = "default_cache_ttl_secs")]
+    cache_ttl_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    60
+}
+
+/// An external command configured in `annotators.<name>`.
+#[derive(Debug)]
+struct Annotator {
+    command: CommandNameAndArgs,
+    /// Hash of the command, which keys the cached annotations so that they
+    /// are discarded when the command changes.
+    command_hash: String,
+    timeout: Duration,
+    cache_ttl: Duration,
+}
+
+/// Annotation stored in the disk cache.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum CachedAnnotation {
+    Annotation(String),
+    Failed,
+    TimedOut,
+}
+
+/// The configured annotators.
+///
+/// Annotations are cached in memory for the lifetime of this object, and on
+/// disk for the configured time to live, so that e.g. `jj log` doesn't run
+/// the annotators for the same commits again and again. Failures and timeouts
+/// are cached too.
+///
+/// The disk cache is laid out as `<cache_dir>/<name>/<command hash>/<commit
+/// id>`. Entries of removed annotators, of changed commands, and expired
+/// entries are pruned before the cache is first written to.
+#[derive(Debug, Default)]
+pub struct Annotators {
+    annotators: BTreeMap<String, Annotator>,
+    workspace_root: PathBuf,
+    cache_dir: Option<PathBuf>,
+    annotations: Mutex<HashMap<(String, CommitId), Result<String, AnnotatorError>>>,
+    pruned: OnceLock<()>,
+}
+
+impl Annotators {
+    /// Loads the annotators from the `annotators` config table. The
+    /// annotators are run in `workspace_root`, and their output is cached in
+    /// `cache_dir`.
+    pub fn from_settings(
+        settings: &UserSettings,
+        workspace_root: &Path,
+        cache_dir: &Path,
+    ) -> Result<Self, ConfigGetError> {
+        let annotators = settings
+            .table_keys("annotators")
+            .map(|name| -> Result<_, ConfigGetError> {
+                let config: RawAnnotatorConfig = settings.get(["annotators", name])?;
+                let annotator = Annotator {
+                    command_hash: command_hash(&config.command),
+                    command: config.command,
+                    timeout: Duration::from_secs(config.timeout_secs),
+                    cache_ttl: Duration::from_secs(config.cache_ttl_secs),
+                };
+                Ok((name.to_owned(), annotator))
+            })
+            .try_collect()?;
+        Ok(Self {
+            annotators,
+            workspace_root: workspace_root.to_owned(),
+            cache_dir: Some(cache_dir.to_owned()),
+            annotations: Mutex::default(),
+            pruned: OnceLock::new(),
+        })
+    }
+
+    /// Returns the names of the annotators, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.annotators.keys().map(String::as_str)
+    }
+
+    /// Returns the annotation of `commit` by the annotator `name`, running the
+    /// annotator if the annotation isn't cached.
+    ///
+    /// The annotation is empty if there's no such annotator.
+    pub fn annotate(&self, name: &str, commit: &Commit) -> Result<String, AnnotatorError> {
+        let Some(annotator) = self.annotators.get(name) else {
+            return Ok(String::new());
+        };
+        let key = (name.to_owned(), commit.id().clone());
+        if let Some(result) = self.annotations.lock().unwrap().get(&key) {
+            return result.clone();
+        }
+        let cache_path = self.cache_dir.as_ref().map(|dir| {
+            dir.join(name)
+                .join(&annotator.command_hash)
+                .join(commit.id().hex())
+        });
+        let cached = cache_path
+            .as_deref()
+            .and_then(|path| read_cached_annotation(path, annotator.cache_ttl));
+        let result = match cached {
+            Some(CachedAnnotation::Annotation(annotation)) => Ok(annotation),
+            Some(CachedAnnotation::Failed) => Err(AnnotatorError::Failed {
+                name: name.to_owned(),
+            }),
+            Some(CachedAnnotation::TimedOut) => Err(AnnotatorError::Timeout {
+                name: name.to_owned(),
+            }),
+            None => {
+                let result = annotator.run(name, &self.workspace_root, commit);
+                let cached = match &result {
+                    Ok(annotation) => Some(CachedAnnotation::Annotation(annotation.clone())),
+                    Err(AnnotatorError::Failed { .. }) => Some(CachedAnnotation::Failed),
+                    Err(AnnotatorError::Timeout { .. }) => Some(CachedAnnotation::TimedOut),
+                    // The command may be missing only temporarily.
+                    Err(AnnotatorError::Io { .. }) => None,
+                };
+                if let Some(path) = &cache_path
+                    && let Some(cached) = cached
+                    && !annotator.cache_ttl.is_zero()
+                {
+                    self.pruned.get_or_init(|| self.prune_cache());
+                    // The cache is only an optimization.
+                    write_cached_annotation(path, &cached).ok();
+                }
+                result
+            }
+        };
+        self.annotations.lock().unwrap().insert(key, result.clone());
+        result
+    }
+
+    /// Annotates each of `commits` by each annotator, running the annotators
+    /// in parallel. The results are cached, so the following calls to
+    /// [`Self::annotate()`] don't have to wait for the annotators one by one.
+    pub fn prefetch(&self, commits: &[Commit]) {
+        let pairs = self.names().cartesian_product(commits).collect_vec();
+        for chunk in pairs.chunks(MAX_CONCURRENT_ANNOTATORS) {
+            thread::scope(|scope| {
+                for &(name, commit) in chunk {
+                    scope.spawn(move || self.annotate(name, commit));
+                }
+            });
+        }
+    }
+
+    /// Removes the cached annotations of annotators that are no longer
+    /// configured or whose command changed, and the expired annotations.
+    fn prune_cache(&self) {
+        let Some(cache_dir) = &self.cache_dir else {
+            return;
+        };
+        for entry in read_dir_entries(cache_dir) {
+            let annotator = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| self.annotators.get(name));
+            let Some(annotator) = annotator else {
+                remove_entry(&entry);
+                continue;
+            };
+            for entry in read_dir_entries(&entry.path()) {
+                if entry.file_name().to_str() != Some(&annotator.command_hash) {
+                    remove_entry(&entry);
+                    continue;
+                }
+                for entry in read_dir_entries(&entry.path()) {
+                    if read_cached_annotation(&entry.path(), annotator.cache_ttl).is_none() {
+                        remove_entry(&entry);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Annotator {
+    /// Runs the command, and returns the first line of its output.
+    fn run(&self, name: &str, cwd: &Path, commit: &Commit) -> Result<String, AnnotatorError> {
+        let io_error = |source| AnnotatorError::Io {
+            name: name.to_owned(),
+            source: Arc::new(source),
+        };
+        let commit_id = commit.id().hex();
+        let change_id = commit.change_id().reverse_hex();
+        let variables = HashMap::from([
+            ("commit_id", commit_id.as_str()),
+            ("change_id", change_id.as_str()),
+        ]);
+        let mut command = self.command.to_command_with_variables(&variables);
+        tracing::debug!(?command, "spawning annotator");
+        let mut child = command
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(io_error)?;
+        let mut stdout = child.stdout.take().unwrap();
+        // Read the output concurrently so the annotator doesn't block on a
+        // full pipe.
+        let reader = thread::spawn(move || {
+            let mut output = vec![];
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait().map_err(io_error)? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                child.kill().ok();
+                child.wait().ok();
+                return Err(AnnotatorError::Timeout {
+                    name: name.to_owned(),
+                });
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        if !status.success() {
+            return Err(AnnotatorError::Failed {
+                name: name.to_owned(),
+            });
+        }
+        let output = reader.join().unwrap().map_err(io_error)?;
+        let output = String::from_utf8_lossy(&output);
+        Ok(output.lines().next().unwrap_or_default().trim().to_owned())
+    }
+}
+
+/// Returns a short hash identifying `command`, including its environment.
+fn command_hash(command: &CommandNameAndArgs) -> String {
+    let (name, args) = command.split_name_and_args();
+    let argv = itertools::chain([name.into_owned()], args.iter().cloned()).collect_vec();
+    let env = match command {
+        CommandNameAndArgs::Structured { env, .. } => env
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .sorted()
+            .collect(),
+        CommandNameAndArgs::String(_) | CommandNameAndArgs::Vec(_) => vec![],
+    };
+    let hash = blake2b_hash(&vec![argv, env]);
+    hex_util::encode_hex(&hash[..16])
+}
+
+/// Reads the annotation cached at `path`, unless it's older than `ttl`.
+fn read_cached_annotation(path: &Path, ttl: Duration) -> Option<CachedAnnotation> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    if modified.elapsed().ok()? >= ttl {
+        return None;
+    }
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+fn write_cached_annotation(path: &Path, annotation: &CachedAnnotation) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_vec(annotation).unwrap())
+}
+
+fn read_dir_entries(dir: &Path) -> impl Iterator<Item = fs::DirEntry> {
+    fs::read_dir(dir).into_iter().flatten().flatten()
+}
+
+/// Removes the file or directory at `entry`, ignoring errors since the cache
+/// is only an optimization.
+fn remove_entry(entry: &fs::DirEntry) {
+    let path = entry.path();
+    if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+        fs::remove_dir_all(path).ok();
+    } else {
+        fs::remove_file(path).ok();
+    }
+}
@@ -21,7 +21,10 @@ use std::env;
 use std::ffi::OsString;
 use std::fmt;
 use std::fmt::Debug;
+use std::fs;
+use std::fs::File;
 use std::io;
+use std::io::Read as _;
 use std::io::Write as _;
 use std::mem;
 use std::path::Path;
@@ -45,6 +48,8 @@ use clap::error::ContextKind;
 use clap::error::ContextValue;
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use clap_complete::env::Shells;
+use futures::StreamExt as _;
 use indexmap::IndexMap;
 use indexmap::IndexSet;
 use indoc::indoc;
@@ -63,6 +68,8 @@ use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
+use jj_lib::evolution::accumulate_transaction_predecessors;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
@@ -82,6 +89,9 @@ use jj_lib::op_store::RefTarget;
 use jj_lib::op_walk;
 use jj_lib::op_walk::OpsetEvaluationError;
 use jj_lib::operation::Operation;
+use jj_lib::patch::FilePatch;
+use jj_lib::patch::apply_patch_to_tree;
+use jj_lib::patch::parse_patch;
 use jj_lib::ref_name::RefName;
 use jj_lib::ref_name::RefNameBuf;
 use jj_lib::ref_name::RemoteName;
@@ -121,6 +131,7 @@ use jj_lib::store::Store;
 use jj_lib::str_util::StringExpression;
 use jj_lib::str_util::StringMatcher;
 use jj_lib::str_util::StringPattern;
+use jj_lib::time_util::DatePattern;
 use jj_lib::transaction::Transaction;
 use jj_lib::working_copy;
 use jj_lib::working_copy::CheckoutStats;
@@ -144,8 +155,11 @@ use tracing::instrument;
 use tracing_chrome::ChromeLayerBuilder;
 use tracing_subscriber::prelude::*;
 
+use crate::annotator::Annotators;
+use crate::cleanup_guard::CleanupGuard;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
+use crate::command_error::config_error;
 use crate::command_error::config_error_with_message;
 use crate::command_error::handle_command_result;
 use crate::command_error::internal_error;
@@ -153,6 +167,7 @@ use crate::command_error::internal_error_with_message;
 use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::commit_templater::CommitTemplateLanguageExtension;
 use crate::complete;
@@ -166,12 +181,15 @@ use crate::diff_util;
 use crate::diff_util::DiffFormat;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
+use crate::diff_util::describe_patch_rejection;
 use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
+use crate::graphlog::GraphStyle;
 use crate::merge_tools::DiffEditor;
 use crate::merge_tools::MergeEditor;
 use crate::merge_tools::MergeToolConfigError;
+use crate::op_diff_util::show_repo_diff;
 use crate::operation_templater::OperationTemplateLanguage;
 use crate::operation_templater::OperationTemplateLanguageExtension;
 use crate::revset_util;
@@ -188,27 +206,49 @@ use crate::ui::Ui;
 
 const SHORT_CHANGE_ID_TEMPLATE_TEXT: &str = "format_short_change_id(self.change_id())";
 
+type ChromeTracingLayer = Option<tracing_chrome::ChromeLayer<tracing_subscriber::Registry>>;
+type TracingRegistry = tracing_subscriber::layer::Layered<
+    tracing_subscriber::reload::Layer<ChromeTracingLayer, tracing_subscriber::Registry>,
+    tracing_subscriber::Registry,
+>;
+
 #[derive(Clone)]
-struct ChromeTracingFlushGuard {
-    _inner: Option<Rc<tracing_chrome::FlushGuard>>,
+struct ChromeTracing {
+    reload_layer:
+        tracing_subscriber::reload::Handle<ChromeTracingLayer, tracing_subscriber::Registry>,
+    flush_guard: Rc<OnceCell<tracing_chrome::FlushGuard>>,
 }
 
-impl Debug for ChromeTracingFlushGuard {
+impl Debug for ChromeTracing {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let Self { _inner } = self;
-        f.debug_struct("ChromeTracingFlushGuard")
+        let Self {
+            reload_layer: _,
+            flush_guard,
+        } = self;
+        f.debug_struct("ChromeTracing")
+            .field("enabled", &flush_guard.get().is_some())
             .finish_non_exhaustive()
     }
 }
 
 /// Handle to initialize or change tracing subscription.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct TracingSubscription {
-    reload_log_filter: tracing_subscriber::reload::Handle<
-        tracing_subscriber::EnvFilter,
-        tracing_subscriber::Registry,
-    >,
-    _chrome_tracing_flush_guard: ChromeTracingFlushGuard,
+    reload_log_filter:
+        tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, TracingRegistry>,
+    chrome_tracing: ChromeTracing,
+}
+
+impl Debug for TracingSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let Self {
+            reload_log_filter: _,
+            chrome_tracing,
+        } = self;
+        f.debug_struct("TracingSubscription")
+            .field("chrome_tracing", chrome_tracing)
+            .finish_non_exhaustive()
+    }
 }
 
 impl TracingSubscription {
@@ -236,33 +276,53 @@ impl TracingSubscription {
                 } else {
                     filename
                 };
-                let include_args = std::env::var("JJ_TRACE_INCLUDE_ARGS").is_ok();
-                let (layer, guard) = ChromeLayerBuilder::new()
-                    .file(filename)
-                    .include_args(include_args)
-                    .build();
-                (
-                    Some(layer),
-                    ChromeTracingFlushGuard {
-                        _inner: Some(Rc::new(guard)),
-                    },
-                )
+                let (layer, guard) = chrome_tracing_layer_builder().file(filename).build();
+                (Some(layer), OnceCell::from(guard))
             }
-            Err(_) => (None, ChromeTracingFlushGuard { _inner: None }),
+            Err(_) => (None, OnceCell::new()),
         };
+        let (chrome_tracing_layer, reload_chrome_tracing_layer) =
+            tracing_subscriber::reload::Layer::new(chrome_tracing_layer);
 
         tracing_subscriber::registry()
+            .with(chrome_tracing_layer)
             .with(
                 tracing_subscriber::fmt::Layer::default()
                     .with_writer(std::io::stderr)
                     .with_filter(filter),
             )
-            .with(chrome_tracing_layer)
             .init();
         Self {
             reload_log_filter,
-            _chrome_tracing_flush_guard: chrome_tracing_flush_guard,
+            chrome_tracing: ChromeTracing {
+                reload_layer: reload_chrome_tracing_layer,
+                flush_guard: Rc::new(chrome_tracing_flush_guard),
+            },
+        }
+    }
+
+    /// Starts writing spans to `path` in the Chrome trace event format. The
+    /// file is flushed when the last handle to this subscription is dropped.
+    pub fn enable_chrome_tracing(&self, path: &Path) -> Result<(), CommandError> {
+        if self.chrome_tracing.flush_guard.get().is_some() {
+            return Err(user_error(
+                "Trace output is already enabled by the JJ_TRACE environment variable",
+            ));
         }
+        let file = File::create(path).map_err(|err| {
+            user_error_with_message(
+                format!("Failed to create trace file {}", path.display()),
+                err,
+            )
+        })?;
+        let (layer, guard) = chrome_tracing_layer_builder().writer(file).build();
+        self.chrome_tracing
+            .reload_layer
+            .reload(Some(layer))
+            .map_err(|err| internal_error_with_message("failed to enable trace output", err))?;
+        self.chrome_tracing.flush_guard.get_or_init(|| guard);
+        tracing::info!(?path, "trace output enabled");
+        Ok(())
     }
 
     pub fn enable_debug_logging(&self) -> Result<(), CommandError> {
@@ -284,6 +344,11 @@ impl TracingSubscription {
     }
 }
 
+fn chrome_tracing_layer_builder() -> ChromeLayerBuilder<tracing_subscriber::Registry> {
+    let include_args = std::env::var("JJ_TRACE_INCLUDE_ARGS").is_ok();
+    ChromeLayerBuilder::new().include_args(include_args)
+}
+
 #[derive(Clone)]
 pub struct CommandHelper {
     data: Rc<CommandHelperData>,
@@ -345,6 +410,11 @@ impl CommandHelper {
         &self.data.raw_config
     }
 
+    /// Rules to migrate deprecated config variables.
+    pub fn config_migrations(&self) -> &[ConfigMigrationRule] {
+        &self.data.config_migrations
+    }
+
     /// Settings for the current command and workspace.
     ///
     /// This may be different from the settings for new workspace created by
@@ -791,6 +861,8 @@ pub struct WorkspaceCommandEnvironment {
     immutable_heads_expression: Arc<UserRevsetExpression>,
     short_prefixes_expression: Option<Arc<UserRevsetExpression>>,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
+    annotators: Arc<Annotators>,
 }
 
 impl WorkspaceCommandEnvironment {
@@ -815,6 +887,12 @@ impl WorkspaceCommandEnvironment {
             immutable_heads_expression: RevsetExpression::root(),
             short_prefixes_expression: None,
             conflict_marker_style: settings.get("ui.conflict-marker-style")?,
+            conflict_marker_style_overrides: ConflictMarkerStyleOverrides::from_settings(settings)?,
+            annotators: Arc::new(Annotators::from_settings(
+                settings,
+                workspace.workspace_root(),
+                &workspace.repo_path().join("annotations"),
+            )?),
         };
         env.immutable_heads_expression = env.load_immutable_heads_expression(ui)?;
         env.short_prefixes_expression = env.load_short_prefixes_expression(ui)?;
@@ -829,6 +907,11 @@ impl WorkspaceCommandEnvironment {
         &self.workspace_name
     }
 
+    /// Returns the annotators configured for this workspace.
+    pub fn annotators(&self) -> &Annotators {
+        &self.annotators
+    }
+
     pub(crate) fn revset_parse_context(&self) -> RevsetParseContext<'_> {
         let workspace_context = RevsetWorkspaceContext {
             path_converter: &self.path_converter,
@@ -879,6 +962,11 @@ impl WorkspaceCommandEnvironment {
         self.conflict_marker_style
     }
 
+    /// User-configured conflict marker styles for specific files
+    pub fn conflict_marker_style_overrides(&self) -> &ConflictMarkerStyleOverrides {
+        &self.conflict_marker_style_overrides
+    }
+
     fn load_immutable_heads_expression(
         &self,
         ui: &Ui,
@@ -992,6 +1080,8 @@ impl WorkspaceCommandEnvironment {
             id_prefix_context,
             self.immutable_expression(),
             self.conflict_marker_style,
+            &self.conflict_marker_style_overrides,
+            self.annotators.clone(),
             &self.command.data.commit_template_extensions,
         )
     }
@@ -1452,6 +1542,7 @@ to the current parents may contain changes from multiple commits.
             self.repo().as_ref(),
             self.path_converter(),
             self.env.conflict_marker_style(),
+            self.env.conflict_marker_style_overrides(),
             formats,
         )
     }
@@ -1520,6 +1611,31 @@ to the current parents may contain changes from multiple commits.
         }
     }
 
+    /// Loads a diff selector that selects the changes in the patch file at
+    /// `path`, or in the standard input if `path` is `-`.
+    pub fn patch_selector(&self, path: &Path) -> Result<DiffSelector, CommandError> {
+        let content = if path.as_os_str() == "-" {
+            let mut content = vec![];
+            io::stdin()
+                .read_to_end(&mut content)
+                .map_err(|err| user_error_with_message("Failed to read standard input", err))?;
+            content
+        } else {
+            fs::read(self.env.command.cwd().join(path)).map_err(|err| {
+                user_error_with_message(format!("Failed to read {}", path.display()), err)
+            })?
+        };
+        let patches = parse_patch(&content, 1)
+            .map_err(|err| user_error_with_message("Failed to parse the patch", err))?;
+        if patches.is_empty() {
+            return Err(user_error("No changes found in the patch"));
+        }
+        Ok(DiffSelector::Patch {
+            patches,
+            path_converter: self.path_converter().clone(),
+        })
+    }
+
     /// Loads 3-way merge editor from the settings.
     ///
     /// If the `tool_name` isn't specified, the default editor will be returned.
@@ -1837,6 +1953,45 @@ to the current parents may contain changes from multiple commits.
         Err(error)
     }
 
+    /// Starts the built-in filesystem monitor daemon in the background if it's
+    /// enabled but not running. Snapshots benefit from it once it has started.
+    #[cfg(all(feature = "notify", unix))]
+    fn maybe_start_builtin_fsmonitor(&self, ui: &Ui) -> Result<(), CommandError> {
+        use std::os::unix::process::CommandExt as _;
+        use std::process::Command;
+        use std::process::Stdio;
+
+        use jj_lib::fsmonitor::FsmonitorSettings;
+        use jj_lib::fsmonitor::builtin;
+
+        if !matches!(
+            FsmonitorSettings::from_settings(self.settings())?,
+            FsmonitorSettings::Builtin(_)
+        ) || builtin::is_daemon_running(self.workspace_root())
+        {
+            return Ok(());
+        }
+        let result = env::current_exe().and_then(|jj_path| {
+            Command::new(jj_path)
+                .arg("--repository")
+                .arg(self.workspace_root())
+                .args(["debug", "fsmonitor-daemon"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                // Don't get killed along with the current command by Ctrl-C
+                .process_group(0)
+                .spawn()
+        });
+        if let Err(err) = result {
+            writeln!(
+                ui.warning_default(),
+                "Failed to start the filesystem monitor daemon: {err}"
+            )?;
+        }
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     fn snapshot_working_copy(
         &mut self,
@@ -1856,6 +2011,9 @@ to the current parents may contain changes from multiple commits.
             // committing the working copy.
             return Ok(SnapshotStats::default());
         };
+        #[cfg(all(feature = "notify", unix))]
+        self.maybe_start_builtin_fsmonitor(ui)
+            .map_err(snapshot_command_error)?;
         let auto_tracking_matcher = self
             .auto_tracking_matcher(ui)
             .map_err(snapshot_command_error)?;
@@ -1989,7 +2147,66 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             maybe_old_commit,
             new_commit,
         )?;
-        self.print_updated_working_copy_stats(ui, maybe_old_commit, new_commit, &stats)
+        self.print_updated_working_copy_stats(ui, maybe_old_commit, new_commit, &stats)?;
+        #[cfg(feature = "git")]
+        self.update_git_submodules(ui, new_commit)?;
+        Ok(())
+    }
+
+    /// Checks out the commits recorded for Git submodules if
+    /// `git.update-submodules` is enabled.
+    #[cfg(feature = "git")]
+    fn update_git_submodules(&self, ui: &Ui, new_commit: &Commit) -> Result<(), CommandError> {
+        use jj_lib::git;
+
+        use crate::submodule_util;
+
+        if !self.settings().get_bool("git.update-submodules")? {
+            return Ok(());
+        }
+        let Ok(git_backend) = git::get_git_backend(self.repo().store()) else {
+            return Ok(());
+        };
+        let git_settings = git::GitSettings::from_settings(self.settings())?;
+        let modules_dir = git_backend.git_repo_path().join("modules");
+        // Relative submodule URLs are resolved against the default remote,
+        // falling back to the workspace itself like Git does.
+        let git_repo = git_backend.git_repo();
+        let superproject_url = git_repo
+            .remote_default_name(gix::remote::Direction::Fetch)
+            .and_then(|name| git_repo.try_find_remote(name.as_ref())?.ok())
+            .and_then(|remote| Some(remote.url(gix::remote::Direction::Fetch)?.to_bstring()))
+            .map_or_else(
+                || self.workspace_root().to_string_lossy().into_owned(),
+                |url| url.to_string(),
+            );
+        for (submodule, commit_id) in submodule_util::submodules_in_tree(&new_commit.tree())? {
+            let dir = submodule.path.to_fs_path_unchecked(self.workspace_root());
+            let head = submodule_util::submodule_head(&git_settings.executable_path, &dir);
+            if head.as_ref() == Some(&commit_id) {
+                continue;
+            }
+            let ui_path = self.format_file_path(&submodule.path);
+            match submodule_util::check_out_submodule(
+                &git_settings.executable_path,
+                self.workspace_root(),
+                &modules_dir,
+                &superproject_url,
+                &submodule,
+                &commit_id,
+            ) {
+                Ok(()) => writeln!(
+                    ui.status(),
+                    "Checked out submodule {ui_path} at {}",
+                    short_commit_hash(&commit_id)
+                )?,
+                Err(err) => writeln!(
+                    ui.warning_default(),
+                    "Failed to update submodule {ui_path}: {err}"
+                )?,
+            }
+        }
+        Ok(())
     }
 
     fn print_updated_working_copy_stats(
@@ -2039,15 +2256,12 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         }
     }
 
-    fn finish_transaction(
-        &mut self,
-        ui: &Ui,
-        mut tx: Transaction,
-        description: impl Into<String>,
-    ) -> Result<(), CommandError> {
+    /// Rebases descendants and checks out new working-copy commits as needed.
+    /// Returns false if the transaction has no changes.
+    fn prepare_transaction(&self, ui: &Ui, tx: &mut Transaction) -> Result<bool, CommandError> {
         if !tx.repo().has_changes() {
             writeln!(ui.status(), "Nothing changed.")?;
-            return Ok(());
+            return Ok(false);
         }
         let num_rebased = tx.repo_mut().rebase_descendants()?;
         if num_rebased > 0 {
@@ -2070,6 +2284,18 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 )?;
             }
         }
+        Ok(true)
+    }
+
+    fn finish_transaction(
+        &mut self,
+        ui: &Ui,
+        mut tx: Transaction,
+        description: impl Into<String>,
+    ) -> Result<(), CommandError> {
+        if !self.prepare_transaction(ui, &mut tx)? {
+            return Ok(());
+        }
 
         let old_repo = tx.base_repo().clone();
 
@@ -2152,6 +2378,48 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         Ok(())
     }
 
+    /// Shows the changes the transaction would make to the repo, without
+    /// committing the transaction or updating the working copy.
+    fn preview_transaction(&self, ui: &Ui, mut tx: Transaction) -> Result<(), CommandError> {
+        if !self.prepare_transaction(ui, &mut tx)? {
+            return Ok(());
+        }
+        // The diff is computed from the uncommitted transaction, so no
+        // operation or view is written. Commits created by the command have
+        // already been written to the backend, though.
+        let predecessor_commits = accumulate_transaction_predecessors(tx.repo())?;
+
+        let settings = self.settings();
+        let id_prefix_context = self.env.new_id_prefix_context();
+        let language = self
+            .env
+            .commit_template_language(tx.repo(), &id_prefix_context);
+        let commit_summary_template = self
+            .env
+            .parse_template(ui, &language, &self.commit_summary_template_text)?
+            .labeled(["commit"]);
+        let graph_style = GraphStyle::from_settings(settings)?;
+        let with_content_format = LogContentFormat::new(ui, settings)?;
+        let mut buffer = vec![];
+        show_repo_diff(
+            ui,
+            ui.new_formatter(&mut buffer).as_mut(),
+            tx.repo(),
+            tx.base_repo().as_ref(),
+            tx.repo(),
+            &predecessor_commits,
+            &commit_summary_template,
+            Some(graph_style),
+            &with_content_format,
+            None,
+        )?;
+        // Each section of the diff starts with an empty line.
+        ui.stdout()
+            .write_all(buffer.strip_prefix(b"\n").unwrap_or(&buffer))?;
+        writeln!(ui.status(), "Dry run: no changes were made.")?;
+        Ok(())
+    }
+
     /// Inform the user about important changes to the repo since the previous
     /// operation (when `old_repo` was loaded).
     fn report_repo_changes(
@@ -2482,6 +2750,15 @@ impl WorkspaceCommandTransaction<'_> {
         self.helper.finish_transaction(ui, self.tx, description)
     }
 
+    /// Shows the changes made by the transaction instead of committing it.
+    ///
+    /// No operation is recorded and the working copy isn't updated, but the
+    /// commits written by the transaction stay in the backend (and in
+    /// `refs/jj/keep/*` for the Git backend) until `jj util gc` removes them.
+    pub fn finish_dry_run(self, ui: &Ui) -> Result<(), CommandError> {
+        self.helper.preview_transaction(ui, self.tx)
+    }
+
     /// Returns the wrapped [`Transaction`] for circumstances where
     /// finer-grained control is needed. The caller becomes responsible for
     /// finishing the `Transaction`, including rebasing descendants and updating
@@ -2998,11 +3275,30 @@ pub fn short_operation_hash(operation_id: &OperationId) -> String {
     format!("{operation_id:.12}")
 }
 
+/// Parses an absolute or relative `date` into a pattern matching the date and
+/// later.
+pub fn parse_date_after(settings: &UserSettings, date: &str) -> Result<DatePattern, CommandError> {
+    let now = if let Some(timestamp) = settings.commit_timestamp() {
+        chrono::Local
+            .timestamp_millis_opt(timestamp.timestamp.0)
+            .unwrap()
+    } else {
+        chrono::Local::now()
+    };
+    DatePattern::from_str_kind(date, "after", now)
+        .map_err(|err| user_error_with_message(format!("Invalid date: {date}"), err))
+}
+
 /// Wrapper around a `DiffEditor` to conditionally start interactive session.
 #[derive(Clone, Debug)]
 pub enum DiffSelector {
     NonInteractive,
     Interactive(DiffEditor),
+    /// Selects the changes described by a patch.
+    Patch {
+        patches: Vec<FilePatch>,
+        path_converter: RepoPathUiConverter,
+    },
 }
 
 impl DiffSelector {
@@ -3033,6 +3329,47 @@ impl DiffSelector {
                     format_instructions,
                 )?)
             }
+            Self::Patch {
+                patches,
+                path_converter,
+            } => {
+                let application = apply_patch_to_tree(trees.before, patches).block_on()?;
+                if !application.rejections.is_empty() {
+                    let details = application
+                        .rejections
+                        .iter()
+                        .map(|rejection| {
+                            let path = path_converter.format_file_path(&rejection.path);
+                            format!("  {path}: {}", describe_patch_rejection(&rejection.reason))
+                        })
+                        .join("\n");
+                    return Err(user_error(format!("Failed to apply the patch:\n{details}")));
+                }
+                // Changes to unmatched paths aren't selected.
+                let selected_tree =
+                    restore_tree(&application.tree, trees.before, matcher).block_on()?;
+                // The selected changes must be part of the diff, so reapplying
+                // the diff on top of them should produce the right tree.
+                let reapplied_tree = selected_tree
+                    .clone()
+                    .merge(trees.before.clone(), trees.after.clone())
+                    .block_on()?;
+                if reapplied_tree.tree_ids() != trees.after.tree_ids() {
+                    let paths: Vec<_> = reapplied_tree
+                        .diff_stream(trees.after, matcher)
+                        .map(|entry| entry.path)
+                        .collect()
+                        .block_on();
+                    let details = paths
+                        .iter()
+                        .map(|path| format!("  {}", path_converter.format_file_path(path)))
+                        .join("\n");
+                    return Err(user_error(format!(
+                        "The patch contains changes that are not in the diff:\n{details}"
+                    )));
+                }
+                Ok(selected_tree)
+            }
         }
     }
 }
@@ -3270,6 +3607,14 @@ pub struct GlobalArgs {
     /// Enable debug logging
     #[arg(long, global = true)]
     pub debug: bool,
+    /// Write a performance trace to the given file
+    ///
+    /// The trace is written in the Chrome trace event format, which can be
+    /// loaded into `chrome://tracing` or <https://ui.perfetto.dev>. It includes
+    /// spans for working-copy snapshots, index loading, revset evaluation, and
+    /// backend reads and writes.
+    #[arg(long, value_name = "FILE", global = true, value_hint = clap::ValueHint::FilePath)]
+    pub trace_output: Option<PathBuf>,
 
     #[command(flatten)]
     pub early_args: EarlyArgs,
@@ -3480,7 +3825,7 @@ fn resolve_aliases(
                 )));
             }
             if let Some(&alias_name) = defined_aliases.get(&*alias_name) {
-                let alias_definition: Vec<String> = config.get(["aliases", alias_name])?;
+                let alias_definition = resolve_alias_definition(config, alias_name)?;
                 assert!(string_args.ends_with(&alias_args));
                 string_args.truncate(string_args.len() - 1 - alias_args.len());
                 string_args.extend(alias_definition);
@@ -3497,6 +3842,29 @@ fn resolve_aliases(
     }
 }
 
+/// Looks up the command-line arguments the alias expands to.
+///
+/// A string alias starting with `!` is a shell command, which is run through
+/// `jj util exec` so the arguments are available as `$1`, `$2`, ..., and `$@`.
+fn resolve_alias_definition(
+    config: &StackedConfig,
+    alias_name: &str,
+) -> Result<Vec<String>, CommandError> {
+    let name = ["aliases", alias_name];
+    let Ok(definition) = config.get::<String>(name) else {
+        return Ok(config.get(name)?);
+    };
+    let Some(script) = definition.strip_prefix('!') else {
+        return Err(config_error(format!(
+            "Alias `{alias_name}` must be an array of strings, or a string starting with `!` to \
+             run a shell command"
+        )));
+    };
+    Ok(["util", "exec", "--", "sh", "-c", script, alias_name]
+        .map(str::to_owned)
+        .to_vec())
+}
+
 /// Parse args that must be interpreted early, e.g. before printing help.
 fn parse_early_args(
     app: &Command,
@@ -3604,6 +3972,14 @@ fn handle_shell_completion(
             // for completing aliases
             .allow_external_subcommands(true)
     })
+    .shells(Shells(&[
+        &clap_complete::env::Bash,
+        &clap_complete::env::Elvish,
+        &clap_complete::env::Fish,
+        &crate::complete::Nushell,
+        &clap_complete::env::Powershell,
+        &clap_complete::env::Zsh,
+    ]))
     .try_complete(args.iter(), Some(cwd))?;
     assert!(
         ran_completion,
@@ -3919,6 +4295,9 @@ impl<'a> CliRunner<'a> {
             // TODO: set up debug logging as early as possible
             self.tracing_subscription.enable_debug_logging()?;
         }
+        if let Some(path) = &args.global_args.trace_output {
+            self.tracing_subscription.enable_chrome_tracing(path)?;
+        }
         for process_global_args_fn in self.process_global_args_fns {
             process_global_args_fn(ui, &matches)?;
         }
@@ -4010,7 +4389,10 @@ impl<'a> CliRunner<'a> {
         // If it had, the configuration will be fixed by the next ui.reset().
         let mut ui = Ui::with_config(config.as_ref())
             .expect("default config should be valid, env vars are stringly typed");
+        // Signing agents are stopped even if the command is interrupted.
+        let agent_guard = CleanupGuard::new(jj_lib::ssh_signing::stop_batch_session_agents);
         let result = self.run_internal(&mut ui, config);
+        drop(agent_guard);
         let exit_code = handle_command_result(&mut ui, result);
         ui.finalize_pager();
         exit_code
@@ -61,6 +61,7 @@ use jj_lib::working_copy::ResetError;
 use jj_lib::working_copy::SnapshotError;
 use jj_lib::working_copy::WorkingCopyStateError;
 use jj_lib::workspace::WorkspaceInitError;
+use jj_lib::workspace_store::WorkspaceStoreError;
 use thiserror::Error;
 
 use crate::cli_util::short_operation_hash;
@@ -365,10 +366,19 @@ impl From<WorkspaceInitError> for CommandError {
             }
             WorkspaceInitError::SignInit(err) => user_error(err),
             WorkspaceInitError::TransactionCommit(err) => err.into(),
+            WorkspaceInitError::WorkspaceStore(err) => {
+                internal_error_with_message("Failed to record the workspace location", err)
+            }
         }
     }
 }
 
+impl From<WorkspaceStoreError> for CommandError {
+    fn from(err: WorkspaceStoreError) -> Self {
+        internal_error_with_message("Failed to access the workspace store", err)
+    }
+}
+
 impl From<OpHeadResolutionError> for CommandError {
     fn from(err: OpHeadResolutionError) -> Self {
         match err {
@@ -528,14 +538,18 @@ impl From<TrailerParseError> for CommandError {
 
 #[cfg(feature = "git")]
 mod git {
+    use jj_lib::git::GitBundleError;
     use jj_lib::git::GitDefaultRefspecError;
     use jj_lib::git::GitExportError;
     use jj_lib::git::GitFetchError;
     use jj_lib::git::GitImportError;
+    use jj_lib::git::GitNotesError;
     use jj_lib::git::GitPushError;
     use jj_lib::git::GitRefExpansionError;
     use jj_lib::git::GitRemoteManagementError;
     use jj_lib::git::GitResetHeadError;
+    use jj_lib::git::GitSparseCheckoutError;
+    use jj_lib::git::GitTagError;
     use jj_lib::git::UnexpectedGitBackendError;
 
     use super::*;
@@ -547,8 +561,9 @@ mod git {
                 | GitImportError::MissingRefAncestor { .. } => Some(
                     "\
 Is this Git repository a partial clone (cloned with the --filter argument)?
-jj currently does not support partial clones. To use jj with this repository, try re-cloning with \
-                     the full repository contents."
+jj only supports partial clones which omit file contents, such as `--filter=blob:none`. To use jj \
+                     with this repository, try re-cloning with a blob filter or the full \
+                     repository contents."
                         .to_string(),
                 ),
                 GitImportError::Backend(_) => None,
@@ -569,6 +584,58 @@ jj currently does not support partial clones. To use jj with this repository, tr
         }
     }
 
+    impl From<GitBundleError> for CommandError {
+        fn from(err: GitBundleError) -> Self {
+            match err {
+                GitBundleError::RefNotExported(_) => user_error_with_hint(
+                    err,
+                    "Conflicted bookmarks can't be bundled. Use `jj bookmark set` to resolve the \
+                     conflict.",
+                ),
+                GitBundleError::Subprocess(_) => user_error(err),
+                GitBundleError::UnexpectedBackend(_) => user_error(err),
+            }
+        }
+    }
+
+    impl From<GitNotesError> for CommandError {
+        fn from(err: GitNotesError) -> Self {
+            match err {
+                GitNotesError::InvalidRef(_) => user_error_with_hint(
+                    err,
+                    "Notes refs must start with `refs/notes/`. Check the `git.notes-ref` config.",
+                ),
+                GitNotesError::Read { .. } | GitNotesError::Write { .. } => internal_error(err),
+                GitNotesError::UnexpectedBackend(_) => user_error(err),
+            }
+        }
+    }
+
+    impl From<GitSparseCheckoutError> for CommandError {
+        fn from(err: GitSparseCheckoutError) -> Self {
+            match err {
+                GitSparseCheckoutError::UnsupportedPattern(_) => user_error_with_hint(
+                    err,
+                    "Only cone mode patterns and patterns like `/foo/bar` that select a path \
+                     and everything under it are supported.",
+                ),
+                GitSparseCheckoutError::UnexpectedBackend(_) => user_error(err),
+                GitSparseCheckoutError::Io(_) | GitSparseCheckoutError::Config(_) => {
+                    internal_error(err)
+                }
+            }
+        }
+    }
+
+    impl From<GitTagError> for CommandError {
+        fn from(err: GitTagError) -> Self {
+            match err {
+                GitTagError::Sign { .. } | GitTagError::UnexpectedBackend(_) => user_error(err),
+                GitTagError::Write { .. } => internal_error(err),
+            }
+        }
+    }
+
     impl From<GitFetchError> for CommandError {
         fn from(err: GitFetchError) -> Self {
             match err {
@@ -578,6 +645,8 @@ jj currently does not support partial clones. To use jj with this repository, tr
                     "Run `jj git remote rename` to give a different name.",
                 ),
                 GitFetchError::Subprocess(_) => user_error(err),
+                GitFetchError::Backend(err) => err.into(),
+                GitFetchError::UnexpectedBackend(_) => user_error(err),
             }
         }
     }
@@ -587,6 +656,7 @@ jj currently does not support partial clones. To use jj with this repository, tr
             match err {
                 GitDefaultRefspecError::NoSuchRemote(_) => user_error(err),
                 GitDefaultRefspecError::InvalidRemoteConfiguration(_, _) => user_error(err),
+                GitDefaultRefspecError::UnexpectedRefspecDirection { .. } => internal_error(err),
             }
         }
     }
@@ -641,6 +711,25 @@ jj currently does not support partial clones. To use jj with this repository, tr
     }
 }
 
+#[cfg(feature = "github")]
+impl From<crate::github_util::GitHubError> for CommandError {
+    fn from(err: crate::github_util::GitHubError) -> Self {
+        use crate::github_util::GitHubError;
+        match err {
+            GitHubError::MissingToken => user_error_with_hint(
+                err,
+                "Set the `GITHUB_TOKEN` environment variable, or the `github.token` config \
+                 option.",
+            ),
+            GitHubError::CurlNotFound(_) => user_error_with_hint(
+                err,
+                "Install curl, or set the `github.curl-path` config option to its location.",
+            ),
+            _ => user_error(err),
+        }
+    }
+}
+
 impl From<RevsetEvaluationError> for CommandError {
     fn from(err: RevsetEvaluationError) -> Self {
         user_error(err)
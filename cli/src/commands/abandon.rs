@@ -64,6 +64,10 @@ pub(crate) struct AbandonArgs {
     /// Do not modify the content of the children of the abandoned commits
     #[arg(long)]
     restore_descendants: bool,
+
+    /// Show the changes that would be made, without making them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[instrument(skip_all)]
@@ -138,7 +142,11 @@ pub(crate) fn cmd_abandon(
     .collect_vec();
 
     if let Some(mut formatter) = ui.status_formatter() {
-        writeln!(formatter, "Abandoned {} commits:", to_abandon.len())?;
+        if args.dry_run {
+            writeln!(formatter, "Would abandon {} commits:", to_abandon.len())?;
+        } else {
+            writeln!(formatter, "Abandoned {} commits:", to_abandon.len())?;
+        }
         let abandoned_commits: Vec<_> = to_abandon
             .iter()
             .map(|id| tx.base_repo().store().get_commit(id))
@@ -149,23 +157,33 @@ pub(crate) fn cmd_abandon(
             &abandoned_commits,
         )?;
         if !deleted_bookmarks.is_empty() {
+            let verb = if args.dry_run {
+                "Would delete"
+            } else {
+                "Deleted"
+            };
             writeln!(
                 formatter,
-                "Deleted bookmarks: {}",
+                "{verb} bookmarks: {}",
                 deleted_bookmarks.iter().map(|n| n.as_symbol()).join(", ")
             )?;
         }
         if num_rebased > 0 {
+            let verb = if args.dry_run {
+                "Would rebase"
+            } else {
+                "Rebased"
+            };
             if args.restore_descendants {
                 writeln!(
                     formatter,
-                    "Rebased {num_rebased} descendant commits (while preserving their content) \
+                    "{verb} {num_rebased} descendant commits (while preserving their content) \
                      onto parents of abandoned commits",
                 )?;
             } else {
                 writeln!(
                     formatter,
-                    "Rebased {num_rebased} descendant commits onto parents of abandoned commits",
+                    "{verb} {num_rebased} descendant commits onto parents of abandoned commits",
                 )?;
             }
         }
@@ -180,6 +198,9 @@ pub(crate) fn cmd_abandon(
             to_abandon.len() - 1
         )
     };
+    if args.dry_run {
+        return tx.finish_dry_run(ui);
+    }
     tx.finish(ui, transaction_description)?;
 
     #[cfg(feature = "git")]
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use clap_complete::ArgValueCompleter;
+use jj_lib::absorb::AbsorbOptions;
 use jj_lib::absorb::AbsorbSource;
 use jj_lib::absorb::absorb_hunks;
 use jj_lib::absorb::split_hunks_to_trees;
@@ -36,6 +37,11 @@ use crate::ui::Ui;
 /// last. If the destination revision cannot be determined unambiguously, the
 /// change will be left in the source revision.
 ///
+/// Added and deleted files and changes to the executable bit are left in the
+/// source revision, unless `--file-changes` is specified. With
+/// `--file-changes`, they are moved to the closest destination revision that
+/// modified the file, or for added files, any file in the same directory.
+///
 /// The source revision will be abandoned if all changes are absorbed into the
 /// destination revisions, and if the source revision has no description.
 ///
@@ -67,6 +73,13 @@ pub(crate) struct AbsorbArgs {
         add = ArgValueCompleter::new(complete::modified_from_files),
     )]
     paths: Vec<String>,
+    /// Also move added and deleted files and changes to the executable bit
+    #[arg(long)]
+    file_changes: bool,
+
+    /// Show the changes that would be made, without making them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[instrument(skip_all)]
@@ -87,7 +100,11 @@ pub(crate) fn cmd_absorb(
 
     let repo = workspace_command.repo().as_ref();
     let source = AbsorbSource::from_commit(repo, source_commit.clone())?;
-    let selected_trees = split_hunks_to_trees(repo, &source, &destinations, &matcher).block_on()?;
+    let options = AbsorbOptions {
+        file_changes: args.file_changes,
+    };
+    let selected_trees =
+        split_hunks_to_trees(repo, &source, &destinations, &matcher, &options).block_on()?;
 
     print_unmatched_explicit_paths(
         ui,
@@ -129,6 +146,9 @@ pub(crate) fn cmd_absorb(
         }
     }
 
+    if args.dry_run {
+        return tx.finish_dry_run(ui);
+    }
     tx.finish(
         ui,
         format!(
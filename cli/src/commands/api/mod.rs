@@ -0,0 +1,37 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod serve;
+
+use self::serve::ApiServeArgs;
+use self::serve::cmd_api_serve;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Interfaces for editors and other tools
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum ApiCommand {
+    Serve(ApiServeArgs),
+}
+
+pub fn cmd_api(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &ApiCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        ApiCommand::Serve(args) => cmd_api_serve(ui, command, args),
+    }
+}
@@ -0,0 +1,629 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::iter;
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::sync::mpsc;
+#[cfg(unix)]
+use std::thread;
+#[cfg(unix)]
+use std::time::Duration;
+
+use futures::executor::block_on_stream;
+use itertools::Itertools as _;
+use jj_lib::annotate::FileAnnotator;
+use jj_lib::annotate::LineOrigin;
+use jj_lib::commit::Commit;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merged_tree::TreeDiffEntry;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::rewrite::CommitWithSelection;
+use jj_lib::rewrite::merge_commit_trees;
+use jj_lib::rewrite::squash_commits;
+use pollster::FutureExt as _;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::description_util::try_combine_messages;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::UnifiedDiffOptions;
+use crate::formatter::PlainTextFormatter;
+use crate::text_util::complete_newline;
+use crate::ui::Ui;
+
+// Error codes defined by the JSON-RPC 2.0 specification. Errors reported by
+// the requested operation use the range reserved for implementations.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const COMMAND_ERROR: i64 = -32000;
+
+/// Serve a JSON-RPC API for editor integrations
+///
+/// Listens on a Unix domain socket and answers [JSON-RPC 2.0] requests, one
+/// JSON object per line. The repo is loaded once and kept in memory between
+/// requests. It's only reloaded when another process has changed it, which
+/// keeps requests fast in large repos. The working copy is snapshotted before
+/// each request. Requests from multiple connections are handled one at a time,
+/// and connections idle for 10 minutes are closed.
+///
+/// The following methods are supported. Revisions are given as revset
+/// strings, and commits are returned in the same format as the `json(self)`
+/// commit template.
+///
+/// * `log`: commits in `revisions` (defaults to `revsets.log`), at most
+///   `limit` of them
+///
+/// * `status`: the working-copy commit, its parents, its changed files, and
+///   its conflicted files
+///
+/// * `annotate`: the commit each line of `path` at `revision` (defaults to
+///   `@`) originates from
+///
+/// * `diff`: the changes in `revision` (defaults to `@`) in Git format
+///
+/// * `describe`: sets the description of `revision` (defaults to `@`) to
+///   `message`
+///
+/// * `new`: creates a new commit on top of `revisions` (defaults to `["@"]`)
+///   with the given `message`, and edits it
+///
+/// * `squash`: moves the changes in `revision` (defaults to `@`) into its
+///   parent, using `message` as the combined description if both have one
+///
+/// [JSON-RPC 2.0]: https://www.jsonrpc.org/specification
+#[derive(clap::Args, Clone, Debug)]
+pub struct ApiServeArgs {
+    /// The socket to listen on
+    ///
+    /// Defaults to `.jj/api.sock` in the workspace root.
+    #[arg(long, value_hint = clap::ValueHint::FilePath)]
+    socket: Option<PathBuf>,
+    /// Serve a single client on stdin and stdout instead of listening on a
+    /// socket
+    ///
+    /// The server exits at the end of the input.
+    #[arg(long, conflicts_with = "socket")]
+    stdio: bool,
+}
+
+pub fn cmd_api_serve(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ApiServeArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let mut server = ApiServer {
+        command,
+        workspace_command,
+    };
+    if args.stdio {
+        server.serve(ui, io::stdin().lock(), io::stdout().lock())?;
+        return Ok(());
+    }
+    serve_socket(ui, &mut server, args)
+}
+
+#[cfg(unix)]
+fn serve_socket(ui: &Ui, server: &mut ApiServer, args: &ApiServeArgs) -> Result<(), CommandError> {
+    use std::fs;
+    use std::os::unix::fs::FileTypeExt as _;
+    use std::os::unix::net::UnixListener;
+    use std::os::unix::net::UnixStream;
+
+    use crate::command_error::user_error_with_message;
+
+    let socket_path = match &args.socket {
+        Some(path) => path.clone(),
+        None => server
+            .workspace_command
+            .workspace_root()
+            .join(".jj")
+            .join("api.sock"),
+    };
+    // A server that exited uncleanly leaves its socket behind, which would
+    // prevent binding to the same path.
+    if let Ok(metadata) = fs::symlink_metadata(&socket_path)
+        && metadata.file_type().is_socket()
+    {
+        if UnixStream::connect(&socket_path).is_ok() {
+            return Err(user_error(format!(
+                "Another server is already listening on {}",
+                socket_path.display()
+            )));
+        }
+        fs::remove_file(&socket_path)?;
+    }
+    let listener = UnixListener::bind(&socket_path).map_err(|err| {
+        user_error_with_message(
+            format!("Failed to listen on {}", socket_path.display()),
+            err,
+        )
+    })?;
+    writeln!(ui.status(), "Listening on {}", socket_path.display())?;
+    // Each connection is read and answered by its own thread, so an idle client
+    // doesn't block the others. The requests are handled one at a time on this
+    // thread, which owns the workspace.
+    let (event_tx, event_rx) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    let message = format!("Failed to accept connection: {err}");
+                    event_tx.send(Event::Warning(message)).ok();
+                    continue;
+                }
+            };
+            let event_tx = event_tx.clone();
+            thread::spawn(move || {
+                if let Err(err) = serve_connection(&stream, &event_tx) {
+                    let message = format!("Failed to handle connection: {err}");
+                    event_tx.send(Event::Warning(message)).ok();
+                }
+            });
+        }
+    });
+    for event in event_rx {
+        match event {
+            Event::Request { line, reply } => {
+                reply.send(server.handle_request(ui, &line)).ok();
+            }
+            Event::Warning(message) => writeln!(ui.warning_default(), "{message}")?,
+        }
+    }
+    Ok(())
+}
+
+/// Idle connections are closed after this time.
+#[cfg(unix)]
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[cfg(unix)]
+enum Event {
+    /// Request line to be handled by the main thread.
+    Request {
+        line: String,
+        reply: mpsc::Sender<Option<Value>>,
+    },
+    Warning(String),
+}
+
+#[cfg(unix)]
+fn serve_connection(
+    stream: &std::os::unix::net::UnixStream,
+    events: &mpsc::Sender<Event>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+    let mut writer = stream;
+    for line in io::BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (reply, reply_rx) = mpsc::channel();
+        events.send(Event::Request { line, reply }).ok();
+        let Ok(response) = reply_rx.recv() else {
+            break;
+        };
+        if let Some(response) = response {
+            serde_json::to_writer(&mut writer, &response)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_socket(
+    _ui: &Ui,
+    _server: &mut ApiServer,
+    _args: &ApiServeArgs,
+) -> Result<(), CommandError> {
+    Err(user_error_with_hint(
+        "Listening on a socket is only supported on Unix",
+        "Use `--stdio` to serve a single client on stdin and stdout.",
+    ))
+}
+
+#[derive(serde::Deserialize)]
+struct Request {
+    /// Absent for notifications, which don't get a response.
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl From<CommandError> for RpcError {
+    fn from(err: CommandError) -> Self {
+        let error: &dyn error::Error = err.error.as_ref();
+        Self {
+            code: COMMAND_ERROR,
+            message: iter::successors(Some(error), |err| err.source()).join(": "),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NoParams {}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LogParams {
+    revisions: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnnotateParams {
+    path: String,
+    revision: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DiffParams {
+    revision: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DescribeParams {
+    revision: Option<String>,
+    message: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewParams {
+    revisions: Option<Vec<String>>,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SquashParams {
+    revision: Option<String>,
+    message: Option<String>,
+}
+
+struct ApiServer<'a> {
+    command: &'a CommandHelper,
+    /// Kept between requests so that the repo and its index don't have to be
+    /// loaded again for every request.
+    workspace_command: WorkspaceCommandHelper,
+}
+
+impl ApiServer<'_> {
+    /// Answers each request read from `reader` until the end of the input.
+    fn serve(&mut self, ui: &Ui, reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_request(ui, &line) {
+                serde_json::to_writer(&mut writer, &response)?;
+                writer.write_all(b"\n")?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, ui: &Ui, line: &str) -> Option<Value> {
+        let request: Request = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                let code = if err.is_data() {
+                    INVALID_REQUEST
+                } else {
+                    PARSE_ERROR
+                };
+                let err = RpcError {
+                    code,
+                    message: err.to_string(),
+                };
+                return Some(error_response(Value::Null, err));
+            }
+        };
+        let result = self.call(ui, &request.method, request.params);
+        let id = request.id?;
+        Some(match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(err) => error_response(id, err),
+        })
+    }
+
+    fn call(&mut self, ui: &Ui, method: &str, params: Value) -> Result<Value, RpcError> {
+        // Check the method and parameters before doing any work.
+        let call = match method {
+            "log" => Call::Log(parse_params(params)?),
+            "status" => {
+                parse_params::<NoParams>(params)?;
+                Call::Status
+            }
+            "annotate" => Call::Annotate(parse_params(params)?),
+            "diff" => Call::Diff(parse_params(params)?),
+            "describe" => Call::Describe(parse_params(params)?),
+            "new" => Call::New(parse_params(params)?),
+            "squash" => Call::Squash(parse_params(params)?),
+            _ => {
+                return Err(RpcError {
+                    code: METHOD_NOT_FOUND,
+                    message: format!("Unknown method: {method}"),
+                });
+            }
+        };
+        self.refresh(ui)?;
+        let result = match call {
+            Call::Log(params) => self.log(ui, params),
+            Call::Status => self.status(),
+            Call::Annotate(params) => self.annotate(ui, params),
+            Call::Diff(params) => self.diff(ui, params),
+            Call::Describe(params) => self.describe(ui, params),
+            Call::New(params) => self.new_commit(ui, params),
+            Call::Squash(params) => self.squash(ui, params),
+        };
+        Ok(result?)
+    }
+
+    /// Reloads the repo if it has been changed by another process, and
+    /// snapshots the working copy.
+    fn refresh(&mut self, ui: &Ui) -> Result<(), CommandError> {
+        let repo = self.workspace_command.repo();
+        let op_heads = repo.op_heads_store().get_op_heads().block_on()?;
+        if op_heads == [repo.op_id().clone()] {
+            self.workspace_command.maybe_snapshot(ui)
+        } else {
+            self.workspace_command = self.command.workspace_helper(ui)?;
+            Ok(())
+        }
+    }
+
+    fn resolve_revision(&self, ui: &Ui, revision: Option<String>) -> Result<Commit, CommandError> {
+        let revision = revision.map_or(RevisionArg::AT, RevisionArg::from);
+        self.workspace_command.resolve_single_rev(ui, &revision)
+    }
+
+    fn log(&self, ui: &Ui, params: LogParams) -> Result<Value, CommandError> {
+        let workspace_command = &self.workspace_command;
+        let revset_string = match params.revisions {
+            Some(revisions) => revisions,
+            None => workspace_command.settings().get_string("revsets.log")?,
+        };
+        let commits: Vec<Commit> = workspace_command
+            .parse_revset(ui, &RevisionArg::from(revset_string))?
+            .evaluate_to_commits()?
+            .take(params.limit.unwrap_or(usize::MAX))
+            .try_collect()?;
+        Ok(json!(commits))
+    }
+
+    fn status(&self) -> Result<Value, CommandError> {
+        let repo = self.workspace_command.repo();
+        let Some(wc_commit_id) = self.workspace_command.get_wc_commit_id() else {
+            return Err(user_error("This command requires a working copy"));
+        };
+        let wc_commit = repo.store().get_commit(wc_commit_id)?;
+        let parents: Vec<Commit> = wc_commit.parents().try_collect()?;
+        let tree = wc_commit.tree();
+        let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
+        let mut changes = vec![];
+        for TreeDiffEntry { path, values } in
+            block_on_stream(parent_tree.diff_stream(&tree, &EverythingMatcher))
+        {
+            let values = values?;
+            let status = match (values.before.is_present(), values.after.is_present()) {
+                (true, true) => "modified",
+                (false, true) => "added",
+                (true, false) => "removed",
+                (false, false) => continue,
+            };
+            changes.push(json!({"path": path.as_internal_file_string(), "status": status}));
+        }
+        let conflicts = tree
+            .conflicts()
+            .map(|(path, _)| path.as_internal_file_string().to_owned())
+            .collect_vec();
+        Ok(json!({
+            "working_copy": wc_commit,
+            "parents": parents,
+            "changes": changes,
+            "conflicts": conflicts,
+        }))
+    }
+
+    fn annotate(&self, ui: &Ui, params: AnnotateParams) -> Result<Value, CommandError> {
+        let repo = self.workspace_command.repo();
+        let commit = self.resolve_revision(ui, params.revision)?;
+        let file_path = self.workspace_command.parse_file_path(&params.path)?;
+        let file_value = commit.tree().path_value(&file_path)?;
+        if !file_value.is_present() || file_value.is_tree() {
+            return Err(user_error(format!("No such file: {}", params.path)));
+        }
+        let mut annotator = FileAnnotator::from_commit(&commit, &file_path)?;
+        annotator.compute(repo.as_ref(), &RevsetExpression::all())?;
+        let annotation = annotator.to_annotation();
+        // Lines without a known origin are attributed to the root commit, as
+        // `jj file annotate` does.
+        let default_line_origin = LineOrigin {
+            commit_id: repo.store().root_commit_id().clone(),
+            line_number: 0,
+        };
+        let lines = annotation
+            .line_origins()
+            .enumerate()
+            .map(|(line_number, (line_origin, content))| {
+                let line_origin = line_origin.unwrap_or(&default_line_origin);
+                json!({
+                    "line_number": line_number + 1,
+                    "commit_id": line_origin.commit_id.hex(),
+                    "original_line_number": line_origin.line_number + 1,
+                    "content": content.to_string(),
+                })
+            })
+            .collect_vec();
+        Ok(json!(lines))
+    }
+
+    fn diff(&self, ui: &Ui, params: DiffParams) -> Result<Value, CommandError> {
+        let commit = self.resolve_revision(ui, params.revision)?;
+        let options = UnifiedDiffOptions::from_settings(self.workspace_command.settings())?;
+        let mut output = vec![];
+        self.workspace_command
+            .diff_renderer(vec![DiffFormat::Git(Box::new(options))])
+            .show_patch(
+                ui,
+                &mut PlainTextFormatter::new(&mut output),
+                &commit,
+                &EverythingMatcher,
+                80,
+            )
+            .block_on()?;
+        Ok(json!(String::from_utf8_lossy(&output)))
+    }
+
+    fn describe(&mut self, ui: &Ui, params: DescribeParams) -> Result<Value, CommandError> {
+        let commit = self.resolve_revision(ui, params.revision)?;
+        self.workspace_command.check_rewritable([commit.id()])?;
+        let mut tx = self.workspace_command.start_transaction();
+        let new_commit = tx
+            .repo_mut()
+            .rewrite_commit(&commit)
+            .set_description(complete_newline(params.message))
+            .write()?;
+        tx.finish(ui, format!("describe commit {}", commit.id().hex()))?;
+        Ok(json!(new_commit))
+    }
+
+    fn new_commit(&mut self, ui: &Ui, params: NewParams) -> Result<Value, CommandError> {
+        let revisions = params.revisions.map_or_else(
+            || vec![RevisionArg::AT],
+            |revisions| revisions.into_iter().map(RevisionArg::from).collect(),
+        );
+        let parents: Vec<Commit> = self
+            .workspace_command
+            .parse_union_revsets(ui, &revisions)?
+            .evaluate_to_commits()?
+            .try_collect()?;
+        if parents.is_empty() {
+            return Err(user_error("No revisions to create a commit on top of"));
+        }
+        let mut tx = self.workspace_command.start_transaction();
+        let merged_tree = merge_commit_trees(tx.repo(), &parents).block_on()?;
+        let parent_ids = parents.iter().map(|commit| commit.id().clone()).collect();
+        let new_commit = tx
+            .repo_mut()
+            .new_commit(parent_ids, merged_tree)
+            .set_description(complete_newline(params.message))
+            .write()?;
+        tx.edit(&new_commit)?;
+        tx.finish(ui, "new empty commit")?;
+        Ok(json!(new_commit))
+    }
+
+    fn squash(&mut self, ui: &Ui, params: SquashParams) -> Result<Value, CommandError> {
+        let commit = self.resolve_revision(ui, params.revision)?;
+        let [parent_id] = commit.parent_ids() else {
+            return Err(user_error("Cannot squash a merge commit into its parents"));
+        };
+        let destination = self
+            .workspace_command
+            .repo()
+            .store()
+            .get_commit(parent_id)?;
+        self.workspace_command
+            .check_rewritable([commit.id(), destination.id()])?;
+        let mut tx = self.workspace_command.start_transaction();
+        let source = CommitWithSelection {
+            selected_tree: commit.tree(),
+            parent_tree: commit.parent_tree(tx.repo())?,
+            commit: commit.clone(),
+        };
+        let Some(squashed) = squash_commits(tx.repo_mut(), &[source], &destination, false)? else {
+            return Err(user_error("No changes to squash"));
+        };
+        let description = match params.message {
+            Some(message) => complete_newline(message),
+            None => try_combine_messages(&squashed.abandoned_commits, &destination).ok_or_else(
+                || {
+                    user_error_with_hint(
+                        "Both revisions have descriptions",
+                        "Pass a `message` to use for the combined revision.",
+                    )
+                },
+            )?,
+        };
+        let new_commit = squashed
+            .commit_builder
+            .set_description(description)
+            .write()?;
+        tx.finish(ui, format!("squash commit {}", commit.id().hex()))?;
+        Ok(json!(new_commit))
+    }
+}
+
+enum Call {
+    Log(LogParams),
+    Status,
+    Annotate(AnnotateParams),
+    Diff(DiffParams),
+    Describe(DescribeParams),
+    New(NewParams),
+    Squash(SquashParams),
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    // Omitted parameters are the same as no parameters.
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|err| RpcError {
+        code: INVALID_PARAMS,
+        message: err.to_string(),
+    })
+}
+
+fn error_response(id: Value, err: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": err.code, "message": err.message},
+    })
+}
@@ -0,0 +1,423 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use bstr::ByteSlice as _;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::ChangeId;
+use jj_lib::backend::Timestamp;
+use jj_lib::commit::Commit;
+use jj_lib::patch::apply_patch_to_tree;
+use jj_lib::patch::parse_patch;
+use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::commands::format_patch::CHANGE_ID_HEADER;
+use crate::complete;
+use crate::diff_util::describe_patch_rejection;
+use crate::ui::Ui;
+
+/// Apply patch emails as new revisions
+///
+/// Reads patches in the mbox format of `git format-patch` and `jj
+/// format-patch`, and creates a revision for each of them with the author,
+/// date, and description of the email. The revisions are created as a stack on
+/// top of the destination, in the order of the patches.
+///
+/// If a patch has an `X-Jj-Change-Id` header, its revision keeps that change
+/// ID, unless a visible revision already has it.
+///
+/// Nothing is created if any of the patches doesn't apply.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ApplyPatchArgs {
+    /// Files containing one or more patches, or `-` to read from the standard
+    /// input
+    #[arg(required = true, value_hint = clap::ValueHint::FilePath)]
+    paths: Vec<PathBuf>,
+
+    /// The revision to apply the patches onto
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    onto: RevisionArg,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_apply_patch(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ApplyPatchArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut messages = vec![];
+    for path in &args.paths {
+        let content = if path.as_os_str() == "-" {
+            let mut content = vec![];
+            std::io::stdin()
+                .read_to_end(&mut content)
+                .map_err(|err| user_error_with_message("Failed to read standard input", err))?;
+            content
+        } else {
+            std::fs::read(command.cwd().join(path)).map_err(|err| {
+                user_error_with_message(format!("Failed to read {}", path.display()), err)
+            })?
+        };
+        messages.extend(split_mbox(&content).into_iter().map(parse_message));
+    }
+    if messages.is_empty() {
+        writeln!(ui.status(), "No patches to apply.")?;
+        return Ok(());
+    }
+
+    let mut parent = workspace_command.resolve_single_rev(ui, &args.onto)?;
+    let mut tx = workspace_command.start_transaction();
+    let mut new_commits: Vec<Commit> = vec![];
+    for (index, message) in messages.iter().enumerate() {
+        let describe_patch = || match message.subject.as_deref() {
+            Some(subject) => format!("patch {} ({subject})", index + 1),
+            None => format!("patch {}", index + 1),
+        };
+        let file_patches = parse_patch(&message.body, 1).map_err(|err| {
+            user_error_with_message(format!("Failed to parse {}", describe_patch()), err)
+        })?;
+        let application = apply_patch_to_tree(&parent.tree(), &file_patches).block_on()?;
+        if !application.rejections.is_empty() {
+            let details = application
+                .rejections
+                .iter()
+                .map(|rejection| {
+                    let path = tx.base_workspace_helper().format_file_path(&rejection.path);
+                    format!("  {path}: {}", describe_patch_rejection(&rejection.reason))
+                })
+                .join("\n");
+            return Err(user_error(format!(
+                "Failed to apply {}:\n{details}",
+                describe_patch()
+            )));
+        }
+
+        let mut author = tx.settings().signature();
+        if let Some((name, email)) = &message.author {
+            author.name = name.clone();
+            author.email = email.clone();
+        }
+        if let Some(timestamp) = message.date {
+            author.timestamp = timestamp;
+        }
+        let unused_change_id = match &message.change_id {
+            Some(change_id)
+                if tx
+                    .repo()
+                    .resolve_change_id(change_id)?
+                    .is_none_or(|ids| ids.is_empty())
+                    && !new_commits
+                        .iter()
+                        .any(|commit| commit.change_id() == change_id) =>
+            {
+                Some(change_id.clone())
+            }
+            _ => None,
+        };
+        let mut builder = tx
+            .repo_mut()
+            .new_commit(vec![parent.id().clone()], application.tree)
+            .set_description(message.description())
+            .set_author(author);
+        if let Some(change_id) = unused_change_id {
+            builder = builder.set_change_id(change_id);
+        }
+        let new_commit = builder.write()?;
+        parent = new_commit.clone();
+        new_commits.push(new_commit);
+    }
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Applied {} patches:", new_commits.len())?;
+        for commit in &new_commits {
+            write!(formatter, "  ")?;
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    tx.finish(ui, format!("apply {} patches", new_commits.len()))?;
+    Ok(())
+}
+
+/// A patch email.
+#[derive(Debug, Default)]
+struct PatchMessage {
+    author: Option<(String, String)>,
+    date: Option<Timestamp>,
+    subject: Option<String>,
+    change_id: Option<ChangeId>,
+    /// Commit message body followed by the patch.
+    body: Vec<u8>,
+}
+
+impl PatchMessage {
+    /// Returns the commit description, made of the subject and the part of the
+    /// body before the `---` separator or the patch.
+    fn description(&self) -> String {
+        let mut message_lines = vec![];
+        for line in self.body.lines() {
+            if line == b"---" || line.starts_with(b"diff --git ") || line.starts_with(b"Index: ") {
+                break;
+            }
+            message_lines.push(line.to_str_lossy());
+        }
+        let body = message_lines.join("\n");
+        let body = body.trim();
+        let subject = self.subject.as_deref().unwrap_or("").trim();
+        match (subject.is_empty(), body.is_empty()) {
+            (true, true) => String::new(),
+            (false, true) => format!("{subject}\n"),
+            (true, false) => format!("{body}\n"),
+            (false, false) => format!("{subject}\n\n{body}\n"),
+        }
+    }
+}
+
+/// Splits mbox content into messages at `From <commit> <date>` lines as
+/// written by `format-patch`. Content that doesn't start with such a line is
+/// treated as a single message.
+fn split_mbox(content: &[u8]) -> Vec<&[u8]> {
+    let mut messages = vec![];
+    let mut start = 0;
+    let mut pos = 0;
+    for line in content.lines_with_terminator() {
+        if is_mbox_separator(line) && pos > start {
+            messages.push(&content[start..pos]);
+            start = pos;
+        }
+        pos += line.len();
+    }
+    if pos > start {
+        messages.push(&content[start..pos]);
+    }
+    messages
+}
+
+/// Returns true if `line` is a `From <commit> <date>` line. Other lines
+/// starting with `From ` may appear in commit descriptions.
+fn is_mbox_separator(line: &[u8]) -> bool {
+    let Some(rest) = line.strip_prefix(b"From ") else {
+        return false;
+    };
+    let Some((hash, date)) = rest.split_once_str(" ") else {
+        return false;
+    };
+    matches!(hash.len(), 40 | 64)
+        && hash.iter().all(|b| b.is_ascii_hexdigit())
+        && !date.trim().is_empty()
+}
+
+fn parse_message(message: &[u8]) -> PatchMessage {
+    let mut lines = message.lines_with_terminator().peekable();
+    // Skip the mbox "From <commit> <date>" line
+    lines.next_if(|line| is_mbox_separator(line));
+    let mut headers: Vec<(String, String)> = vec![];
+    let mut body_start = 0;
+    let mut pos = message.len() - lines.clone().map(|line| line.len()).sum::<usize>();
+    for line in lines {
+        pos += line.len();
+        let text = line.trim_end_with(|c| c == '\n' || c == '\r');
+        if text.is_empty() {
+            body_start = pos;
+            break;
+        }
+        if text.starts_with(b" ") || text.starts_with(b"\t") {
+            // Folded header line
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(text.trim_start().to_str_lossy().as_ref());
+            }
+            continue;
+        }
+        let Some((name, value)) = text.split_once_str(":") else {
+            // Not a header, so there are no headers
+            body_start = 0;
+            headers.clear();
+            break;
+        };
+        headers.push((
+            name.to_str_lossy().trim().to_owned(),
+            value.to_str_lossy().trim().to_owned(),
+        ));
+        body_start = pos;
+    }
+
+    let mut patch_message = PatchMessage {
+        body: message[body_start..].to_vec(),
+        ..Default::default()
+    };
+    for (name, value) in headers {
+        let value = decode_header_value(&value);
+        if name.eq_ignore_ascii_case("From") {
+            patch_message.author = Some(parse_address(&value));
+        } else if name.eq_ignore_ascii_case("Date") {
+            patch_message.date = chrono::DateTime::parse_from_rfc2822(&value)
+                .ok()
+                .map(Timestamp::from_datetime);
+        } else if name.eq_ignore_ascii_case("Subject") {
+            patch_message.subject = Some(strip_subject_prefix(&value).to_owned());
+        } else if name.eq_ignore_ascii_case(CHANGE_ID_HEADER) {
+            patch_message.change_id = ChangeId::try_from_reverse_hex(value.trim());
+        }
+    }
+    patch_message
+}
+
+/// Parses `Name <email>` or a bare email address.
+fn parse_address(value: &str) -> (String, String) {
+    match value.rsplit_once('<') {
+        Some((name, email)) => (
+            name.trim().trim_matches('"').to_owned(),
+            email.trim_end_matches('>').trim().to_owned(),
+        ),
+        None => (String::new(), value.trim().to_owned()),
+    }
+}
+
+/// Strips `Re:` and `[PATCH n/m]`-like prefixes from a subject.
+fn strip_subject_prefix(mut subject: &str) -> &str {
+    loop {
+        subject = subject.trim_start();
+        if let Some(rest) = subject
+            .strip_prefix("Re:")
+            .or_else(|| subject.strip_prefix("RE:"))
+        {
+            subject = rest;
+        } else if subject.starts_with('[')
+            && let Some((_, rest)) = subject.split_once(']')
+        {
+            subject = rest;
+        } else {
+            return subject;
+        }
+    }
+}
+
+/// Decodes RFC 2047 encoded words using the `Q` encoding, as written by `git
+/// format-patch`. Other encoded words are kept as is.
+fn decode_header_value(value: &str) -> String {
+    // Marks the end of a decoded word. It can't appear in decoded text
+    // because it's not valid in a header.
+    const WORD_END: &str = "\0";
+    let mut decoded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("=?") {
+        let Some((word, len)) = decode_encoded_word(&rest[start + 2..]) else {
+            decoded.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            continue;
+        };
+        let end = start + 2 + len;
+        // Whitespace between adjacent encoded words is ignored
+        let prefix = &rest[..start];
+        if !(decoded.ends_with(WORD_END) && prefix.trim().is_empty()) {
+            decoded.push_str(prefix);
+        }
+        decoded.push_str(&word);
+        decoded.push_str(WORD_END);
+        rest = &rest[end..];
+    }
+    decoded.push_str(rest);
+    decoded.replace(WORD_END, "")
+}
+
+/// Decodes `charset?q?text?=` at the start of `word` if the charset is UTF-8
+/// or ASCII. Returns the decoded text and the length of the encoded word.
+fn decode_encoded_word(word: &str) -> Option<(String, usize)> {
+    // The text may start with `=`, so the end marker is only searched for after
+    // the charset and the encoding.
+    let (charset, rest) = word.split_once('?')?;
+    let (encoding, rest) = rest.split_once('?')?;
+    let (text, _) = rest.split_once("?=")?;
+    let len = charset.len() + encoding.len() + text.len() + 4;
+    if !(charset.eq_ignore_ascii_case("utf-8") || charset.eq_ignore_ascii_case("us-ascii"))
+        || !encoding.eq_ignore_ascii_case("q")
+    {
+        return None;
+    }
+    let mut bytes = vec![];
+    let mut chars = text.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [chars.next()?, chars.next()?];
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    Some((String::from_utf8(bytes).ok()?, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_header_value() {
+        assert_eq!(decode_header_value("plain"), "plain");
+        assert_eq!(
+            decode_header_value("=?UTF-8?q?[PATCH]_Caf=C3=A9?= au lait"),
+            "[PATCH] Café au lait"
+        );
+        assert_eq!(
+            decode_header_value("=?UTF-8?q?=5BPATCH=5D_Caf=C3=A9?="),
+            "[PATCH] Café"
+        );
+        assert_eq!(decode_header_value("=?UTF-8?q?a?= =?UTF-8?q?b?="), "ab");
+        assert_eq!(decode_header_value("=?UTF-8?b?w6k=?="), "=?UTF-8?b?w6k=?=");
+    }
+
+    #[test]
+    fn test_split_mbox() {
+        let hash = "0123456789abcdef0123456789abcdef01234567";
+        let first = format!("From {hash} Mon Sep 17 00:00:00 2001\nSubject: a\n\nFrom here\n");
+        let second = format!("From {hash} Mon Sep 17 00:00:00 2001\nSubject: b\n\nbody\n");
+        let content = format!("{first}{second}");
+        assert_eq!(
+            split_mbox(content.as_bytes()),
+            [first.as_bytes(), second.as_bytes()]
+        );
+        assert_eq!(
+            split_mbox(b"Subject: a\n\nFrom abc Mon Sep 17\n"),
+            [b"Subject: a\n\nFrom abc Mon Sep 17\n"]
+        );
+    }
+
+    #[test]
+    fn test_strip_subject_prefix() {
+        assert_eq!(strip_subject_prefix("[PATCH 1/2] Fix it"), "Fix it");
+        assert_eq!(strip_subject_prefix("Re: [PATCH v2] Fix it"), "Fix it");
+        assert_eq!(strip_subject_prefix("Fix [it]"), "Fix [it]");
+    }
+}
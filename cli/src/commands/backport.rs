@@ -0,0 +1,230 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::rewrite::DuplicateCommitsStats;
+use jj_lib::rewrite::duplicate_commits;
+use jj_lib::trailer::parse_description_trailers;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::print_updated_commits;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::revset_util;
+use crate::ui::Ui;
+
+/// Trailer recording the change a backported commit was created from.
+const BACKPORTED_FROM_TRAILER: &str = "Backported-from";
+
+/// Copy revisions onto one or more bookmarks
+///
+/// The given revisions are duplicated onto the revision each bookmark points
+/// to, and the bookmark is moved to the duplicated revisions, all in a single
+/// operation. This is typically used to apply fixes to several release
+/// branches at once.
+///
+/// The duplicated revisions get new change IDs. The change ID of the original
+/// revision is recorded in a `Backported-from` trailer in the description,
+/// unless the original revision was itself backported, in which case its
+/// trailer is kept. Backported revisions can be found with e.g.
+/// `jj log -r 'description("Backported-from:")'`.
+///
+/// Revisions that don't apply cleanly onto a bookmark are still created, with
+/// conflicts. The conflicts are reported for each bookmark.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BackportArgs {
+    /// The revision(s) to backport
+    #[arg(
+        long,
+        short,
+        required = true,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+    /// The bookmark(s) to backport onto (can be repeated)
+    #[arg(
+        long = "bookmark",
+        short,
+        required = true,
+        value_name = "BOOKMARK",
+        value_parser = revset_util::parse_bookmark_name,
+        add = ArgValueCandidates::new(complete::local_bookmarks),
+    )]
+    bookmarks: Vec<RefNameBuf>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_backport(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BackportArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let to_backport: Vec<CommitId> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commit_ids()?
+        .try_collect()?; // in reverse topological order
+    if to_backport.is_empty() {
+        writeln!(ui.status(), "No revisions to backport.")?;
+        return Ok(());
+    }
+    if to_backport.last() == Some(workspace_command.repo().store().root_commit_id()) {
+        return Err(user_error("Cannot backport the root commit"));
+    }
+
+    let repo = workspace_command.repo().as_ref();
+    let destinations: Vec<(&RefNameBuf, CommitId)> = args
+        .bookmarks
+        .iter()
+        .unique()
+        .map(|name| {
+            let target = repo.view().get_local_bookmark(name);
+            if target.is_absent() {
+                Err(user_error(format!(
+                    "No such bookmark: {name}",
+                    name = name.as_symbol()
+                )))
+            } else if let Some(id) = target.as_normal() {
+                Ok((name, id.clone()))
+            } else {
+                Err(user_error(format!(
+                    "Bookmark {name} is conflicted",
+                    name = name.as_symbol()
+                )))
+            }
+        })
+        .try_collect()?;
+    // The bookmarks are moved only if there's a single revision to move them
+    // to.
+    let head_id = match RevsetExpression::commits(to_backport.clone())
+        .heads()
+        .evaluate(repo)?
+        .iter()
+        .try_collect::<_, Vec<_>, _>()?
+        .as_slice()
+    {
+        [head_id] => Some(head_id.clone()),
+        _ => {
+            writeln!(
+                ui.warning_default(),
+                "The revisions to backport have multiple heads, so the bookmarks will not be \
+                 moved."
+            )?;
+            None
+        }
+    };
+    let new_descs: HashMap<_, _> = to_backport
+        .iter()
+        .map(|commit_id| -> Result<_, CommandError> {
+            let commit = repo.store().get_commit(commit_id)?;
+            Ok((commit_id.clone(), add_backported_from_trailer(&commit)))
+        })
+        .try_collect()?;
+
+    let mut tx = workspace_command.start_transaction();
+    let mut backported: Vec<(&RefNameBuf, Vec<Commit>)> = vec![];
+    for (name, destination_id) in destinations {
+        let DuplicateCommitsStats {
+            duplicated_commits,
+            num_rebased: _,
+        } = duplicate_commits(
+            tx.repo_mut(),
+            &to_backport,
+            &new_descs,
+            &[destination_id],
+            &[],
+        )
+        .block_on()?;
+        if let Some(new_head) = head_id.as_ref().map(|id| &duplicated_commits[id]) {
+            tx.repo_mut()
+                .set_local_bookmark_target(name, RefTarget::normal(new_head.id().clone()));
+        }
+        backported.push((name, duplicated_commits.into_values().collect()));
+    }
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        let template = tx.commit_summary_template();
+        for (name, commits) in &backported {
+            writeln!(
+                formatter,
+                "Backported {} commits onto {name}:",
+                commits.len(),
+                name = name.as_symbol()
+            )?;
+            print_updated_commits(formatter.as_mut(), &template, commits)?;
+        }
+    }
+    for (name, commits) in &backported {
+        let num_conflicted = commits
+            .iter()
+            .filter(|commit| commit.has_conflict())
+            .count();
+        if num_conflicted > 0 {
+            writeln!(
+                ui.warning_default(),
+                "There are unresolved conflicts in {num_conflicted} commits backported onto \
+                 {name}.",
+                name = name.as_symbol()
+            )?;
+        }
+    }
+    tx.finish(
+        ui,
+        format!(
+            "backport {} commit(s) onto {}",
+            to_backport.len(),
+            backported
+                .iter()
+                .map(|(name, _)| name.as_symbol())
+                .join(", ")
+        ),
+    )?;
+    Ok(())
+}
+
+/// Returns the description of `commit` with a trailer recording its change ID.
+fn add_backported_from_trailer(commit: &Commit) -> String {
+    let trailers = parse_description_trailers(commit.description());
+    if trailers
+        .iter()
+        .any(|trailer| trailer.key == BACKPORTED_FROM_TRAILER)
+    {
+        return commit.description().to_owned();
+    }
+    let mut description = commit.description().to_owned();
+    if trailers.is_empty() && !description.is_empty() {
+        description.push('\n');
+    }
+    description.push_str(&format!(
+        "{BACKPORTED_FROM_TRAILER}: {}\n",
+        commit.change_id().reverse_hex()
+    ));
+    description
+}
@@ -0,0 +1,115 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::time::Instant;
+
+use jj_lib::commit::Commit;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::working_copy::CheckoutStats;
+use jj_lib::workspace::Workspace;
+use pollster::FutureExt as _;
+
+use super::BenchReport;
+use super::CriterionArgs;
+use super::iter_recorded;
+use super::new_criterion;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Check out a revision into the working copy
+///
+/// Each iteration checks out the revision starting from the working-copy
+/// commit, which is restored afterwards. Only checking out the revision is
+/// measured. The operation log isn't updated.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BenchCheckoutArgs {
+    /// The revision to check out
+    revision: RevisionArg,
+    #[command(flatten)]
+    criterion: CriterionArgs,
+}
+
+pub fn cmd_bench_checkout(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BenchCheckoutArgs,
+) -> Result<(), CommandError> {
+    // Record pending changes first so that restoring the working-copy commit
+    // doesn't lose them.
+    let workspace_command = command.workspace_helper(ui)?;
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?
+        .clone();
+    let target_id = workspace_command
+        .resolve_single_rev(ui, &args.revision)?
+        .id()
+        .clone();
+    drop(workspace_command);
+
+    // The commits must be loaded from the store of the workspace.
+    let mut workspace = command.load_workspace()?;
+    let store = workspace.repo_loader().store().clone();
+    let wc_commit = store.get_commit(&wc_commit_id)?;
+    let target = store.get_commit(&target_id)?;
+    let before = Instant::now();
+    let stats = check_out(&mut workspace, &target)?;
+    let after = Instant::now();
+    check_out(&mut workspace, &wc_commit)?;
+    writeln!(
+        ui.status(),
+        "First run took {:?} and updated {} files",
+        after.duration_since(before),
+        stats.updated_files + stats.added_files + stats.removed_files
+    )?;
+
+    let mut criterion = new_criterion(ui, &args.criterion);
+    let mut report = BenchReport::default();
+    let mut samples = vec![];
+    let workspace = RefCell::new(workspace);
+    criterion.bench_function("checkout", |bencher| {
+        iter_recorded(
+            bencher,
+            &mut samples,
+            || check_out(&mut workspace.borrow_mut(), &wc_commit).unwrap(),
+            |_| check_out(&mut workspace.borrow_mut(), &target).unwrap(),
+        );
+    });
+    check_out(&mut workspace.borrow_mut(), &wc_commit)?;
+    report.add("checkout", &samples, &args.criterion);
+    report.write(command, &args.criterion)
+}
+
+fn check_out(workspace: &mut Workspace, commit: &Commit) -> Result<CheckoutStats, CommandError> {
+    let mut locked_ws = workspace.start_working_copy_mutation()?;
+    // Keep the working copy associated with the current operation.
+    let operation_id = locked_ws.locked_wc().old_operation_id().clone();
+    let stats = locked_ws
+        .locked_wc()
+        .check_out(commit)
+        .block_on()
+        .map_err(|err| {
+            internal_error_with_message(
+                format!("Failed to check out commit {}", commit.id().hex()),
+                err,
+            )
+        })?;
+    locked_ws.finish(operation_id)?;
+    Ok(stats)
+}
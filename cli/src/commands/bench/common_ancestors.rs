@@ -45,6 +45,7 @@ pub fn cmd_bench_common_ancestors(
         || index.common_ancestors(slice::from_ref(commit1.id()), slice::from_ref(commit2.id()));
     run_bench(
         ui,
+        command,
         &format!("common-ancestors-{}-{}", args.revision1, args.revision2),
         &args.criterion,
         routine,
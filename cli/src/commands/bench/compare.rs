@@ -0,0 +1,154 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use super::Timings;
+use super::write_json;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::ui::Ui;
+
+/// Compare the wall-clock time of a command between two jj binaries
+///
+/// Both binaries are run against the current repository with the given
+/// arguments. Runs alternate between the binaries so that changes in system
+/// load affect both of them equally.
+///
+/// Example: `jj bench compare --baseline ./jj-main --candidate ./jj-new --
+/// log -r ::@`
+#[derive(clap::Args, Clone, Debug)]
+pub struct BenchCompareArgs {
+    /// Path to the jj binary to compare against
+    #[arg(long, value_hint = clap::ValueHint::ExecutablePath)]
+    baseline: PathBuf,
+    /// Path to the jj binary to measure
+    #[arg(long, value_hint = clap::ValueHint::ExecutablePath)]
+    candidate: PathBuf,
+    /// Number of measured runs per binary
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+    /// Number of unmeasured runs per binary before measuring
+    #[arg(long, default_value_t = 1)]
+    warmup: u32,
+    /// Write the results to this file as JSON
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    json: Option<PathBuf>,
+    /// Arguments to pass to both binaries
+    #[arg(required = true, last = true)]
+    args: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CompareResult<'a> {
+    args: &'a [String],
+    baseline: BinaryResult,
+    candidate: BinaryResult,
+    /// Median time of the candidate relative to the baseline.
+    ratio: f64,
+}
+
+#[derive(Serialize)]
+struct BinaryResult {
+    binary: PathBuf,
+    #[serde(flatten)]
+    timings: Timings,
+}
+
+impl BinaryResult {
+    fn new(binary: &Path, runs: &[Duration]) -> Self {
+        Self {
+            binary: binary.to_owned(),
+            timings: Timings::new(runs),
+        }
+    }
+}
+
+pub fn cmd_bench_compare(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BenchCompareArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let workspace_root = workspace_command.workspace_root();
+    let run_once = |binary: &Path| -> Result<Duration, CommandError> {
+        let before = Instant::now();
+        let status = Command::new(binary)
+            .arg("--repository")
+            .arg(workspace_root)
+            .args(&args.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|err| {
+                user_error_with_message(format!("Failed to run {}", binary.display()), err)
+            })?;
+        let elapsed = before.elapsed();
+        if !status.success() {
+            return Err(user_error(format!(
+                "{} exited with {status}",
+                binary.display()
+            )));
+        }
+        Ok(elapsed)
+    };
+
+    for _ in 0..args.warmup {
+        run_once(&args.baseline)?;
+        run_once(&args.candidate)?;
+    }
+    let mut baseline_runs = vec![];
+    let mut candidate_runs = vec![];
+    for _ in 0..args.runs {
+        baseline_runs.push(run_once(&args.baseline)?);
+        candidate_runs.push(run_once(&args.candidate)?);
+    }
+
+    let baseline = BinaryResult::new(&args.baseline, &baseline_runs);
+    let candidate = BinaryResult::new(&args.candidate, &candidate_runs);
+    let ratio = candidate.timings.median_ms / baseline.timings.median_ms;
+    for (name, result) in [("baseline", &baseline), ("candidate", &candidate)] {
+        writeln!(
+            ui.stdout(),
+            "{name:<9} {}: min {:.1}ms, median {:.1}ms, mean {:.1}ms",
+            result.binary.display(),
+            result.timings.min_ms,
+            result.timings.median_ms,
+            result.timings.mean_ms,
+        )?;
+    }
+    writeln!(ui.stdout(), "candidate/baseline median: {ratio:.3}")?;
+
+    if let Some(path) = &args.json {
+        let result = CompareResult {
+            args: &args.args,
+            baseline,
+            candidate,
+            ratio,
+        };
+        write_json(command, path, &result)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::executor::block_on_stream;
+use jj_lib::matchers::EverythingMatcher;
+
+use super::CriterionArgs;
+use super::run_bench;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Compute the tree diff between two revisions
+#[derive(clap::Args, Clone, Debug)]
+pub struct BenchDiffArgs {
+    from: RevisionArg,
+    to: RevisionArg,
+    #[command(flatten)]
+    criterion: CriterionArgs,
+}
+
+pub fn cmd_bench_diff(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BenchDiffArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let from_tree = workspace_command.resolve_single_rev(ui, &args.from)?.tree();
+    let to_tree = workspace_command.resolve_single_rev(ui, &args.to)?.tree();
+    let routine = || {
+        let mut count = 0;
+        for entry in block_on_stream(from_tree.diff_stream(&to_tree, &EverythingMatcher)) {
+            entry.values.unwrap();
+            count += 1;
+        }
+        count
+    };
+    run_bench(
+        ui,
+        command,
+        &format!("diff-{}-{}", args.from, args.to),
+        &args.criterion,
+        routine,
+    )?;
+    Ok(())
+}
@@ -42,6 +42,7 @@ pub fn cmd_bench_is_ancestor(
     let routine = || index.is_ancestor(ancestor_commit.id(), descendant_commit.id());
     run_bench(
         ui,
+        command,
         &format!("is-ancestor-{}-{}", args.ancestor, args.descendant),
         &args.criterion,
         routine,
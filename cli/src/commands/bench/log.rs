@@ -0,0 +1,77 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools as _;
+use jj_lib::repo::Repo as _;
+
+use super::CriterionArgs;
+use super::run_bench;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Render the log template for the revisions in the revset
+///
+/// The graph isn't drawn, and the output is discarded.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BenchLogArgs {
+    /// The revisions to render [default: `revsets.log` config]
+    #[arg(long, short)]
+    revisions: Option<RevisionArg>,
+    /// The template to render [default: `templates.log` config]
+    #[arg(long, short = 'T')]
+    template: Option<String>,
+    #[command(flatten)]
+    criterion: CriterionArgs,
+}
+
+pub fn cmd_bench_log(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BenchLogArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let settings = workspace_command.settings();
+    let revision = match &args.revisions {
+        Some(revision) => revision.clone(),
+        None => RevisionArg::from(settings.get_string("revsets.log")?),
+    };
+    let template_text = match &args.template {
+        Some(template) => template.clone(),
+        None => settings.get_string("templates.log")?,
+    };
+    let commit_ids: Vec<_> = workspace_command
+        .parse_revset(ui, &revision)?
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+    let template = workspace_command.parse_commit_template(ui, &template_text)?;
+    let store = workspace_command.repo().store();
+    let routine = || {
+        let mut len = 0;
+        for commit_id in &commit_ids {
+            let commit = store.get_commit(commit_id).unwrap();
+            len += template.format_plain_text(&commit).len();
+        }
+        len
+    };
+    run_bench(
+        ui,
+        command,
+        &format!("log-{revision}"),
+        &args.criterion,
+        routine,
+    )?;
+    Ok(())
+}
@@ -12,38 +12,65 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod checkout;
 mod common_ancestors;
+mod compare;
+mod diff;
 mod is_ancestor;
+mod log;
 mod resolve_prefix;
 mod revset;
+mod snapshot;
 
 use std::fmt::Debug;
-use std::io;
+use std::hint::black_box;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
 use std::time::Instant;
 
 use clap::Subcommand;
+use criterion::Bencher;
 use criterion::Criterion;
+use itertools::Itertools as _;
+use serde::Serialize;
 
+use self::checkout::BenchCheckoutArgs;
+use self::checkout::cmd_bench_checkout;
 use self::common_ancestors::BenchCommonAncestorsArgs;
 use self::common_ancestors::cmd_bench_common_ancestors;
+use self::compare::BenchCompareArgs;
+use self::compare::cmd_bench_compare;
+use self::diff::BenchDiffArgs;
+use self::diff::cmd_bench_diff;
 use self::is_ancestor::BenchIsAncestorArgs;
 use self::is_ancestor::cmd_bench_is_ancestor;
+use self::log::BenchLogArgs;
+use self::log::cmd_bench_log;
 use self::resolve_prefix::BenchResolvePrefixArgs;
 use self::resolve_prefix::cmd_bench_resolve_prefix;
 use self::revset::BenchRevsetArgs;
 use self::revset::cmd_bench_revset;
+use self::snapshot::BenchSnapshotArgs;
+use self::snapshot::cmd_bench_snapshot;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
 use crate::ui::Ui;
 
 /// Commands for benchmarking internal operations
 #[derive(Subcommand, Clone, Debug)]
 #[command(hide = true)]
 pub enum BenchCommand {
+    Checkout(BenchCheckoutArgs),
     CommonAncestors(BenchCommonAncestorsArgs),
+    Compare(BenchCompareArgs),
+    Diff(BenchDiffArgs),
     IsAncestor(BenchIsAncestorArgs),
+    Log(BenchLogArgs),
     ResolvePrefix(BenchResolvePrefixArgs),
     Revset(BenchRevsetArgs),
+    Snapshot(BenchSnapshotArgs),
 }
 
 pub(crate) fn cmd_bench(
@@ -52,10 +79,15 @@ pub(crate) fn cmd_bench(
     subcommand: &BenchCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        BenchCommand::Checkout(args) => cmd_bench_checkout(ui, command, args),
         BenchCommand::CommonAncestors(args) => cmd_bench_common_ancestors(ui, command, args),
+        BenchCommand::Compare(args) => cmd_bench_compare(ui, command, args),
+        BenchCommand::Diff(args) => cmd_bench_diff(ui, command, args),
         BenchCommand::IsAncestor(args) => cmd_bench_is_ancestor(ui, command, args),
+        BenchCommand::Log(args) => cmd_bench_log(ui, command, args),
         BenchCommand::ResolvePrefix(args) => cmd_bench_resolve_prefix(ui, command, args),
         BenchCommand::Revset(args) => cmd_bench_revset(ui, command, args),
+        BenchCommand::Snapshot(args) => cmd_bench_snapshot(ui, command, args),
     }
 }
 
@@ -70,6 +102,9 @@ struct CriterionArgs {
     /// Sample size for the benchmarks, which must be at least 10
     #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(10..))]
     sample_size: u32, // not usize because https://github.com/clap-rs/clap/issues/4253
+    /// Write the measured timings to this file as JSON
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    json: Option<PathBuf>,
 }
 
 fn new_criterion(ui: &Ui, args: &CriterionArgs) -> Criterion {
@@ -83,7 +118,13 @@ fn new_criterion(ui: &Ui, args: &CriterionArgs) -> Criterion {
     criterion.sample_size(args.sample_size as usize)
 }
 
-fn run_bench<R, O>(ui: &mut Ui, id: &str, args: &CriterionArgs, mut routine: R) -> io::Result<()>
+fn run_bench<R, O>(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    id: &str,
+    args: &CriterionArgs,
+    mut routine: R,
+) -> Result<(), CommandError>
 where
     R: (FnMut() -> O) + Copy,
     O: Debug,
@@ -98,8 +139,120 @@ where
         after.duration_since(before),
         result
     )?;
-    criterion.bench_function(id, |bencher: &mut criterion::Bencher| {
-        bencher.iter(routine);
+    let mut report = BenchReport::default();
+    let mut samples = vec![];
+    criterion.bench_function(id, |bencher: &mut Bencher| {
+        iter_recorded(bencher, &mut samples, || (), |_| routine());
+    });
+    report.add(id, &samples, args);
+    report.write(command, args)
+}
+
+/// Runs the benchmark like `Bencher::iter_batched_ref()`, and records the
+/// mean time per iteration of each sample.
+///
+/// Only the time spent in `routine` is measured.
+fn iter_recorded<I, O>(
+    bencher: &mut Bencher,
+    samples: &mut Vec<Duration>,
+    mut setup: impl FnMut() -> I,
+    mut routine: impl FnMut(&mut I) -> O,
+) {
+    bencher.iter_custom(|iters| {
+        let mut elapsed = Duration::ZERO;
+        for _ in 0..iters {
+            let mut input = setup();
+            let start = Instant::now();
+            let output = black_box(routine(&mut input));
+            elapsed += start.elapsed();
+            drop((input, output));
+        }
+        samples.push(elapsed.div_f64(iters as f64));
+        elapsed
     });
+}
+
+/// Summary of measured durations.
+#[derive(Debug, Serialize)]
+struct Timings {
+    samples_ms: Vec<f64>,
+    min_ms: f64,
+    median_ms: f64,
+    mean_ms: f64,
+}
+
+impl Timings {
+    fn new(samples: &[Duration]) -> Self {
+        let samples_ms = samples
+            .iter()
+            .map(|sample| sample.as_secs_f64() * 1000.0)
+            .collect_vec();
+        let sorted_ms = samples_ms
+            .iter()
+            .copied()
+            .sorted_by(f64::total_cmp)
+            .collect_vec();
+        let median_ms = if sorted_ms.len() % 2 == 0 {
+            let mid = sorted_ms.len() / 2;
+            (sorted_ms[mid - 1] + sorted_ms[mid]) / 2.0
+        } else {
+            sorted_ms[sorted_ms.len() / 2]
+        };
+        Self {
+            min_ms: sorted_ms[0],
+            median_ms,
+            mean_ms: samples_ms.iter().sum::<f64>() / samples_ms.len() as f64,
+            samples_ms,
+        }
+    }
+}
+
+/// Results of the benchmarks run by a command, written by `--json`.
+#[derive(Debug, Default, Serialize)]
+struct BenchReport {
+    benchmarks: Vec<BenchResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct BenchResult {
+    id: String,
+    #[serde(flatten)]
+    timings: Timings,
+}
+
+impl BenchReport {
+    /// Adds the samples recorded by `iter_recorded()`.
+    fn add(&mut self, id: &str, samples: &[Duration], args: &CriterionArgs) {
+        // Criterion runs the routine while warming up before taking the
+        // configured number of samples.
+        let sample_size = args.sample_size as usize;
+        let samples = &samples[samples.len().saturating_sub(sample_size)..];
+        if samples.is_empty() {
+            return;
+        }
+        self.benchmarks.push(BenchResult {
+            id: id.to_owned(),
+            timings: Timings::new(samples),
+        });
+    }
+
+    fn write(&self, command: &CommandHelper, args: &CriterionArgs) -> Result<(), CommandError> {
+        if let Some(path) = &args.json {
+            write_json(command, path, self)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_json(
+    command: &CommandHelper,
+    path: &Path,
+    value: &impl Serialize,
+) -> Result<(), CommandError> {
+    let file = std::fs::File::create(command.cwd().join(path)).map_err(|err| {
+        user_error_with_message(format!("Failed to create {}", path.display()), err)
+    })?;
+    serde_json::to_writer_pretty(file, value)
+        .map_err(|err| user_error_with_message("Failed to write results", err))?;
     Ok(())
 }
@@ -40,6 +40,7 @@ pub fn cmd_bench_resolve_prefix(
     let routine = || index.resolve_commit_id_prefix(&prefix);
     run_bench(
         ui,
+        command,
         &format!("resolve-prefix-{}", prefix.hex()),
         &args.criterion,
         routine,
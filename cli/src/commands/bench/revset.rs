@@ -13,17 +13,19 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::Instant;
 
-use criterion::BatchSize;
 use criterion::BenchmarkGroup;
 use criterion::BenchmarkId;
-use criterion::measurement::Measurement;
+use criterion::measurement::WallTime;
 use jj_lib::revset::SymbolResolver;
 use jj_lib::revset::SymbolResolverExtension;
 use jj_lib::revset::UserRevsetExpression;
 
+use super::BenchReport;
 use super::CriterionArgs;
+use super::iter_recorded;
 use super::new_criterion;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
@@ -62,22 +64,24 @@ pub fn cmd_bench_revset(
     };
     let mut criterion = new_criterion(ui, &args.criterion);
     let mut group = criterion.benchmark_group("revsets");
+    let mut report = BenchReport::default();
     for revset in &revsets {
-        bench_revset(ui, command, &workspace_command, &mut group, revset)?;
+        let samples = bench_revset(ui, command, &workspace_command, &mut group, revset)?;
+        report.add(&format!("revsets/{revset}"), &samples, &args.criterion);
     }
     // Neither of these seem to report anything...
     group.finish();
     criterion.final_summary();
-    Ok(())
+    report.write(command, &args.criterion)
 }
 
-fn bench_revset<M: Measurement>(
+fn bench_revset(
     ui: &mut Ui,
     command: &CommandHelper,
     workspace_command: &WorkspaceCommandHelper,
-    group: &mut BenchmarkGroup<M>,
+    group: &mut BenchmarkGroup<WallTime>,
     revset: &RevisionArg,
-) -> Result<(), CommandError> {
+) -> Result<Vec<Duration>, CommandError> {
     writeln!(ui.status(), "----------Testing revset: {revset}----------")?;
     let expression = workspace_command
         .parse_revset(ui, revset)?
@@ -105,11 +109,14 @@ fn bench_revset<M: Measurement>(
         after.duration_since(before),
     )?;
 
+    let mut samples = vec![];
     group.bench_with_input(
         BenchmarkId::from_parameter(revset),
         &expression,
         |bencher, expression| {
-            bencher.iter_batched(
+            iter_recorded(
+                bencher,
+                &mut samples,
                 // Reload repo and backend store to clear caches (such as commit objects
                 // in `Store`), but preload index since it's more likely to be loaded
                 // by preceding operation. `repo.reload_at()` isn't enough to clear
@@ -119,11 +126,9 @@ fn bench_revset<M: Measurement>(
                     workspace_command.repo().readonly_index();
                     workspace_command
                 },
-                |workspace_command| routine(&workspace_command, expression.clone()),
-                // Index-preloaded repo may consume a fair amount of memory
-                BatchSize::LargeInput,
+                |workspace_command| routine(workspace_command, expression.clone()),
             );
         },
     );
-    Ok(())
+    Ok(samples)
 }
@@ -0,0 +1,81 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Instant;
+
+use jj_lib::working_copy::SnapshotStats;
+use pollster::FutureExt as _;
+
+use super::BenchReport;
+use super::CriterionArgs;
+use super::iter_recorded;
+use super::new_criterion;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Snapshot the working copy without recording the result
+#[derive(clap::Args, Clone, Debug)]
+pub struct BenchSnapshotArgs {
+    #[command(flatten)]
+    criterion: CriterionArgs,
+}
+
+pub fn cmd_bench_snapshot(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BenchSnapshotArgs,
+) -> Result<(), CommandError> {
+    // Record pending changes first so that every iteration measures the same
+    // amount of work.
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let before = Instant::now();
+    let stats = snapshot(ui, &mut workspace_command)?;
+    let after = Instant::now();
+    writeln!(
+        ui.status(),
+        "First run took {:?} and left {} paths untracked",
+        after.duration_since(before),
+        stats.untracked_paths.len()
+    )?;
+
+    let mut criterion = new_criterion(ui, &args.criterion);
+    let mut report = BenchReport::default();
+    let mut samples = vec![];
+    criterion.bench_function("snapshot", |bencher| {
+        iter_recorded(
+            bencher,
+            &mut samples,
+            || command.workspace_helper_no_snapshot(ui).unwrap(),
+            |workspace_command| snapshot(ui, workspace_command).unwrap(),
+        );
+    });
+    report.add("snapshot", &samples, &args.criterion);
+    report.write(command, &args.criterion)
+}
+
+fn snapshot(
+    ui: &Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+) -> Result<SnapshotStats, CommandError> {
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let options =
+        workspace_command.snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)?;
+    let (mut locked_ws, _wc_commit) = workspace_command.start_working_copy_mutation()?;
+    // The lock is released without finishing, so the working-copy state isn't
+    // updated.
+    let (_tree, stats) = locked_ws.locked_wc().snapshot(&options).block_on()?;
+    Ok(stats)
+}
@@ -20,7 +20,9 @@ use jj_lib::str_util::StringPattern;
 use super::find_local_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::command_error::user_error_with_hint;
 use crate::complete;
+use crate::revset_util::parse_protected_bookmarks;
 use crate::ui::Ui;
 
 /// Delete an existing bookmark and propagate the deletion to remotes on the
@@ -33,6 +35,9 @@ use crate::ui::Ui;
 ///
 /// If you don't want the deletion of the local bookmark to propagate to any
 /// tracked remote bookmarks, use `jj bookmark forget` instead.
+///
+/// Bookmarks matching the `bookmarks.protected` setting can only be deleted
+/// with `--allow-protected`.
 #[derive(clap::Args, Clone, Debug)]
 pub struct BookmarkDeleteArgs {
     /// The bookmarks to delete
@@ -48,6 +53,10 @@ pub struct BookmarkDeleteArgs {
         add = ArgValueCandidates::new(complete::local_bookmarks),
     )]
     names: Vec<StringPattern>,
+
+    /// Allow deleting protected bookmarks
+    #[arg(long)]
+    allow_protected: bool,
 }
 
 pub fn cmd_bookmark_delete(
@@ -58,6 +67,21 @@ pub fn cmd_bookmark_delete(
     let mut workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo().clone();
     let matched_bookmarks = find_local_bookmarks(repo.view(), &args.names)?;
+    if !args.allow_protected {
+        let protected = parse_protected_bookmarks(ui, workspace_command.settings())?;
+        if let Some((name, _)) = matched_bookmarks
+            .iter()
+            .find(|(name, _)| protected.is_match(name.as_str()))
+        {
+            return Err(user_error_with_hint(
+                format!(
+                    "Refusing to delete protected bookmark: {name}",
+                    name = name.as_symbol()
+                ),
+                "Use --allow-protected to allow it.",
+            ));
+        }
+    }
     let mut tx = workspace_command.start_transaction();
     for (name, _) in &matched_bookmarks {
         tx.repo_mut()
@@ -85,6 +85,13 @@ pub struct BookmarkListArgs {
     #[arg(long, short, conflicts_with_all = ["all_remotes"])]
     conflicted: bool,
 
+    /// Show archived bookmarks only
+    ///
+    /// Archived bookmarks are omitted by default unless they are selected by
+    /// name.
+    #[arg(long)]
+    archived: bool,
+
     /// Show bookmarks whose local name matches
     ///
     /// By default, the specified name matches exactly. Use `glob:` prefix to
@@ -183,7 +190,15 @@ pub fn cmd_bookmark_list(
                     .added_ids()
                     .any(|id| matched_local_targets.contains(id))
         })
-        .filter(|(_, target)| !args.conflicted || target.local_target.has_conflict());
+        .filter(|(_, target)| !args.conflicted || target.local_target.has_conflict())
+        .filter(|(name, _)| {
+            if args.archived {
+                view.is_bookmark_archived(name)
+            } else {
+                !view.is_bookmark_archived(name)
+                    || args.names.is_some() && name_matcher.is_match(name.as_str())
+            }
+        });
     let mut any_conflicts = false;
     for (name, bookmark_target) in bookmarks_to_list {
         let local_target = bookmark_target.local_target;
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod archive;
 mod create;
 mod delete;
 mod forget;
@@ -20,6 +21,7 @@ mod r#move;
 mod rename;
 mod set;
 mod track;
+mod unarchive;
 mod untrack;
 
 use itertools::Itertools as _;
@@ -34,6 +36,8 @@ use jj_lib::str_util::StringMatcher;
 use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
 
+use self::archive::BookmarkArchiveArgs;
+use self::archive::cmd_bookmark_archive;
 use self::create::BookmarkCreateArgs;
 use self::create::cmd_bookmark_create;
 use self::delete::BookmarkDeleteArgs;
@@ -50,6 +54,8 @@ use self::set::BookmarkSetArgs;
 use self::set::cmd_bookmark_set;
 use self::track::BookmarkTrackArgs;
 use self::track::cmd_bookmark_track;
+use self::unarchive::BookmarkUnarchiveArgs;
+use self::unarchive::cmd_bookmark_unarchive;
 use self::untrack::BookmarkUntrackArgs;
 use self::untrack::cmd_bookmark_untrack;
 use crate::cli_util::CommandHelper;
@@ -69,6 +75,7 @@ use crate::ui::Ui;
 ///     https://jj-vcs.github.io/jj/latest/bookmarks
 #[derive(clap::Subcommand, Clone, Debug)]
 pub enum BookmarkCommand {
+    Archive(BookmarkArchiveArgs),
     #[command(visible_alias("c"))]
     Create(BookmarkCreateArgs),
     #[command(visible_alias("d"))]
@@ -85,6 +92,7 @@ pub enum BookmarkCommand {
     Set(BookmarkSetArgs),
     #[command(visible_alias("t"))]
     Track(BookmarkTrackArgs),
+    Unarchive(BookmarkUnarchiveArgs),
     Untrack(BookmarkUntrackArgs),
 }
 
@@ -94,6 +102,7 @@ pub fn cmd_bookmark(
     subcommand: &BookmarkCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        BookmarkCommand::Archive(args) => cmd_bookmark_archive(ui, command, args),
         BookmarkCommand::Create(args) => cmd_bookmark_create(ui, command, args),
         BookmarkCommand::Delete(args) => cmd_bookmark_delete(ui, command, args),
         BookmarkCommand::Forget(args) => cmd_bookmark_forget(ui, command, args),
@@ -102,6 +111,7 @@ pub fn cmd_bookmark(
         BookmarkCommand::Rename(args) => cmd_bookmark_rename(ui, command, args),
         BookmarkCommand::Set(args) => cmd_bookmark_set(ui, command, args),
         BookmarkCommand::Track(args) => cmd_bookmark_track(ui, command, args),
+        BookmarkCommand::Unarchive(args) => cmd_bookmark_unarchive(ui, command, args),
         BookmarkCommand::Untrack(args) => cmd_bookmark_untrack(ui, command, args),
     }
 }
@@ -168,7 +178,7 @@ fn find_trackable_remote_bookmarks<'a>(
     }
     match &unmatched_patterns[..] {
         [] => {
-            matching_bookmarks.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
+            matching_bookmarks.sort_unstable_by_key(|(sym, _)| *sym);
             matching_bookmarks.dedup_by(|(sym1, _), (sym2, _)| sym1 == sym2);
             Ok(matching_bookmarks)
         }
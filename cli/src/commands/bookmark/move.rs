@@ -28,6 +28,7 @@ use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::command_error::user_error_with_hint;
 use crate::complete;
+use crate::revset_util::parse_protected_bookmarks;
 use crate::ui::Ui;
 
 /// Move existing bookmarks to target revision
@@ -42,6 +43,9 @@ use crate::ui::Ui;
 /// Example: pull up the nearest bookmarks to the working-copy parent
 ///
 /// $ jj bookmark move --from 'heads(::@- & bookmarks())' --to @-
+///
+/// Bookmarks matching the `bookmarks.protected` setting can only be moved
+/// backwards or sideways with `--allow-protected`.
 #[derive(clap::Args, Clone, Debug)]
 #[command(group(clap::ArgGroup::new("source").multiple(true).required(true)))]
 pub struct BookmarkMoveArgs {
@@ -80,6 +84,10 @@ pub struct BookmarkMoveArgs {
     /// Allow moving bookmarks backwards or sideways
     #[arg(long, short = 'B')]
     allow_backwards: bool,
+
+    /// Allow moving protected bookmarks backwards or sideways
+    #[arg(long, requires = "allow_backwards")]
+    allow_protected: bool,
 }
 
 pub fn cmd_bookmark_move(
@@ -149,6 +157,26 @@ pub fn cmd_bookmark_move(
             "Use --allow-backwards to allow it.",
         ));
     }
+    if args.allow_backwards && !args.allow_protected {
+        let protected = parse_protected_bookmarks(ui, workspace_command.settings())?;
+        if let Some((name, _)) = fallible_find(
+            matched_bookmarks
+                .iter()
+                .filter(|(name, _)| protected.is_match(name.as_str())),
+            |(_, old_target)| -> Result<_, CommandError> {
+                let is_ff = is_fast_forward(repo.as_ref(), old_target, target_commit.id())?;
+                Ok(!is_ff)
+            },
+        )? {
+            return Err(user_error_with_hint(
+                format!(
+                    "Refusing to move protected bookmark backwards or sideways: {name}",
+                    name = name.as_symbol()
+                ),
+                "Use --allow-protected to allow it.",
+            ));
+        }
+    }
     if target_commit.is_discardable(repo.as_ref())? {
         writeln!(ui.warning_default(), "Target revision is empty.")?;
     }
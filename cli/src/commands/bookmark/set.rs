@@ -29,9 +29,13 @@ use crate::command_error::CommandError;
 use crate::command_error::user_error_with_hint;
 use crate::complete;
 use crate::revset_util;
+use crate::revset_util::parse_protected_bookmarks;
 use crate::ui::Ui;
 
 /// Create or update a bookmark to point to a certain commit
+///
+/// Bookmarks matching the `bookmarks.protected` setting can only be moved
+/// backwards or sideways with `--allow-protected`.
 #[derive(clap::Args, Clone, Debug)]
 pub struct BookmarkSetArgs {
     /// The bookmark's target revision
@@ -48,6 +52,10 @@ pub struct BookmarkSetArgs {
     #[arg(long, short = 'B')]
     allow_backwards: bool,
 
+    /// Allow moving protected bookmarks backwards or sideways
+    #[arg(long, requires = "allow_backwards")]
+    allow_protected: bool,
+
     /// The bookmarks to update
     #[arg(
         required = true,
@@ -66,6 +74,7 @@ pub fn cmd_bookmark_set(
     let target_commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
     let repo = workspace_command.repo().as_ref();
     let bookmark_names = &args.names;
+    let protected = parse_protected_bookmarks(ui, workspace_command.settings())?;
     let mut new_bookmarks = HashSet::new();
     let mut moved_bookmark_count = 0;
     for name in bookmark_names {
@@ -86,6 +95,18 @@ pub fn cmd_bookmark_set(
                 "Use --allow-backwards to allow it.",
             ));
         }
+        if !args.allow_protected
+            && protected.is_match(name.as_str())
+            && !is_fast_forward(repo, old_target, target_commit.id())?
+        {
+            return Err(user_error_with_hint(
+                format!(
+                    "Refusing to move protected bookmark backwards or sideways: {name}",
+                    name = name.as_symbol()
+                ),
+                "Use --allow-protected to allow it.",
+            ));
+        }
     }
     if target_commit.is_discardable(repo)? {
         writeln!(ui.warning_default(), "Target revision is empty.")?;
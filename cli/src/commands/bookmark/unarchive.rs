@@ -0,0 +1,78 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::str_util::StringPattern;
+
+use super::find_local_bookmarks;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Restore archived bookmarks to the default listing
+#[derive(clap::Args, Clone, Debug)]
+pub struct BookmarkUnarchiveArgs {
+    /// The archived bookmarks to restore
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select bookmarks by [wildcard pattern].
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(
+        required = true,
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::archived_bookmarks),
+    )]
+    names: Vec<StringPattern>,
+}
+
+pub fn cmd_bookmark_unarchive(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BookmarkUnarchiveArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().clone();
+    let view = repo.view();
+    let mut names = Vec::new();
+    for (name, _) in find_local_bookmarks(view, &args.names)? {
+        if view.is_bookmark_archived(name) {
+            names.push(name);
+        } else {
+            writeln!(
+                ui.warning_default(),
+                "Bookmark not archived: {name}",
+                name = name.as_symbol()
+            )?;
+        }
+    }
+    let mut tx = workspace_command.start_transaction();
+    for &name in &names {
+        tx.repo_mut().set_bookmark_archived(name, false);
+    }
+    if !names.is_empty() {
+        writeln!(ui.status(), "Unarchived {} bookmarks.", names.len())?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "unarchive bookmark {}",
+            names.iter().map(|name| name.as_symbol()).join(", ")
+        ),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,199 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::formatter::FormatterExt as _;
+use crate::ui::Ui;
+
+/// Generate release notes from the descriptions of revisions
+///
+/// The revisions are grouped by the type of their description, as in the
+/// [Conventional Commits] format (e.g. `feat` in `feat(cli): add a command`).
+/// The groups are configured in the `changelog.groups` setting, each with a
+/// title and a list of types. A group with the type `*` collects the revisions
+/// that don't belong to any other group, including revisions whose description
+/// doesn't follow the format. For example:
+///
+/// ```toml
+/// [changelog]
+/// groups = [
+///   { title = "New features", types = ["feat"] },
+///   { title = "Fixed bugs", types = ["fix"] },
+///   { title = "Other changes", types = ["*"] },
+/// ]
+/// ```
+///
+/// Each group is printed as a Markdown heading followed by the revisions in
+/// the group, newest first. Groups without revisions are omitted. Revisions
+/// are rendered with the `templates.changelog_entry` template, which by
+/// default removes the type from the first line of the description, and
+/// appends the values of any `Fixes` trailers.
+///
+/// [Conventional Commits]: https://www.conventionalcommits.org/
+#[derive(clap::Args, Clone, Debug)]
+#[command(verbatim_doc_comment)]
+pub(crate) struct ChangelogArgs {
+    /// The revision(s) to include in the changelog
+    ///
+    /// If no revisions are specified, this defaults to the `revsets.changelog`
+    /// setting, which includes the non-empty revisions since the last tag.
+    #[arg(
+        long, short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// Render each revision using the given template
+    ///
+    /// All 0-argument methods of the [`Commit` type] are available as
+    /// keywords in the template expression. See [`jj help -k templates`] for
+    /// more information.
+    ///
+    /// If not specified, this defaults to the `templates.changelog_entry`
+    /// setting.
+    ///
+    /// [`Commit` type]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/#commit-type
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+/// A group of revisions in the changelog.
+#[derive(Clone, Debug, serde::Deserialize)]
+struct ChangelogGroup {
+    title: String,
+    types: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_changelog(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ChangelogArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let settings = workspace_command.settings();
+
+    let revset_expression = if args.revisions.is_empty() {
+        let revset_string = settings.get_string("revsets.changelog")?;
+        workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+    } else {
+        workspace_command.parse_union_revsets(ui, &args.revisions)?
+    };
+    let commits: Vec<Commit> = revset_expression.evaluate_to_commits()?.try_collect()?;
+
+    let template_string = match &args.template {
+        Some(value) => value.clone(),
+        None => settings.get_string("templates.changelog_entry")?,
+    };
+    let template = workspace_command
+        .parse_commit_template(ui, &template_string)?
+        .labeled(["changelog", "commit"]);
+
+    let groups: Vec<ChangelogGroup> = settings.get("changelog.groups")?;
+    let mut grouped_commits: Vec<Vec<&Commit>> = vec![vec![]; groups.len()];
+    for commit in &commits {
+        let commit_type = conventional_commit_type(commit.description());
+        let matches_type = |group: &ChangelogGroup| {
+            commit_type.is_some_and(|commit_type| {
+                group
+                    .types
+                    .iter()
+                    .any(|ty| ty.eq_ignore_ascii_case(commit_type))
+            })
+        };
+        let index = groups.iter().position(matches_type).or_else(|| {
+            groups
+                .iter()
+                .position(|group| group.types.iter().any(|ty| ty == "*"))
+        });
+        if let Some(index) = index {
+            grouped_commits[index].push(commit);
+        }
+    }
+
+    if grouped_commits.iter().all(|commits| commits.is_empty()) {
+        writeln!(ui.status(), "No revisions to include in the changelog.")?;
+        return Ok(());
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let non_empty_groups = groups
+        .iter()
+        .zip(&grouped_commits)
+        .filter(|(_, commits)| !commits.is_empty());
+    for (i, (group, commits)) in non_empty_groups.enumerate() {
+        if i > 0 {
+            writeln!(formatter)?;
+        }
+        writeln!(formatter.labeled("heading"), "### {}", group.title)?;
+        writeln!(formatter)?;
+        for commit in commits {
+            template.format(commit, formatter.as_mut())?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the type of a description in the Conventional Commits format, e.g.
+/// `feat` for `feat(cli)!: add a command`.
+fn conventional_commit_type(description: &str) -> Option<&str> {
+    let first_line = description.lines().next()?;
+    let (prefix, _) = first_line.split_once(": ")?;
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let commit_type = match prefix.split_once('(') {
+        Some((commit_type, scope)) => scope.ends_with(')').then_some(commit_type)?,
+        None => prefix,
+    };
+    let is_valid = !commit_type.is_empty()
+        && commit_type
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    is_valid.then_some(commit_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conventional_commit_type() {
+        assert_eq!(conventional_commit_type("feat: add x"), Some("feat"));
+        assert_eq!(conventional_commit_type("fix(cli): fix x\n"), Some("fix"));
+        assert_eq!(conventional_commit_type("feat!: change x"), Some("feat"));
+        assert_eq!(conventional_commit_type("feat(lib)!: x"), Some("feat"));
+        assert_eq!(conventional_commit_type("Fix: x\n\nbody"), Some("Fix"));
+        assert_eq!(conventional_commit_type("add x"), None);
+        assert_eq!(conventional_commit_type("cli: add x"), Some("cli"));
+        assert_eq!(conventional_commit_type("Update docs: x y"), None);
+        assert_eq!(conventional_commit_type("feat(cli: x"), None);
+        assert_eq!(conventional_commit_type(""), None);
+    }
+}
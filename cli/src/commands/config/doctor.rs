@@ -0,0 +1,418 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io::Write as _;
+use std::ops::Range;
+use std::sync::Arc;
+
+use itertools::Itertools as _;
+use jj_lib::config::ConfigFile;
+use jj_lib::config::ConfigItem;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigNamePathBuf;
+use jj_lib::config::ConfigSource;
+use jj_lib::config::ConfigTableLike;
+use jj_lib::config::StackedConfig;
+use jj_lib::dsl_util::collect_similar;
+use jj_lib::revset;
+use jj_lib::revset::RevsetAliasesMap;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::config::CONFIG_SCHEMA;
+use crate::template_parser;
+use crate::template_parser::TemplateAliasesMap;
+use crate::ui::Ui;
+
+/// Check config files for problems
+///
+/// Each user, repo, and workspace config file is checked against the known
+/// config options. Unknown keys, values of unexpected type, deprecated names,
+/// and alias definitions which can't be parsed are reported with their file
+/// and line number.
+#[derive(clap::Args, Clone, Debug)]
+pub struct ConfigDoctorArgs {
+    /// Rename deprecated config options in place
+    #[arg(long)]
+    fix: bool,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_config_doctor(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigDoctorArgs,
+) -> Result<(), CommandError> {
+    let schema: serde_json::Value = serde_json::from_str(CONFIG_SCHEMA).unwrap();
+    let mut num_problems = 0;
+    let mut num_fixable = 0;
+    for layer in command.raw_config().as_ref().layers() {
+        if !matches!(
+            layer.source,
+            ConfigSource::User
+                | ConfigSource::Repo
+                | ConfigSource::Workspace
+                | ConfigSource::CommandArg
+        ) {
+            continue;
+        }
+        let location = match &layer.path {
+            Some(path) => path.display().to_string(),
+            None => layer.source.to_string(),
+        };
+
+        let mut migrated_config = StackedConfig::empty();
+        migrated_config.add_layer(layer.clone());
+        let descriptions =
+            match jj_lib::config::migrate(&mut migrated_config, command.config_migrations()) {
+                Ok(descriptions) => descriptions,
+                Err(err) => {
+                    writeln!(ui.stdout(), "{location}: {}", err.error)?;
+                    num_problems += 1;
+                    vec![]
+                }
+            };
+        let migrated_layer = &migrated_config.layers()[0];
+        let renamed = migrated_layer.data.to_string() != layer.data.to_string();
+        let fixed = args.fix && renamed && layer.path.is_some();
+        if fixed {
+            let file = ConfigFile::from_layer(Arc::clone(migrated_layer))
+                .expect("layer should have a path");
+            file.save()?;
+        }
+        for (_, desc) in &descriptions {
+            if fixed {
+                writeln!(ui.stdout(), "{location}: Fixed: {desc}")?;
+            } else {
+                writeln!(ui.stdout(), "{location}: Deprecated: {desc}")?;
+                num_problems += 1;
+            }
+        }
+        if renamed && !fixed {
+            num_fixable += 1;
+        }
+
+        // Re-read the file to get the line numbers. The file may have been
+        // updated by the fix above.
+        let text = layer
+            .path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok());
+        let document = text
+            .as_deref()
+            .and_then(|text| toml_edit::Document::parse(text).ok());
+        let root_table: &ConfigTableLike = match &document {
+            Some(document) => document.as_table(),
+            None => migrated_layer.data.as_table(),
+        };
+        let mut checker = SchemaChecker {
+            schema: &schema,
+            text: text.as_deref(),
+            migrated_layer,
+            problems: vec![],
+        };
+        checker.check_table(&schema, &ConfigNamePathBuf::root(), root_table);
+        checker.check_aliases(root_table);
+        for (line, message) in checker.problems {
+            match line {
+                Some(line) => writeln!(ui.stdout(), "{location}:{line}: {message}")?,
+                None => writeln!(ui.stdout(), "{location}: {message}")?,
+            }
+            num_problems += 1;
+        }
+    }
+
+    if num_problems == 0 {
+        writeln!(ui.status(), "No problems found.")?;
+    } else {
+        writeln!(ui.status(), "Found {num_problems} problem(s).")?;
+    }
+    if num_fixable > 0 {
+        writeln!(
+            ui.hint_default(),
+            "Run `jj config doctor --fix` to rename the deprecated config options."
+        )?;
+    }
+    Ok(())
+}
+
+/// Checks config items against the JSON schema of the known config options.
+///
+/// Only the subset of the JSON schema used by `config-schema.json` is
+/// interpreted.
+struct SchemaChecker<'a> {
+    schema: &'a serde_json::Value,
+    text: Option<&'a str>,
+    migrated_layer: &'a ConfigLayer,
+    problems: Vec<(Option<usize>, String)>,
+}
+
+impl<'a> SchemaChecker<'a> {
+    fn report(&mut self, span: Option<Range<usize>>, message: String) {
+        let line = span
+            .zip(self.text)
+            .map(|(span, text)| text[..span.start].matches('\n').count() + 1);
+        self.problems.push((line, message));
+    }
+
+    fn resolve(&self, mut schema: &'a serde_json::Value) -> &'a serde_json::Value {
+        while let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+            let resolved = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.schema.pointer(pointer));
+            match resolved {
+                Some(resolved) => schema = resolved,
+                None => break,
+            }
+        }
+        schema
+    }
+
+    fn check_table(
+        &mut self,
+        schema: &'a serde_json::Value,
+        name: &ConfigNamePathBuf,
+        table: &ConfigTableLike,
+    ) {
+        let schema = self.resolve(schema);
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        let additional = schema.get("additionalProperties");
+        for (key, item) in table.iter() {
+            let span = table.get_key_value(key).and_then(|(key, _)| key.span());
+            let mut item_name = name.clone();
+            item_name.push(key);
+            if let Some(item_schema) = properties.and_then(|p| p.get(key)) {
+                self.check_item(item_schema, &item_name, item, span);
+                continue;
+            }
+            match additional {
+                Some(serde_json::Value::Bool(true)) => {}
+                Some(item_schema @ serde_json::Value::Object(_)) => {
+                    self.check_item(item_schema, &item_name, item, span);
+                }
+                None if properties.is_none() => {}
+                _ => {
+                    if self.is_renamed(&item_name) {
+                        // Already reported as deprecated.
+                        continue;
+                    }
+                    let similar = properties
+                        .map(|p| collect_similar(key, p.keys()))
+                        .unwrap_or_default();
+                    let message = match similar.first() {
+                        Some(similar) => {
+                            let mut similar_name = name.clone();
+                            similar_name.push(similar.as_str());
+                            format!("Unknown key `{item_name}`. Did you mean `{similar_name}`?")
+                        }
+                        None => format!("Unknown key `{item_name}`"),
+                    };
+                    self.report(span, message);
+                }
+            }
+        }
+    }
+
+    fn check_item(
+        &mut self,
+        schema: &'a serde_json::Value,
+        name: &ConfigNamePathBuf,
+        item: &ConfigItem,
+        span: Option<Range<usize>>,
+    ) {
+        let schema = self.resolve(schema);
+        if let Some(branches) = schema.get("oneOf").and_then(|b| b.as_array()) {
+            match branches
+                .iter()
+                .find(|branch| self.type_matches(branch, item))
+            {
+                Some(branch) => self.check_item(branch, name, item, span),
+                None => {
+                    let expected = branches
+                        .iter()
+                        .flat_map(|branch| self.expected_types(branch))
+                        .unique()
+                        .join(" or ");
+                    let actual = item.type_name();
+                    self.report(
+                        span,
+                        format!("`{name}`: Expected {expected}, but is {actual}"),
+                    );
+                }
+            }
+            return;
+        }
+        if !self.type_matches(schema, item) {
+            let expected = self.expected_types(schema).join(" or ");
+            let actual = item.type_name();
+            self.report(
+                span,
+                format!("`{name}`: Expected {expected}, but is {actual}"),
+            );
+            return;
+        }
+        if let (Some(variants), Some(value)) =
+            (schema.get("enum").and_then(|v| v.as_array()), item.as_str())
+        {
+            if !variants
+                .iter()
+                .any(|variant| variant.as_str() == Some(value))
+            {
+                let variants = variants
+                    .iter()
+                    .map(|variant| variant.to_string())
+                    .join(", ");
+                self.report(
+                    span,
+                    format!("`{name}`: Invalid value {value:?}, expected one of {variants}"),
+                );
+            }
+            return;
+        }
+        if let Some(table) = item.as_table_like() {
+            self.check_table(schema, name, table);
+        } else if let Some(item_schema) = schema.get("items") {
+            if let Some(array) = item.as_array() {
+                for value in array {
+                    let value_item = ConfigItem::Value(value.clone());
+                    self.check_item(item_schema, name, &value_item, value.span());
+                }
+            } else if let Some(array) = item.as_array_of_tables() {
+                for table in array {
+                    let table_item = ConfigItem::Table(table.clone());
+                    self.check_item(item_schema, name, &table_item, table.span());
+                }
+            }
+        }
+    }
+
+    fn check_aliases(&mut self, root_table: &ConfigTableLike) {
+        self.check_alias_table(root_table, "revset-aliases", |decl, defn| {
+            RevsetAliasesMap::new()
+                .insert(decl, defn)
+                .map_err(|err| err.to_string())?;
+            revset::parse_program(defn).map_err(|err| err.to_string())?;
+            Ok(())
+        });
+        self.check_alias_table(root_table, "template-aliases", |decl, defn| {
+            TemplateAliasesMap::new()
+                .insert(decl, defn)
+                .map_err(|err| err.to_string())?;
+            template_parser::parse_template(defn).map_err(|err| err.to_string())?;
+            Ok(())
+        });
+    }
+
+    /// Checks that the alias declarations and definitions can be parsed. These
+    /// errors would otherwise be reported only when the alias is used.
+    fn check_alias_table(
+        &mut self,
+        root_table: &ConfigTableLike,
+        table_name: &str,
+        parse: impl Fn(&str, &str) -> Result<(), String>,
+    ) {
+        let Some(table) = root_table.get(table_name).and_then(|t| t.as_table_like()) else {
+            return;
+        };
+        for (decl, item) in table.iter() {
+            // Non-string values are reported by the schema check.
+            let Some(defn) = item.as_str() else {
+                continue;
+            };
+            if let Err(err) = parse(decl, defn) {
+                let span = table.get_key_value(decl).and_then(|(key, _)| key.span());
+                let name = ConfigNamePathBuf::from_iter([table_name, decl]);
+                self.report(span, format!("Failed to parse `{name}`: {err}"));
+            }
+        }
+    }
+
+    fn type_matches(&self, schema: &'a serde_json::Value, item: &ConfigItem) -> bool {
+        let schema = self.resolve(schema);
+        if let Some(branches) = schema.get("oneOf").and_then(|b| b.as_array()) {
+            return branches
+                .iter()
+                .any(|branch| self.type_matches(branch, item));
+        }
+        let actual = json_type_of(item);
+        match schema.get("type") {
+            Some(serde_json::Value::String(ty)) => type_accepts(ty, actual),
+            Some(serde_json::Value::Array(types)) => types
+                .iter()
+                .filter_map(|ty| ty.as_str())
+                .any(|ty| type_accepts(ty, actual)),
+            _ => true,
+        }
+    }
+
+    fn expected_types(&self, schema: &'a serde_json::Value) -> Vec<&'static str> {
+        let schema = self.resolve(schema);
+        let types = match schema.get("type") {
+            Some(serde_json::Value::String(ty)) => vec![ty.as_str()],
+            Some(serde_json::Value::Array(types)) => {
+                types.iter().filter_map(|ty| ty.as_str()).collect()
+            }
+            _ => vec![],
+        };
+        types
+            .into_iter()
+            .map(|ty| match ty {
+                "array" => "an array",
+                "boolean" => "a boolean",
+                "integer" => "an integer",
+                "number" => "a number",
+                "object" => "a table",
+                "string" => "a string",
+                _ => "a value",
+            })
+            .collect()
+    }
+
+    /// Returns true if the item at `name` is moved by config migration.
+    fn is_renamed(&self, name: &ConfigNamePathBuf) -> bool {
+        // Migration rules don't apply to scoped tables.
+        let scoped = name
+            .components()
+            .next()
+            .is_some_and(|key| key.get() == "--scope");
+        if scoped {
+            return false;
+        }
+        // The parent table of the moved item is left behind.
+        match self.migrated_layer.look_up_item(name) {
+            Ok(None) => true,
+            Ok(Some(item)) => item.as_table_like().is_some_and(|table| table.is_empty()),
+            Err(_) => false,
+        }
+    }
+}
+
+fn json_type_of(item: &ConfigItem) -> &'static str {
+    match item {
+        ConfigItem::None => "null",
+        ConfigItem::Value(toml_edit::Value::String(_)) => "string",
+        ConfigItem::Value(toml_edit::Value::Integer(_)) => "integer",
+        ConfigItem::Value(toml_edit::Value::Float(_)) => "number",
+        ConfigItem::Value(toml_edit::Value::Boolean(_)) => "boolean",
+        ConfigItem::Value(toml_edit::Value::Datetime(_)) => "datetime",
+        ConfigItem::Value(toml_edit::Value::Array(_)) | ConfigItem::ArrayOfTables(_) => "array",
+        ConfigItem::Value(toml_edit::Value::InlineTable(_)) | ConfigItem::Table(_) => "object",
+    }
+}
+
+fn type_accepts(expected: &str, actual: &str) -> bool {
+    expected == actual || (expected == "number" && actual == "integer")
+}
@@ -0,0 +1,339 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::config::ConfigNamePathBuf;
+use jj_lib::config::ConfigValue;
+use tracing::instrument;
+
+use super::ConfigLevelArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
+use crate::ui::Ui;
+
+/// Import settings from other tools into jj config
+///
+/// With `--from-git`, the global Git config is read, and the settings which
+/// have a jj equivalent are translated: user identity, editor and pager,
+/// commit signing, diff and merge tool definitions, and aliases of commands
+/// with a direct jj equivalent. Settings which are already set in the target
+/// config file are left unchanged.
+#[derive(clap::Args, Clone, Debug)]
+pub struct ConfigMigrateArgs {
+    /// Import settings from the global Git config
+    #[arg(long, required = true)]
+    from_git: bool,
+    /// Print the translated settings without writing them
+    #[arg(long)]
+    dry_run: bool,
+    #[command(flatten)]
+    level: ConfigLevelArgs,
+}
+
+/// Git setting translated to jj config.
+struct Translated {
+    git_key: String,
+    name: ConfigNamePathBuf,
+    value: ConfigValue,
+}
+
+/// Git setting which couldn't be translated, with the reason.
+struct Untranslated {
+    git_key: String,
+    reason: String,
+}
+
+#[derive(Default)]
+struct Migration {
+    translated: Vec<Translated>,
+    untranslated: Vec<Untranslated>,
+}
+
+impl Migration {
+    fn translate(
+        &mut self,
+        git_key: impl Into<String>,
+        name: ConfigNamePathBuf,
+        value: impl Into<ConfigValue>,
+    ) {
+        self.translated.push(Translated {
+            git_key: git_key.into(),
+            name,
+            value: value.into(),
+        });
+    }
+
+    fn skip(&mut self, git_key: impl Into<String>, reason: impl Into<String>) {
+        self.untranslated.push(Untranslated {
+            git_key: git_key.into(),
+            reason: reason.into(),
+        });
+    }
+}
+
+#[instrument(skip_all)]
+pub fn cmd_config_migrate(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigMigrateArgs,
+) -> Result<(), CommandError> {
+    assert!(args.from_git);
+    let git_config = gix::config::File::from_globals()
+        .map_err(|err| user_error_with_message("Failed to read Git config", err))?;
+    let mut migration = translate_git_config(&git_config);
+    let mut file = args.level.edit_config_file(ui, command)?;
+
+    let mut imported = vec![];
+    for translated in migration.translated {
+        if !matches!(file.layer().look_up_item(&translated.name), Ok(None)) {
+            migration.untranslated.push(Untranslated {
+                git_key: translated.git_key,
+                reason: format!("`{}` is already set", translated.name),
+            });
+            continue;
+        }
+        file.set_value(&translated.name, translated.value.clone())
+            .map_err(|err| {
+                user_error_with_message(format!("Failed to set {}", translated.name), err)
+            })?;
+        imported.push(translated);
+    }
+    if !args.dry_run && !imported.is_empty() {
+        file.save()?;
+    }
+
+    if imported.is_empty() {
+        writeln!(ui.stdout(), "Nothing imported from Git config.")?;
+    } else if args.dry_run {
+        writeln!(ui.stdout(), "Would import from Git config:")?;
+    } else {
+        writeln!(ui.stdout(), "Imported from Git config:")?;
+    }
+    for Translated {
+        git_key,
+        name,
+        value,
+    } in &imported
+    {
+        writeln!(ui.stdout(), "  {git_key} -> {name} = {value}")?;
+    }
+    if !migration.untranslated.is_empty() {
+        writeln!(ui.stdout(), "Not imported:")?;
+        for Untranslated { git_key, reason } in &migration.untranslated {
+            writeln!(ui.stdout(), "  {git_key}: {reason}")?;
+        }
+    }
+    Ok(())
+}
+
+fn translate_git_config(git_config: &gix::config::File) -> Migration {
+    let mut migration = Migration::default();
+    let get_string = |key: &str| git_config.string(key).map(|value| value.to_string());
+
+    for (git_key, name) in [
+        ("user.name", ["user", "name"]),
+        ("user.email", ["user", "email"]),
+        ("core.editor", ["ui", "editor"]),
+        ("core.pager", ["ui", "pager"]),
+    ] {
+        if let Some(value) = get_string(git_key) {
+            migration.translate(git_key, ConfigNamePathBuf::from_iter(name), value);
+        }
+    }
+
+    translate_signing(git_config, &mut migration);
+
+    for (section_name, args_name, vars) in [
+        ("difftool", "diff-args", DIFF_TOOL_VARIABLES),
+        ("mergetool", "merge-args", MERGE_TOOL_VARIABLES),
+    ] {
+        for section in git_config
+            .sections_by_name(section_name)
+            .into_iter()
+            .flatten()
+        {
+            let Some(tool) = section.header().subsection_name() else {
+                continue;
+            };
+            let tool = tool.to_string();
+            let git_key = format!("{section_name}.{tool}.cmd");
+            let Some(cmd) = section.value("cmd") else {
+                continue;
+            };
+            let Some(mut args) = translate_tool_command(&cmd.to_string(), vars) else {
+                migration.skip(git_key, "the command uses shell syntax");
+                continue;
+            };
+            let program = args.remove(0);
+            migration.translate(
+                git_key.clone(),
+                ConfigNamePathBuf::from_iter(["merge-tools", &tool, "program"]),
+                program,
+            );
+            migration.translate(
+                git_key,
+                ConfigNamePathBuf::from_iter(["merge-tools", &tool, args_name]),
+                toml_edit::Array::from_iter(args),
+            );
+        }
+    }
+    if let Some(tool) = get_string("merge.tool") {
+        migration.translate(
+            "merge.tool",
+            ConfigNamePathBuf::from_iter(["ui", "merge-editor"]),
+            tool,
+        );
+    }
+    if let Some(tool) = get_string("diff.tool") {
+        migration.skip(
+            "diff.tool",
+            format!("use `jj diff --tool {tool}` to run the tool"),
+        );
+    }
+
+    for section in git_config.sections_by_name("alias").into_iter().flatten() {
+        for alias in section.value_names() {
+            let alias: &str = alias.as_ref();
+            let git_key = format!("alias.{alias}");
+            let Some(value) = section.value(alias) else {
+                continue;
+            };
+            let value = value.to_string();
+            if let Some(script) = value.strip_prefix('!') {
+                // Git passes the alias arguments after the shell command.
+                migration.translate(
+                    git_key,
+                    ConfigNamePathBuf::from_iter(["aliases", alias]),
+                    format!(r#"!{} "$@""#, script.trim()),
+                );
+                continue;
+            }
+            let Some(args) = translate_git_subcommand(value.trim()) else {
+                migration.skip(
+                    git_key,
+                    format!("`git {value}` has no direct jj equivalent"),
+                );
+                continue;
+            };
+            migration.translate(
+                git_key,
+                ConfigNamePathBuf::from_iter(["aliases", alias]),
+                toml_edit::Array::from_iter(args),
+            );
+        }
+    }
+
+    migration
+}
+
+fn translate_signing(git_config: &gix::config::File, migration: &mut Migration) {
+    let format = git_config
+        .string("gpg.format")
+        .map(|value| value.to_string());
+    let key = git_config
+        .string("user.signingKey")
+        .map(|value| value.to_string());
+    let sign_commits = git_config
+        .boolean("commit.gpgSign")
+        .and_then(Result::ok)
+        .unwrap_or(false);
+    if key.is_none() && !sign_commits {
+        return;
+    }
+    let backend = match format.as_deref().unwrap_or("openpgp") {
+        "openpgp" => "gpg",
+        "x509" => "gpgsm",
+        "ssh" => "ssh",
+        format => {
+            migration.skip("gpg.format", format!("unknown signing format `{format}`"));
+            return;
+        }
+    };
+    migration.translate(
+        "gpg.format",
+        ConfigNamePathBuf::from_iter(["signing", "backend"]),
+        backend,
+    );
+    if let Some(key) = key {
+        migration.translate(
+            "user.signingKey",
+            ConfigNamePathBuf::from_iter(["signing", "key"]),
+            key,
+        );
+    }
+    if sign_commits {
+        migration.translate(
+            "commit.gpgSign",
+            ConfigNamePathBuf::from_iter(["signing", "behavior"]),
+            "own",
+        );
+    }
+}
+
+const DIFF_TOOL_VARIABLES: &[(&str, &str)] = &[("LOCAL", "$left"), ("REMOTE", "$right")];
+const MERGE_TOOL_VARIABLES: &[(&str, &str)] = &[
+    ("BASE", "$base"),
+    ("LOCAL", "$left"),
+    ("REMOTE", "$right"),
+    ("MERGED", "$output"),
+];
+
+/// Splits Git tool command into jj program and arguments, replacing Git's
+/// variables with jj's. Returns `None` if the command needs a shell.
+fn translate_tool_command(cmd: &str, vars: &[(&str, &str)]) -> Option<Vec<String>> {
+    if cmd.contains(['|', ';', '&', '<', '>', '(', ')', '`', '\\']) {
+        return None;
+    }
+    let args: Vec<String> = cmd
+        .split_whitespace()
+        .map(|arg| {
+            let arg = arg
+                .strip_prefix('"')
+                .and_then(|arg| arg.strip_suffix('"'))
+                .or_else(|| arg.strip_prefix('\'')?.strip_suffix('\''))
+                .unwrap_or(arg);
+            if arg.contains(['"', '\'']) {
+                return None;
+            }
+            let arg = vars.iter().fold(arg.to_owned(), |arg, (git_var, jj_var)| {
+                arg.replace(&format!("${{{git_var}}}"), jj_var)
+                    .replace(&format!("${git_var}"), jj_var)
+            });
+            // Any remaining variable isn't known to jj.
+            let unknown_var = arg
+                .match_indices('$')
+                .any(|(i, _)| !vars.iter().any(|(_, jj_var)| arg[i..].starts_with(jj_var)));
+            (!unknown_var).then_some(arg)
+        })
+        .collect::<Option<_>>()?;
+    (!args.is_empty()).then_some(args)
+}
+
+/// Returns jj command equivalent to the Git subcommand, if any.
+fn translate_git_subcommand(subcommand: &str) -> Option<Vec<&'static str>> {
+    let args: &[&str] = match subcommand {
+        "blame" => &["file", "annotate"],
+        "commit" => &["commit"],
+        "diff" => &["diff"],
+        "fetch" => &["git", "fetch"],
+        "log" => &["log"],
+        "push" => &["git", "push"],
+        "show" => &["show"],
+        "status" => &["status"],
+        _ => return None,
+    };
+    Some(args.to_vec())
+}
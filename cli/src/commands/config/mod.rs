@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod doctor;
 mod edit;
 mod get;
 mod list;
+#[cfg(feature = "git")]
+mod migrate;
 mod path;
 mod set;
 mod unset;
@@ -26,12 +29,18 @@ use jj_lib::config::ConfigFile;
 use jj_lib::config::ConfigSource;
 use tracing::instrument;
 
+use self::doctor::ConfigDoctorArgs;
+use self::doctor::cmd_config_doctor;
 use self::edit::ConfigEditArgs;
 use self::edit::cmd_config_edit;
 use self::get::ConfigGetArgs;
 use self::get::cmd_config_get;
 use self::list::ConfigListArgs;
 use self::list::cmd_config_list;
+#[cfg(feature = "git")]
+use self::migrate::ConfigMigrateArgs;
+#[cfg(feature = "git")]
+use self::migrate::cmd_config_migrate;
 use self::path::ConfigPathArgs;
 use self::path::cmd_config_path;
 use self::set::ConfigSetArgs;
@@ -150,12 +159,15 @@ impl ConfigLevelArgs {
 ///     https://jj-vcs.github.io/jj/latest/config/
 #[derive(clap::Subcommand, Clone, Debug)]
 pub(crate) enum ConfigCommand {
+    Doctor(ConfigDoctorArgs),
     #[command(visible_alias("e"))]
     Edit(ConfigEditArgs),
     #[command(visible_alias("g"))]
     Get(ConfigGetArgs),
     #[command(visible_alias("l"))]
     List(ConfigListArgs),
+    #[cfg(feature = "git")]
+    Migrate(ConfigMigrateArgs),
     #[command(visible_alias("p"))]
     Path(ConfigPathArgs),
     #[command(visible_alias("s"))]
@@ -171,9 +183,12 @@ pub(crate) fn cmd_config(
     subcommand: &ConfigCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        ConfigCommand::Doctor(args) => cmd_config_doctor(ui, command, args),
         ConfigCommand::Edit(args) => cmd_config_edit(ui, command, args),
         ConfigCommand::Get(args) => cmd_config_get(ui, command, args),
         ConfigCommand::List(args) => cmd_config_list(ui, command, args),
+        #[cfg(feature = "git")]
+        ConfigCommand::Migrate(args) => cmd_config_migrate(ui, command, args),
         ConfigCommand::Path(args) => cmd_config_path(ui, command, args),
         ConfigCommand::Set(args) => cmd_config_set(ui, command, args),
         ConfigCommand::Unset(args) => cmd_config_unset(ui, command, args),
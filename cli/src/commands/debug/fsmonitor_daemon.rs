@@ -0,0 +1,78 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Run the built-in filesystem monitor for the workspace
+///
+/// This is started in the background when `fsmonitor.backend = "builtin"`. If
+/// `fsmonitor.builtin.auto-snapshot` is enabled, it also snapshots the working
+/// copy whenever it has changed and then been left alone for a moment.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugFsmonitorDaemonArgs {}
+
+#[cfg(all(feature = "notify", unix))]
+pub fn cmd_debug_fsmonitor_daemon(
+    _ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &DebugFsmonitorDaemonArgs,
+) -> Result<(), CommandError> {
+    use std::env;
+    use std::process::Command;
+    use std::process::Stdio;
+
+    use jj_lib::fsmonitor::FsmonitorSettings;
+
+    let workspace_root = command.workspace_loader()?.workspace_root().to_owned();
+    let auto_snapshot: Option<Box<dyn Fn() + Send>> =
+        match FsmonitorSettings::from_settings(command.settings())? {
+            FsmonitorSettings::Builtin(config) if config.auto_snapshot => {
+                let jj_path = env::current_exe()?;
+                let workspace_root = workspace_root.clone();
+                Some(Box::new(move || {
+                    let result = Command::new(&jj_path)
+                        .arg("--repository")
+                        .arg(&workspace_root)
+                        .args(["debug", "snapshot"])
+                        .stdin(Stdio::null())
+                        .stdout(Stdio::null())
+                        .stderr(Stdio::null())
+                        .status();
+                    match result {
+                        Ok(status) if status.success() => {}
+                        Ok(status) => tracing::warn!(%status, "Automatic snapshot failed"),
+                        Err(err) => tracing::warn!(?err, "Failed to run automatic snapshot"),
+                    }
+                }))
+            }
+            _ => None,
+        };
+    jj_lib::fsmonitor::builtin::run_daemon(&workspace_root, auto_snapshot).map_err(user_error)?;
+    Ok(())
+}
+
+#[cfg(not(all(feature = "notify", unix)))]
+pub fn cmd_debug_fsmonitor_daemon(
+    _ui: &mut Ui,
+    _command: &CommandHelper,
+    _args: &DebugFsmonitorDaemonArgs,
+) -> Result<(), CommandError> {
+    Err(user_error(
+        "The built-in filesystem monitor requires a Unix platform and jj compiled with the \
+         `notify` feature",
+    ))
+}
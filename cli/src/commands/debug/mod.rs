@@ -14,6 +14,7 @@
 
 mod copy_detection;
 mod fileset;
+mod fsmonitor_daemon;
 mod index;
 mod index_changed_paths;
 mod init_simple;
@@ -35,6 +36,8 @@ use self::copy_detection::CopyDetectionArgs;
 use self::copy_detection::cmd_debug_copy_detection;
 use self::fileset::DebugFilesetArgs;
 use self::fileset::cmd_debug_fileset;
+use self::fsmonitor_daemon::DebugFsmonitorDaemonArgs;
+use self::fsmonitor_daemon::cmd_debug_fsmonitor_daemon;
 use self::index::DebugIndexArgs;
 use self::index::cmd_debug_index;
 use self::index_changed_paths::DebugIndexChangedPathsArgs;
@@ -70,6 +73,7 @@ use crate::ui::Ui;
 pub enum DebugCommand {
     CopyDetection(CopyDetectionArgs),
     Fileset(DebugFilesetArgs),
+    FsmonitorDaemon(DebugFsmonitorDaemonArgs),
     Index(DebugIndexArgs),
     IndexChangedPaths(DebugIndexChangedPathsArgs),
     InitSimple(DebugInitSimpleArgs),
@@ -94,6 +98,7 @@ pub fn cmd_debug(
     match subcommand {
         DebugCommand::CopyDetection(args) => cmd_debug_copy_detection(ui, command, args),
         DebugCommand::Fileset(args) => cmd_debug_fileset(ui, command, args),
+        DebugCommand::FsmonitorDaemon(args) => cmd_debug_fsmonitor_daemon(ui, command, args),
         DebugCommand::Index(args) => cmd_debug_index(ui, command, args),
         DebugCommand::IndexChangedPaths(args) => cmd_debug_index_changed_paths(ui, command, args),
         DebugCommand::InitSimple(args) => cmd_debug_init_simple(ui, command, args),
@@ -13,15 +13,20 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+use std::io;
 use std::io::Write as _;
 
+use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::default_index::RevsetProfileNode;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
 use jj_lib::revset;
 use jj_lib::revset::RevsetDiagnostics;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::print_parse_diagnostics;
+use crate::command_error::user_error;
 use crate::revset_util;
 use crate::ui::Ui;
 
@@ -35,6 +40,11 @@ pub struct DebugRevsetArgs {
     /// Do not rewrite expression to optimized form
     #[arg(long)]
     no_optimize: bool,
+    /// Print the evaluation plan with per-node row counts and timings
+    ///
+    /// The reported time of a node includes the time spent on its children.
+    #[arg(long, conflicts_with = "no_resolve")]
+    explain: bool,
 }
 
 pub fn cmd_debug_revset(
@@ -85,6 +95,21 @@ pub fn cmd_debug_revset(
     writeln!(ui.stdout(), "{backend_expression:#?}")?;
     writeln!(ui.stdout())?;
 
+    if args.explain {
+        let index = repo
+            .readonly_index()
+            .downcast_ref::<DefaultReadonlyIndex>()
+            .ok_or_else(|| user_error("--explain is only supported by the default index"))?;
+        let (revset, profile) =
+            index.evaluate_revset_with_profile(&backend_expression, repo.store())?;
+        for commit_id in revset.into_inner().iter() {
+            commit_id?;
+        }
+        writeln!(ui.stdout(), "-- Explain:")?;
+        print_profile(ui, &profile, 0)?;
+        writeln!(ui.stdout())?;
+    }
+
     let revset = expression.evaluate_unoptimized(repo)?;
     writeln!(ui.stdout(), "-- Evaluated:")?;
     writeln!(ui.stdout(), "{revset:#?}")?;
@@ -96,3 +121,20 @@ pub fn cmd_debug_revset(
     }
     Ok(())
 }
+
+fn print_profile(ui: &Ui, node: &RevsetProfileNode, depth: usize) -> io::Result<()> {
+    writeln!(
+        ui.stdout(),
+        "{:indent$}{label} (strategy={strategy}, rows={rows}, time={time:?})",
+        "",
+        indent = depth * 2,
+        label = node.label(),
+        strategy = node.strategy(),
+        rows = node.rows(),
+        time = node.time(),
+    )?;
+    for child in node.children() {
+        print_profile(ui, child, depth + 1)?;
+    }
+    Ok(())
+}
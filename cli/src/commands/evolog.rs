@@ -33,6 +33,7 @@ use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::graphlog::GraphStyle;
+use crate::graphlog::colored_lanes_from_settings;
 use crate::graphlog::get_graphlog;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
@@ -105,6 +106,7 @@ pub(crate) fn cmd_evolog(
 
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
     let graph_style = GraphStyle::from_settings(workspace_command.settings())?;
+    let colored_lanes = colored_lanes_from_settings(ui, workspace_command.settings())?;
     let with_content_format = LogContentFormat::new(ui, workspace_command.settings())?;
 
     let template: TemplateRenderer<CommitEvolutionEntry>;
@@ -138,7 +140,7 @@ pub(crate) fn cmd_evolog(
     let evolution_entries = walk_predecessors(repo, &start_commit_ids);
     if !args.no_graph {
         let mut raw_output = formatter.raw()?;
-        let mut graph = get_graphlog(graph_style, raw_output.as_mut());
+        let mut graph = get_graphlog(graph_style, colored_lanes, raw_output.as_mut());
 
         let evolution_nodes = evolution_entries.map_ok(|entry| {
             let ids = entry.predecessor_ids();
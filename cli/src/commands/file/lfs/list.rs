@@ -0,0 +1,46 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use tracing::instrument;
+
+use super::gitattributes_path;
+use super::lfs_pattern;
+use super::read_gitattributes;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List patterns of files stored in Git LFS
+///
+/// Only patterns in the `.gitattributes` file at the workspace root are
+/// listed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct FileLfsListArgs {}
+
+#[instrument(skip_all)]
+pub fn cmd_file_lfs_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &FileLfsListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let content = read_gitattributes(&gitattributes_path(workspace_command.workspace_root()))?;
+    let mut formatter = ui.stdout_formatter();
+    for pattern in content.lines().filter_map(lfs_pattern) {
+        writeln!(formatter, "{pattern}")?;
+    }
+    Ok(())
+}
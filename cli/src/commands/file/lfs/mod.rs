@@ -0,0 +1,82 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod list;
+mod track;
+mod untrack;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
+use crate::ui::Ui;
+
+/// Manage files stored in Git LFS
+///
+/// Files matching the tracked patterns are given the `filter=lfs` attribute in
+/// the `.gitattributes` file at the workspace root. If `git.lfs.enabled` is
+/// set, their content is moved to the Git LFS object store when snapshotting
+/// the working copy, and only small pointer files are committed.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum FileLfsCommand {
+    List(list::FileLfsListArgs),
+    Track(track::FileLfsTrackArgs),
+    Untrack(untrack::FileLfsUntrackArgs),
+}
+
+pub fn cmd_file_lfs(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &FileLfsCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        FileLfsCommand::List(args) => list::cmd_file_lfs_list(ui, command, args),
+        FileLfsCommand::Track(args) => track::cmd_file_lfs_track(ui, command, args),
+        FileLfsCommand::Untrack(args) => untrack::cmd_file_lfs_untrack(ui, command, args),
+    }
+}
+
+/// Attributes assigned to patterns tracked by `jj file lfs track`.
+const LFS_ATTRIBUTES: &str = "filter=lfs diff=lfs merge=lfs -text";
+
+fn gitattributes_path(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".gitattributes")
+}
+
+fn read_gitattributes(path: &Path) -> Result<String, CommandError> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(user_error_with_message(
+            format!("Failed to read {}", path.display()),
+            err,
+        )),
+    }
+}
+
+fn write_gitattributes(path: &Path, content: &str) -> Result<(), CommandError> {
+    fs::write(path, content)
+        .map_err(|err| user_error_with_message(format!("Failed to write {}", path.display()), err))
+}
+
+/// Returns the pattern of `line` if it assigns the `filter=lfs` attribute.
+fn lfs_pattern(line: &str) -> Option<&str> {
+    let mut words = line.split_ascii_whitespace();
+    let pattern = words.next().filter(|pattern| !pattern.starts_with('#'))?;
+    words.any(|word| word == "filter=lfs").then_some(pattern)
+}
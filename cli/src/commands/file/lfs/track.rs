@@ -0,0 +1,72 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use tracing::instrument;
+
+use super::LFS_ATTRIBUTES;
+use super::gitattributes_path;
+use super::lfs_pattern;
+use super::read_gitattributes;
+use super::write_gitattributes;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Start storing files matching patterns in Git LFS
+///
+/// The patterns use the `.gitattributes` syntax, and are added to the
+/// `.gitattributes` file at the workspace root. Files already committed are
+/// converted the next time they are modified.
+#[derive(clap::Args, Clone, Debug)]
+pub struct FileLfsTrackArgs {
+    /// Patterns of files to store in Git LFS (e.g. `*.psd`)
+    #[arg(required = true, value_name = "PATTERNS")]
+    patterns: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_file_lfs_track(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileLfsTrackArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let path = gitattributes_path(workspace_command.workspace_root());
+    let mut content = read_gitattributes(&path)?;
+    let tracked = content.lines().filter_map(lfs_pattern).collect::<Vec<_>>();
+    let new_patterns = args
+        .patterns
+        .iter()
+        .filter(|pattern| !tracked.contains(&pattern.as_str()))
+        .collect::<Vec<_>>();
+    if new_patterns.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for pattern in &new_patterns {
+        content.push_str(&format!("{pattern} {LFS_ATTRIBUTES}\n"));
+    }
+    write_gitattributes(&path, &content)?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        for pattern in new_patterns {
+            writeln!(formatter, "Tracking {pattern} in Git LFS")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use tracing::instrument;
+
+use super::gitattributes_path;
+use super::lfs_pattern;
+use super::read_gitattributes;
+use super::write_gitattributes;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Stop storing files matching patterns in Git LFS
+///
+/// The patterns are removed from the `.gitattributes` file at the workspace
+/// root. Files already committed as pointers are converted back the next time
+/// they are modified.
+#[derive(clap::Args, Clone, Debug)]
+pub struct FileLfsUntrackArgs {
+    /// Patterns previously passed to `jj file lfs track`
+    #[arg(required = true, value_name = "PATTERNS")]
+    patterns: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_file_lfs_untrack(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileLfsUntrackArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let path = gitattributes_path(workspace_command.workspace_root());
+    let content = read_gitattributes(&path)?;
+    let mut removed = vec![];
+    let new_content = content
+        .split_inclusive('\n')
+        .filter(|line| match lfs_pattern(line) {
+            Some(pattern) if args.patterns.iter().any(|p| p == pattern) => {
+                removed.push(pattern);
+                false
+            }
+            _ => true,
+        })
+        .join("");
+    for pattern in &args.patterns {
+        if !removed.contains(&pattern.as_str()) {
+            writeln!(ui.warning_default(), "{pattern} is not tracked in Git LFS")?;
+        }
+    }
+    if removed.is_empty() {
+        return Ok(());
+    }
+    write_gitattributes(&path, &new_content)?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        for pattern in removed.iter().dedup() {
+            writeln!(formatter, "Untracking {pattern} in Git LFS")?;
+        }
+    }
+    Ok(())
+}
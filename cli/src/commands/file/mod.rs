@@ -14,6 +14,8 @@
 
 mod annotate;
 mod chmod;
+#[cfg(feature = "git")]
+mod lfs;
 mod list;
 mod show;
 mod track;
@@ -28,6 +30,9 @@ use crate::ui::Ui;
 pub enum FileCommand {
     Annotate(annotate::FileAnnotateArgs),
     Chmod(chmod::FileChmodArgs),
+    #[cfg(feature = "git")]
+    #[command(subcommand)]
+    Lfs(lfs::FileLfsCommand),
     List(list::FileListArgs),
     Show(show::FileShowArgs),
     Track(track::FileTrackArgs),
@@ -42,6 +47,8 @@ pub fn cmd_file(
     match subcommand {
         FileCommand::Annotate(args) => annotate::cmd_file_annotate(ui, command, args),
         FileCommand::Chmod(args) => chmod::cmd_file_chmod(ui, command, args),
+        #[cfg(feature = "git")]
+        FileCommand::Lfs(subcommand) => lfs::cmd_file_lfs(ui, command, subcommand),
         FileCommand::List(args) => list::cmd_file_list(ui, command, args),
         FileCommand::Show(args) => show::cmd_file_show(ui, command, args),
         FileCommand::Track(args) => track::cmd_file_track(ui, command, args),
@@ -174,11 +174,14 @@ fn write_tree_entries(
                 copy_async_to_sync(file.reader, ui.stdout_formatter().as_mut()).block_on()?;
             }
             MaterializedTreeValue::FileConflict(file) => {
+                let env = workspace_command.env();
                 let options = ConflictMaterializeOptions {
-                    marker_style: workspace_command.env().conflict_marker_style(),
+                    marker_style: env.conflict_marker_style(),
+                    marker_style_overrides: env.conflict_marker_style_overrides().clone(),
                     marker_len: None,
                     merge: repo.store().merge_options().clone(),
-                };
+                }
+                .for_path(&entry.path);
                 materialize_merge_result(&file.contents, &mut ui.stdout_formatter(), &options)?;
             }
             MaterializedTreeValue::OtherConflict { id } => {
@@ -125,6 +125,10 @@ pub(crate) struct FixArgs {
     /// specified, all files in the repo will be fixed.
     #[arg(long)]
     include_unchanged_files: bool,
+
+    /// Show the changes that would be made, without making them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[instrument(skip_all)]
@@ -194,6 +198,9 @@ pub(crate) fn cmd_fix(
         summary.num_fixed_commits,
         summary.num_checked_commits
     )?;
+    if args.dry_run {
+        return tx.finish_dry_run(ui);
+    }
     tx.finish(ui, format!("fixed {} commits", summary.num_fixed_commits))
 }
 
@@ -0,0 +1,295 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathUiConverter;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_commit_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::DiffRenderer;
+use crate::diff_util::UnifiedDiffOptions;
+use crate::formatter::PlainTextFormatter;
+use crate::ui::Ui;
+
+/// Mail header that records the change ID of a patch.
+pub(crate) const CHANGE_ID_HEADER: &str = "X-Jj-Change-Id";
+
+/// Width of the diffstat in patches, which is what `git format-patch` uses.
+const STAT_WIDTH: usize = 72;
+
+/// Export revisions as patch emails
+///
+/// Each revision is written to a file in the mbox format of `git
+/// format-patch`, named after its position in the series and its subject, such
+/// as `0001-fix-the-frobnicator.patch`. The patches can be sent with `git
+/// send-email`, and applied with `jj apply-patch` or `git am`.
+///
+/// The change ID of each revision is recorded in the `X-Jj-Change-Id` header
+/// of its patch, which `jj apply-patch` uses to keep the change ID.
+///
+/// Merge commits can't be exported as patches.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FormatPatchArgs {
+    /// The revisions to export (default: @)
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// Directory to write the patch files to (default: current directory)
+    #[arg(long, short, value_hint = clap::ValueHint::DirPath, conflicts_with = "stdout")]
+    output_dir: Option<PathBuf>,
+
+    /// Write all patches to the standard output as a single mbox
+    #[arg(long)]
+    stdout: bool,
+
+    /// The prefix of the subject lines, which are formatted like `[PATCH 1/2]`
+    #[arg(long, default_value = "PATCH")]
+    subject_prefix: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_format_patch(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FormatPatchArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let revision_args = if args.revisions.is_empty() {
+        &[RevisionArg::AT][..]
+    } else {
+        &args.revisions
+    };
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, revision_args)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let commits = commits.into_iter().rev().collect_vec();
+    let repo = workspace_command.repo();
+    for commit in &commits {
+        if commit.id() == repo.store().root_commit_id() {
+            return Err(user_error("Cannot export the root commit as a patch"));
+        }
+        if commit.parent_ids().len() > 1 {
+            return Err(user_error(format!(
+                "Cannot export merge commit {} as a patch",
+                short_commit_hash(commit.id())
+            )));
+        }
+    }
+    if commits.is_empty() {
+        writeln!(ui.status(), "No revisions to export.")?;
+        return Ok(());
+    }
+
+    // Paths in the diffstat are relative to the workspace root like the paths
+    // in the diff
+    let root = workspace_command.workspace_root().to_owned();
+    let path_converter = RepoPathUiConverter::Fs {
+        cwd: root.clone(),
+        base: root,
+    };
+    let new_renderer = |format| {
+        DiffRenderer::new(
+            repo.as_ref(),
+            &path_converter,
+            workspace_command.env().conflict_marker_style(),
+            workspace_command.env().conflict_marker_style_overrides(),
+            vec![format],
+        )
+    };
+    let stat_renderer = new_renderer(DiffFormat::Stat(Box::default()));
+    let diff_renderer = new_renderer(DiffFormat::Git(Box::new(
+        UnifiedDiffOptions::from_settings(command.settings())?,
+    )));
+
+    let output_dir = args
+        .output_dir
+        .as_ref()
+        .map_or_else(|| command.cwd().to_owned(), |dir| command.cwd().join(dir));
+    if !args.stdout {
+        std::fs::create_dir_all(&output_dir).map_err(|err| {
+            user_error_with_message(format!("Failed to create {}", output_dir.display()), err)
+        })?;
+    }
+    for (index, commit) in commits.iter().enumerate() {
+        let subject_prefix = if commits.len() > 1 {
+            format!("{} {}/{}", args.subject_prefix, index + 1, commits.len())
+        } else {
+            args.subject_prefix.clone()
+        };
+        let mut patch = vec![];
+        write_patch(
+            ui,
+            &mut patch,
+            &stat_renderer,
+            &diff_renderer,
+            commit,
+            &subject_prefix,
+        )?;
+        if args.stdout {
+            ui.stdout().write_all(&patch)?;
+        } else {
+            let file_name = patch_file_name(index + 1, commit.description());
+            let path = output_dir.join(&file_name);
+            std::fs::write(&path, &patch).map_err(|err| {
+                user_error_with_message(format!("Failed to write {}", path.display()), err)
+            })?;
+            writeln!(ui.stdout(), "{}", ui_path(command, &path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes `commit` as a patch email to `output`.
+fn write_patch(
+    ui: &Ui,
+    output: &mut Vec<u8>,
+    stat_renderer: &DiffRenderer,
+    diff_renderer: &DiffRenderer,
+    commit: &Commit,
+    subject_prefix: &str,
+) -> Result<(), CommandError> {
+    let author = commit.author();
+    let date = author
+        .timestamp
+        .to_datetime()
+        .map_err(|_| user_error("Author timestamp of the commit is out of range"))?;
+    let (subject, body) = split_description(commit.description());
+    writeln!(
+        output,
+        "From {} Mon Sep 17 00:00:00 2001",
+        commit.id().hex()
+    )?;
+    writeln!(
+        output,
+        "From: {} <{}>",
+        encode_header_value(&author.name),
+        author.email
+    )?;
+    writeln!(output, "Date: {}", date.to_rfc2822())?;
+    writeln!(
+        output,
+        "Subject: {}",
+        encode_header_value(&format!("[{subject_prefix}] {subject}"))
+    )?;
+    writeln!(
+        output,
+        "{CHANGE_ID_HEADER}: {}",
+        commit.change_id().reverse_hex()
+    )?;
+    writeln!(output, "MIME-Version: 1.0")?;
+    writeln!(output, "Content-Type: text/plain; charset=UTF-8")?;
+    writeln!(output, "Content-Transfer-Encoding: 8bit")?;
+    writeln!(output)?;
+    if !body.is_empty() {
+        writeln!(output, "{body}")?;
+    }
+    writeln!(output, "---")?;
+    let mut stat = vec![];
+    stat_renderer
+        .show_patch(
+            ui,
+            &mut PlainTextFormatter::new(&mut stat),
+            commit,
+            &EverythingMatcher,
+            STAT_WIDTH - 1,
+        )
+        .block_on()?;
+    // The diffstat is indented by one space like `git format-patch` does.
+    for line in stat.split_inclusive(|&b| b == b'\n') {
+        output.push(b' ');
+        output.extend_from_slice(line);
+    }
+    diff_renderer
+        .show_patch(
+            ui,
+            &mut PlainTextFormatter::new(&mut *output),
+            commit,
+            &EverythingMatcher,
+            STAT_WIDTH,
+        )
+        .block_on()?;
+    writeln!(output)?;
+    Ok(())
+}
+
+/// Splits a description into its first line and the rest.
+fn split_description(description: &str) -> (&str, &str) {
+    let (subject, body) = description.split_once('\n').unwrap_or((description, ""));
+    (subject.trim(), body.trim())
+}
+
+/// Encodes a header value as an RFC 2047 encoded word if it isn't ASCII.
+fn encode_header_value(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_owned();
+    }
+    let mut encoded = "=?UTF-8?q?".to_owned();
+    for byte in value.bytes() {
+        match byte {
+            b' ' => encoded.push('_'),
+            b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'!' | b'*' | b'+' | b'-' | b'/' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("={byte:02X}")),
+        }
+    }
+    encoded.push_str("?=");
+    encoded
+}
+
+/// Returns a file name like `0001-fix-the-frobnicator.patch`, which is how
+/// `git format-patch` names patches.
+fn patch_file_name(number: usize, description: &str) -> String {
+    const MAX_SLUG_LEN: usize = 52;
+    let (subject, _) = split_description(description);
+    let slug = subject
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+        .filter(|word| !word.is_empty())
+        .join("-");
+    let slug = slug[..slug.len().min(MAX_SLUG_LEN)].trim_end_matches(['-', '.']);
+    if slug.is_empty() {
+        format!("{number:04}.patch")
+    } else {
+        format!("{number:04}-{slug}.patch")
+    }
+}
+
+fn ui_path(command: &CommandHelper, path: &std::path::Path) -> String {
+    path.strip_prefix(command.cwd())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
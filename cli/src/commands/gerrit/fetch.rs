@@ -0,0 +1,156 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use jj_lib::git;
+use jj_lib::git::GitSettings;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::repo::Repo as _;
+use jj_lib::trailer::parse_description_trailers;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::short_change_hash;
+use crate::command_error::CommandError;
+use crate::command_error::cli_error;
+use crate::command_error::user_error;
+use crate::commands::gerrit::upload::calculate_push_remote;
+use crate::complete;
+use crate::git_util::with_remote_git_callbacks;
+use crate::ui::Ui;
+
+/// Download a change from Gerrit for review.
+///
+/// The patchset is fetched into the repo as a new revision, without moving
+/// any bookmarks. Its description keeps the `Change-Id` footer, so uploading
+/// the revision (or a rewrite of it) with `jj gerrit upload` updates the same
+/// Gerrit change.
+///
+/// If the patchset was uploaded with jj, it keeps the jj change ID it was
+/// uploaded with.
+#[derive(clap::Args, Clone, Debug)]
+pub struct FetchArgs {
+    /// The change number, optionally followed by `/` and a patchset number
+    ///
+    /// The latest patchset is fetched if the patchset number is omitted.
+    #[arg(value_name = "CHANGE[/PATCHSET]")]
+    change: String,
+
+    /// The Gerrit remote to fetch from
+    ///
+    /// Can be configured with the `gerrit.default-remote` repository option as
+    /// well.
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: Option<String>,
+}
+
+pub fn cmd_gerrit_fetch(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FetchArgs,
+) -> Result<(), CommandError> {
+    let (change, patchset) = parse_change_arg(&args.change)?;
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let store = workspace_command.repo().store().clone();
+    let remote = calculate_push_remote(&store, command.settings(), args.remote.as_deref())?;
+    let remote = RemoteName::new(&remote);
+    let git_settings = GitSettings::from_settings(command.settings())?;
+
+    // Changes are stored as refs/changes/<last two digits>/<change>/<patchset>
+    let change_ref_prefix = format!("refs/changes/{:02}/{change}/", change % 100);
+    let pattern = match patchset {
+        Some(patchset) => format!("{change_ref_prefix}{patchset}"),
+        None => format!("{change_ref_prefix}*"),
+    };
+    let patchset_refs = git::list_remote_refs(&store, &git_settings, remote, &[&pattern])?;
+    let Some((patchset, (ref_name, commit_id))) = patchset_refs
+        .into_iter()
+        .filter_map(|(ref_name, commit_id)| {
+            // Skip refs/changes/<nn>/<change>/meta, which holds review metadata
+            let patchset: u32 = ref_name
+                .as_str()
+                .strip_prefix(&change_ref_prefix)?
+                .parse()
+                .ok()?;
+            Some((patchset, (ref_name, commit_id)))
+        })
+        .max_by_key(|(patchset, _)| *patchset)
+    else {
+        return Err(user_error(match patchset {
+            Some(patchset) => format!(
+                "Change {change} has no patchset {patchset} on remote {}",
+                remote.as_symbol()
+            ),
+            None => format!("Change {change} not found on remote {}", remote.as_symbol()),
+        }));
+    };
+
+    let mut tx = workspace_command.start_transaction();
+    let commits = with_remote_git_callbacks(ui, |callbacks| {
+        git::fetch_commits(
+            tx.repo_mut(),
+            &git_settings,
+            remote,
+            &[(ref_name, commit_id)],
+            callbacks,
+        )
+    })?;
+    let commit = &commits[0];
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(
+            formatter,
+            "Fetched patchset {patchset} of change {change}: "
+        )?;
+        tx.write_commit_summary(formatter.as_mut(), commit)?;
+        writeln!(formatter)?;
+        let has_change_id = parse_description_trailers(commit.description())
+            .iter()
+            .any(|trailer| trailer.key == "Change-Id");
+        if !has_change_id {
+            writeln!(
+                ui.warning_default(),
+                "The fetched revision has no Change-Id footer"
+            )?;
+        }
+        writeln!(
+            ui.hint_default(),
+            "Run `jj new {}` to work on top of it.",
+            short_change_hash(commit.change_id())
+        )?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "fetch gerrit change {change} patchset {patchset} from git remote {}",
+            remote.as_symbol()
+        ),
+    )?;
+    Ok(())
+}
+
+fn parse_change_arg(arg: &str) -> Result<(u64, Option<u32>), CommandError> {
+    let invalid = || cli_error(format!("Invalid change number: {arg}"));
+    let (change, patchset) = match arg.split_once('/') {
+        Some((change, patchset)) => (change, Some(patchset)),
+        None => (arg, None),
+    };
+    let change = change.parse().map_err(|_| invalid())?;
+    let patchset = patchset
+        .map(|patchset| patchset.parse())
+        .transpose()
+        .map_err(|_| invalid())?;
+    Ok((change, patchset))
+}
@@ -24,6 +24,7 @@ use crate::ui::Ui;
 /// Interact with Gerrit Code Review.
 #[derive(Subcommand, Clone, Debug)]
 pub enum GerritCommand {
+    Fetch(gerrit::fetch::FetchArgs),
     Upload(gerrit::upload::UploadArgs),
 }
 
@@ -33,8 +34,10 @@ pub fn cmd_gerrit(
     subcommand: &GerritCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        GerritCommand::Fetch(args) => gerrit::fetch::cmd_gerrit_fetch(ui, command, args),
         GerritCommand::Upload(review) => gerrit::upload::cmd_gerrit_upload(ui, command, review),
     }
 }
 
+mod fetch;
 mod upload;
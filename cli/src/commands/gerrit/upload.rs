@@ -23,6 +23,7 @@ use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::git;
+use jj_lib::git::GitPushOptions;
 use jj_lib::git::GitRefUpdate;
 use jj_lib::git::GitSettings;
 use jj_lib::object_id::ObjectId as _;
@@ -86,12 +87,63 @@ pub struct UploadArgs {
     #[arg(long)]
     remote: Option<String>,
 
+    /// Set the topic of the uploaded changes
+    #[arg(long)]
+    topic: Option<String>,
+
+    /// Add a reviewer to the uploaded changes
+    ///
+    /// This can be an email address or a Gerrit username, and can be repeated
+    /// to add several reviewers.
+    #[arg(long, value_name = "REVIEWER")]
+    reviewer: Vec<String>,
+
+    /// Add a hashtag to the uploaded changes
+    ///
+    /// This can be repeated to add several hashtags.
+    #[arg(long, value_name = "HASHTAG")]
+    hashtag: Vec<String>,
+
+    /// Mark the uploaded changes as work in progress
+    #[arg(long, conflicts_with = "ready")]
+    wip: bool,
+
+    /// Mark the uploaded changes as ready for review
+    ///
+    /// This removes the work-in-progress state of existing changes.
+    #[arg(long)]
+    ready: bool,
+
     /// Do not actually push the changes to Gerrit
     #[arg(long = "dry-run", short = 'n')]
     dry_run: bool,
 }
 
-fn calculate_push_remote(
+impl UploadArgs {
+    /// Gerrit options for the uploaded changes, which are sent as push
+    /// options.
+    fn push_options(&self) -> Vec<String> {
+        let mut options = vec![];
+        if let Some(topic) = &self.topic {
+            options.push(format!("topic={topic}"));
+        }
+        options.extend(self.reviewer.iter().map(|reviewer| format!("r={reviewer}")));
+        options.extend(
+            self.hashtag
+                .iter()
+                .map(|hashtag| format!("hashtag={hashtag}")),
+        );
+        if self.wip {
+            options.push("wip".to_owned());
+        }
+        if self.ready {
+            options.push("ready".to_owned());
+        }
+        options
+    }
+}
+
+pub(super) fn calculate_push_remote(
     store: &Arc<Store>,
     settings: &UserSettings,
     remote: Option<&str>,
@@ -326,6 +378,10 @@ pub fn cmd_gerrit_upload(
 
     writeln!(ui.stderr())?;
 
+    let push_options = GitPushOptions {
+        remote_push_options: args.push_options(),
+    };
+
     // NOTE (aseipp): because we are pushing everything to the same remote ref,
     // we have to loop and push each commit one at a time, even though
     // push_updates in theory supports multiple GitRefUpdates at once, because
@@ -369,6 +425,7 @@ pub fn cmd_gerrit_upload(
                     new_target: Some(new_commit.id().clone()),
                 }],
                 cb,
+                &push_options,
             )
         })
         // Despite the fact that a manual git push will error out with 'no new
@@ -0,0 +1,134 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use itertools::Itertools as _;
+use jj_lib::patch::FilePatch;
+use jj_lib::patch::apply_patch_to_tree;
+use jj_lib::patch::parse_patch;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::diff_util::describe_patch_rejection;
+use crate::ui::Ui;
+
+/// Apply a diff to the working-copy commit
+///
+/// Reads a diff in the unified format, as produced by `jj diff --git`, `git
+/// diff`, or `diff -u`, and applies it to the working-copy commit. Text around
+/// the diff, such as a commit message or a review comment, is ignored.
+///
+/// Like `git apply`, nothing is changed if any hunk doesn't apply, unless
+/// `--reject` is given.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitApplyArgs {
+    /// Files containing the diff, or `-` to read from the standard input
+    /// (default: -)
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    paths: Vec<PathBuf>,
+
+    /// Apply the diff in reverse, undoing its changes
+    #[arg(long)]
+    reverse: bool,
+
+    /// Remove this many leading components from the paths in the diff
+    ///
+    /// Paths in diffs produced by Git start with `a/` and `b/`, which are
+    /// removed with the default value.
+    #[arg(short = 'p', value_name = "N", default_value_t = 1)]
+    strip: usize,
+
+    /// Apply the hunks that match, and report the others
+    #[arg(long)]
+    reject: bool,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_git_apply(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitApplyArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut file_patches: Vec<FilePatch> = vec![];
+    let stdin_path = [PathBuf::from("-")];
+    let paths = if args.paths.is_empty() {
+        &stdin_path[..]
+    } else {
+        &args.paths
+    };
+    for path in paths {
+        let content = if path.as_os_str() == "-" {
+            let mut content = vec![];
+            std::io::stdin()
+                .read_to_end(&mut content)
+                .map_err(|err| user_error_with_message("Failed to read standard input", err))?;
+            content
+        } else {
+            std::fs::read(command.cwd().join(path)).map_err(|err| {
+                user_error_with_message(format!("Failed to read {}", path.display()), err)
+            })?
+        };
+        let patches = parse_patch(&content, args.strip).map_err(|err| {
+            user_error_with_message(format!("Failed to parse {}", path.display()), err)
+        })?;
+        file_patches.extend(patches);
+    }
+    if args.reverse {
+        file_patches = file_patches.iter().map(FilePatch::reversed).collect();
+    }
+    if file_patches.is_empty() {
+        return Err(user_error("No diff found in the input"));
+    }
+
+    let wc_commit = workspace_command.resolve_single_rev(ui, &RevisionArg::AT)?;
+    workspace_command.check_rewritable([wc_commit.id()])?;
+    let application = apply_patch_to_tree(&wc_commit.tree(), &file_patches).block_on()?;
+    let rejections = application
+        .rejections
+        .iter()
+        .map(|rejection| {
+            let path = workspace_command.format_file_path(&rejection.path);
+            format!("  {path}: {}", describe_patch_rejection(&rejection.reason))
+        })
+        .collect_vec();
+    if !rejections.is_empty() {
+        if !args.reject {
+            return Err(user_error(format!(
+                "Failed to apply the diff:\n{}",
+                rejections.join("\n")
+            )));
+        }
+        writeln!(ui.warning_default(), "Some changes could not be applied:")?;
+        for rejection in &rejections {
+            writeln!(ui.warning_no_heading(), "{rejection}")?;
+        }
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    tx.repo_mut()
+        .rewrite_commit(&wc_commit)
+        .set_tree(application.tree)
+        .write()?;
+    tx.finish(ui, "apply diff to working copy")?;
+    Ok(())
+}
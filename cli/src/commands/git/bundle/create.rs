@@ -0,0 +1,137 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::git;
+use jj_lib::git::GitSettings;
+use jj_lib::revset::RevsetExpression;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_commit_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::complete;
+use crate::git_util::print_git_export_stats;
+use crate::ui::Ui;
+
+/// Create a bundle file from the given revisions
+///
+/// The bundle contains the local bookmarks pointing to the revisions, along
+/// with the history of the revisions. Ancestors of the revisions which aren't
+/// in the set are omitted; a repo fetching from the bundle must already
+/// contain them.
+///
+/// Each head of the revisions must be pointed to by a bookmark.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitBundleCreateArgs {
+    /// Path of the bundle file to create
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    path: PathBuf,
+    /// Revisions to include in the bundle
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        required = true,
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+pub fn cmd_git_bundle_create(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitBundleCreateArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    // The bundle records the refs in the underlying Git repo, which aren't
+    // kept up to date unless the workspace is colocated.
+    let mut tx = workspace_command.start_transaction();
+    let stats = git::export_refs(tx.repo_mut())?;
+    if tx.repo().has_changes() {
+        tx.finish(ui, "export git refs")?;
+    }
+    print_git_export_stats(ui, &stats)?;
+
+    let repo = workspace_command.repo();
+    let expression = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .expression()
+        .clone();
+    let commit_ids: HashSet<CommitId> = workspace_command
+        .attach_revset_evaluator(expression.clone())
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+    if commit_ids.is_empty() {
+        return Err(user_error("No revisions to bundle"));
+    }
+
+    let bookmarks = repo
+        .view()
+        .local_bookmarks()
+        .filter_map(|(name, target)| Some((name, target.as_normal()?)))
+        .filter(|(_, commit_id)| commit_ids.contains(*commit_id))
+        .map(|(name, commit_id)| (name.to_owned(), commit_id.clone()))
+        .collect_vec();
+    let head_ids: Vec<CommitId> = workspace_command
+        .attach_revset_evaluator(expression.heads())
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+    for head_id in &head_ids {
+        if !bookmarks.iter().any(|(_, commit_id)| commit_id == head_id) {
+            return Err(user_error_with_hint(
+                format!(
+                    "Revision {} is not pointed to by a bookmark",
+                    short_commit_hash(head_id)
+                ),
+                "Use `jj bookmark create` to name the revisions to bundle.",
+            ));
+        }
+    }
+
+    let prerequisites: Vec<CommitId> = workspace_command
+        .attach_revset_evaluator(
+            expression
+                .parents()
+                .minus(&expression)
+                .minus(&RevsetExpression::root()),
+        )
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+
+    let git_settings = GitSettings::from_settings(workspace_command.settings())?;
+    let path = command.cwd().join(&args.path);
+    git::create_bundle(
+        repo.as_ref(),
+        &git_settings,
+        &path,
+        &bookmarks,
+        &prerequisites,
+    )?;
+    writeln!(
+        ui.status(),
+        "Created bundle {} with {} bookmarks",
+        args.path.display(),
+        bookmarks.len()
+    )?;
+    Ok(())
+}
@@ -0,0 +1,44 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod create;
+
+use clap::Subcommand;
+
+use self::create::GitBundleCreateArgs;
+use self::create::cmd_git_bundle_create;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage Git bundle files
+///
+/// A bundle is a single file containing commits and bookmarks, which can be
+/// used to transfer them without network access. To fetch from a bundle, use
+/// its path as the URL of a remote, e.g. `jj git clone repo.bundle` or `jj git
+/// remote add backup repo.bundle` followed by `jj git fetch --remote backup`.
+#[derive(Subcommand, Clone, Debug)]
+pub enum GitBundleCommand {
+    Create(GitBundleCreateArgs),
+}
+
+pub fn cmd_git_bundle(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &GitBundleCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        GitBundleCommand::Create(args) => cmd_git_bundle_create(ui, command, args),
+    }
+}
@@ -19,6 +19,7 @@ use std::num::NonZeroU32;
 use std::path::Path;
 
 use jj_lib::git;
+use jj_lib::git::FetchDepth;
 use jj_lib::git::FetchTagsOverride;
 use jj_lib::git::GitFetch;
 use jj_lib::git::GitSettings;
@@ -40,6 +41,7 @@ use crate::command_error::user_error_with_message;
 use crate::commands::git::FetchTagsMode;
 use crate::commands::git::maybe_add_gitignore;
 use crate::git_util::absolute_git_url;
+use crate::git_util::maybe_write_commit_graph;
 use crate::git_util::print_git_import_stats;
 use crate::git_util::with_remote_git_callbacks;
 use crate::ui::Ui;
@@ -92,6 +94,19 @@ pub struct GitCloneArgs {
     /// Create a shallow clone of the given depth
     #[arg(long)]
     depth: Option<NonZeroU32>,
+    /// Create a shallow clone of commits newer than the given date
+    ///
+    /// The date can be in any format accepted by `git fetch --shallow-since`,
+    /// e.g. `2024-01-01` or `"2 weeks ago"`.
+    #[arg(long, value_name = "DATE", conflicts_with = "depth")]
+    shallow_since: Option<String>,
+    /// Create a partial clone omitting the objects excluded by the filter
+    ///
+    /// For example, `--filter=blob:none` omits all file contents, which are
+    /// fetched from the remote when they are needed. See the `--filter` option
+    /// of `git rev-list` for the filter syntax.
+    #[arg(long, value_name = "FILTER_SPEC")]
+    filter: Option<String>,
     /// Configure when to fetch tags
     ///
     /// Unless otherwise specified, the initial clone will fetch all tags,
@@ -118,7 +133,10 @@ pub struct GitCloneArgs {
 }
 
 fn clone_destination_for_source(source: &str) -> Option<&str> {
-    let destination = source.strip_suffix(".git").unwrap_or(source);
+    let destination = source
+        .strip_suffix(".git")
+        .or_else(|| source.strip_suffix(".bundle"))
+        .unwrap_or(source);
     let destination = destination.strip_suffix('/').unwrap_or(destination);
     destination
         .rsplit_once(&['/', '\\', ':'][..])
@@ -185,12 +203,21 @@ pub fn cmd_git_clone(
             args.fetch_tags.unwrap_or(FetchTagsMode::Included),
             args.branch.as_deref(),
         )?;
+        if let Some(filter) = &args.filter {
+            git::set_partial_clone_filter(workspace_command.repo().store(), remote_name, filter)?;
+            workspace_command = reload_workspace(ui, command, &workspace_command)?;
+        }
+        let depth = match (args.depth, &args.shallow_since) {
+            (Some(depth), _) => FetchDepth::Depth(depth),
+            (None, Some(date)) => FetchDepth::ShallowSince(date.clone()),
+            (None, None) => FetchDepth::Full,
+        };
         let default_branch = fetch_new_remote(
             ui,
             &mut workspace_command,
             remote_name,
             args.branch.as_deref(),
-            args.depth,
+            &depth,
             args.fetch_tags,
         )?;
         Ok((workspace_command, default_branch))
@@ -287,6 +314,14 @@ fn configure_remote(
     tx.finish(ui, format!("add git remote {}", remote_name.as_symbol()))?;
     // Reload workspace to apply new remote configuration to
     // gix::ThreadSafeRepository behind the store.
+    reload_workspace(ui, command, &workspace_command)
+}
+
+fn reload_workspace(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<WorkspaceCommandHelper, CommandError> {
     let workspace = command.load_workspace_at(
         workspace_command.workspace_root(),
         workspace_command.settings(),
@@ -303,7 +338,7 @@ fn fetch_new_remote(
     workspace_command: &mut WorkspaceCommandHelper,
     remote_name: &RemoteName,
     target_branches: Option<&[StringPattern]>,
-    depth: Option<NonZeroU32>,
+    depth: &FetchDepth,
     fetch_tags: Option<FetchTagsMode>,
 ) -> Result<(Option<RefNameBuf>, bool), CommandError> {
     writeln!(
@@ -388,6 +423,7 @@ fn fetch_new_remote(
         }
     }
     print_git_import_stats(ui, tx.repo(), &import_stats, true)?;
+    maybe_write_commit_graph(ui, tx.repo().store(), &git_settings)?;
     if git_settings.auto_local_bookmark && !should_track_default {
         writeln!(
             ui.hint_default(),
@@ -13,32 +13,44 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::io::Write as _;
+use std::iter;
+use std::num::NonZeroU32;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::default_index::DefaultIndexStore;
 use jj_lib::git;
+use jj_lib::git::ExpandedFetchRefSpecs;
+use jj_lib::git::FetchDepth;
+use jj_lib::git::FetchTagsOverride;
 use jj_lib::git::GitFetch;
 use jj_lib::git::GitSettings;
-use jj_lib::git::IgnoredRefspec;
-use jj_lib::git::IgnoredRefspecs;
 use jj_lib::git::expand_default_fetch_refspecs;
 use jj_lib::git::expand_fetch_refspecs;
 use jj_lib::git::get_git_backend;
 use jj_lib::ref_name::RemoteName;
 use jj_lib::repo::Repo as _;
+use jj_lib::settings::UserSettings;
 use jj_lib::str_util::StringMatcher;
 use jj_lib::str_util::StringPattern;
+use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::CommandError;
 use crate::command_error::config_error;
+use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::commands::git::get_single_remote;
+use crate::commands::git::warn_ignored_refspecs;
 use crate::complete;
+use crate::git_util::maybe_write_commit_graph;
 use crate::git_util::print_git_import_stats;
+use crate::git_util::transfer_git_lfs_objects;
 use crate::git_util::with_remote_git_callbacks;
 use crate::ui::Ui;
 
@@ -89,6 +101,13 @@ pub struct GitFetchArgs {
     /// Fetch from all remotes
     #[arg(long, conflicts_with = "remotes")]
     all_remotes: bool,
+    /// Fetch the given number of additional commits beyond the shallow
+    /// boundary of a shallow clone
+    ///
+    /// The commit index is rebuilt afterwards so that commits previously
+    /// shown as having no parents are connected to their fetched ancestors.
+    #[arg(long, value_name = "N")]
+    deepen: Option<NonZeroU32>,
 }
 
 #[tracing::instrument(skip_all)]
@@ -143,46 +162,126 @@ pub fn cmd_git_fetch(
                 .filter(|(_, targets)| targets.remote_ref.is_tracked())
                 .map(|(name, _)| StringPattern::exact(name))
                 .collect_vec();
-            expansions.push((remote, expand_fetch_refspecs(remote, tracked_branches)?));
+            expansions.push((*remote, expand_fetch_refspecs(remote, tracked_branches)?));
         }
     } else if args.branch.is_empty() {
         let git_repo = get_git_backend(tx.repo_mut().store())?.git_repo();
         for remote in &remotes {
             let (ignored, expanded) = expand_default_fetch_refspecs(remote, &git_repo)?;
             warn_ignored_refspecs(ui, remote, ignored)?;
-            expansions.push((remote, expanded));
+            expansions.push((*remote, expanded));
         }
     } else {
         for remote in &remotes {
             let expanded = expand_fetch_refspecs(remote, args.branch.clone())?;
-            expansions.push((remote, expanded));
+            expansions.push((*remote, expanded));
         }
     };
 
+    let depth = match args.deepen {
+        Some(n) => FetchDepth::Deepen(n),
+        None => FetchDepth::Full,
+    };
+    fetch_and_import(ui, &mut tx, expansions, &depth)?;
+    warn_if_branches_not_found(ui, &tx, &args.branch, &remotes)?;
+    tx.finish(
+        ui,
+        format!(
+            "fetch from git remote(s) {}",
+            remotes.iter().map(|n| n.as_symbol()).join(",")
+        ),
+    )?;
+    if args.deepen.is_some() {
+        reindex_deepened_repo(ui, command)?;
+    }
+    Ok(())
+}
+
+/// Fetches the expanded refspecs from each remote, and imports the fetched
+/// refs.
+pub(crate) fn fetch_and_import(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    expansions: Vec<(&RemoteName, ExpandedFetchRefSpecs)>,
+    depth: &FetchDepth,
+) -> Result<(), CommandError> {
     let git_settings = GitSettings::from_settings(tx.settings())?;
+    let remotes = expansions.iter().map(|(remote, _)| *remote).collect_vec();
+    let fetch_tags: Vec<_> = remotes
+        .iter()
+        .map(|remote| fetch_tags_override(tx.settings(), remote))
+        .try_collect()?;
     let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
-
-    for (remote, expanded) in expansions {
+    for ((remote, expanded), fetch_tags) in iter::zip(expansions, fetch_tags) {
         with_remote_git_callbacks(ui, |callbacks| {
-            git_fetch.fetch(remote, expanded, callbacks, None, None)
+            git_fetch.fetch(remote, expanded, callbacks, depth, fetch_tags)
         })?;
     }
 
     let import_stats = git_fetch.import_refs()?;
     print_git_import_stats(ui, tx.repo(), &import_stats, true)?;
-    warn_if_branches_not_found(ui, &tx, &args.branch, &remotes)?;
-    tx.finish(
-        ui,
-        format!(
-            "fetch from git remote(s) {}",
-            remotes.iter().map(|n| n.as_symbol()).join(",")
-        ),
+    maybe_write_commit_graph(ui, tx.repo().store(), &git_settings)?;
+    for remote in remotes {
+        let fetched_commits = import_stats
+            .changed_remote_bookmarks
+            .iter()
+            .filter(|(symbol, _)| symbol.remote == remote)
+            .flat_map(|(_, (_, new_target))| new_target.added_ids().cloned());
+        transfer_git_lfs_objects(
+            tx.repo().store(),
+            tx.settings(),
+            "fetch",
+            remote,
+            fetched_commits,
+        )?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the commit index after the shallow boundary moved. The index
+/// recorded the former shallow commits as having no parents.
+fn reindex_deepened_repo(ui: &Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    // Load the repo again so the backend picks up the new shallow boundary.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    let index_store = repo_loader.index_store();
+    let Some(default_index_store) = index_store.downcast_ref::<DefaultIndexStore>() else {
+        return Ok(());
+    };
+    default_index_store.reinit().map_err(internal_error)?;
+    let index = default_index_store
+        .build_index_at_operation(&op, repo_loader.store())
+        .block_on()
+        .map_err(internal_error)?;
+    writeln!(
+        ui.status(),
+        "Reindexed {} commits after deepening the history.",
+        index.num_commits()
     )?;
     Ok(())
 }
 
 const DEFAULT_REMOTE: &RemoteName = RemoteName::new("origin");
 
+/// Returns whether to fetch all tags or no tags from `remote` as configured by
+/// `remotes.<name>.fetch-tags`, or `None` to follow the remote's `tagOpt`.
+fn fetch_tags_override(
+    settings: &UserSettings,
+    remote: &RemoteName,
+) -> Result<Option<FetchTagsOverride>, ConfigGetError> {
+    settings
+        .get_value_with(
+            ["remotes", remote.as_str(), "fetch-tags"],
+            |value| match value.as_str() {
+                Some("all") => Ok(FetchTagsOverride::AllTags),
+                Some("none") => Ok(FetchTagsOverride::NoTags),
+                _ => Err(r#"expected "all" or "none""#),
+            },
+        )
+        .optional()
+}
+
 fn get_default_fetch_remotes(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
@@ -253,19 +352,3 @@ fn warn_if_branches_not_found(
 
     Ok(())
 }
-
-fn warn_ignored_refspecs(
-    ui: &Ui,
-    remote_name: &RemoteName,
-    IgnoredRefspecs(ignored_refspecs): IgnoredRefspecs,
-) -> Result<(), CommandError> {
-    let remote_name = remote_name.as_symbol();
-    for IgnoredRefspec { refspec, reason } in ignored_refspecs {
-        writeln!(
-            ui.warning_default(),
-            "Ignored refspec `{refspec}` from `{remote_name}`: {reason}",
-        )?;
-    }
-
-    Ok(())
-}
@@ -14,9 +14,11 @@
 
 use jj_lib::git;
 use jj_lib::git::GitSettings;
+use jj_lib::repo::Repo as _;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::git_util::maybe_write_commit_graph;
 use crate::git_util::print_git_import_stats;
 use crate::ui::Ui;
 
@@ -43,6 +45,7 @@ pub fn cmd_git_import(
     git::import_head(tx.repo_mut())?;
     let stats = git::import_refs(tx.repo_mut(), &git_settings)?;
     print_git_import_stats(ui, tx.repo(), &stats, true)?;
+    maybe_write_commit_graph(ui, tx.repo().store(), &git_settings)?;
     tx.finish(ui, "import git refs")?;
     Ok(())
 }
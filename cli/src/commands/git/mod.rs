@@ -12,15 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod apply;
+mod bundle;
 mod clone;
 mod colocation;
 mod export;
 mod fetch;
 mod import;
 mod init;
-mod push;
+pub(crate) mod push;
 mod remote;
 mod root;
+mod sync;
 
 use std::io::Write as _;
 use std::path::Path;
@@ -30,11 +33,18 @@ use clap::ValueEnum;
 use jj_lib::config::ConfigFile;
 use jj_lib::config::ConfigSource;
 use jj_lib::git;
+use jj_lib::git::IgnoredRefspec;
+use jj_lib::git::IgnoredRefspecs;
 use jj_lib::git::UnexpectedGitBackendError;
+use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteNameBuf;
 use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::store::Store;
 
+use self::apply::GitApplyArgs;
+use self::apply::cmd_git_apply;
+use self::bundle::GitBundleCommand;
+use self::bundle::cmd_git_bundle;
 use self::clone::GitCloneArgs;
 use self::clone::cmd_git_clone;
 use self::colocation::GitColocationCommand;
@@ -54,6 +64,8 @@ use self::remote::RemoteCommand;
 use self::remote::cmd_git_remote;
 use self::root::GitRootArgs;
 use self::root::cmd_git_root;
+use self::sync::GitSyncArgs;
+use self::sync::cmd_git_sync;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
@@ -71,6 +83,9 @@ use crate::ui::Ui;
 ///     https://jj-vcs.github.io/jj/latest/git-command-table
 #[derive(Subcommand, Clone, Debug)]
 pub enum GitCommand {
+    Apply(GitApplyArgs),
+    #[command(subcommand)]
+    Bundle(GitBundleCommand),
     Clone(GitCloneArgs),
     #[command(subcommand)]
     Colocation(GitColocationCommand),
@@ -82,6 +97,7 @@ pub enum GitCommand {
     #[command(subcommand)]
     Remote(RemoteCommand),
     Root(GitRootArgs),
+    Sync(GitSyncArgs),
 }
 
 pub fn cmd_git(
@@ -90,6 +106,8 @@ pub fn cmd_git(
     subcommand: &GitCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        GitCommand::Apply(args) => cmd_git_apply(ui, command, args),
+        GitCommand::Bundle(subcommand) => cmd_git_bundle(ui, command, subcommand),
         GitCommand::Clone(args) => cmd_git_clone(ui, command, args),
         GitCommand::Colocation(subcommand) => cmd_git_colocation(ui, command, subcommand),
         GitCommand::Export(args) => cmd_git_export(ui, command, args),
@@ -99,6 +117,7 @@ pub fn cmd_git(
         GitCommand::Push(args) => cmd_git_push(ui, command, args),
         GitCommand::Remote(args) => cmd_git_remote(ui, command, args),
         GitCommand::Root(args) => cmd_git_root(ui, command, args),
+        GitCommand::Sync(args) => cmd_git_sync(ui, command, args),
     }
 }
 
@@ -125,6 +144,22 @@ fn get_single_remote(store: &Store) -> Result<Option<RemoteNameBuf>, UnexpectedG
     })
 }
 
+fn warn_ignored_refspecs(
+    ui: &Ui,
+    remote_name: &RemoteName,
+    IgnoredRefspecs(ignored_refspecs): IgnoredRefspecs,
+) -> Result<(), CommandError> {
+    let remote_name = remote_name.as_symbol();
+    for IgnoredRefspec { refspec, reason } in ignored_refspecs {
+        writeln!(
+            ui.warning_default(),
+            "Ignored refspec `{refspec}` from `{remote_name}`: {reason}",
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Sets repository level `trunk()` alias to the specified remote symbol.
 fn write_repository_level_trunk_alias(
     ui: &Ui,
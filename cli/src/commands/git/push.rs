@@ -18,6 +18,7 @@ use std::fmt;
 use std::io;
 use std::io::Write as _;
 use std::iter;
+use std::sync::Arc;
 
 use clap::ArgGroup;
 use clap_complete::ArgValueCandidates;
@@ -45,6 +46,7 @@ use jj_lib::refs::BookmarkPushUpdate;
 use jj_lib::refs::LocalAndRemoteRef;
 use jj_lib::refs::classify_bookmark_push_action;
 use jj_lib::repo::Repo;
+use jj_lib::revset::ResolvedRevsetExpression;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::SignBehavior;
@@ -65,19 +67,25 @@ use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::user_error_with_message;
 use crate::commands::git::get_single_remote;
+use crate::commands::git::warn_ignored_refspecs;
 use crate::complete;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
+use crate::git_util::transfer_git_lfs_objects;
 use crate::git_util::with_remote_git_callbacks;
 use crate::revset_util::parse_bookmark_name;
+use crate::revset_util::parse_protected_bookmarks;
+use crate::revset_util::parse_tag_name;
 use crate::ui::Ui;
 
 /// Push to a Git remote
 ///
 /// By default, pushes tracking bookmarks pointing to
-/// `remote_bookmarks(remote=<remote>)..@`. Use `--bookmark` to push specific
-/// bookmarks. Use `--all` to push all bookmarks. Use `--change` to generate
-/// bookmark names based on the change IDs of specific commits.
+/// `remote_bookmarks(remote=<remote>)..@`, except those excluded by the push
+/// refspecs configured for the remote (`remote.<name>.push`). Use `--bookmark`
+/// to push specific bookmarks. Use `--all` to push all bookmarks. Use
+/// `--change` to generate bookmark names based on the change IDs of specific
+/// commits.
 ///
 /// Unlike in Git, the remote to push to is not derived from the tracked remote
 /// bookmarks. Use `--remote` to select the remote Git repository by name. There
@@ -95,7 +103,7 @@ use crate::ui::Ui;
 ///     https://jj-vcs.github.io/jj/latest/bookmarks/#conflicts
 
 #[derive(clap::Args, Clone, Debug)]
-#[command(group(ArgGroup::new("specific").args(&["bookmark", "change", "revisions", "named"]).multiple(true)))]
+#[command(group(ArgGroup::new("specific").args(&["bookmark", "change", "revisions", "named", "tag"]).multiple(true)))]
 #[command(group(ArgGroup::new("what").args(&["all", "tracked"]).conflicts_with("specific")))]
 pub struct GitPushArgs {
     /// The remote to push to (only named remotes are supported)
@@ -154,6 +162,13 @@ pub struct GitPushArgs {
     /// commits are eligible to be pushed.
     #[arg(long)]
     allow_private: bool,
+    /// Allow deleting protected bookmarks on the remote, or moving them
+    /// backwards or sideways
+    ///
+    /// The set of protected bookmarks can be configured by the
+    /// `bookmarks.protected` setting.
+    #[arg(long)]
+    allow_protected: bool,
     /// Push bookmarks pointing to these commits (can be repeated)
     #[arg(
         long,
@@ -191,7 +206,22 @@ pub struct GitPushArgs {
         add = ArgValueCompleter::new(complete::branch_name_equals_any_revision)
     )]
     named: Vec<String>,
+    /// Push this tag (can be repeated)
+    ///
+    /// Tags are only created on the remote. Pushing a tag that already exists
+    /// on the remote fails. Annotated tags created by `jj tag create` are
+    /// pushed as such.
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = parse_tag_name,
+        add = ArgValueCandidates::new(complete::local_tags),
+    )]
+    tag: Vec<RefNameBuf>,
     /// Only display what will change on the remote
+    ///
+    /// The remote is queried to check whether the bookmarks moved since they
+    /// were last fetched, in which case the push would be rejected.
     #[arg(long)]
     dry_run: bool,
 }
@@ -365,7 +395,8 @@ pub fn cmd_git_push(
         let use_default_revset = args.bookmark.is_empty()
             && args.change.is_empty()
             && args.revisions.is_empty()
-            && args.named.is_empty();
+            && args.named.is_empty()
+            && args.tag.is_empty();
         let bookmarks_targeted = find_bookmarks_targeted_by_revisions(
             ui,
             tx.base_workspace_helper(),
@@ -373,6 +404,7 @@ pub fn cmd_git_push(
             &args.revisions,
             use_default_revset,
         )?;
+        let mut targeted_updates = vec![];
         for &(name, targets) in &bookmarks_targeted {
             if !seen_bookmarks.insert(name) {
                 continue;
@@ -384,11 +416,16 @@ pub fn cmd_git_push(
                 allow_new,
                 allow_delete,
             ) {
-                Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
+                Ok(Some(update)) => targeted_updates.push((name.to_owned(), update)),
                 Ok(None) => {}
                 Err(reason) => reason.print(ui)?,
             }
         }
+        // Bookmarks selected explicitly are pushed regardless of the refspecs.
+        if use_default_revset {
+            exclude_bookmarks_by_push_refspecs(ui, &tx, remote, &mut targeted_updates)?;
+        }
+        bookmark_updates.extend(targeted_updates);
 
         tx_description = format!(
             "{TX_DESC_PUSH}{names} to git remote {remote}",
@@ -401,26 +438,124 @@ pub fn cmd_git_push(
             remote = remote.as_symbol()
         );
     }
-    if bookmark_updates.is_empty() {
+    let tags_to_push: Vec<(RefNameBuf, CommitId)> = args
+        .tag
+        .iter()
+        .map(|name| {
+            let target = tx.repo().view().get_local_tag(name);
+            match target.as_normal() {
+                Some(id) => Ok((name.clone(), id.clone())),
+                None if target.is_absent() => Err(user_error(format!(
+                    "No such tag: {name}",
+                    name = name.as_symbol()
+                ))),
+                None => Err(user_error_with_hint(
+                    format!("Tag {name} is conflicted", name = name.as_symbol()),
+                    "Use `jj tag set --allow-move` to resolve the conflict.",
+                )),
+            }
+        })
+        .try_collect()?;
+    if bookmark_updates.is_empty() && tags_to_push.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
     }
+    // Pushing tags doesn't change the repo view.
+    let tags_only = bookmark_updates.is_empty();
+
+    let options = BookmarkPushOptions {
+        allow_empty_description: args.allow_empty_description,
+        allow_private: args.allow_private,
+        allow_protected: args.allow_protected,
+        dry_run: args.dry_run,
+    };
+    push_bookmark_updates(
+        ui,
+        &mut tx,
+        remote,
+        bookmark_updates,
+        &tags_to_push,
+        &options,
+    )?;
+    if args.dry_run || tags_only {
+        return Ok(());
+    }
+    tx.finish(ui, tx_description)?;
+    Ok(())
+}
+
+/// Prints the tags to push, and pushes them to the remote unless `dry_run`.
+fn push_tags(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    remote: &RemoteName,
+    tags: &[(RefNameBuf, CommitId)],
+    dry_run: bool,
+) -> Result<(), CommandError> {
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(
+            formatter,
+            "Tags to push to {remote}:",
+            remote = remote.as_symbol()
+        )?;
+        for (name, target) in tags {
+            writeln!(
+                formatter,
+                "  Add tag {name} to {target}",
+                name = name.as_symbol(),
+                target = short_commit_hash(target)
+            )?;
+        }
+    }
+    if dry_run {
+        return Ok(());
+    }
+    let git_settings = GitSettings::from_settings(tx.settings())?;
+    let push_stats = with_remote_git_callbacks(ui, |cb| {
+        git::push_tags(tx.repo(), &git_settings, remote, tags, cb)
+    })?;
+    process_push_stats(&push_stats)?;
+    Ok(())
+}
 
+/// Options for [`push_bookmark_updates()`].
+#[derive(Clone, Debug)]
+pub(crate) struct BookmarkPushOptions {
+    pub allow_empty_description: bool,
+    pub allow_private: bool,
+    pub allow_protected: bool,
+    pub dry_run: bool,
+}
+
+/// Validates the commits to push, signs them if configured, and pushes the
+/// tags and bookmark updates to the remote. Nothing is pushed unless all
+/// bookmark updates pass the validation. The transaction isn't finished.
+pub(crate) fn push_bookmark_updates(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    remote: &RemoteName,
+    mut bookmark_updates: Vec<(RefNameBuf, BookmarkPushUpdate)>,
+    tags: &[(RefNameBuf, CommitId)],
+    options: &BookmarkPushOptions,
+) -> Result<(), CommandError> {
+    if !options.allow_protected {
+        validate_protected_bookmark_updates(ui, tx.repo(), tx.settings(), &bookmark_updates)?;
+    }
     let sign_behavior = if tx.settings().get_bool("git.sign-on-push")? {
         Some(SignBehavior::Own)
     } else {
         None
     };
     let commits_to_sign =
-        validate_commits_ready_to_push(ui, &bookmark_updates, remote, &tx, args, sign_behavior)?;
-    if !args.dry_run
+        validate_commits_ready_to_push(ui, &bookmark_updates, remote, tx, options, sign_behavior)?;
+    if !options.dry_run
         && !commits_to_sign.is_empty()
         && let Some(sign_behavior) = sign_behavior
     {
         let num_updated_signatures = commits_to_sign.len();
         let num_rebased_descendants;
         (num_rebased_descendants, bookmark_updates) =
-            sign_commits_before_push(&mut tx, commits_to_sign, sign_behavior, bookmark_updates)?;
+            sign_commits_before_push(tx, commits_to_sign, sign_behavior, bookmark_updates)?;
         if let Some(mut formatter) = ui.status_formatter() {
             writeln!(
                 formatter,
@@ -435,16 +570,27 @@ pub fn cmd_git_push(
         }
     }
 
+    if !tags.is_empty() {
+        push_tags(ui, tx, remote, tags, options.dry_run)?;
+        if bookmark_updates.is_empty() {
+            if options.dry_run {
+                writeln!(ui.status(), "Dry-run requested, not pushing.")?;
+            }
+            return Ok(());
+        }
+    }
+
     if let Some(mut formatter) = ui.status_formatter() {
         writeln!(
             formatter,
             "Changes to push to {remote}:",
             remote = remote.as_symbol()
         )?;
-        print_commits_ready_to_push(formatter.as_mut(), tx.repo(), &bookmark_updates)?;
+        print_commits_ready_to_push(formatter.as_mut(), tx.repo(), remote, &bookmark_updates)?;
     }
 
-    if args.dry_run {
+    if options.dry_run {
+        warn_if_remote_bookmarks_moved(ui, tx, remote, &bookmark_updates)?;
         writeln!(ui.status(), "Dry-run requested, not pushing.")?;
         return Ok(());
     }
@@ -452,12 +598,54 @@ pub fn cmd_git_push(
     let targets = GitBranchPushTargets {
         branch_updates: bookmark_updates,
     };
+    // LFS objects have to be uploaded before the commits referencing them.
+    transfer_git_lfs_objects(
+        tx.repo().store(),
+        tx.settings(),
+        "push",
+        remote,
+        targets
+            .branch_updates
+            .iter()
+            .filter_map(|(_, update)| update.new_target.clone()),
+    )?;
     let git_settings = GitSettings::from_settings(tx.settings())?;
     let push_stats = with_remote_git_callbacks(ui, |cb| {
         git::push_branches(tx.repo_mut(), &git_settings, remote, &targets, cb)
     })?;
     process_push_stats(&push_stats)?;
-    tx.finish(ui, tx_description)?;
+    Ok(())
+}
+
+/// Checks that no protected bookmark is deleted or moved backwards or sideways
+/// on the remote.
+fn validate_protected_bookmark_updates(
+    ui: &Ui,
+    repo: &dyn Repo,
+    settings: &UserSettings,
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+) -> Result<(), CommandError> {
+    let protected = parse_protected_bookmarks(ui, settings)?;
+    for (name, update) in bookmark_updates {
+        if !protected.is_match(name.as_str()) {
+            continue;
+        }
+        let Some(old_target) = &update.old_target else {
+            continue;
+        };
+        let action = match &update.new_target {
+            None => "delete",
+            Some(new_target) if !repo.index().is_ancestor(old_target, new_target)? => "force-push",
+            Some(_) => continue,
+        };
+        return Err(user_error_with_hint(
+            format!(
+                "Refusing to {action} protected bookmark: {name}",
+                name = name.as_symbol()
+            ),
+            "Use --allow-protected to allow it.",
+        ));
+    }
     Ok(())
 }
 
@@ -515,7 +703,7 @@ fn validate_commits_ready_to_push(
     bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
     remote: &RemoteName,
     tx: &WorkspaceCommandTransaction,
-    args: &GitPushArgs,
+    options: &BookmarkPushOptions,
     sign_behavior: Option<SignBehavior>,
 ) -> Result<Vec<Commit>, CommandError> {
     let workspace_helper = tx.base_workspace_helper();
@@ -555,7 +743,7 @@ fn validate_commits_ready_to_push(
     {
         let commit = commit?;
         let mut reasons = vec![];
-        if commit.description().is_empty() && !args.allow_empty_description {
+        if commit.description().is_empty() && !options.allow_empty_description {
             reasons.push("it has no description");
         }
         if commit.author().name.is_empty()
@@ -573,7 +761,7 @@ fn validate_commits_ready_to_push(
             reasons.push("it has conflicts");
         }
         let is_private = is_private(commit.id())?;
-        if !args.allow_private && is_private {
+        if !options.allow_private && is_private {
             reasons.push("it is private");
         }
         if !reasons.is_empty() {
@@ -587,7 +775,7 @@ fn validate_commits_ready_to_push(
                 workspace_helper.write_commit_summary(formatter, &commit)?;
                 Ok(())
             });
-            if !args.allow_private && is_private {
+            if !options.allow_private && is_private {
                 error.add_hint(format!(
                     "Configured git.private-commits: '{private_revset_str}'",
                 ));
@@ -657,8 +845,23 @@ fn sign_commits_before_push(
 fn print_commits_ready_to_push(
     formatter: &mut dyn Formatter,
     repo: &dyn Repo,
+    remote: &RemoteName,
     bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
 ) -> Result<(), CommandError> {
+    let count_commits =
+        |from: Arc<ResolvedRevsetExpression>, to: &CommitId| -> Result<usize, CommandError> {
+            let revset = from
+                .range(&RevsetExpression::commit(to.clone()))
+                .evaluate(repo)?;
+            Ok(revset.iter().count())
+        };
+    let format_counts = |added: usize, removed: usize| match (added, removed) {
+        (0, 0) => String::new(),
+        (added, 0) => format!(" ({added} new commits)"),
+        (0, removed) => format!(" ({removed} commits removed)"),
+        (added, removed) => format!(" ({added} new commits, {removed} removed)"),
+    };
+
     let to_direction =
         |old_target: &CommitId, new_target: &CommitId| -> IndexResult<BookmarkMoveDirection> {
             assert_ne!(old_target, new_target);
@@ -683,15 +886,24 @@ fn print_commits_ready_to_push(
                 // among many was moved sideways (say). TODO: People on Discord
                 // suggest "Move bookmark ... forward by n commits",
                 // possibly "Move bookmark ... sideways (X forward, Y back)".
+                let added =
+                    count_commits(RevsetExpression::commit(old_target.clone()), new_target)?;
+                let removed =
+                    count_commits(RevsetExpression::commit(new_target.clone()), old_target)?;
+                let counts = format_counts(added, removed);
                 let msg = match to_direction(old_target, new_target)? {
                     BookmarkMoveDirection::Forward => {
-                        format!("Move forward bookmark {bookmark_name} from {old} to {new}")
+                        format!("Move forward bookmark {bookmark_name} from {old} to {new}{counts}")
                     }
                     BookmarkMoveDirection::Backward => {
-                        format!("Move backward bookmark {bookmark_name} from {old} to {new}")
+                        format!(
+                            "Move backward bookmark {bookmark_name} from {old} to {new}{counts}"
+                        )
                     }
                     BookmarkMoveDirection::Sideways => {
-                        format!("Move sideways bookmark {bookmark_name} from {old} to {new}")
+                        format!(
+                            "Move sideways bookmark {bookmark_name} from {old} to {new}{counts}"
+                        )
                     }
                 };
                 writeln!(formatter, "  {msg}")?;
@@ -705,11 +917,21 @@ fn print_commits_ready_to_push(
                 )?;
             }
             (None, Some(new_target)) => {
+                // Commits already on the remote don't need to be pushed.
+                let remote_heads = RevsetExpression::commits(
+                    repo.view()
+                        .remote_bookmarks(remote)
+                        .flat_map(|(_, remote_ref)| remote_ref.target.added_ids())
+                        .cloned()
+                        .collect(),
+                );
+                let added = count_commits(remote_heads, new_target)?;
                 writeln!(
                     formatter,
-                    "  Add bookmark {bookmark_name} to {new}",
+                    "  Add bookmark {bookmark_name} to {new}{counts}",
                     bookmark_name = bookmark_name.as_symbol(),
-                    new = short_commit_hash(new_target)
+                    new = short_commit_hash(new_target),
+                    counts = format_counts(added, 0)
                 )?;
             }
             (None, None) => {
@@ -720,6 +942,87 @@ fn print_commits_ready_to_push(
     Ok(())
 }
 
+/// Removes bookmarks which the push refspecs configured for the remote don't
+/// allow to be pushed.
+pub(crate) fn exclude_bookmarks_by_push_refspecs(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    remote: &RemoteName,
+    bookmark_updates: &mut Vec<(RefNameBuf, BookmarkPushUpdate)>,
+) -> Result<(), CommandError> {
+    let git_repo = git::get_git_backend(tx.repo().store())?.git_repo();
+    let (ignored, expanded) = git::expand_default_push_refspecs(remote, &git_repo)?;
+    warn_ignored_refspecs(ui, remote, ignored)?;
+    let (allowed, excluded): (Vec<_>, Vec<_>) = bookmark_updates
+        .drain(..)
+        .partition(|(name, _)| expanded.is_allowed(name));
+    *bookmark_updates = allowed;
+    for (name, _) in excluded {
+        writeln!(
+            ui.warning_default(),
+            "Bookmark {name} is excluded by the push refspecs of remote {remote}",
+            name = name.as_symbol(),
+            remote = remote.as_symbol()
+        )?;
+    }
+    Ok(())
+}
+
+/// Checks the remote for bookmarks which moved since they were last fetched.
+/// Pushing such bookmarks would be rejected.
+fn warn_if_remote_bookmarks_moved(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    remote: &RemoteName,
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+) -> Result<(), CommandError> {
+    let git_settings = GitSettings::from_settings(tx.settings())?;
+    let remote_branches: HashMap<RefNameBuf, CommitId> =
+        match git::list_remote_branches(tx.repo().store(), &git_settings, remote) {
+            Ok(branches) => branches.into_iter().collect(),
+            Err(err) => {
+                writeln!(
+                    ui.warning_default(),
+                    "Failed to check the bookmarks on the remote: {err}"
+                )?;
+                return Ok(());
+            }
+        };
+    let mut any_moved = false;
+    for (name, update) in bookmark_updates {
+        let actual = remote_branches.get(name);
+        if actual == update.old_target.as_ref() || actual == update.new_target.as_ref() {
+            continue;
+        }
+        let remote_symbol = name.to_remote_symbol(remote);
+        let expected = match &update.old_target {
+            Some(old_target) => short_commit_hash(old_target),
+            None => "(absent)".to_owned(),
+        };
+        match actual {
+            Some(actual) => writeln!(
+                ui.warning_default(),
+                "Bookmark {remote_symbol} is at {actual} on the remote, expected {expected}",
+                actual = short_commit_hash(actual)
+            )?,
+            None => writeln!(
+                ui.warning_default(),
+                "Bookmark {remote_symbol} was deleted on the remote, expected {expected}"
+            )?,
+        }
+        any_moved = true;
+    }
+    if any_moved {
+        writeln!(
+            ui.hint_default(),
+            "Pushing these bookmarks would be rejected. Run `jj git fetch --remote {remote}` and \
+             resolve the resulting bookmark conflicts.",
+            remote = remote.as_symbol()
+        )?;
+    }
+    Ok(())
+}
+
 fn get_default_push_remote(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
@@ -743,7 +1046,7 @@ fn get_default_push_remote(
 }
 
 #[derive(Clone, Debug)]
-struct RejectedBookmarkUpdateReason {
+pub(crate) struct RejectedBookmarkUpdateReason {
     message: String,
     hint: Option<String>,
 }
@@ -767,7 +1070,7 @@ impl From<RejectedBookmarkUpdateReason> for CommandError {
     }
 }
 
-fn classify_bookmark_update(
+pub(crate) fn classify_bookmark_update(
     remote_symbol: RemoteRefSymbol<'_>,
     targets: LocalAndRemoteRef,
     allow_new: bool,
@@ -823,7 +1126,10 @@ fn classify_bookmark_update(
     }
 }
 
-fn ensure_new_bookmark_name(repo: &dyn Repo, name: &RefName) -> Result<(), CommandError> {
+pub(crate) fn ensure_new_bookmark_name(
+    repo: &dyn Repo,
+    name: &RefName,
+) -> Result<(), CommandError> {
     let symbol = name.as_symbol();
     if repo.view().get_local_bookmark(name).is_present() {
         return Err(user_error_with_hint(
@@ -900,23 +1206,7 @@ fn create_change_bookmarks(
         .iter()
         .map(|id| tx.repo().store().get_commit(id))
         .try_collect()?;
-    let bookmark_names: Vec<_> = {
-        let template_text = tx.settings().get_string("templates.git_push_bookmark")?;
-        let template = tx.parse_commit_template(ui, &template_text)?;
-        all_commits
-            .iter()
-            .map(|commit| {
-                let output = template.format_plain_text(commit);
-                let name = String::from_utf8(output).map_err(|err| {
-                    user_error_with_message("Invalid character in bookmark name", err.utf8_error())
-                })?;
-                if name.is_empty() {
-                    return Err(user_error("Empty bookmark name generated"));
-                }
-                Ok(RefNameBuf::from(name))
-            })
-            .try_collect()?
-    };
+    let bookmark_names = generate_change_bookmark_names(ui, tx, &all_commits)?;
 
     for (commit, name) in iter::zip(&all_commits, &bookmark_names) {
         let target = RefTarget::normal(commit.id().clone());
@@ -936,6 +1226,30 @@ fn create_change_bookmarks(
     Ok(bookmark_names)
 }
 
+/// Generates bookmark names for the commits from the
+/// `templates.git_push_bookmark` template.
+pub(crate) fn generate_change_bookmark_names(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    commits: &[Commit],
+) -> Result<Vec<RefNameBuf>, CommandError> {
+    let template_text = tx.settings().get_string("templates.git_push_bookmark")?;
+    let template = tx.parse_commit_template(ui, &template_text)?;
+    commits
+        .iter()
+        .map(|commit| {
+            let output = template.format_plain_text(commit);
+            let name = String::from_utf8(output).map_err(|err| {
+                user_error_with_message("Invalid character in bookmark name", err.utf8_error())
+            })?;
+            if name.is_empty() {
+                return Err(user_error("Empty bookmark name generated"));
+            }
+            Ok(RefNameBuf::from(name))
+        })
+        .try_collect()
+}
+
 fn find_bookmarks_to_push<'a>(
     view: &'a View,
     bookmark_patterns: &[StringPattern],
@@ -0,0 +1,171 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::git;
+use jj_lib::git::FetchDepth;
+use jj_lib::git::expand_default_fetch_refspecs;
+use jj_lib::git::get_git_backend;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::rewrite::EmptyBehavior;
+use jj_lib::rewrite::MoveCommitsLocation;
+use jj_lib::rewrite::MoveCommitsTarget;
+use jj_lib::rewrite::RebaseOptions;
+use jj_lib::rewrite::RewriteRefsOptions;
+use jj_lib::rewrite::compute_move_commits;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_commit_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::commands::git::fetch::fetch_and_import;
+use crate::commands::git::warn_ignored_refspecs;
+use crate::complete;
+use crate::revset_util::RevsetExpressionEvaluator;
+use crate::ui::Ui;
+
+/// Fetch from all Git remotes and rebase your work onto the updated trunk
+///
+/// This fetches the refspecs configured for every remote, like `jj git fetch
+/// --all-remotes`, which also deletes the bookmarks deleted on the remotes.
+/// Then the stacks of your mutable commits (`mutable() & mine()`) that were
+/// based on the destination are rebased onto its new location. Stacks based
+/// on other commits are left in place.
+///
+/// Everything is recorded as a single operation, which can be undone with
+/// `jj undo`.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitSyncArgs {
+    /// The revision to rebase onto [default: `git.sync-onto` config, or
+    /// `trunk()`]
+    #[arg(
+        long,
+        short,
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    onto: Option<RevisionArg>,
+}
+
+pub fn cmd_git_sync(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitSyncArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let onto_arg = match &args.onto {
+        Some(onto) => onto.clone(),
+        None => RevisionArg::from(workspace_command.settings().get_string("git.sync-onto")?),
+    };
+    let onto_evaluator = workspace_command.parse_revset(ui, &onto_arg)?;
+    let old_onto_ids: Vec<CommitId> = onto_evaluator.evaluate_to_commit_ids()?.try_collect()?;
+    let onto_expression = onto_evaluator.expression().clone();
+    let stacks_expression = workspace_command
+        .parse_revset(ui, &RevisionArg::from("mutable() & mine()".to_owned()))?
+        .expression()
+        .clone();
+
+    let remotes = git::get_all_remote_names(workspace_command.repo().store())?;
+    if remotes.is_empty() {
+        return Err(user_error("No git remotes to fetch from"));
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let git_repo = get_git_backend(tx.repo().store())?.git_repo();
+    let mut expansions = Vec::with_capacity(remotes.len());
+    for remote in &remotes {
+        let (ignored, expanded) = expand_default_fetch_refspecs(remote, &git_repo)?;
+        warn_ignored_refspecs(ui, remote, ignored)?;
+        expansions.push((remote.as_ref(), expanded));
+    }
+    fetch_and_import(ui, &mut tx, expansions, &FetchDepth::Full)?;
+
+    // Resolve the destination again now that the remote bookmarks moved.
+    let evaluate = |expression| {
+        RevsetExpressionEvaluator::new(
+            tx.repo(),
+            command.revset_extensions().clone(),
+            tx.base_workspace_helper().id_prefix_context(),
+            expression,
+        )
+    };
+    let new_onto: Vec<_> = evaluate(onto_expression.clone())
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let [new_onto] = &new_onto[..] else {
+        return Err(user_error(format!(
+            "Revset `{onto_arg}` resolved to {} revisions after fetching, expected one",
+            new_onto.len()
+        )));
+    };
+    let new_onto_expression = RevsetExpression::commits(vec![new_onto.id().clone()]);
+    let root_ids: Vec<_> = evaluate(
+        stacks_expression
+            .minus(&onto_expression.ancestors())
+            .roots()
+            .intersection(
+                &RevsetExpression::commits(old_onto_ids)
+                    .ancestors()
+                    .children(),
+            )
+            .minus(&new_onto_expression.children()),
+    )
+    .evaluate_to_commit_ids()?
+    .try_collect()?;
+
+    if !root_ids.is_empty() {
+        let loc = MoveCommitsLocation {
+            new_parent_ids: vec![new_onto.id().clone()],
+            new_child_ids: vec![],
+            target: MoveCommitsTarget::Roots(root_ids),
+        };
+        let rebase_options = RebaseOptions {
+            empty: EmptyBehavior::AbandonNewlyEmpty,
+            rewrite_refs: RewriteRefsOptions {
+                delete_abandoned_bookmarks: false,
+            },
+            simplify_ancestor_merge: false,
+        };
+        let stats = compute_move_commits(tx.repo(), &loc)?.apply(tx.repo_mut(), &rebase_options)?;
+        if let Some(mut formatter) = ui.status_formatter() {
+            writeln!(
+                formatter,
+                "Rebased {} commits onto {}",
+                stats.num_rebased_targets + stats.num_rebased_descendants,
+                short_commit_hash(new_onto.id())
+            )?;
+            if stats.num_abandoned_empty > 0 {
+                writeln!(
+                    formatter,
+                    "Abandoned {} commits that became empty",
+                    stats.num_abandoned_empty
+                )?;
+            }
+        }
+    }
+    tx.finish(
+        ui,
+        format!(
+            "sync with git remote(s) {}",
+            remotes.iter().map(|n| n.as_symbol()).join(",")
+        ),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,96 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod pr;
+
+use std::fmt::Debug;
+
+use clap::Subcommand;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::git;
+use jj_lib::ref_name::RemoteNameBuf;
+use jj_lib::repo::Repo as _;
+
+use self::pr::PrCommand;
+use self::pr::cmd_github_pr;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::github_util::GitHubRepo;
+use crate::github_util::parse_github_remote_url;
+use crate::ui::Ui;
+
+/// Interact with GitHub
+///
+/// Pull requests are managed in the GitHub repository that a Git remote points
+/// to. Requests to the GitHub API are authenticated with the token in the
+/// `GH_TOKEN` or `GITHUB_TOKEN` environment variable, or in the `github.token`
+/// config option.
+#[derive(Subcommand, Clone, Debug)]
+pub enum GithubCommand {
+    #[command(subcommand)]
+    Pr(PrCommand),
+}
+
+pub fn cmd_github(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &GithubCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        GithubCommand::Pr(args) => cmd_github_pr(ui, command, args),
+    }
+}
+
+/// Determines the remote to use, and the GitHub repository it points to. The
+/// remote is the one given by `--remote`, `github.default-remote`, or
+/// `git.push`, in that order, and "origin" otherwise.
+pub(crate) fn resolve_github_remote(
+    workspace_command: &WorkspaceCommandHelper,
+    remote: Option<&str>,
+) -> Result<(RemoteNameBuf, GitHubRepo), CommandError> {
+    let settings = workspace_command.settings();
+    let remote = match remote {
+        Some(remote) => remote.to_owned(),
+        None => match settings.get_string("github.default-remote").optional()? {
+            Some(remote) => remote,
+            None => settings
+                .get_string("git.push")
+                .optional()?
+                .unwrap_or_else(|| "origin".to_owned()),
+        },
+    };
+    let git_repo = git::get_git_repo(workspace_command.repo().store())?;
+    let url = match git_repo.try_find_remote(remote.as_str()) {
+        Some(Ok(git_remote)) => git_remote
+            .url(gix::remote::Direction::Fetch)
+            .map(|url| url.to_bstring().to_string()),
+        Some(Err(_)) | None => {
+            return Err(user_error(format!("No git remote named '{remote}'")));
+        }
+    };
+    let github_repo = url
+        .as_deref()
+        .and_then(parse_github_remote_url)
+        .ok_or_else(|| {
+            user_error_with_hint(
+                format!("The remote '{remote}' doesn't point to a GitHub repository"),
+                "Use `--remote` or the `github.default-remote` config option to choose another \
+                 remote.",
+            )
+        })?;
+    Ok((remote.into(), github_repo))
+}
@@ -0,0 +1,155 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+
+use super::is_pushed;
+use super::select_bookmarks;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_change_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::commands::github::resolve_github_remote;
+use crate::complete;
+use crate::github_util::GitHubClient;
+use crate::github_util::NewPullRequest;
+use crate::ui::Ui;
+
+/// Open pull requests for bookmarks
+///
+/// A pull request is opened for each selected bookmark, which must have been
+/// pushed to the remote already. The title and body of the pull request are
+/// taken from the description of the commit the bookmark points to. Bookmarks
+/// that already have an open pull request are skipped.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GithubPrCreateArgs {
+    /// Open pull requests for the bookmarks pointing to these revisions
+    ///
+    /// Defaults to the closest bookmarked mutable ancestor of the working
+    /// copy, `heads(::@ & mutable() & bookmarks())`.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        conflicts_with = "bookmark",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// Open a pull request for this bookmark
+    #[arg(
+        long,
+        short,
+        add = ArgValueCandidates::new(complete::local_bookmarks),
+    )]
+    bookmark: Vec<String>,
+
+    /// The branch the changes should be merged into [default: the default
+    /// branch of the repository]
+    #[arg(long)]
+    base: Option<String>,
+
+    /// Open the pull requests as drafts
+    #[arg(long)]
+    draft: bool,
+
+    /// The remote whose GitHub repository the pull requests are opened in
+    /// [default: `github.default-remote` or `git.push` config, or "origin"]
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: Option<String>,
+}
+
+pub fn cmd_github_pr_create(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GithubPrCreateArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let (remote, github_repo) = resolve_github_remote(&workspace_command, args.remote.as_deref())?;
+    let bookmarks = select_bookmarks(ui, &workspace_command, &args.bookmark, &args.revisions)?;
+    for (name, commit) in &bookmarks {
+        if !is_pushed(&workspace_command, &remote, name, commit) {
+            return Err(user_error_with_hint(
+                format!(
+                    "Bookmark {} is not pushed to {}",
+                    name.as_symbol(),
+                    remote.as_symbol()
+                ),
+                format!(
+                    "Run `jj git push --remote {} --bookmark {}` first.",
+                    remote.as_symbol(),
+                    name.as_symbol()
+                ),
+            ));
+        }
+        if commit.description().trim().is_empty() {
+            return Err(user_error(format!(
+                "Refusing to open a pull request for bookmark {} because revision {} has no \
+                 description",
+                name.as_symbol(),
+                short_change_hash(commit.change_id())
+            )));
+        }
+    }
+
+    let client = GitHubClient::from_settings(workspace_command.settings())?;
+    let base = match &args.base {
+        Some(base) => base.clone(),
+        None => client.default_branch(&github_repo)?,
+    };
+    for (name, commit) in &bookmarks {
+        let head = name.as_str();
+        if let Some(existing) = client
+            .list_pull_requests(&github_repo, Some(head), "open")?
+            .first()
+        {
+            writeln!(
+                ui.status(),
+                "Pull request #{} already exists for bookmark {}: {}",
+                existing.number,
+                name.as_symbol(),
+                existing.html_url
+            )?;
+            continue;
+        }
+        let (title, body) = commit
+            .description()
+            .trim()
+            .split_once('\n')
+            .unwrap_or((commit.description().trim(), ""));
+        let pull_request = client.create_pull_request(
+            &github_repo,
+            &NewPullRequest {
+                title,
+                body: body.trim(),
+                head,
+                base: &base,
+                draft: args.draft,
+            },
+        )?;
+        writeln!(
+            ui.status(),
+            "Created pull request #{} for bookmark {}: {}",
+            pull_request.number,
+            name.as_symbol(),
+            pull_request.html_url
+        )?;
+    }
+    Ok(())
+}
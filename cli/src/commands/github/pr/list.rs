@@ -0,0 +1,68 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use jj_lib::ref_name::RefName;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::commands::github::resolve_github_remote;
+use crate::complete;
+use crate::github_util::GitHubClient;
+use crate::ui::Ui;
+
+/// List open pull requests for local bookmarks
+#[derive(clap::Args, Clone, Debug)]
+pub struct GithubPrListArgs {
+    /// List all open pull requests in the repository, including the ones for
+    /// branches without a local bookmark
+    #[arg(long)]
+    all: bool,
+
+    /// The remote whose GitHub repository is queried [default:
+    /// `github.default-remote` or `git.push` config, or "origin"]
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: Option<String>,
+}
+
+pub fn cmd_github_pr_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GithubPrListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let (_remote, github_repo) = resolve_github_remote(&workspace_command, args.remote.as_deref())?;
+    let client = GitHubClient::from_settings(workspace_command.settings())?;
+    let view = workspace_command.repo().view();
+    let pull_requests = client.list_pull_requests(&github_repo, None, "open")?;
+    let mut formatter = ui.stdout_formatter();
+    for pull_request in &pull_requests {
+        let head = RefName::new(&pull_request.head.name);
+        if !args.all && view.get_local_bookmark(head).is_absent() {
+            continue;
+        }
+        write!(formatter, "#{} ", pull_request.number)?;
+        if pull_request.draft {
+            write!(formatter, "(draft) ")?;
+        }
+        writeln!(
+            formatter,
+            "{} -> {}: {}",
+            pull_request.head.name, pull_request.base.name, pull_request.title
+        )?;
+    }
+    Ok(())
+}
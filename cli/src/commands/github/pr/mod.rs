@@ -0,0 +1,133 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod create;
+mod list;
+mod status;
+
+use clap::Subcommand;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::repo::Repo as _;
+
+use self::create::GithubPrCreateArgs;
+use self::create::cmd_github_pr_create;
+use self::list::GithubPrListArgs;
+use self::list::cmd_github_pr_list;
+use self::status::GithubPrStatusArgs;
+use self::status::cmd_github_pr_status;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::ui::Ui;
+
+/// Manage GitHub pull requests
+#[derive(Subcommand, Clone, Debug)]
+pub enum PrCommand {
+    Create(GithubPrCreateArgs),
+    List(GithubPrListArgs),
+    Status(GithubPrStatusArgs),
+}
+
+pub fn cmd_github_pr(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &PrCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        PrCommand::Create(args) => cmd_github_pr_create(ui, command, args),
+        PrCommand::List(args) => cmd_github_pr_list(ui, command, args),
+        PrCommand::Status(args) => cmd_github_pr_status(ui, command, args),
+    }
+}
+
+/// Revset selecting the closest bookmarked mutable ancestor of the working
+/// copy.
+const DEFAULT_REVSET: &str = "heads(::@ & mutable() & bookmarks())";
+
+/// Resolves the bookmarks given by name, or else the bookmarks pointing to the
+/// given revisions (or [`DEFAULT_REVSET`]).
+fn select_bookmarks(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    bookmark_names: &[String],
+    revisions: &[RevisionArg],
+) -> Result<Vec<(RefNameBuf, Commit)>, CommandError> {
+    let repo = workspace_command.repo();
+    let view = repo.view();
+    if !bookmark_names.is_empty() {
+        return bookmark_names
+            .iter()
+            .map(|name| {
+                let name = RefNameBuf::from(name.as_str());
+                let target = view.get_local_bookmark(&name);
+                let Some(id) = target.as_normal() else {
+                    return Err(if target.is_absent() {
+                        user_error(format!("No such bookmark: {}", name.as_symbol()))
+                    } else {
+                        user_error(format!("Bookmark {} is conflicted", name.as_symbol()))
+                    });
+                };
+                let commit = repo.store().get_commit(id)?;
+                Ok((name, commit))
+            })
+            .try_collect();
+    }
+
+    let revisions = if revisions.is_empty() {
+        &[RevisionArg::from(DEFAULT_REVSET.to_owned())][..]
+    } else {
+        revisions
+    };
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let bookmarks = commits
+        .into_iter()
+        .flat_map(|commit| {
+            view.local_bookmarks_for_commit(commit.id())
+                .filter(|(_, target)| !target.has_conflict())
+                .map(|(name, _)| (name.to_owned(), commit.clone()))
+                .collect_vec()
+        })
+        .collect_vec();
+    if bookmarks.is_empty() {
+        return Err(user_error_with_hint(
+            "No bookmarks point to the selected revisions",
+            "Use `jj git push --change` to create and push a bookmark for a revision.",
+        ));
+    }
+    Ok(bookmarks)
+}
+
+/// Returns true if the bookmark was pushed to the remote, and the local
+/// bookmark hasn't moved since.
+fn is_pushed(
+    workspace_command: &WorkspaceCommandHelper,
+    remote: &RemoteName,
+    name: &RefNameBuf,
+    commit: &Commit,
+) -> bool {
+    let remote_ref = workspace_command
+        .repo()
+        .view()
+        .get_remote_bookmark(name.to_remote_symbol(remote));
+    remote_ref.target.as_normal() == Some(commit.id())
+}
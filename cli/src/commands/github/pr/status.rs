@@ -0,0 +1,97 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+
+use super::is_pushed;
+use super::select_bookmarks;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::commands::github::resolve_github_remote;
+use crate::complete;
+use crate::github_util::GitHubClient;
+use crate::ui::Ui;
+
+/// Show the pull requests of bookmarks
+///
+/// For each selected bookmark, the most recent pull request opened from it is
+/// shown, whether it is open, a draft, merged, or closed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GithubPrStatusArgs {
+    /// Show the pull requests of the bookmarks pointing to these revisions
+    ///
+    /// Defaults to the closest bookmarked mutable ancestor of the working
+    /// copy, `heads(::@ & mutable() & bookmarks())`.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        conflicts_with = "bookmark",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// Show the pull request of this bookmark
+    #[arg(
+        long,
+        short,
+        add = ArgValueCandidates::new(complete::local_bookmarks),
+    )]
+    bookmark: Vec<String>,
+
+    /// The remote whose GitHub repository is queried [default:
+    /// `github.default-remote` or `git.push` config, or "origin"]
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: Option<String>,
+}
+
+pub fn cmd_github_pr_status(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GithubPrStatusArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let (remote, github_repo) = resolve_github_remote(&workspace_command, args.remote.as_deref())?;
+    let bookmarks = select_bookmarks(ui, &workspace_command, &args.bookmark, &args.revisions)?;
+    let client = GitHubClient::from_settings(workspace_command.settings())?;
+    let mut formatter = ui.stdout_formatter();
+    for (name, commit) in &bookmarks {
+        let pull_requests = client.list_pull_requests(&github_repo, Some(name.as_str()), "all")?;
+        let Some(pull_request) = pull_requests.first() else {
+            writeln!(formatter, "{}: no pull request", name.as_symbol())?;
+            continue;
+        };
+        writeln!(
+            formatter,
+            "{}: #{} {}: {}",
+            name.as_symbol(),
+            pull_request.number,
+            pull_request.status(),
+            pull_request.title
+        )?;
+        writeln!(formatter, "  {}", pull_request.html_url)?;
+        if !is_pushed(&workspace_command, &remote, name, commit) {
+            writeln!(
+                formatter,
+                "  The local bookmark has changes not pushed to {}",
+                remote.as_symbol()
+            )?;
+        }
+    }
+    Ok(())
+}
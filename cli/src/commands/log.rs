@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
@@ -20,6 +23,7 @@ use jj_lib::commit::Commit;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::graph::compact_linear_runs;
 use jj_lib::graph::reverse_graph;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetEvaluationError;
@@ -32,15 +36,22 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::format_template;
+use crate::cli_util::print_snapshot_stats;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::formatter::FormatterExt as _;
 use crate::graphlog::GraphStyle;
+use crate::graphlog::colored_lanes_from_settings;
 use crate::graphlog::get_graphlog;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
+use crate::watch_util::watch_workspace;
+
+/// Minimum number of revisions in a linear run to be elided by `--compact-graph`.
+const COMPACT_GRAPH_MIN_ELIDED: usize = 3;
 
 /// Show revision history
 ///
@@ -96,6 +107,14 @@ pub(crate) struct LogArgs {
     /// Don't show the graph, show a flat list of revisions
     #[arg(long, short = 'G')]
     no_graph: bool,
+    /// Collapse long runs of linear history in the graph
+    ///
+    /// Runs of revisions with a single parent and a single child are rendered
+    /// as a synthetic node showing the number of elided revisions. Working-copy
+    /// revisions and revisions pointed to by bookmarks or tags are always
+    /// shown.
+    #[arg(long, conflicts_with = "no_graph")]
+    compact_graph: bool,
     /// Render each revision using the given template
     ///
     /// Run `jj log -T` to list the built-in templates.
@@ -118,6 +137,17 @@ pub(crate) struct LogArgs {
     patch: bool,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
+    /// Keep running, and re-render the log whenever the repo changes
+    ///
+    /// The working copy is snapshotted and the operation log is checked for
+    /// new operations whenever files in the workspace change. Configure a
+    /// [filesystem monitor] to keep the snapshots cheap in large working
+    /// copies.
+    ///
+    /// [filesystem monitor]:
+    ///     https://jj-vcs.github.io/jj/latest/config/#filesystem-monitor
+    #[arg(long)]
+    watch: bool,
 }
 
 #[instrument(skip_all)]
@@ -126,7 +156,21 @@ pub(crate) fn cmd_log(
     command: &CommandHelper,
     args: &LogArgs,
 ) -> Result<(), CommandError> {
+    if args.watch {
+        return watch_workspace(ui, command, |ui, workspace_command, stats| {
+            print_snapshot_stats(ui, stats, workspace_command.env().path_converter())?;
+            show_log(ui, workspace_command, args)
+        });
+    }
     let workspace_command = command.workspace_helper(ui)?;
+    show_log(ui, &workspace_command, args)
+}
+
+fn show_log(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &LogArgs,
+) -> Result<(), CommandError> {
     let settings = workspace_command.settings();
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
@@ -160,8 +204,11 @@ pub(crate) fn cmd_log(
     let store = repo.store();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
     let graph_style = GraphStyle::from_settings(settings)?;
+    let colored_lanes = colored_lanes_from_settings(ui, settings)?;
 
-    let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
+    // Elided runs are only counted by synthetic nodes.
+    let use_elided_nodes =
+        args.compact_graph || settings.get_bool("ui.log-synthetic-elided-nodes")?;
     let with_content_format = LogContentFormat::new(ui, settings)?;
 
     let template: TemplateRenderer<Commit>;
@@ -187,7 +234,7 @@ pub(crate) fn cmd_log(
 
         if !args.no_graph {
             let mut raw_output = formatter.raw()?;
-            let mut graph = get_graphlog(graph_style, raw_output.as_mut());
+            let mut graph = get_graphlog(graph_style, colored_lanes, raw_output.as_mut());
             let iter: Box<dyn Iterator<Item = _>> = {
                 let mut forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id);
 
@@ -209,6 +256,26 @@ pub(crate) fn cmd_log(
                     Box::new(forward_iter)
                 }
             };
+            let mut elided_runs = HashMap::new();
+            let iter: Box<dyn Iterator<Item = _>> = if args.compact_graph {
+                let view = repo.view();
+                let labeled_ids: HashSet<&CommitId> = view
+                    .local_bookmarks()
+                    .chain(view.local_tags())
+                    .flat_map(|(_, target)| target.added_ids())
+                    .collect();
+                let nodes: Vec<_> = iter.try_collect()?;
+                let (nodes, runs) = compact_linear_runs(
+                    nodes,
+                    |id| id,
+                    |id| !view.is_wc_commit_id(id) && !labeled_ids.contains(id),
+                    COMPACT_GRAPH_MIN_ELIDED,
+                );
+                elided_runs = runs;
+                Box::new(nodes.into_iter().map(Ok))
+            } else {
+                iter
+            };
             for node in iter {
                 let (commit_id, edges) = node?;
 
@@ -278,6 +345,7 @@ pub(crate) fn cmd_log(
                 explicit_paths.retain(|&path| tree.path_value(path).unwrap().is_absent());
 
                 for elided_target in elided_targets {
+                    let elided_ids = elided_runs.remove(&elided_target);
                     let elided_key = (elided_target, true);
                     let real_key = (elided_key.0.clone(), false);
                     let edges = [GraphEdge::direct(real_key)];
@@ -285,7 +353,15 @@ pub(crate) fn cmd_log(
                     let within_graph =
                         with_content_format.sub_width(graph.width(&elided_key, &edges));
                     within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
-                        writeln!(formatter.labeled("elided"), "(elided revisions)")
+                        if let Some(ids) = &elided_ids {
+                            writeln!(
+                                formatter.labeled("elided"),
+                                "({} revisions elided)",
+                                ids.len()
+                            )
+                        } else {
+                            writeln!(formatter.labeled("elided"), "(elided revisions)")
+                        }
                     })?;
                     let node_symbol = format_template(ui, &None, &node_template);
                     graph.add_node(
@@ -294,6 +370,12 @@ pub(crate) fn cmd_log(
                         &node_symbol,
                         &String::from_utf8_lossy(&buffer),
                     )?;
+
+                    for id in elided_ids.iter().flatten() {
+                        let tree = store.get_commit(id)?.tree();
+                        // TODO: propagate errors
+                        explicit_paths.retain(|&path| tree.path_value(path).unwrap().is_absent());
+                    }
                 }
             }
         } else {
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::Signature;
 use jj_lib::backend::Timestamp;
 use jj_lib::commit::Commit;
+use jj_lib::mailmap::Mailmap;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::time_util::parse_datetime;
 use tracing::instrument;
@@ -26,6 +29,7 @@ use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::print_updated_commits;
 use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
 use crate::complete;
 use crate::description_util::join_message_paragraphs;
 use crate::text_util::parse_author;
@@ -109,6 +113,24 @@ pub(crate) struct MetaeditArgs {
     )]
     author_timestamp: Option<Timestamp>,
 
+    /// Rewrite author and committer identities according to a mapping file
+    ///
+    /// The file uses the `.mailmap` format. Each line maps an old identity to
+    /// a new one, for example:
+    ///
+    /// New Name <new@example.com> <old@example.com>
+    ///
+    /// Revisions whose author and committer don't match any entry are left
+    /// unchanged. The committer of a rewritten revision is also mapped
+    /// instead of being reset to the configured user.
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["author", "update_author"]
+    )]
+    map_authors: Option<PathBuf>,
+
     /// Rewrite the commit, even if no other metadata changed
     ///
     /// This updates the committer timestamp to the current time, as well as the
@@ -168,6 +190,15 @@ pub(crate) fn cmd_metaedit(
         return Ok(());
     }
 
+    let author_map = if let Some(path) = &args.map_authors {
+        let content = std::fs::read(command.cwd().join(path)).map_err(|err| {
+            user_error_with_message(format!("Failed to read {}", path.display()), err)
+        })?;
+        Some(Mailmap::parse(&content))
+    } else {
+        None
+    };
+
     let mut tx = workspace_command.start_transaction();
     let tx_description = match commit_ids.as_slice() {
         [] => unreachable!(),
@@ -204,6 +235,7 @@ pub(crate) fn cmd_metaedit(
                     || rewriter.parents_changed();
 
                 let old_author = rewriter.old_commit().author().clone();
+                let old_committer = rewriter.old_commit().committer().clone();
                 let mut commit_builder = rewriter.reparent();
                 let mut new_author = commit_builder.author().clone();
                 if let Some((name, email)) = args.author.clone() {
@@ -212,6 +244,10 @@ pub(crate) fn cmd_metaedit(
                 } else if args.update_author {
                     new_author.name = commit_builder.committer().name.clone();
                     new_author.email = commit_builder.committer().email.clone();
+                } else if let Some(author_map) = &author_map {
+                    let (name, email) = author_map.resolve(&old_author.name, &old_author.email);
+                    new_author.name = name;
+                    new_author.email = email;
                 }
                 if args.update_author_timestamp {
                     new_author.timestamp = commit_builder.committer().timestamp;
@@ -232,6 +268,22 @@ pub(crate) fn cmd_metaedit(
                     rewrite = true;
                 }
 
+                if let Some(author_map) = &author_map {
+                    let (name, email) =
+                        author_map.resolve(&old_committer.name, &old_committer.email);
+                    if name != old_committer.name || email != old_committer.email {
+                        rewrite = true;
+                    }
+                    // Keep the (mapped) committer identity rather than
+                    // resetting it to the configured user.
+                    let timestamp = commit_builder.committer().timestamp;
+                    commit_builder = commit_builder.set_committer(Signature {
+                        name,
+                        email,
+                        timestamp,
+                    });
+                }
+
                 if let Some(description) = &new_description
                     && description != commit_builder.description()
                 {
@@ -14,10 +14,14 @@
 
 mod abandon;
 mod absorb;
+mod api;
+mod apply_patch;
+mod backport;
 #[cfg(feature = "bench")]
 mod bench;
 mod bisect;
 mod bookmark;
+mod changelog;
 mod commit;
 mod config;
 mod debug;
@@ -29,20 +33,26 @@ mod edit;
 mod evolog;
 mod file;
 mod fix;
+mod format_patch;
 #[cfg(feature = "git")]
 mod gerrit;
 #[cfg(feature = "git")]
 mod git;
+#[cfg(feature = "github")]
+mod github;
 mod help;
 mod interdiff;
 mod log;
 mod metaedit;
 mod new;
 mod next;
+#[cfg(feature = "git")]
+mod note;
 mod operation;
 mod parallelize;
 mod prev;
 mod rebase;
+mod recover;
 mod redo;
 mod resolve;
 mod restore;
@@ -55,12 +65,19 @@ mod simplify_parents;
 mod sparse;
 mod split;
 mod squash;
+#[cfg(feature = "github")]
+mod stack;
+mod stash;
 mod status;
 mod tag;
+mod tui;
 mod undo;
 mod unsign;
 mod util;
+mod verify;
 mod version;
+#[cfg(feature = "web")]
+mod web;
 mod workspace;
 
 use std::fmt::Debug;
@@ -93,6 +110,10 @@ const STYLES: Styles = Styles::styled()
 enum Command {
     Abandon(abandon::AbandonArgs),
     Absorb(absorb::AbsorbArgs),
+    #[command(subcommand)]
+    Api(api::ApiCommand),
+    ApplyPatch(apply_patch::ApplyPatchArgs),
+    Backport(backport::BackportArgs),
     #[cfg(feature = "bench")]
     #[command(subcommand)]
     Bench(bench::BenchCommand),
@@ -100,6 +121,7 @@ enum Command {
     Bisect(bisect::BisectCommand),
     #[command(subcommand)]
     Bookmark(bookmark::BookmarkCommand),
+    Changelog(changelog::ChangelogArgs),
     Commit(commit::CommitArgs),
     #[command(subcommand)]
     Config(config::ConfigCommand),
@@ -115,24 +137,32 @@ enum Command {
     #[command(subcommand)]
     File(file::FileCommand),
     Fix(fix::FixArgs),
+    FormatPatch(format_patch::FormatPatchArgs),
     #[cfg(feature = "git")]
     #[command(subcommand)]
     Gerrit(gerrit::GerritCommand),
     #[cfg(feature = "git")]
     #[command(subcommand)]
     Git(git::GitCommand),
+    #[cfg(feature = "github")]
+    #[command(subcommand)]
+    Github(github::GithubCommand),
     Help(help::HelpArgs),
     Interdiff(interdiff::InterdiffArgs),
     Log(log::LogArgs),
     Metaedit(metaedit::MetaeditArgs),
     New(new::NewArgs),
     Next(next::NextArgs),
+    #[cfg(feature = "git")]
+    #[command(subcommand)]
+    Note(note::NoteCommand),
     #[command(subcommand)]
     #[command(visible_alias = "op")]
     Operation(operation::OperationCommand),
     Parallelize(parallelize::ParallelizeArgs),
     Prev(prev::PrevArgs),
     Rebase(rebase::RebaseArgs),
+    Recover(recover::RecoverArgs),
     Redo(redo::RedoArgs),
     Resolve(resolve::ResolveArgs),
     Restore(restore::RestoreArgs),
@@ -148,14 +178,23 @@ enum Command {
     Sparse(sparse::SparseCommand),
     Split(split::SplitArgs),
     Squash(squash::SquashArgs),
+    #[cfg(feature = "github")]
+    #[command(subcommand)]
+    Stack(stack::StackCommand),
+    #[command(subcommand)]
+    Stash(stash::StashCommand),
     Status(status::StatusArgs),
     #[command(subcommand)]
     Tag(tag::TagCommand),
+    Tui(tui::TuiArgs),
     Undo(undo::UndoArgs),
     Unsign(unsign::UnsignArgs),
     #[command(subcommand)]
     Util(util::UtilCommand),
+    Verify(verify::VerifyArgs),
     Version(version::VersionArgs),
+    #[cfg(feature = "web")]
+    Web(web::WebArgs),
     #[command(subcommand)]
     Workspace(workspace::WorkspaceCommand),
 }
@@ -170,10 +209,14 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
     match &subcommand {
         Command::Abandon(args) => abandon::cmd_abandon(ui, command_helper, args),
         Command::Absorb(args) => absorb::cmd_absorb(ui, command_helper, args),
+        Command::Api(args) => api::cmd_api(ui, command_helper, args),
+        Command::ApplyPatch(args) => apply_patch::cmd_apply_patch(ui, command_helper, args),
+        Command::Backport(args) => backport::cmd_backport(ui, command_helper, args),
         #[cfg(feature = "bench")]
         Command::Bench(args) => bench::cmd_bench(ui, command_helper, args),
         Command::Bisect(args) => bisect::cmd_bisect(ui, command_helper, args),
         Command::Bookmark(args) => bookmark::cmd_bookmark(ui, command_helper, args),
+        Command::Changelog(args) => changelog::cmd_changelog(ui, command_helper, args),
         Command::Commit(args) => commit::cmd_commit(ui, command_helper, args),
         Command::Config(args) => config::cmd_config(ui, command_helper, args),
         Command::Debug(args) => debug::cmd_debug(ui, command_helper, args),
@@ -185,20 +228,26 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Evolog(args) => evolog::cmd_evolog(ui, command_helper, args),
         Command::File(args) => file::cmd_file(ui, command_helper, args),
         Command::Fix(args) => fix::cmd_fix(ui, command_helper, args),
+        Command::FormatPatch(args) => format_patch::cmd_format_patch(ui, command_helper, args),
         #[cfg(feature = "git")]
         Command::Gerrit(sub_args) => gerrit::cmd_gerrit(ui, command_helper, sub_args),
         #[cfg(feature = "git")]
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
+        #[cfg(feature = "github")]
+        Command::Github(args) => github::cmd_github(ui, command_helper, args),
         Command::Help(args) => help::cmd_help(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
         Command::Log(args) => log::cmd_log(ui, command_helper, args),
         Command::Metaedit(args) => metaedit::cmd_metaedit(ui, command_helper, args),
         Command::New(args) => new::cmd_new(ui, command_helper, args),
         Command::Next(args) => next::cmd_next(ui, command_helper, args),
+        #[cfg(feature = "git")]
+        Command::Note(args) => note::cmd_note(ui, command_helper, args),
         Command::Operation(args) => operation::cmd_operation(ui, command_helper, args),
         Command::Parallelize(args) => parallelize::cmd_parallelize(ui, command_helper, args),
         Command::Prev(args) => prev::cmd_prev(ui, command_helper, args),
         Command::Rebase(args) => rebase::cmd_rebase(ui, command_helper, args),
+        Command::Recover(args) => recover::cmd_recover(ui, command_helper, args),
         Command::Redo(args) => redo::cmd_redo(ui, command_helper, args),
         Command::Resolve(args) => resolve::cmd_resolve(ui, command_helper, args),
         Command::Restore(args) => restore::cmd_restore(ui, command_helper, args),
@@ -213,12 +262,19 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Sparse(args) => sparse::cmd_sparse(ui, command_helper, args),
         Command::Split(args) => split::cmd_split(ui, command_helper, args),
         Command::Squash(args) => squash::cmd_squash(ui, command_helper, args),
+        #[cfg(feature = "github")]
+        Command::Stack(args) => stack::cmd_stack(ui, command_helper, args),
+        Command::Stash(args) => stash::cmd_stash(ui, command_helper, args),
         Command::Status(args) => status::cmd_status(ui, command_helper, args),
         Command::Tag(args) => tag::cmd_tag(ui, command_helper, args),
+        Command::Tui(args) => tui::cmd_tui(ui, command_helper, args),
         Command::Undo(args) => undo::cmd_undo(ui, command_helper, args),
         Command::Unsign(args) => unsign::cmd_unsign(ui, command_helper, args),
         Command::Util(args) => util::cmd_util(ui, command_helper, args),
+        Command::Verify(args) => verify::cmd_verify(ui, command_helper, args),
         Command::Version(args) => version::cmd_version(ui, command_helper, args),
+        #[cfg(feature = "web")]
+        Command::Web(args) => web::cmd_web(ui, command_helper, args),
         Command::Workspace(args) => workspace::cmd_workspace(ui, command_helper, args),
     }
 }
@@ -0,0 +1,87 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+
+use super::notes_ref;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_hint;
+use crate::complete;
+use crate::description_util::join_message_paragraphs;
+use crate::ui::Ui;
+
+/// Attach a note to a revision
+#[derive(clap::Args, Clone, Debug)]
+pub struct NoteAddArgs {
+    /// The revision to attach the note to
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+
+    /// The note to attach
+    ///
+    /// If multiple messages are given, they are joined as paragraphs.
+    #[arg(long = "message", short, value_name = "MESSAGE", required = true)]
+    message_paragraphs: Vec<String>,
+
+    /// Replace the existing note of the revision
+    #[arg(long, short)]
+    force: bool,
+}
+
+pub fn cmd_note_add(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteAddArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let notes_ref = notes_ref(command)?;
+    let store = workspace_command.repo().store();
+    let notes = git::read_notes(store, &notes_ref)?;
+    if notes.contains_key(commit.id()) && !args.force {
+        return Err(user_error_with_hint(
+            format!(
+                "Revision {} already has a note",
+                workspace_command.format_commit_summary(&commit)
+            ),
+            "Use --force to replace it.",
+        ));
+    }
+    let note = join_message_paragraphs(&args.message_paragraphs);
+    git::write_note(
+        store,
+        &notes_ref,
+        commit.id(),
+        Some(&note),
+        &command.settings().signature(),
+    )?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Added note to ")?;
+        workspace_command.write_commit_summary(formatter.as_mut(), &commit)?;
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
+
+use super::notes_ref;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// List revisions that have notes, along with their notes
+#[derive(clap::Args, Clone, Debug)]
+pub struct NoteListArgs {
+    /// Only list notes of these revisions
+    #[arg(
+        long,
+        short,
+        default_value = "all()",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+pub fn cmd_note_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let notes_ref = notes_ref(command)?;
+    let notes = git::read_notes(workspace_command.repo().store(), &notes_ref)?;
+    let repo = workspace_command.repo();
+    let mut noted_ids = vec![];
+    for id in notes.keys() {
+        // Notes can be attached to objects that aren't imported commits
+        if repo.index().has_id(id)? {
+            noted_ids.push(id.clone());
+        }
+    }
+    let mut revset_expression = workspace_command.parse_union_revsets(ui, &args.revisions)?;
+    revset_expression.intersect_with(&RevsetExpression::commits(noted_ids));
+    let revset = revset_expression.evaluate()?;
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for commit in revset.iter().commits(repo.store()) {
+        let commit = commit?;
+        let note = &notes[commit.id()];
+        workspace_command.write_commit_summary(formatter.as_mut(), &commit)?;
+        writeln!(formatter)?;
+        for line in note.lines() {
+            writeln!(formatter, "  {line}")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod add;
+mod list;
+mod remove;
+mod show;
+
+use self::add::NoteAddArgs;
+use self::add::cmd_note_add;
+use self::list::NoteListArgs;
+use self::list::cmd_note_list;
+use self::remove::NoteRemoveArgs;
+use self::remove::cmd_note_remove;
+use self::show::NoteShowArgs;
+use self::show::cmd_note_show;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage Git notes attached to revisions
+///
+/// Notes are stored in the underlying Git repo in the ref set by the
+/// `git.notes-ref` config (`refs/notes/commits` by default), so they are
+/// shared with `git notes` in colocated repos. They are not recorded in the
+/// operation log, and `jj undo` doesn't revert them.
+///
+/// Notes are attached to commits, not changes. When a revision is rewritten,
+/// its note stays with the old commit.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum NoteCommand {
+    Add(NoteAddArgs),
+    #[command(visible_alias("l"))]
+    List(NoteListArgs),
+    Remove(NoteRemoveArgs),
+    Show(NoteShowArgs),
+}
+
+pub fn cmd_note(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &NoteCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        NoteCommand::Add(args) => cmd_note_add(ui, command, args),
+        NoteCommand::List(args) => cmd_note_list(ui, command, args),
+        NoteCommand::Remove(args) => cmd_note_remove(ui, command, args),
+        NoteCommand::Show(args) => cmd_note_show(ui, command, args),
+    }
+}
+
+fn notes_ref(command: &CommandHelper) -> Result<String, CommandError> {
+    Ok(command.settings().get_string("git.notes-ref")?)
+}
@@ -0,0 +1,61 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+
+use super::notes_ref;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Show the note of a revision
+#[derive(clap::Args, Clone, Debug)]
+pub struct NoteShowArgs {
+    /// The revision to show the note of
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+}
+
+pub fn cmd_note_show(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteShowArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let notes_ref = notes_ref(command)?;
+    let mut notes = git::read_notes(workspace_command.repo().store(), &notes_ref)?;
+    let Some(note) = notes.remove(commit.id()) else {
+        return Err(user_error(format!(
+            "Revision {} has no note",
+            workspace_command.format_commit_summary(&commit)
+        )));
+    };
+    ui.request_pager();
+    write!(ui.stdout(), "{note}")?;
+    Ok(())
+}
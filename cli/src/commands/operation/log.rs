@@ -23,7 +23,6 @@ use jj_lib::op_walk;
 use jj_lib::operation::Operation;
 use jj_lib::repo::RepoLoader;
 
-use super::diff::show_op_diff;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::WorkspaceCommandEnvironment;
@@ -35,7 +34,9 @@ use crate::diff_util::DiffRenderer;
 use crate::diff_util::diff_formats_for_log;
 use crate::formatter::Formatter;
 use crate::graphlog::GraphStyle;
+use crate::graphlog::colored_lanes_from_settings;
 use crate::graphlog::get_graphlog;
+use crate::op_diff_util::show_op_diff;
 use crate::operation_templater::OperationTemplateLanguage;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
@@ -117,6 +118,7 @@ fn do_op_log(
 ) -> Result<(), CommandError> {
     let settings = repo_loader.settings();
     let graph_style = GraphStyle::from_settings(settings)?;
+    let colored_lanes = colored_lanes_from_settings(ui, settings)?;
     let with_content_format = LogContentFormat::new(ui, settings)?;
 
     let template: TemplateRenderer<Operation>;
@@ -165,11 +167,13 @@ fn do_op_log(
             };
             let path_converter = workspace_env.path_converter();
             let conflict_marker_style = workspace_env.conflict_marker_style();
+            let conflict_marker_style_overrides = workspace_env.conflict_marker_style_overrides();
             let diff_renderer = (!diff_formats.is_empty()).then(|| {
                 DiffRenderer::new(
                     repo.as_ref(),
                     path_converter,
                     conflict_marker_style,
+                    conflict_marker_style_overrides,
                     diff_formats.clone(),
                 )
             });
@@ -204,7 +208,7 @@ fn do_op_log(
 
     if !args.no_graph {
         let mut raw_output = formatter.raw()?;
-        let mut graph = get_graphlog(graph_style, raw_output.as_mut());
+        let mut graph = get_graphlog(graph_style, colored_lanes, raw_output.as_mut());
         let iter = iter.map(|op| -> Result<_, OpStoreError> {
             let op = op?;
             let ids = op.parent_ids();
@@ -15,6 +15,7 @@
 mod abandon;
 mod diff;
 mod log;
+mod prune;
 mod restore;
 pub mod revert;
 mod show;
@@ -26,6 +27,8 @@ use diff::OperationDiffArgs;
 use diff::cmd_op_diff;
 use log::OperationLogArgs;
 use log::cmd_op_log;
+use prune::OperationPruneArgs;
+use prune::cmd_op_prune;
 use restore::OperationRestoreArgs;
 use restore::cmd_op_restore;
 use revert::OperationRevertArgs;
@@ -49,6 +52,7 @@ pub enum OperationCommand {
     Abandon(OperationAbandonArgs),
     Diff(OperationDiffArgs),
     Log(OperationLogArgs),
+    Prune(OperationPruneArgs),
     Restore(OperationRestoreArgs),
     Revert(OperationRevertArgs),
     Show(OperationShowArgs),
@@ -66,6 +70,7 @@ pub fn cmd_operation(
         OperationCommand::Abandon(args) => cmd_op_abandon(ui, command, args),
         OperationCommand::Diff(args) => cmd_op_diff(ui, command, args),
         OperationCommand::Log(args) => cmd_op_log(ui, command, args),
+        OperationCommand::Prune(args) => cmd_op_prune(ui, command, args),
         OperationCommand::Restore(args) => cmd_op_restore(ui, command, args),
         OperationCommand::Revert(args) => cmd_op_revert(ui, command, args),
         OperationCommand::Show(args) => cmd_op_show(ui, command, args),
@@ -116,5 +121,7 @@ pub(crate) fn view_with_desired_portions_restored(
         git_refs: current_view.git_refs.clone(),
         git_head: current_view.git_head.clone(),
         wc_commit_ids: repo_source.wc_commit_ids.clone(),
+        archived_bookmarks: repo_source.archived_bookmarks.clone(),
+        stashes: repo_source.stashes.clone(),
     }
 }
@@ -0,0 +1,213 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write as _;
+use std::iter;
+use std::slice;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use itertools::Itertools as _;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::op_store::OperationId;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use pollster::FutureExt as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::parse_date_after;
+use crate::cli_util::short_operation_hash;
+use crate::command_error::CommandError;
+use crate::command_error::cli_error;
+use crate::command_error::user_error_with_hint;
+use crate::ui::Ui;
+
+/// Abandon operations according to a retention policy
+///
+/// Operations are kept if they're among the `--keep-count` most recent
+/// operations, or if they finished at or after `--keep-since`. The current
+/// operations are always kept. If neither option is given, the
+/// `operation.retention.keep-count` and `operation.retention.keep-since`
+/// settings are used.
+///
+/// The other operations are abandoned as with `jj op abandon`, and the
+/// descendants of the abandoned operations are reparented onto the root
+/// operation. Abandoned operations and views that are older than 2 weeks are
+/// then deleted. Use `jj util gc` to delete the commits that are no longer
+/// reachable.
+///
+/// The working copies of other workspaces are updated to refer to the
+/// reparented operations. This command fails if the working copy of a
+/// workspace refers to an operation that would be abandoned.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationPruneArgs {
+    /// Keep the given number of most recent operations
+    #[arg(long, value_name = "COUNT")]
+    keep_count: Option<usize>,
+    /// Keep operations that finished at or after the given date
+    ///
+    /// The date can be absolute like `2025-01-01`, or relative like `3 months
+    /// ago`.
+    #[arg(long, value_name = "DATE")]
+    keep_since: Option<String>,
+}
+
+pub fn cmd_op_prune(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationPruneArgs,
+) -> Result<(), CommandError> {
+    // Don't load the repo so that this command can be used to recover from
+    // corrupted repo state.
+    let mut workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op_store = repo_loader.op_store().clone();
+    let op_heads_store = repo_loader.op_heads_store();
+    if command.global_args().at_operation.is_some() {
+        return Err(cli_error("--at-op is not respected"));
+    }
+    let settings = command.settings();
+    let (keep_count, keep_since) = if args.keep_count.is_none() && args.keep_since.is_none() {
+        (
+            settings
+                .get::<usize>("operation.retention.keep-count")
+                .optional()?,
+            settings
+                .get_string("operation.retention.keep-since")
+                .optional()?,
+        )
+    } else {
+        (args.keep_count, args.keep_since.clone())
+    };
+    if keep_count.is_none() && keep_since.is_none() {
+        return Err(user_error_with_hint(
+            "No retention policy is specified",
+            "Use `--keep-count` or `--keep-since`, or set `operation.retention.keep-count` or \
+             `operation.retention.keep-since`.",
+        ));
+    }
+    let keep_since = keep_since
+        .map(|date| parse_date_after(settings, &date))
+        .transpose()?;
+
+    let current_head_ops = op_walk::get_current_head_ops(&op_store, op_heads_store.as_ref())?;
+    let root_op = repo_loader.root_operation();
+    let mut prune_ops = vec![];
+    for (index, op) in op_walk::walk_ancestors(&current_head_ops).enumerate() {
+        let op = op?;
+        if op.id() == root_op.id() || current_head_ops.contains(&op) {
+            continue;
+        }
+        let keep = keep_count.is_some_and(|count| index < count)
+            || keep_since.is_some_and(|pattern| pattern.matches(&op.metadata().time.end));
+        if !keep {
+            prune_ops.push(op);
+        }
+    }
+    if prune_ops.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    let pruned_op_ids: HashSet<OperationId> = op_walk::walk_ancestors(&prune_ops)
+        .map_ok(|op| op.id().clone())
+        .try_collect()?;
+
+    // The working copies of all workspaces have to be remapped to the
+    // reparented operations.
+    let mut workspaces = vec![];
+    if !command.global_args().ignore_working_copy {
+        let workspace_names: BTreeSet<WorkspaceNameBuf> = current_head_ops
+            .iter()
+            .map(|op| op.view())
+            .map_ok(|view| view.wc_commit_ids().keys().cloned().collect_vec())
+            .flatten_ok()
+            .try_collect()?;
+        let known_paths = SimpleWorkspaceStore::load(workspace.repo_path()).workspace_paths()?;
+        for name in &workspace_names {
+            if &**name == workspace.workspace_name() {
+                continue;
+            }
+            match known_paths.get(name) {
+                Some(path) if path.is_dir() => {
+                    let other = command.load_workspace_at(path, settings)?;
+                    if other.workspace_name() == &**name {
+                        workspaces.push(other);
+                    }
+                }
+                _ => writeln!(
+                    ui.warning_default(),
+                    "The working copy of workspace {} can't be updated because its location is \
+                     unknown",
+                    name.as_symbol()
+                )?,
+            }
+        }
+    }
+    for ws in iter::once(&workspace).chain(&workspaces) {
+        let op_id = ws.working_copy().operation_id();
+        if pruned_op_ids.contains(op_id) {
+            return Err(user_error_with_hint(
+                format!(
+                    "The working copy of workspace {} is at operation {}, which would be abandoned",
+                    ws.workspace_name().as_symbol(),
+                    short_operation_hash(op_id)
+                ),
+                "Run `jj status` in the workspace to update the working copy, then try again.",
+            ));
+        }
+    }
+
+    // Reparent the working-copy operations along with the heads.
+    let mut head_ops = current_head_ops.clone();
+    for ws in iter::once(&workspace).chain(&workspaces) {
+        let op_id = ws.working_copy().operation_id();
+        if !head_ops.iter().any(|op| op.id() == op_id) {
+            head_ops.push(repo_loader.load_operation(op_id)?);
+        }
+    }
+    let stats = op_walk::reparent_range(op_store.as_ref(), &prune_ops, &head_ops, &root_op)?;
+    let new_op_ids: HashMap<&OperationId, &OperationId> =
+        iter::zip(head_ops.iter().map(Operation::id), &stats.new_head_ids).collect();
+    writeln!(
+        ui.status(),
+        "Abandoned {} operations and reparented {} descendant operations.",
+        stats.unreachable_count,
+        stats.rewritten_count,
+    )?;
+    for old in &current_head_ops {
+        let new_id = new_op_ids[old.id()];
+        if old.id() != new_id {
+            op_heads_store
+                .update_op_heads(slice::from_ref(old.id()), new_id)
+                .block_on()?;
+        }
+    }
+    for ws in iter::once(&mut workspace).chain(&mut workspaces) {
+        let mut locked_ws = ws.start_working_copy_mutation()?;
+        let old_op_id = locked_ws.locked_wc().old_operation_id();
+        // The working copy may have been updated concurrently.
+        if let Some(&new_id) = new_op_ids.get(old_op_id) {
+            locked_ws.finish(new_id.clone())?;
+        }
+    }
+
+    let keep_newer = SystemTime::now() - Duration::from_secs(14 * 86400);
+    op_store.gc(&stats.new_head_ids, keep_newer)?;
+    Ok(())
+}
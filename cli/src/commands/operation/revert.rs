@@ -30,7 +30,8 @@ use crate::ui::Ui;
 /// Create a new operation that reverts an earlier operation
 ///
 /// This reverts an individual operation by applying the inverse of the
-/// operation.
+/// operation. Unlike `jj op restore`, the changes made by the later operations
+/// are kept.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationRevertArgs {
     /// The operation to revert
@@ -16,7 +16,6 @@ use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::operation::Operation;
 
-use super::diff::show_op_diff;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::command_error::CommandError;
@@ -25,6 +24,7 @@ use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
 use crate::diff_util::diff_formats_for_log;
 use crate::graphlog::GraphStyle;
+use crate::op_diff_util::show_op_diff;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
@@ -94,11 +94,13 @@ pub fn cmd_op_show(
         let formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
         let path_converter = workspace_env.path_converter();
         let conflict_marker_style = workspace_env.conflict_marker_style();
+        let conflict_marker_style_overrides = workspace_env.conflict_marker_style_overrides();
         (!formats.is_empty()).then(|| {
             DiffRenderer::new(
                 repo.as_ref(),
                 path_converter,
                 conflict_marker_style,
+                conflict_marker_style_overrides,
                 formats,
             )
         })
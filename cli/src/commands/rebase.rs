@@ -328,6 +328,10 @@ pub(crate) struct RebaseArgs {
     /// destination with identical changes.
     #[arg(long)]
     keep_divergent: bool,
+
+    /// Show the changes that would be made, without making them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[derive(clap::Args, Clone, Debug)]
@@ -402,9 +406,14 @@ pub(crate) fn cmd_rebase(
         if !abandoned_divergent.is_empty()
             && let Some(mut formatter) = ui.status_formatter()
         {
+            let verb = if args.dry_run {
+                "Would abandon"
+            } else {
+                "Abandoned"
+            };
             writeln!(
                 formatter,
-                "Abandoned {} divergent commits that were already present in the destination:",
+                "{verb} {} divergent commits that were already present in the destination:",
                 abandoned_divergent.len(),
             )?;
             print_updated_commits(
@@ -415,7 +424,10 @@ pub(crate) fn cmd_rebase(
         }
     };
     let stats = computed_move.apply(tx.repo_mut(), &rebase_options)?;
-    print_move_commits_stats(ui, &stats)?;
+    print_move_commits_stats(ui, &stats, args.dry_run)?;
+    if args.dry_run {
+        return tx.finish_dry_run(ui);
+    }
     tx.finish(ui, tx_description(&loc.target))?;
 
     Ok(())
@@ -584,7 +596,13 @@ fn tx_description(target: &MoveCommitsTarget) -> String {
 }
 
 /// Print details about the provided [`MoveCommitsStats`].
-fn print_move_commits_stats(ui: &Ui, stats: &MoveCommitsStats) -> std::io::Result<()> {
+///
+/// With `dry_run`, the messages describe what would have happened.
+fn print_move_commits_stats(
+    ui: &Ui,
+    stats: &MoveCommitsStats,
+    dry_run: bool,
+) -> std::io::Result<()> {
     let Some(mut formatter) = ui.status_formatter() else {
         return Ok(());
     };
@@ -595,28 +613,33 @@ fn print_move_commits_stats(ui: &Ui, stats: &MoveCommitsStats) -> std::io::Resul
         num_abandoned_empty,
         rebased_commits: _,
     } = stats;
+    let (skipped, rebased, abandoned) = if dry_run {
+        ("Would skip", "Would rebase", "Would abandon")
+    } else {
+        ("Skipped", "Rebased", "Abandoned")
+    };
     if num_skipped_rebases > 0 {
         writeln!(
             formatter,
-            "Skipped rebase of {num_skipped_rebases} commits that were already in place"
+            "{skipped} rebase of {num_skipped_rebases} commits that were already in place"
         )?;
     }
     if num_rebased_targets > 0 {
         writeln!(
             formatter,
-            "Rebased {num_rebased_targets} commits to destination"
+            "{rebased} {num_rebased_targets} commits to destination"
         )?;
     }
     if num_rebased_descendants > 0 {
         writeln!(
             formatter,
-            "Rebased {num_rebased_descendants} descendant commits"
+            "{rebased} {num_rebased_descendants} descendant commits"
         )?;
     }
     if num_abandoned_empty > 0 {
         writeln!(
             formatter,
-            "Abandoned {num_abandoned_empty} newly emptied commits"
+            "{abandoned} {num_abandoned_empty} newly emptied commits"
         )?;
     }
     Ok(())
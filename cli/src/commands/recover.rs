@@ -0,0 +1,255 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write as _;
+use std::slice;
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::rewrite::CommitRewriter;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::print_updated_commits;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Recover abandoned changes
+///
+/// Without arguments, lists the changes that were hidden by recent operations,
+/// most recent first, along with the operation that hid them. Only changes
+/// that have no visible revisions left are listed. Empty revisions without a
+/// description are omitted.
+///
+/// With arguments, makes the given revisions visible again. Since hidden
+/// revisions are not matched by most revset expressions, they are usually
+/// specified by commit ID. If the parent of a recovered revision has been
+/// rewritten since, the recovered revision is rebased onto the new version of
+/// the parent. If the parent is hidden and isn't recovered along with it, the
+/// recovered revision is rebased onto the nearest visible ancestors instead.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct RecoverArgs {
+    /// The revision(s) to recover
+    #[arg(value_name = "REVSETS")]
+    revisions: Vec<RevisionArg>,
+    /// Limit the number of changes to list
+    #[arg(long, short = 'n', default_value_t = 10)]
+    limit: usize,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_recover(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &RecoverArgs,
+) -> Result<(), CommandError> {
+    if args.revisions.is_empty() {
+        list_hidden_changes(ui, command, args.limit)
+    } else {
+        recover_revisions(ui, command, &args.revisions)
+    }
+}
+
+fn list_hidden_changes(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    limit: usize,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().as_ref();
+    let commit_summary_template = workspace_command.commit_summary_template();
+    let op_summary_template = workspace_command.operation_summary_template();
+
+    let hidden = find_hidden_changes(repo, limit)?;
+    if hidden.is_empty() {
+        writeln!(ui.status(), "No recently hidden changes.")?;
+        return Ok(());
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (commit, op) in &hidden {
+        commit_summary_template.format(commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+        write!(formatter, "  Hidden by: ")?;
+        op_summary_template.format(op, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    drop(formatter);
+    writeln!(
+        ui.hint_default(),
+        "Use `jj recover <commit id>` to make a revision visible again."
+    )?;
+    Ok(())
+}
+
+/// Walks the operation log back from the current operation, collecting up to
+/// `limit` hidden changes along with the operation that hid them.
+fn find_hidden_changes(
+    repo: &ReadonlyRepo,
+    limit: usize,
+) -> Result<Vec<(Commit, Operation)>, CommandError> {
+    let mut seen_change_ids = HashSet::new();
+    let mut hidden = vec![];
+    for op in op_walk::walk_ancestors(slice::from_ref(repo.operation())) {
+        if hidden.len() >= limit {
+            break;
+        }
+        let op = op?;
+        if op.parent_ids().is_empty() {
+            continue;
+        }
+        let parent_heads: Vec<CommitId> = op
+            .parents()
+            .map(|parent| Ok::<_, CommandError>(parent?.view()?.heads().clone()))
+            .flatten_ok()
+            .try_collect()?;
+        let op_heads = op.view()?.heads().iter().cloned().collect_vec();
+        let newly_hidden = RevsetExpression::commits(op_heads)
+            .range(&RevsetExpression::commits(parent_heads))
+            .evaluate(repo)?;
+        for item in newly_hidden.commit_change_ids() {
+            let (commit_id, change_id) = item?;
+            if !seen_change_ids.insert(change_id.clone()) {
+                continue;
+            }
+            if repo.resolve_change_id(&change_id)?.is_some() {
+                continue;
+            }
+            let commit = repo.store().get_commit(&commit_id)?;
+            if commit.is_discardable(repo)? {
+                continue;
+            }
+            hidden.push((commit, op.clone()));
+            if hidden.len() >= limit {
+                break;
+            }
+        }
+    }
+    Ok(hidden)
+}
+
+fn recover_revisions(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    revisions: &[RevisionArg],
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let target_expr = workspace_command
+        .parse_union_revsets(ui, revisions)?
+        .resolve()?;
+    let hidden_expr = target_expr.minus(&RevsetExpression::visible_heads().ancestors());
+    let targets: Vec<_> = target_expr
+        .evaluate(workspace_command.repo().as_ref())?
+        .iter()
+        .try_collect()?;
+    // Parents first, so that recovered children can be rebased onto the
+    // recovered parents.
+    let to_recover: Vec<Commit> = hidden_expr
+        .evaluate(workspace_command.repo().as_ref())?
+        .iter()
+        .commits(workspace_command.repo().store())
+        .try_collect::<_, Vec<_>, _>()?
+        .into_iter()
+        .rev()
+        .collect();
+    if to_recover.len() < targets.len() {
+        writeln!(
+            ui.status(),
+            "Skipping {n} revisions that are already visible.",
+            n = targets.len() - to_recover.len()
+        )?;
+    }
+    if to_recover.is_empty() {
+        writeln!(ui.status(), "No revisions to recover.")?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let mut new_ids: HashMap<CommitId, CommitId> = HashMap::new();
+    let mut recovered = vec![];
+    for commit in &to_recover {
+        let mut new_parent_ids = vec![];
+        for parent_id in commit.parent_ids() {
+            if let Some(new_id) = new_ids.get(parent_id) {
+                new_parent_ids.push(new_id.clone());
+            } else {
+                new_parent_ids.extend(visible_version(tx.repo(), parent_id)?);
+            }
+        }
+        let new_parent_ids = new_parent_ids.into_iter().unique().collect_vec();
+        let new_commit = if new_parent_ids == commit.parent_ids() {
+            tx.repo_mut().add_head(commit)?;
+            commit.clone()
+        } else {
+            // The hidden commit mustn't be recorded as rewritten. Otherwise its
+            // hidden parents would become visible again as heads.
+            CommitRewriter::new(tx.repo_mut(), commit.clone(), new_parent_ids)
+                .rebase()
+                .block_on()?
+                .clear_rewrite_source()
+                .write()?
+        };
+        new_ids.insert(commit.id().clone(), new_commit.id().clone());
+        recovered.push(new_commit);
+    }
+    tx.repo_mut().rebase_descendants()?;
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Recovered {} revisions:", recovered.len())?;
+        print_updated_commits(
+            formatter.as_mut(),
+            &tx.commit_summary_template(),
+            &recovered,
+        )?;
+    }
+    let transaction_description = if to_recover.len() == 1 {
+        format!("recover commit {}", to_recover[0].id())
+    } else {
+        format!(
+            "recover commit {} and {} more",
+            to_recover[0].id(),
+            to_recover.len() - 1
+        )
+    };
+    tx.finish(ui, transaction_description)?;
+    Ok(())
+}
+
+/// Returns the visible commit of the same change as `commit_id`. If the change
+/// has no single visible commit, returns the nearest visible ancestors of
+/// `commit_id` instead, so hidden ancestors don't become visible again.
+fn visible_version(repo: &dyn Repo, commit_id: &CommitId) -> Result<Vec<CommitId>, CommandError> {
+    let commit = repo.store().get_commit(commit_id)?;
+    if let Some([id]) = repo.resolve_change_id(commit.change_id())?.as_deref() {
+        return Ok(vec![id.clone()]);
+    }
+    let visible_ancestors = RevsetExpression::commit(commit_id.clone())
+        .ancestors()
+        .intersection(&RevsetExpression::visible_heads().ancestors())
+        .heads();
+    let ids: Vec<_> = visible_ancestors.evaluate(repo)?.iter().try_collect()?;
+    Ok(ids)
+}
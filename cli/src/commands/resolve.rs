@@ -12,19 +12,49 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
+use std::iter::zip;
+use std::sync::Arc;
+
+use bstr::BString;
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendResult;
+use jj_lib::backend::CopyId;
+use jj_lib::backend::TreeValue;
+use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
+use jj_lib::conflicts::ConflictMaterializeOptions;
+use jj_lib::conflicts::MaterializedFileConflictValue;
+use jj_lib::conflicts::choose_materialized_conflict_marker_len;
+use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::parse_conflict;
+use jj_lib::conflicts::try_materialize_file_conflict_value;
+use jj_lib::conflicts::update_from_content;
+use jj_lib::files;
+use jj_lib::files::MergeResult;
+use jj_lib::merge::Merge;
+use jj_lib::merge::MergedTreeValue;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::merged_tree::MergedTreeBuilder;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::store::Store;
+use jj_lib::tree_merge::resolve_file_values;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::complete;
+use crate::description_util::TextEditor;
 use crate::formatter::FormatterExt as _;
 use crate::ui::Ui;
 
@@ -36,6 +66,9 @@ use crate::ui::Ui;
 /// resolved. To stop resolving conflicts, exit the merge tool without making
 /// any changes.
 ///
+/// With `--interactive`, conflicts are instead resolved hunk by hunk by
+/// answering prompts, without an external merge tool.
+///
 /// Note that conflicts can also be resolved without using this command. You may
 /// edit the conflict markers in the conflicted file directly with a text
 /// editor.
@@ -71,6 +104,23 @@ pub(crate) struct ResolveArgs {
         add = ArgValueCandidates::new(complete::merge_editors),
     )]
     tool: Option<String>,
+    /// Resolve the conflicts by taking the given side without running a merge
+    /// tool
+    ///
+    /// `left` and `right` are side #1 and side #2 of the conflict, and `base`
+    /// is the common ancestor. Unlike `--tool=:ours`, this also works for
+    /// conflicts involving deleted files or non-file values. Conflicts with
+    /// more than two sides are left unresolved.
+    #[arg(long, value_enum, value_name = "SIDE", conflicts_with_all = ["list", "tool"])]
+    take: Option<ResolveSide>,
+    /// Walk through the conflicts hunk by hunk and resolve them interactively
+    ///
+    /// For each conflicted hunk, you can pick one of the sides, edit the hunk
+    /// in the text editor, or leave it conflicted. An edited hunk which still
+    /// has conflict markers stays conflicted. Files with hunks left conflicted
+    /// are written back with the remaining conflict markers.
+    #[arg(long, short, conflicts_with_all = ["list", "tool", "take"])]
+    interactive: bool,
     /// Only resolve conflicts in these paths. You can use the `--list` argument
     /// to find paths to use here.
     #[arg(
@@ -81,6 +131,13 @@ pub(crate) struct ResolveArgs {
     paths: Vec<String>,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum ResolveSide {
+    Left,
+    Right,
+    Base,
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_resolve(
     ui: &mut Ui,
@@ -114,14 +171,65 @@ pub(crate) fn cmd_resolve(
         );
     };
 
-    let repo_paths = conflicts
-        .iter()
-        .map(|(path, _)| path.as_ref())
-        .collect_vec();
     workspace_command.check_rewritable([commit.id()])?;
-    let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
+    if let Some(side) = args.take {
+        let (new_tree, skipped_paths) = take_conflict_side(&tree, conflicts, side)?;
+        if !skipped_paths.is_empty()
+            && let Some(mut formatter) = ui.status_formatter()
+        {
+            writeln!(
+                formatter.labeled("warning").with_heading("Warning: "),
+                "Skipped conflicts with more than two sides:"
+            )?;
+            for path in &skipped_paths {
+                writeln!(formatter, "  {}", workspace_command.format_file_path(path))?;
+            }
+        }
+        let mut tx = workspace_command.start_transaction();
+        tx.repo_mut()
+            .rewrite_commit(&commit)
+            .set_tree(new_tree)
+            .write()?;
+        tx.finish(
+            ui,
+            format!("Resolve conflicts in commit {}", commit.id().hex()),
+        )?;
+        return Ok(());
+    }
+    if args.interactive {
+        let text_editor = workspace_command.text_editor()?;
+        let marker_style = workspace_command.env().conflict_marker_style();
+        let marker_style_overrides = workspace_command.env().conflict_marker_style_overrides();
+        let new_tree = resolve_hunks_interactively(
+            ui,
+            &workspace_command,
+            &text_editor,
+            marker_style,
+            marker_style_overrides,
+            &tree,
+            conflicts,
+        )?;
+        let mut tx = workspace_command.start_transaction();
+        tx.repo_mut()
+            .rewrite_commit(&commit)
+            .set_tree(new_tree)
+            .write()?;
+        tx.finish(
+            ui,
+            format!("Resolve conflicts in commit {}", commit.id().hex()),
+        )?;
+        return Ok(());
+    }
+    // Paths with a configured merge driver don't need a merge tool.
+    let (tree, unresolved_paths) = resolve_with_merge_drivers(&tree, conflicts)?;
+    let (new_tree, partial_resolution_error) = if unresolved_paths.is_empty() {
+        (tree, None)
+    } else {
+        let repo_paths = unresolved_paths.iter().map(AsRef::as_ref).collect_vec();
+        let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
+        merge_editor.edit_files(ui, &tree, &repo_paths)?
+    };
     let mut tx = workspace_command.start_transaction();
-    let (new_tree, partial_resolution_error) = merge_editor.edit_files(ui, &tree, &repo_paths)?;
     let new_commit = tx
         .repo_mut()
         .rewrite_commit(&commit)
@@ -153,3 +261,225 @@ pub(crate) fn cmd_resolve(
     }
     Ok(())
 }
+
+/// Resolves conflicts in the paths matching a configured merge driver. Returns
+/// the new tree and the paths that are still conflicted.
+fn resolve_with_merge_drivers(
+    tree: &MergedTree,
+    conflicts: Vec<(RepoPathBuf, BackendResult<MergedTreeValue>)>,
+) -> Result<(MergedTree, Vec<RepoPathBuf>), CommandError> {
+    let store = tree.store();
+    let drivers = &store.merge_options().drivers;
+    if drivers.is_empty() {
+        let paths = conflicts.into_iter().map(|(path, _)| path).collect();
+        return Ok((tree.clone(), paths));
+    }
+    let mut tree_builder = MergedTreeBuilder::new(tree.clone());
+    let mut unresolved_paths = vec![];
+    for (path, value) in conflicts {
+        if drivers.find(&path).is_some() {
+            let resolved = resolve_file_values(store, &path, value?).block_on()?;
+            if resolved.is_resolved() {
+                tree_builder.set_or_remove(path, resolved);
+                continue;
+            }
+        }
+        unresolved_paths.push(path);
+    }
+    Ok((tree_builder.write_tree()?, unresolved_paths))
+}
+
+/// Resolves 2-sided conflicts by taking the `side`. Returns the new tree and
+/// the paths of the conflicts that were left unresolved.
+fn take_conflict_side(
+    tree: &MergedTree,
+    conflicts: Vec<(RepoPathBuf, BackendResult<MergedTreeValue>)>,
+    side: ResolveSide,
+) -> Result<(MergedTree, Vec<RepoPathBuf>), CommandError> {
+    let mut tree_builder = MergedTreeBuilder::new(tree.clone());
+    let mut skipped_paths = vec![];
+    for (path, value) in conflicts {
+        let value = value?;
+        if value.num_sides() != 2 {
+            skipped_paths.push(path);
+            continue;
+        }
+        let taken = match side {
+            ResolveSide::Left => value.get_add(0),
+            ResolveSide::Right => value.get_add(1),
+            ResolveSide::Base => value.get_remove(0),
+        };
+        let taken = taken
+            .expect("2-sided conflict should have the side")
+            .clone();
+        tree_builder.set_or_remove(path, Merge::resolved(taken));
+    }
+    Ok((tree_builder.write_tree()?, skipped_paths))
+}
+
+/// How the user chose to resolve a conflicted hunk.
+enum HunkChoice {
+    Side(usize),
+    Base,
+    Edit,
+    Skip,
+    Quit,
+}
+
+/// Prompts for the resolution of each conflicted hunk in the file conflicts.
+/// Hunks which aren't resolved are left as conflicts.
+fn resolve_hunks_interactively(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    text_editor: &TextEditor,
+    marker_style: ConflictMarkerStyle,
+    marker_style_overrides: &ConflictMarkerStyleOverrides,
+    tree: &MergedTree,
+    conflicts: Vec<(RepoPathBuf, BackendResult<MergedTreeValue>)>,
+) -> Result<MergedTree, CommandError> {
+    let store = tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(tree.clone());
+    for (path, value) in conflicts {
+        let value = value?;
+        let Some(file) = try_materialize_file_conflict_value(store, &path, &value).block_on()?
+        else {
+            writeln!(
+                ui.warning_default(),
+                "Skipping {path}: {summary} can't be resolved hunk by hunk",
+                path = workspace_command.format_file_path(&path),
+                summary = value.describe(),
+            )?;
+            continue;
+        };
+        let MergeResult::Conflict(mut hunks) =
+            files::merge_hunks(&file.contents, store.merge_options())
+        else {
+            continue;
+        };
+        let marker_style = marker_style_overrides.style_for(&path, marker_style);
+        let num_conflicts = hunks.iter().filter(|hunk| !hunk.is_resolved()).count();
+        let num_sides = file.contents.num_sides();
+        let mut conflict_index = 0;
+        let mut changed = false;
+        let mut quit = false;
+        for hunk in &mut hunks {
+            if hunk.is_resolved() {
+                continue;
+            }
+            conflict_index += 1;
+            let marker_len = choose_materialized_conflict_marker_len(hunk);
+            let options = ConflictMaterializeOptions {
+                marker_style,
+                marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+                marker_len: Some(marker_len),
+                merge: store.merge_options().clone(),
+            };
+            let materialized = materialize_merge_result_to_bytes(hunk, &options);
+            writeln!(
+                ui.stderr(),
+                "Conflict {conflict_index} of {num_conflicts} in {path}:",
+                path = workspace_command.format_file_path(&path),
+            )?;
+            ui.stderr().write_all(&materialized)?;
+            let sides = (1..=num_sides).map(|i| i.to_string()).join("/");
+            let base_choice = if num_sides == 2 { ", (b)ase" } else { "" };
+            let prompt = format!("Take side ({sides}){base_choice}, (e)dit, (s)kip, or (q)uit");
+            let choice = ui.prompt_choice_with(&prompt, None, |input| match input {
+                "b" if num_sides == 2 => Ok(HunkChoice::Base),
+                "e" => Ok(HunkChoice::Edit),
+                "s" => Ok(HunkChoice::Skip),
+                "q" => Ok(HunkChoice::Quit),
+                _ => match input.parse::<usize>() {
+                    Ok(side) if (1..=num_sides).contains(&side) => Ok(HunkChoice::Side(side - 1)),
+                    _ => Err("unrecognized response"),
+                },
+            })?;
+            let resolution = match choice {
+                HunkChoice::Side(index) => Merge::resolved(hunk.get_add(index).unwrap().clone()),
+                HunkChoice::Base => Merge::resolved(hunk.get_remove(0).unwrap().clone()),
+                HunkChoice::Edit => {
+                    let edited = text_editor
+                        .edit_str(&materialized, None)
+                        .map_err(|err| err.with_name("conflict hunk"))?;
+                    // Conflict markers left in the edited hunk keep it conflicted,
+                    // the same way as when editing the file in the working copy.
+                    match parse_conflict(edited.as_bytes(), num_sides, marker_len) {
+                        Some(edited_hunks) => concat_hunks(num_sides, &edited_hunks),
+                        None => Merge::resolved(edited.into()),
+                    }
+                }
+                HunkChoice::Skip => continue,
+                HunkChoice::Quit => {
+                    quit = true;
+                    break;
+                }
+            };
+            *hunk = resolution;
+            changed = true;
+        }
+        if changed {
+            let new_value = write_partially_resolved_file(store, &path, &value, &file, &hunks)?;
+            tree_builder.set_or_remove(path, new_value);
+        }
+        if quit {
+            break;
+        }
+    }
+    Ok(tree_builder.write_tree()?)
+}
+
+/// Writes the file with the resolved `hunks` applied to all sides of the
+/// conflict, keeping the remaining hunks conflicted.
+fn write_partially_resolved_file(
+    store: &Arc<Store>,
+    path: &RepoPath,
+    value: &MergedTreeValue,
+    file: &MaterializedFileConflictValue,
+    hunks: &[Merge<BString>],
+) -> Result<MergedTreeValue, CommandError> {
+    let contents = concat_hunks(file.contents.num_sides(), hunks);
+    // Go through the materialized form so the conflict is stored the same way
+    // as if the conflict markers were edited in the working copy.
+    let marker_len = choose_materialized_conflict_marker_len(&contents);
+    let options = ConflictMaterializeOptions {
+        marker_style: ConflictMarkerStyle::Diff,
+        marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+        marker_len: Some(marker_len),
+        merge: store.merge_options().clone(),
+    };
+    let materialized = materialize_merge_result_to_bytes(&contents, &options);
+    let new_file_ids = update_from_content(
+        &file.unsimplified_ids,
+        store,
+        path,
+        &materialized,
+        marker_len,
+    )
+    .block_on()?;
+    match new_file_ids.into_resolved() {
+        Ok(file_id) => Ok(Merge::normal(TreeValue::File {
+            id: file_id.expect("resolved content should be written"),
+            executable: file.executable.unwrap_or(false),
+            copy_id: file.copy_id.clone().unwrap_or_else(CopyId::placeholder),
+        })),
+        Err(new_file_ids) => Ok(value.with_new_file_ids(&new_file_ids)),
+    }
+}
+
+/// Concatenates the `hunks` of a conflict with `num_sides` sides. Resolved
+/// hunks are appended to all sides.
+fn concat_hunks(num_sides: usize, hunks: &[Merge<BString>]) -> Merge<BString> {
+    let mut contents = Merge::from_vec(vec![BString::default(); num_sides * 2 - 1]);
+    for hunk in hunks {
+        if let Some(resolved) = hunk.as_resolved() {
+            for content in contents.iter_mut() {
+                content.extend_from_slice(resolved);
+            }
+        } else {
+            for (content, term) in zip(contents.iter_mut(), hunk.iter()) {
+                content.extend_from_slice(term);
+            }
+        }
+    }
+    contents
+}
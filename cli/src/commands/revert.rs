@@ -16,10 +16,13 @@ use std::collections::HashSet;
 
 use bstr::ByteVec as _;
 use clap::ArgGroup;
+use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use indexmap::IndexSet;
+use indoc::formatdoc;
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
+use jj_lib::merge::Diff;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::merge_commit_trees;
@@ -29,6 +32,7 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::compute_commit_location;
+use crate::cli_util::print_unmatched_explicit_paths;
 use crate::cli_util::print_updated_commits;
 use crate::command_error::CommandError;
 use crate::complete;
@@ -39,6 +43,10 @@ use crate::ui::Ui;
 /// The reverse of each of the given revisions is applied sequentially in
 /// reverse topological order at the given location.
 ///
+/// If paths are given, or with `--interactive`, only the selected parts of the
+/// given revisions are reverted. Revisions with no selected changes are
+/// skipped.
+///
 /// The description of the new revisions can be customized with the
 /// `templates.revert_description` config variable.
 #[derive(clap::Args, Clone, Debug)]
@@ -83,6 +91,23 @@ pub(crate) struct RevertArgs {
         add = ArgValueCompleter::new(complete::revset_expression_mutable),
     )]
     insert_before: Option<Vec<RevisionArg>>,
+    /// Revert only changes to these paths (instead of all paths)
+    #[arg(
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::modified_revision_files),
+    )]
+    paths: Vec<String>,
+    /// Interactively choose which parts to revert
+    #[arg(long, short)]
+    interactive: bool,
+    /// Specify diff editor to be used (implies --interactive)
+    #[arg(
+        long,
+        value_name = "NAME",
+        add = ArgValueCandidates::new(complete::diff_editors),
+    )]
+    tool: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -117,17 +142,48 @@ pub(crate) fn cmd_revert(
             to_revert.len() - 1
         )
     };
+    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let matcher = fileset_expression.to_matcher();
+    let diff_selector =
+        workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
+    let mut commits_to_revert_with_selected_trees = vec![];
+    let mut all_trees = vec![];
+    for commit in to_revert {
+        let base_tree = commit.parent_tree(workspace_command.repo().as_ref())?;
+        let tree = commit.tree();
+        let format_instructions = || {
+            formatdoc! {"
+                You are selecting changes to revert from: {commit}
+
+                The diff initially shows all changes in the commit. Adjust the right side
+                until it shows the changes you want to revert.
+                ",
+                commit = workspace_command.format_commit_summary(&commit),
+            }
+        };
+        let selected_tree =
+            diff_selector.select(Diff::new(&base_tree, &tree), &matcher, format_instructions)?;
+        if selected_tree.tree_ids() != base_tree.tree_ids() {
+            commits_to_revert_with_selected_trees.push((commit, base_tree.clone(), selected_tree));
+        }
+        all_trees.extend([base_tree, tree]);
+    }
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, &all_trees)?;
+    if commits_to_revert_with_selected_trees.is_empty() {
+        writeln!(ui.status(), "No changes to revert.")?;
+        return Ok(());
+    }
     let commits_to_revert_with_new_commit_descriptions = {
         let template_text = command
             .settings()
             .get_string("templates.revert_description")?;
         let template = workspace_command.parse_commit_template(ui, &template_text)?;
 
-        to_revert
+        commits_to_revert_with_selected_trees
             .into_iter()
-            .map(|commit| {
+            .map(|(commit, base_tree, selected_tree)| {
                 let commit_description = template.format_plain_text(&commit).into_string_lossy();
-                (commit, commit_description)
+                (commit, base_tree, selected_tree, commit_description)
             })
             .collect_vec()
     };
@@ -141,12 +197,12 @@ pub(crate) fn cmd_revert(
     let mut parent_ids = new_parent_ids;
 
     let mut reverted_commits = vec![];
-    for (commit_to_revert, new_commit_description) in
+    for (_commit_to_revert, old_base_tree, selected_tree, new_commit_description) in
         &commits_to_revert_with_new_commit_descriptions
     {
-        let old_base_tree = commit_to_revert.parent_tree(tx.repo())?;
-        let old_tree = commit_to_revert.tree();
-        let new_tree = new_base_tree.merge(old_tree, old_base_tree).block_on()?;
+        let new_tree = new_base_tree
+            .merge(selected_tree.clone(), old_base_tree.clone())
+            .block_on()?;
         let new_parent_ids = parent_ids.clone();
         let new_commit = tx
             .repo_mut()
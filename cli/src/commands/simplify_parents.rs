@@ -42,6 +42,10 @@ pub(crate) struct SimplifyParentsArgs {
         add = ArgValueCompleter::new(complete::revset_expression_mutable),
     )]
     revisions: Vec<RevisionArg>,
+
+    /// Show the changes that would be made, without making them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 pub(crate) fn cmd_simplify_parents(
@@ -120,6 +124,9 @@ pub(crate) fn cmd_simplify_parents(
             )?;
         }
     }
+    if args.dry_run {
+        return tx.finish_dry_run(ui);
+    }
     tx.finish(ui, format!("simplify {num_orig_commits} commits"))?;
 
     Ok(())
@@ -0,0 +1,46 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Write the patterns to Git's sparse-checkout file
+///
+/// This writes `info/sparse-checkout` in the Git repository and enables sparse
+/// checkouts in its configuration, in cone mode if the patterns are in cone
+/// mode. Run `git sparse-checkout reapply` to make Git aware of the files that
+/// aren't present in the working copy.
+#[derive(clap::Args, Clone, Debug)]
+pub struct SparseExportArgs {}
+
+#[instrument(skip_all)]
+pub fn cmd_sparse_export(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &SparseExportArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let working_copy = workspace_command.working_copy();
+    git::write_sparse_checkout(
+        workspace_command.repo().store(),
+        working_copy.sparse_patterns()?,
+        working_copy.sparse_mode()?,
+    )?;
+    Ok(())
+}
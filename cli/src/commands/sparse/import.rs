@@ -0,0 +1,53 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use super::update_sparse_patterns_and_mode_with;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Replace the patterns with those from Git's sparse-checkout file
+///
+/// This reads `info/sparse-checkout` from the Git repository. Patterns written
+/// in Git's cone mode are imported in cone mode (see `jj sparse set --cone`).
+/// Otherwise, only patterns that select a path and everything under it, such
+/// as `/foo/bar`, are supported.
+#[derive(clap::Args, Clone, Debug)]
+pub struct SparseImportArgs {}
+
+#[instrument(skip_all)]
+pub fn cmd_sparse_import(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &SparseImportArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let Some((mut new_patterns, new_mode)) =
+        git::read_sparse_checkout(workspace_command.repo().store())?
+    else {
+        return Err(user_error(
+            "The Git repository doesn't have a sparse-checkout file",
+        ));
+    };
+    new_patterns.sort_unstable();
+    new_patterns.dedup();
+    update_sparse_patterns_and_mode_with(ui, &mut workspace_command, |_ui, _, _| {
+        Ok((new_patterns, new_mode))
+    })
+}
@@ -13,17 +13,30 @@
 // limitations under the License.
 
 mod edit;
+#[cfg(feature = "git")]
+mod export;
+#[cfg(feature = "git")]
+mod import;
 mod list;
 mod reset;
 mod set;
 
 use clap::Subcommand;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::working_copy::SparseMode;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use self::edit::SparseEditArgs;
 use self::edit::cmd_sparse_edit;
+#[cfg(feature = "git")]
+use self::export::SparseExportArgs;
+#[cfg(feature = "git")]
+use self::export::cmd_sparse_export;
+#[cfg(feature = "git")]
+use self::import::SparseImportArgs;
+#[cfg(feature = "git")]
+use self::import::cmd_sparse_import;
 use self::list::SparseListArgs;
 use self::list::cmd_sparse_list;
 use self::reset::SparseResetArgs;
@@ -42,6 +55,10 @@ use crate::ui::Ui;
 #[derive(Subcommand, Clone, Debug)]
 pub(crate) enum SparseCommand {
     Edit(SparseEditArgs),
+    #[cfg(feature = "git")]
+    Export(SparseExportArgs),
+    #[cfg(feature = "git")]
+    Import(SparseImportArgs),
     List(SparseListArgs),
     Reset(SparseResetArgs),
     Set(SparseSetArgs),
@@ -55,6 +72,10 @@ pub(crate) fn cmd_sparse(
 ) -> Result<(), CommandError> {
     match subcommand {
         SparseCommand::Edit(args) => cmd_sparse_edit(ui, command, args),
+        #[cfg(feature = "git")]
+        SparseCommand::Export(args) => cmd_sparse_export(ui, command, args),
+        #[cfg(feature = "git")]
+        SparseCommand::Import(args) => cmd_sparse_import(ui, command, args),
         SparseCommand::List(args) => cmd_sparse_list(ui, command, args),
         SparseCommand::Reset(args) => cmd_sparse_reset(ui, command, args),
         SparseCommand::Set(args) => cmd_sparse_set(ui, command, args),
@@ -65,12 +86,27 @@ fn update_sparse_patterns_with(
     ui: &mut Ui,
     workspace_command: &mut WorkspaceCommandHelper,
     f: impl FnOnce(&mut Ui, &[RepoPathBuf]) -> Result<Vec<RepoPathBuf>, CommandError>,
+) -> Result<(), CommandError> {
+    update_sparse_patterns_and_mode_with(ui, workspace_command, |ui, old_patterns, old_mode| {
+        Ok((f(ui, old_patterns)?, old_mode))
+    })
+}
+
+fn update_sparse_patterns_and_mode_with(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    f: impl FnOnce(
+        &mut Ui,
+        &[RepoPathBuf],
+        SparseMode,
+    ) -> Result<(Vec<RepoPathBuf>, SparseMode), CommandError>,
 ) -> Result<(), CommandError> {
     let (mut locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
-    let new_patterns = f(ui, locked_ws.locked_wc().sparse_patterns()?)?;
+    let locked_wc = locked_ws.locked_wc();
+    let (new_patterns, new_mode) = f(ui, locked_wc.sparse_patterns()?, locked_wc.sparse_mode()?)?;
     let stats = locked_ws
         .locked_wc()
-        .set_sparse_patterns(new_patterns)
+        .set_sparse_patterns_with_mode(new_patterns, new_mode)
         .block_on()
         .map_err(|err| internal_error_with_message("Failed to update working copy paths", err))?;
     let operation_id = locked_ws.locked_wc().old_operation_id().clone();
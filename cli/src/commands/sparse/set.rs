@@ -16,9 +16,10 @@ use std::collections::HashSet;
 
 use itertools::Itertools as _;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::working_copy::SparseMode;
 use tracing::instrument;
 
-use super::update_sparse_patterns_with;
+use super::update_sparse_patterns_and_mode_with;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
@@ -48,6 +49,15 @@ pub struct SparseSetArgs {
     /// Include no files in the working copy (combine with --add)
     #[arg(long)]
     clear: bool,
+    /// Interpret the patterns as directories like Git's cone mode does
+    ///
+    /// In cone mode, the files directly in the root directory and in the
+    /// parent directories of each pattern are included as well.
+    #[arg(long, conflicts_with = "no_cone")]
+    cone: bool,
+    /// Interpret the patterns as path prefixes (the default)
+    #[arg(long)]
+    no_cone: bool,
 }
 
 #[instrument(skip_all)]
@@ -57,17 +67,31 @@ pub fn cmd_sparse_set(
     args: &SparseSetArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    update_sparse_patterns_with(ui, &mut workspace_command, |_ui, old_patterns| {
-        let mut new_patterns = HashSet::new();
-        if !args.clear {
-            new_patterns.extend(old_patterns.iter().cloned());
-            for path in &args.remove {
-                new_patterns.remove(path);
+    update_sparse_patterns_and_mode_with(
+        ui,
+        &mut workspace_command,
+        |_ui, old_patterns, old_mode| {
+            let mut new_patterns = HashSet::new();
+            if !args.clear {
+                new_patterns.extend(old_patterns.iter().cloned());
+                for path in &args.remove {
+                    new_patterns.remove(path);
+                }
+            }
+            for path in &args.add {
+                new_patterns.insert(path.to_owned());
             }
-        }
-        for path in &args.add {
-            new_patterns.insert(path.to_owned());
-        }
-        Ok(new_patterns.into_iter().sorted_unstable().collect())
-    })
+            let new_mode = if args.cone {
+                SparseMode::Cone
+            } else if args.no_cone {
+                SparseMode::Prefix
+            } else {
+                old_mode
+            };
+            Ok((
+                new_patterns.into_iter().sorted_unstable().collect(),
+                new_mode,
+            ))
+        },
+    )
 }
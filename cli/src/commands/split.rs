@@ -13,6 +13,7 @@
 // limitations under the License.
 use std::collections::HashMap;
 use std::io::Write as _;
+use std::path::PathBuf;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
@@ -79,6 +80,18 @@ pub(crate) struct SplitArgs {
         add = ArgValueCandidates::new(complete::diff_editors),
     )]
     tool: Option<String>,
+    /// Put the changes in this patch file in the selected changes, or `-` to
+    /// read the patch from the standard input
+    ///
+    /// The patch must be in the unified diff format of `jj diff --git`, and
+    /// apply to the parent of the revision to split.
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["interactive", "tool"],
+    )]
+    patch_file: Option<PathBuf>,
     /// The revision to split
     #[arg(
         long, short,
@@ -170,11 +183,15 @@ impl SplitArgs {
         let repo = workspace_command.repo();
         let fileset_expression = workspace_command.parse_file_patterns(ui, &self.paths)?;
         let matcher = fileset_expression.to_matcher();
-        let diff_selector = workspace_command.diff_selector(
-            ui,
-            self.tool.as_deref(),
-            self.interactive || self.paths.is_empty(),
-        )?;
+        let diff_selector = if let Some(path) = &self.patch_file {
+            workspace_command.patch_selector(path)?
+        } else {
+            workspace_command.diff_selector(
+                ui,
+                self.tool.as_deref(),
+                self.interactive || self.paths.is_empty(),
+            )?
+        };
         let use_move_flags =
             self.onto.is_some() || self.insert_after.is_some() || self.insert_before.is_some();
         let (new_parent_ids, new_child_ids) = if use_move_flags {
@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::iter::once;
+use std::path::PathBuf;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
@@ -62,7 +63,8 @@ use crate::ui::Ui;
 ///
 /// If, after moving changes out, the source revision is empty compared to its
 /// parent(s), and `--keep-emptied` is not set, it will be abandoned. Without
-/// `--interactive` or paths, the source revision will always be empty.
+/// `--interactive`, `--patch-file`, or paths, the source revision will always
+/// be empty.
 ///
 /// If the source was abandoned and both the source and destination had a
 /// non-empty description, you will be asked for the combined description. If
@@ -178,6 +180,21 @@ pub(crate) struct SquashArgs {
     )]
     tool: Option<String>,
 
+    /// Move only the changes in this patch file, or `-` to read the patch from
+    /// the standard input
+    ///
+    /// The patch must be in the unified diff format of `jj diff --git`, and
+    /// apply to the parent of the source revision. This is useful for
+    /// selecting specific hunks without an interactive diff editor, e.g. by
+    /// editing the output of `jj diff --git`.
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["interactive", "tool"],
+    )]
+    patch_file: Option<PathBuf>,
+
     /// Move only changes to these paths (instead of all paths)
     #[arg(
         value_name = "FILESETS",
@@ -189,6 +206,10 @@ pub(crate) struct SquashArgs {
     /// The source revision will not be abandoned
     #[arg(long, short)]
     keep_emptied: bool,
+
+    /// Show the changes that would be made, without making them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[instrument(skip_all)]
@@ -318,9 +339,12 @@ pub(crate) fn cmd_squash(
         .base_workspace_helper()
         .parse_file_patterns(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
-    let diff_selector =
+    let diff_selector = if let Some(path) = &args.patch_file {
+        tx.base_workspace_helper().patch_selector(path)?
+    } else {
         tx.base_workspace_helper()
-            .diff_selector(ui, args.tool.as_deref(), args.interactive)?;
+            .diff_selector(ui, args.tool.as_deref(), args.interactive)?
+    };
     let text_editor = tx.base_workspace_helper().text_editor()?;
     let squashed_description = SquashedDescription::from_args(args);
 
@@ -354,7 +378,7 @@ pub(crate) fn cmd_squash(
             } else {
                 commit_builder.set_description(&description);
                 let description_with_trailers = add_trailers(ui, &tx, &commit_builder)?;
-                if args.editor {
+                if args.editor && !args.dry_run {
                     commit_builder.set_description(&description_with_trailers);
                     let temp_commit = commit_builder.write_hidden()?;
                     let intro = "";
@@ -364,6 +388,16 @@ pub(crate) fn cmd_squash(
                     description_with_trailers
                 }
             }
+        } else if args.dry_run {
+            // don't open the editor for a preview; join the non-empty descriptions
+            let paragraphs = (!insert_destination_commit)
+                .then_some(&destination)
+                .into_iter()
+                .chain(&squashed.abandoned_commits)
+                .map(|commit| commit.description().to_owned())
+                .filter(|description| !description.is_empty())
+                .collect_vec();
+            join_message_paragraphs(&paragraphs)
         } else {
             // edit combined
             let abandoned_commits = &squashed.abandoned_commits;
@@ -397,12 +431,21 @@ pub(crate) fn cmd_squash(
         let num_rebased = tx.repo_mut().rebase_descendants()?;
         if let Some(mut formatter) = ui.status_formatter() {
             if insert_destination_commit {
-                write!(formatter, "Created new commit ")?;
+                if args.dry_run {
+                    write!(formatter, "Would create new commit ")?;
+                } else {
+                    write!(formatter, "Created new commit ")?;
+                }
                 tx.write_commit_summary(formatter.as_mut(), &commit)?;
                 writeln!(formatter)?;
             }
             if num_rebased > 0 {
-                writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+                let verb = if args.dry_run {
+                    "Would rebase"
+                } else {
+                    "Rebased"
+                };
+                writeln!(formatter, "{verb} {num_rebased} descendant commits")?;
             }
         }
     } else {
@@ -412,12 +455,21 @@ pub(crate) fn cmd_squash(
 
         if let Some(mut formatter) = ui.status_formatter() {
             if insert_destination_commit {
-                write!(formatter, "Created new commit ")?;
+                if args.dry_run {
+                    write!(formatter, "Would create new commit ")?;
+                } else {
+                    write!(formatter, "Created new commit ")?;
+                }
                 tx.write_commit_summary(formatter.as_mut(), &destination)?;
                 writeln!(formatter)?;
             }
             if num_rebased > 0 {
-                writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+                let verb = if args.dry_run {
+                    "Would rebase"
+                } else {
+                    "Rebased"
+                };
+                writeln!(formatter, "{verb} {num_rebased} descendant commits")?;
             }
         }
 
@@ -437,6 +489,9 @@ pub(crate) fn cmd_squash(
             }
         }
     }
+    if args.dry_run {
+        return tx.finish_dry_run(ui);
+    }
     tx.finish(ui, tx_description)?;
     Ok(())
 }
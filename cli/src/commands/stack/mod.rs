@@ -0,0 +1,42 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod submit;
+
+use clap::Subcommand;
+
+use self::submit::StackSubmitArgs;
+use self::submit::cmd_stack_submit;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Work with stacks of changes under review
+///
+/// Each change in a stack is reviewed in its own pull request, based on the
+/// pull request of its parent. Pull requests are opened on GitHub.
+#[derive(Subcommand, Clone, Debug)]
+pub enum StackCommand {
+    Submit(StackSubmitArgs),
+}
+
+pub fn cmd_stack(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &StackCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        StackCommand::Submit(args) => cmd_stack_submit(ui, command, args),
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::op_store::RefTarget;
+use jj_lib::refs::LocalAndRemoteRef;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::short_change_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::commands::git::push::BookmarkPushOptions;
+use crate::commands::git::push::classify_bookmark_update;
+use crate::commands::git::push::ensure_new_bookmark_name;
+use crate::commands::git::push::exclude_bookmarks_by_push_refspecs;
+use crate::commands::git::push::generate_change_bookmark_names;
+use crate::commands::git::push::push_bookmark_updates;
+use crate::commands::github::resolve_github_remote;
+use crate::complete;
+use crate::github_util::GitHubClient;
+use crate::github_util::NewPullRequest;
+use crate::github_util::PullRequestUpdate;
+use crate::github_util::format_stack_section;
+use crate::github_util::replace_stack_section;
+use crate::ui::Ui;
+
+/// Push a stack of changes and open a pull request for each of them
+///
+/// Each change in the stack is pushed to its own bookmark. Changes without a
+/// bookmark get one named by the `templates.git_push_bookmark` template, like
+/// `jj git push --change`. Then a pull request is opened for each bookmark
+/// that doesn't have one yet. The pull request of the bottom change is based on
+/// the target branch, and each other pull request is based on the bookmark of
+/// the change below it, so that it only shows the changes of its own commit.
+///
+/// When the stack is submitted again, the bookmarks are pushed, the bases of
+/// the pull requests are updated to follow the stack, and the list of pull
+/// requests in the stack is updated in the description of each pull request.
+#[derive(clap::Args, Clone, Debug)]
+pub struct StackSubmitArgs {
+    /// The changes to submit, which must form a linear stack [default:
+    /// `trunk()..@`]
+    ///
+    /// The working-copy commit is skipped if it's empty and has no
+    /// description.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// The branch the stack should be merged into [default: the default
+    /// branch of the repository]
+    #[arg(long)]
+    base: Option<String>,
+
+    /// Open new pull requests as drafts
+    #[arg(long)]
+    draft: bool,
+
+    /// The remote to push to, and whose GitHub repository the pull requests
+    /// are opened in [default: `github.default-remote` or `git.push` config,
+    /// or "origin"]
+    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: Option<String>,
+}
+
+pub fn cmd_stack_submit(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &StackSubmitArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let (remote, github_repo) = resolve_github_remote(&workspace_command, args.remote.as_deref())?;
+    let revisions = if args.revisions.is_empty() {
+        vec![RevisionArg::from("trunk()..@".to_owned())]
+    } else {
+        args.revisions.clone()
+    };
+    let mut commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    // Order the stack from the bottom
+    commits.reverse();
+    if let Some(commit) = commits.last()
+        && workspace_command.get_wc_commit_id() == Some(commit.id())
+        && commit.is_discardable(workspace_command.repo().as_ref())?
+    {
+        commits.pop();
+    }
+    if commits.is_empty() {
+        writeln!(ui.status(), "No revisions to submit.")?;
+        return Ok(());
+    }
+    for (parent, commit) in commits.iter().tuple_windows() {
+        if commit.parent_ids() != [parent.id().clone()] {
+            return Err(user_error(format!(
+                "Revision {} is not the only child of revision {}, the revisions to submit must \
+                 form a linear stack",
+                short_change_hash(commit.change_id()),
+                short_change_hash(parent.change_id())
+            )));
+        }
+    }
+    // Fail before pushing if the token is missing
+    let client = GitHubClient::from_settings(workspace_command.settings())?;
+
+    let mut tx = workspace_command.start_transaction();
+    let generated_names = generate_change_bookmark_names(ui, &tx, &commits)?;
+    let mut bookmark_names = vec![];
+    for (commit, generated_name) in commits.iter().zip(generated_names) {
+        let existing_name = tx
+            .base_repo()
+            .view()
+            .local_bookmarks_for_commit(commit.id())
+            .find(|(_, target)| !target.has_conflict())
+            .map(|(name, _)| name.to_owned());
+        if let Some(name) = existing_name {
+            bookmark_names.push(name);
+            continue;
+        }
+        ensure_new_bookmark_name(tx.base_repo().as_ref(), &generated_name)?;
+        writeln!(
+            ui.status(),
+            "Creating bookmark {name} for revision {change_id:.12}",
+            name = generated_name.as_symbol(),
+            change_id = commit.change_id()
+        )?;
+        tx.repo_mut()
+            .set_local_bookmark_target(&generated_name, RefTarget::normal(commit.id().clone()));
+        bookmark_names.push(generated_name);
+    }
+
+    let mut bookmark_updates = vec![];
+    for name in &bookmark_names {
+        let remote_symbol = name.to_remote_symbol(&remote);
+        let targets = LocalAndRemoteRef {
+            local_target: tx.repo().view().get_local_bookmark(name),
+            remote_ref: tx.repo().view().get_remote_bookmark(remote_symbol),
+        };
+        let allow_new = true; // each change gets its own remote bookmark
+        let allow_delete = false; // doesn't matter
+        if let Some(update) =
+            classify_bookmark_update(remote_symbol, targets, allow_new, allow_delete)?
+        {
+            bookmark_updates.push((name.clone(), update));
+        }
+    }
+    exclude_bookmarks_by_push_refspecs(ui, &tx, &remote, &mut bookmark_updates)?;
+    if !bookmark_updates.is_empty() {
+        let options = BookmarkPushOptions {
+            allow_empty_description: false,
+            allow_private: false,
+            allow_protected: false,
+            dry_run: false,
+        };
+        push_bookmark_updates(ui, &mut tx, &remote, bookmark_updates, &[], &options)?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "submit stack of {} changes to git remote {}",
+            commits.len(),
+            remote.as_symbol()
+        ),
+    )?;
+
+    let base = match &args.base {
+        Some(base) => base.clone(),
+        None => client.default_branch(&github_repo)?,
+    };
+    let mut pull_requests = vec![];
+    for (i, (name, commit)) in bookmark_names.iter().zip(&commits).enumerate() {
+        let pr_base = if i == 0 {
+            base.as_str()
+        } else {
+            bookmark_names[i - 1].as_str()
+        };
+        let existing = client
+            .list_pull_requests(&github_repo, Some(name.as_str()), "open")?
+            .into_iter()
+            .next();
+        let pull_request = if let Some(existing) = existing {
+            if existing.base.name == pr_base {
+                existing
+            } else {
+                client.update_pull_request(
+                    &github_repo,
+                    existing.number,
+                    &PullRequestUpdate {
+                        base: Some(pr_base),
+                        ..Default::default()
+                    },
+                )?
+            }
+        } else {
+            let (title, body) = commit
+                .description()
+                .trim()
+                .split_once('\n')
+                .unwrap_or((commit.description().trim(), ""));
+            let pull_request = client.create_pull_request(
+                &github_repo,
+                &NewPullRequest {
+                    title,
+                    body: body.trim(),
+                    head: name.as_str(),
+                    base: pr_base,
+                    draft: args.draft,
+                },
+            )?;
+            writeln!(
+                ui.status(),
+                "Created pull request #{} for bookmark {}",
+                pull_request.number,
+                name.as_symbol()
+            )?;
+            pull_request
+        };
+        pull_requests.push(pull_request);
+    }
+
+    let numbers = pull_requests.iter().map(|pr| pr.number).collect_vec();
+    for (name, pull_request) in bookmark_names.iter().zip(&pull_requests) {
+        let section = format_stack_section(&numbers, pull_request.number);
+        let old_body = pull_request.body.as_deref().unwrap_or("");
+        let new_body = replace_stack_section(old_body, &section);
+        if new_body != old_body {
+            client.update_pull_request(
+                &github_repo,
+                pull_request.number,
+                &PullRequestUpdate {
+                    body: Some(&new_body),
+                    ..Default::default()
+                },
+            )?;
+        }
+        writeln!(
+            ui.status(),
+            "{}: {}",
+            name.as_symbol(),
+            pull_request.html_url
+        )?;
+    }
+    Ok(())
+}
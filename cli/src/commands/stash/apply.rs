@@ -0,0 +1,105 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use jj_lib::op_store::RefTarget;
+use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use super::resolve_stash;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Apply the changes in a stash to the working copy, keeping the stash
+///
+/// The changes are applied on top of the current working-copy commit. If they
+/// don't merge cleanly, the working-copy commit will have conflicts.
+#[derive(clap::Args, Clone, Debug)]
+pub struct StashApplyArgs {
+    /// The stash to apply (default: the most recent stash)
+    #[arg(add = ArgValueCandidates::new(complete::stashes))]
+    name: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_stash_apply(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &StashApplyArgs,
+) -> Result<(), CommandError> {
+    apply_stash(ui, command, args.name.as_deref(), false)
+}
+
+/// Applies the stash to the working-copy commit, and drops it if `pop` is set
+/// and the changes were applied without conflicts.
+pub(super) fn apply_stash(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    name: Option<&str>,
+    pop: bool,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let wc_commit = workspace_command.repo().store().get_commit(wc_commit_id)?;
+    workspace_command.check_rewritable([wc_commit.id()])?;
+    let (name, stash_commit) = resolve_stash(workspace_command.repo().as_ref(), name)?;
+
+    let stash_base_tree = stash_commit.parent_tree(workspace_command.repo().as_ref())?;
+    let new_wc_tree = wc_commit
+        .tree()
+        .merge(stash_base_tree, stash_commit.tree())
+        .block_on()?;
+    let has_conflict = new_wc_tree.has_conflict() && !wc_commit.tree().has_conflict();
+    let should_drop = pop && !has_conflict;
+
+    let mut tx = workspace_command.start_transaction();
+    tx.repo_mut()
+        .rewrite_commit(&wc_commit)
+        .set_tree(new_wc_tree)
+        .write()?;
+    let num_rebased = tx.repo_mut().rebase_descendants()?;
+    if should_drop {
+        tx.repo_mut().set_stash_target(&name, RefTarget::absent());
+    }
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Applied stash {name}: ", name = name.as_symbol())?;
+        tx.write_commit_summary(formatter.as_mut(), &stash_commit)?;
+        writeln!(formatter)?;
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+    }
+    if pop && !should_drop {
+        writeln!(
+            ui.warning_default(),
+            "The stash was kept because applying it resulted in conflicts."
+        )?;
+        writeln!(
+            ui.hint_default(),
+            "Run `jj stash drop {name}` once the conflicts are resolved.",
+            name = name.as_symbol()
+        )?;
+    }
+    let verb = if pop { "pop" } else { "apply" };
+    tx.finish(ui, format!("stash {verb} {name}", name = name.as_symbol()))?;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RefNameBuf;
+
+use super::load_stashes;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Drop stashes without applying them
+///
+/// The stashed changes can still be recovered with `jj undo` or from the
+/// operation log.
+#[derive(clap::Args, Clone, Debug)]
+pub struct StashDropArgs {
+    /// The stashes to drop (default: the most recent stash)
+    #[arg(add = ArgValueCandidates::new(complete::stashes))]
+    names: Vec<String>,
+}
+
+pub fn cmd_stash_drop(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &StashDropArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().clone();
+    let names: Vec<RefNameBuf> = if args.names.is_empty() {
+        let Some((name, _, _)) = load_stashes(repo.as_ref())?.into_iter().next() else {
+            return Err(user_error("No stashes"));
+        };
+        vec![name]
+    } else {
+        args.names
+            .iter()
+            .map(|name| {
+                let name = RefName::new(name);
+                if repo.view().get_stash(name).is_absent() {
+                    return Err(user_error(format!(
+                        "No such stash: {name}",
+                        name = name.as_symbol()
+                    )));
+                }
+                Ok(name.to_owned())
+            })
+            .try_collect()?
+    };
+
+    let mut tx = workspace_command.start_transaction();
+    for name in names.iter().unique() {
+        tx.repo_mut().set_stash_target(name, RefTarget::absent());
+        writeln!(
+            ui.status(),
+            "Dropped stash {name}.",
+            name = name.as_symbol()
+        )?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "stash drop {}",
+            names
+                .iter()
+                .unique()
+                .map(|name| name.as_symbol())
+                .join(", ")
+        ),
+    )?;
+    Ok(())
+}
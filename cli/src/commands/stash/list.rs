@@ -0,0 +1,53 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use super::load_stashes;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List stashes, most recent first
+#[derive(clap::Args, Clone, Debug)]
+pub struct StashListArgs {}
+
+pub fn cmd_stash_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &StashListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let stashes = load_stashes(workspace_command.repo().as_ref())?;
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (name, target, commits) in &stashes {
+        if let [commit] = &commits[..]
+            && !target.has_conflict()
+        {
+            write!(formatter, "{name}: ", name = name.as_symbol())?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        } else {
+            writeln!(formatter, "{name} (conflicted):", name = name.as_symbol())?;
+            for commit in commits {
+                write!(formatter, "  + ")?;
+                workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod apply;
+mod drop;
+mod list;
+mod pop;
+mod push;
+
+use std::cmp::Reverse;
+
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::repo::Repo;
+
+use self::apply::StashApplyArgs;
+use self::apply::cmd_stash_apply;
+use self::drop::StashDropArgs;
+use self::drop::cmd_stash_drop;
+use self::list::StashListArgs;
+use self::list::cmd_stash_list;
+use self::pop::StashPopArgs;
+use self::pop::cmd_stash_pop;
+use self::push::StashPushArgs;
+use self::push::cmd_stash_push;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::ui::Ui;
+
+/// Set aside changes in the working copy and restore them later
+///
+/// `jj stash push` moves the changes in the working-copy commit into a new
+/// stash, leaving the working-copy commit (and its description) in place.
+/// `jj stash pop` applies the changes back onto the current working-copy
+/// commit, wherever it is now.
+///
+/// Stashes are hidden commits with a name. They are recorded in the operation
+/// log like bookmarks, so `jj undo` reverts stash operations, and they are
+/// kept until the stash is dropped. Use the `stashes()` revset function to
+/// inspect them, e.g. `jj log -r 'stashes()'`.
+///
+/// Applying a stash that doesn't merge cleanly creates conflicts in the
+/// working-copy commit, which can be resolved like any other conflict.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum StashCommand {
+    Apply(StashApplyArgs),
+    Drop(StashDropArgs),
+    #[command(visible_alias("l"))]
+    List(StashListArgs),
+    Pop(StashPopArgs),
+    Push(StashPushArgs),
+}
+
+pub fn cmd_stash(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &StashCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        StashCommand::Apply(args) => cmd_stash_apply(ui, command, args),
+        StashCommand::Drop(args) => cmd_stash_drop(ui, command, args),
+        StashCommand::List(args) => cmd_stash_list(ui, command, args),
+        StashCommand::Pop(args) => cmd_stash_pop(ui, command, args),
+        StashCommand::Push(args) => cmd_stash_push(ui, command, args),
+    }
+}
+
+/// Loads the stashed commits, most recently stashed first.
+fn load_stashes(
+    repo: &dyn Repo,
+) -> Result<Vec<(RefNameBuf, RefTarget, Vec<Commit>)>, CommandError> {
+    let mut stashes: Vec<_> = repo
+        .view()
+        .stashes()
+        .map(|(name, target)| {
+            let commits: Vec<_> = target
+                .added_ids()
+                .map(|id| repo.store().get_commit(id))
+                .try_collect()?;
+            Ok::<_, CommandError>((name.to_owned(), target.clone(), commits))
+        })
+        .try_collect()?;
+    stashes.sort_by_key(|(_, _, commits)| {
+        Reverse(
+            commits
+                .iter()
+                .map(|commit| commit.committer().timestamp.timestamp)
+                .max(),
+        )
+    });
+    Ok(stashes)
+}
+
+/// Resolves the stash of the given name, or the most recent stash.
+fn resolve_stash(
+    repo: &dyn Repo,
+    name: Option<&str>,
+) -> Result<(RefNameBuf, Commit), CommandError> {
+    let (name, target) = if let Some(name) = name {
+        let name = RefName::new(name);
+        let target = repo.view().get_stash(name);
+        if target.is_absent() {
+            return Err(user_error(format!(
+                "No such stash: {name}",
+                name = name.as_symbol()
+            )));
+        }
+        (name.to_owned(), target.clone())
+    } else {
+        let Some((name, target, _)) = load_stashes(repo)?.into_iter().next() else {
+            return Err(user_error("No stashes"));
+        };
+        (name, target)
+    };
+    let Some(commit_id) = target.as_normal() else {
+        return Err(user_error_with_hint(
+            format!("Stash {name} is conflicted", name = name.as_symbol()),
+            "Use `jj log -r 'stashes()'` to inspect the stashed commits, and `jj stash drop` \
+             to remove the stash.",
+        ));
+    };
+    let commit = repo.store().get_commit(commit_id)?;
+    Ok((name, commit))
+}
@@ -0,0 +1,42 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use tracing::instrument;
+
+use super::apply::apply_stash;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Apply the changes in a stash to the working copy, and drop the stash
+///
+/// Like `jj stash apply`, but the stash is dropped afterwards. If applying the
+/// stash results in conflicts, the stash is kept.
+#[derive(clap::Args, Clone, Debug)]
+pub struct StashPopArgs {
+    /// The stash to pop (default: the most recent stash)
+    #[arg(add = ArgValueCandidates::new(complete::stashes))]
+    name: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_stash_pop(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &StashPopArgs,
+) -> Result<(), CommandError> {
+    apply_stash(ui, command, args.name.as_deref(), true)
+}
@@ -0,0 +1,180 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use indoc::formatdoc;
+use jj_lib::merge::Diff;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::repo::Repo as _;
+use jj_lib::view::View;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::print_unmatched_explicit_paths;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::description_util::join_message_paragraphs;
+use crate::ui::Ui;
+
+/// Move the changes in the working copy into a new stash
+///
+/// The working-copy commit keeps its description and other metadata, but the
+/// stashed changes are removed from it.
+#[derive(clap::Args, Clone, Debug)]
+pub struct StashPushArgs {
+    /// Stash only these paths (instead of all paths)
+    #[arg(
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::modified_files),
+    )]
+    paths: Vec<String>,
+    /// The name of the stash
+    ///
+    /// Defaults to `stash-N`, where `N` is one more than the highest number
+    /// in use.
+    #[arg(long)]
+    name: Option<String>,
+    /// The description of the stashed changes
+    ///
+    /// Defaults to the description of the working-copy commit.
+    #[arg(long = "message", short, value_name = "MESSAGE")]
+    message_paragraphs: Vec<String>,
+    /// Interactively choose which parts to stash
+    #[arg(long, short)]
+    interactive: bool,
+    /// Specify diff editor to be used (implies --interactive)
+    #[arg(
+        long,
+        value_name = "NAME",
+        add = ArgValueCandidates::new(complete::diff_editors),
+    )]
+    tool: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_stash_push(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &StashPushArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let wc_commit = workspace_command.repo().store().get_commit(wc_commit_id)?;
+    workspace_command.check_rewritable([wc_commit.id()])?;
+
+    let name: RefNameBuf = match &args.name {
+        Some(name) if name.is_empty() => {
+            return Err(user_error("Stash name cannot be empty"));
+        }
+        Some(name) => {
+            let name = RefName::new(name);
+            if workspace_command.repo().view().get_stash(name).is_present() {
+                return Err(user_error(format!(
+                    "Stash already exists: {name}",
+                    name = name.as_symbol()
+                )));
+            }
+            name.to_owned()
+        }
+        None => next_stash_name(workspace_command.repo().view()),
+    };
+
+    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let matcher = fileset_expression.to_matcher();
+    let diff_selector =
+        workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
+    let parent_tree = wc_commit.parent_tree(workspace_command.repo().as_ref())?;
+    let wc_tree = wc_commit.tree();
+    let format_instructions = || {
+        formatdoc! {"
+            You are selecting changes to stash from: {wc_commit}
+
+            The diff initially shows all changes in the working copy. Adjust the right side
+            until it shows the changes you want to stash.
+            ",
+            wc_commit = workspace_command.format_commit_summary(&wc_commit),
+        }
+    };
+    let selected_tree = diff_selector.select(
+        Diff::new(&parent_tree, &wc_tree),
+        &matcher,
+        format_instructions,
+    )?;
+
+    print_unmatched_explicit_paths(
+        ui,
+        &workspace_command,
+        &fileset_expression,
+        [&parent_tree, &wc_tree],
+    )?;
+
+    if selected_tree.tree_ids() == parent_tree.tree_ids() {
+        writeln!(ui.status(), "No changes to stash.")?;
+        return Ok(());
+    }
+
+    let description = if args.message_paragraphs.is_empty() {
+        wc_commit.description().to_owned()
+    } else {
+        join_message_paragraphs(&args.message_paragraphs)
+    };
+    let new_wc_tree = wc_tree
+        .merge(selected_tree.clone(), parent_tree)
+        .block_on()?;
+
+    let mut tx = workspace_command.start_transaction();
+    let stash_commit = tx
+        .repo_mut()
+        .new_commit(wc_commit.parent_ids().to_vec(), selected_tree)
+        .set_description(description)
+        .write()?;
+    // The stashed commit is only reachable from the stash.
+    tx.repo_mut().remove_head(stash_commit.id());
+    tx.repo_mut()
+        .set_stash_target(&name, RefTarget::normal(stash_commit.id().clone()));
+    tx.repo_mut()
+        .rewrite_commit(&wc_commit)
+        .set_tree(new_wc_tree)
+        .write()?;
+    tx.repo_mut().rebase_descendants()?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Saved stash {name}: ", name = name.as_symbol())?;
+        tx.write_commit_summary(formatter.as_mut(), &stash_commit)?;
+        writeln!(formatter)?;
+    }
+    tx.finish(ui, format!("stash push {name}", name = name.as_symbol()))?;
+    Ok(())
+}
+
+fn next_stash_name(view: &View) -> RefNameBuf {
+    let last = view
+        .stashes()
+        .filter_map(|(name, _)| {
+            let number = name.as_str().strip_prefix("stash-")?;
+            number.parse::<u64>().ok()
+        })
+        .max()
+        .unwrap_or(0);
+    format!("stash-{}", last + 1).into()
+}
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
+use std::path::PathBuf;
+
 use itertools::Itertools as _;
+use jj_lib::commit::Commit;
 use jj_lib::copies::CopyRecords;
 use jj_lib::merge::Diff;
 use jj_lib::merged_tree::MergedTree;
@@ -21,18 +25,25 @@ use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::working_copy::SnapshotStats;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
+use crate::annotator::Annotators;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_snapshot_stats;
 use crate::cli_util::print_unmatched_explicit_paths;
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::diff_util::DiffFormat;
 use crate::diff_util::get_copy_records;
+use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
+use crate::submodule_util;
 use crate::ui::Ui;
+use crate::watch_util::watch_workspace;
 
 /// Show high-level repo status [default alias: st]
 ///
@@ -43,6 +54,8 @@ use crate::ui::Ui;
 ///
 /// * Conflicts in the working copy
 ///
+/// * The state of Git submodules declared in `.gitmodules`
+///
 /// * [Conflicted bookmarks]
 ///
 /// [Conflicted bookmarks]:
@@ -52,6 +65,17 @@ pub(crate) struct StatusArgs {
     /// Restrict the status display to these paths
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Keep running, and re-render the status whenever the repo changes
+    ///
+    /// The working copy is snapshotted and the operation log is checked for
+    /// new operations whenever files in the workspace change. Configure a
+    /// [filesystem monitor] to keep the snapshots cheap in large working
+    /// copies.
+    ///
+    /// [filesystem monitor]:
+    ///     https://jj-vcs.github.io/jj/latest/config/#filesystem-monitor
+    #[arg(long)]
+    watch: bool,
 }
 
 #[instrument(skip_all)]
@@ -60,12 +84,22 @@ pub(crate) fn cmd_status(
     command: &CommandHelper,
     args: &StatusArgs,
 ) -> Result<(), CommandError> {
+    if args.watch {
+        return watch_workspace(ui, command, |ui, workspace_command, snapshot_stats| {
+            show_status(ui, workspace_command, snapshot_stats, args)
+        });
+    }
     let (workspace_command, snapshot_stats) = command.workspace_helper_with_stats(ui)?;
-    print_snapshot_stats(
-        ui,
-        &snapshot_stats,
-        workspace_command.env().path_converter(),
-    )?;
+    show_status(ui, &workspace_command, &snapshot_stats, args)
+}
+
+fn show_status(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    snapshot_stats: &SnapshotStats,
+    args: &StatusArgs,
+) -> Result<(), CommandError> {
+    print_snapshot_stats(ui, snapshot_stats, workspace_command.env().path_converter())?;
     let repo = workspace_command.repo();
     let maybe_wc_commit = workspace_command
         .get_wc_commit_id()
@@ -81,7 +115,7 @@ pub(crate) fn cmd_status(
         let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
         let tree = wc_commit.tree();
 
-        print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+        print_unmatched_explicit_paths(ui, workspace_command, &fileset_expression, [&tree])?;
 
         let wc_has_changes = tree.tree_ids() != parent_tree.tree_ids();
         let wc_has_untracked = !snapshot_stats.untracked_paths.is_empty();
@@ -132,16 +166,58 @@ pub(crate) fn cmd_status(
             }
         }
 
+        let submodules = submodule_util::submodules_in_tree(&tree)?
+            .into_iter()
+            .filter(|(submodule, _)| matcher.matches(&submodule.path))
+            .collect_vec();
+        if !submodules.is_empty() {
+            writeln!(formatter, "Submodules:")?;
+            let settings = workspace_command.settings();
+            // Inspecting the checked-out commit spawns git for each submodule,
+            // so only do it if jj manages the submodules.
+            let git_executable_path: Option<PathBuf> = settings
+                .get_bool("git.update-submodules")?
+                .then(|| settings.get("git.executable-path"))
+                .transpose()?;
+            for (submodule, commit_id) in submodules {
+                let ui_path = workspace_command.format_file_path(&submodule.path);
+                let mut formatter = formatter.labeled("submodule");
+                write!(
+                    formatter,
+                    "  {ui_path} at {}",
+                    short_commit_hash(&commit_id)
+                )?;
+                let Some(git_executable_path) = &git_executable_path else {
+                    writeln!(formatter)?;
+                    continue;
+                };
+                let dir = submodule
+                    .path
+                    .to_fs_path_unchecked(workspace_command.workspace_root());
+                match submodule_util::submodule_head(git_executable_path, &dir) {
+                    Some(head) if head == commit_id => writeln!(formatter)?,
+                    Some(head) => {
+                        writeln!(formatter, " (checked out at {})", short_commit_hash(&head))?;
+                    }
+                    None => writeln!(formatter, " (not checked out)")?,
+                }
+            }
+        }
+
         let template = workspace_command.commit_summary_template();
+        let annotators = workspace_command.env().annotators();
+        let parents: Vec<_> = wc_commit.parents().try_collect()?;
+        annotators.prefetch(&itertools::chain([wc_commit.clone()], parents.clone()).collect_vec());
         write!(formatter, "Working copy  (@) : ")?;
         template.format(wc_commit, formatter)?;
         writeln!(formatter)?;
-        for parent in wc_commit.parents() {
-            let parent = parent?;
+        print_annotations(formatter, annotators, wc_commit)?;
+        for parent in parents {
             //                "Working copy  (@) : "
             write!(formatter, "Parent commit (@-): ")?;
             template.format(&parent, formatter)?;
             writeln!(formatter)?;
+            print_annotations(formatter, annotators, &parent)?;
         }
 
         if wc_commit.has_conflict() {
@@ -152,7 +228,7 @@ pub(crate) fn cmd_status(
                 formatter.labeled("warning").with_heading("Warning: "),
                 "There are unresolved conflicts at these paths:"
             )?;
-            print_conflicted_paths(conflicts, formatter, &workspace_command)?;
+            print_conflicted_paths(conflicts, formatter, workspace_command)?;
 
             let wc_revset = RevsetExpression::commit(wc_commit.id().clone());
 
@@ -232,6 +308,32 @@ pub(crate) fn cmd_status(
     Ok(())
 }
 
+/// Prints the annotation of `commit` by each configured annotator. Failing
+/// annotators are reported as warnings.
+fn print_annotations(
+    formatter: &mut dyn Formatter,
+    annotators: &Annotators,
+    commit: &Commit,
+) -> io::Result<()> {
+    for name in annotators.names() {
+        match annotators.annotate(name, commit) {
+            Ok(annotation) if annotation.is_empty() => {}
+            Ok(annotation) => {
+                write!(formatter, "  {name}: ")?;
+                write!(formatter.labeled("annotation"), "{annotation}")?;
+                writeln!(formatter)?;
+            }
+            Err(err) => {
+                writeln!(
+                    formatter.labeled("warning").with_heading("Warning: "),
+                    "{err}"
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn visit_collapsed_untracked_files(
     untracked_paths: impl IntoIterator<Item = impl AsRef<RepoPath>>,
     tree: MergedTree,
@@ -0,0 +1,182 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandTransaction;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::complete;
+use crate::description_util::edit_description;
+use crate::description_util::join_message_paragraphs;
+use crate::revset_util;
+use crate::ui::Ui;
+
+/// Create new tags
+///
+/// Unlike `jj tag set`, this refuses to touch existing tags. By default,
+/// lightweight tags are created. With `--annotate`, `--message`, or `--sign`,
+/// the tags are recorded as Git annotated tag objects carrying a message and
+/// the tagger's identity, which requires a Git-backed repository. Signed tags
+/// are signed with the configured [commit signing] backend.
+///
+/// Use `jj git push --tag` to push the tags to a remote.
+///
+/// [commit signing]:
+///     https://jj-vcs.github.io/jj/latest/config/#commit-signing
+#[derive(clap::Args, Clone, Debug)]
+pub struct TagCreateArgs {
+    /// Target revision to point to
+    #[arg(
+        long, short,
+        default_value = "@",
+        visible_alias = "to",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+
+    /// Create annotated tags
+    ///
+    /// If no message is given, an editor is opened to write one.
+    #[arg(long, short)]
+    annotate: bool,
+
+    /// Create signed annotated tags
+    #[arg(long, short)]
+    sign: bool,
+
+    /// The key used for signing
+    #[arg(long, requires = "sign")]
+    key: Option<String>,
+
+    /// The tag message to use (implies `--annotate`)
+    #[arg(long = "message", short, value_name = "MESSAGE")]
+    message_paragraphs: Vec<String>,
+
+    /// Tag names to create
+    #[arg(required = true, value_parser = revset_util::parse_tag_name)]
+    names: Vec<RefNameBuf>,
+}
+
+pub fn cmd_tag_create(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &TagCreateArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let target_commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let repo = workspace_command.repo().as_ref();
+
+    for name in &args.names {
+        if repo.view().get_local_tag(name).is_present() {
+            return Err(user_error_with_hint(
+                format!("Tag already exists: {name}", name = name.as_symbol()),
+                "Use `jj tag set --allow-move` to update existing tags.",
+            ));
+        }
+    }
+    if args.sign && !repo.store().signer().can_sign() {
+        return Err(user_error_with_hint(
+            "No signing backend configured",
+            "For configuring a signing backend, see https://jj-vcs.github.io/jj/latest/config/#commit-signing",
+        ));
+    }
+    if target_commit.is_discardable(repo)? {
+        writeln!(ui.warning_default(), "Target revision is empty.")?;
+    }
+
+    let annotate = args.annotate || args.sign || !args.message_paragraphs.is_empty();
+    let message = if !annotate {
+        None
+    } else if !args.message_paragraphs.is_empty() {
+        Some(join_message_paragraphs(&args.message_paragraphs))
+    } else {
+        let text_editor = workspace_command.text_editor()?;
+        let message = edit_description(&text_editor, "")?;
+        if message.trim().is_empty() {
+            return Err(user_error("Aborting due to empty tag message"));
+        }
+        Some(message)
+    };
+
+    let mut tx = workspace_command.start_transaction();
+    for name in &args.names {
+        if let Some(message) = &message {
+            write_annotated_tag(&mut tx, name, &target_commit, message, args)?;
+        } else {
+            tx.repo_mut()
+                .set_local_tag_target(name, RefTarget::normal(target_commit.id().clone()));
+        }
+    }
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Created {} tags pointing to ", args.names.len())?;
+        tx.write_commit_summary(formatter.as_mut(), &target_commit)?;
+        writeln!(formatter)?;
+    }
+
+    tx.finish(
+        ui,
+        format!(
+            "create tag {names} pointing to commit {id}",
+            names = args.names.iter().map(|n| n.as_symbol()).join(", "),
+            id = target_commit.id()
+        ),
+    )?;
+    Ok(())
+}
+
+#[cfg(feature = "git")]
+fn write_annotated_tag(
+    tx: &mut WorkspaceCommandTransaction,
+    name: &RefName,
+    target_commit: &Commit,
+    message: &str,
+    args: &TagCreateArgs,
+) -> Result<(), CommandError> {
+    let tagger = tx.settings().signature();
+    jj_lib::git::write_annotated_tag(
+        tx.repo_mut(),
+        name,
+        target_commit.id(),
+        message,
+        &tagger,
+        args.sign,
+        args.key.as_deref(),
+    )?;
+    Ok(())
+}
+
+#[cfg(not(feature = "git"))]
+fn write_annotated_tag(
+    _tx: &mut WorkspaceCommandTransaction,
+    _name: &RefName,
+    _target_commit: &Commit,
+    _message: &str,
+    _args: &TagCreateArgs,
+) -> Result<(), CommandError> {
+    Err(user_error(
+        "Annotated tags are only supported in Git-backed repositories",
+    ))
+}
@@ -0,0 +1,362 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::env;
+use std::io;
+use std::io::IsTerminal as _;
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
+use std::process::Command;
+use std::process::ExitStatus;
+
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::queue;
+use crossterm::style::Attribute;
+use crossterm::style::Print;
+use crossterm::style::SetAttribute;
+use crossterm::terminal;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::graphlog::GraphStyle;
+use crate::graphlog::get_graphlog;
+use crate::templater::TemplateRenderer;
+use crate::text_util;
+use crate::ui::Ui;
+
+const HELP: &str = "j/k: move  enter: show  e: edit  n: new  s: squash  a: absorb  S: split  b: set \
+                    bookmark  B: delete bookmark  u: undo  r: refresh  q: quit";
+
+/// Browse and edit the repo in a full-screen terminal interface
+///
+/// Shows the revisions selected by `revsets.log` as a graph, like `jj log`.
+/// Move between revisions with the arrow keys or `j`/`k`, and press a key to
+/// act on the selected revision:
+///
+/// * `enter`: show the revision with `jj show`
+///
+/// * `e`: edit the revision with `jj edit`
+///
+/// * `n`: create a new revision on top with `jj new`
+///
+/// * `s`: squash the revision into its parent with `jj squash`
+///
+/// * `a`: absorb the revision's changes into its ancestors with `jj absorb`
+///
+/// * `S`: split the revision with `jj split`
+///
+/// * `b`: create or move a bookmark to the revision
+///
+/// * `B`: delete a bookmark
+///
+/// * `u`: undo the last operation with `jj undo`
+///
+/// The actions run the corresponding commands, so they behave exactly as when
+/// run from the command line, including their configuration.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct TuiArgs {}
+
+pub(crate) fn cmd_tui(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &TuiArgs,
+) -> Result<(), CommandError> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(user_error(
+            "`jj tui` must be run in an interactive terminal",
+        ));
+    }
+    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_root = workspace_command.workspace_root().to_owned();
+    let mut log = LogView::load(ui, &workspace_command)?;
+    drop(workspace_command);
+
+    let mut message = String::new();
+    loop {
+        let action = {
+            let _screen = ScreenGuard::enter()?;
+            loop {
+                log.draw(&message)?;
+                match read_key()? {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('j') | KeyCode::Down => log.move_selection(1),
+                    KeyCode::Char('k') | KeyCode::Up => log.move_selection(-1),
+                    KeyCode::Char('g') | KeyCode::Home => log.selected = 0,
+                    KeyCode::Char('G') | KeyCode::End => {
+                        log.selected = log.entries.len().saturating_sub(1);
+                    }
+                    KeyCode::Char('r') => break Action::Refresh,
+                    KeyCode::Char('u') => break Action::Run(vec!["undo".to_owned()]),
+                    KeyCode::Enter => {
+                        if let Some(id) = log.selected_id() {
+                            break Action::Run(vec!["show".to_owned(), "-r".to_owned(), id]);
+                        }
+                    }
+                    KeyCode::Char(key @ ('e' | 'n' | 's' | 'a' | 'S' | 'b' | 'B')) => {
+                        if let Some(id) = log.selected_id() {
+                            break Action::OnRevision(key, id);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        };
+
+        let args = match action {
+            Action::Refresh => None,
+            Action::Run(args) => Some(args),
+            Action::OnRevision(key, id) => revision_command(ui, key, id)?,
+        };
+        if let Some(args) = args {
+            let status = run_jj(&workspace_root, &args)?;
+            message = if status.success() {
+                format!("`jj {}` succeeded", args.join(" "))
+            } else {
+                // Leave the command output on screen until acknowledged.
+                writeln!(ui.stderr(), "Press enter to continue")?;
+                io::stdin().read_line(&mut String::new())?;
+                format!("`jj {}` failed with {status}", args.join(" "))
+            };
+        }
+        let workspace_command = command.workspace_helper(ui)?;
+        let selected_id = log.selected_id();
+        log = LogView::load(ui, &workspace_command)?;
+        log.select_id(selected_id.as_deref());
+    }
+}
+
+enum Action {
+    Refresh,
+    Run(Vec<String>),
+    OnRevision(char, String),
+}
+
+/// Builds the arguments of the command bound to `key`, prompting for input if
+/// needed. Returns `None` if the prompt is canceled.
+fn revision_command(ui: &Ui, key: char, id: String) -> Result<Option<Vec<String>>, CommandError> {
+    let args = match key {
+        'e' => vec!["edit".to_owned(), id],
+        'n' => vec!["new".to_owned(), id],
+        's' => vec!["squash".to_owned(), "-r".to_owned(), id],
+        'a' => vec!["absorb".to_owned(), "--from".to_owned(), id],
+        'S' => vec!["split".to_owned(), "-r".to_owned(), id],
+        'b' | 'B' => {
+            let name = ui.prompt("Bookmark name (empty to cancel)")?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Ok(None);
+            }
+            if key == 'b' {
+                let args = ["bookmark", "set", "--allow-backwards", "-r", &id, name];
+                args.map(str::to_owned).to_vec()
+            } else {
+                vec!["bookmark".to_owned(), "delete".to_owned(), name.to_owned()]
+            }
+        }
+        _ => unreachable!(),
+    };
+    Ok(Some(args))
+}
+
+fn run_jj(workspace_root: &Path, args: &[String]) -> io::Result<ExitStatus> {
+    Command::new(env::current_exe()?)
+        .arg("--repository")
+        .arg(workspace_root)
+        .args(args)
+        .status()
+}
+
+fn read_key() -> io::Result<KeyCode> {
+    loop {
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            return Ok(key.code);
+        }
+    }
+}
+
+/// Switches the terminal to the alternate screen in raw mode, and restores it
+/// when dropped.
+struct ScreenGuard;
+
+impl ScreenGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self;
+        queue!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        io::stdout().flush()?;
+        Ok(guard)
+    }
+}
+
+impl Drop for ScreenGuard {
+    fn drop(&mut self) {
+        _ = queue!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        _ = io::stdout().flush();
+        _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Rendered revision graph with a selected revision.
+struct LogView {
+    lines: Vec<String>,
+    entries: Vec<LogEntry>,
+    selected: usize,
+    scroll: usize,
+}
+
+struct LogEntry {
+    commit_id: CommitId,
+    lines: Range<usize>,
+}
+
+impl LogView {
+    fn load(ui: &Ui, workspace_command: &WorkspaceCommandHelper) -> Result<Self, CommandError> {
+        let settings = workspace_command.settings();
+        let revset = workspace_command
+            .parse_revset(ui, &RevisionArg::from(settings.get_string("revsets.log")?))?
+            .evaluate()?;
+        let language = workspace_command.commit_template_language();
+        let template: TemplateRenderer<Commit> = workspace_command.parse_template(
+            ui,
+            &language,
+            &settings.get_string("templates.log")?,
+        )?;
+        let node_template: TemplateRenderer<Option<Commit>> = workspace_command.parse_template(
+            ui,
+            &language,
+            &settings.get_string("templates.log_node")?,
+        )?;
+        let store = workspace_command.repo().store();
+
+        let output = RefCell::new(vec![]);
+        let mut writer = SharedWriter(&output);
+        let mut graph = get_graphlog(GraphStyle::from_settings(settings)?, false, &mut writer);
+        let mut entries = vec![];
+        let mut num_lines = 0;
+        for node in TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id) {
+            let (commit_id, edges) = node?;
+            let commit = store.get_commit(&commit_id)?;
+            let text = String::from_utf8_lossy(&template.format_plain_text(&commit)).into_owned();
+            let node_symbol =
+                String::from_utf8_lossy(&node_template.format_plain_text(&Some(commit)))
+                    .into_owned();
+            graph.add_node(&commit_id, &edges, &node_symbol, &text)?;
+            let end = output.borrow().iter().filter(|&&b| b == b'\n').count();
+            entries.push(LogEntry {
+                commit_id,
+                lines: num_lines..end,
+            });
+            num_lines = end;
+        }
+        drop(graph);
+        let lines = String::from_utf8_lossy(&output.into_inner())
+            .lines()
+            .map(str::to_owned)
+            .collect();
+        Ok(Self {
+            lines,
+            entries,
+            selected: 0,
+            scroll: 0,
+        })
+    }
+
+    fn selected_id(&self) -> Option<String> {
+        let entry = self.entries.get(self.selected)?;
+        Some(entry.commit_id.to_string())
+    }
+
+    fn select_id(&mut self, id: Option<&str>) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| Some(entry.commit_id.to_string().as_str()) == id)
+        {
+            self.selected = index;
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let last = self.entries.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    fn draw(&mut self, message: &str) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (usize::from(width), usize::from(height));
+        // The last two rows show the message and the key bindings.
+        let page_height = height.saturating_sub(2).max(1);
+        let selected_lines = self
+            .entries
+            .get(self.selected)
+            .map_or(0..0, |entry| entry.lines.clone());
+        if selected_lines.start < self.scroll {
+            self.scroll = selected_lines.start;
+        } else if selected_lines.end > self.scroll + page_height {
+            self.scroll = selected_lines.end.saturating_sub(page_height);
+        }
+
+        let mut stdout = io::stdout().lock();
+        queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+        let visible_lines = self.lines.iter().enumerate().skip(self.scroll);
+        for (row, (index, line)) in visible_lines.take(page_height).enumerate() {
+            let (line, _) = text_util::elide_end(line, "", width);
+            queue!(stdout, cursor::MoveTo(0, row as u16))?;
+            if selected_lines.contains(&index) {
+                queue!(
+                    stdout,
+                    SetAttribute(Attribute::Reverse),
+                    Print(line),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(stdout, Print(line))?;
+            }
+        }
+        for (row, text) in [(page_height, message), (page_height + 1, HELP)] {
+            let (text, _) = text_util::elide_end(text, "…", width);
+            queue!(stdout, cursor::MoveTo(0, row as u16), Print(text))?;
+        }
+        stdout.flush()
+    }
+}
+
+/// Writer appending to a buffer that can be inspected while it's borrowed by
+/// the graph renderer.
+struct SharedWriter<'a>(&'a RefCell<Vec<u8>>);
+
+impl Write for SharedWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
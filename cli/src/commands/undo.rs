@@ -12,12 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::slice;
+
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::evolution::accumulate_predecessors;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::OperationId;
+use jj_lib::operation::Operation;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::LogContentFormat;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::internal_error;
 use crate::command_error::user_error;
@@ -30,6 +36,8 @@ use crate::commands::operation::revert::OperationRevertArgs;
 use crate::commands::operation::revert::cmd_op_revert;
 use crate::commands::operation::view_with_desired_portions_restored;
 use crate::complete;
+use crate::graphlog::GraphStyle;
+use crate::op_diff_util::show_repo_diff;
 use crate::ui::Ui;
 
 /// Undo the last operation
@@ -45,6 +53,10 @@ use crate::ui::Ui;
 /// detailed description of any past undo/redo operations. See also `jj op
 /// restore` to explicitly restore an older operation by its id (available in
 /// the operation log).
+///
+/// Use `--preview` to see which commits, bookmarks, and working copies would be
+/// changed by the undo. With `--interactive`, the changes are shown before
+/// asking whether to undo.
 #[derive(clap::Args, Clone, Debug)]
 pub struct UndoArgs {
     /// (deprecated, use `jj op revert <operation>`)
@@ -63,6 +75,14 @@ pub struct UndoArgs {
     /// This option is EXPERIMENTAL.
     #[arg(long, value_enum, hide = true, default_values_t = DEFAULT_REVERT_WHAT)]
     what: Vec<RevertWhatToRestore>,
+
+    /// Show the changes the undo would make, and ask for confirmation
+    #[arg(long, short)]
+    interactive: bool,
+
+    /// Show the changes the undo would make, without undoing anything
+    #[arg(long, conflicts_with = "interactive")]
+    preview: bool,
 }
 
 pub(crate) const UNDO_OP_DESC_PREFIX: &str = "undo: restore to operation ";
@@ -193,6 +213,17 @@ pub fn cmd_undo(ui: &mut Ui, command: &CommandHelper, args: &UndoArgs) -> Result
             .load_operation(&id_of_original_op)?;
     }
 
+    if args.interactive || args.preview {
+        show_undo_preview(ui, &workspace_command, &op_to_restore, &DEFAULT_REVERT_WHAT)?;
+        if args.preview {
+            return Ok(());
+        }
+        if !ui.prompt_yes_no("Undo these changes?", None)? {
+            writeln!(ui.status(), "Nothing changed.")?;
+            return Ok(());
+        }
+    }
+
     let mut tx = workspace_command.start_transaction();
     let new_view = view_with_desired_portions_restored(
         op_to_restore.view()?.store_view(),
@@ -213,3 +244,61 @@ pub fn cmd_undo(ui: &mut Ui, command: &CommandHelper, args: &UndoArgs) -> Result
 
     Ok(())
 }
+
+/// Shows the changes that restoring the `what` portions of `op_to_restore`
+/// would make to the current repo.
+fn show_undo_preview(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    op_to_restore: &Operation,
+    what: &[RevertWhatToRestore],
+) -> Result<(), CommandError> {
+    let workspace_env = workspace_command.env();
+    let settings = workspace_command.settings();
+    let current_repo = workspace_command.repo();
+    let op_repo = current_repo.loader().load_at(op_to_restore)?;
+    // Merge index so commits in both repos are accessible.
+    let mut tx = current_repo.start_transaction();
+    tx.repo_mut().merge_index(&op_repo)?;
+    let new_view = view_with_desired_portions_restored(
+        op_to_restore.view()?.store_view(),
+        current_repo.view().store_view(),
+        what,
+    );
+    tx.repo_mut().set_view(new_view);
+    let restored_repo = tx.repo();
+    let predecessor_commits = accumulate_predecessors(
+        slice::from_ref(op_to_restore),
+        slice::from_ref(current_repo.operation()),
+    )?;
+
+    let id_prefix_context = workspace_env.new_id_prefix_context();
+    let commit_summary_template = {
+        let language = workspace_env.commit_template_language(restored_repo, &id_prefix_context);
+        let text = settings.get_string("templates.commit_summary")?;
+        workspace_env
+            .parse_template(ui, &language, &text)?
+            .labeled(["undo", "commit"])
+    };
+    let graph_style = GraphStyle::from_settings(settings)?;
+    let with_content_format = LogContentFormat::new(ui, settings)?;
+
+    let mut formatter = ui.stdout_formatter();
+    write!(formatter, "Would restore to operation: ")?;
+    workspace_command
+        .operation_summary_template()
+        .format(op_to_restore, formatter.as_mut())?;
+    writeln!(formatter)?;
+    show_repo_diff(
+        ui,
+        formatter.as_mut(),
+        restored_repo,
+        current_repo.as_ref(),
+        restored_repo,
+        &predecessor_commits,
+        &commit_summary_template,
+        Some(graph_style),
+        &with_content_format,
+        None,
+    )
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use jj_lib::repo::Repo as _;
+use jj_lib::workspace::Workspace;
+
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
@@ -20,7 +23,10 @@ use crate::ui::Ui;
 
 /// Execute an external command via jj
 ///
-/// This command will have access to the environment variable JJ_WORKSPACE_ROOT.
+/// When run inside a workspace, this command will have access to the
+/// environment variables JJ_WORKSPACE_ROOT and JJ_CHANGE_ID, the change ID of
+/// the working-copy commit. JJ_CHANGE_ID is left unset if the repo can't be
+/// loaded. The working copy isn't snapshotted before running the command.
 ///
 /// This is useful for arbitrary aliases.
 ///
@@ -74,19 +80,20 @@ pub(crate) struct UtilExecArgs {
 }
 
 pub fn cmd_util_exec(
-    _: &mut Ui,
+    ui: &mut Ui,
     command: &CommandHelper,
     args: &UtilExecArgs,
 ) -> Result<(), CommandError> {
-    let workspace_root = command
-        .workspace_loader()
-        .ok()
-        .map(|loader| loader.workspace_root());
     let mut cmd = std::process::Command::new(&args.command);
     cmd.args(&args.args);
 
-    if let Some(workspace_root) = workspace_root {
-        cmd.env("JJ_WORKSPACE_ROOT", workspace_root);
+    if let Ok(workspace) = command.load_workspace() {
+        cmd.env("JJ_WORKSPACE_ROOT", workspace.workspace_root());
+        // The command may not need the repo at all, so don't fail if it can't
+        // be loaded.
+        if let Ok(Some(change_id)) = working_copy_change_id(ui, command, &workspace) {
+            cmd.env("JJ_CHANGE_ID", change_id);
+        }
     }
 
     let status = cmd.status().map_err(|err| {
@@ -108,3 +115,17 @@ pub fn cmd_util_exec(
     }
     Ok(())
 }
+
+fn working_copy_change_id(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace: &Workspace,
+) -> Result<Option<String>, CommandError> {
+    let op_head = command.resolve_operation(ui, workspace.repo_loader())?;
+    let repo = workspace.repo_loader().load_at(&op_head)?;
+    let Some(commit_id) = repo.view().get_wc_commit_id(workspace.workspace_name()) else {
+        return Ok(None);
+    };
+    let commit = repo.store().get_commit(commit_id)?;
+    Ok(Some(commit.change_id().reverse_hex()))
+}
@@ -12,21 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
 use std::slice;
 use std::time::Duration;
 use std::time::SystemTime;
 
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::fmt_util::binary_prefix;
 use jj_lib::repo::Repo as _;
+use jj_lib::store::Store;
+use jj_lib::time_util::DatePattern;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::parse_date_after;
 use crate::command_error::CommandError;
+use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::ui::Ui;
 
 /// Run backend-dependent garbage collection.
 ///
+/// Deletes operations and views that are no longer reachable from the current
+/// operation, the index files of the deleted operations, and the commits and
+/// other objects that are no longer reachable from the remaining operations.
+/// The amount of disk space reclaimed is reported at the end.
+///
 /// To garbage-collect old operations and the commits/objects referenced by
-/// them, run `jj op abandon ..<some old operation>` before `jj util gc`.
+/// them, run `jj op abandon ..<some old operation>` or `jj op prune` before
+/// `jj util gc`.
 #[derive(clap::Args, Clone, Debug)]
 pub struct UtilGcArgs {
     /// Time threshold
@@ -34,11 +50,17 @@ pub struct UtilGcArgs {
     /// By default, only obsolete objects and operations older than 2 weeks are
     /// pruned.
     ///
-    /// Only the string "now" can be passed to this parameter. Support for
-    /// arbitrary absolute and relative timestamps will come in a subsequent
-    /// release.
-    #[arg(long)]
+    /// Only the string "now" can be passed to this parameter. Use
+    /// `--keep-since` to specify other dates.
+    #[arg(long, conflicts_with = "keep_since")]
     expire: Option<String>,
+    /// Keep obsolete objects and operations modified at or after the given
+    /// date
+    ///
+    /// The date can be absolute like `2025-01-01`, or relative like `3 days
+    /// ago`.
+    #[arg(long, value_name = "DATE")]
+    keep_since: Option<String>,
 }
 
 pub fn cmd_util_gc(
@@ -51,16 +73,109 @@ pub fn cmd_util_gc(
             "Cannot garbage collect from a non-head operation",
         ));
     }
-    let keep_newer = match args.expire.as_deref() {
-        None => SystemTime::now() - Duration::from_secs(14 * 86400),
-        Some("now") => SystemTime::now() - Duration::ZERO,
-        _ => return Err(user_error("--expire only accepts 'now'")),
+    let keep_newer = if let Some(date) = &args.keep_since {
+        let (DatePattern::AtOrAfter(time) | DatePattern::Before(time)) =
+            parse_date_after(command.settings(), date)?;
+        SystemTime::UNIX_EPOCH + Duration::from_millis(time.0.try_into().unwrap_or(0))
+    } else {
+        match args.expire.as_deref() {
+            None => SystemTime::now() - Duration::from_secs(14 * 86400),
+            Some("now") => SystemTime::now() - Duration::ZERO,
+            _ => return Err(user_error("--expire only accepts 'now'")),
+        }
     };
     let workspace_command = command.workspace_helper(ui)?;
 
     let repo = workspace_command.repo();
+    let usage_dirs = UsageDirs::new(workspace_command.repo_path(), repo.store());
+    let usage_before = usage_dirs.measure()?;
     repo.op_store()
         .gc(slice::from_ref(repo.op_id()), keep_newer)?;
+    if let Some(index_store) = repo.index_store().downcast_ref::<DefaultIndexStore>() {
+        index_store
+            .gc(repo.op_store().as_ref(), keep_newer)
+            .map_err(internal_error)?;
+    }
     repo.store().gc(repo.index(), keep_newer)?;
+    let usage_after = usage_dirs.measure()?;
+
+    let reclaimed = |before: u64, after: u64| format_size(before.saturating_sub(after));
+    writeln!(
+        ui.status(),
+        "Reclaimed {total}: {op_log} from the operation log, {index} from the index, {commits} \
+         from commit storage.",
+        total = reclaimed(usage_before.iter().sum(), usage_after.iter().sum()),
+        op_log = reclaimed(usage_before[0], usage_after[0]),
+        index = reclaimed(usage_before[1], usage_after[1]),
+        commits = reclaimed(usage_before[2], usage_after[2]),
+    )?;
     Ok(())
 }
+
+/// Directories whose disk usage is reported.
+struct UsageDirs {
+    op_log: Vec<PathBuf>,
+    index: Vec<PathBuf>,
+    commits: Vec<PathBuf>,
+}
+
+impl UsageDirs {
+    fn new(repo_path: &Path, store: &Store) -> Self {
+        let store_path = repo_path.join("store");
+        let mut commits = vec![store_path.clone()];
+        commits.extend(external_git_repo_path(&store_path, store));
+        Self {
+            op_log: vec![repo_path.join("op_store"), repo_path.join("op_heads")],
+            index: vec![repo_path.join("index")],
+            commits,
+        }
+    }
+
+    fn measure(&self) -> io::Result<[u64; 3]> {
+        let measure_all = |paths: &[PathBuf]| -> io::Result<u64> {
+            paths.iter().map(|path| disk_usage(path)).sum()
+        };
+        Ok([
+            measure_all(&self.op_log)?,
+            measure_all(&self.index)?,
+            measure_all(&self.commits)?,
+        ])
+    }
+}
+
+/// Returns the path to the backing Git repository if it isn't in the store,
+/// as in colocated workspaces.
+#[cfg(feature = "git")]
+fn external_git_repo_path(store_path: &Path, store: &Store) -> Option<PathBuf> {
+    let git_repo_path = jj_lib::git::get_git_backend(store).ok()?.git_repo_path();
+    let store_path = dunce::canonicalize(store_path).unwrap_or_else(|_| store_path.to_owned());
+    (!git_repo_path.starts_with(store_path)).then(|| git_repo_path.to_owned())
+}
+
+#[cfg(not(feature = "git"))]
+fn external_git_repo_path(_store_path: &Path, _store: &Store) -> Option<PathBuf> {
+    None
+}
+
+/// Returns the total size of the files in the `path` directory.
+fn disk_usage(path: &Path) -> io::Result<u64> {
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        // The file may have been removed concurrently.
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err),
+    };
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+    let mut total = 0;
+    for entry in path.read_dir()? {
+        total += disk_usage(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+fn format_size(bytes: u64) -> String {
+    let (scaled, prefix) = binary_prefix(bytes as f32);
+    format!("{scaled:.1} {prefix}B")
+}
@@ -0,0 +1,160 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::signing::SigStatus;
+use jj_lib::signing::SignError;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Check the cryptographic signatures of revisions
+///
+/// Each revision is printed with the status of its signature: `good`,
+/// `unknown` (the signing key isn't trusted or can't be found), `bad` or
+/// `invalid`, or `unsigned`. The command fails if any signature is bad or
+/// invalid, or if any revision is unsigned or signed with an unknown key
+/// unless that is explicitly allowed. The root commit is skipped. For
+/// example, to require that all commits reachable from trunk are signed by a
+/// trusted key:
+///
+/// $ jj verify -r '::trunk()'
+///
+/// Signatures are verified with the configured [commit signing] backends.
+///
+/// [commit signing]:
+///     https://jj-vcs.github.io/jj/latest/config/#commit-signing
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct VerifyArgs {
+    /// The revision(s) to check
+    ///
+    /// If no revisions are specified, this defaults to the `revsets.verify`
+    /// setting.
+    #[arg(
+        long, short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+
+    /// Don't fail on revisions without a signature
+    #[arg(long)]
+    allow_unsigned: bool,
+
+    /// Don't fail on signatures made with unknown keys
+    #[arg(long)]
+    allow_unknown: bool,
+
+    /// Render each revision using the given template
+    ///
+    /// All 0-argument methods of the [`Commit` type] are available as
+    /// keywords in the template expression. See [`jj help -k templates`] for
+    /// more information.
+    ///
+    /// If not specified, this defaults to the `templates.verify` setting.
+    ///
+    /// [`Commit` type]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/#commit-type
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+pub(crate) fn cmd_verify(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &VerifyArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+
+    let revset_expression = if args.revisions.is_empty() {
+        let revset_string = workspace_command.settings().get_string("revsets.verify")?;
+        workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+    } else {
+        workspace_command.parse_union_revsets(ui, &args.revisions)?
+    };
+    // The root commit can't be signed, so it's never checked.
+    let root_commit_id = workspace_command.repo().store().root_commit_id();
+    let commits: Vec<Commit> = revset_expression
+        .evaluate_to_commits()?
+        .filter_ok(|commit| commit.id() != root_commit_id)
+        .try_collect()?;
+
+    let template_string = match &args.template {
+        Some(value) => value.clone(),
+        None => workspace_command
+            .settings()
+            .get_string("templates.verify")?,
+    };
+    let template = workspace_command
+        .parse_commit_template(ui, &template_string)?
+        .labeled(["verify", "commit"]);
+
+    let mut num_good = 0;
+    let mut num_unknown = 0;
+    let mut num_bad = 0;
+    let mut num_unsigned = 0;
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for commit in &commits {
+        template.format(commit, formatter.as_mut())?;
+        match commit.verification() {
+            Ok(None) => num_unsigned += 1,
+            Ok(Some(verification)) => match verification.status {
+                SigStatus::Good => num_good += 1,
+                SigStatus::Unknown => num_unknown += 1,
+                SigStatus::Bad => num_bad += 1,
+            },
+            Err(SignError::InvalidSignatureFormat) => num_bad += 1,
+            Err(err) => {
+                return Err(user_error_with_message(
+                    format!(
+                        "Failed to verify the signature of commit {}",
+                        commit.id().hex()
+                    ),
+                    err,
+                ));
+            }
+        }
+    }
+    drop(formatter);
+
+    writeln!(
+        ui.status(),
+        "Checked {} revisions: {num_good} good, {num_unknown} unknown, {num_bad} bad, \
+         {num_unsigned} unsigned",
+        commits.len()
+    )?;
+    let num_failed = num_bad
+        + if args.allow_unknown { 0 } else { num_unknown }
+        + if args.allow_unsigned { 0 } else { num_unsigned };
+    if num_failed > 0 {
+        return Err(user_error(format!(
+            "{num_failed} revisions failed signature verification"
+        )));
+    }
+    Ok(())
+}
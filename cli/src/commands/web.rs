@@ -0,0 +1,509 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Write as _;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::op_walk;
+use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::short_operation_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::UnifiedDiffOptions;
+use crate::formatter::PlainTextFormatter;
+use crate::graphlog::GraphStyle;
+use crate::graphlog::get_graphlog;
+use crate::templater::TemplateRenderer;
+use crate::ui::Ui;
+
+/// Maximum number of operations shown on the operation log page.
+const OP_LOG_LIMIT: usize = 100;
+
+/// Serve a read-only web interface for browsing the repo
+///
+/// Starts a local HTTP server with pages for the revision graph, the changes
+/// in each revision, the operation log, and the conflicted revisions. The
+/// pages are rendered with the same templates and settings as `jj log` and
+/// `jj show`. The working copy is snapshotted whenever a page is loaded.
+///
+/// The server never modifies the repo other than by snapshotting the working
+/// copy. It listens on the loopback interface unless `--bind` is given, and
+/// only answers requests addressed to `localhost` or to an IP address. Stop it
+/// with Ctrl-C.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct WebArgs {
+    /// The port to listen on
+    #[arg(long, short, default_value_t = 8000)]
+    port: u16,
+    /// The address to listen on
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    bind: IpAddr,
+}
+
+pub(crate) fn cmd_web(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &WebArgs,
+) -> Result<(), CommandError> {
+    // Fail early if there's no repo to serve.
+    command.workspace_helper(ui)?;
+    let listener = TcpListener::bind((args.bind, args.port)).map_err(|err| {
+        user_error_with_message(
+            format!("Failed to listen on {}:{}", args.bind, args.port),
+            err,
+        )
+    })?;
+    let addr = listener.local_addr()?;
+    writeln!(ui.status(), "Serving the repo at http://{addr}/")?;
+    // Each connection is read and answered by its own thread, so a slow client
+    // doesn't block the others. The pages are rendered one at a time on this
+    // thread, which owns the workspace.
+    let (event_tx, event_rx) = mpsc::channel();
+    thread::spawn(move || accept_connections(&listener, addr.ip(), &event_tx));
+    for event in event_rx {
+        match event {
+            Event::Page(request) => {
+                let result = render_page(ui, command, &request.path, &request.query)
+                    .map_err(|err| err.error.to_string());
+                request.reply.send(result).ok();
+            }
+            Event::Warning(message) => writeln!(ui.warning_default(), "{message}")?,
+        }
+    }
+    Ok(())
+}
+
+/// Time allowed for a client to send its request or receive the response.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+enum Event {
+    Page(PageRequest),
+    Warning(String),
+}
+
+/// Page to be rendered by the main thread.
+struct PageRequest {
+    path: String,
+    query: String,
+    reply: mpsc::Sender<Result<Option<String>, String>>,
+}
+
+fn accept_connections(listener: &TcpListener, bind: IpAddr, events: &mpsc::Sender<Event>) {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                let message = format!("Failed to accept connection: {err}");
+                events.send(Event::Warning(message)).ok();
+                continue;
+            }
+        };
+        let events = events.clone();
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(&stream, bind, &events) {
+                let message = format!("Failed to handle request: {err}");
+                events.send(Event::Warning(message)).ok();
+            }
+        });
+    }
+}
+
+fn handle_connection(
+    stream: &TcpStream,
+    bind: IpAddr,
+    events: &mpsc::Sender<Event>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Only the Host header is used. The request body, if any, is ignored.
+    let mut host = None;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("host")
+        {
+            host = Some(value.trim().to_owned());
+        }
+        header.clear();
+    }
+
+    let response = match parse_request_line(&request_line) {
+        // Pages of a local server could otherwise be read by any website
+        // through DNS rebinding.
+        Some(_) if !is_allowed_host(host.as_deref(), bind) => {
+            Response::error("403 Forbidden", "Unexpected Host header")
+        }
+        Some(("GET", target)) => {
+            let (path, query) = target.split_once('?').unwrap_or((target, ""));
+            let (reply, reply_rx) = mpsc::channel();
+            let request = PageRequest {
+                path: path.to_owned(),
+                query: query.to_owned(),
+                reply,
+            };
+            events.send(Event::Page(request)).ok();
+            match reply_rx.recv() {
+                Ok(Ok(Some(body))) => Response::ok(body),
+                Ok(Ok(None)) => Response::error("404 Not Found", "No such page"),
+                Ok(Err(message)) => Response::error("500 Internal Server Error", &message),
+                Err(_) => Response::error("503 Service Unavailable", "Server is stopping"),
+            }
+        }
+        Some(_) => Response::error("405 Method Not Allowed", "Only GET requests are supported"),
+        None => Response::error("400 Bad Request", "Malformed request"),
+    };
+    response.write_to(stream)
+}
+
+/// Returns true if the `Host` header names the server by `localhost` or by IP
+/// address. Other host names may resolve to the server only through DNS
+/// rebinding.
+fn is_allowed_host(host: Option<&str>, bind: IpAddr) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    // Strip the port, taking care of bracketed IPv6 addresses.
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !port.contains(']') => name,
+        _ => host,
+    };
+    let name = name
+        .strip_prefix('[')
+        .and_then(|name| name.strip_suffix(']'))
+        .unwrap_or(name);
+    if name.eq_ignore_ascii_case("localhost") {
+        return true;
+    }
+    match name.parse::<IpAddr>() {
+        Ok(ip) => ip == bind || ip.is_loopback() || bind.is_unspecified(),
+        Err(_) => false,
+    }
+}
+
+/// Parses `METHOD TARGET HTTP/x.y` into method and target.
+fn parse_request_line(line: &str) -> Option<(&str, &str)> {
+    let (method, target, version) = line.split_whitespace().collect_tuple()?;
+    version.starts_with("HTTP/").then_some((method, target))
+}
+
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self {
+            status: "200 OK",
+            body,
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        let body = html_page(status, &format!("<pre>{}</pre>", escape_html(message)));
+        Self { status, body }
+    }
+
+    fn write_to(&self, mut stream: &TcpStream) -> io::Result<()> {
+        let headers = [
+            format!("HTTP/1.1 {}", self.status),
+            "Content-Type: text/html; charset=utf-8".to_owned(),
+            format!("Content-Length: {}", self.body.len()),
+            "Connection: close".to_owned(),
+        ];
+        for header in headers {
+            stream.write_all(header.as_bytes())?;
+            stream.write_all(b"\r\n")?;
+        }
+        stream.write_all(b"\r\n")?;
+        stream.write_all(self.body.as_bytes())?;
+        stream.flush()
+    }
+}
+
+fn render_page(
+    ui: &Ui,
+    command: &CommandHelper,
+    path: &str,
+    query: &str,
+) -> Result<Option<String>, CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let page = if path == "/" || path == "/log" {
+        let revisions = query_param(query, "r");
+        let content = render_log(ui, &workspace_command, revisions.as_deref())?;
+        html_page("Log", &content)
+    } else if let Some(id) = path.strip_prefix("/show/") {
+        let Some(commit_id) = CommitId::try_from_hex(id) else {
+            return Ok(None);
+        };
+        let content = render_show(ui, &workspace_command, &commit_id)?;
+        html_page(id, &content)
+    } else if path == "/ops" {
+        let content = render_op_log(&workspace_command)?;
+        html_page("Operation log", &content)
+    } else if path == "/conflicts" {
+        let content = render_conflicts(ui, &workspace_command)?;
+        html_page("Conflicts", &content)
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(page))
+}
+
+fn render_log(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    revisions: Option<&str>,
+) -> Result<String, CommandError> {
+    let settings = workspace_command.settings();
+    let revset_string = match revisions {
+        Some(revisions) => revisions.to_owned(),
+        None => settings.get_string("revsets.log")?,
+    };
+    let revset = workspace_command
+        .parse_revset(ui, &RevisionArg::from(revset_string.clone()))?
+        .evaluate()?;
+    let language = workspace_command.commit_template_language();
+    let template =
+        workspace_command.parse_template(ui, &language, &settings.get_string("templates.log")?)?;
+    let node_template: TemplateRenderer<Option<Commit>> = workspace_command.parse_template(
+        ui,
+        &language,
+        &settings.get_string("templates.log_node")?,
+    )?;
+    let store = workspace_command.repo().store();
+
+    let mut output = vec![];
+    {
+        let mut graph = get_graphlog(GraphStyle::from_settings(settings)?, false, &mut output);
+        for node in TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id) {
+            let (commit_id, edges) = node?;
+            let commit = store.get_commit(&commit_id)?;
+            let text = String::from_utf8_lossy(&template.format_plain_text(&commit)).into_owned();
+            // Link the first line of each entry to the revision page. The graph
+            // renderer doesn't interpret the text, so markup can be embedded.
+            let (first_line, rest) = text.split_once('\n').unwrap_or((&text, ""));
+            let text = format!(
+                "<a href=\"/show/{commit_id}\">{}</a>\n{}",
+                escape_html(first_line),
+                escape_html(rest)
+            );
+            let node_symbol = escape_html(&String::from_utf8_lossy(
+                &node_template.format_plain_text(&Some(commit)),
+            ));
+            graph.add_node(&commit_id, &edges, &node_symbol, &text)?;
+        }
+    }
+    Ok(format!(
+        "<form action=\"/log\"><input name=\"r\" size=\"60\" value=\"{}\"> <button>Show</button>\
+         </form>\n<pre>{}</pre>",
+        escape_html(&revset_string),
+        String::from_utf8_lossy(&output)
+    ))
+}
+
+fn render_show(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    commit_id: &CommitId,
+) -> Result<String, CommandError> {
+    let commit = workspace_command.repo().store().get_commit(commit_id)?;
+    let template = workspace_command.parse_commit_template(
+        ui,
+        &workspace_command.settings().get_string("templates.show")?,
+    )?;
+    let options = UnifiedDiffOptions::from_settings(workspace_command.settings())?;
+    let mut output = vec![];
+    {
+        let mut formatter = PlainTextFormatter::new(&mut output);
+        template.format(&commit, &mut formatter)?;
+        workspace_command
+            .diff_renderer(vec![DiffFormat::Git(Box::new(options))])
+            .show_patch(ui, &mut formatter, &commit, &EverythingMatcher, 80)
+            .block_on()?;
+    }
+    Ok(format!(
+        "<pre>{}</pre>",
+        escape_html(&String::from_utf8_lossy(&output))
+    ))
+}
+
+fn render_op_log(workspace_command: &WorkspaceCommandHelper) -> Result<String, CommandError> {
+    let current_op = workspace_command.repo().operation().clone();
+    let mut output = String::new();
+    for op in op_walk::walk_ancestors(&[current_op]).take(OP_LOG_LIMIT) {
+        let op = op?;
+        let metadata = op.metadata();
+        output.push_str(&escape_html(&format!(
+            "{} {}@{} {}\n",
+            short_operation_hash(op.id()),
+            metadata.username,
+            metadata.hostname,
+            metadata.description
+        )));
+    }
+    Ok(format!("<pre>{output}</pre>"))
+}
+
+fn render_conflicts(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<String, CommandError> {
+    let commits: Vec<Commit> = workspace_command
+        .parse_revset(ui, &RevisionArg::from("conflicts()".to_owned()))?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if commits.is_empty() {
+        return Ok("<p>There are no conflicted revisions.</p>".to_owned());
+    }
+    let template = workspace_command.commit_summary_template();
+    let mut output = String::new();
+    for commit in &commits {
+        let summary = String::from_utf8_lossy(&template.format_plain_text(commit)).into_owned();
+        output.push_str(&format!(
+            "<a href=\"/show/{}\">{}</a>\n",
+            commit.id(),
+            escape_html(&summary)
+        ));
+        for (path, _) in commit.tree().conflicts() {
+            let ui_path = workspace_command.format_file_path(&path);
+            output.push_str(&format!("    {}\n", escape_html(&ui_path)));
+        }
+    }
+    Ok(format!("<pre>{output}</pre>"))
+}
+
+fn html_page(title: &str, content: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{} - jj</title>\n\
+         </head>\n<body>\n<nav><a href=\"/log\">Log</a> | <a href=\"/ops\">Operations</a> | <a \
+         href=\"/conflicts\">Conflicts</a></nav>\n{content}\n</body>\n</html>\n",
+        escape_html(title)
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Looks up a parameter in a URL query string and percent-decodes its value.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    let value = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)?;
+    let mut bytes = vec![];
+    let mut rest = value.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let decoded = rest
+                    .get(..2)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(decoded) = decoded {
+                    bytes.push(decoded);
+                    rest = &rest[2..];
+                } else {
+                    bytes.push(b);
+                }
+            }
+            _ => bytes.push(b),
+        }
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_line() {
+        assert_eq!(
+            parse_request_line("GET /log?r=%40 HTTP/1.1\r\n"),
+            Some(("GET", "/log?r=%40"))
+        );
+        assert_eq!(parse_request_line("GET /log\r\n"), None);
+        assert_eq!(parse_request_line("GET /log SMTP\r\n"), None);
+    }
+
+    #[test]
+    fn test_is_allowed_host() {
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert!(is_allowed_host(Some("localhost:8000"), localhost));
+        assert!(is_allowed_host(Some("127.0.0.1:8000"), localhost));
+        assert!(is_allowed_host(Some("[::1]:8000"), localhost));
+        assert!(is_allowed_host(Some("[::1]"), localhost));
+        assert!(!is_allowed_host(Some("evil.example.com:8000"), localhost));
+        assert!(!is_allowed_host(Some("192.168.1.2:8000"), localhost));
+        assert!(!is_allowed_host(None, localhost));
+
+        let any = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        assert!(is_allowed_host(Some("192.168.1.2:8000"), any));
+        assert!(!is_allowed_host(Some("evil.example.com"), any));
+    }
+
+    #[test]
+    fn test_query_param() {
+        assert_eq!(query_param("r=%40-%3A%3A", "r").as_deref(), Some("@-::"));
+        assert_eq!(query_param("a=1&r=x+%7C+y", "r").as_deref(), Some("x | y"));
+        assert_eq!(query_param("r=100%", "r").as_deref(), Some("100%"));
+        assert_eq!(query_param("a=1", "r"), None);
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("a <b> & \"c\""),
+            "a &lt;b&gt; &amp; &quot;c&quot;"
+        );
+    }
+}
@@ -13,27 +13,34 @@
 // limitations under the License.
 
 use std::fs;
+use std::path::Path;
 
 use itertools::Itertools as _;
 use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::file_util;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::rewrite::merge_commit_trees;
+use jj_lib::working_copy::SparseMode;
 use jj_lib::workspace::Workspace;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::ui::Ui;
 
 /// How to handle sparse patterns when creating a new workspace.
-#[derive(clap::ValueEnum, Clone, Debug, Eq, PartialEq)]
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 enum SparseInheritance {
     /// Copy all sparse patterns from the current workspace.
     Copy,
@@ -43,10 +50,25 @@ enum SparseInheritance {
     Empty,
 }
 
+/// Settings of a `workspace-presets.<name>` table.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct WorkspacePreset {
+    #[serde(default)]
+    revisions: Vec<String>,
+    sparse_patterns: Option<SparseInheritance>,
+    sparse_from: Option<String>,
+}
+
 /// Add a workspace
 ///
 /// By default, the new workspace inherits the sparse patterns of the current
-/// workspace. You can override this with the `--sparse-patterns` option.
+/// workspace. You can override this with the `--sparse-patterns` or
+/// `--sparse-from` options.
+///
+/// Options that are used often together can be saved as a named preset in the
+/// `workspace-presets.<name>` config table and applied with `--preset`.
+/// Options given on the command line take precedence over the preset.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceAddArgs {
     /// Where to create the new workspace
@@ -72,9 +94,25 @@ pub struct WorkspaceAddArgs {
     /// new r1 r2 r3 ...`.
     #[arg(long, short, value_name = "REVSETS")]
     revision: Vec<RevisionArg>,
-    /// How to handle sparse patterns when creating a new workspace.
-    #[arg(long, value_enum, default_value_t = SparseInheritance::Copy)]
-    sparse_patterns: SparseInheritance,
+    /// How to handle sparse patterns when creating a new workspace [default:
+    /// copy]
+    #[arg(long, value_enum)]
+    sparse_patterns: Option<SparseInheritance>,
+    /// Copy the sparse patterns from another workspace or from a file
+    ///
+    /// If the path is a directory, it's loaded as a workspace and its sparse
+    /// patterns are copied. Otherwise, the file is read as a list of patterns,
+    /// one per line, like in `jj sparse edit`.
+    #[arg(
+        long,
+        value_name = "WORKSPACE|FILE",
+        value_hint = clap::ValueHint::AnyPath,
+        conflicts_with = "sparse_patterns"
+    )]
+    sparse_from: Option<String>,
+    /// Apply the options saved in the `workspace-presets.<name>` config table
+    #[arg(long, value_name = "NAME")]
+    preset: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -84,6 +122,29 @@ pub fn cmd_workspace_add(
     args: &WorkspaceAddArgs,
 ) -> Result<(), CommandError> {
     let old_workspace_command = command.workspace_helper(ui)?;
+    let preset = if let Some(name) = &args.preset {
+        command
+            .settings()
+            .get::<WorkspacePreset>(["workspace-presets", name])
+            .optional()?
+            .ok_or_else(|| user_error(format!("No workspace preset named '{name}'")))?
+    } else {
+        WorkspacePreset::default()
+    };
+    // Resolve the sparse patterns before creating anything on disk so that a
+    // bad path doesn't leave an empty workspace directory behind.
+    let sparsity = if let Some(source) = &args.sparse_from {
+        Some(read_sparse_from(command, &command.cwd().join(source))?)
+    } else if let Some(inheritance) = args.sparse_patterns {
+        sparse_inheritance(&old_workspace_command, inheritance)?
+    } else if let Some(source) = &preset.sparse_from {
+        let source_path = old_workspace_command.workspace_root().join(source);
+        Some(read_sparse_from(command, &source_path)?)
+    } else {
+        let inheritance = preset.sparse_patterns.unwrap_or(SparseInheritance::Copy);
+        sparse_inheritance(&old_workspace_command, inheritance)?
+    };
+
     let destination_path = command.cwd().join(&args.destination);
     if destination_path.exists() {
         return Err(user_error("Workspace already exists"));
@@ -136,23 +197,11 @@ pub fn cmd_workspace_add(
 
     let mut new_workspace_command = command.for_workable_repo(ui, new_workspace, repo)?;
 
-    let sparsity = match args.sparse_patterns {
-        SparseInheritance::Full => None,
-        SparseInheritance::Empty => Some(vec![]),
-        SparseInheritance::Copy => {
-            let sparse_patterns = old_workspace_command
-                .working_copy()
-                .sparse_patterns()?
-                .to_vec();
-            Some(sparse_patterns)
-        }
-    };
-
-    if let Some(sparse_patterns) = sparsity {
+    if let Some((sparse_patterns, sparse_mode)) = sparsity {
         let (mut locked_ws, _wc_commit) = new_workspace_command.start_working_copy_mutation()?;
         locked_ws
             .locked_wc()
-            .set_sparse_patterns(sparse_patterns)
+            .set_sparse_patterns_with_mode(sparse_patterns, sparse_mode)
             .block_on()
             .map_err(|err| internal_error_with_message("Failed to set sparse patterns", err))?;
         let operation_id = locked_ws.locked_wc().old_operation_id().clone();
@@ -161,9 +210,19 @@ pub fn cmd_workspace_add(
 
     let mut tx = new_workspace_command.start_transaction();
 
+    let revisions = if args.revision.is_empty() {
+        preset
+            .revisions
+            .into_iter()
+            .map(RevisionArg::from)
+            .collect_vec()
+    } else {
+        args.revision.clone()
+    };
+
     // If no parent revisions are specified, create a working-copy commit based
     // on the parent of the current working-copy commit.
-    let parents = if args.revision.is_empty() {
+    let parents = if revisions.is_empty() {
         // Check out parents of the current workspace's working-copy commit, or the
         // root if there is no working-copy commit in the current workspace.
         if let Some(old_wc_commit_id) = tx
@@ -181,7 +240,7 @@ pub fn cmd_workspace_add(
         }
     } else {
         old_workspace_command
-            .resolve_some_revsets_default_single(ui, &args.revision)?
+            .resolve_some_revsets_default_single(ui, &revisions)?
             .iter()
             .map(|id| tx.repo().store().get_commit(id))
             .try_collect()?
@@ -201,3 +260,44 @@ pub fn cmd_workspace_add(
     )?;
     Ok(())
 }
+
+fn sparse_inheritance(
+    workspace_command: &WorkspaceCommandHelper,
+    inheritance: SparseInheritance,
+) -> Result<Option<(Vec<RepoPathBuf>, SparseMode)>, CommandError> {
+    match inheritance {
+        SparseInheritance::Full => Ok(None),
+        SparseInheritance::Empty => Ok(Some((vec![], SparseMode::Prefix))),
+        SparseInheritance::Copy => {
+            let working_copy = workspace_command.working_copy();
+            let sparse_patterns = working_copy.sparse_patterns()?.to_vec();
+            Ok(Some((sparse_patterns, working_copy.sparse_mode()?)))
+        }
+    }
+}
+
+/// Reads sparse patterns from the workspace at `path`, or from the patterns
+/// file at `path` if it isn't a directory.
+fn read_sparse_from(
+    command: &CommandHelper,
+    path: &Path,
+) -> Result<(Vec<RepoPathBuf>, SparseMode), CommandError> {
+    if path.is_dir() {
+        let workspace = command.load_workspace_at(path, command.settings())?;
+        let working_copy = workspace.working_copy();
+        let sparse_patterns = working_copy.sparse_patterns()?.to_vec();
+        return Ok((sparse_patterns, working_copy.sparse_mode()?));
+    }
+    let content = fs::read_to_string(path).context(path)?;
+    let sparse_patterns = content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            RepoPathBuf::from_relative_path(line).map_err(|err| {
+                user_error_with_message(format!("Failed to parse sparse pattern: {line}"), err)
+            })
+        })
+        .try_collect()?;
+    Ok((sparse_patterns, SparseMode::Prefix))
+}
@@ -12,27 +12,50 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+use std::io::Write as _;
+use std::path::Path;
+use std::sync::Arc;
+
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::file_util;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::workspace::Workspace;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::complete;
 use crate::ui::Ui;
 
 /// Stop tracking a workspace's working-copy commit in the repo
 ///
-/// The workspace will not be touched on disk. It can be deleted from disk
-/// before or after running this command.
+/// The workspace will not be touched on disk unless `--delete-files` is
+/// given. It can be deleted from disk before or after running this command.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceForgetArgs {
     /// Names of the workspaces to forget. By default, forgets only the current
     /// workspace.
     #[arg(add = ArgValueCandidates::new(complete::workspaces))]
     workspaces: Vec<WorkspaceNameBuf>,
+    /// Also delete the workspace directories from disk
+    ///
+    /// The workspaces are checked first, and nothing is forgotten or deleted
+    /// if any of them is stale or has changes that haven't been snapshotted.
+    #[arg(long)]
+    delete_files: bool,
+    /// Delete the workspace directories even if they're stale or have changes
+    /// that haven't been snapshotted
+    #[arg(long, requires = "delete_files")]
+    force: bool,
 }
 
 #[instrument(skip_all)]
@@ -60,6 +83,39 @@ pub fn cmd_workspace_forget(
         }
     }
 
+    let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path());
+    let mut paths_to_delete = vec![];
+    if args.delete_files {
+        let mut known_paths = workspace_store.workspace_paths()?;
+        known_paths.insert(
+            workspace_command.workspace_name().to_owned(),
+            workspace_command.workspace_root().to_owned(),
+        );
+        for ws in &wss {
+            let Some(path) = known_paths.remove(ws) else {
+                return Err(user_error_with_hint(
+                    format!("The location of workspace {} is unknown", ws.as_symbol()),
+                    "Forget the workspace without `--delete-files` and delete its directory \
+                     manually.",
+                ));
+            };
+            if workspace_command.repo_path().starts_with(&path) {
+                return Err(user_error(format!(
+                    "Workspace {} contains the repo and can't be deleted",
+                    ws.as_symbol()
+                )));
+            }
+            // The identity of the directory is checked even with `--force`, so
+            // that whatever now lives at the recorded path isn't deleted.
+            let workspace =
+                load_workspace_to_delete(command, workspace_command.repo_path(), ws, &path)?;
+            if !args.force {
+                check_deletable(ui, command, workspace_command.repo(), workspace)?;
+            }
+            paths_to_delete.push(path);
+        }
+    }
+
     // bundle every workspace forget into a single transaction, so that e.g.
     // undo correctly restores all of them at once.
     let mut tx = workspace_command.start_transaction();
@@ -75,5 +131,88 @@ pub fn cmd_workspace_forget(
     };
 
     tx.finish(ui, description)?;
+
+    for path in &paths_to_delete {
+        fs::remove_dir_all(path).context(path)?;
+        writeln!(
+            ui.status(),
+            "Deleted workspace directory {}",
+            file_util::relative_path(command.cwd(), path).display()
+        )?;
+    }
+    if args.delete_files {
+        let names = wss.iter().map(|ws| &**ws).collect_vec();
+        workspace_store.forget(&names)?;
+    }
+    Ok(())
+}
+
+/// Loads the workspace at `path`, checking that it's still the workspace
+/// `name` of the repo at `repo_path`.
+fn load_workspace_to_delete(
+    command: &CommandHelper,
+    repo_path: &Path,
+    name: &WorkspaceName,
+    path: &Path,
+) -> Result<Workspace, CommandError> {
+    let workspace = command.load_workspace_at(path, command.settings())?;
+    let same_repo = match (
+        dunce::canonicalize(workspace.repo_path()),
+        dunce::canonicalize(repo_path),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    if !same_repo {
+        return Err(user_error(format!(
+            "The directory of workspace {name} now belongs to another repo: {path}",
+            name = name.as_symbol(),
+            path = path.display()
+        )));
+    }
+    if workspace.workspace_name() != name {
+        return Err(user_error(format!(
+            "The directory of workspace {name} now belongs to workspace {other}: {path}",
+            name = name.as_symbol(),
+            other = workspace.workspace_name().as_symbol(),
+            path = path.display()
+        )));
+    }
+    Ok(workspace)
+}
+
+/// Checks that the working copy of the `workspace` is in sync with its
+/// working-copy commit and has no changes that haven't been snapshotted.
+/// Nothing is written to the working copy or the repo.
+fn check_deletable(
+    ui: &Ui,
+    command: &CommandHelper,
+    repo: &Arc<ReadonlyRepo>,
+    workspace: Workspace,
+) -> Result<(), CommandError> {
+    let name = workspace.workspace_name().to_owned();
+    let mut workspace_command = command.for_workable_repo(ui, workspace, repo.clone())?;
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let options =
+        workspace_command.snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)?;
+    let (mut locked_ws, wc_commit) = workspace_command.unchecked_start_working_copy_mutation()?;
+    if wc_commit.tree_ids() != locked_ws.locked_wc().old_tree().tree_ids() {
+        return Err(user_error_with_hint(
+            format!("Workspace {} is stale", name.as_symbol()),
+            "Run `jj workspace update-stale` in the workspace, or use `--force` to delete it \
+             anyway.",
+        ));
+    }
+    let (new_tree, _stats) = locked_ws.locked_wc().snapshot(&options).block_on()?;
+    if new_tree.tree_ids() != wc_commit.tree_ids() {
+        return Err(user_error_with_hint(
+            format!(
+                "Workspace {} has changes that haven't been snapshotted",
+                name.as_symbol()
+            ),
+            "Run `jj status` in the workspace to snapshot them, or use `--force` to delete \
+             them.",
+        ));
+    }
     Ok(())
 }
@@ -0,0 +1,215 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Output;
+use std::process::Stdio;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::file_util;
+use jj_lib::ref_name::WorkspaceName;
+use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Run a command in the root directory of each workspace
+///
+/// The command is run directly, not through a shell. Use e.g. `jj workspace
+/// run -- sh -c '...'` to run a shell script. The `JJ_WORKSPACE_ROOT` and
+/// `JJ_WORKSPACE_NAME` environment variables are set for the command.
+///
+/// Workspaces are found at the location they were created at. Workspaces
+/// created by older versions of jj are skipped unless they're the current
+/// workspace.
+///
+/// The command fails if the command failed in any of the workspaces.
+#[derive(clap::Args, Clone, Debug)]
+pub struct WorkspaceRunArgs {
+    /// Run the command in all workspaces (the default)
+    #[arg(long, conflicts_with = "workspaces")]
+    all: bool,
+    /// Run the command only in the given workspaces
+    #[arg(
+        long = "workspace",
+        value_name = "NAME",
+        add = ArgValueCandidates::new(complete::workspaces),
+    )]
+    workspaces: Vec<WorkspaceNameBuf>,
+    /// Run the command in all workspaces at the same time
+    ///
+    /// The output of each command is printed once it has finished.
+    #[arg(long)]
+    parallel: bool,
+    /// The command to run, followed by its arguments
+    #[arg(required = true, last = true, value_name = "COMMAND")]
+    command: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_workspace_run(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &WorkspaceRunArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let wc_commit_ids = workspace_command.repo().view().wc_commit_ids();
+    let names = if args.workspaces.is_empty() {
+        wc_commit_ids.keys().cloned().collect_vec()
+    } else {
+        for name in &args.workspaces {
+            if !wc_commit_ids.contains_key(name) {
+                return Err(user_error(format!(
+                    "No such workspace: {}",
+                    name.as_symbol()
+                )));
+            }
+        }
+        args.workspaces.iter().unique().cloned().collect_vec()
+    };
+
+    let mut known_paths =
+        SimpleWorkspaceStore::load(workspace_command.repo_path()).workspace_paths()?;
+    known_paths.insert(
+        workspace_command.workspace_name().to_owned(),
+        workspace_command.workspace_root().to_owned(),
+    );
+    let mut targets: Vec<(WorkspaceNameBuf, PathBuf)> = vec![];
+    for name in names {
+        match known_paths.remove(&name) {
+            Some(path) if path.is_dir() => targets.push((name, path)),
+            Some(path) => writeln!(
+                ui.warning_default(),
+                "Skipping workspace {name} because {path} doesn't exist",
+                name = name.as_symbol(),
+                path = path.display()
+            )?,
+            None => writeln!(
+                ui.warning_default(),
+                "Skipping workspace {name} because its location is unknown",
+                name = name.as_symbol()
+            )?,
+        }
+    }
+
+    let statuses = if args.parallel {
+        run_parallel(ui, command, &args.command, &targets)?
+    } else {
+        run_sequential(ui, command, &args.command, &targets)?
+    };
+    let failed = statuses
+        .iter()
+        .filter(|(_, status)| !status.success())
+        .map(|(name, _)| name.as_symbol())
+        .collect_vec();
+    if !failed.is_empty() {
+        return Err(user_error(format!(
+            "Command failed in {count} of {total} workspaces: {names}",
+            count = failed.len(),
+            total = statuses.len(),
+            names = failed.iter().join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn new_command(argv: &[String], name: &WorkspaceName, root: &Path) -> Command {
+    let (program, args) = argv.split_first().unwrap();
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .current_dir(root)
+        .env("JJ_WORKSPACE_ROOT", root)
+        .env("JJ_WORKSPACE_NAME", name.as_str());
+    cmd
+}
+
+fn spawn_error(argv: &[String], err: io::Error) -> CommandError {
+    user_error_with_message(
+        format!("Failed to execute external command '{}'", argv[0]),
+        err,
+    )
+}
+
+fn write_heading(
+    ui: &Ui,
+    command: &CommandHelper,
+    name: &WorkspaceName,
+    root: &Path,
+) -> io::Result<()> {
+    writeln!(
+        ui.status(),
+        "Running in workspace {name} ({path}):",
+        name = name.as_symbol(),
+        path = file_util::relative_path(command.cwd(), root).display()
+    )
+}
+
+fn run_sequential<'a>(
+    ui: &Ui,
+    command: &CommandHelper,
+    argv: &[String],
+    targets: &'a [(WorkspaceNameBuf, PathBuf)],
+) -> Result<Vec<(&'a WorkspaceName, ExitStatus)>, CommandError> {
+    let mut statuses: Vec<(&WorkspaceName, ExitStatus)> = vec![];
+    for (name, root) in targets {
+        write_heading(ui, command, name, root)?;
+        let status = new_command(argv, name, root)
+            .stdin(Stdio::null())
+            .status()
+            .map_err(|err| spawn_error(argv, err))?;
+        statuses.push((name.as_ref(), status));
+    }
+    Ok(statuses)
+}
+
+fn run_parallel<'a>(
+    ui: &Ui,
+    command: &CommandHelper,
+    argv: &[String],
+    targets: &'a [(WorkspaceNameBuf, PathBuf)],
+) -> Result<Vec<(&'a WorkspaceName, ExitStatus)>, CommandError> {
+    let outputs: Vec<io::Result<Output>> = std::thread::scope(|scope| {
+        let handles = targets
+            .iter()
+            .map(|(name, root)| {
+                scope.spawn(move || new_command(argv, name, root).stdin(Stdio::null()).output())
+            })
+            .collect_vec();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    let mut statuses: Vec<(&WorkspaceName, ExitStatus)> = vec![];
+    for ((name, root), output) in targets.iter().zip(outputs) {
+        let output = output.map_err(|err| spawn_error(argv, err))?;
+        write_heading(ui, command, name, root)?;
+        ui.stdout().write_all(&output.stdout)?;
+        ui.stderr().write_all(&output.stderr)?;
+        statuses.push((name.as_ref(), output.status));
+    }
+    Ok(statuses)
+}
@@ -32,10 +32,12 @@ use itertools::Itertools as _;
 use jj_lib::backend::BackendResult;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::Signature;
 use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::conflicts;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopiesTreeDiffEntryPath;
 use jj_lib::copies::CopyRecords;
@@ -44,9 +46,11 @@ use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
+use jj_lib::gitattributes::TreeGitAttributes;
 use jj_lib::id_prefix::IdPrefixContext;
 use jj_lib::id_prefix::IdPrefixIndex;
 use jj_lib::index::IndexResult;
+use jj_lib::mailmap::Mailmap;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Diff;
 use jj_lib::merge::MergedTreeValue;
@@ -82,6 +86,7 @@ use once_cell::unsync::OnceCell;
 use pollster::FutureExt as _;
 use serde::Serialize as _;
 
+use crate::annotator::Annotators;
 use crate::diff_util;
 use crate::diff_util::DiffStats;
 use crate::formatter::Formatter;
@@ -137,6 +142,8 @@ pub struct CommitTemplateLanguage<'repo> {
     id_prefix_context: &'repo IdPrefixContext,
     immutable_expression: Arc<UserRevsetExpression>,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &'repo ConflictMarkerStyleOverrides,
+    annotators: Arc<Annotators>,
     build_fn_table: CommitTemplateBuildFnTable<'repo>,
     keyword_cache: CommitKeywordCache<'repo>,
     cache_extensions: ExtensionsMap,
@@ -154,6 +161,8 @@ impl<'repo> CommitTemplateLanguage<'repo> {
         id_prefix_context: &'repo IdPrefixContext,
         immutable_expression: Arc<UserRevsetExpression>,
         conflict_marker_style: ConflictMarkerStyle,
+        conflict_marker_style_overrides: &'repo ConflictMarkerStyleOverrides,
+        annotators: Arc<Annotators>,
         extensions: &[impl AsRef<dyn CommitTemplateLanguageExtension>],
     ) -> Self {
         let mut build_fn_table = CommitTemplateBuildFnTable::builtin();
@@ -174,6 +183,8 @@ impl<'repo> CommitTemplateLanguage<'repo> {
             id_prefix_context,
             immutable_expression,
             conflict_marker_style,
+            conflict_marker_style_overrides,
+            annotators,
             build_fn_table,
             keyword_cache: CommitKeywordCache::default(),
             cache_extensions,
@@ -601,7 +612,9 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::TreeDiffEntryList(_) => None,
             Self::TreeEntry(_) => None,
             Self::TreeEntryList(_) => None,
-            Self::DiffStats(_) => None,
+            Self::DiffStats(property) => {
+                Some(property.map(|formatted| formatted.stats).into_serialize())
+            }
             Self::CryptographicSignatureOpt(_) => None,
             Self::AnnotationLine(_) => None,
             Self::Trailer(_) => None,
@@ -883,8 +896,10 @@ impl CommitTemplateBuildFnTable<'_> {
 
     /// Creates new symbol table containing the builtin methods.
     fn builtin() -> Self {
+        let mut core = CoreTemplateBuildFnTable::builtin();
+        merge_fn_map(&mut core.signature_methods, builtin_signature_methods());
         Self {
-            core: CoreTemplateBuildFnTable::builtin(),
+            core,
             operation: OperationTemplateBuildFnTable::builtin(),
             commit_methods: builtin_commit_methods(),
             commit_list_methods: template_builder::builtin_unformattable_list_methods(),
@@ -917,6 +932,9 @@ pub struct CommitKeywordCache<'repo> {
     bookmarks_index: OnceCell<Rc<CommitRefsIndex>>,
     tags_index: OnceCell<Rc<CommitRefsIndex>>,
     git_refs_index: OnceCell<Rc<CommitRefsIndex>>,
+    #[cfg(feature = "git")]
+    git_notes: OnceCell<Rc<HashMap<CommitId, String>>>,
+    mailmap: OnceCell<Rc<Mailmap>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
 }
 
@@ -936,6 +954,50 @@ impl<'repo> CommitKeywordCache<'repo> {
             .get_or_init(|| Rc::new(build_commit_refs_index(repo.view().git_refs())))
     }
 
+    #[cfg(feature = "git")]
+    pub fn git_notes(
+        &self,
+        language: &CommitTemplateLanguage<'repo>,
+        span: pest::Span<'_>,
+    ) -> TemplateParseResult<&Rc<HashMap<CommitId, String>>> {
+        self.git_notes.get_or_try_init(|| {
+            let make_error = || TemplateParseError::expression("Failed to read Git notes", span);
+            let notes_ref = language
+                .settings()
+                .get_string("git.notes-ref")
+                .map_err(|err| make_error().with_source(err))?;
+            match jj_lib::git::read_notes(language.repo.store(), &notes_ref) {
+                Ok(notes) => Ok(Rc::new(notes)),
+                // Repos that aren't backed by Git have no notes
+                Err(jj_lib::git::GitNotesError::UnexpectedBackend(_)) => Ok(Rc::default()),
+                Err(err) => Err(make_error().with_source(err)),
+            }
+        })
+    }
+
+    /// Loads `.mailmap` from the working-copy commit of the current workspace.
+    pub fn mailmap(
+        &self,
+        language: &CommitTemplateLanguage<'repo>,
+        span: pest::Span<'_>,
+    ) -> TemplateParseResult<&Rc<Mailmap>> {
+        self.mailmap.get_or_try_init(|| {
+            let repo = language.repo;
+            let Some(wc_commit_id) = repo.view().get_wc_commit_id(&language.workspace_name) else {
+                return Ok(Rc::default());
+            };
+            let make_error = || TemplateParseError::expression("Failed to load mailmap", span);
+            let commit = repo
+                .store()
+                .get_commit(wc_commit_id)
+                .map_err(|err| make_error().with_source(err))?;
+            let mailmap = Mailmap::load_from_tree(&commit.tree())
+                .block_on()
+                .map_err(|err| make_error().with_source(err))?;
+            Ok(Rc::new(mailmap))
+        })
+    }
+
     pub fn is_immutable_fn(
         &self,
         language: &CommitTemplateLanguage<'repo>,
@@ -952,6 +1014,27 @@ impl<'repo> CommitKeywordCache<'repo> {
     }
 }
 
+/// Signature methods that depend on the repository.
+fn builtin_signature_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Signature> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<Signature>::new();
+    map.insert(
+        "mapped",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let mailmap = language
+                .keyword_cache
+                .mailmap(language, function.name_span)?
+                .clone();
+            let out_property =
+                self_property.map(move |signature| mailmap.map_signature(&signature));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
 fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Commit> {
     // Not using maplit::hashmap!{} or custom declarative macro here because
     // code completion inside macro is quite restricted.
@@ -1143,6 +1226,20 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    #[cfg(feature = "git")]
+    map.insert(
+        "git_note",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let notes = language
+                .keyword_cache
+                .git_notes(language, function.name_span)?
+                .clone();
+            let out_property = self_property
+                .map(move |commit| notes.get(commit.id()).cloned().unwrap_or_default());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "divergent",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1253,6 +1350,20 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "annotation",
+        |language, diagnostics, _build_ctx, self_property, function| {
+            let [name_node] = function.expect_exact_arguments()?;
+            let name =
+                template_parser::catch_aliases(diagnostics, name_node, |_diagnostics, node| {
+                    Ok(template_parser::expect_string_literal(node)?.to_owned())
+                })?;
+            let annotators = language.annotators.clone();
+            let out_property =
+                self_property.and_then(move |commit| Ok(annotators.annotate(&name, &commit)?));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "root",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -2164,7 +2275,12 @@ impl TreeDiff {
 
     fn into_formatted<F, E>(self, show: F) -> TreeDiffFormatted<F>
     where
-        F: Fn(&mut dyn Formatter, &Store, BoxStream<CopiesTreeDiffEntry>) -> Result<(), E>,
+        F: Fn(
+            &mut dyn Formatter,
+            &Store,
+            &TreeGitAttributes,
+            BoxStream<CopiesTreeDiffEntry>,
+        ) -> Result<(), E>,
         E: Into<TemplatePropertyError>,
     {
         TreeDiffFormatted { diff: self, show }
@@ -2179,14 +2295,21 @@ struct TreeDiffFormatted<F> {
 
 impl<F, E> Template for TreeDiffFormatted<F>
 where
-    F: Fn(&mut dyn Formatter, &Store, BoxStream<CopiesTreeDiffEntry>) -> Result<(), E>,
+    F: Fn(
+        &mut dyn Formatter,
+        &Store,
+        &TreeGitAttributes,
+        BoxStream<CopiesTreeDiffEntry>,
+    ) -> Result<(), E>,
     E: Into<TemplatePropertyError>,
 {
     fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
         let show = &self.show;
         let store = self.diff.from_tree.store();
+        let git_attributes = TreeGitAttributes::new(self.diff.to_tree.clone());
         let tree_diff = self.diff.diff_stream();
-        show(formatter.as_mut(), store, tree_diff).or_else(|err| formatter.handle_error(err.into()))
+        show(formatter.as_mut(), store, &git_attributes, tree_diff)
+            .or_else(|err| formatter.handle_error(err.into()))
     }
 }
 
@@ -2226,20 +2349,23 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
                     TemplateParseError::expression(message, function.name_span).with_source(err)
                 })?;
             let conflict_marker_style = language.conflict_marker_style;
+            let conflict_marker_style_overrides = language.conflict_marker_style_overrides;
             let template = (self_property, context_property)
                 .map(move |(diff, context)| {
                     let mut options = options.clone();
                     if let Some(context) = context {
                         options.context = context;
                     }
-                    diff.into_formatted(move |formatter, store, tree_diff| {
+                    diff.into_formatted(move |formatter, store, git_attributes, tree_diff| {
                         diff_util::show_color_words_diff(
                             formatter,
                             store,
+                            git_attributes,
                             tree_diff,
                             path_converter,
                             &options,
                             conflict_marker_style,
+                            conflict_marker_style_overrides,
                         )
                         .block_on()
                     })
@@ -2262,25 +2388,30 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
                     )
                 })
                 .transpose()?;
+            let path_converter = language.path_converter;
             let options = diff_util::UnifiedDiffOptions::from_settings(language.settings())
                 .map_err(|err| {
                     let message = "Failed to load diff settings";
                     TemplateParseError::expression(message, function.name_span).with_source(err)
                 })?;
             let conflict_marker_style = language.conflict_marker_style;
+            let conflict_marker_style_overrides = language.conflict_marker_style_overrides;
             let template = (self_property, context_property)
                 .map(move |(diff, context)| {
                     let mut options = options.clone();
                     if let Some(context) = context {
                         options.context = context;
                     }
-                    diff.into_formatted(move |formatter, store, tree_diff| {
+                    diff.into_formatted(move |formatter, store, git_attributes, tree_diff| {
                         diff_util::show_git_diff(
                             formatter,
                             store,
+                            git_attributes,
                             tree_diff,
+                            path_converter,
                             &options,
                             conflict_marker_style,
+                            conflict_marker_style_overrides,
                         )
                         .block_on()
                     })
@@ -2307,12 +2438,21 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
             // No user configuration exists for diff stat.
             let options = diff_util::DiffStatOptions::default();
             let conflict_marker_style = language.conflict_marker_style;
+            let conflict_marker_style_overrides = language.conflict_marker_style_overrides;
             // TODO: cache and reuse stats within the current evaluation?
             let out_property = (self_property, width_property).and_then(move |(diff, width)| {
                 let store = diff.from_tree.store();
+                let git_attributes = TreeGitAttributes::new(diff.to_tree.clone());
                 let tree_diff = diff.diff_stream();
-                let stats = DiffStats::calculate(store, tree_diff, &options, conflict_marker_style)
-                    .block_on()?;
+                let stats = DiffStats::calculate(
+                    store,
+                    &git_attributes,
+                    tree_diff,
+                    &options,
+                    conflict_marker_style,
+                    conflict_marker_style_overrides,
+                )
+                .block_on()?;
                 Ok(DiffStatsFormatted {
                     stats,
                     path_converter,
@@ -2330,7 +2470,7 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
             let path_converter = language.path_converter;
             let template = self_property
                 .map(move |diff| {
-                    diff.into_formatted(move |formatter, _store, tree_diff| {
+                    diff.into_formatted(move |formatter, _store, _git_attributes, tree_diff| {
                         diff_util::show_diff_summary(formatter, tree_diff, path_converter)
                             .block_on()
                     })
@@ -2525,6 +2665,27 @@ fn builtin_diff_stats_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "files_changed",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.and_then(|stats| Ok(i64::try_from(stats.entries().len())?));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "largest_file",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|stats| {
+                stats
+                    .largest_entry()
+                    .map(|entry| entry.path.target().to_owned())
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
@@ -2744,6 +2905,7 @@ mod tests {
         revset_aliases_map: RevsetAliasesMap,
         template_aliases_map: TemplateAliasesMap,
         immutable_expression: Arc<UserRevsetExpression>,
+        conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
         extra_functions: HashMap<&'static str, BuildFunctionFn>,
     }
 
@@ -2767,6 +2929,7 @@ mod tests {
                 revset_aliases_map: RevsetAliasesMap::new(),
                 template_aliases_map: TemplateAliasesMap::new(),
                 immutable_expression: RevsetExpression::none(),
+                conflict_marker_style_overrides: ConflictMarkerStyleOverrides::default(),
                 extra_functions: HashMap::new(),
             }
         }
@@ -2803,6 +2966,8 @@ mod tests {
                 &self.id_prefix_context,
                 self.immutable_expression.clone(),
                 ConflictMarkerStyle::Diff,
+                &self.conflict_marker_style_overrides,
+                Arc::default(),
                 &[] as &[Box<dyn CommitTemplateLanguageExtension>],
             );
             // Not using .extend() to infer lifetime of f
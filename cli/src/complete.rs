@@ -20,6 +20,7 @@ use clap::FromArgMatches as _;
 use clap::builder::StyledStr;
 use clap_complete::CompletionCandidate;
 use indoc::indoc;
+use indoc::writedoc;
 use itertools::Itertools as _;
 use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::file_util::normalize_path;
@@ -27,6 +28,7 @@ use jj_lib::file_util::slash_path;
 use jj_lib::settings::UserSettings;
 use jj_lib::workspace::DefaultWorkspaceLoaderFactory;
 use jj_lib::workspace::WorkspaceLoaderFactory as _;
+use serde::Serialize;
 
 use crate::cli_util::GlobalArgs;
 use crate::cli_util::expand_args;
@@ -96,6 +98,49 @@ pub fn local_bookmarks() -> Vec<CompletionCandidate> {
     })
 }
 
+pub fn archived_bookmarks() -> Vec<CompletionCandidate> {
+    with_jj(|jj, _| {
+        let output = jj
+            .build()
+            .arg("bookmark")
+            .arg("list")
+            .arg("--archived")
+            .arg("--config")
+            .arg(BOOKMARK_HELP_TEMPLATE)
+            .arg("--template")
+            .arg(r#"if(!remote, name ++ bookmark_help()) ++ "\n""#)
+            .output()
+            .map_err(user_error)?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(split_help_text)
+            .map(|(name, help)| CompletionCandidate::new(name).help(help))
+            .collect())
+    })
+}
+
+pub fn stashes() -> Vec<CompletionCandidate> {
+    with_jj(|jj, _| {
+        let output = jj
+            .build()
+            .arg("stash")
+            .arg("list")
+            .output()
+            .map_err(user_error)?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            // Skip targets of conflicted stashes
+            .filter(|line| !line.starts_with(' '))
+            .filter_map(|line| {
+                let (name, help) = line.split_once(": ")?;
+                Some(CompletionCandidate::new(name).help(Some(help.to_string().into())))
+            })
+            .collect())
+    })
+}
+
 pub fn tracked_bookmarks() -> Vec<CompletionCandidate> {
     with_jj(|jj, _| {
         let output = jj
@@ -1153,6 +1198,95 @@ impl JjBuilder {
     }
 }
 
+/// Nushell adapter for the dynamic completion engine
+///
+/// The registration script installs an external completer for `jj`, and
+/// forwards other commands to the external completer configured before.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Nushell;
+
+impl clap_complete::env::EnvCompleter for Nushell {
+    fn name(&self) -> &'static str {
+        "nushell"
+    }
+
+    fn is(&self, name: &str) -> bool {
+        name == "nushell" || name == "nu"
+    }
+
+    fn write_registration(
+        &self,
+        var: &str,
+        _name: &str,
+        bin: &str,
+        completer: &str,
+        buf: &mut dyn std::io::Write,
+    ) -> Result<(), std::io::Error> {
+        let bin = nu_raw_string(bin);
+        let completer = nu_raw_string(completer);
+        writedoc!(
+            buf,
+            r#"
+                do --env {{
+                    let jj_completer = {{|spans: list<string>|
+                        {var}=nushell run-external {completer} "--" ...$spans | from json
+                    }}
+                    let fallback_completer = $env.config.completions.external.completer?
+                    $env.config.completions.external.enable = true
+                    $env.config.completions.external.completer = {{|spans: list<string>|
+                        if ($spans | first) == {bin} {{
+                            do $jj_completer $spans
+                        }} else if $fallback_completer != null {{
+                            do $fallback_completer $spans
+                        }}
+                    }}
+                }}
+            "#
+        )
+    }
+
+    fn write_complete(
+        &self,
+        cmd: &mut clap::Command,
+        args: Vec<std::ffi::OsString>,
+        current_dir: Option<&Path>,
+        buf: &mut dyn std::io::Write,
+    ) -> Result<(), std::io::Error> {
+        // Nushell passes the command line up to the cursor, including the
+        // (possibly empty) word being completed.
+        let index = args.len() - 1;
+        let completions = clap_complete::engine::complete(cmd, args, index, current_dir)?;
+        let records = completions
+            .iter()
+            .map(|candidate| NushellCompletion {
+                value: candidate.get_value().to_string_lossy().into_owned(),
+                description: candidate.get_help().map(|help| {
+                    let help = help.to_string();
+                    help.lines().next().unwrap_or_default().to_owned()
+                }),
+            })
+            .collect_vec();
+        serde_json::to_writer(&mut *buf, &records)?;
+        writeln!(buf)
+    }
+}
+
+/// Completion record read by the Nushell external completer.
+#[derive(Serialize)]
+struct NushellCompletion {
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+fn nu_raw_string(s: &str) -> String {
+    let hashes = (1..)
+        .map(|n| "#".repeat(n))
+        .find(|hashes| !s.contains(&format!("'{hashes}")))
+        .unwrap();
+    format!("r{hashes}'{s}'{hashes}")
+}
+
 /// Functions for parsing revisions and revision ranges from the command line.
 /// Parsing is done on a best-effort basis and relies on the heuristic that
 /// most command line flags are consistent across different subcommands.
@@ -1197,8 +1331,15 @@ mod parse {
         })
     }
 
+    // `jj diff` accepts `--revisions` and may be given multiple revisions, in
+    // which case the union of them is completed.
     pub fn parse_revision_impl(args: impl Iterator<Item = String>) -> Option<String> {
-        parse_flag(&["-r", "--revision"], args).next()
+        let revisions: Vec<_> = parse_flag(&["-r", "--revision", "--revisions"], args).collect();
+        match revisions.as_slice() {
+            [] => None,
+            [revision] => Some(revision.clone()),
+            _ => Some(format!("({})", revisions.join(")|("))),
+        }
     }
 
     pub fn revision() -> Option<String> {
@@ -1228,7 +1369,8 @@ mod parse {
         T: Iterator<Item = String>,
     {
         let from = parse_flag(&["-f", "--from"], args()).next()?;
-        let to = parse_flag(&["-t", "--to"], args())
+        // `jj restore` calls the destination `--into`.
+        let to = parse_flag(&["-t", "--to", "--into"], args())
             .next()
             .unwrap_or_else(|| "@".into());
 
@@ -1353,12 +1495,14 @@ mod tests {
             &["-r'foo'"],
             &["-r\"foo\""],
             &["--revision", "foo"],
+            &["--revisions", "foo"],
             &["-r=foo"],
             &["-r='foo'"],
             &["-r=\"foo\""],
             &["--revision=foo"],
             &["--revision='foo'"],
             &["--revision=\"foo\""],
+            &["--revisions=foo"],
             &["preceding_arg", "-r", "foo"],
             &["-r", "foo", "following_arg"],
         ];
@@ -1375,6 +1519,11 @@ mod tests {
             let args = case.iter().map(|s| s.to_string());
             assert_eq!(parse::parse_revision_impl(args), None, "case: {case:?}");
         }
+        let args = ["-r", "foo", "--revisions=bar"].map(|s| s.to_string());
+        assert_eq!(
+            parse::parse_revision_impl(args.into_iter()),
+            Some("(foo)|(bar)".into()),
+        );
     }
 
     #[test]
@@ -1420,6 +1569,7 @@ mod tests {
         let to_cases: &[&[&str]] = &[
             &["-f", "foo", "-t", "bar"],
             &["-f", "foo", "--to", "bar"],
+            &["-f", "foo", "--into", "bar"],
             &["-f=foo", "-t=bar"],
             &["-t=bar", "-f=foo"],
         ];
@@ -327,6 +327,7 @@ pub struct ConfigEnv {
     workspace_config_path: Option<ConfigPath>,
     command: Option<String>,
     hostname: Option<String>,
+    remote_urls: Vec<String>,
 }
 
 impl ConfigEnv {
@@ -356,6 +357,7 @@ impl ConfigEnv {
             workspace_config_path: None,
             command: None,
             hostname: whoami::fallible::hostname().ok(),
+            remote_urls: vec![],
         }
     }
 
@@ -423,9 +425,13 @@ impl ConfigEnv {
 
     /// Sets the directory where repo-specific config file is stored. The path
     /// is usually `.jj/repo`.
+    ///
+    /// The remote URLs of the backing Git repository, if any, are also loaded
+    /// so they can be matched by conditional scopes.
     pub fn reset_repo_path(&mut self, path: &Path) {
         self.repo_path = Some(path.to_owned());
         self.repo_config_path = Some(ConfigPath::new(path.join("config.toml")));
+        self.remote_urls = git_remote_urls(path);
     }
 
     /// Returns a path to the repo-specific config file.
@@ -536,11 +542,50 @@ impl ConfigEnv {
             workspace_path: self.workspace_path.as_deref(),
             command: self.command.as_deref(),
             hostname: self.hostname.as_deref().unwrap_or(""),
+            remote_urls: &self.remote_urls,
         };
         jj_lib::config::resolve(config.as_ref(), &context)
     }
 }
 
+/// Returns the fetch URLs of the remotes configured in the Git repository
+/// backing the repo at `repo_path`. Returns an empty list if the repo isn't
+/// backed by Git or the Git repository can't be opened.
+#[cfg(feature = "git")]
+fn git_remote_urls(repo_path: &Path) -> Vec<String> {
+    let store_path = repo_path.join("store");
+    let Ok(git_target) = std::fs::read(store_path.join("git_target")) else {
+        return vec![];
+    };
+    let Ok(git_repo_path) = jj_lib::file_util::path_from_bytes(&git_target) else {
+        return vec![];
+    };
+    let git_repo = match gix::open_opts(
+        store_path.join(git_repo_path),
+        gix::open::Options::isolated(),
+    ) {
+        Ok(git_repo) => git_repo,
+        Err(err) => {
+            tracing::warn!(?err, "failed to open Git repository to read remote URLs");
+            return vec![];
+        }
+    };
+    git_repo
+        .remote_names()
+        .iter()
+        .filter_map(|name| git_repo.try_find_remote(&**name)?.ok())
+        .filter_map(|remote| {
+            let url = remote.url(gix::remote::Direction::Fetch)?;
+            Some(url.to_bstring().to_string())
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "git"))]
+fn git_remote_urls(_repo_path: &Path) -> Vec<String> {
+    vec![]
+}
+
 fn config_files_for(
     config: &RawConfig,
     source: ConfigSource,
@@ -1769,6 +1814,7 @@ mod tests {
             workspace_config_path: None,
             command: None,
             hostname: None,
+            remote_urls: vec![],
         }
     }
 }
@@ -36,6 +36,7 @@ use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::conflicts::ConflictMaterializeOptions;
 use jj_lib::conflicts::MaterializedTreeDiffEntry;
 use jj_lib::conflicts::MaterializedTreeValue;
@@ -63,12 +64,14 @@ use jj_lib::files::ConflictDiffHunk;
 use jj_lib::files::DiffLineHunkSide;
 use jj_lib::files::DiffLineIterator;
 use jj_lib::files::DiffLineNumber;
+use jj_lib::gitattributes::TreeGitAttributes;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Diff;
 use jj_lib::merge::Merge;
 use jj_lib::merge::MergeBuilder;
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::merged_tree::MergedTree;
+use jj_lib::patch::PatchRejectionReason;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::InvalidRepoPathError;
 use jj_lib::repo_path::RepoPath;
@@ -81,6 +84,7 @@ use thiserror::Error;
 use tracing::instrument;
 use unicode_width::UnicodeWidthStr as _;
 
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::commit_templater;
@@ -94,6 +98,7 @@ use crate::merge_tools::ExternalMergeTool;
 use crate::merge_tools::generate_diff;
 use crate::merge_tools::invoke_external_diff;
 use crate::merge_tools::new_utf8_temp_dir;
+use crate::submodule_util;
 use crate::templater::TemplateRenderer;
 use crate::text_util;
 use crate::ui::Ui;
@@ -142,6 +147,12 @@ pub struct DiffFormatArgs {
     /// Number of lines of context to show
     #[arg(long)]
     context: Option<usize>,
+    /// How to show changes to Git submodules
+    ///
+    /// With `log`, the commits added and removed in the submodule are listed.
+    /// This requires the submodule to be checked out in the workspace.
+    #[arg(long, value_name = "FORMAT")]
+    submodule: Option<SubmoduleDiffFormat>,
 
     // Short flags are set by command to avoid future conflicts.
     /// Ignore whitespace when comparing lines.
@@ -420,6 +431,7 @@ pub struct DiffRenderer<'a> {
     repo: &'a dyn Repo,
     path_converter: &'a RepoPathUiConverter,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &'a ConflictMarkerStyleOverrides,
     formats: Vec<DiffFormat>,
 }
 
@@ -428,12 +440,14 @@ impl<'a> DiffRenderer<'a> {
         repo: &'a dyn Repo,
         path_converter: &'a RepoPathUiConverter,
         conflict_marker_style: ConflictMarkerStyle,
+        conflict_marker_style_overrides: &'a ConflictMarkerStyleOverrides,
         formats: Vec<DiffFormat>,
     ) -> Self {
         Self {
             repo,
             path_converter,
             conflict_marker_style,
+            conflict_marker_style_overrides,
             formats,
         }
     }
@@ -470,6 +484,7 @@ impl<'a> DiffRenderer<'a> {
 
         let store = self.repo.store();
         let path_converter = self.path_converter;
+        let git_attributes = TreeGitAttributes::new(trees.after.clone());
         for format in &self.formats {
             match format {
                 DiffFormat::Summary => {
@@ -478,9 +493,15 @@ impl<'a> DiffRenderer<'a> {
                 }
                 DiffFormat::Stat(options) => {
                     let tree_diff = diff_stream();
-                    let stats =
-                        DiffStats::calculate(store, tree_diff, options, self.conflict_marker_style)
-                            .block_on()?;
+                    let stats = DiffStats::calculate(
+                        store,
+                        &git_attributes,
+                        tree_diff,
+                        options,
+                        self.conflict_marker_style,
+                        self.conflict_marker_style_overrides,
+                    )
+                    .block_on()?;
                     show_diff_stats(formatter, &stats, path_converter, width)?;
                 }
                 DiffFormat::Types => {
@@ -496,9 +517,12 @@ impl<'a> DiffRenderer<'a> {
                     show_git_diff(
                         formatter,
                         store,
+                        &git_attributes,
                         tree_diff,
+                        path_converter,
                         options,
                         self.conflict_marker_style,
+                        self.conflict_marker_style_overrides,
                     )
                     .await?;
                 }
@@ -507,10 +531,12 @@ impl<'a> DiffRenderer<'a> {
                     show_color_words_diff(
                         formatter,
                         store,
+                        &git_attributes,
                         tree_diff,
                         path_converter,
                         options,
                         self.conflict_marker_style,
+                        self.conflict_marker_style_overrides,
                     )
                     .await?;
                 }
@@ -526,6 +552,7 @@ impl<'a> DiffRenderer<'a> {
                                 path_converter,
                                 tool,
                                 self.conflict_marker_style,
+                                self.conflict_marker_style_overrides,
                                 width,
                             )
                             .await
@@ -539,6 +566,7 @@ impl<'a> DiffRenderer<'a> {
                                 matcher,
                                 tool,
                                 self.conflict_marker_style,
+                                self.conflict_marker_style_overrides,
                                 width,
                             )
                             .map_err(DiffRenderError::DiffGenerate)
@@ -561,6 +589,7 @@ impl<'a> DiffRenderer<'a> {
         const DUMMY_PATH: &str = "JJ-COMMIT-DESCRIPTION";
         let materialize_options = ConflictMaterializeOptions {
             marker_style: self.conflict_marker_style,
+            marker_style_overrides: Default::default(),
             marker_len: None,
             merge: self.repo.store().merge_options().clone(),
         };
@@ -712,6 +741,39 @@ impl LineDiffOptions {
     }
 }
 
+/// How changes to Git submodules are shown in diffs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubmoduleDiffFormat {
+    /// Show the commit ids only
+    Short,
+    /// List the commits added and removed
+    Log,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubmoduleDiffOptions {
+    /// How changes to submodules are shown.
+    pub format: SubmoduleDiffFormat,
+    /// The `git` executable used to list submodule commits.
+    pub git_executable_path: PathBuf,
+}
+
+impl SubmoduleDiffOptions {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        Ok(Self {
+            format: settings.get("diff.submodule")?,
+            git_executable_path: settings.get("git.executable-path")?,
+        })
+    }
+
+    fn merge_args(&mut self, args: &DiffFormatArgs) {
+        if let Some(format) = args.submodule {
+            self.format = format;
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ColorWordsDiffOptions {
     /// How conflicts are processed and rendered.
@@ -722,6 +784,8 @@ pub struct ColorWordsDiffOptions {
     pub line_diff: LineDiffOptions,
     /// Maximum number of removed/added word alternation to inline.
     pub max_inline_alternation: Option<usize>,
+    /// How changes to Git submodules are shown.
+    pub submodule: SubmoduleDiffOptions,
 }
 
 impl ColorWordsDiffOptions {
@@ -742,6 +806,7 @@ impl ColorWordsDiffOptions {
             context: settings.get("diff.color-words.context")?,
             line_diff: LineDiffOptions::default(),
             max_inline_alternation,
+            submodule: SubmoduleDiffOptions::from_settings(settings)?,
         })
     }
 
@@ -750,6 +815,7 @@ impl ColorWordsDiffOptions {
             self.context = context;
         }
         self.line_diff.merge_args(args);
+        self.submodule.merge_args(args);
     }
 }
 
@@ -1240,7 +1306,9 @@ fn diff_content(
         path,
         value,
         |content| content,
-        |contents| materialize_merge_result_to_bytes(&contents, materialize_options),
+        |contents| {
+            materialize_merge_result_to_bytes(&contents, &materialize_options.for_path(path))
+        },
     )
 }
 
@@ -1293,6 +1361,16 @@ fn diff_content_with<T>(
     }
 }
 
+/// Returns true if the `diff` attribute is unset for the path, in which case
+/// the contents should be presented as binary.
+async fn is_diff_disabled(
+    git_attributes: &TreeGitAttributes,
+    path: &RepoPath,
+) -> BackendResult<bool> {
+    let attributes = git_attributes.content_attributes(path).await?;
+    Ok(attributes.is_diff_disabled())
+}
+
 fn basic_diff_file_type(value: &MaterializedTreeValue) -> &'static str {
     match value {
         MaterializedTreeValue::Absent => {
@@ -1315,16 +1393,20 @@ fn basic_diff_file_type(value: &MaterializedTreeValue) -> &'static str {
     }
 }
 
+#[expect(clippy::too_many_arguments)]
 pub async fn show_color_words_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
+    git_attributes: &TreeGitAttributes,
     tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
     path_converter: &RepoPathUiConverter,
     options: &ColorWordsDiffOptions,
     marker_style: ConflictMarkerStyle,
+    marker_style_overrides: &ConflictMarkerStyleOverrides,
 ) -> Result<(), DiffRenderError> {
     let materialize_options = ConflictMaterializeOptions {
         marker_style,
+        marker_style_overrides: marker_style_overrides.clone(),
         marker_len: None,
         merge: store.merge_options().clone(),
     };
@@ -1336,6 +1418,20 @@ pub async fn show_color_words_diff(
         let left_ui_path = path_converter.format_file_path(left_path);
         let right_ui_path = path_converter.format_file_path(right_path);
         let (left_value, right_value) = values?;
+        let diff_disabled = is_diff_disabled(git_attributes, right_path).await?;
+        let file_materialize_options = materialize_options.for_path(right_path);
+        if options.submodule.format == SubmoduleDiffFormat::Log
+            && let Some(ids) = submodule_diff_ids(&left_value, &right_value)
+        {
+            show_submodule_log(
+                formatter,
+                path_converter,
+                &options.submodule.git_executable_path,
+                right_path,
+                ids,
+            )?;
+            continue;
+        }
 
         match (&left_value, &right_value) {
             (MaterializedTreeValue::AccessDenied(source), _) => {
@@ -1365,14 +1461,14 @@ pub async fn show_color_words_diff(
             let right_content = diff_content_as_merge(right_path, right_value)?;
             if right_content.is_empty() {
                 writeln!(formatter.labeled("empty"), "    (empty)")?;
-            } else if right_content.is_binary {
+            } else if right_content.is_binary || diff_disabled {
                 writeln!(formatter.labeled("binary"), "    (binary)")?;
             } else {
                 show_color_words_diff_hunks(
                     formatter,
                     Diff::new(&empty_content(), &right_content.contents),
                     options,
-                    &materialize_options,
+                    &file_materialize_options,
                 )?;
             }
         } else if right_value.is_present() {
@@ -1432,14 +1528,14 @@ pub async fn show_color_words_diff(
                     "{description} {right_ui_path} ({left_ui_path} => {right_ui_path}):"
                 )?;
             }
-            if left_content.is_binary || right_content.is_binary {
+            if left_content.is_binary || right_content.is_binary || diff_disabled {
                 writeln!(formatter.labeled("binary"), "    (binary)")?;
             } else if left_content.contents != right_content.contents {
                 show_color_words_diff_hunks(
                     formatter,
                     Diff::new(&left_content.contents, &right_content.contents),
                     options,
-                    &materialize_options,
+                    &file_materialize_options,
                 )?;
             }
         } else {
@@ -1451,14 +1547,14 @@ pub async fn show_color_words_diff(
             let left_content = diff_content_as_merge(left_path, left_value)?;
             if left_content.is_empty() {
                 writeln!(formatter.labeled("empty"), "    (empty)")?;
-            } else if left_content.is_binary {
+            } else if left_content.is_binary || diff_disabled {
                 writeln!(formatter.labeled("binary"), "    (binary)")?;
             } else {
                 show_color_words_diff_hunks(
                     formatter,
                     Diff::new(&left_content.contents, &empty_content()),
                     options,
-                    &materialize_options,
+                    &file_materialize_options,
                 )?;
             }
         }
@@ -1475,10 +1571,12 @@ pub async fn show_file_by_file_diff(
     path_converter: &RepoPathUiConverter,
     tool: &ExternalMergeTool,
     marker_style: ConflictMarkerStyle,
+    marker_style_overrides: &ConflictMarkerStyleOverrides,
     width: usize,
 ) -> Result<(), DiffRenderError> {
     let materialize_options = ConflictMaterializeOptions {
         marker_style,
+        marker_style_overrides: marker_style_overrides.clone(),
         marker_len: None,
         merge: store.merge_options().clone(),
     };
@@ -1554,6 +1652,8 @@ pub struct UnifiedDiffOptions {
     pub context: usize,
     /// How lines are tokenized and compared.
     pub line_diff: LineDiffOptions,
+    /// How changes to Git submodules are shown.
+    pub submodule: SubmoduleDiffOptions,
 }
 
 impl UnifiedDiffOptions {
@@ -1561,6 +1661,7 @@ impl UnifiedDiffOptions {
         Ok(Self {
             context: settings.get("diff.git.context")?,
             line_diff: LineDiffOptions::default(),
+            submodule: SubmoduleDiffOptions::from_settings(settings)?,
         })
     }
 
@@ -1569,6 +1670,7 @@ impl UnifiedDiffOptions {
             self.context = context;
         }
         self.line_diff.merge_args(args);
+        self.submodule.merge_args(args);
     }
 }
 
@@ -1631,15 +1733,20 @@ fn show_diff_line_tokens(
     Ok(())
 }
 
+#[expect(clippy::too_many_arguments)]
 pub async fn show_git_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
+    git_attributes: &TreeGitAttributes,
     tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    path_converter: &RepoPathUiConverter,
     options: &UnifiedDiffOptions,
     marker_style: ConflictMarkerStyle,
+    marker_style_overrides: &ConflictMarkerStyleOverrides,
 ) -> Result<(), DiffRenderError> {
     let materialize_options = ConflictMaterializeOptions {
         marker_style,
+        marker_style_overrides: marker_style_overrides.clone(),
         marker_len: None,
         merge: store.merge_options().clone(),
     };
@@ -1650,9 +1757,22 @@ pub async fn show_git_diff(
         let left_path_string = left_path.as_internal_file_string();
         let right_path_string = right_path.as_internal_file_string();
         let (left_value, right_value) = values?;
+        if options.submodule.format == SubmoduleDiffFormat::Log
+            && let Some(ids) = submodule_diff_ids(&left_value, &right_value)
+        {
+            show_submodule_log(
+                formatter,
+                path_converter,
+                &options.submodule.git_executable_path,
+                right_path,
+                ids,
+            )?;
+            continue;
+        }
 
         let left_part = git_diff_part(left_path, left_value, &materialize_options)?;
         let right_part = git_diff_part(right_path, right_value, &materialize_options)?;
+        let diff_disabled = is_diff_disabled(git_attributes, right_path).await?;
 
         {
             let mut formatter = formatter.labeled("file_header");
@@ -1707,7 +1827,7 @@ pub async fn show_git_diff(
             Some(_) => format!("b/{right_path_string}"),
             None => "/dev/null".to_owned(),
         };
-        if left_part.content.is_binary || right_part.content.is_binary {
+        if left_part.content.is_binary || right_part.content.is_binary || diff_disabled {
             // TODO: add option to emit Git binary diff
             writeln!(
                 formatter,
@@ -1726,6 +1846,70 @@ pub async fn show_git_diff(
     Ok(())
 }
 
+/// Returns the submodule commits before and after if the diff is a change
+/// to a Git submodule.
+fn submodule_diff_ids(
+    left_value: &MaterializedTreeValue,
+    right_value: &MaterializedTreeValue,
+) -> Option<Diff<Option<CommitId>>> {
+    let to_id = |value: &MaterializedTreeValue| match value {
+        MaterializedTreeValue::GitSubmodule(id) => Some(Some(id.clone())),
+        MaterializedTreeValue::Absent => Some(None),
+        _ => None,
+    };
+    let ids = Diff::new(to_id(left_value)?, to_id(right_value)?);
+    (ids.before.is_some() || ids.after.is_some()).then_some(ids)
+}
+
+/// Shows the commits between the submodule revisions similar to
+/// `git diff --submodule=log`.
+fn show_submodule_log(
+    formatter: &mut dyn Formatter,
+    path_converter: &RepoPathUiConverter,
+    git_executable_path: &Path,
+    path: &RepoPath,
+    ids: Diff<Option<CommitId>>,
+) -> io::Result<()> {
+    let ui_path = path_converter.format_file_path(path);
+    let mut formatter = formatter.labeled("submodule");
+    match (&ids.before, &ids.after) {
+        (None, Some(after)) => {
+            let after = short_commit_hash(after);
+            writeln!(formatter, "Submodule {ui_path} {after} (new submodule)")
+        }
+        (Some(before), None) => {
+            let before = short_commit_hash(before);
+            writeln!(
+                formatter,
+                "Submodule {ui_path} {before} (submodule deleted)"
+            )
+        }
+        (Some(before), Some(after)) => {
+            let RepoPathUiConverter::Fs { base, .. } = path_converter;
+            let dir = path.to_fs_path_unchecked(base);
+            let range = format!(
+                "{}..{}",
+                short_commit_hash(before),
+                short_commit_hash(after)
+            );
+            match submodule_util::submodule_log(git_executable_path, &dir, before, after) {
+                Some(lines) => {
+                    writeln!(formatter, "Submodule {ui_path} {range}:")?;
+                    for line in lines {
+                        writeln!(formatter, "  {line}")?;
+                    }
+                    Ok(())
+                }
+                None => writeln!(
+                    formatter,
+                    "Submodule {ui_path} {range} (commits not present)"
+                ),
+            }
+        }
+        (None, None) => Ok(()),
+    }
+}
+
 /// Generates diff of non-binary contents in Git format.
 fn show_git_diff_texts<T: AsRef<[u8]>>(
     formatter: &mut dyn Formatter,
@@ -1813,20 +1997,27 @@ impl DiffStats {
     /// Calculates stats of changed lines per file.
     pub async fn calculate(
         store: &Store,
+        git_attributes: &TreeGitAttributes,
         tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
         options: &DiffStatOptions,
         marker_style: ConflictMarkerStyle,
+        marker_style_overrides: &ConflictMarkerStyleOverrides,
     ) -> BackendResult<Self> {
         let materialize_options = ConflictMaterializeOptions {
             marker_style,
+            marker_style_overrides: marker_style_overrides.clone(),
             marker_len: None,
             merge: store.merge_options().clone(),
         };
         let entries = materialized_diff_stream(store, tree_diff)
-            .map(|MaterializedTreeDiffEntry { path, values }| {
+            .then(async |MaterializedTreeDiffEntry { path, values }| {
                 let (left, right) = values?;
-                let left_content = diff_content(path.source(), left, &materialize_options)?;
-                let right_content = diff_content(path.target(), right, &materialize_options)?;
+                let mut left_content = diff_content(path.source(), left, &materialize_options)?;
+                let mut right_content = diff_content(path.target(), right, &materialize_options)?;
+                if is_diff_disabled(git_attributes, path.target()).await? {
+                    left_content.is_binary = true;
+                    right_content.is_binary = true;
+                }
                 let stat =
                     get_diff_stat_entry(path, Diff::new(&left_content, &right_content), options);
                 BackendResult::Ok(stat)
@@ -1856,6 +2047,26 @@ impl DiffStats {
             .filter_map(|stat| stat.added_removed.map(|(_, removed)| removed))
             .sum()
     }
+
+    /// Entry with the most inserted and deleted lines. Binary files count as
+    /// unchanged lines. The first entry wins if there are ties.
+    pub fn largest_entry(&self) -> Option<&DiffStatEntry> {
+        self.entries.iter().rev().max_by_key(|stat| {
+            stat.added_removed
+                .map_or(0, |(added, removed)| added + removed)
+        })
+    }
+}
+
+impl serde::Serialize for DiffStats {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+        let mut state = serializer.serialize_struct("DiffStats", 3)?;
+        state.serialize_field("total_added", &self.count_total_added())?;
+        state.serialize_field("total_removed", &self.count_total_removed())?;
+        state.serialize_field("files", &self.entries)?;
+        state.end()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1867,6 +2078,20 @@ pub struct DiffStatEntry {
     pub bytes_delta: isize,
 }
 
+impl serde::Serialize for DiffStatEntry {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+        let (added, removed) = self.added_removed.unzip();
+        let mut state = serializer.serialize_struct("DiffStatEntry", 4)?;
+        state.serialize_field("path", self.path.target())?;
+        // null for binary files
+        state.serialize_field("added", &added)?;
+        state.serialize_field("removed", &removed)?;
+        state.serialize_field("bytes_delta", &self.bytes_delta)?;
+        state.end()
+    }
+}
+
 fn get_diff_stat_entry(
     path: CopiesTreeDiffEntryPath,
     contents: Diff<&FileContent<BString>>,
@@ -2101,3 +2326,20 @@ pub async fn show_templated(
     }
     Ok(())
 }
+
+/// Describes why a patch couldn't be applied to a file.
+pub fn describe_patch_rejection(reason: &PatchRejectionReason) -> String {
+    match reason {
+        PatchRejectionReason::Missing => "No such file".to_owned(),
+        PatchRejectionReason::AlreadyExists => "File already exists".to_owned(),
+        PatchRejectionReason::NotAFile => "Not a regular file".to_owned(),
+        PatchRejectionReason::Binary => "Binary patches are not supported".to_owned(),
+        PatchRejectionReason::Hunks(hunks) => {
+            let numbers = hunks
+                .iter()
+                .map(|index| format!("#{}", index + 1))
+                .join(", ");
+            format!("Hunk {numbers} doesn't match")
+        }
+    }
+}
@@ -30,17 +30,25 @@ use crossterm::terminal::Clear;
 use crossterm::terminal::ClearType;
 use indoc::writedoc;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
 use jj_lib::fmt_util::binary_prefix;
 use jj_lib::git;
 use jj_lib::git::FailedRefExportReason;
 use jj_lib::git::GitExportStats;
 use jj_lib::git::GitImportStats;
 use jj_lib::git::GitRefKind;
+use jj_lib::git_lfs::GitLfsSettings;
+use jj_lib::git_lfs::LfsError;
+use jj_lib::git_lfs::LfsObjectStore;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
+use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
+use jj_lib::settings::UserSettings;
+use jj_lib::store::Store;
 use jj_lib::workspace::Workspace;
 use unicode_width::UnicodeWidthStr as _;
 
@@ -48,6 +56,7 @@ use crate::cleanup_guard::CleanupGuard;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::ui::ProgressOutput;
@@ -186,14 +195,11 @@ impl GitSidebandProgressMessageWriter {
     pub fn write(&mut self, ui: &Ui, progress_message: &[u8]) -> std::io::Result<()> {
         let mut index = 0;
         // Append a suffix to each nonempty line to clear the end of the screen line.
-        loop {
-            let Some(i) = progress_message[index..]
-                .iter()
-                .position(|&c| c == b'\r' || c == b'\n')
-                .map(|i| index + i)
-            else {
-                break;
-            };
+        while let Some(i) = progress_message[index..]
+            .iter()
+            .position(|&c| c == b'\r' || c == b'\n')
+            .map(|i| index + i)
+        {
             let line_length = i - index;
 
             // For messages sent across the packet boundary, there would be a nonempty
@@ -280,6 +286,25 @@ pub fn with_remote_git_callbacks<T>(ui: &Ui, f: impl FnOnce(git::RemoteCallbacks
     result
 }
 
+/// Updates the Git commit-graph if `git.write-commit-graph` is enabled. A
+/// failure is reported as a warning since the graph is only a cache.
+pub fn maybe_write_commit_graph(
+    ui: &Ui,
+    store: &Store,
+    git_settings: &git::GitSettings,
+) -> Result<(), CommandError> {
+    if !git_settings.write_commit_graph {
+        return Ok(());
+    }
+    if let Err(err) = git::write_commit_graph(store, git_settings) {
+        writeln!(
+            ui.warning_default(),
+            "Failed to write Git commit-graph: {err}"
+        )?;
+    }
+    Ok(())
+}
+
 pub fn print_git_import_stats(
     ui: &Ui,
     repo: &dyn Repo,
@@ -572,6 +597,45 @@ enum ImportStatus {
     Updated,
 }
 
+/// Runs `git lfs <subcommand> <remote> <commits>` to transfer the Git LFS
+/// objects referenced by `commits`. Does nothing if Git LFS support is
+/// disabled.
+pub fn transfer_git_lfs_objects(
+    store: &Store,
+    settings: &UserSettings,
+    subcommand: &str,
+    remote: &RemoteName,
+    commits: impl IntoIterator<Item = CommitId>,
+) -> Result<(), CommandError> {
+    let Some(lfs_settings) = GitLfsSettings::from_settings(settings)? else {
+        return Ok(());
+    };
+    let commits = commits.into_iter().map(|id| id.hex()).collect_vec();
+    if commits.is_empty() {
+        return Ok(());
+    }
+    let git_backend = git::get_git_backend(store)?;
+    let lfs_store = LfsObjectStore::new(
+        git_backend.git_repo_path().to_owned(),
+        lfs_settings.executable_path,
+    );
+    let output = lfs_store
+        .git_command()
+        .args(["lfs", subcommand, remote.as_str()])
+        .args(&commits)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| user_error_with_message("Failed to run git lfs", err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(user_error_with_message(
+            format!("Failed to {subcommand} Git LFS objects"),
+            LfsError::Command(stderr.trim_end().to_owned()),
+        ));
+    }
+    Ok(())
+}
+
 pub fn print_git_export_stats(ui: &Ui, stats: &GitExportStats) -> Result<(), std::io::Error> {
     if !stats.failed_bookmarks.is_empty() {
         writeln!(ui.warning_default(), "Failed to export some bookmarks:")?;
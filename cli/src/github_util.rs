@@ -0,0 +1,443 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client for the GitHub REST API.
+//!
+//! Requests are sent by running `curl` (or the `github.curl-path` executable),
+//! so proxies and certificates are picked up the same way as for other
+//! command-line tools. The request, including the
+//! token, is passed to `curl` through stdin so it doesn't show up in the
+//! process list.
+
+use std::io;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::settings::UserSettings;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+/// Error while talking to the GitHub API.
+#[derive(Debug, Error)]
+pub enum GitHubError {
+    #[error("No GitHub token is configured")]
+    MissingToken,
+    #[error("Could not find curl at `{}`", .0.display())]
+    CurlNotFound(PathBuf),
+    #[error("Failed to run curl")]
+    Spawn(#[source] io::Error),
+    #[error("curl failed: {0}")]
+    Curl(String),
+    #[error("GitHub API request failed with status {status}: {message}")]
+    Api { status: u32, message: String },
+    #[error("Unexpected response from the GitHub API")]
+    Response(#[source] serde_json::Error),
+    #[error(transparent)]
+    Config(#[from] ConfigGetError),
+}
+
+/// Repository on GitHub, identified by its owner and name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitHubRepo {
+    pub owner: String,
+    pub name: String,
+}
+
+/// Pull request as returned by the GitHub API.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    pub merged_at: Option<String>,
+    pub head: PullRequestRef,
+    pub base: PullRequestRef,
+}
+
+impl PullRequest {
+    /// Returns "open", "draft", "merged", or "closed".
+    pub fn status(&self) -> &str {
+        if self.merged_at.is_some() {
+            "merged"
+        } else if self.state == "open" && self.draft {
+            "draft"
+        } else {
+            &self.state
+        }
+    }
+}
+
+/// Branch a pull request is opened from or against.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PullRequestRef {
+    #[serde(rename = "ref")]
+    pub name: String,
+}
+
+/// Parameters of a pull request to create.
+#[derive(Clone, Debug, Serialize)]
+pub struct NewPullRequest<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub head: &'a str,
+    pub base: &'a str,
+    pub draft: bool,
+}
+
+/// Fields of a pull request to update.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PullRequestUpdate<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    default_branch: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+/// Authenticated connection to the GitHub API.
+#[derive(Clone, Debug)]
+pub struct GitHubClient {
+    api_url: String,
+    token: String,
+    curl_path: PathBuf,
+}
+
+impl GitHubClient {
+    /// Creates a client for the API configured in `github.api-url`.
+    ///
+    /// The token is read from the `GH_TOKEN` or `GITHUB_TOKEN` environment
+    /// variable, or from the `github.token` config. Requests are sent by the
+    /// `github.curl-path` executable.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, GitHubError> {
+        let api_url = settings.get_string("github.api-url")?;
+        let curl_path = PathBuf::from(settings.get_string("github.curl-path")?);
+        let token = ["GH_TOKEN", "GITHUB_TOKEN"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|token| !token.is_empty()));
+        let token = match token {
+            Some(token) => token,
+            None => settings
+                .get_string("github.token")
+                .optional()?
+                .ok_or(GitHubError::MissingToken)?,
+        };
+        Ok(Self {
+            api_url: api_url.trim_end_matches('/').to_owned(),
+            token,
+            curl_path,
+        })
+    }
+
+    /// Returns the name of the default branch of the repository.
+    pub fn default_branch(&self, repo: &GitHubRepo) -> Result<String, GitHubError> {
+        let path = format!("/repos/{}/{}", repo.owner, repo.name);
+        let repository: Repository = self.request("GET", &path, None)?;
+        Ok(repository.default_branch)
+    }
+
+    /// Lists the pull requests in the given `state` ("open", "closed", or
+    /// "all"), most recently created first. Only the first 100 pull requests
+    /// are returned.
+    ///
+    /// If `head` is specified, only pull requests opened from that branch of
+    /// the repository are listed.
+    pub fn list_pull_requests(
+        &self,
+        repo: &GitHubRepo,
+        head: Option<&str>,
+        state: &str,
+    ) -> Result<Vec<PullRequest>, GitHubError> {
+        let mut path = format!(
+            "/repos/{}/{}/pulls?state={state}&per_page=100",
+            repo.owner, repo.name
+        );
+        if let Some(head) = head {
+            path.push_str(&format!("&head={}:{}", repo.owner, percent_encode(head)));
+        }
+        let pull_requests: Vec<PullRequest> = self.request("GET", &path, None)?;
+        // The head filter is applied again in case it was ignored by the server.
+        Ok(pull_requests
+            .into_iter()
+            .filter(|pr| head.is_none_or(|head| pr.head.name == head))
+            .collect())
+    }
+
+    /// Opens a pull request.
+    pub fn create_pull_request(
+        &self,
+        repo: &GitHubRepo,
+        pull_request: &NewPullRequest,
+    ) -> Result<PullRequest, GitHubError> {
+        let path = format!("/repos/{}/{}/pulls", repo.owner, repo.name);
+        let body = serde_json::to_string(pull_request).unwrap();
+        self.request("POST", &path, Some(&body))
+    }
+
+    /// Updates the body or base branch of a pull request.
+    pub fn update_pull_request(
+        &self,
+        repo: &GitHubRepo,
+        number: u64,
+        update: &PullRequestUpdate,
+    ) -> Result<PullRequest, GitHubError> {
+        let path = format!("/repos/{}/{}/pulls/{number}", repo.owner, repo.name);
+        let body = serde_json::to_string(update).unwrap();
+        self.request("PATCH", &path, Some(&body))
+    }
+
+    fn request<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+    ) -> Result<T, GitHubError> {
+        let mut config = String::new();
+        let mut add_option = |name: &str, value: &str| {
+            config.push_str(&format!("{name} = {}\n", quote_curl_config(value)));
+        };
+        add_option("url", &format!("{}{path}", self.api_url));
+        add_option("request", method);
+        add_option("header", "Accept: application/vnd.github+json");
+        add_option("header", "X-GitHub-Api-Version: 2022-11-28");
+        add_option("header", &format!("Authorization: Bearer {}", self.token));
+        add_option("user-agent", "jj");
+        add_option("write-out", "\n%{response_code}");
+        if let Some(body) = body {
+            add_option("header", "Content-Type: application/json");
+            add_option("data-binary", body);
+        }
+
+        let mut child = Command::new(&self.curl_path)
+            .args(["--silent", "--show-error", "--location", "--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => GitHubError::CurlNotFound(self.curl_path.clone()),
+                _ => GitHubError::Spawn(err),
+            })?;
+        let mut stdin = child.stdin.take().unwrap();
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(GitHubError::Spawn)?;
+        drop(stdin);
+        let output = child.wait_with_output().map_err(GitHubError::Spawn)?;
+        if !output.status.success() {
+            return Err(GitHubError::Curl(
+                String::from_utf8_lossy(&output.stderr)
+                    .trim_end()
+                    .to_owned(),
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (content, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+        // The status is 0 for non-HTTP URLs.
+        let status: u32 = status.trim().parse().unwrap_or(0);
+        if status >= 400 {
+            let message = serde_json::from_str::<ErrorResponse>(content)
+                .map(|response| response.message)
+                .unwrap_or_else(|_| content.trim().to_owned());
+            return Err(GitHubError::Api { status, message });
+        }
+        serde_json::from_str(content).map_err(GitHubError::Response)
+    }
+}
+
+/// Parses the owner and name of a GitHub repository from the URL of a Git
+/// remote, such as `https://github.com/owner/name.git` or
+/// `git@github.com:owner/name.git`. The host isn't checked so GitHub
+/// Enterprise remotes are accepted as well.
+pub fn parse_github_remote_url(url: &str) -> Option<GitHubRepo> {
+    let path = if let Some((_, rest)) = url.split_once("://") {
+        let (_, path) = rest.split_once('/')?;
+        path
+    } else {
+        // scp-like syntax: [user@]host:path
+        let (host, path) = url.split_once(':')?;
+        if host.contains('/') {
+            return None;
+        }
+        path
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some(GitHubRepo {
+        owner: owner.to_owned(),
+        name: name.to_owned(),
+    })
+}
+
+const STACK_START_MARKER: &str = "<!-- jj stack -->";
+const STACK_END_MARKER: &str = "<!-- /jj stack -->";
+
+/// Formats the list of pull requests in a stack, with the top of the stack
+/// first. The pull request `current` is marked.
+pub fn format_stack_section(numbers: &[u64], current: u64) -> String {
+    let mut section = format!("{STACK_START_MARKER}\nStack:\n");
+    for &number in numbers.iter().rev() {
+        if number == current {
+            section.push_str(&format!("* #{number} (this pull request)\n"));
+        } else {
+            section.push_str(&format!("* #{number}\n"));
+        }
+    }
+    section.push_str(STACK_END_MARKER);
+    section
+}
+
+/// Replaces the stack section in the body of a pull request, or appends it if
+/// the body doesn't have one.
+pub fn replace_stack_section(body: &str, section: &str) -> String {
+    if let Some(start) = body.find(STACK_START_MARKER)
+        && let Some(end) = body[start..].find(STACK_END_MARKER)
+    {
+        let end = start + end + STACK_END_MARKER.len();
+        format!("{}{section}{}", &body[..start], &body[end..])
+    } else if body.trim().is_empty() {
+        section.to_owned()
+    } else {
+        format!("{}\n\n{section}", body.trim_end())
+    }
+}
+
+/// Quotes a value for a `curl` config file.
+fn quote_curl_config(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str(r#"\""#),
+            '\\' => quoted.push_str(r"\\"),
+            '\n' => quoted.push_str(r"\n"),
+            '\r' => quoted.push_str(r"\r"),
+            '\t' => quoted.push_str(r"\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(owner: &str, name: &str) -> Option<GitHubRepo> {
+        Some(GitHubRepo {
+            owner: owner.to_owned(),
+            name: name.to_owned(),
+        })
+    }
+
+    #[test]
+    fn test_parse_github_remote_url() {
+        assert_eq!(
+            parse_github_remote_url("https://github.com/jj-vcs/jj.git"),
+            repo("jj-vcs", "jj")
+        );
+        assert_eq!(
+            parse_github_remote_url("https://github.com/jj-vcs/jj/"),
+            repo("jj-vcs", "jj")
+        );
+        assert_eq!(
+            parse_github_remote_url("ssh://git@github.example.com:2222/jj-vcs/jj"),
+            repo("jj-vcs", "jj")
+        );
+        assert_eq!(
+            parse_github_remote_url("git@github.com:jj-vcs/jj.git"),
+            repo("jj-vcs", "jj")
+        );
+        assert_eq!(parse_github_remote_url("https://github.com/jj-vcs"), None);
+        assert_eq!(
+            parse_github_remote_url("https://gitlab.com/group/subgroup/project"),
+            None
+        );
+        assert_eq!(parse_github_remote_url("/path/to/repo"), None);
+    }
+
+    #[test]
+    fn test_quote_curl_config() {
+        assert_eq!(quote_curl_config("plain"), r#""plain""#);
+        assert_eq!(
+            quote_curl_config("say \"hi\"\n\\o/"),
+            r#""say \"hi\"\n\\o/""#
+        );
+    }
+
+    #[test]
+    fn test_replace_stack_section() {
+        let section = format_stack_section(&[1, 2, 3], 2);
+        insta::assert_snapshot!(section, @r"
+        <!-- jj stack -->
+        Stack:
+        * #3
+        * #2 (this pull request)
+        * #1
+        <!-- /jj stack -->
+        ");
+        assert_eq!(replace_stack_section("", &section), section);
+        assert_eq!(
+            replace_stack_section("Details.\n", &section),
+            format!("Details.\n\n{section}")
+        );
+
+        let new_section = format_stack_section(&[1, 2], 2);
+        assert_eq!(
+            replace_stack_section(&format!("Details.\n\n{section}\nFooter"), &new_section),
+            format!("Details.\n\n{new_section}\nFooter")
+        );
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("feature/a-b_c.d"), "feature/a-b_c.d");
+        assert_eq!(percent_encode("a+b c"), "a%2Bb%20c");
+    }
+}
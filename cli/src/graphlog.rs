@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Write as _;
 use std::hash::Hash;
 use std::io;
 use std::io::Write;
@@ -24,6 +25,8 @@ use renderdag::Ancestor;
 use renderdag::GraphRowRenderer;
 use renderdag::Renderer;
 
+use crate::ui::Ui;
+
 pub trait GraphLog<K: Clone + Eq + Hash> {
     fn add_node(
         &mut self,
@@ -39,6 +42,7 @@ pub trait GraphLog<K: Clone + Eq + Hash> {
 pub struct SaplingGraphLog<'writer, R> {
     renderer: R,
     writer: &'writer mut dyn Write,
+    colored_lanes: bool,
 }
 
 fn convert_graph_edge_into_ancestor<K: Clone>(e: &GraphEdge<K>) -> Ancestor<K> {
@@ -61,6 +65,7 @@ where
         node_symbol: &str,
         text: &str,
     ) -> io::Result<()> {
+        let width = self.width(id, edges);
         let row = self.renderer.next_row(
             id.clone(),
             edges.iter().map(convert_graph_edge_into_ancestor).collect(),
@@ -68,7 +73,11 @@ where
             text.into(),
         );
 
-        write!(self.writer, "{row}")
+        if self.colored_lanes {
+            write!(self.writer, "{}", colorize_lanes(&row, width))
+        } else {
+            write!(self.writer, "{row}")
+        }
     }
 
     fn width(&self, id: &K, edges: &[GraphEdge<K>]) -> usize {
@@ -82,6 +91,7 @@ impl<'writer, R> SaplingGraphLog<'writer, R> {
     pub fn create<K>(
         renderer: R,
         formatter: &'writer mut dyn Write,
+        colored_lanes: bool,
     ) -> Box<dyn GraphLog<K> + 'writer>
     where
         K: Clone + Eq + Hash + 'writer,
@@ -90,10 +100,52 @@ impl<'writer, R> SaplingGraphLog<'writer, R> {
         Box::new(SaplingGraphLog {
             renderer,
             writer: formatter,
+            colored_lanes,
         })
     }
 }
 
+/// ANSI color indices assigned to graph lanes in turn: blue, magenta, cyan, green,
+/// yellow, and red.
+const LANE_COLORS: [u8; 6] = [4, 5, 6, 2, 3, 1];
+
+/// Colors the glyphs in the first `width` columns of each line by lane. Glyphs
+/// already styled by the node template are left as is.
+fn colorize_lanes(row: &str, width: usize) -> String {
+    let mut output = String::with_capacity(row.len() * 2);
+    for line in row.split_inclusive('\n') {
+        let mut chars = line.chars();
+        let mut column = 0;
+        let mut styled = false;
+        while column < width {
+            let Some(c) = chars.next() else {
+                break;
+            };
+            if c == '\x1b' {
+                let mut sequence = String::from(c);
+                for c in chars.by_ref() {
+                    sequence.push(c);
+                    if c == 'm' {
+                        break;
+                    }
+                }
+                styled = !matches!(sequence.as_str(), "\x1b[0m" | "\x1b[39m");
+                output.push_str(&sequence);
+                continue;
+            }
+            if styled || c.is_whitespace() {
+                output.push(c);
+            } else {
+                let color = LANE_COLORS[(column / 2) % LANE_COLORS.len()];
+                write!(output, "\x1b[38;5;{color}m{c}\x1b[39m").unwrap();
+            }
+            column += 1;
+        }
+        output.push_str(chars.as_str());
+    }
+    output
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
 #[serde(rename_all(deserialize = "kebab-case"))]
 pub enum GraphStyle {
@@ -109,17 +161,34 @@ impl GraphStyle {
     }
 }
 
+/// Returns true if the graph lanes should be colored per `ui.graph.colored-lanes`.
+pub fn colored_lanes_from_settings(
+    ui: &Ui,
+    settings: &UserSettings,
+) -> Result<bool, ConfigGetError> {
+    Ok(ui.color() && settings.get_bool("ui.graph.colored-lanes")?)
+}
+
 pub fn get_graphlog<'a, K: Clone + Eq + Hash + 'a>(
     style: GraphStyle,
+    colored_lanes: bool,
     formatter: &'a mut dyn Write,
 ) -> Box<dyn GraphLog<K> + 'a> {
     let builder = GraphRowRenderer::new().output().with_min_row_height(0);
     match style {
-        GraphStyle::Ascii => SaplingGraphLog::create(builder.build_ascii(), formatter),
-        GraphStyle::AsciiLarge => SaplingGraphLog::create(builder.build_ascii_large(), formatter),
-        GraphStyle::Curved => SaplingGraphLog::create(builder.build_box_drawing(), formatter),
-        GraphStyle::Square => {
-            SaplingGraphLog::create(builder.build_box_drawing().with_square_glyphs(), formatter)
+        GraphStyle::Ascii => {
+            SaplingGraphLog::create(builder.build_ascii(), formatter, colored_lanes)
+        }
+        GraphStyle::AsciiLarge => {
+            SaplingGraphLog::create(builder.build_ascii_large(), formatter, colored_lanes)
+        }
+        GraphStyle::Curved => {
+            SaplingGraphLog::create(builder.build_box_drawing(), formatter, colored_lanes)
         }
+        GraphStyle::Square => SaplingGraphLog::create(
+            builder.build_box_drawing().with_square_glyphs(),
+            formatter,
+            colored_lanes,
+        ),
     }
 }
@@ -14,6 +14,7 @@
 
 #![deny(unused_must_use)]
 
+pub mod annotator;
 pub mod cleanup_guard;
 pub mod cli_util;
 pub mod command_error;
@@ -38,15 +39,20 @@ pub mod git_util {
         false
     }
 }
+#[cfg(feature = "github")]
+pub mod github_util;
 pub mod graphlog;
 pub mod merge_tools;
 pub mod movement_util;
+pub mod op_diff_util;
 pub mod operation_templater;
 mod progress;
 pub mod revset_util;
+pub mod submodule_util;
 pub mod template_builder;
 pub mod template_parser;
 pub mod templater;
 pub mod text_util;
 pub mod time_util;
 pub mod ui;
+pub mod watch_util;
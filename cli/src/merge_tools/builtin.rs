@@ -10,6 +10,7 @@ use jj_lib::backend::CopyId;
 use jj_lib::backend::TreeValue;
 use jj_lib::conflicts;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::conflicts::ConflictMaterializeOptions;
 use jj_lib::conflicts::MIN_CONFLICT_MARKER_LEN;
 use jj_lib::conflicts::MaterializedTreeValue;
@@ -264,9 +265,11 @@ async fn make_diff_files(
     store: &Arc<Store>,
     tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
     marker_style: ConflictMarkerStyle,
+    marker_style_overrides: &ConflictMarkerStyleOverrides,
 ) -> Result<(Vec<RepoPathBuf>, Vec<scm_record::File<'static>>), BuiltinToolError> {
     let materialize_options = ConflictMaterializeOptions {
         marker_style,
+        marker_style_overrides: marker_style_overrides.clone(),
         marker_len: None,
         merge: store.merge_options().clone(),
     };
@@ -277,8 +280,16 @@ async fn make_diff_files(
         let left_path = entry.path.source();
         let right_path = entry.path.target();
         let (left_value, right_value) = entry.values?;
-        let left_info = read_file_contents(left_value, left_path, &materialize_options)?;
-        let right_info = read_file_contents(right_value, right_path, &materialize_options)?;
+        let left_info = read_file_contents(
+            left_value,
+            left_path,
+            &materialize_options.for_path(left_path),
+        )?;
+        let right_info = read_file_contents(
+            right_value,
+            right_path,
+            &materialize_options.for_path(right_path),
+        )?;
         let mut sections = Vec::new();
 
         if left_info.file_mode != right_info.file_mode {
@@ -540,6 +551,7 @@ pub fn edit_diff_builtin(
     trees: Diff<&MergedTree>,
     matcher: &dyn Matcher,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
 ) -> Result<MergedTree, BuiltinToolError> {
     let store = trees.before.store().clone();
     // TODO: handle copy tracking
@@ -547,8 +559,13 @@ pub fn edit_diff_builtin(
     let tree_diff = trees
         .before
         .diff_stream_with_copies(trees.after, matcher, &copy_records);
-    let (changed_files, files) =
-        make_diff_files(&store, tree_diff, conflict_marker_style).block_on()?;
+    let (changed_files, files) = make_diff_files(
+        &store,
+        tree_diff,
+        conflict_marker_style,
+        conflict_marker_style_overrides,
+    )
+    .block_on()?;
     let mut input = scm_record::helpers::CrosstermInput;
     let recorder = scm_record::Recorder::new(
         scm_record::RecordState {
@@ -761,9 +778,14 @@ mod tests {
     ) -> (Vec<RepoPathBuf>, Vec<scm_record::File<'static>>) {
         let copy_records = CopyRecords::default();
         let tree_diff = left_tree.diff_stream_with_copies(right_tree, matcher, &copy_records);
-        make_diff_files(store, tree_diff, ConflictMarkerStyle::Diff)
-            .block_on()
-            .unwrap()
+        make_diff_files(
+            store,
+            tree_diff,
+            ConflictMarkerStyle::Diff,
+            &ConflictMarkerStyleOverrides::default(),
+        )
+        .block_on()
+        .unwrap()
     }
 
     fn apply_diff(
@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use futures::StreamExt as _;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::EolConversionMode;
@@ -133,6 +134,7 @@ pub(crate) fn check_out_trees(
     matcher: &dyn Matcher,
     diff_type: DiffType,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
 ) -> Result<DiffWorkingCopies, DiffCheckoutError> {
     let store = trees.before.store();
     let changed_files: Vec<_> = trees
@@ -153,8 +155,11 @@ pub(crate) fn check_out_trees(
         std::fs::create_dir(&state_dir).map_err(DiffCheckoutError::SetUpDir)?;
         let tree_state_settings = TreeStateSettings {
             conflict_marker_style,
+            conflict_marker_style_overrides: conflict_marker_style_overrides.clone(),
             eol_conversion_mode: EolConversionMode::None,
             fsmonitor_settings: FsmonitorSettings::None,
+            git_lfs_settings: None,
+            jobs: None,
         };
         let mut state = TreeState::init(store.clone(), wc_path, state_dir, &tree_state_settings)?;
         state.set_sparse_patterns(changed_files.clone())?;
@@ -190,8 +195,15 @@ impl DiffEditWorkingCopies {
         diff_type: DiffType,
         instructions: Option<&str>,
         conflict_marker_style: ConflictMarkerStyle,
+        conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
     ) -> Result<Self, DiffEditError> {
-        let working_copies = check_out_trees(trees, matcher, diff_type, conflict_marker_style)?;
+        let working_copies = check_out_trees(
+            trees,
+            matcher,
+            diff_type,
+            conflict_marker_style,
+            conflict_marker_style_overrides,
+        )?;
         working_copies.set_left_readonly()?;
         if diff_type == DiffType::ThreeWay {
             working_copies.set_right_readonly()?;
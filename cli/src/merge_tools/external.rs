@@ -13,6 +13,7 @@ use jj_lib::backend::CopyId;
 use jj_lib::backend::TreeValue;
 use jj_lib::conflicts;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::conflicts::ConflictMaterializeOptions;
 use jj_lib::conflicts::MIN_CONFLICT_MARKER_LEN;
 use jj_lib::conflicts::choose_materialized_conflict_marker_len;
@@ -184,6 +185,7 @@ fn run_mergetool_external_single_file(
     store: &Store,
     merge_tool_file: &MergeToolFile,
     default_conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
     tree_builder: &mut MergedTreeBuilder,
 ) -> Result<(), ConflictResolveError> {
     let MergeToolFile {
@@ -205,9 +207,10 @@ fn run_mergetool_external_single_file(
     };
     let initial_output_content = if editor.merge_tool_edits_conflict_markers {
         let options = ConflictMaterializeOptions {
-            marker_style: editor
-                .conflict_marker_style
-                .unwrap_or(default_conflict_marker_style),
+            marker_style: editor.conflict_marker_style.unwrap_or_else(|| {
+                conflict_marker_style_overrides.style_for(repo_path, default_conflict_marker_style)
+            }),
+            marker_style_overrides: ConflictMarkerStyleOverrides::default(),
             marker_len: Some(conflict_marker_len),
             merge: store.merge_options().clone(),
         };
@@ -337,6 +340,7 @@ pub fn run_mergetool_external(
     tree: &MergedTree,
     merge_tool_files: &[MergeToolFile],
     default_conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
 ) -> Result<(MergedTree, Option<MergeToolPartialResolutionError>), ConflictResolveError> {
     // TODO: add support for "dir" invocation mode, similar to the
     // "diff-invocation-mode" config option for diffs
@@ -353,6 +357,7 @@ pub fn run_mergetool_external(
             tree.store(),
             merge_tool_file,
             default_conflict_marker_style,
+            conflict_marker_style_overrides,
             &mut tree_builder,
         ) {
             Ok(()) => {}
@@ -375,6 +380,23 @@ pub fn run_mergetool_external(
     Ok((new_tree, partial_resolution_error))
 }
 
+/// Returns the conflict marker styles to materialize files for `tool` with.
+/// The style configured for the tool takes precedence over the per-path
+/// overrides since the tool may only understand that style.
+fn tool_conflict_marker_style(
+    tool: &ExternalMergeTool,
+    default_conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
+) -> (ConflictMarkerStyle, ConflictMarkerStyleOverrides) {
+    match tool.conflict_marker_style {
+        Some(style) => (style, ConflictMarkerStyleOverrides::default()),
+        None => (
+            default_conflict_marker_style,
+            conflict_marker_style_overrides.clone(),
+        ),
+    }
+}
+
 pub fn edit_diff_external(
     editor: &ExternalMergeTool,
     trees: Diff<&MergedTree>,
@@ -382,10 +404,13 @@ pub fn edit_diff_external(
     instructions: Option<&str>,
     base_ignores: Arc<GitIgnoreFile>,
     default_conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
 ) -> Result<MergedTree, DiffEditError> {
-    let conflict_marker_style = editor
-        .conflict_marker_style
-        .unwrap_or(default_conflict_marker_style);
+    let (conflict_marker_style, conflict_marker_style_overrides) = tool_conflict_marker_style(
+        editor,
+        default_conflict_marker_style,
+        conflict_marker_style_overrides,
+    );
 
     let got_output_field = find_all_variables(&editor.edit_args).contains(&"output");
     let diff_type = if got_output_field {
@@ -399,6 +424,7 @@ pub fn edit_diff_external(
         diff_type,
         instructions,
         conflict_marker_style,
+        &conflict_marker_style_overrides,
     )?;
 
     let patterns = diffedit_wc.working_copies.to_command_variables(false);
@@ -421,6 +447,7 @@ pub fn edit_diff_external(
 }
 
 /// Generates textual diff by the specified `tool` and writes into `writer`.
+#[expect(clippy::too_many_arguments)]
 pub fn generate_diff(
     ui: &Ui,
     writer: &mut dyn Write,
@@ -428,12 +455,21 @@ pub fn generate_diff(
     matcher: &dyn Matcher,
     tool: &ExternalMergeTool,
     default_conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &ConflictMarkerStyleOverrides,
     width: usize,
 ) -> Result<(), DiffGenerateError> {
-    let conflict_marker_style = tool
-        .conflict_marker_style
-        .unwrap_or(default_conflict_marker_style);
-    let diff_wc = check_out_trees(trees, matcher, DiffType::TwoWay, conflict_marker_style)?;
+    let (conflict_marker_style, conflict_marker_style_overrides) = tool_conflict_marker_style(
+        tool,
+        default_conflict_marker_style,
+        conflict_marker_style_overrides,
+    );
+    let diff_wc = check_out_trees(
+        trees,
+        matcher,
+        DiffType::TwoWay,
+        conflict_marker_style,
+        &conflict_marker_style_overrides,
+    )?;
     diff_wc.set_left_readonly()?;
     diff_wc.set_right_readonly()?;
     let mut patterns = diff_wc.to_command_variables(true);
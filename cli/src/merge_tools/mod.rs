@@ -26,6 +26,7 @@ use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::conflicts::MaterializedFileConflictValue;
 use jj_lib::conflicts::try_materialize_file_conflict_value;
 use jj_lib::gitignore::GitIgnoreFile;
@@ -237,6 +238,7 @@ pub struct DiffEditor {
     base_ignores: Arc<GitIgnoreFile>,
     use_instructions: bool,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
 }
 
 impl DiffEditor {
@@ -289,6 +291,7 @@ impl DiffEditor {
             base_ignores,
             use_instructions: settings.get_bool("ui.diff-instructions")?,
             conflict_marker_style,
+            conflict_marker_style_overrides: ConflictMarkerStyleOverrides::from_settings(settings)?,
         })
     }
 
@@ -300,12 +303,13 @@ impl DiffEditor {
         format_instructions: impl FnOnce() -> String,
     ) -> Result<MergedTree, DiffEditError> {
         match &self.tool {
-            DiffEditTool::Builtin => {
-                Ok(
-                    edit_diff_builtin(trees, matcher, self.conflict_marker_style)
-                        .map_err(Box::new)?,
-                )
-            }
+            DiffEditTool::Builtin => Ok(edit_diff_builtin(
+                trees,
+                matcher,
+                self.conflict_marker_style,
+                &self.conflict_marker_style_overrides,
+            )
+            .map_err(Box::new)?),
             DiffEditTool::External(editor) => {
                 let instructions = self.use_instructions.then(format_instructions);
                 edit_diff_external(
@@ -315,6 +319,7 @@ impl DiffEditor {
                     instructions.as_deref(),
                     self.base_ignores.clone(),
                     self.conflict_marker_style,
+                    &self.conflict_marker_style_overrides,
                 )
             }
         }
@@ -371,6 +376,7 @@ pub struct MergeEditor {
     tool: MergeTool,
     path_converter: RepoPathUiConverter,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
 }
 
 impl MergeEditor {
@@ -384,7 +390,7 @@ impl MergeEditor {
     ) -> Result<Self, MergeToolConfigError> {
         let tool = MergeTool::get_tool_config(settings, name)?
             .unwrap_or_else(|| MergeTool::external(ExternalMergeTool::with_program(name)));
-        Self::new_inner(name, tool, path_converter, conflict_marker_style)
+        Self::new_inner(name, tool, settings, path_converter, conflict_marker_style)
     }
 
     /// Loads the default 3-way merge editor from the settings.
@@ -401,12 +407,13 @@ impl MergeEditor {
             None
         }
         .unwrap_or_else(|| MergeTool::external(ExternalMergeTool::with_merge_args(&args)));
-        Self::new_inner(&args, tool, path_converter, conflict_marker_style)
+        Self::new_inner(&args, tool, settings, path_converter, conflict_marker_style)
     }
 
     fn new_inner(
         name: impl ToString,
         tool: MergeTool,
+        settings: &UserSettings,
         path_converter: RepoPathUiConverter,
         conflict_marker_style: ConflictMarkerStyle,
     ) -> Result<Self, MergeToolConfigError> {
@@ -421,6 +428,7 @@ impl MergeEditor {
             tool,
             path_converter,
             conflict_marker_style,
+            conflict_marker_style_overrides: ConflictMarkerStyleOverrides::from_settings(settings)?,
         })
     }
 
@@ -456,6 +464,7 @@ impl MergeEditor {
                 tree,
                 &merge_tool_files,
                 self.conflict_marker_style,
+                &self.conflict_marker_style_overrides,
             ),
         }
     }
@@ -260,6 +260,11 @@ pub(crate) fn move_to_commit(
     };
 
     let target = get_target_commit(ui, &workspace_command, direction, current_wc_id, &args)?;
+    let num_conflicted_descendants = if direction == Direction::Next && !args.conflict {
+        count_conflicted_descendants(&workspace_command, &target)?
+    } else {
+        0
+    };
     let current_short = short_commit_hash(current_wc_id);
     let target_short = short_commit_hash(target.id());
     let cmd = direction.cmd();
@@ -273,11 +278,41 @@ pub(crate) fn move_to_commit(
             ui,
             format!("{cmd}: {current_short} -> editing {target_short}"),
         )?;
-        return Ok(());
+    } else {
+        let mut tx = workspace_command.start_transaction();
+        // Move the working-copy commit to the new parent.
+        tx.check_out(&target)?;
+        tx.finish(ui, format!("{cmd}: {current_short} -> {target_short}"))?;
+    }
+    if num_conflicted_descendants > 0 {
+        writeln!(
+            ui.hint_default(),
+            "There are unresolved conflicts in {num_conflicted_descendants} descendant \
+             commit{}. Use `jj next --conflict` to jump to the nearest one.",
+            if num_conflicted_descendants == 1 {
+                ""
+            } else {
+                "s"
+            },
+        )?;
     }
-    let mut tx = workspace_command.start_transaction();
-    // Move the working-copy commit to the new parent.
-    tx.check_out(&target)?;
-    tx.finish(ui, format!("{cmd}: {current_short} -> {target_short}"))?;
     Ok(())
 }
+
+/// Counts the descendants of `target` (excluding `target` itself) that have
+/// conflicts.
+fn count_conflicted_descendants(
+    workspace_command: &WorkspaceCommandHelper,
+    target: &Commit,
+) -> Result<usize, CommandError> {
+    let target_revset = RevsetExpression::commit(target.id().clone());
+    let conflicted_revset = target_revset
+        .children()
+        .descendants()
+        .filtered(RevsetFilterPredicate::HasConflict);
+    let count = conflicted_revset
+        .evaluate(workspace_command.repo().as_ref())?
+        .iter()
+        .process_results(|iter| iter.count())?;
+    Ok(count)
+}
@@ -42,7 +42,9 @@ use jj_lib::revset::RevsetResolutionError;
 use jj_lib::revset::SymbolResolver;
 use jj_lib::revset::SymbolResolverExtension;
 use jj_lib::revset::UserRevsetExpression;
+use jj_lib::settings::UserSettings;
 use jj_lib::str_util::StringExpression;
+use jj_lib::str_util::StringMatcher;
 use thiserror::Error;
 
 use crate::command_error::CommandError;
@@ -370,3 +372,13 @@ where
     print_parse_diagnostics(ui, "In name pattern", &diagnostics)?;
     Ok(StringExpression::union_all(expressions))
 }
+
+/// Parses the `bookmarks.protected` name patterns into a matcher of bookmarks
+/// that shouldn't be deleted or moved backwards without explicit consent.
+pub fn parse_protected_bookmarks(
+    ui: &Ui,
+    settings: &UserSettings,
+) -> Result<StringMatcher, CommandError> {
+    let patterns: Vec<String> = settings.get("bookmarks.protected")?;
+    Ok(parse_union_name_patterns(ui, &patterns)?.to_matcher())
+}
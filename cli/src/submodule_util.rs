@@ -0,0 +1,430 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Git submodules checked out in the working copy.
+//!
+//! The working-copy backend doesn't manage the content of submodules. These
+//! helpers inspect and update submodule repositories by running `git`.
+
+use std::fs;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+
+use jj_lib::backend::BackendError;
+use jj_lib::backend::BackendResult;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::TreeValue;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use pollster::FutureExt as _;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+/// Error while running `git` in a submodule.
+#[derive(Debug, Error)]
+pub enum SubmoduleError {
+    #[error("Failed to run git")]
+    Spawn(#[source] io::Error),
+    #[error("git {command} failed: {stderr}")]
+    Command { command: String, stderr: String },
+    #[error("No URL is configured for the submodule")]
+    MissingUrl,
+    #[error("Invalid submodule name {0:?}")]
+    InvalidName(String),
+    #[error("Invalid submodule URL {0:?}")]
+    InvalidUrl(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Submodule declared in `.gitmodules`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submodule {
+    pub name: String,
+    pub path: RepoPathBuf,
+    pub url: Option<String>,
+}
+
+/// Parses the content of a `.gitmodules` file. Entries without a valid path
+/// are skipped.
+pub fn parse_gitmodules(content: &str) -> Vec<Submodule> {
+    let mut submodules = vec![];
+    let mut current: Option<(String, Option<String>, Option<String>)> = None;
+    let mut flush = |current: Option<(String, Option<String>, Option<String>)>| {
+        if let Some((name, Some(path), url)) = current
+            && let Ok(path) = RepoPathBuf::from_relative_path(path.trim_end_matches('/'))
+        {
+            submodules.push(Submodule { name, path, url });
+        }
+    };
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[') {
+            flush(current.take());
+            current = section
+                .strip_suffix(']')
+                .and_then(|section| section.trim().strip_prefix("submodule"))
+                .and_then(|name| name.trim().strip_prefix('"')?.strip_suffix('"'))
+                .map(|name| (name.to_owned(), None, None));
+        } else if let Some((_, path, url)) = &mut current
+            && let Some((key, value)) = line.split_once('=')
+        {
+            let value = value.trim().trim_matches('"').to_owned();
+            match key.trim() {
+                "path" => *path = Some(value),
+                "url" => *url = Some(value),
+                _ => {}
+            }
+        }
+    }
+    flush(current);
+    submodules
+}
+
+/// Returns submodules declared in `.gitmodules` along with the commits
+/// recorded for them in the `tree`. Submodules not present in the tree are
+/// skipped.
+pub fn submodules_in_tree(tree: &MergedTree) -> BackendResult<Vec<(Submodule, CommitId)>> {
+    let gitmodules_path = RepoPath::from_internal_string(".gitmodules").unwrap();
+    let value = tree.path_value(gitmodules_path)?;
+    let Some(Some(TreeValue::File { id, .. })) = value.as_resolved() else {
+        return Ok(vec![]);
+    };
+    let mut reader = tree.store().read_file(gitmodules_path, id).block_on()?;
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .block_on()
+        .map_err(|err| BackendError::ReadFile {
+            path: gitmodules_path.to_owned(),
+            id: id.clone(),
+            source: err.into(),
+        })?;
+    let mut submodules = vec![];
+    for submodule in parse_gitmodules(&String::from_utf8_lossy(&content)) {
+        if let Some(Some(TreeValue::GitSubmodule(commit_id))) =
+            tree.path_value(&submodule.path)?.as_resolved()
+        {
+            let commit_id = commit_id.clone();
+            submodules.push((submodule, commit_id));
+        }
+    }
+    Ok(submodules)
+}
+
+/// Returns the commit checked out in the submodule directory `dir`, or `None`
+/// if the submodule isn't checked out.
+pub fn submodule_head(git_executable_path: &Path, dir: &Path) -> Option<CommitId> {
+    // Without this check, git would find the repository containing `dir`.
+    if !dir.join(".git").exists() {
+        return None;
+    }
+    let output = run_git(git_executable_path, dir, &["rev-parse", "--verify", "HEAD"]).ok()?;
+    CommitId::try_from_hex(output.trim_end())
+}
+
+/// Returns the subjects of commits between `from` and `to` in the submodule
+/// directory `dir`. Each subject is prefixed by `>` if it was added or `<` if
+/// it was removed. Returns `None` if the commits aren't available.
+pub fn submodule_log(
+    git_executable_path: &Path,
+    dir: &Path,
+    from: &CommitId,
+    to: &CommitId,
+) -> Option<Vec<String>> {
+    if !dir.join(".git").exists() {
+        return None;
+    }
+    let range = format!("{}...{}", from.hex(), to.hex());
+    let output = run_git(
+        git_executable_path,
+        dir,
+        &[
+            "log",
+            "--left-right",
+            "--first-parent",
+            "--format=%m %s",
+            &range,
+        ],
+    )
+    .ok()?;
+    Some(output.lines().map(|line| line.to_owned()).collect())
+}
+
+/// Checks out `commit_id` in the `submodule` in the workspace. If the
+/// submodule isn't cloned yet, its repository is cloned into `modules_dir`
+/// from the submodule URL. Like in Git, relative URLs are resolved against
+/// `superproject_url`, which should be the URL of the default remote, or the
+/// workspace root if there's no such remote.
+pub fn check_out_submodule(
+    git_executable_path: &Path,
+    workspace_root: &Path,
+    modules_dir: &Path,
+    superproject_url: &str,
+    submodule: &Submodule,
+    commit_id: &CommitId,
+) -> Result<(), SubmoduleError> {
+    // The name comes from the untrusted `.gitmodules`, and is used as a path
+    // under `modules_dir`.
+    if !is_valid_submodule_name(&submodule.name) {
+        return Err(SubmoduleError::InvalidName(submodule.name.clone()));
+    }
+    let dir = &submodule.path.to_fs_path_unchecked(workspace_root);
+    if !dir.join(".git").exists() {
+        let git_dir = modules_dir.join(&submodule.name);
+        if git_dir.exists() {
+            // Reuse the repository cloned by `git submodule update`.
+            fs::create_dir_all(dir)?;
+            fs::write(
+                dir.join(".git"),
+                format!("gitdir: {}\n", dunce::canonicalize(&git_dir)?.display()),
+            )?;
+            let work_tree = dunce::canonicalize(dir)?;
+            let work_tree = work_tree.to_string_lossy();
+            run_git(
+                git_executable_path,
+                dir,
+                &["config", "core.worktree", &work_tree],
+            )?;
+        } else {
+            let url = submodule.url.as_ref().ok_or(SubmoduleError::MissingUrl)?;
+            let url = resolve_submodule_url(url, superproject_url);
+            // The URL comes from the untrusted `.gitmodules`, and must not be
+            // parsed as an option such as `--upload-pack`.
+            if url.starts_with('-') {
+                return Err(SubmoduleError::InvalidUrl(url));
+            }
+            fs::create_dir_all(modules_dir)?;
+            let mut cmd = git_command(git_executable_path, workspace_root);
+            cmd.args(["clone", "--quiet", "--no-checkout", "--separate-git-dir"])
+                .arg(&git_dir)
+                .arg("--")
+                .arg(url)
+                .arg(dir);
+            run_command(cmd, "clone")?;
+        }
+    }
+    let hex = commit_id.hex();
+    let checkout = ["checkout", "--quiet", "--detach", &hex];
+    if run_git(git_executable_path, dir, &checkout).is_err() {
+        // The commit may not have been fetched yet.
+        run_git(
+            git_executable_path,
+            dir,
+            &["fetch", "--quiet", "origin", &hex],
+        )?;
+        run_git(git_executable_path, dir, &checkout)?;
+    }
+    Ok(())
+}
+
+/// Resolves the submodule `url` relative to `base_url` if it starts with `./`
+/// or `../`, as `git submodule` does. Each `../` removes the last component of
+/// the base URL, which can also be the path of a `host:path` URL.
+fn resolve_submodule_url(url: &str, base_url: &str) -> String {
+    if !url.starts_with("./") && !url.starts_with("../") {
+        return url.to_owned();
+    }
+    let mut base = base_url.trim_end_matches('/');
+    let mut separator = '/';
+    let mut rest = url;
+    loop {
+        if let Some(stripped) = rest.strip_prefix("./") {
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix("../") {
+            rest = stripped;
+            if let Some(pos) = base.rfind(['/', ':']) {
+                separator = base.as_bytes()[pos].into();
+                base = &base[..pos];
+            } else {
+                base = ".";
+                separator = '/';
+            }
+        } else {
+            break;
+        }
+    }
+    format!("{base}{separator}{rest}")
+}
+
+/// Returns true if the submodule `name` stays within the modules directory
+/// when joined to it. Like Git, names with `..` components are rejected, as
+/// well as absolute names and names containing backslashes.
+fn is_valid_submodule_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains('\\')
+        && Path::new(name)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn git_command(git_executable_path: &Path, dir: &Path) -> Command {
+    let mut cmd = Command::new(git_executable_path);
+    cmd.current_dir(dir)
+        // Don't pick up the repository containing the submodule.
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_WORK_TREE")
+        .stdin(Stdio::null());
+    cmd
+}
+
+fn run_git(
+    git_executable_path: &Path,
+    dir: &Path,
+    args: &[&str],
+) -> Result<String, SubmoduleError> {
+    let mut cmd = git_command(git_executable_path, dir);
+    cmd.args(args);
+    run_command(cmd, args[0])
+}
+
+fn run_command(mut cmd: Command, name: &str) -> Result<String, SubmoduleError> {
+    let output = cmd.output().map_err(SubmoduleError::Spawn)?;
+    if !output.status.success() {
+        return Err(SubmoduleError::Command {
+            command: name.to_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr)
+                .trim_end()
+                .to_owned(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_gitmodules() {
+        let content = indoc::indoc! {r#"
+            [submodule "lib"]
+            	path = vendor/lib
+            	url = https://example.com/lib.git
+            # comment
+            [submodule "no-url"]
+            	path = other/
+            [core]
+            	path = ignored
+            [submodule "no-path"]
+            	url = https://example.com/no-path.git
+        "#};
+        assert_eq!(
+            parse_gitmodules(content),
+            vec![
+                Submodule {
+                    name: "lib".to_owned(),
+                    path: RepoPathBuf::from_internal_string("vendor/lib").unwrap(),
+                    url: Some("https://example.com/lib.git".to_owned()),
+                },
+                Submodule {
+                    name: "no-url".to_owned(),
+                    path: RepoPathBuf::from_internal_string("other").unwrap(),
+                    url: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_valid_submodule_name() {
+        assert!(is_valid_submodule_name("lib"));
+        assert!(is_valid_submodule_name("vendor/lib"));
+        assert!(is_valid_submodule_name("lib..old"));
+        assert!(!is_valid_submodule_name(""));
+        assert!(!is_valid_submodule_name(".."));
+        assert!(!is_valid_submodule_name("../hooks"));
+        assert!(!is_valid_submodule_name("lib/../../hooks"));
+        assert!(!is_valid_submodule_name("/tmp/lib"));
+        assert!(!is_valid_submodule_name("..\\hooks"));
+        assert!(!is_valid_submodule_name("lib\\..\\..\\hooks"));
+    }
+
+    #[test]
+    fn test_check_out_submodule_invalid_name() {
+        let submodule = Submodule {
+            name: "../../hooks".to_owned(),
+            path: RepoPathBuf::from_internal_string("lib").unwrap(),
+            url: Some("https://example.com/lib.git".to_owned()),
+        };
+        let commit_id = CommitId::from_hex("0123456789abcdef0123456789abcdef01234567");
+        let result = check_out_submodule(
+            Path::new("git"),
+            Path::new("/nonexistent/workspace"),
+            Path::new("/nonexistent/workspace/.git/modules"),
+            "https://example.com/repo.git",
+            &submodule,
+            &commit_id,
+        );
+        assert_matches!(result, Err(SubmoduleError::InvalidName(name)) if name == "../../hooks");
+    }
+
+    #[test]
+    fn test_check_out_submodule_option_url() {
+        let submodule = Submodule {
+            name: "lib".to_owned(),
+            path: RepoPathBuf::from_internal_string("lib").unwrap(),
+            url: Some("--upload-pack=touch pwned".to_owned()),
+        };
+        let commit_id = CommitId::from_hex("0123456789abcdef0123456789abcdef01234567");
+        let result = check_out_submodule(
+            Path::new("git"),
+            Path::new("/nonexistent/workspace"),
+            Path::new("/nonexistent/workspace/.git/modules"),
+            "https://example.com/repo.git",
+            &submodule,
+            &commit_id,
+        );
+        assert_matches!(result, Err(SubmoduleError::InvalidUrl(url)) if url.starts_with("--"));
+    }
+
+    #[test]
+    fn test_resolve_submodule_url() {
+        let base = "https://example.com/org/repo.git";
+        assert_eq!(
+            resolve_submodule_url("https://example.com/lib.git", base),
+            "https://example.com/lib.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("../lib.git", base),
+            "https://example.com/org/lib.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("../../other/lib.git", base),
+            "https://example.com/other/lib.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("./lib.git", base),
+            "https://example.com/org/repo.git/lib.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("../lib.git", "git@example.com:repo.git"),
+            "git@example.com:lib.git"
+        );
+        assert_eq!(
+            resolve_submodule_url("../lib", "/home/user/repo/"),
+            "/home/user/lib"
+        );
+    }
+}
@@ -1096,6 +1096,14 @@ fn builtin_string_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "slugify",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|s| slugify(&s));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "replace",
         |language, diagnostics, build_ctx, self_property, function| {
@@ -1157,6 +1165,15 @@ fn string_index_to_char_boundary(s: &str, i: isize) -> usize {
     }
 }
 
+/// Lowercases ASCII alphanumeric characters and collapses everything else into
+/// single `-` separators, trimming them from both ends.
+fn slugify(s: &str) -> String {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+        .join("-")
+}
+
 fn builtin_config_value_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
 -> TemplateBuildMethodFnMap<'a, L, ConfigValue> {
     fn extract<'de, T: Deserialize<'de>>(value: ConfigValue) -> Result<T, TemplatePropertyError> {
@@ -3193,6 +3210,11 @@ mod tests {
         insta::assert_snapshot!(env.render_ok(r#""hello".escape_json()"#), @r#""hello""#);
         insta::assert_snapshot!(env.render_ok(r#""he \n ll \n \" o".escape_json()"#), @r#""he \n ll \n \" o""#);
 
+        insta::assert_snapshot!(env.render_ok(r#""Fix the Frobnicator!".slugify()"#), @"fix-the-frobnicator");
+        insta::assert_snapshot!(env.render_ok(r#""  --a_b  c.d-- ".slugify()"#), @"a-b-c-d");
+        insta::assert_snapshot!(env.render_ok(r#""💩 ünïcode".slugify()"#), @"n-code");
+        insta::assert_snapshot!(env.render_ok(r#""".slugify()"#), @"");
+
         // simple substring replacement
         insta::assert_snapshot!(env.render_ok(r#""hello world".replace("world", "jj")"#), @"hello jj");
         insta::assert_snapshot!(env.render_ok(r#""hello world world".replace("world", "jj")"#), @"hello jj jj");
@@ -367,6 +367,11 @@ impl Ui {
         Ok(())
     }
 
+    /// Prevents the output from being switched to the pager later on.
+    pub fn disable_pager(&mut self) {
+        self.pager = PagerConfig::Disabled;
+    }
+
     /// Switches the output to use the pager, if allowed.
     #[instrument(skip_all)]
     pub fn request_pager(&mut self) {
@@ -0,0 +1,179 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for commands that re-render their output as the repository changes.
+
+use std::io;
+use std::io::IsTerminal as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use jj_lib::working_copy::SnapshotStats;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// How often the working copy and the operation log are checked for changes
+/// if filesystem events aren't available.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait for more filesystem events after one arrives, so that a
+/// burst of changes results in a single re-render.
+#[cfg(feature = "notify")]
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Calls `render` whenever the repository changes, until the process is
+/// interrupted.
+///
+/// The working copy is snapshotted whenever a file in the workspace or the
+/// operation heads change, so changes to it show up as new operations just
+/// like operations run by other processes. If the filesystem can't be watched,
+/// the working copy and the operation log are checked every second instead.
+/// With a filesystem monitor configured (`fsmonitor.backend`), the snapshots
+/// only look at the files reported as changed.
+pub fn watch_workspace(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    mut render: impl FnMut(&mut Ui, &WorkspaceCommandHelper, &SnapshotStats) -> Result<(), CommandError>,
+) -> Result<(), CommandError> {
+    // The output is replaced on every change, which doesn't work with a pager.
+    ui.disable_pager();
+    let mut last_op_id = None;
+    let mut watcher = None;
+    loop {
+        let (workspace_command, stats) = command.workspace_helper_with_stats(ui)?;
+        let op_id = workspace_command.repo().op_id().clone();
+        if last_op_id.as_ref() != Some(&op_id) {
+            if io::stdout().is_terminal() {
+                // Clear the screen and move the cursor to the top-left corner.
+                write!(ui.stdout(), "\x1b[2J\x1b[H")?;
+            }
+            render(ui, &workspace_command, &stats)?;
+            ui.stdout().flush()?;
+            last_op_id = Some(op_id);
+        }
+        let watcher = watcher.get_or_insert_with(|| {
+            ChangeWatcher::new(
+                workspace_command.workspace_root(),
+                &workspace_command.repo_path().join("op_heads"),
+            )
+        });
+        watcher.wait();
+    }
+}
+
+/// Waits for changes to the workspace or the operation log.
+enum ChangeWatcher {
+    #[cfg(feature = "notify")]
+    Events {
+        // Stops watching when dropped.
+        _watcher: notify::RecommendedWatcher,
+        receiver: std::sync::mpsc::Receiver<()>,
+    },
+    Poll,
+}
+
+impl ChangeWatcher {
+    /// Watches the files in `workspace_root` and the operation heads in
+    /// `op_heads_dir`, falling back to polling if that fails.
+    #[cfg_attr(not(feature = "notify"), expect(unused_variables))]
+    fn new(workspace_root: &Path, op_heads_dir: &Path) -> Self {
+        #[cfg(feature = "notify")]
+        match Self::watch(workspace_root, op_heads_dir) {
+            Ok(watcher) => return watcher,
+            Err(err) => {
+                tracing::warn!(?err, "failed to watch the workspace, polling instead");
+            }
+        }
+        Self::Poll
+    }
+
+    #[cfg(feature = "notify")]
+    fn watch(workspace_root: &Path, op_heads_dir: &Path) -> notify::Result<Self> {
+        use notify::Watcher as _;
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let jj_dir = workspace_root.join(".jj");
+        let op_heads_dir = op_heads_dir.to_owned();
+        let mut watcher = notify::recommended_watcher({
+            let op_heads_dir = op_heads_dir.clone();
+            move |event: notify::Result<notify::Event>| {
+                // Report errors as changes so that nothing is missed.
+                let relevant = event.map_or(true, |event| {
+                    event
+                        .paths
+                        .iter()
+                        .any(|path| is_relevant_change(path, &jj_dir, &op_heads_dir))
+                });
+                if relevant {
+                    sender.send(()).ok();
+                }
+            }
+        })?;
+        watcher.watch(workspace_root, notify::RecursiveMode::Recursive)?;
+        if !op_heads_dir.starts_with(workspace_root) {
+            watcher.watch(&op_heads_dir, notify::RecursiveMode::Recursive)?;
+        }
+        Ok(Self::Events {
+            _watcher: watcher,
+            receiver,
+        })
+    }
+
+    /// Blocks until something may have changed.
+    fn wait(&self) {
+        match self {
+            #[cfg(feature = "notify")]
+            Self::Events { receiver, .. } => {
+                if receiver.recv().is_err() {
+                    thread::sleep(POLL_INTERVAL);
+                    return;
+                }
+                while receiver.recv_timeout(DEBOUNCE_INTERVAL).is_ok() {}
+            }
+            Self::Poll => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Returns true if a change to `path` may change the output. Changes in the
+/// `.jj` directory are only relevant if they're to the operation heads, since
+/// snapshotting the working copy itself writes there.
+#[cfg_attr(not(feature = "notify"), expect(dead_code))]
+fn is_relevant_change(path: &Path, jj_dir: &Path, op_heads_dir: &Path) -> bool {
+    path.starts_with(op_heads_dir) || !path.starts_with(jj_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_is_relevant_change() {
+        let jj_dir = PathBuf::from("/ws/.jj");
+        let op_heads_dir = PathBuf::from("/ws/.jj/repo/op_heads");
+        let is_relevant = |path: &str| is_relevant_change(Path::new(path), &jj_dir, &op_heads_dir);
+        assert!(is_relevant("/ws/file"));
+        assert!(is_relevant("/ws/dir/file"));
+        assert!(is_relevant("/ws/.jj/repo/op_heads/heads/0123"));
+        assert!(!is_relevant("/ws/.jj/working_copy/tree_state"));
+        assert!(!is_relevant("/ws/.jj/repo/op_store/operations/0123"));
+    }
+}
@@ -13,9 +13,13 @@ mod test_absorb_command;
 mod test_acls;
 mod test_advance_bookmarks;
 mod test_alias;
+mod test_annotators;
+mod test_api_command;
+mod test_backport_command;
 mod test_bisect_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
+mod test_changelog_command;
 mod test_commit_command;
 mod test_commit_template;
 mod test_completion;
@@ -33,12 +37,17 @@ mod test_edit_command;
 mod test_evolog_command;
 mod test_file_annotate_command;
 mod test_file_chmod_command;
+mod test_file_lfs_command;
 mod test_file_list_command;
 mod test_file_show_command;
 mod test_file_track_untrack_commands;
 mod test_fix_command;
+mod test_format_apply_patch_commands;
 mod test_generate_md_cli_help;
+mod test_gerrit_fetch;
 mod test_gerrit_upload;
+mod test_git_apply;
+mod test_git_bundle;
 mod test_git_clone;
 mod test_git_colocated;
 mod test_git_colocation;
@@ -49,6 +58,9 @@ mod test_git_private_commits;
 mod test_git_push;
 mod test_git_remotes;
 mod test_git_root;
+mod test_git_submodules;
+mod test_git_sync;
+mod test_github_pr;
 mod test_gitignores;
 mod test_global_opts;
 mod test_help_command;
@@ -59,10 +71,12 @@ mod test_log_command;
 mod test_metaedit_command;
 mod test_new_command;
 mod test_next_prev_commands;
+mod test_note_command;
 mod test_op_revert_command;
 mod test_operations;
 mod test_parallelize_command;
 mod test_rebase_command;
+mod test_recover_command;
 mod test_repo_change_report;
 mod test_resolve_command;
 mod test_restore_command;
@@ -75,10 +89,16 @@ mod test_simplify_parents_command;
 mod test_sparse_command;
 mod test_split_command;
 mod test_squash_command;
+mod test_stack_submit;
+mod test_stash_command;
 mod test_status_command;
 mod test_tag_command;
 mod test_templater;
+mod test_tui_command;
 mod test_undo_redo_commands;
 mod test_util_command;
+mod test_verify_command;
+#[cfg(feature = "web")]
+mod test_web_command;
 mod test_working_copy;
 mod test_workspaces;
@@ -206,6 +206,41 @@ fn test_abandon_many() {
     ");
 }
 
+#[test]
+fn test_abandon_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = "description.first_line()""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &[]);
+    let setup_log = get_log_output(&work_dir);
+    let setup_op_log = work_dir.run_jj(["op", "log"]);
+
+    let output = work_dir.run_jj(["abandon", "--dry-run", "b"]);
+    insta::assert_snapshot!(output, @r"
+    Changed commits:
+    ○  - b
+
+    Changed local bookmarks:
+    b:
+    + (absent)
+    - b
+    [EOF]
+    ------- stderr -------
+    Would abandon 1 commits:
+      b
+    Would delete bookmarks: b
+    Dry run: no changes were made.
+    [EOF]
+    ");
+    // Nothing changed, and no operation was recorded
+    assert_eq!(get_log_output(&work_dir), setup_log);
+    assert_eq!(work_dir.run_jj(["op", "log"]), setup_op_log);
+}
+
 // This behavior illustrates https://github.com/jj-vcs/jj/issues/2600.
 // See also the corresponding test in `test_rebase_command`
 #[test]
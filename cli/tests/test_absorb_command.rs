@@ -687,6 +687,60 @@ fn test_absorb_file_mode() {
     ");
 }
 
+#[test]
+fn test_absorb_file_changes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m1"]).success();
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file1", "1a\n");
+    work_dir.write_file("file2", "");
+
+    work_dir.run_jj(["new", "-m2"]).success();
+    work_dir.create_dir("other");
+    work_dir.write_file("other/file1", "2a\n");
+
+    // Added files go to the commit that modified the directory last, deletion
+    // and mode change go to the commit that modified the file last. Added
+    // files at the root directory aren't absorbed.
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("dir/file3", "3a\n");
+    work_dir.write_file("file4", "3a\n");
+    work_dir.write_file("other/file3", "3a\n");
+    work_dir.remove_file("file2");
+    work_dir
+        .run_jj(["file", "chmod", "x", "dir/file1"])
+        .success();
+    work_dir.run_jj(["absorb", "--file-changes"]).success();
+
+    let template = r#"description.first_line() ++ "\n""#;
+    let output = work_dir.run_jj(["log", "-r=mutable()", "--no-graph", "-T", template, "-s"]);
+    insta::assert_snapshot!(output, @r"
+
+    A file4
+    2
+    A other/file1
+    A other/file3
+    1
+    A dir/file1
+    A dir/file3
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-r=description(1)", "--git", "dir/file1"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/dir/file1 b/dir/file1
+    new file mode 100755
+    index 0000000000..a8994dc188
+    --- /dev/null
+    +++ b/dir/file1
+    @@ -0,0 +1,1 @@
+    +1a
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_absorb_from_into() {
     let test_env = TestEnvironment::default();
@@ -297,6 +297,37 @@ fn test_alias_invalid_definition() {
     ");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_alias_shell_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    test_env.add_config(
+        r#"[aliases]
+    greet = '!echo "hello $1 from $0"; echo "$@"; echo "$JJ_CHANGE_ID"'
+    plain = 'log'
+    "#,
+    );
+    let output = work_dir.run_jj(["greet", "world", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    hello world from greet
+    world -r @
+    qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["plain"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Alias `plain` must be an array of strings, or a string starting with `!` to run a shell command
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_alias_in_repo_config() {
     let test_env = TestEnvironment::default();
@@ -0,0 +1,256 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use indoc::formatdoc;
+use itertools::Itertools as _;
+
+use crate::common::TestEnvironment;
+
+fn set_up_fake_annotator(test_env: &TestEnvironment, name: &str, args: &[&str]) {
+    let formatter_path = assert_cmd::cargo::cargo_bin!("fake-formatter");
+    assert!(formatter_path.is_file());
+    test_env.add_config(formatdoc! {"
+        [annotators.{name}]
+        command = {command}
+        ",
+        command = toml_edit::Value::from_iter(
+            [formatter_path.to_str().unwrap()]
+                .iter()
+                .chain(args)
+                .copied()
+        )
+    });
+}
+
+fn count_calls(path: &Path) -> usize {
+    std::fs::read_to_string(path).map_or(0, |calls| calls.lines().count())
+}
+
+#[test]
+fn test_annotation_template() {
+    let test_env = TestEnvironment::default();
+    let calls_path = test_env.env_root().join("calls");
+    set_up_fake_annotator(
+        &test_env,
+        "ci",
+        &["--stdout", "passed", "--tee", calls_path.to_str().unwrap()],
+    );
+    set_up_fake_annotator(&test_env, "multi", &["--stdout", "  first  \nsecond"]);
+    set_up_fake_annotator(&test_env, "id", &["--stdout", "$change_id"]);
+    let bad_calls_path = test_env.env_root().join("bad-calls");
+    set_up_fake_annotator(
+        &test_env,
+        "bad",
+        &[
+            "--stdout",
+            "bad\n",
+            "--fail",
+            "--tee",
+            bad_calls_path.to_str().unwrap(),
+        ],
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let template = r#"self.annotation("ci") ++ "\n""#;
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    passed
+    [EOF]
+    ");
+    assert_eq!(count_calls(&calls_path), 1);
+
+    // The annotation is cached
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    passed
+    [EOF]
+    ");
+    assert_eq!(count_calls(&calls_path), 1);
+
+    // Only the first line of the output is used, without surrounding whitespace
+    let template = r#""[" ++ self.annotation("multi") ++ "]\n""#;
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    [first]
+    [EOF]
+    ");
+
+    // Variables in the command are replaced with the IDs of the commit
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", r#"self.annotation("id")"#]);
+    let expected = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", "change_id"]);
+    assert_eq!(output.stdout.raw(), expected.stdout.raw());
+
+    // Unknown annotators produce an empty annotation
+    let template = r#""[" ++ self.annotation("unknown") ++ "]\n""#;
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    []
+    [EOF]
+    ");
+
+    // Failures are rendered in place of the annotation, and are cached too
+    let template = r#"self.annotation("bad") ++ "\n""#;
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    <Error: Annotator bad failed>
+    [EOF]
+    ");
+    assert_eq!(count_calls(&bad_calls_path), 1);
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    <Error: Annotator bad failed>
+    [EOF]
+    ");
+    assert_eq!(count_calls(&bad_calls_path), 1);
+
+    // The name must be a string literal
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "--no-graph",
+        "-T",
+        "self.annotation(description)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse template: Expected string literal
+    Caused by:  --> 1:17
+      |
+    1 | self.annotation(description)
+      |                 ^---------^
+      |
+      = Expected string literal
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_annotation_cache_invalidation() {
+    let test_env = TestEnvironment::default();
+    let calls_path = test_env.env_root().join("calls");
+    set_up_fake_annotator(
+        &test_env,
+        "ci",
+        &["--stdout", "passed\n", "--tee", calls_path.to_str().unwrap()],
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let cache_dir = work_dir.root().join(".jj/repo/annotations");
+    let list_dir = |dir: &Path| -> Vec<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .sorted()
+            .collect()
+    };
+
+    let template = r#"self.annotation("ci") ++ "\n""#;
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    passed
+    [EOF]
+    ");
+    assert_eq!(count_calls(&calls_path), 1);
+    let [command_hash] = &list_dir(&cache_dir.join("ci"))[..] else {
+        panic!("expected a single command directory");
+    };
+
+    std::fs::create_dir_all(cache_dir.join("removed").join(command_hash)).unwrap();
+
+    // Changing the command invalidates the cached annotations
+    let config = format!(
+        "annotators.ci.command={}",
+        toml_edit::Value::from_iter([
+            assert_cmd::cargo::cargo_bin!("fake-formatter")
+                .to_str()
+                .unwrap(),
+            "--stdout",
+            "failed\n",
+            "--tee",
+            calls_path.to_str().unwrap(),
+        ])
+    );
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "--no-graph",
+        "-T",
+        template,
+        "--config",
+        &config,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    failed
+    [EOF]
+    ");
+    assert_eq!(count_calls(&calls_path), 2);
+
+    // The entries of the old command and of removed annotators are pruned
+    assert_eq!(list_dir(&cache_dir), ["ci"]);
+    let command_hashes = list_dir(&cache_dir.join("ci"));
+    assert_eq!(command_hashes.len(), 1);
+    assert_ne!(&command_hashes[0], command_hash);
+}
+
+#[test]
+fn test_status_annotations() {
+    let test_env = TestEnvironment::default();
+    set_up_fake_annotator(&test_env, "ci", &["--stdout", "passed"]);
+    set_up_fake_annotator(&test_env, "empty", &["--stdout", ""]);
+    set_up_fake_annotator(&test_env, "bad", &["--fail"]);
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "base"]).success();
+    work_dir.run_jj(["new", "-m", "wip"]).success();
+
+    // Annotators are listed in sorted order, and empty annotations are omitted
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : wip
+    Warning: Annotator bad failed
+      ci: passed
+    Parent commit (@-): base
+    Warning: Annotator bad failed
+      ci: passed
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_annotator_timeout() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+        [annotators.slow]
+        command = ["sleep", "10"]
+        timeout-secs = 1
+        "#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let template = r#"self.annotation("slow") ++ "\n""#;
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    <Error: Annotator slow timed out>
+    [EOF]
+    ");
+}
@@ -0,0 +1,184 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::Value;
+use serde_json::json;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+#[must_use]
+fn serve_stdio(work_dir: &TestWorkDir, requests: &[Value]) -> Vec<Value> {
+    let input = requests
+        .iter()
+        .map(|request| format!("{request}\n"))
+        .collect::<String>();
+    let output = work_dir
+        .run_jj_with(|cmd| cmd.args(["api", "serve", "--stdio"]).write_stdin(input))
+        .success();
+    output
+        .stdout
+        .raw()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_api_serve_queries_and_mutations() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\nb\n");
+
+    let responses = serve_stdio(
+        &work_dir,
+        &[
+            json!({"jsonrpc": "2.0", "id": 1, "method": "status"}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "describe",
+                   "params": {"message": "add file"}}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "new"}),
+            json!({"jsonrpc": "2.0", "id": 4, "method": "log",
+                   "params": {"revisions": "::@ ~ root()"}}),
+            json!({"jsonrpc": "2.0", "id": 5, "method": "annotate",
+                   "params": {"path": "file"}}),
+        ],
+    );
+    assert_eq!(responses.len(), 5);
+    assert_eq!(
+        responses[0]["result"]["changes"],
+        json!([{"path": "file", "status": "added"}])
+    );
+    assert_eq!(responses[0]["result"]["conflicts"], json!([]));
+    let described = &responses[1]["result"];
+    assert_eq!(described["description"], "add file\n");
+    let new_commit = &responses[2]["result"];
+    assert_eq!(new_commit["parents"], json!([described["commit_id"]]));
+    let log = responses[3]["result"].as_array().unwrap();
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0]["commit_id"], new_commit["commit_id"]);
+    assert_eq!(log[1]["description"], "add file\n");
+    let annotation = responses[4]["result"].as_array().unwrap();
+    assert_eq!(annotation.len(), 2);
+    assert_eq!(annotation[0]["commit_id"], described["commit_id"]);
+    assert_eq!(annotation[1]["content"], "b\n");
+
+    // The mutations were recorded as operations
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "-n2",
+        "-Tdescription.first_line() ++ \"\\n\"",
+    ]);
+    // The description names the commit before it was rewritten
+    let snapshot_id = responses[0]["result"]["working_copy"]["commit_id"]
+        .as_str()
+        .unwrap();
+    insta::assert_snapshot!(output.normalize_stdout_with(|s| s.replace(snapshot_id, "<ID>")), @r"
+    new empty commit
+    describe commit <ID>
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_api_serve_errors() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let input = [
+        "not json",
+        r#"{"jsonrpc": "2.0", "id": 1}"#,
+        r#"{"jsonrpc": "2.0", "id": 2, "method": "frobnicate"}"#,
+        r#"{"jsonrpc": "2.0", "id": 3, "method": "log", "params": {"revset": "@"}}"#,
+        r#"{"jsonrpc": "2.0", "id": 4, "method": "diff", "params": {"revision": "nonexistent"}}"#,
+        r#"{"jsonrpc": "2.0", "method": "new"}"#,
+    ]
+    .join("\n");
+    let output =
+        work_dir.run_jj_with(|cmd| cmd.args(["api", "serve", "--stdio"]).write_stdin(input));
+    insta::assert_snapshot!(output.stdout, @r#"
+    {"error":{"code":-32700,"message":"expected ident at line 1 column 2"},"id":null,"jsonrpc":"2.0"}
+    {"error":{"code":-32600,"message":"missing field `method` at line 1 column 27"},"id":null,"jsonrpc":"2.0"}
+    {"error":{"code":-32601,"message":"Unknown method: frobnicate"},"id":2,"jsonrpc":"2.0"}
+    {"error":{"code":-32602,"message":"unknown field `revset`, expected `revisions` or `limit`"},"id":3,"jsonrpc":"2.0"}
+    {"error":{"code":-32000,"message":"Revision `nonexistent` doesn't exist"},"id":4,"jsonrpc":"2.0"}
+    [EOF]
+    "#);
+
+    // Notifications are processed without a response
+    let output = work_dir.run_jj(["log", "-r::@", "-Tjson(description) ++ \"\\n\""]);
+    insta::assert_snapshot!(output, @r#"
+    @  ""
+    ○  ""
+    ◆  ""
+    [EOF]
+    "#);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_api_serve_socket_concurrent_connections() {
+    use std::io::BufRead as _;
+    use std::io::BufReader;
+    use std::io::Write as _;
+    use std::os::unix::net::UnixStream;
+    use std::process::Stdio;
+
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let jj_cmd = test_env.new_jj_cmd();
+    let mut cmd = std::process::Command::new(jj_cmd.get_program());
+    cmd.env_clear();
+    for (key, value) in jj_cmd.get_envs() {
+        if let Some(value) = value {
+            cmd.env(key, value);
+        }
+    }
+    let socket_path = test_env.env_root().join("api.sock");
+    let mut child = cmd
+        .current_dir(work_dir.root())
+        .args(["api", "serve", "--socket"])
+        .arg(&socket_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut line = String::new();
+    BufReader::new(child.stderr.take().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+    assert!(line.starts_with("Listening on "), "{line}");
+
+    // An idle client doesn't block the others
+    let _idle_stream = UnixStream::connect(&socket_path).unwrap();
+    let mut stream = UnixStream::connect(&socket_path).unwrap();
+    writeln!(
+        stream,
+        r#"{{"jsonrpc": "2.0", "id": 1, "method": "status"}}"#
+    )
+    .unwrap();
+    let mut response = String::new();
+    BufReader::new(&stream).read_line(&mut response).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["id"], 1);
+    assert!(response["result"].is_object(), "{response}");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
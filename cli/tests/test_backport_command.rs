@@ -0,0 +1,147 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_backport() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let log_template = r#"
+        separate(" ",
+          bookmarks,
+          description.first_line(),
+          trailers.map(|t| t.key()),
+          if(conflict, "(conflict)"),
+        ) ++ "\n"
+    "#;
+
+    work_dir.write_file("file", "a\n");
+    work_dir.write_file("other", "x\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "release-1", "-r@-"])
+        .success();
+    work_dir.write_file("other", "y\n");
+    work_dir.run_jj(["commit", "-m", "release 2"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "release-2", "-r@-"])
+        .success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["commit", "-m", "fix"]).success();
+    work_dir.write_file("other", "z\n");
+    work_dir.run_jj(["commit", "-m", "other fix"]).success();
+
+    let output = work_dir.run_jj(["backport", "-r@--", "-brelease-1", "-brelease-2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Backported 1 commits onto release-1:
+      fix
+    Backported 1 commits onto release-2:
+      fix
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "-r=release-1 | release-2",
+        "--no-graph",
+        "-T",
+        log_template,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    release-2 fix Backported-from
+    release-1 fix Backported-from
+    [EOF]
+    ");
+    // The trailer records the change ID of the original revision
+    let original_change_id = work_dir
+        .run_jj(["log", "--no-graph", "-r=@--", "-T=change_id"])
+        .success()
+        .stdout
+        .into_raw();
+    let trailer_value = work_dir
+        .run_jj([
+            "log",
+            "--no-graph",
+            "-r=release-1",
+            "-T=trailers.map(|t| t.value())",
+        ])
+        .success()
+        .stdout
+        .into_raw();
+    assert_eq!(trailer_value, original_change_id);
+
+    // Conflicts are reported for each bookmark
+    let output = work_dir.run_jj(["backport", "-r@-", "-brelease-1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Backported 1 commits onto release-1:
+      other fix
+    Warning: There are unresolved conflicts in 1 commits backported onto release-1.
+    New conflicts appeared in 1 commits:
+      other fix
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new kmkuslsw
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r=release-1", "--no-graph", "-T", log_template]);
+    insta::assert_snapshot!(output, @r"
+    release-1 other fix Backported-from (conflict)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["backport", "-r@-", "-bnope"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such bookmark: nope
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_backport_empty_description() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["bookmark", "create", "release", "-r@"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["backport", "-r@", "-brelease"]).success();
+
+    // The description consists of just the trailer
+    let original_change_id = work_dir
+        .run_jj(["log", "--no-graph", "-r=@", "-T=change_id"])
+        .success()
+        .stdout
+        .into_raw();
+    let description = work_dir
+        .run_jj(["log", "--no-graph", "-r=release", "-T=description"])
+        .success()
+        .stdout
+        .into_raw();
+    assert_eq!(
+        description,
+        format!("Backported-from: {original_change_id}\n")
+    );
+}
@@ -786,6 +786,157 @@ fn test_bookmark_forget_glob() {
     ");
 }
 
+#[test]
+fn test_bookmark_protected() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"bookmarks.protected = ["main", "glob:release/*"]"#);
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m=first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main", "release/1", "feature"])
+        .success();
+    work_dir.run_jj(["new", "-m=second"]).success();
+
+    // Moving protected bookmarks forward is fine
+    work_dir
+        .run_jj(["bookmark", "move", "main", "--to=@"])
+        .success();
+
+    let output = work_dir.run_jj(["bookmark", "move", "main", "--to=@-", "--allow-backwards"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to move protected bookmark backwards or sideways: main
+    Hint: Use --allow-protected to allow it.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["bookmark", "set", "main", "-r@-", "--allow-backwards"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to move protected bookmark backwards or sideways: main
+    Hint: Use --allow-protected to allow it.
+    [EOF]
+    [exit status: 1]
+    ");
+    work_dir
+        .run_jj([
+            "bookmark",
+            "move",
+            "main",
+            "--to=@-",
+            "--allow-backwards",
+            "--allow-protected",
+        ])
+        .success();
+
+    let output = work_dir.run_jj(["bookmark", "delete", "glob:*"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to delete protected bookmark: main
+    Hint: Use --allow-protected to allow it.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["bookmark", "delete", "feature"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Deleted 1 bookmarks.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "delete", "--allow-protected", "release/1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Deleted 1 bookmarks.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_archive() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let template = r#"separate(" ", bookmarks, description.first_line(), if(root, "root"))"#;
+
+    work_dir.run_jj(["describe", "-m=feature1"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "feature1"])
+        .success();
+    work_dir.run_jj(["new", "root()", "-m=feature2"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "feature2"])
+        .success();
+    work_dir.run_jj(["new", "root()", "-m=work"]).success();
+
+    let output = work_dir.run_jj(["bookmark", "archive", "feature1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Archived 1 bookmarks.
+    [EOF]
+    ");
+
+    // Archived bookmarks are hidden from the default log and listing
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  work
+    │ ○  feature2 feature2
+    ├─╯
+    ◆  root
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    feature2
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "--archived", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    feature1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "feature1", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    feature1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r", "archived_bookmarks()", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    ○  feature1 feature1
+    │
+    ~
+    [EOF]
+    ");
+
+    // Archiving again is a no-op
+    let output = work_dir.run_jj(["bookmark", "archive", "feature1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Bookmark already archived: feature1
+    Nothing changed.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["bookmark", "unarchive", "feature1", "feature2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Bookmark not archived: feature2
+    Unarchived 1 bookmarks.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  work
+    │ ○  feature2 feature2
+    ├─╯
+    │ ○  feature1 feature1
+    ├─╯
+    ◆  root
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_delete_glob() {
     // Set up a git repo with a bookmark and a jj repo that has it as a remote.
@@ -0,0 +1,79 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_changelog() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let commit = |file: &str, message: &[&str]| {
+        work_dir.write_file(file, file);
+        let mut args = vec!["commit"];
+        for paragraph in message {
+            args.extend(["-m", *paragraph]);
+        }
+        work_dir.run_jj(args).success();
+    };
+
+    commit("a", &["feat: released feature"]);
+    work_dir.run_jj(["tag", "set", "-r@-", "v1.0.0"]).success();
+    commit("b", &["feat(cli): add a command"]);
+    commit("c", &["fix: fix a crash", "Fixes: #123"]);
+    commit("d", &["docs: update the tutorial"]);
+    commit("e", &["refactor!: rename things"]);
+    commit("f", &["Update README"]);
+
+    // The revisions since the last tag are included by default
+    let output = work_dir.run_jj(["changelog"]);
+    insta::assert_snapshot!(output, @r"
+    ### New features
+
+    * add a command
+
+    ### Fixed bugs
+
+    * fix a crash (#123)
+
+    ### Other changes
+
+    * Update README
+    * rename things
+    * update the tutorial
+    [EOF]
+    ");
+
+    // Revisions that don't belong to any group are omitted
+    test_env.add_config(r#"changelog.groups = [{ title = "Fixes", types = ["FIX"] }]"#);
+    let output = work_dir.run_jj([
+        "changelog",
+        "-r=all()",
+        "-T",
+        r#"description.first_line() ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ### Fixes
+
+    fix: fix a crash
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["changelog", "-r=none()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No revisions to include in the changelog.
+    [EOF]
+    ");
+}
@@ -209,6 +209,7 @@ fn test_bookmark_names() {
     --ignore-immutable	Allow rewriting immutable commits
     --at-operation	Operation to load the repo at
     --debug	Enable debug logging
+    --trace-output	Write a performance trace to the given file
     --color	When to colorize output
     --quiet	Silence non-primary command output
     --no-pager	Disable the pager
@@ -434,6 +435,53 @@ fn test_completions_are_generated() {
     ");
 }
 
+#[test]
+fn test_nushell_completion() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "aaa"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "abc"])
+        .success();
+
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.env("COMPLETE", "nushell")
+            .args(["--", "jj", "bookmark", "rename", "a"])
+    });
+    insta::assert_snapshot!(output, @r#"
+    [{"value":"aaa","description":"first"},{"value":"abc","description":"first"}]
+    [EOF]
+    "#);
+
+    test_env.add_env_var("COMPLETE", "nushell");
+    let mut insta_settings = insta::Settings::clone_current();
+    insta_settings.add_filter(r"(run-external) r#'.*'#", "$1 .."); // omit path to jj binary
+    let _guard = insta_settings.bind_to_scope();
+
+    let output = test_env.run_jj_in(".", [""; 0]);
+    insta::assert_snapshot!(output, @r#"
+    do --env {
+        let jj_completer = {|spans: list<string>|
+            COMPLETE=nushell run-external .. "--" ...$spans | from json
+        }
+        let fallback_completer = $env.config.completions.external.completer?
+        $env.config.completions.external.enable = true
+        $env.config.completions.external.completer = {|spans: list<string>|
+            if ($spans | first) == r#'jj'# {
+                do $jj_completer $spans
+            } else if $fallback_completer != null {
+                do $fallback_completer $spans
+            }
+        }
+    }
+    [EOF]
+    "#);
+}
+
 #[test_case(Shell::Bash; "bash")]
 #[test_case(Shell::Zsh; "zsh")]
 #[test_case(Shell::Fish; "fish")]
@@ -1081,7 +1129,8 @@ fn test_config() {
 
     let output = test_env.complete_fish(["config", "get", "f"]);
     insta::assert_snapshot!(output, @r"
-    fsmonitor.backend	Whether to use an external filesystem monitor, useful for large repos
+    fsmonitor.backend	Which filesystem monitor to use, if any, useful for large repos
+    fsmonitor.builtin.auto-snapshot	Whether the built-in monitor should snapshot the working copy in the background once changes settle.
     fsmonitor.watchman.register-snapshot-trigger	Whether to use triggers to monitor for changes in the background.
     [EOF]
     ");
@@ -1089,7 +1138,9 @@ fn test_config() {
     let output = test_env.complete_fish(["config", "list", "fs"]);
     insta::assert_snapshot!(output, @r"
     fsmonitor	External filesystem monitor settings, useful for large repos
-    fsmonitor.backend	Whether to use an external filesystem monitor, useful for large repos
+    fsmonitor.backend	Which filesystem monitor to use, if any, useful for large repos
+    fsmonitor.builtin
+    fsmonitor.builtin.auto-snapshot	Whether the built-in monitor should snapshot the working copy in the background once changes settle.
     fsmonitor.watchman
     fsmonitor.watchman.register-snapshot-trigger	Whether to use triggers to monitor for changes in the background.
     [EOF]
@@ -1097,7 +1148,8 @@ fn test_config() {
 
     let output = test_env.complete_fish(["log", "--config", "f"]);
     insta::assert_snapshot!(output, @r"
-    fsmonitor.backend=	Whether to use an external filesystem monitor, useful for large repos
+    fsmonitor.backend=	Which filesystem monitor to use, if any, useful for large repos
+    fsmonitor.builtin.auto-snapshot=	Whether the built-in monitor should snapshot the working copy in the background once changes settle.
     fsmonitor.watchman.register-snapshot-trigger=	Whether to use triggers to monitor for changes in the background.
     [EOF]
     ");
@@ -1550,6 +1602,16 @@ fn test_files() {
         [EOF]
         ");
 
+    // `--revisions` is accepted as well as `-r`
+    let output = work_dir.complete_fish(["diff", "--revisions", "@-", "f_dir/"]);
+    insta::assert_snapshot!(output, @r"
+    f_dir/dir_file_1	Added
+    f_dir/dir_file_2	Added
+    f_dir/dir_file_3	Added
+    f_dir/f_renamed_3	Renamed
+    [EOF]
+    ");
+
     let output = work_dir.complete_fish(["diff", "--from", "root()", "--to", "@-", "f_"]);
     insta::assert_snapshot!(output, @r"
     f_added	Added
@@ -1592,6 +1654,18 @@ fn test_files() {
     [EOF]
     ");
 
+    let output = work_dir.complete_fish(["restore", "--from", "root()", "--into", "first", "f_"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    f_deleted	Added
+    f_modified	Added
+    f_not_yet_copied	Added
+    f_not_yet_renamed	Added
+    f_not_yet_renamed_2	Added
+    f_not_yet_renamed_3	Added
+    f_unchanged	Added
+    [EOF]
+    ");
+
     // interdiff has a different behavior with --from and --to flags
     let output = work_dir.complete_fish([
         "interdiff",
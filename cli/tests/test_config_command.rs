@@ -655,6 +655,191 @@ fn test_config_set_for_user() {
     "#);
 }
 
+#[test]
+fn test_config_migrate_from_git() {
+    let mut test_env = TestEnvironment::default();
+    let user_config_path = test_env.config_path().join("config.toml");
+    test_env.set_config_path(&user_config_path);
+    let git_config_path = test_env.env_root().join("gitconfig");
+    std::fs::write(
+        &git_config_path,
+        indoc! {r#"
+            [user]
+                name = Git User
+                email = git.user@example.com
+            [core]
+                editor = vim
+            [merge]
+                tool = meld
+            [mergetool "meld"]
+                cmd = meld "$LOCAL" "$BASE" "$REMOTE" --output "$MERGED"
+            [alias]
+                st = status
+                lg = !git log --graph
+                co = checkout
+        "#},
+    )
+    .unwrap();
+    test_env.add_env_var("GIT_CONFIG_GLOBAL", &git_config_path);
+
+    let output = test_env.run_jj_in(".", ["config", "migrate", "--from-git", "--user"]);
+    insta::assert_snapshot!(output, @r#"
+    Imported from Git config:
+      user.name -> user.name = "Git User"
+      user.email -> user.email = "git.user@example.com"
+      core.editor -> ui.editor = "vim"
+      mergetool.meld.cmd -> merge-tools.meld.program = "meld"
+      mergetool.meld.cmd -> merge-tools.meld.merge-args = ["$left", "$base", "$right", "--output", "$output"]
+      merge.tool -> ui.merge-editor = "meld"
+      alias.st -> aliases.st = ["status"]
+      alias.lg -> aliases.lg = '!git log --graph "$@"'
+    Not imported:
+      alias.co: `git checkout` has no direct jj equivalent
+    [EOF]
+    "#);
+    let user_config_toml = std::fs::read_to_string(&user_config_path).unwrap();
+    insta::assert_snapshot!(user_config_toml, @r#"
+    #:schema https://jj-vcs.github.io/jj/latest/config-schema.json
+
+    [user]
+    name = "Git User"
+    email = "git.user@example.com"
+
+    [ui]
+    editor = "vim"
+    merge-editor = "meld"
+
+    [merge-tools.meld]
+    program = "meld"
+    merge-args = ["$left", "$base", "$right", "--output", "$output"]
+
+    [aliases]
+    st = ["status"]
+    lg = '!git log --graph "$@"'
+    "#);
+
+    // Settings which are already set are left unchanged
+    let output = test_env.run_jj_in(
+        ".",
+        ["config", "migrate", "--from-git", "--user", "--dry-run"],
+    );
+    insta::assert_snapshot!(output, @r"
+    Nothing imported from Git config.
+    Not imported:
+      alias.co: `git checkout` has no direct jj equivalent
+      user.name: `user.name` is already set
+      user.email: `user.email` is already set
+      core.editor: `ui.editor` is already set
+      mergetool.meld.cmd: `merge-tools.meld.program` is already set
+      mergetool.meld.cmd: `merge-tools.meld.merge-args` is already set
+      merge.tool: `ui.merge-editor` is already set
+      alias.st: `aliases.st` is already set
+      alias.lg: `aliases.lg` is already set
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_config_doctor() {
+    let mut test_env = TestEnvironment::default();
+    let user_config_path = test_env.config_path().join("config.toml");
+    test_env.set_config_path(&user_config_path);
+    std::fs::write(
+        &user_config_path,
+        indoc! {r#"
+            ui.colour = "never"
+            ui.graph.style = "fancy"
+            ui.log-word-wrap = "yes"
+
+            [revset-aliases]
+            'badfn(a, a)' = 'a'
+            'mine_or_trunk' = 'mine() - trunk()'
+
+            [core]
+            fsmonitor = "none"
+        "#},
+    )
+    .unwrap();
+
+    let output = test_env.run_jj_in(".", ["config", "doctor"]);
+    insta::assert_snapshot!(output, @r#"
+    $TEST_ENV/config/config.toml: Deprecated: core.fsmonitor is renamed to fsmonitor.backend
+    $TEST_ENV/config/config.toml:1: Unknown key `ui.colour`. Did you mean `ui.color`?
+    $TEST_ENV/config/config.toml:2: `ui.graph.style`: Invalid value "fancy", expected one of "curved", "square", "ascii", "ascii-large"
+    $TEST_ENV/config/config.toml:3: `ui.log-word-wrap`: Expected a boolean, but is string
+    $TEST_ENV/config/config.toml:6: Failed to parse `revset-aliases."badfn(a, a)"`:  --> 1:7
+      |
+    1 | badfn(a, a)
+      |       ^--^
+      |
+      = Redefinition of function parameter
+    $TEST_ENV/config/config.toml:7: Failed to parse `revset-aliases.mine_or_trunk`:  --> 1:8
+      |
+    1 | mine() - trunk()
+      |        ^
+      |
+      = `-` is not an infix operator
+    [EOF]
+    ------- stderr -------
+    Warning: Deprecated user-level config: core.fsmonitor is renamed to fsmonitor.backend
+    Found 6 problem(s).
+    Hint: Run `jj config doctor --fix` to rename the deprecated config options.
+    [EOF]
+    "#);
+
+    // Deprecated names are renamed in place
+    let output = test_env.run_jj_in(".", ["config", "doctor", "--fix"]);
+    insta::assert_snapshot!(output, @r#"
+    $TEST_ENV/config/config.toml: Fixed: core.fsmonitor is renamed to fsmonitor.backend
+    $TEST_ENV/config/config.toml:1: Unknown key `ui.colour`. Did you mean `ui.color`?
+    $TEST_ENV/config/config.toml:2: `ui.graph.style`: Invalid value "fancy", expected one of "curved", "square", "ascii", "ascii-large"
+    $TEST_ENV/config/config.toml:3: `ui.log-word-wrap`: Expected a boolean, but is string
+    $TEST_ENV/config/config.toml:6: Failed to parse `revset-aliases."badfn(a, a)"`:  --> 1:7
+      |
+    1 | badfn(a, a)
+      |       ^--^
+      |
+      = Redefinition of function parameter
+    $TEST_ENV/config/config.toml:7: Failed to parse `revset-aliases.mine_or_trunk`:  --> 1:8
+      |
+    1 | mine() - trunk()
+      |        ^
+      |
+      = `-` is not an infix operator
+    [EOF]
+    ------- stderr -------
+    Warning: Deprecated user-level config: core.fsmonitor is renamed to fsmonitor.backend
+    Found 5 problem(s).
+    [EOF]
+    "#);
+    let user_config_toml = std::fs::read_to_string(&user_config_path).unwrap();
+    insta::assert_snapshot!(user_config_toml, @r#"
+    ui.colour = "never"
+    ui.graph.style = "fancy"
+    ui.log-word-wrap = "yes"
+
+    [revset-aliases]
+    'badfn(a, a)' = 'a'
+    'mine_or_trunk' = 'mine() - trunk()'
+
+    [core]
+
+    [fsmonitor]
+    backend = "none"
+    "#);
+}
+
+#[test]
+fn test_config_doctor_no_problems() {
+    let test_env = TestEnvironment::default();
+    let output = test_env.run_jj_in(".", ["config", "doctor"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No problems found.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_config_set_for_user_directory() {
     let test_env = TestEnvironment::default();
@@ -1701,6 +1886,46 @@ fn test_config_conditional_without_home_dir() {
     ");
 }
 
+#[test]
+fn test_config_conditional_remotes() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let user_config_path = test_env.env_root().join("config.toml");
+    test_env.set_config_path(&user_config_path);
+    std::fs::write(
+        &user_config_path,
+        indoc! {"
+            foo = 'global'
+            [[--scope]]
+            --when.remotes = ['*example.com[:/]work/*']
+            foo = 'work'
+        "},
+    )
+    .unwrap();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["config", "get", "foo"]);
+    insta::assert_snapshot!(output, @r"
+    global
+    [EOF]
+    ");
+
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "origin",
+            "git@example.com:work/repo.git",
+        ])
+        .success();
+    let output = work_dir.run_jj(["config", "get", "foo"]);
+    insta::assert_snapshot!(output, @r"
+    work
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_config_show_paths() {
     let test_env = TestEnvironment::default();
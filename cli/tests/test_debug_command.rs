@@ -147,6 +147,97 @@ fn test_debug_revset() {
     ");
 }
 
+#[test]
+fn test_debug_revset_explain() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let mut insta_settings = insta::Settings::clone_current();
+    insta_settings.add_filter(r"(?m)(^    .*\n)+", "    ..\n");
+    insta_settings.add_filter(r"time=[^)]+", "time=[TIME]");
+    let _guard = insta_settings.bind_to_scope();
+
+    let output = work_dir.run_jj(["debug", "revset", "--explain", "root()"]);
+    assert_snapshot!(output, @r"
+    -- Parsed:
+    Root
+
+    -- Resolved:
+    Root
+
+    -- Optimized:
+    Root
+
+    -- Backend:
+    Commits(
+        ..
+    )
+
+    -- Explain:
+    Commits [1] (strategy=EagerRevset, rows=1, time=[TIME])
+
+    -- Evaluated:
+    RevsetImpl {
+        ..
+    }
+
+    -- Commit IDs:
+    0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["debug", "revset", "--explain", "::@ ~ root()"]);
+    assert_snapshot!(output, @r"
+    -- Parsed:
+    Difference(
+        ..
+    )
+
+    -- Resolved:
+    Difference(
+        ..
+    )
+
+    -- Optimized:
+    WithinReference {
+        ..
+    }
+
+    -- Backend:
+    Difference(
+        ..
+    )
+
+    -- Explain:
+    Difference (strategy=DifferenceRevset, rows=1, time=[TIME])
+      Ancestors generation=0..18446744073709551615 parents=0..4294967295 (strategy=RevWalkRevset, rows=2, time=[TIME])
+        ..
+      Commits [1] (strategy=EagerRevset, rows=1, time=[TIME])
+
+    -- Evaluated:
+    RevsetImpl {
+        ..
+    }
+
+    -- Commit IDs:
+    e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["debug", "revset", "--explain", "--no-resolve", "root()"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--explain' cannot be used with '--no-resolve'
+
+    Usage: jj debug revset --explain <REVISION>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_debug_index() {
     let test_env = TestEnvironment::default();
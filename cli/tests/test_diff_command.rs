@@ -3569,6 +3569,80 @@ fn test_diff_external_tool_conflict_marker_style() {
     ");
 }
 
+#[test]
+fn test_diff_conflict_marker_style_per_path() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(indoc! {r#"
+        [ui.conflict-marker-styles.git]
+        patterns = ["glob:'*.git'"]
+        style = "git"
+    "#});
+
+    let files = [("file.git", "base\n"), ("file.txt", "base\n")];
+    create_commit_with_files(&work_dir, "base", &[], &files);
+    let files = [("file.git", "a\n"), ("file.txt", "a\n")];
+    create_commit_with_files(&work_dir, "a", &["base"], &files);
+    let files = [("file.git", "b\n"), ("file.txt", "b\n")];
+    create_commit_with_files(&work_dir, "b", &["base"], &files);
+    let files = [("file.git", "resolved\n"), ("file.txt", "resolved\n")];
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &files);
+
+    // The conflicts resolved in the working copy are rendered in the style
+    // configured for each file
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file.git b/file.git
+    index 0000000000..2ab19ae607 100644
+    --- a/file.git
+    +++ b/file.git
+    @@ -1,7 +1,1 @@
+    -<<<<<<< Side #1 (Conflict 1 of 1)
+    -a
+    -||||||| Base
+    -base
+    -=======
+    -b
+    ->>>>>>> Side #2 (Conflict 1 of 1 ends)
+    +resolved
+    diff --git a/file.txt b/file.txt
+    index 0000000000..2ab19ae607 100644
+    --- a/file.txt
+    +++ b/file.txt
+    @@ -1,7 +1,1 @@
+    -<<<<<<< Conflict 1 of 1
+    -%%%%%%% Changes from base to side #1
+    --base
+    -+a
+    -+++++++ Contents of side #2
+    -b
+    ->>>>>>> Conflict 1 of 1 ends
+    +resolved
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--color-words"]);
+    insta::assert_snapshot!(output, @r"
+    Resolved conflict in file.git:
+       1     : <<<<<<< Side #1 (Conflict 1 of 1)
+       2     : a
+       3     : ||||||| Base
+       4     : base
+       5     : =======
+       6     : b
+       7    1: >>>>>>> Side #2 (Conflict 1 of 1 ends)resolved
+    Resolved conflict in file.txt:
+       1     : <<<<<<< Conflict 1 of 1
+       2     : %%%%%%% Changes from base to side #1
+       3     : -base
+       4     : +a
+       5     : +++++++ Contents of side #2
+       6     : b
+       7    1: >>>>>>> Conflict 1 of 1 endsresolved
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_stat() {
     let test_env = TestEnvironment::default();
@@ -3848,6 +3922,45 @@ fn test_diff_binary() {
     ");
 }
 
+#[test]
+fn test_diff_gitattributes_binary() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file(".gitattributes", "*.lock -diff\n");
+    work_dir.write_file("file.lock", "a\n");
+    work_dir.write_file("file.txt", "a\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file.lock", "b\n");
+    work_dir.write_file("file.txt", "b\n");
+
+    let output = work_dir.run_jj(["diff"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file.lock:
+        (binary)
+    Modified regular file file.txt:
+       1    1: ab
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--git", "file.lock"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file.lock b/file.lock
+    index 7898192261..6178079822 100644
+    Binary files a/file.lock and b/file.lock differ
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--stat"]);
+    insta::assert_snapshot!(output, @r"
+    file.lock | (binary)
+    file.txt  | 2 +-
+    2 files changed, 1 insertion(+), 1 deletion(-)
+    [EOF]
+    ");
+}
+
 /// Test diff --stat output width for diffs that have different cases of right
 /// side text: solely "(binary)", a mixture of text and binary diffs, and binary
 /// size changes.
@@ -0,0 +1,94 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_file_lfs_track_untrack() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file(".gitattributes", "*.txt text\n");
+
+    let output = work_dir.run_jj(["file", "lfs", "track", "*.bin", "*.psd"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Tracking *.bin in Git LFS
+    Tracking *.psd in Git LFS
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "lfs", "track", "*.bin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file(".gitattributes"), @r"
+    *.txt text
+    *.bin filter=lfs diff=lfs merge=lfs -text
+    *.psd filter=lfs diff=lfs merge=lfs -text
+    ");
+    let output = work_dir.run_jj(["file", "lfs", "list"]);
+    insta::assert_snapshot!(output, @r"
+    *.bin
+    *.psd
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["file", "lfs", "untrack", "*.bin", "*.zip"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: *.zip is not tracked in Git LFS
+    Untracking *.bin in Git LFS
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file(".gitattributes"), @r"
+    *.txt text
+    *.psd filter=lfs diff=lfs merge=lfs -text
+    ");
+}
+
+#[test]
+fn test_file_lfs_snapshot_and_checkout() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.lfs.enabled = true");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["file", "lfs", "track", "*.bin"]).success();
+    work_dir.write_file("large.bin", "large content\n");
+    work_dir.write_file("small.txt", "small content\n");
+
+    // The committed content is a pointer to the object in the LFS store
+    let output = work_dir.run_jj(["file", "show", "large.bin"]);
+    insta::assert_snapshot!(output, @r"
+    version https://git-lfs.github.com/spec/v1
+    oid sha256:ca27f3a1fa46b52b9b770c0f09ec44d48b0f6a1e6b0c8955b8ac1976b6d6c3cb
+    size 14
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "small.txt"]);
+    insta::assert_snapshot!(output, @r"
+    small content
+    [EOF]
+    ");
+
+    // Checking out the pointer restores the content from the LFS store
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "lfs"])
+        .success();
+    work_dir.run_jj(["new", "root()"]).success();
+    assert!(!work_dir.root().join("large.bin").exists());
+    work_dir.run_jj(["new", "lfs"]).success();
+    insta::assert_snapshot!(work_dir.read_file("large.bin"), @"large content");
+}
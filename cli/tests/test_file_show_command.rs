@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use indoc::indoc;
+
 use crate::common::TestEnvironment;
+use crate::common::create_commit_with_files;
 
 #[test]
 fn test_show() {
@@ -116,6 +119,48 @@ fn test_show() {
     ");
 }
 
+#[test]
+fn test_show_conflict_marker_style_per_path() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(indoc! {r#"
+        [ui.conflict-marker-styles.git]
+        patterns = ["glob:'*.git'"]
+        style = "git"
+    "#});
+
+    let files = [("file.git", "base\n"), ("file.txt", "base\n")];
+    create_commit_with_files(&work_dir, "base", &[], &files);
+    let files = [("file.git", "a\n"), ("file.txt", "a\n")];
+    create_commit_with_files(&work_dir, "a", &["base"], &files);
+    let files = [("file.git", "b\n"), ("file.txt", "b\n")];
+    create_commit_with_files(&work_dir, "b", &["base"], &files);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    let template = r#""--- " ++ path ++ "\n""#;
+    let output = work_dir.run_jj(["file", "show", "-T", template, "."]);
+    insta::assert_snapshot!(output, @r"
+    --- file.git
+    <<<<<<< Side #1 (Conflict 1 of 1)
+    a
+    ||||||| Base
+    base
+    =======
+    b
+    >>>>>>> Side #2 (Conflict 1 of 1 ends)
+    --- file.txt
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -base
+    +a
+    +++++++ Contents of side #2
+    b
+    >>>>>>> Conflict 1 of 1 ends
+    [EOF]
+    ");
+}
+
 #[cfg(unix)]
 #[test]
 fn test_show_symlink() {
@@ -0,0 +1,184 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_format_patch_files() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir
+        .run_jj(["commit", "-m", "Add the file\n\nWith a body."])
+        .success();
+    work_dir.write_file("file", "a\nb\n");
+    work_dir.run_jj(["commit", "-m", "Fix the file!"]).success();
+
+    let output = work_dir.run_jj(["format-patch", "-r", "::@- ~ root()", "-o", "out"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    out/0001-Add-the-file.patch
+    out/0002-Fix-the-file.patch
+    [EOF]
+    ");
+    let patch = work_dir.read_file("out/0002-Fix-the-file.patch");
+    let patch = patch.to_string();
+    assert!(patch.contains("Subject: [PATCH 2/2] Fix the file!\n"));
+    assert!(patch.contains("X-Jj-Change-Id: "));
+    assert!(
+        patch.contains("\n---\n file | 1 +\n 1 file changed, 1 insertion(+), 0 deletions(-)\n")
+    );
+    assert!(patch.contains("\n a\n+b\n"));
+
+    // Merge commits can't be exported
+    work_dir.run_jj(["new", "@-", "@--"]).success();
+    let output = work_dir.run_jj(["format-patch", "--stdout"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(|s| {
+        s.split_once("merge commit ").unwrap().0.to_owned() + "merge commit <id>\n"
+    }), @r"
+    ------- stderr -------
+    Error: Cannot export merge commit <id>
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_format_apply_patch_round_trip() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\nb\nc\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("file", "a\nB\nc\n");
+    work_dir.write_file("new", "new\n");
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "Café changes\n\nMore details.\nFrom now on, it's fixed.",
+        ])
+        .success();
+    work_dir
+        .run_jj(["metaedit", "--author", "Patch Author <patch@example.com>"])
+        .success();
+    let patch = work_dir.run_jj(["format-patch", "--stdout"]).success();
+    work_dir.write_file("../series.mbox", patch.stdout.raw());
+    let template = r#"change_id ++ "\n""#;
+    let original_change_id = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-T", template])
+        .success()
+        .stdout
+        .into_raw();
+
+    // The change ID is kept once the original change is abandoned
+    work_dir.run_jj(["abandon", "@"]).success();
+    work_dir
+        .run_jj(["apply-patch", "../series.mbox", "-o", "@-"])
+        .success();
+    let template = r#"
+        concat(
+          change_id, "\n",
+          author.name(), " <", author.email(), ">\n",
+          description,
+        )
+    "#;
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        "children(@-) ~ @",
+        "-T",
+        template,
+    ]);
+    assert_eq!(
+        output.stdout.raw().lines().next().unwrap(),
+        original_change_id.trim()
+    );
+    insta::assert_snapshot!(output.normalize_stdout_with(|s| s.split_once('\n').unwrap().1.to_owned()), @r"
+    Patch Author <patch@example.com>
+    Café changes
+
+    More details.
+    From now on, it's fixed.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "children(@-) ~ @", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    B
+    c
+    [EOF]
+    ");
+
+    // Applying the same patch again gives a new change ID
+    work_dir
+        .run_jj(["apply-patch", "../series.mbox", "-o", "@-"])
+        .success();
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        "all()",
+        "-T",
+        r#"if(divergent, "divergent\n")"#,
+    ]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_apply_patch_rejected() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "x\n");
+    work_dir.write_file(
+        "../fix.patch",
+        indoc::indoc! {"
+            From: Someone <someone@example.com>
+            Subject: [PATCH] Fix it
+
+            ---
+            diff --git a/file b/file
+            --- a/file
+            +++ b/file
+            @@ -1 +1 @@
+            -a
+            +b
+            diff --git a/missing b/missing
+            --- a/missing
+            +++ b/missing
+            @@ -1 +1 @@
+            -a
+            +b
+        "},
+    );
+    let output = work_dir.run_jj(["apply-patch", "../fix.patch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to apply patch 1 (Fix it):
+      file: Hunk #1 doesn't match
+      missing: No such file
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["apply-patch", "../nonexistent.patch"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: Failed to read ../nonexistent.patch
+    [EOF]
+    [exit status: 1]
+    ");
+}
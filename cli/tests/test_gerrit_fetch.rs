@@ -0,0 +1,127 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use testutils::git;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+/// Creates a Gerrit-like remote with two patchsets of change 12345
+fn init_gerrit_remote(test_env: &TestEnvironment) -> gix::Repository {
+    let git_repo = git::init(test_env.env_root().join("gerrit"));
+    let base = git::add_commit(&git_repo, "refs/heads/main", "file", b"base\n", "base", &[]);
+    git::add_commit(
+        &git_repo,
+        "refs/changes/45/12345/1",
+        "file",
+        b"first\n",
+        "Fix a bug\n\nChange-Id: I0123456789abcdef0123456789abcdef01234567\n",
+        &[base.commit_id],
+    );
+    git::add_commit(
+        &git_repo,
+        "refs/changes/45/12345/2",
+        "file",
+        b"second\n",
+        "Fix a bug, take two\n\nChange-Id: I0123456789abcdef0123456789abcdef01234567\n",
+        &[base.commit_id],
+    );
+    git_repo
+}
+
+fn init_repo(test_env: &TestEnvironment) -> TestWorkDir<'_> {
+    init_gerrit_remote(test_env);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "gerrit", "../gerrit"])
+        .success();
+    work_dir
+}
+
+#[test]
+fn test_gerrit_fetch_latest_patchset() {
+    let test_env = TestEnvironment::default();
+    let work_dir = init_repo(&test_env);
+
+    let output = work_dir.run_jj(["gerrit", "fetch", "12345"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Fetched patchset 2 of change 12345: ytuqpusk 71d788c6 Fix a bug, take two
+    Hint: Run `jj new ytuqpuskpvrn` to work on top of it.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r", "all()", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    │ ○  Fix a bug, take two
+    │ │
+    │ │  Change-Id: I0123456789abcdef0123456789abcdef01234567
+    │ ○  base
+    ├─╯
+    ◆
+    [EOF]
+    ");
+
+    // No bookmarks are created for the change
+    let output = work_dir.run_jj(["bookmark", "list", "--all-remotes"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_gerrit_fetch_patchset() {
+    let test_env = TestEnvironment::default();
+    let work_dir = init_repo(&test_env);
+
+    let output = work_dir.run_jj(["gerrit", "fetch", "12345/1", "--remote=gerrit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Fetched patchset 1 of change 12345: kssksuwq 13f4b8c9 Fix a bug
+    Hint: Run `jj new kssksuwqzqsl` to work on top of it.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  fetch gerrit change 12345 patchset 1 from git remote gerrit
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_gerrit_fetch_errors() {
+    let test_env = TestEnvironment::default();
+    let work_dir = init_repo(&test_env);
+
+    let output = work_dir.run_jj(["gerrit", "fetch", "12345/x"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid change number: 12345/x
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj(["gerrit", "fetch", "12345/3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Change 12345 has no patchset 3 on remote gerrit
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["gerrit", "fetch", "99"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Change 99 not found on remote gerrit
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -170,3 +170,105 @@ fn test_gerrit_upload_local() {
     [EOF]
     "###);
 }
+
+#[cfg(unix)]
+#[test]
+fn test_gerrit_upload_push_options() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "remote"])
+        .success();
+    let remote_dir = test_env.work_dir("remote");
+    create_commit(&remote_dir, "a", &[]);
+    remote_dir
+        .run_jj([
+            "util",
+            "exec",
+            "--",
+            "git",
+            "config",
+            "receive.advertisePushOptions",
+            "true",
+        ])
+        .success();
+    // Print the received push options like Gerrit would interpret them
+    let hook_path = remote_dir.root().join(".git/hooks/pre-receive");
+    std::fs::create_dir_all(hook_path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &hook_path,
+        indoc::indoc! {r#"
+            #!/bin/sh
+            i=0
+            while [ "$i" -lt "${GIT_PUSH_OPTION_COUNT:-0}" ]; do
+                eval "echo \"option: \$GIT_PUSH_OPTION_$i\""
+                i=$((i + 1))
+            done
+        "#},
+    )
+    .unwrap();
+    std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    test_env
+        .run_jj_in(".", ["git", "clone", "remote", "local"])
+        .success();
+    let local_dir = test_env.work_dir("local");
+    create_commit(&local_dir, "b", &["a@origin"]);
+
+    let output = local_dir.run_jj([
+        "gerrit",
+        "upload",
+        "-r",
+        "b",
+        "--remote-branch=main",
+        "--topic=feature",
+        "--reviewer=alice@example.com",
+        "--reviewer=bob",
+        "--hashtag=cleanup",
+        "--wip",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+
+    Found 1 heads to push to Gerrit (remote 'origin'), target branch 'main'
+
+    Pushing royxmykx d4195223 b | b
+    remote: option: topic=feature        
+    remote: option: r=alice@example.com        
+    remote: option: r=bob        
+    remote: option: hashtag=cleanup        
+    remote: option: wip        
+    [EOF]
+    ");
+
+    let output = local_dir.run_jj([
+        "gerrit",
+        "upload",
+        "-r",
+        "b",
+        "--remote-branch=other",
+        "--ready",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+
+    Found 1 heads to push to Gerrit (remote 'origin'), target branch 'other'
+
+    Pushing royxmykx d4195223 b | b
+    remote: option: ready        
+    [EOF]
+    ");
+
+    let output = local_dir.run_jj(["gerrit", "upload", "-r", "b", "--wip", "--ready"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--wip' cannot be used with '--ready'
+
+    Usage: jj gerrit upload --revisions <REVISIONS> --wip
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
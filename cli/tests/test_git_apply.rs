@@ -0,0 +1,147 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indoc::indoc;
+
+use crate::common::TestEnvironment;
+
+const DIFF: &str = indoc! {"
+    Please apply this:
+
+    diff --git a/file b/file
+    --- a/file
+    +++ b/file
+    @@ -1,3 +1,3 @@
+     a
+    -b
+    +B
+     c
+    diff --git a/added b/added
+    new file mode 100644
+    --- /dev/null
+    +++ b/added
+    @@ -0,0 +1 @@
+    +new
+"};
+
+#[test]
+fn test_git_apply() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\nb\nc\n");
+
+    work_dir
+        .run_jj_with(|cmd| cmd.args(["git", "apply"]).write_stdin(DIFF))
+        .success();
+    let output = work_dir.run_jj(["diff", "--git", "--from", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/added b/added
+    new file mode 100644
+    index 0000000000..3e757656cf
+    --- /dev/null
+    +++ b/added
+    @@ -0,0 +1,1 @@
+    +new
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..7be73ce3c1
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,3 @@
+    +a
+    +B
+    +c
+    [EOF]
+    ");
+
+    // Reversing the diff restores the original content
+    work_dir.write_file("../fix.diff", DIFF);
+    work_dir
+        .run_jj(["git", "apply", "--reverse", "../fix.diff"])
+        .success();
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file
+    [EOF]
+    ");
+    assert_eq!(work_dir.read_file("file"), "a\nb\nc\n");
+}
+
+#[test]
+fn test_git_apply_strip() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("dir/file", "a\n");
+    work_dir.write_file(
+        "../plain.diff",
+        indoc! {"
+            --- dir/file
+            +++ dir/file
+            @@ -1 +1 @@
+            -a
+            +b
+        "},
+    );
+
+    let output = work_dir.run_jj(["git", "apply", "../plain.diff"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to apply the diff:
+      file: No such file
+    [EOF]
+    [exit status: 1]
+    ");
+    work_dir
+        .run_jj(["git", "apply", "-p0", "../plain.diff"])
+        .success();
+    assert_eq!(work_dir.read_file("dir/file"), "b\n");
+}
+
+#[test]
+fn test_git_apply_reject() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "x\ny\nz\n");
+
+    let output = work_dir.run_jj_with(|cmd| cmd.args(["git", "apply"]).write_stdin(DIFF));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to apply the diff:
+      file: Hunk #1 doesn't match
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(!work_dir.root().join("added").exists());
+
+    let output = work_dir
+        .run_jj_with(|cmd| cmd.args(["git", "apply", "--reject"]).write_stdin(DIFF))
+        .success();
+    insta::assert_snapshot!(output.stderr.into_raw().lines().take(2).collect::<Vec<_>>().join("\n"), @r"
+    Warning: Some changes could not be applied:
+      file: Hunk #1 doesn't match
+    ");
+    assert_eq!(work_dir.read_file("added"), "new\n");
+    assert_eq!(work_dir.read_file("file"), "x\ny\nz\n");
+
+    let output = work_dir.run_jj_with(|cmd| cmd.args(["git", "apply"]).write_stdin("hello\n"));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No diff found in the input
+    [EOF]
+    [exit status: 1]
+    ");
+}
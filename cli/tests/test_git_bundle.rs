@@ -0,0 +1,119 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_git_bundle_create_and_clone() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "1\n");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.write_file("file", "2\n");
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@--", "old"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "main"])
+        .success();
+
+    // Every head must be pointed to by a bookmark
+    let output = work_dir.run_jj(["git", "bundle", "create", "../all.bundle", "-r", "::@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revision de7462a1c318 is not pointed to by a bookmark
+    Hint: Use `jj bookmark create` to name the revisions to bundle.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["git", "bundle", "create", "../all.bundle", "-r", "::main"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created bundle ../all.bundle with 2 bookmarks
+    [EOF]
+    ");
+
+    // The destination is named after the bundle
+    let output = test_env.run_jj_in(".", ["git", "clone", "all.bundle"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/all"
+    bookmark: main@origin [new] untracked
+    bookmark: old@origin  [new] untracked
+    [EOF]
+    "#);
+    let output = test_env
+        .work_dir("all")
+        .run_jj(["log", "-r", "::main@origin"]);
+    insta::assert_snapshot!(output, @r"
+    ◆  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 main@origin ab60778a
+    │  second
+    ◆  qpvuntsm test.user@example.com 2001-02-03 08:05:08 old@origin 2d7eba5a
+    │  first
+    ◆  zzzzzzzz root() 00000000
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_bundle_fetch_incremental() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "1\n");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "main"])
+        .success();
+    work_dir
+        .run_jj(["git", "bundle", "create", "../base.bundle", "-r", "::main"])
+        .success();
+    test_env
+        .run_jj_in(".", ["git", "clone", "base.bundle", "clone"])
+        .success();
+
+    work_dir.write_file("file", "2\n");
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    work_dir
+        .run_jj(["bookmark", "move", "--to=@-", "main"])
+        .success();
+    // Only the new commit is included
+    let output = work_dir.run_jj(["git", "bundle", "create", "../update.bundle", "-r", "main"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created bundle ../update.bundle with 1 bookmarks
+    [EOF]
+    ");
+
+    let clone_dir = test_env.work_dir("clone");
+    clone_dir
+        .run_jj(["git", "remote", "add", "update", "../update.bundle"])
+        .success();
+    let output = clone_dir.run_jj(["git", "fetch", "--remote", "update"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: main@update [new] untracked
+    [EOF]
+    ");
+    let output = clone_dir.run_jj(["log", "-r", "::main@update", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    ◆  second
+    ◆  first
+    ◆
+    [EOF]
+    ");
+}
@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
 use std::path;
 
+use bstr::ByteSlice as _;
 use indoc::formatdoc;
 use indoc::indoc;
+use itertools::Itertools as _;
 use testutils::git;
 
 use crate::common::CommandOutput;
@@ -1020,6 +1023,185 @@ fn test_git_clone_with_depth() {
     ");
 }
 
+#[test]
+fn test_git_clone_with_depth_and_deepen() {
+    let test_env = TestEnvironment::default();
+    let root_dir = test_env.work_dir("");
+    test_env.add_config("remotes.origin.auto-track-bookmarks = 'glob:*'");
+    let clone_dir = test_env.work_dir("clone");
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(git_repo_path);
+    let mut parents = vec![];
+    for message in ["first", "second", "third"] {
+        let commit = git::add_commit(
+            &git_repo,
+            "refs/heads/main",
+            "file",
+            message.as_bytes(),
+            message,
+            &parents,
+        );
+        parents = vec![commit.commit_id];
+    }
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+
+    root_dir
+        .run_jj(["git", "clone", "--depth", "1", "source", "clone"])
+        .success();
+    let output = clone_dir.run_jj(["log", "-r", "::main", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    ◆  third
+    ◆
+    [EOF]
+    ");
+
+    let output = clone_dir.run_jj(["git", "fetch", "--deepen", "1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    Reindexed 5 commits after deepening the history.
+    [EOF]
+    ");
+    let output = clone_dir.run_jj(["log", "-r", "::main", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    ◆  third
+    ◆  second
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_clone_with_blob_filter() {
+    let test_env = TestEnvironment::default();
+    let root_dir = test_env.work_dir("");
+    let clone_dir = test_env.work_dir("clone");
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(&git_repo_path);
+    let commit1 = git::add_commit(&git_repo, "refs/heads/main", "file", b"1\n", "first", &[]);
+    git::add_commit(
+        &git_repo,
+        "refs/heads/main",
+        "file",
+        b"2\n",
+        "second",
+        &[commit1.commit_id],
+    );
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    // Allow fetching filtered packs and individual blobs from the source
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(git_repo.path().join("config"))
+        .unwrap();
+    writeln!(
+        config,
+        "[uploadpack]\n\tallowFilter = true\n\tallowAnySHA1InWant = true"
+    )
+    .unwrap();
+
+    let output = root_dir.run_jj(["git", "clone", "--filter=blob:none", "source", "clone"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/clone"
+    bookmark: main@origin [new] tracked
+    Setting the revset alias `trunk()` to `main@origin`
+    Working copy  (@) now at: sqpuoqvx 339ee455 (empty) (no description set)
+    Parent commit (@-)      : pmrtovyk dff2560a main | second
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+    let git_config = clone_dir.read_file(".jj/repo/store/git/config");
+    assert!(git_config.contains_str("partialclonefilter = blob:none"));
+
+    // File contents of other commits are fetched on demand
+    let output = clone_dir.run_jj(["file", "show", "-r", "main-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    1
+    [EOF]
+    ");
+    let output = clone_dir.run_jj(["diff", "--git", "-r", "main"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file b/file
+    index d00491fd7e..0cfbf08886 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,1 @@
+    -1
+    +2
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_git_clone_with_blob_filter_fetches_in_batch() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let test_env = TestEnvironment::default();
+    let root_dir = test_env.work_dir("");
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(&git_repo_path);
+    let mut parents = vec![];
+    for name in ["a", "b", "c"] {
+        let commit = git::add_commit(
+            &git_repo,
+            "refs/heads/main",
+            name,
+            name.as_bytes(),
+            name,
+            &parents,
+        );
+        parents = vec![commit.commit_id];
+    }
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(git_repo.path().join("config"))
+        .unwrap();
+    writeln!(
+        config,
+        "[uploadpack]\n\tallowFilter = true\n\tallowAnySHA1InWant = true"
+    )
+    .unwrap();
+    // Log the git commands run by jj
+    let calls_path = test_env.env_root().join("git-calls");
+    let wrapper_path = test_env.env_root().join("git-wrapper");
+    std::fs::write(
+        &wrapper_path,
+        format!(
+            "#!/bin/sh\necho \"$*\" >> '{}'\nexec git \"$@\"\n",
+            calls_path.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&wrapper_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    test_env.add_config(format!(
+        "git.executable-path = {}",
+        to_toml_value(wrapper_path.to_str().unwrap())
+    ));
+
+    let output = root_dir.run_jj(["git", "clone", "--filter=blob:none", "source", "clone"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/clone"
+    bookmark: main@origin [new] tracked
+    Setting the revset alias `trunk()` to `main@origin`
+    Working copy  (@) now at: sqpuoqvx c56a0427 (empty) (no description set)
+    Parent commit (@-)      : yrzknrwv 4c5a971a main | c
+    Added 3 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+
+    // The missing blobs of the checked-out tree are fetched by a single git
+    // process
+    let calls = std::fs::read_to_string(&calls_path).unwrap();
+    let fetches = calls
+        .lines()
+        .filter(|line| line.contains("--stdin") || line.starts_with("cat-file"))
+        .collect_vec();
+    assert_eq!(fetches.len(), 1, "{calls}");
+}
+
 #[test]
 fn test_git_clone_invalid_immutable_heads() {
     let test_env = TestEnvironment::default();
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use testutils::git;
+
 use crate::common::TestEnvironment;
 
 fn read_git_target(workspace_root: &std::path::Path) -> String {
@@ -87,6 +89,71 @@ fn test_git_colocation_enable_success() {
     [EOF]");
 }
 
+#[test]
+fn test_git_colocation_roundtrip_preserves_bookmarks_and_operations() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(
+            test_env.env_root(),
+            ["git", "init", "--no-colocate", "repo"],
+        )
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let workspace_root = work_dir.root();
+    work_dir.write_file("file", "contents");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "main"])
+        .success();
+    let op_log_before = work_dir.run_jj(["op", "log", "--no-graph", "-T", "id.short() ++ '\\n'"]);
+
+    work_dir.run_jj(["git", "colocation", "enable"]).success();
+
+    // Bookmarks are available as Git branches, and the files of the
+    // working-copy commit's parent are in the Git index.
+    let git_repo = git::open(workspace_root);
+    let main_ref = git_repo.find_reference("refs/heads/main").unwrap();
+    let output = work_dir.run_jj(["log", "-r=main", "--no-graph", "-T=commit_id"]);
+    assert_eq!(main_ref.id().to_string(), output.stdout.raw());
+    insta::assert_debug_snapshot!(git::status(&git_repo), @r#"
+    [
+        GitStatus {
+            path: ".jj/.gitignore",
+            status: Worktree(
+                Ignored,
+            ),
+        },
+        GitStatus {
+            path: ".jj/repo",
+            status: Worktree(
+                Ignored,
+            ),
+        },
+        GitStatus {
+            path: ".jj/working_copy",
+            status: Worktree(
+                Ignored,
+            ),
+        },
+    ]
+    "#);
+
+    work_dir.run_jj(["git", "colocation", "disable"]).success();
+
+    // The operations recorded before the conversion are kept.
+    let op_log_after = work_dir.run_jj(["op", "log", "--no-graph", "-T", "id.short() ++ '\\n'"]);
+    assert!(
+        op_log_after
+            .stdout
+            .raw()
+            .ends_with(op_log_before.stdout.raw())
+    );
+    insta::assert_snapshot!(work_dir.run_jj(["bookmark", "list"]), @r"
+    main: qpvuntsm cdf25360 first
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_colocation_enable_already_colocated() {
     let test_env = TestEnvironment::default();
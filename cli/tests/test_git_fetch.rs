@@ -287,6 +287,79 @@ fn test_git_fetch_with_ignored_refspecs() {
     ");
 }
 
+#[test]
+fn test_git_fetch_tags_setting() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "origin");
+    let create_tag = |name: &str, id: gix::ObjectId| {
+        git_repo
+            .reference(
+                format!("refs/tags/{name}"),
+                id,
+                gix::refs::transaction::PreviousValue::MustNotExist,
+                "create tag",
+            )
+            .unwrap();
+    };
+    let fetched_id = git_repo
+        .find_reference("refs/heads/origin")
+        .unwrap()
+        .peel_to_id()
+        .unwrap()
+        .detach();
+    create_tag("fetched", fetched_id);
+    create_tag("other", add_commit_to_branch(&git_repo, "other", "other"));
+
+    // No tags are fetched
+    let output = work_dir.run_jj([
+        "git",
+        "fetch",
+        "-b=origin",
+        "--config=remotes.origin.fetch-tags='none'",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: origin@origin [new] untracked
+    [EOF]
+    ");
+
+    // By default, only the tags pointing to fetched commits are fetched
+    let output = work_dir.run_jj(["git", "fetch", "-b=origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    tag: fetched@git [new] 
+    [EOF]
+    ");
+
+    // All tags are fetched
+    let output = work_dir.run_jj([
+        "git",
+        "fetch",
+        "-b=origin",
+        "--config=remotes.origin.fetch-tags='all'",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    tag: other@git [new] 
+    [EOF]
+    ");
+
+    // Invalid values are rejected
+    let output = work_dir.run_jj(["git", "fetch", "--config=remotes.origin.fetch-tags='some'"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Config error: Invalid type or value for remotes.origin.fetch-tags
+    Caused by: expected "all" or "none"
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
 #[test]
 fn test_git_fetch_with_glob() {
     let test_env = TestEnvironment::default();
@@ -211,6 +211,43 @@ fn test_git_import_undo() {
     ");
 }
 
+#[test]
+fn test_git_import_write_commit_graph() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo_path = work_dir.root().join(".jj/repo/store/git");
+    let git_repo = git::open(&git_repo_path);
+    let chain_path = git_repo_path.join("objects/info/commit-graphs/commit-graph-chain");
+
+    let commit_id = work_dir
+        .run_jj(&["log", "-Tcommit_id", "--no-graph", "-r@"])
+        .success()
+        .stdout
+        .into_raw();
+    let commit_id = gix::ObjectId::from_hex(commit_id.as_bytes()).unwrap();
+    git_repo
+        .reference(
+            "refs/heads/a",
+            commit_id,
+            gix::refs::transaction::PreviousValue::Any,
+            "",
+        )
+        .unwrap();
+
+    // The commit-graph isn't written by default
+    work_dir.run_jj(["git", "import"]).success();
+    assert!(!chain_path.exists());
+
+    let output = work_dir.run_jj(["git", "import", "--config=git.write-commit-graph=true"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+    assert!(chain_path.exists());
+}
+
 #[test]
 fn test_git_import_move_export_with_default_undo() {
     let test_env = TestEnvironment::default();
@@ -106,7 +106,7 @@ fn test_git_private_commits_block_pushing() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark main from 95cc152cd086 to 7f665ca27d4e
+      Move forward bookmark main from 95cc152cd086 to 7f665ca27d4e (1 new commits)
     Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
     Working copy  (@) now at: znkkpsqq 8227d51b (empty) (no description set)
     Parent commit (@-)      : yqosqzyt 7f665ca2 main | (empty) private 1
@@ -142,7 +142,7 @@ fn test_git_private_commits_can_be_overridden() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark main from 95cc152cd086 to 7f665ca27d4e
+      Move forward bookmark main from 95cc152cd086 to 7f665ca27d4e (1 new commits)
     Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
     Working copy  (@) now at: znkkpsqq 8227d51b (empty) (no description set)
     Parent commit (@-)      : yqosqzyt 7f665ca2 main | (empty) private 1
@@ -167,7 +167,7 @@ fn test_git_private_commits_are_not_checked_if_immutable() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark main from 95cc152cd086 to 7f665ca27d4e
+      Move forward bookmark main from 95cc152cd086 to 7f665ca27d4e (1 new commits)
     Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
     Working copy  (@) now at: yostqsxw 17947f20 (empty) (no description set)
     Parent commit (@-)      : yqosqzyt 7f665ca2 main | (empty) private 1
@@ -221,7 +221,7 @@ fn test_git_private_commits_descending_from_commits_pushed_do_not_block_pushing(
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark main from 95cc152cd086 to f0291dea729d
+      Move forward bookmark main from 95cc152cd086 to f0291dea729d (1 new commits)
     [EOF]
     ");
 }
@@ -249,7 +249,7 @@ fn test_git_private_commits_already_on_the_remote_do_not_block_push() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark main from 95cc152cd086 to 03bc2bf271e0
+      Move forward bookmark main from 95cc152cd086 to 03bc2bf271e0 (2 new commits)
       Add bookmark bookmark1 to 95cc152cd086
     Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
     Working copy  (@) now at: kpqxywon 5308110d (empty) (no description set)
@@ -267,7 +267,7 @@ fn test_git_private_commits_already_on_the_remote_do_not_block_push() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark1 from 95cc152cd086 to 03bc2bf271e0
+      Move forward bookmark bookmark1 from 95cc152cd086 to 03bc2bf271e0 (2 new commits)
     [EOF]
     ");
 
@@ -283,7 +283,7 @@ fn test_git_private_commits_already_on_the_remote_do_not_block_push() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark bookmark2 to 987ee765174d
+      Add bookmark bookmark2 to 987ee765174d (1 new commits)
     [EOF]
     ");
 }
@@ -307,7 +307,7 @@ fn test_git_private_commits_are_evaluated_separately_for_each_remote() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark main from 95cc152cd086 to 7eb69d0eaf71
+      Move forward bookmark main from 95cc152cd086 to 7eb69d0eaf71 (2 new commits)
     [EOF]
     ");
 
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
+
 use testutils::git;
 
 use crate::common::CommandOutput;
@@ -118,8 +120,8 @@ fn test_git_push_current_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f
-      Add bookmark my-bookmark to 88ca14a7d46f
+      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f (1 new commits)
+      Add bookmark my-bookmark to 88ca14a7d46f (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -127,8 +129,8 @@ fn test_git_push_current_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f
-      Add bookmark my-bookmark to 88ca14a7d46f
+      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f (1 new commits)
+      Add bookmark my-bookmark to 88ca14a7d46f (1 new commits)
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -165,7 +167,7 @@ fn test_git_push_current_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move backward bookmark bookmark2 from 88ca14a7d46f to 38a204733702
+      Move backward bookmark bookmark2 from 88ca14a7d46f to 38a204733702 (1 commits removed)
     [EOF]
     ");
 }
@@ -188,7 +190,7 @@ fn test_git_push_parent_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark bookmark1 from 9b2e76de3920 to 80560a3e08e2
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to 80560a3e08e2 (1 new commits, 1 removed)
     [EOF]
     ");
 }
@@ -256,7 +258,7 @@ fn test_git_push_other_remote_has_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark bookmark1 from 9b2e76de3920 to a843bfad2abb
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to a843bfad2abb (1 new commits, 1 removed)
     [EOF]
     ");
     // Since it's already pushed to origin, nothing will happen if push again
@@ -282,7 +284,7 @@ fn test_git_push_other_remote_has_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to other:
-      Add bookmark bookmark1 to a843bfad2abb
+      Add bookmark bookmark1 to a843bfad2abb (2 new commits)
     [EOF]
     ");
 }
@@ -316,7 +318,7 @@ fn test_git_push_forward_unexpectedly_moved() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark1 from 9b2e76de3920 to 624f94a35f00
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 624f94a35f00 (1 new commits)
     Error: Failed to push some bookmarks
     Hint: The following references unexpectedly moved on the remote:
       refs/heads/bookmark1 (reason: stale info)
@@ -330,7 +332,7 @@ fn test_git_push_forward_unexpectedly_moved() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark1 from 9b2e76de3920 to 624f94a35f00
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 624f94a35f00 (1 new commits)
     [1m[38;5;1mError: [39mFailed to push some bookmarks[0m
     [1m[38;5;6mHint: [0m[39mThe following references unexpectedly moved on the remote:[39m
     [39m  [38;5;2mrefs/heads/bookmark1[39m (reason: stale info)[39m
@@ -340,6 +342,48 @@ fn test_git_push_forward_unexpectedly_moved() {
     ");
 }
 
+#[test]
+fn test_git_push_dry_run_unexpectedly_moved() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+
+    // Move bookmark1 forward and delete bookmark2 on the remote
+    let origin_dir = test_env.work_dir("origin");
+    origin_dir
+        .run_jj(["new", "bookmark1", "-m=remote"])
+        .success();
+    origin_dir
+        .run_jj(["bookmark", "set", "bookmark1", "-r@"])
+        .success();
+    origin_dir
+        .run_jj(["bookmark", "delete", "bookmark2"])
+        .success();
+    origin_dir.run_jj(["git", "export"]).success();
+
+    work_dir.run_jj(["new", "bookmark1", "-m=local"]).success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark1", "-r@"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "--allow-backwards", "bookmark2", "-r@"])
+        .success();
+
+    // The dry run reports the bookmarks which would be rejected
+    let output = work_dir.run_jj(["git", "push", "--all", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 44cb5d49d8cc (1 new commits)
+      Move sideways bookmark bookmark2 from 38a204733702 to 44cb5d49d8cc (2 new commits, 1 removed)
+    Warning: Bookmark bookmark1@origin is at ff693d9083b2 on the remote, expected 9b2e76de3920
+    Warning: Bookmark bookmark2@origin was deleted on the remote, expected 38a204733702
+    Hint: Pushing these bookmarks would be rejected. Run `jj git fetch --remote origin` and resolve the resulting bookmark conflicts.
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_sideways_unexpectedly_moved() {
     let test_env = TestEnvironment::default();
@@ -382,7 +426,7 @@ fn test_git_push_sideways_unexpectedly_moved() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark bookmark1 from 9b2e76de3920 to 827b8a385853
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to 827b8a385853 (1 new commits, 1 removed)
     Error: Failed to push some bookmarks
     Hint: The following references unexpectedly moved on the remote:
       refs/heads/bookmark1 (reason: stale info)
@@ -396,7 +440,7 @@ fn test_git_push_sideways_unexpectedly_moved() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark bookmark1 from 9b2e76de3920 to 827b8a385853
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to 827b8a385853 (1 new commits, 1 removed)
     [1m[38;5;1mError: [39mFailed to push some bookmarks[0m
     [1m[38;5;6mHint: [0m[39mThe following references unexpectedly moved on the remote:[39m
     [39m  [38;5;2mrefs/heads/bookmark1[39m (reason: stale info)[39m
@@ -497,7 +541,7 @@ fn test_git_push_unexpectedly_deleted() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark bookmark1 from 9b2e76de3920 to 09919fb051bf
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to 09919fb051bf (1 new commits, 1 removed)
     Error: Failed to push some bookmarks
     Hint: The following references unexpectedly moved on the remote:
       refs/heads/bookmark1 (reason: stale info)
@@ -565,7 +609,7 @@ fn test_git_push_creation_unexpectedly_already_exists() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark bookmark1 to a43cb8011c85
+      Add bookmark bookmark1 to a43cb8011c85 (1 new commits)
     Error: Failed to push some bookmarks
     Hint: The following references unexpectedly moved on the remote:
       refs/heads/bookmark1 (reason: stale info)
@@ -602,7 +646,7 @@ fn test_git_push_locally_created_and_rewritten() {
     ------- stderr -------
     Warning: --allow-new is deprecated, track bookmarks manually or configure remotes.<name>.auto-track-bookmarks instead.
     Changes to push to origin:
-      Add bookmark my to e0cba5e497ee
+      Add bookmark my to e0cba5e497ee (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -618,7 +662,7 @@ fn test_git_push_locally_created_and_rewritten() {
     Example: jj config set --user remotes.origin.auto-track-bookmarks 'glob:*'
     For details, see: https://jj-vcs.github.io/jj/latest/config/#automatic-tracking-of-bookmarks
     Changes to push to origin:
-      Add bookmark my to e0cba5e497ee
+      Add bookmark my to e0cba5e497ee (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -630,7 +674,7 @@ fn test_git_push_locally_created_and_rewritten() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark my to e0cba5e497ee
+      Add bookmark my to e0cba5e497ee (1 new commits)
     [EOF]
     ");
 
@@ -650,7 +694,7 @@ fn test_git_push_locally_created_and_rewritten() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark my from e0cba5e497ee to 9ebc3217a0b8
+      Move sideways bookmark my from e0cba5e497ee to 9ebc3217a0b8 (1 new commits, 1 removed)
     [EOF]
     ");
 }
@@ -687,8 +731,8 @@ fn test_git_push_multiple() {
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
-      Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75 (1 new commits, 1 removed)
+      Add bookmark my-bookmark to 352fa1879f75 (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -698,7 +742,7 @@ fn test_git_push_multiple() {
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
-      Add bookmark my-bookmark to 352fa1879f75
+      Add bookmark my-bookmark to 352fa1879f75 (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -716,7 +760,7 @@ fn test_git_push_multiple() {
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
-      Add bookmark my-bookmark to 352fa1879f75
+      Add bookmark my-bookmark to 352fa1879f75 (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -726,7 +770,7 @@ fn test_git_push_multiple() {
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75 (1 new commits, 1 removed)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -754,8 +798,8 @@ fn test_git_push_multiple() {
     Warning: Refusing to push deleted bookmark bookmark1
     Hint: Push deleted bookmarks with --deleted or forget the bookmark to suppress this warning.
     Changes to push to origin:
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
-      Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75 (1 new commits, 1 removed)
+      Add bookmark my-bookmark to 352fa1879f75 (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -764,8 +808,8 @@ fn test_git_push_multiple() {
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
-      Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75 (1 new commits, 1 removed)
+      Add bookmark my-bookmark to 352fa1879f75 (1 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -775,8 +819,8 @@ fn test_git_push_multiple() {
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from 9b2e76de3920
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
-      Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75 (1 new commits, 1 removed)
+      Add bookmark my-bookmark to 352fa1879f75 (1 new commits)
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -799,6 +843,76 @@ fn test_git_push_multiple() {
     ");
 }
 
+#[test]
+fn test_git_push_with_push_refspecs() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config("remotes.origin.auto-track-bookmarks = 'glob:*'");
+    let work_dir = test_env.work_dir("local");
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(git_repo_dir_for_jj_repo(&work_dir).join("config"))
+        .unwrap()
+        .write_all(
+            br#"
+            [remote "origin"]
+            push = refs/heads/bookmark*:refs/heads/bookmark*
+            push = ^refs/heads/bookmark2
+            push = refs/heads/main:refs/heads/mirror/main
+            "#,
+        )
+        .unwrap();
+    work_dir.run_jj(["new", "bookmark1", "-m", "foo"]).success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "--allow-backwards",
+            "bookmark1",
+            "bookmark2",
+            "-r@",
+        ])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "other"])
+        .success();
+
+    // Bookmarks not matched by the refspecs are excluded from the default
+    // selection
+    let output = work_dir.run_jj(["git", "push", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Ignored refspec `refs/heads/main:refs/heads/mirror/main` from `origin`: renaming is not supported
+    Warning: Bookmark bookmark2 is excluded by the push refspecs of remote origin
+    Warning: Bookmark other is excluded by the push refspecs of remote origin
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 18ba325df20c (1 new commits)
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // Bookmarks specified explicitly are pushed regardless of the refspecs
+    let output = work_dir.run_jj(["git", "push", "-b=bookmark2", "-b=other", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move sideways bookmark bookmark2 from 38a204733702 to 18ba325df20c (2 new commits, 1 removed)
+      Add bookmark other to 18ba325df20c (1 new commits)
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--all", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 18ba325df20c (1 new commits)
+      Move sideways bookmark bookmark2 from 38a204733702 to 18ba325df20c (2 new commits, 1 removed)
+      Add bookmark other to 18ba325df20c (1 new commits)
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_changes() {
     let test_env = TestEnvironment::default();
@@ -814,7 +928,7 @@ fn test_git_push_changes() {
     ------- stderr -------
     Creating bookmark push-yostqsxwqrlt for revision yostqsxwqrlt
     Changes to push to origin:
-      Add bookmark push-yostqsxwqrlt to 916414184c47
+      Add bookmark push-yostqsxwqrlt to 916414184c47 (2 new commits)
     [EOF]
     ");
     // test pushing two changes at once
@@ -824,7 +938,7 @@ fn test_git_push_changes() {
     ------- stderr -------
     Creating bookmark push-yqosqzytrlsw for revision yqosqzytrlsw
     Changes to push to origin:
-      Move sideways bookmark push-yostqsxwqrlt from 916414184c47 to 2723f6111cb9
+      Move sideways bookmark push-yostqsxwqrlt from 916414184c47 to 2723f6111cb9 (1 new commits, 1 removed)
       Add bookmark push-yqosqzytrlsw to 0f8164cd580b
     [EOF]
     ");
@@ -834,7 +948,7 @@ fn test_git_push_changes() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark push-yostqsxwqrlt from 2723f6111cb9 to ee4011999491
+      Move sideways bookmark push-yostqsxwqrlt from 2723f6111cb9 to ee4011999491 (1 new commits, 1 removed)
     [EOF]
     ");
 
@@ -844,7 +958,7 @@ fn test_git_push_changes() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move sideways bookmark push-yostqsxwqrlt from ee4011999491 to 1b393e646dec
+      Move sideways bookmark push-yostqsxwqrlt from ee4011999491 to 1b393e646dec (1 new commits, 1 removed)
     [EOF]
     ");
 
@@ -898,7 +1012,7 @@ fn test_git_push_changes() {
     ------- stderr -------
     Creating bookmark test-yostqsxwqrlt for revision yostqsxwqrlt
     Changes to push to origin:
-      Add bookmark test-yostqsxwqrlt to 41aca6a29460
+      Add bookmark test-yostqsxwqrlt to 41aca6a29460 (1 new commits)
     [EOF]
     ");
 
@@ -947,7 +1061,7 @@ fn test_git_push_changes_with_name() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark b1 to 5f4f9a466c96
+      Add bookmark b1 to 5f4f9a466c96 (2 new commits)
     [EOF]
     ");
     // Spaces before the = sign are treated like part of the bookmark name and such
@@ -1041,7 +1155,7 @@ fn test_git_push_changes_with_name() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark b2 to 95ba7bdacb38
+      Add bookmark b2 to 95ba7bdacb38 (1 new commits)
     [EOF]
     ");
 }
@@ -1078,7 +1192,7 @@ fn test_git_push_changes_with_name_deleted_tracked() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark b1 to 08f401c17d51
+      Add bookmark b1 to 08f401c17d51 (2 new commits)
     [EOF]
     ");
     work_dir.run_jj(["bookmark", "delete", "b1"]).success();
@@ -1131,7 +1245,7 @@ fn test_git_push_changes_with_name_deleted_tracked() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to another_remote:
-      Add bookmark b1 to 08f401c17d51
+      Add bookmark b1 to 08f401c17d51 (3 new commits)
     [EOF]
     ");
     let output = work_dir
@@ -1226,7 +1340,7 @@ fn test_git_push_changes_with_name_untracked_or_forgotten() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark b1 to aa9ad64cb4ce
+      Add bookmark b1 to aa9ad64cb4ce (1 new commits)
     Error: Failed to push some bookmarks
     Hint: The following references unexpectedly moved on the remote:
       refs/heads/b1 (reason: stale info)
@@ -1242,7 +1356,7 @@ fn test_git_push_changes_with_name_untracked_or_forgotten() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark b1 to 9a0f76645905
+      Add bookmark b1 to 9a0f76645905 (3 new commits)
     Error: Failed to push some bookmarks
     Hint: The following references unexpectedly moved on the remote:
       refs/heads/b1 (reason: stale info)
@@ -1257,7 +1371,7 @@ fn test_git_push_changes_with_name_untracked_or_forgotten() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark b1 to 767b63a598e1
+      Add bookmark b1 to 767b63a598e1 (2 new commits)
     [EOF]
     ");
 }
@@ -1305,7 +1419,7 @@ fn test_git_push_revisions() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark bookmark-1 to e76139e55e1e
+      Add bookmark bookmark-1 to e76139e55e1e (2 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1315,7 +1429,7 @@ fn test_git_push_revisions() {
     ------- stderr -------
     Warning: No bookmarks point to the specified revisions: @--
     Changes to push to origin:
-      Add bookmark bookmark-1 to e76139e55e1e
+      Add bookmark bookmark-1 to e76139e55e1e (2 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1324,8 +1438,8 @@ fn test_git_push_revisions() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark bookmark-2a to 57d822f901bb
-      Add bookmark bookmark-2b to 57d822f901bb
+      Add bookmark bookmark-2a to 57d822f901bb (3 new commits)
+      Add bookmark bookmark-2b to 57d822f901bb (3 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1334,7 +1448,7 @@ fn test_git_push_revisions() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark bookmark-1 to e76139e55e1e
+      Add bookmark bookmark-1 to e76139e55e1e (2 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1391,12 +1505,78 @@ fn test_git_push_mixed() {
     Warning: --allow-new is deprecated, track bookmarks manually or configure remotes.<name>.auto-track-bookmarks instead.
     Creating bookmark push-yqosqzytrlsw for revision yqosqzytrlsw
     Changes to push to origin:
-      Add bookmark push-yqosqzytrlsw to 0f8164cd580b
-      Add bookmark bookmark-1 to e76139e55e1e
-      Add bookmark bookmark-2a to 57d822f901bb
-      Add bookmark bookmark-2b to 57d822f901bb
+      Add bookmark push-yqosqzytrlsw to 0f8164cd580b (1 new commits)
+      Add bookmark bookmark-1 to e76139e55e1e (2 new commits)
+      Add bookmark bookmark-2a to 57d822f901bb (3 new commits)
+      Add bookmark bookmark-2b to 57d822f901bb (3 new commits)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_push_tags() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["tag", "create", "-rbookmark1", "-mRelease 1", "v1"])
+        .success();
+    work_dir
+        .run_jj(["tag", "create", "-rbookmark2", "v2"])
+        .success();
+
+    let output = work_dir.run_jj(["git", "push", "--tag=v3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such tag: v3
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["git", "push", "--tag=v1", "--tag=v2", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Tags to push to origin:
+      Add tag v1 to 9b2e76de3920
+      Add tag v2 to 38a204733702
+    Dry-run requested, not pushing.
     [EOF]
     ");
+
+    // Tags aren't pushed if a bookmark update is rejected
+    work_dir.run_jj(["new", "-m="]).success();
+    let output = work_dir.run_jj(["git", "push", "--tag=v1", "--named=no-desc=@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Won't push commit f4058ed24b38 since it has no description
+    Hint: Rejected commit: kmkuslsw f4058ed2 (empty) (no description set)
+    [EOF]
+    [exit status: 1]
+    ");
+    let origin_git_repo = git::open(git_repo_dir_for_jj_repo(&test_env.work_dir("origin")));
+    assert!(
+        origin_git_repo
+            .try_find_reference("refs/tags/v1")
+            .unwrap()
+            .is_none()
+    );
+
+    let output = work_dir.run_jj(["git", "push", "--tag=v1", "--tag=v2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Tags to push to origin:
+      Add tag v1 to 9b2e76de3920
+      Add tag v2 to 38a204733702
+    [EOF]
+    ");
+
+    // The annotated tag object is pushed as is
+    let object_kind = |name: &str| {
+        let git_ref = origin_git_repo.find_reference(name).unwrap();
+        git_ref.id().object().unwrap().kind
+    };
+    assert_eq!(object_kind("refs/tags/v1"), gix::object::Kind::Tag);
+    assert_eq!(object_kind("refs/tags/v2"), gix::object::Kind::Commit);
 }
 
 #[test]
@@ -1497,7 +1677,7 @@ fn test_git_push_no_description_in_immutable() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark my-bookmark to 240e2e89abb2
+      Add bookmark my-bookmark to 240e2e89abb2 (2 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1572,7 +1752,7 @@ fn test_git_push_missing_author_in_immutable() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark my-bookmark to 96080b93b4ce
+      Add bookmark my-bookmark to 96080b93b4ce (3 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1665,7 +1845,7 @@ fn test_git_push_missing_committer_in_immutable() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Add bookmark my-bookmark to e0dff9c29479
+      Add bookmark my-bookmark to e0dff9c29479 (3 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -1706,6 +1886,55 @@ fn test_git_push_deleted() {
     ");
 }
 
+#[test]
+fn test_git_push_protected() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    test_env.add_config(r#"bookmarks.protected = ["glob:bookmark*"]"#);
+
+    // Deleting a protected bookmark on the remote is refused
+    work_dir
+        .run_jj(["bookmark", "delete", "--allow-protected", "bookmark1"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--deleted"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to delete protected bookmark: bookmark1
+    Hint: Use --allow-protected to allow it.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // So is moving it sideways
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "--allow-backwards",
+            "--allow-protected",
+            "-rbookmark1@origin",
+            "bookmark2",
+        ])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "-bbookmark2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to force-push protected bookmark: bookmark2
+    Hint: Use --allow-protected to allow it.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["git", "push", "--deleted", "--allow-protected"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark1 from 9b2e76de3920
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_conflicting_bookmarks() {
     let test_env = TestEnvironment::default();
@@ -1777,7 +2006,7 @@ fn test_git_push_conflicting_bookmarks() {
     Warning: Bookmark bookmark2 is conflicted
     Hint: Run `jj bookmark list` to inspect, and use `jj bookmark set` to fix it up.
     Changes to push to origin:
-      Move forward bookmark bookmark1 from 9b2e76de3920 to 749c2e6d999f
+      Move forward bookmark bookmark1 from 9b2e76de3920 to 749c2e6d999f (1 new commits)
     [EOF]
     ");
 
@@ -1789,7 +2018,7 @@ fn test_git_push_conflicting_bookmarks() {
     Warning: Bookmark bookmark2 is conflicted
     Hint: Run `jj bookmark list` to inspect, and use `jj bookmark set` to fix it up.
     Changes to push to origin:
-      Move forward bookmark bookmark1 from 749c2e6d999f to 9bb0f427b517
+      Move forward bookmark bookmark1 from 749c2e6d999f to 9bb0f427b517 (1 new commits)
     [EOF]
     ");
 }
@@ -1917,7 +2146,7 @@ fn test_git_push_tracked_vs_all() {
     Warning: Non-tracking remote bookmark bookmark1@origin exists
     Hint: Run `jj bookmark track bookmark1@origin` to import the remote bookmark.
     Changes to push to origin:
-      Add bookmark bookmark3 to 0004a65e1d28
+      Add bookmark bookmark3 to 0004a65e1d28 (1 new commits)
     [EOF]
     ");
 }
@@ -1988,8 +2217,8 @@ fn test_git_push_to_remote_named_git() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to git:
-      Add bookmark bookmark1 to 9b2e76de3920
-      Add bookmark bookmark2 to 38a204733702
+      Add bookmark bookmark1 to 9b2e76de3920 (2 new commits)
+      Add bookmark bookmark2 to 38a204733702 (2 new commits)
     Error: Git remote named 'git' is reserved for local Git repository
     Hint: Run `jj git remote rename` to give a different name.
     [EOF]
@@ -2013,8 +2242,8 @@ fn test_git_push_to_remote_with_slashes() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to slash/origin:
-      Add bookmark bookmark1 to 9b2e76de3920
-      Add bookmark bookmark2 to 38a204733702
+      Add bookmark bookmark1 to 9b2e76de3920 (2 new commits)
+      Add bookmark bookmark2 to 38a204733702 (2 new commits)
     Error: Git remotes with slashes are incompatible with jj: slash/origin
     Hint: Run `jj git remote rename` to give a different name.
     [EOF]
@@ -2078,7 +2307,7 @@ fn test_git_push_sign_on_push() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark2 from 38a204733702 to 3779ed7f18df
+      Move forward bookmark bookmark2 from 38a204733702 to 3779ed7f18df (2 new commits)
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -2101,7 +2330,7 @@ fn test_git_push_sign_on_push() {
     Updated signatures of 2 commits
     Rebased 2 descendant commits
     Changes to push to origin:
-      Move forward bookmark bookmark2 from 38a204733702 to d45e2adce0ad
+      Move forward bookmark bookmark2 from 38a204733702 to d45e2adce0ad (2 new commits)
     Working copy  (@) now at: kmkuslsw 3d5a9465 (empty) commit which should not be signed 2
     Parent commit (@-)      : kpqxywon 48ea83e9 (empty) commit which should not be signed 1
     [EOF]
@@ -2148,7 +2377,7 @@ fn test_git_push_sign_on_push() {
     Warning: Refusing to create new remote bookmark bookmark3@origin
     Hint: Run `jj bookmark track bookmark3@origin` and try again.
     Changes to push to origin:
-      Move forward bookmark bookmark2 from d45e2adce0ad to 48ea83e9499c
+      Move forward bookmark bookmark2 from d45e2adce0ad to 48ea83e9499c (1 new commits)
     [EOF]
     ");
     let output = work_dir.run_jj(["log", "-T", template, "-r", "::"]);
@@ -2225,7 +2454,7 @@ fn test_git_push_rejected_by_remote() {
         insta::assert_snapshot!(output, @r"
         ------- stderr -------
         Changes to push to origin:
-          Move forward bookmark bookmark1 from 9b2e76de3920 to 0fc4cf312e83
+          Move forward bookmark bookmark1 from 9b2e76de3920 to 0fc4cf312e83 (1 new commits)
         remote: error: hook declined to update refs/heads/bookmark1
         Error: Failed to push some bookmarks
         Hint: The remote rejected the following updates:
@@ -0,0 +1,152 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gix::object::tree::EntryKind;
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+/// Creates a submodule repository with two commits, and a superproject
+/// repository with a commit pointing at each of them. Returns the ids of the
+/// superproject commits.
+fn set_up_superproject(test_env: &TestEnvironment) -> (String, String) {
+    let sub_path = test_env.env_root().join("sub");
+    let sub_repo = git::init(&sub_path);
+    let sub1 = git::add_commit(&sub_repo, "refs/heads/main", "file", b"1\n", "first", &[]);
+    let sub2 = git::add_commit(
+        &sub_repo,
+        "refs/heads/main",
+        "file",
+        b"2\n",
+        "second",
+        &[sub1.commit_id],
+    );
+
+    let repo = git::init(test_env.env_root().join("repo"));
+    let gitmodules = "[submodule \"sub\"]\n\tpath = sub\n\turl = ../sub\n";
+    let gitmodules_id = repo.write_blob(gitmodules).unwrap().detach();
+    let mut parents = vec![];
+    for sub_commit in [sub1.commit_id, sub2.commit_id] {
+        let mut editor = repo
+            .edit_tree(gix::ObjectId::empty_tree(repo.object_hash()))
+            .unwrap();
+        editor
+            .upsert(".gitmodules", EntryKind::Blob, gitmodules_id)
+            .unwrap();
+        editor.upsert("sub", EntryKind::Commit, sub_commit).unwrap();
+        let tree_id = editor.write().unwrap().detach();
+        let commit_id = git::write_commit(&repo, "refs/heads/main", tree_id, "update", &parents);
+        parents = vec![commit_id];
+    }
+    let commit2 = parents[0];
+    let commit1 = repo
+        .find_commit(commit2)
+        .unwrap()
+        .parent_ids()
+        .next()
+        .unwrap();
+    (commit1.to_string(), commit2.to_string())
+}
+
+#[test]
+fn test_git_submodule_checkout_and_status() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.update-submodules = true");
+    let (commit1, commit2) = set_up_superproject(&test_env);
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["git", "init", "--colocate"]).success();
+
+    let output = work_dir.run_jj(["new", &commit1]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    ignoring git submodule at "sub"
+    Working copy  (@) now at: rlvkpnrz efa48bf1 (empty) (no description set)
+    Parent commit (@-)      : xoswyozt 3966829f update
+    Added 2 files, modified 0 files, removed 0 files
+    Checked out submodule sub at c04a5c3ebc6e
+    [EOF]
+    "#);
+    assert_eq!(work_dir.read_file("sub/file"), "1\n");
+
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Submodules:
+      sub at c04a5c3ebc6e
+    Working copy  (@) : rlvkpnrz efa48bf1 (empty) (no description set)
+    Parent commit (@-): xoswyozt 3966829f update
+    [EOF]
+    ");
+
+    // Submodule checked out at a different commit
+    let output = work_dir.run_jj(["new", &commit2]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    ignoring git submodule at "sub"
+    Working copy  (@) now at: zsuskuln 42368e83 (empty) (no description set)
+    Parent commit (@-)      : vnwluklv c2b92445 main | update
+    Added 0 files, modified 1 files, removed 0 files
+    Checked out submodule sub at dff2560aad13
+    [EOF]
+    "#);
+    assert_eq!(work_dir.read_file("sub/file"), "2\n");
+
+    let output = work_dir.run_jj(["diff", "--git", "--submodule=log", "-r", &commit2]);
+    insta::assert_snapshot!(output, @r"
+    Submodule sub c04a5c3ebc6e..dff2560aad13:
+      > second
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--submodule=log", "-r", &commit1]);
+    insta::assert_snapshot!(output, @r#"
+    Added regular file .gitmodules:
+            1: [submodule "sub"]
+            2: 	path = sub
+            3: 	url = ../sub
+    Submodule sub c04a5c3ebc6e (new submodule)
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_git_submodule_status_not_checked_out() {
+    let test_env = TestEnvironment::default();
+    let (commit1, _commit2) = set_up_superproject(&test_env);
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["git", "init", "--colocate"]).success();
+    work_dir.run_jj(["new", &commit1]).success();
+
+    // Submodules aren't updated by default, and their checked-out commits
+    // aren't inspected
+    assert!(!work_dir.root().join("sub/file").exists());
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Submodules:
+      sub at c04a5c3ebc6e
+    Working copy  (@) : rlvkpnrz efa48bf1 (empty) (no description set)
+    Parent commit (@-): xoswyozt 3966829f update
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["status", "--config=git.update-submodules=true"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Submodules:
+      sub at c04a5c3ebc6e (not checked out)
+    Working copy  (@) : rlvkpnrz efa48bf1 (empty) (no description set)
+    Parent commit (@-): xoswyozt 3966829f update
+    [EOF]
+    ");
+}
@@ -0,0 +1,200 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use testutils::git;
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+fn add_commit_to_branch(
+    git_repo: &gix::Repository,
+    branch: &str,
+    file: &str,
+    message: &str,
+) -> gix::ObjectId {
+    let parents = git_repo
+        .find_reference(&format!("refs/heads/{branch}"))
+        .ok()
+        .and_then(|mut r| r.peel_to_commit().ok())
+        .map(|c| vec![c.id().detach()])
+        .unwrap_or_default();
+    git::add_commit(
+        git_repo,
+        &format!("refs/heads/{branch}"),
+        file,
+        message.as_bytes(),
+        message,
+        &parents,
+    )
+    .commit_id
+}
+
+fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
+    let template = r#"description.first_line() ++ " " ++ bookmarks"#;
+    work_dir.run_jj(["log", "-T", template, "-r", "all()"])
+}
+
+#[test]
+fn test_git_sync() {
+    let test_env = TestEnvironment::default();
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    add_commit_to_branch(&git_repo, "main", "file", "trunk 1");
+    add_commit_to_branch(&git_repo, "feature", "feature", "feature");
+    test_env
+        .run_jj_in(".", ["git", "clone", "origin", "local"])
+        .success();
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["bookmark", "track", "feature@origin"])
+        .success();
+
+    // A stack based on trunk, and a stack based on someone else's bookmark
+    work_dir
+        .run_jj(["new", "main@origin", "-m=mine 1"])
+        .success();
+    work_dir.write_file("mine", "1");
+    work_dir.run_jj(["new", "-m=mine 2"]).success();
+    work_dir
+        .run_jj(["new", "feature", "-m=on feature"])
+        .success();
+    work_dir.run_jj(["new", "description('mine 2')"]).success();
+
+    add_commit_to_branch(&git_repo, "main", "file", "trunk 2");
+    git_repo
+        .find_reference("refs/heads/feature")
+        .unwrap()
+        .delete()
+        .unwrap();
+
+    let output = work_dir.run_jj(["git", "sync"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: feature@origin [deleted] untracked
+    bookmark: main@origin    [updated] untracked
+    Abandoned 1 commits that are no longer reachable.
+    Rebased 3 commits onto b0d78ac975f3
+    Rebased 1 descendant commits
+    Working copy  (@) now at: royxmykx 439b6724 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln fef7ac33 (empty) mine 2
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @
+    ○  mine 2
+    ○  mine 1
+    ◆  trunk 2 main@origin
+    ◆  trunk 1
+    │ ○  on feature
+    ├─╯
+    ◆
+    [EOF]
+    ");
+
+    // The sync is a single operation
+    work_dir.run_jj(["undo"]).success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @
+    ○  mine 2
+    ○  mine 1
+    ◆  trunk 1 main@origin
+    │ ○  on feature
+    │ ○  feature feature
+    ├─╯
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_sync_onto() {
+    let test_env = TestEnvironment::default();
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    add_commit_to_branch(&git_repo, "main", "file", "trunk 1");
+    add_commit_to_branch(&git_repo, "release", "release", "release 1");
+    test_env
+        .run_jj_in(".", ["git", "clone", "origin", "local"])
+        .success();
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["bookmark", "track", "release@origin"])
+        .success();
+    work_dir.run_jj(["new", "release", "-m=fix"]).success();
+    add_commit_to_branch(&git_repo, "release", "release", "release 2");
+
+    let output = work_dir.run_jj(["git", "sync", "--onto=release@origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: release@origin [updated] tracked
+    Rebased 1 commits onto 1cb360f4a684
+    Working copy  (@) now at: kkmpptxz 361c7326 (empty) fix
+    Parent commit (@-)      : kxvnnnly 1cb360f4 release | release 2
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  fix
+    ○  release 2 release
+    ○  release 1
+    │ ◆  trunk 1 main@origin
+    ├─╯
+    ◆
+    [EOF]
+    ");
+
+    // The destination must resolve to a single revision
+    let output = work_dir.run_jj(["git", "sync", "--onto=all()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revset `all()` resolved to 5 revisions after fetching, expected one
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_git_sync_conflicts() {
+    let test_env = TestEnvironment::default();
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    add_commit_to_branch(&git_repo, "main", "file", "trunk 1");
+    test_env
+        .run_jj_in(".", ["git", "clone", "origin", "local"])
+        .success();
+    let work_dir = test_env.work_dir("local");
+    work_dir.run_jj(["new", "main@origin", "-m=mine"]).success();
+    work_dir.write_file("file", "mine\n");
+    add_commit_to_branch(&git_repo, "main", "file", "trunk 2");
+
+    let output = work_dir.run_jj(["git", "sync"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: main@origin [updated] untracked
+    Rebased 1 commits onto b0d78ac975f3
+    Working copy  (@) now at: rlvkpnrz a08d6c2b (conflict) mine
+    Parent commit (@-)      : sxusvyvn b0d78ac9 main@origin | trunk 2
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    New conflicts appeared in 1 commits:
+      rlvkpnrz a08d6c2b (conflict) mine
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new rlvkpnrz
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+}
@@ -0,0 +1,210 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use testutils::git;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+/// Clones a repository with a "feature" bookmark, and points the "origin"
+/// remote to GitHub.
+fn set_up(test_env: &TestEnvironment) -> TestWorkDir<'_> {
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    let main = git::add_commit(&git_repo, "refs/heads/main", "file", b"1", "main", &[]);
+    git::add_commit(
+        &git_repo,
+        "refs/heads/feature",
+        "file",
+        b"2",
+        "Add feature\n\nMore details.",
+        &[main.commit_id],
+    );
+    test_env
+        .run_jj_in(".", ["git", "clone", "origin", "local"])
+        .success();
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["bookmark", "track", "feature@origin"])
+        .success();
+    work_dir.run_jj(["new", "feature"]).success();
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "set-url",
+            "origin",
+            "https://github.com/owner/repo.git",
+        ])
+        .success();
+    work_dir
+}
+
+#[test]
+fn test_github_pr_errors() {
+    let test_env = TestEnvironment::default();
+    let work_dir = set_up(&test_env);
+
+    let output = work_dir.run_jj(["github", "pr", "status", "--remote=upstream"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No git remote named 'upstream'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "upstream",
+            "https://example.com/repo",
+        ])
+        .success();
+    let output = work_dir.run_jj(["github", "pr", "status", "--remote=upstream"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The remote 'upstream' doesn't point to a GitHub repository
+    Hint: Use `--remote` or the `github.default-remote` config option to choose another remote.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["github", "pr", "status"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No GitHub token is configured
+    Hint: Set the `GITHUB_TOKEN` environment variable, or the `github.token` config option.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj([
+        "github",
+        "pr",
+        "status",
+        "--config=github.token=secret",
+        "--config=github.curl-path=nonexistent-curl",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Could not find curl at `nonexistent-curl`
+    Hint: Install curl, or set the `github.curl-path` config option to its location.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Bookmarks must be pushed before opening pull requests
+    work_dir.run_jj(["describe", "-m=more"]).success();
+    work_dir
+        .run_jj(["bookmark", "set", "feature", "-r@"])
+        .success();
+    let output = work_dir.run_jj(["github", "pr", "create"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Bookmark feature is not pushed to origin
+    Hint: Run `jj git push --remote origin --bookmark feature` first.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir.run_jj(["new", "root()"]).success();
+    let output = work_dir.run_jj(["github", "pr", "create"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No bookmarks point to the selected revisions
+    Hint: Use `jj git push --change` to create and push a bookmark for a revision.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_github_pr_list_and_status() {
+    let test_env = TestEnvironment::default();
+    let work_dir = set_up(&test_env);
+    // Serve the API responses from files
+    let api_dir = test_env.env_root().join("api");
+    std::fs::create_dir_all(api_dir.join("repos/owner/repo")).unwrap();
+    std::fs::write(
+        api_dir.join("repos/owner/repo/pulls"),
+        r#"[
+            {
+                "number": 2,
+                "title": "Someone else's change",
+                "body": null,
+                "html_url": "https://github.com/owner/repo/pull/2",
+                "state": "open",
+                "draft": true,
+                "merged_at": null,
+                "head": {"ref": "other"},
+                "base": {"ref": "main"}
+            },
+            {
+                "number": 1,
+                "title": "Add feature",
+                "body": "More details.",
+                "html_url": "https://github.com/owner/repo/pull/1",
+                "state": "open",
+                "draft": false,
+                "merged_at": null,
+                "head": {"ref": "feature"},
+                "base": {"ref": "main"}
+            }
+        ]"#,
+    )
+    .unwrap();
+    test_env.add_config(format!(
+        r#"
+        github.api-url = "file://{}"
+        github.token = "secret"
+        "#,
+        api_dir.display()
+    ));
+
+    let output = work_dir.run_jj(["github", "pr", "list"]);
+    insta::assert_snapshot!(output, @r"
+    #1 feature -> main: Add feature
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["github", "pr", "list", "--all"]);
+    insta::assert_snapshot!(output, @r"
+    #2 (draft) other -> main: Someone else's change
+    #1 feature -> main: Add feature
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["github", "pr", "status"]);
+    insta::assert_snapshot!(output, @r"
+    feature: #1 open: Add feature
+      https://github.com/owner/repo/pull/1
+    [EOF]
+    ");
+
+    work_dir
+        .run_jj(["bookmark", "create", "unsubmitted", "-r@"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "feature", "-r@", "--allow-backwards"])
+        .success();
+    let output = work_dir.run_jj(["github", "pr", "status", "-b=feature", "-b=unsubmitted"]);
+    insta::assert_snapshot!(output, @r"
+    feature: #1 open: Add feature
+      https://github.com/owner/repo/pull/1
+      The local bookmark has changes not pushed to origin
+    unsubmitted: no pull request
+    [EOF]
+    ");
+}
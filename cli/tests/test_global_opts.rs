@@ -1061,6 +1061,7 @@ fn test_help() {
           --ignore-immutable             Allow rewriting immutable commits
           --at-operation <AT_OPERATION>  Operation to load the repo at [aliases: --at-op]
           --debug                        Enable debug logging
+          --trace-output <FILE>          Write a performance trace to the given file
           --color <WHEN>                 When to colorize output [possible values: always, never, debug,
                                          auto]
           --quiet                        Silence non-primary command output
@@ -1091,3 +1092,31 @@ fn test_debug_logging_enabled() {
     // Luckily, insta will print this in color when reviewing.
     insta::assert_snapshot!(log_line, @"[32m INFO[0m [2mjj_cli::cli_util[0m[2m:[0m debug logging enabled");
 }
+
+#[test]
+fn test_trace_output() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let trace_path = test_env.env_root().join("trace.json");
+
+    work_dir
+        .run_jj(["log", "--trace-output", trace_path.to_str().unwrap()])
+        .success();
+    let trace = std::fs::read_to_string(&trace_path).unwrap();
+    let events: Vec<serde_json::Value> = serde_json::from_str(&trace).unwrap();
+    assert!(
+        events
+            .iter()
+            .any(|event| event["name"] == "snapshot_working_copy")
+    );
+
+    // The trace file must be writable
+    let output = work_dir.run_jj(["log", "--trace-output", "nonexistent/trace.json"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: Failed to create trace file nonexistent/trace.json
+    [EOF]
+    [exit status: 1]
+    ");
+}
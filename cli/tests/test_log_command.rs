@@ -1629,6 +1629,109 @@ fn test_elided() {
     ");
 }
 
+#[test]
+fn test_log_compact_graph() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "c1"]).success();
+    for i in 2..=6 {
+        work_dir.run_jj(["new", "-m", &format!("c{i}")]).success();
+    }
+    work_dir
+        .run_jj(["bookmark", "create", "-r", "description(c2)", "b"])
+        .success();
+
+    let get_log =
+        |args: &[&str]| work_dir.run_jj([&["log", "-T", r#"description ++ "\n""#], args].concat());
+
+    // Runs of at least three revisions are elided, but the bookmarked
+    // revision is kept
+    insta::assert_snapshot!(get_log(&["-r", "::", "--compact-graph"]), @r"
+    @  c6
+    │
+    ~  (3 revisions elided)
+    ○  c2
+    │
+    ○  c1
+    │
+    ◆
+    [EOF]
+    ");
+
+    // Shorter runs are kept
+    insta::assert_snapshot!(get_log(&["-r", "description(c3)::", "--compact-graph"]), @r"
+    @  c6
+    │
+    ○  c5
+    │
+    ○  c4
+    │
+    ○  c3
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--compact-graph", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--compact-graph' cannot be used with '--no-graph'
+
+    Usage: jj log --compact-graph [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_log_colored_lanes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(
+        r#"
+        ui.graph.colored-lanes = true
+        templates.log_node = '"o"'
+        "#,
+    );
+
+    work_dir.run_jj(["describe", "-m", "base"]).success();
+    work_dir.run_jj(["new", "-m", "a"]).success();
+    work_dir.run_jj(["new", "@-", "-m", "b"]).success();
+
+    let output = work_dir.run_jj([
+        "--color=always",
+        "log",
+        "-r",
+        "description(base)::",
+        "-T",
+        "description",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;4mo[39m  b
+    [38;5;4m│[39m [38;5;5mo[39m  a
+    [38;5;4m├[39m[38;5;4m─[39m[38;5;5m╯[39m
+    [38;5;4mo[39m  base
+    [38;5;4m~[39m
+    [EOF]
+    ");
+
+    // Lanes aren't colored without color output
+    let output = work_dir.run_jj(["log", "-r", "description(base)::", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    o  b
+    │ o  a
+    ├─╯
+    o  base
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_with_custom_symbols() {
     // Test that elided commits are shown as synthetic nodes.
@@ -830,6 +830,74 @@ fn test_metaedit_set_same_timestamp_twice() {
     ");
 }
 
+#[test]
+fn test_metaedit_map_authors() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir
+        .run_jj(["metaedit", "--author", "Old One <one@old.example.com>"])
+        .success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir.run_jj(["new", "-m", "third"]).success();
+    work_dir
+        .run_jj(["metaedit", "--author", "Someone Else <else@example.com>"])
+        .success();
+    std::fs::write(
+        test_env.env_root().join("authors"),
+        indoc::indoc! {"
+            # Migrate to the new domain
+            New One <one@new.example.com> <one@old.example.com>
+            <test.user@new.example.com> <TEST.USER@example.com>
+        "},
+    )
+    .unwrap();
+
+    let template = r#"separate(" ",
+      description.first_line() ++ ":",
+      author.name(), author.email(), "/", committer.name(), committer.email(),
+    ) ++ "\n""#;
+    let output = work_dir.run_jj(["metaedit", "--map-authors", "../authors", "::@ ~ root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Modified 3 commits:
+      first
+      second
+      third
+    Working copy  (@) now at: third
+    Parent commit (@-)      : second
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r::@ ~ root()", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    third: Someone Else else@example.com / Test User test.user@new.example.com
+    second: Test User test.user@new.example.com / Test User test.user@new.example.com
+    first: New One one@new.example.com / Test User test.user@new.example.com
+    [EOF]
+    ");
+
+    // Already mapped identities are left alone
+    let output = work_dir.run_jj(["metaedit", "--map-authors", "../authors", "::@ ~ root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Missing mapping file
+    let output = work_dir.run_jj(["metaedit", "--map-authors", "../missing"]);
+    insta::assert_snapshot!(
+        output.normalize_stderr_with(|s| s.split_inclusive('\n').take(1).collect()), @r"
+    ------- stderr -------
+    Error: Failed to read ../missing
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[must_use]
 fn get_log(work_dir: &TestWorkDir) -> CommandOutput {
     work_dir.run_jj([
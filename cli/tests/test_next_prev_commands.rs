@@ -13,6 +13,8 @@
 // limitations under the License.
 //
 
+use itertools::Itertools as _;
+
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
@@ -866,6 +868,46 @@ fn test_next_conflict() {
     ");
 }
 
+#[test]
+fn test_next_hints_conflict() {
+    // Plain `jj next` hints at conflicts further up the stack.
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("content.txt", "first");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    // Create a conflict in the third commit.
+    work_dir.write_file("content.txt", "third");
+    work_dir.run_jj(["commit", "-m", "third"]).success();
+    work_dir.run_jj(["new", "description(first)"]).success();
+    work_dir.write_file("content.txt", "first v2");
+    work_dir
+        .run_jj(["squash", "--into", "description(third)"])
+        .success();
+    let output = work_dir.run_jj(["next"]);
+    let hints = output
+        .stderr
+        .normalized()
+        .lines()
+        .filter(|line| line.starts_with("Hint:"))
+        .join("\n");
+    insta::assert_snapshot!(hints, @"Hint: There are unresolved conflicts in 1 descendant commit. Use `jj next --conflict` to jump to the nearest one.");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  yqosqzytrlsw
+    │ ×  kkmpptxzrspx conflict third
+    ├─╯
+    ○  rlvkpnrzqnoo second
+    ○  qpvuntsmwlqt first
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // No hint once there are no conflicts ahead
+    let output = work_dir.run_jj(["next"]);
+    assert!(!output.stderr.normalized().contains("descendant commit"));
+}
+
 #[test]
 fn test_next_conflict_editing() {
     // There is a conflict in the third commit, so after next it should be our
@@ -0,0 +1,145 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::process::Command;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+fn run_git(work_dir: &TestWorkDir, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args([
+            "-c",
+            "user.name=Git User",
+            "-c",
+            "user.email=git@example.com",
+        ])
+        .args(args)
+        .current_dir(work_dir.root())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_note_add_show_remove() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    let output = work_dir.run_jj(["note", "show"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revision first has no note
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["note", "add", "-m", "build: passed"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added note to first
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["note", "show"]);
+    insta::assert_snapshot!(output, @r"
+    build: passed
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["note", "add", "-m", "build: failed"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revision first already has a note
+    Hint: Use --force to replace it.
+    [EOF]
+    [exit status: 1]
+    ");
+    work_dir
+        .run_jj(["note", "add", "-f", "-m", "build: failed", "-m", "flaky"])
+        .success();
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", "git_note"]);
+    insta::assert_snapshot!(output, @r"
+    build: failed
+
+    flaky
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["note", "remove"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Removed note from first
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", "git_note"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["note", "remove"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revision first has no note
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_note_colocated_git_interop() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+
+    // Notes written by jj can be read by Git
+    work_dir
+        .run_jj(["note", "add", "-r", "@-", "-m", "from jj"])
+        .success();
+    insta::assert_snapshot!(run_git(&work_dir, &["notes", "show", "HEAD"]), @"from jj");
+
+    // Notes written by Git can be read by jj
+    run_git(&work_dir, &["notes", "add", "-m", "from git", "HEAD~1"]);
+    let output = work_dir.run_jj(["note", "list"]);
+    insta::assert_snapshot!(output, @r"
+    second
+      from jj
+    first
+      from git
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["note", "list", "-r", "@--"]);
+    insta::assert_snapshot!(output, @r"
+    first
+      from git
+    [EOF]
+    ");
+
+    // A different notes ref can be configured
+    let output = work_dir.run_jj(["note", "list", "--config=git.notes-ref=refs/notes/ci"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["note", "list", "--config=git.notes-ref=notes"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid notes ref notes
+    Hint: Notes refs must start with `refs/notes/`. Check the `git.notes-ref` config.
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -84,6 +84,50 @@ fn test_revert_rewrite_with_child() {
     ");
 }
 
+#[test]
+fn test_revert_bookmark_move_keeps_later_changes() {
+    // Test that reverting an earlier bookmark move doesn't discard the changes
+    // made by the later operations.
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir
+        .run_jj(["bookmark", "move", "foo", "--to=@"])
+        .success();
+    let move_op_id = work_dir.current_operation_id();
+    work_dir.run_jj(["new", "-m", "c"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "bar"])
+        .success();
+
+    work_dir.run_jj(["op", "revert", &move_op_id]).success();
+    let output = work_dir.run_jj([
+        "bookmark",
+        "list",
+        "-T",
+        r#"name ++ ": " ++ normal_target.description()"#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    bar: c
+    foo: a
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  c
+    ○  b
+    ○  a
+    ◆
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_revert() {
     let test_env = TestEnvironment::default();
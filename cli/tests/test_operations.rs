@@ -907,6 +907,85 @@ fn test_op_abandon_multiple_heads() {
     ");
 }
 
+#[test]
+fn test_op_prune() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let secondary_dir = test_env.work_dir("secondary");
+    let op_log_template = r#"description.first_line() ++ "\n""#;
+
+    work_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    work_dir.run_jj(["commit", "-m", "commit 2"]).success();
+    work_dir.run_jj(["commit", "-m", "commit 3"]).success();
+
+    let output = work_dir.run_jj(["op", "prune"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No retention policy is specified
+    Hint: Use `--keep-count` or `--keep-since`, or set `operation.retention.keep-count` or `operation.retention.keep-since`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["op", "prune", "--keep-count=2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 2 operations and reparented 2 descendant operations.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "--no-graph", "-T", op_log_template]);
+    insta::assert_snapshot!(output, @r"
+    commit 4b087e94a5d14530c3953d617623d075a13294c8
+    commit 4e0592f3dd52e7a4998a97d9a1f354e2727a856b
+
+    [EOF]
+    ");
+
+    // The retention policy can be configured
+    let output = work_dir.run_jj([
+        "op",
+        "prune",
+        "--config=operation.retention.keep-since='2000-01-01'",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Can't abandon the operation of another workspace's working copy
+    work_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+    let secondary_op_id = work_dir.current_operation_id();
+    work_dir.run_jj(["commit", "-m", "commit 4"]).success();
+    let output = work_dir.run_jj(["op", "prune", "--keep-count=1"]);
+    insta::assert_snapshot!(
+        output.normalize_stderr_with(|s| s.replace(&secondary_op_id[..12], "<OP>")), @r"
+    ------- stderr -------
+    Error: The working copy of workspace secondary is at operation <OP>, which would be abandoned
+    Hint: Run `jj status` in the workspace to update the working copy, then try again.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The working copy of the other workspace is updated
+    secondary_dir.run_jj(["status"]).success();
+    let output = work_dir.run_jj(["op", "prune", "--keep-count=1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 4 operations and reparented 1 descendant operations.
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["op", "log", "--no-graph", "-T", r#""op\n""#]);
+    insta::assert_snapshot!(output, @r"
+    op
+    op
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_recover_from_bad_gc() {
     let test_env = TestEnvironment::default();
@@ -1523,7 +1602,7 @@ fn test_op_diff() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark-1 from 0dee631320b1 to 731ab19950fc
+      Move forward bookmark bookmark-1 from 0dee631320b1 to 731ab19950fc (1 new commits)
       Delete bookmark bookmark-2 from e1a239a57eb1
     [EOF]
     ");
@@ -2572,7 +2651,7 @@ fn test_op_show() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark-1 from 0dee631320b1 to 8f340dd76dc6
+      Move forward bookmark bookmark-1 from 0dee631320b1 to 8f340dd76dc6 (1 new commits)
       Delete bookmark bookmark-2 from e1a239a57eb1
     [EOF]
     ");
@@ -862,6 +862,50 @@ fn test_rebase_multiple_destinations() {
     ");
 }
 
+#[test]
+fn test_rebase_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    let setup_log = get_log_output(&work_dir);
+    let setup_op_log = work_dir.run_jj(["op", "log"]);
+
+    let output = work_dir.run_jj(["rebase", "--dry-run", "-r", "b", "-o", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    Changed commits:
+    ○  + royxmykx 65cd964b c | c
+       - royxmykx hidden dffaa0d4 c
+    ○  + zsuskuln 5c6d09da b | b
+       - zsuskuln hidden 123b4d91 b
+
+    Changed working copy default@:
+    + royxmykx 65cd964b c | c
+    - royxmykx hidden dffaa0d4 c
+
+    Changed local bookmarks:
+    b:
+    + zsuskuln 5c6d09da b | b
+    - zsuskuln hidden 123b4d91 b
+    c:
+    + royxmykx 65cd964b c | c
+    - royxmykx hidden dffaa0d4 c
+    [EOF]
+    ------- stderr -------
+    Would rebase 1 commits to destination
+    Would rebase 1 descendant commits
+    Dry run: no changes were made.
+    [EOF]
+    ");
+
+    // Nothing changed, and no operation was recorded
+    assert_eq!(get_log_output(&work_dir), setup_log);
+    assert_eq!(work_dir.run_jj(["op", "log"]), setup_op_log);
+}
+
 #[test]
 fn test_rebase_with_descendants() {
     let test_env = TestEnvironment::default();
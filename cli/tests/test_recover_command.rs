@@ -0,0 +1,150 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_recover() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.add_config(r#"templates.op_summary = 'description.first_line().substr(0, 14)'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let log_template = r#"
+        "[" ++ description.first_line() ++ "] <- ["
+          ++ parents.map(|c| c.description().first_line()).join(", ") ++ "]\n"
+    "#;
+
+    let output = work_dir.run_jj(["recover"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No recently hidden changes.
+    [EOF]
+    ");
+
+    work_dir.write_file("file1", "1\n");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.write_file("file2", "2\n");
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    work_dir.write_file("file3", "3\n");
+    work_dir.run_jj(["commit", "-m", "third"]).success();
+    let second_id = work_dir
+        .run_jj([
+            "log",
+            "--no-graph",
+            "--color=never",
+            "-T=commit_id",
+            "-r=@--",
+        ])
+        .success()
+        .stdout
+        .into_raw();
+
+    work_dir.run_jj(["abandon", "@--"]).success();
+    // Rewritten and empty undescribed commits are not listed
+    work_dir
+        .run_jj(["describe", "@--", "-m", "first v2"])
+        .success();
+    work_dir.run_jj(["new", "@-"]).success();
+    let output = work_dir.run_jj(["recover"]);
+    insta::assert_snapshot!(output, @r"
+    second
+      Hidden by: abandon commit
+    [EOF]
+    ------- stderr -------
+    Hint: Use `jj recover <commit id>` to make a revision visible again.
+    [EOF]
+    ");
+
+    // The recovered commit is rebased onto the new version of its parent
+    let output = work_dir.run_jj(["recover", &second_id]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Recovered 1 revisions:
+      second
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", log_template]);
+    insta::assert_snapshot!(output, @r"
+    [second] <- [first v2]
+    [] <- [third]
+    [third] <- [first v2]
+    [first v2] <- []
+    [] <- []
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["recover"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No recently hidden changes.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["recover", "@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Skipping 1 revisions that are already visible.
+    No revisions to recover.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_recover_with_hidden_parent() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let log_template = r#"
+        "[" ++ description.first_line() ++ "] <- ["
+          ++ parents.map(|c| c.description().first_line()).join(", ") ++ "]\n"
+    "#;
+
+    work_dir.write_file("file1", "1\n");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.write_file("file2", "2\n");
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    work_dir.write_file("file3", "3\n");
+    work_dir.run_jj(["commit", "-m", "third"]).success();
+    let third_id = work_dir
+        .run_jj([
+            "log",
+            "--no-graph",
+            "--color=never",
+            "-T=commit_id",
+            "-r=@-",
+        ])
+        .success()
+        .stdout
+        .into_raw();
+
+    work_dir.run_jj(["abandon", "@--", "@-"]).success();
+    // The abandoned parent stays hidden; the recovered commit is rebased onto
+    // its nearest visible ancestor instead
+    let output = work_dir.run_jj(["recover", &third_id]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Recovered 1 revisions:
+      third
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", log_template]);
+    insta::assert_snapshot!(output, @r"
+    [third] <- [first]
+    [] <- [first]
+    [first] <- []
+    [] <- []
+    [EOF]
+    ");
+}
@@ -14,12 +14,16 @@
 
 use std::path::Path;
 
+use indoc::formatdoc;
 use indoc::indoc;
 
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
 use crate::common::create_commit_with_files;
+use crate::common::fake_editor_path;
+use crate::common::force_interactive;
+use crate::common::to_toml_value;
 
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
@@ -2186,3 +2190,442 @@ fn test_resolve_with_contents_of_side() {
     [exit status: 2]
     "#);
 }
+
+#[test]
+fn test_resolve_with_merge_driver() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "base\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+    work_dir.run_jj(["new", "root()"]).success();
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list", "-r", "conflict"]), @r"
+    file    2-sided conflict
+    [EOF]
+    ");
+
+    // The driver resolves the existing conflict without a merge tool
+    let union_config = r#"merge.drivers.union={patterns=["file"], strategy="union"}"#;
+    let output = work_dir.run_jj(["resolve", "-r", "conflict", "--config", union_config]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["file", "show", "-r", "conflict", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    b
+    [EOF]
+    ");
+
+    // Merges made while the driver is configured don't conflict
+    work_dir
+        .run_jj(["new", "a", "b", "--config", union_config])
+        .success();
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    a
+    b
+    ");
+
+    // A failing command leaves the conflict in place
+    let output = work_dir.run_jj([
+        "new",
+        "a",
+        "b",
+        "--config",
+        r#"merge.drivers.fail={patterns=["file"], command=["false"]}"#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: kxryzmor c7176c24 (conflict) (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 45537d53 a | a
+    Parent commit (@-)      : royxmykx 89d1b299 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_resolve_with_merge_driver_command() {
+    let mut test_env = TestEnvironment::default();
+    let editor_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "base\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "b\n")]);
+
+    std::fs::write(&editor_script, "write\nmerged\n").unwrap();
+    let editor_path = to_toml_value(fake_editor_path());
+    test_env.add_config(formatdoc! {r#"
+        [merge.drivers.tool]
+        patterns = ["file"]
+        command = [{editor_path}, "$output"]
+    "#});
+    work_dir.run_jj(["new", "a", "b"]).success();
+    insta::assert_snapshot!(work_dir.read_file("file"), @"merged");
+}
+
+#[test]
+fn test_resolve_take() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(
+        &work_dir,
+        "base",
+        &[],
+        &[("file1", "base1\n"), ("file2", "base2\n")],
+    );
+    create_commit_with_files(
+        &work_dir,
+        "a",
+        &["base"],
+        &[("file1", "a1\n"), ("file2", "a2\n")],
+    );
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file1", "b1\n")]);
+    work_dir.remove_file("file2");
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list"]), @r"
+    file1    2-sided conflict
+    file2    2-sided conflict including 1 deletion
+    [EOF]
+    ");
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["resolve", "--take=right"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv fffa3cc1 conflict | conflict
+    Parent commit (@-)      : zsuskuln b540ca46 a | a
+    Parent commit (@-)      : royxmykx ef7033ca b | b
+    Added 0 files, modified 1 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["file", "list"]), @r"
+    file1
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file1"), @"b1");
+
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj(["resolve", "--take=base", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv 79679ecb conflict | (conflict) conflict
+    Parent commit (@-)      : zsuskuln b540ca46 a | a
+    Parent commit (@-)      : royxmykx ef7033ca b | b
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file1    2-sided conflict
+    New conflicts appeared in 1 commits:
+      vruxwmqv 79679ecb conflict | (conflict) conflict
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new vruxwmqv
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file2"), @"base2");
+
+    // --take can't be combined with --tool
+    let output = work_dir.run_jj(["resolve", "--take=left", "--tool=:ours"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--take <SIDE>' cannot be used with '--tool <NAME>'
+
+    Usage: jj resolve --take <SIDE> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_resolve_interactive() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "1\n2\n3\n4\n5\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "1a\n2\n3\n4\n5a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "1b\n2\n3\n4\n5b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    // Take side #1 for the first hunk and leave the second one conflicted
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["resolve", "--interactive"])
+            .write_stdin("1\ns\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Conflict 1 of 2 in file:
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -1
+    +1a
+    +++++++ Contents of side #2
+    1b
+    >>>>>>> Conflict 1 of 1 ends
+    Take side (1/2), (b)ase, (e)dit, (s)kip, or (q)uit: Conflict 2 of 2 in file:
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -5
+    +5a
+    +++++++ Contents of side #2
+    5b
+    >>>>>>> Conflict 1 of 1 ends
+    Take side (1/2), (b)ase, (e)dit, (s)kip, or (q)uit: Working copy  (@) now at: vruxwmqv cb06f9d9 conflict | (conflict) conflict
+    Parent commit (@-)      : zsuskuln fb2325b1 a | a
+    Parent commit (@-)      : royxmykx 9aec7e27 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    New conflicts appeared in 1 commits:
+      vruxwmqv cb06f9d9 conflict | (conflict) conflict
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new vruxwmqv
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    1a
+    2
+    3
+    4
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -5
+    +5a
+    +++++++ Contents of side #2
+    5b
+    >>>>>>> Conflict 1 of 1 ends
+    ");
+
+    // Resolve the remaining hunk with the base
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["resolve", "-i"])
+            .write_stdin("x\nb\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Conflict 1 of 1 in file:
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -5
+    +5a
+    +++++++ Contents of side #2
+    5b
+    >>>>>>> Conflict 1 of 1 ends
+    Take side (1/2), (b)ase, (e)dit, (s)kip, or (q)uit: unrecognized response
+    Take side (1/2), (b)ase, (e)dit, (s)kip, or (q)uit: Working copy  (@) now at: vruxwmqv 37f6dd99 conflict | conflict
+    Parent commit (@-)      : zsuskuln fb2325b1 a | a
+    Parent commit (@-)      : royxmykx 9aec7e27 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    Existing conflicts were resolved or abandoned from 1 commits.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    1a
+    2
+    3
+    4
+    5
+    ");
+}
+
+#[test]
+fn test_resolve_interactive_edit_and_quit() {
+    let mut test_env = TestEnvironment::default();
+    let editor_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "1\n2\n3\n4\n5\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "1a\n2\n3\n4\n5a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "1b\n2\n3\n4\n5b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    // Edit the first hunk and quit before the second one
+    std::fs::write(&editor_script, "write\n1ab\n").unwrap();
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["resolve", "-i"])
+            .write_stdin("e\nq\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Conflict 1 of 2 in file:
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -1
+    +1a
+    +++++++ Contents of side #2
+    1b
+    >>>>>>> Conflict 1 of 1 ends
+    Take side (1/2), (b)ase, (e)dit, (s)kip, or (q)uit: Conflict 2 of 2 in file:
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -5
+    +5a
+    +++++++ Contents of side #2
+    5b
+    >>>>>>> Conflict 1 of 1 ends
+    Take side (1/2), (b)ase, (e)dit, (s)kip, or (q)uit: Working copy  (@) now at: vruxwmqv 68556132 conflict | (conflict) conflict
+    Parent commit (@-)      : zsuskuln fb2325b1 a | a
+    Parent commit (@-)      : royxmykx 9aec7e27 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    New conflicts appeared in 1 commits:
+      vruxwmqv 68556132 conflict | (conflict) conflict
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new vruxwmqv
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    1ab
+    2
+    3
+    4
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -5
+    +5a
+    +++++++ Contents of side #2
+    5b
+    >>>>>>> Conflict 1 of 1 ends
+    ");
+}
+
+#[test]
+fn test_resolve_interactive_edit_with_conflict_markers() {
+    let mut test_env = TestEnvironment::default();
+    let editor_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "1\n2\n3\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "1a\n2\n3\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "1b\n2\n3\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    // Edit one side of the hunk, but leave the conflict markers in place
+    std::fs::write(
+        &editor_script,
+        indoc! {"
+            write
+            <<<<<<< Conflict 1 of 1
+            %%%%%%% Changes from base to side #1
+            -1
+            +1a
+            +++++++ Contents of side #2
+            1c
+            >>>>>>> Conflict 1 of 1 ends
+        "},
+    )
+    .unwrap();
+    work_dir
+        .run_jj_with(|cmd| {
+            force_interactive(cmd)
+                .args(["resolve", "-i"])
+                .write_stdin("e\n")
+        })
+        .success();
+    // The hunk is still conflicted, with the edited contents
+    insta::assert_snapshot!(work_dir.run_jj(["resolve", "--list"]), @r"
+    file    2-sided conflict
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -1
+    +1a
+    +++++++ Contents of side #2
+    1c
+    >>>>>>> Conflict 1 of 1 ends
+    2
+    3
+    ");
+}
+
+#[test]
+fn test_resolve_conflict_marker_style_per_path() {
+    let mut test_env = TestEnvironment::default();
+    let editor_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(indoc! {r#"
+        [ui.conflict-marker-styles.git]
+        patterns = ["glob:'*.git'"]
+        style = "git"
+    "#});
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file.git", "base\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file.git", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file.git", "b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+    let setup_opid = work_dir.current_operation_id();
+
+    // The merge tool gets the conflict markers in the style configured for the
+    // file
+    std::fs::write(
+        &editor_script,
+        ["dump editor", "write\nresolution\n"].join("\0"),
+    )
+    .unwrap();
+    work_dir
+        .run_jj([
+            "resolve",
+            "--config=merge-tools.fake-editor.merge-tool-edits-conflict-markers=true",
+        ])
+        .success();
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r"
+    <<<<<<< Side #1 (Conflict 1 of 1)
+    a
+    ||||||| Base
+    base
+    =======
+    b
+    >>>>>>> Side #2 (Conflict 1 of 1 ends)
+    ");
+
+    // The style configured for the merge tool takes precedence
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj([
+            "resolve",
+            "--config=merge-tools.fake-editor.merge-tool-edits-conflict-markers=true",
+            "--config=merge-tools.fake-editor.conflict-marker-style=snapshot",
+        ])
+        .success();
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r"
+    <<<<<<< Conflict 1 of 1
+    +++++++ Contents of side #1
+    a
+    ------- Contents of base
+    base
+    +++++++ Contents of side #2
+    b
+    >>>>>>> Conflict 1 of 1 ends
+    ");
+}
@@ -354,6 +354,65 @@ fn test_revert_description_template() {
     "#);
 }
 
+#[test]
+fn test_revert_paths() {
+    let mut test_env = TestEnvironment::default();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit_with_files(
+        &work_dir,
+        "base",
+        &[],
+        &[("file1", "1\n"), ("file2", "1\n")],
+    );
+    create_commit_with_files(
+        &work_dir,
+        "a",
+        &["base"],
+        &[("file1", "2\n"), ("file2", "2\n")],
+    );
+    let setup_opid = work_dir.current_operation_id();
+
+    // Revert only the given paths
+    work_dir.run_jj(["revert", "-ra", "-d@", "file1"]).success();
+    let output = work_dir.run_jj(["diff", "-r@+", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r@+", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    1
+    [EOF]
+    ");
+
+    // Nothing is created if no changes are selected
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj(["revert", "-ra", "-d@", "nonexistent"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No matching entries for paths: nonexistent
+    No changes to revert.
+    [EOF]
+    ");
+
+    // Interactively select the changes to revert
+    let diff_script = [
+        "files-before file1 file2",
+        "files-after JJ-INSTRUCTIONS file1 file2",
+        "reset file2",
+    ]
+    .join("\0");
+    std::fs::write(diff_editor, diff_script).unwrap();
+    work_dir.run_jj(["revert", "-ra", "-d@", "-i"]).success();
+    let output = work_dir.run_jj(["diff", "-r@+", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"commit_id.short() ++ " " ++ description"#;
@@ -86,8 +86,8 @@ fn test_show_basic() {
     insta::assert_snapshot!(output, @r"
     <<show commit::Commit ID: >>[38;5;4m<<show commit commit_id::92e687faa4e5b681937f5a9c47feaa33e6b4892c>>[39m<<show commit::>>
     <<show commit::Change ID: >>[38;5;5m<<show commit change_id::rlvkpnrzqnoowoytxnquwvuryrwnrmlp>>[39m<<show commit::>>
-    <<show commit::Author   : >>[38;5;3m<<show commit author name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit author email local::test.user>><<show commit author email::@>><<show commit author email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit author timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
-    <<show commit::Committer: >>[38;5;3m<<show commit committer name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit committer email local::test.user>><<show commit committer email::@>><<show commit committer email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit committer timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
+    <<show commit::Author   : >>[38;5;3m<<show commit author mapped name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit author mapped email local::test.user>><<show commit author mapped email::@>><<show commit author mapped email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit author mapped timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
+    <<show commit::Committer: >>[38;5;3m<<show commit committer mapped name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit committer mapped email local::test.user>><<show commit committer mapped email::@>><<show commit committer mapped email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit committer mapped timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
     <<show commit::>>
     [38;5;3m<<show commit description placeholder::    (no description set)>>[39m<<show commit::>>
     <<show commit::>>
@@ -178,8 +178,8 @@ fn test_show_basic() {
     insta::assert_snapshot!(output, @r"
     <<show commit::Commit ID: >>[38;5;4m<<show commit commit_id::92e687faa4e5b681937f5a9c47feaa33e6b4892c>>[39m<<show commit::>>
     <<show commit::Change ID: >>[38;5;5m<<show commit change_id::rlvkpnrzqnoowoytxnquwvuryrwnrmlp>>[39m<<show commit::>>
-    <<show commit::Author   : >>[38;5;3m<<show commit author name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit author email local::test.user>><<show commit author email::@>><<show commit author email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit author timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
-    <<show commit::Committer: >>[38;5;3m<<show commit committer name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit committer email local::test.user>><<show commit committer email::@>><<show commit committer email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit committer timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
+    <<show commit::Author   : >>[38;5;3m<<show commit author mapped name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit author mapped email local::test.user>><<show commit author mapped email::@>><<show commit author mapped email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit author mapped timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
+    <<show commit::Committer: >>[38;5;3m<<show commit committer mapped name::Test User>>[39m<<show commit:: <>>[38;5;3m<<show commit committer mapped email local::test.user>><<show commit committer mapped email::@>><<show commit committer mapped email domain::example.com>>[39m<<show commit::> (>>[38;5;6m<<show commit committer mapped timestamp local format::2001-02-03 08:05:09>>[39m<<show commit::)>>
     <<show commit::>>
     [38;5;3m<<show commit description placeholder::    (no description set)>>[39m<<show commit::>>
     <<show commit::>>
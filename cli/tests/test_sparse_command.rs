@@ -201,3 +201,130 @@ fn test_sparse_editor_avoids_unc() {
     // over 260 chars.
     assert_eq!(edited_path, dunce::simplified(&edited_path));
 }
+
+#[test]
+fn test_sparse_cone_mode() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "contents");
+    work_dir.write_file("a/file", "contents");
+    work_dir.write_file("a/b/file", "contents");
+    work_dir.write_file("a/b/c/file", "contents");
+    work_dir.write_file("a/other/file", "contents");
+    work_dir.write_file("other/file", "contents");
+
+    // Files in the parent directories of the pattern are included too
+    let output = work_dir.run_jj(["sparse", "set", "--clear", "--add", "a/b", "--cone"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    assert!(work_dir.root().join("file").exists());
+    assert!(work_dir.root().join("a/file").exists());
+    assert!(work_dir.root().join("a/b/file").exists());
+    assert!(work_dir.root().join("a/b/c/file").exists());
+    assert!(!work_dir.root().join("a/other").exists());
+    assert!(!work_dir.root().join("other").exists());
+
+    // The mode is kept when the patterns are changed
+    let output = work_dir.run_jj(["sparse", "set", "--add", "other"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    a/b
+    other
+    [EOF]
+    ");
+
+    // Switching back to prefix mode removes the files in the parent directories
+    let output = work_dir.run_jj(["sparse", "set", "--no-cone"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    assert!(!work_dir.root().join("file").exists());
+    assert!(!work_dir.root().join("a/file").exists());
+    assert!(work_dir.root().join("a/b/file").exists());
+
+    // Snapshots don't consider the missing files deleted
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    A a/b/c/file
+    A a/b/file
+    A a/file
+    A a/other/file
+    A file
+    A other/file
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_sparse_git_import_export() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let sparse_checkout_path = work_dir.root().join(".git/info/sparse-checkout");
+
+    work_dir.write_file("file", "contents");
+    work_dir.write_file("a/b/file", "contents");
+    work_dir.write_file("c/file", "contents");
+
+    // Nothing to import yet
+    let output = work_dir.run_jj(["sparse", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The Git repository doesn't have a sparse-checkout file
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir
+        .run_jj(["sparse", "set", "--clear", "--add", "a/b", "--cone"])
+        .success();
+    work_dir.run_jj(["sparse", "export"]).success();
+    insta::assert_snapshot!(std::fs::read_to_string(&sparse_checkout_path).unwrap(), @r"
+    /*
+    !/*/
+    /a/
+    !/a/*/
+    /a/b/
+    ");
+
+    // Patterns written by `git sparse-checkout set --cone` can be imported
+    std::fs::write(&sparse_checkout_path, "/*\n!/*/\n/c/\n").unwrap();
+    let output = work_dir.run_jj(["sparse", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 1 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    c
+    [EOF]
+    ");
+    assert!(work_dir.root().join("file").exists());
+    assert!(!work_dir.root().join("a").exists());
+    assert!(work_dir.root().join("c/file").exists());
+
+    std::fs::write(&sparse_checkout_path, "*.txt\n").unwrap();
+    let output = work_dir.run_jj(["sparse", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Unsupported sparse-checkout pattern: *.txt
+    Hint: Only cone mode patterns and patterns like `/foo/bar` that select a path and everything under it are supported.
+    [EOF]
+    [exit status: 1]
+    ");
+}
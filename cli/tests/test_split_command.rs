@@ -197,6 +197,57 @@ fn test_split_by_paths() {
     ");
 }
 
+#[test]
+fn test_split_patch_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "a\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.write_file("file2", "b\n");
+
+    work_dir.write_file(
+        "../select.patch",
+        indoc::indoc! {"
+            diff --git a/file1 b/file1
+            --- a/file1
+            +++ b/file1
+            @@ -1 +1 @@
+            -a
+            +b
+        "},
+    );
+    work_dir
+        .run_jj(["split", "--patch-file", "../select.patch", "-m", "first"])
+        .success();
+    let output = work_dir.run_jj(["diff", "--summary", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file2
+    [EOF]
+    ");
+
+    // The patch can't be combined with interactive selection
+    let output = work_dir.run_jj(["split", "--patch-file", "../select.patch", "-i"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--patch-file <PATH>' cannot be used with '--interactive'
+
+    Usage: jj split --patch-file <PATH> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_split_with_non_empty_description() {
     let mut test_env = TestEnvironment::default();
@@ -416,6 +416,115 @@ fn test_squash_keep_emptied() {
     ");
 }
 
+#[test]
+fn test_squash_patch_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n");
+    work_dir.write_file("file2", "a\n");
+    work_dir.run_jj(["commit", "-m", "parent"]).success();
+    work_dir.write_file("file1", "one\n2\n3\n4\n5\n6\n7\n8\n9\nten\n");
+    work_dir.write_file("file2", "b\n");
+
+    // Only the first hunk of file1 is moved into the parent
+    work_dir.write_file(
+        "../select.patch",
+        indoc::indoc! {"
+            diff --git a/file1 b/file1
+            --- a/file1
+            +++ b/file1
+            @@ -1,4 +1,4 @@
+            -1
+            +one
+             2
+             3
+             4
+        "},
+    );
+    work_dir
+        .run_jj(["squash", "--patch-file", "../select.patch"])
+        .success();
+    let output = work_dir.run_jj(["file", "show", "-r@-", "file1", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    one
+    2
+    3
+    4
+    5
+    6
+    7
+    8
+    9
+    10
+    a
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git", "--context=0", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1 b/file1
+    index 1b6662b767..c261f3550a 100644
+    --- a/file1
+    +++ b/file1
+    @@ -10,1 +10,1 @@
+    -10
+    +ten
+    [EOF]
+    ");
+
+    // The patch must apply to the parent of the source
+    work_dir.write_file(
+        "../reject.patch",
+        indoc::indoc! {"
+            diff --git a/file2 b/file2
+            --- a/file2
+            +++ b/file2
+            @@ -1 +1 @@
+            -x
+            +b
+        "},
+    );
+    let output = work_dir.run_jj(["squash", "--patch-file", "../reject.patch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to apply the patch:
+      file2: Hunk #1 doesn't match
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The patch can't introduce changes that aren't in the source
+    work_dir.write_file(
+        "../extra.patch",
+        indoc::indoc! {"
+            diff --git a/file2 b/file2
+            --- a/file2
+            +++ b/file2
+            @@ -1 +1 @@
+            -a
+            +c
+        "},
+    );
+    let output = work_dir.run_jj(["squash", "--patch-file", "../extra.patch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The patch contains changes that are not in the diff:
+      file2
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir.write_file("../empty.patch", "");
+    let output = work_dir.run_jj(["squash", "--patch-file", "../empty.patch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes found in the patch
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_squash_from_to() {
     let test_env = TestEnvironment::default();
@@ -1557,6 +1666,94 @@ fn test_squash_empty() {
     ");
 }
 
+#[test]
+fn test_squash_dry_run() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    std::fs::write(&edit_script, "fail").unwrap();
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["describe", "-m", "destination"]).success();
+    work_dir.run_jj(["new", "-m", "source"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.run_jj(["new"]).success();
+    let setup_log = get_log_output(&work_dir);
+    let setup_op_log = work_dir.run_jj(["op", "log"]);
+
+    // Both descriptions are non-empty, but the editor isn't opened
+    let output = work_dir.run_jj(["squash", "--dry-run", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    Changed commits:
+    ○  + zsuskuln 0fe7cc72 (empty) (no description set)
+       - zsuskuln hidden de2c46a7 (empty) (no description set)
+    ○  + qpvuntsm f23607cc destination
+       - qpvuntsm hidden b1700263 destination
+       - kkmpptxz hidden 65a0980a source
+
+    Changed working copy default@:
+    + zsuskuln 0fe7cc72 (empty) (no description set)
+    - zsuskuln hidden de2c46a7 (empty) (no description set)
+    [EOF]
+    ------- stderr -------
+    Would rebase 1 descendant commits
+    Dry run: no changes were made.
+    [EOF]
+    ");
+
+    // Same with an explicit --editor
+    let output = work_dir.run_jj([
+        "squash",
+        "--dry-run",
+        "--editor",
+        "-r",
+        "@-",
+        "-m",
+        "custom",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    Changed commits:
+    ○  + zsuskuln f056f0d3 (empty) (no description set)
+       - zsuskuln hidden de2c46a7 (empty) (no description set)
+    ○  + qpvuntsm 834fb278 custom
+       - qpvuntsm hidden b1700263 destination
+       - kkmpptxz hidden 65a0980a source
+
+    Changed working copy default@:
+    + zsuskuln f056f0d3 (empty) (no description set)
+    - zsuskuln hidden de2c46a7 (empty) (no description set)
+    [EOF]
+    ------- stderr -------
+    Would rebase 1 descendant commits
+    Dry run: no changes were made.
+    [EOF]
+    ");
+
+    // Inserting a new commit
+    let output = work_dir.run_jj(["squash", "--dry-run", "--from", "@-", "--insert-after", "@"]);
+    insta::assert_snapshot!(output, @r"
+    Changed commits:
+    ○  + yostqsxw 642b2790 source
+       - kkmpptxz hidden 65a0980a source
+    ○  + zsuskuln e4b4cf66 (empty) (no description set)
+       - zsuskuln hidden de2c46a7 (empty) (no description set)
+
+    Changed working copy default@:
+    + zsuskuln e4b4cf66 (empty) (no description set)
+    - zsuskuln hidden de2c46a7 (empty) (no description set)
+    [EOF]
+    ------- stderr -------
+    Would create new commit yostqsxw 642b2790 source
+    Dry run: no changes were made.
+    [EOF]
+    ");
+
+    // Nothing changed, and no operation was recorded
+    assert_eq!(get_log_output(&work_dir), setup_log);
+    assert_eq!(work_dir.run_jj(["op", "log"]), setup_op_log);
+}
+
 #[test]
 fn test_squash_use_destination_message() {
     let test_env = TestEnvironment::default();
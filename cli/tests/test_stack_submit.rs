@@ -0,0 +1,184 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use testutils::git;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+/// Clones a repository, and points the "origin" remote to GitHub while still
+/// pushing to the cloned repository.
+fn set_up(test_env: &TestEnvironment) -> TestWorkDir<'_> {
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    git::add_commit(&git_repo, "refs/heads/main", "file", b"1", "main", &[]);
+    test_env
+        .run_jj_in(".", ["git", "clone", "origin", "local"])
+        .success();
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "set-url",
+            "origin",
+            "https://github.com/owner/repo.git",
+        ])
+        .success();
+    let mut git_config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(work_dir.root().join(".jj/repo/store/git/config"))
+        .unwrap();
+    writeln!(
+        git_config,
+        "[remote \"origin\"]\n\tpushurl = {}",
+        test_env.env_root().join("origin").display()
+    )
+    .unwrap();
+    work_dir
+}
+
+#[test]
+fn test_stack_submit_errors() {
+    let test_env = TestEnvironment::default();
+    let work_dir = set_up(&test_env);
+
+    // Nothing to submit
+    let output = work_dir.run_jj(["stack", "submit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No revisions to submit.
+    [EOF]
+    ");
+
+    work_dir.run_jj(["new", "main@origin", "-m=A"]).success();
+    work_dir.run_jj(["new", "main@origin", "-m=B"]).success();
+    let output = work_dir.run_jj(["stack", "submit", "-r=description(A)|description(B)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revision mzvwutvlkqwt is not the only child of revision zsuskulnrvyr, the revisions to submit must form a linear stack
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Nothing is pushed without a token
+    let output = work_dir.run_jj(["stack", "submit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No GitHub token is configured
+    Hint: Set the `GITHUB_TOKEN` environment variable, or the `github.token` config option.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_stack_submit() {
+    let test_env = TestEnvironment::default();
+    let work_dir = set_up(&test_env);
+    work_dir
+        .run_jj(["new", "main@origin", "-m=Add A"])
+        .success();
+    work_dir.write_file("a", "a");
+    work_dir.run_jj(["new", "-m=Add B\n\nDetails."]).success();
+    work_dir.write_file("b", "b");
+    work_dir.run_jj(["new"]).success();
+
+    // Serve the API responses from files, as if the pull requests existed
+    // already
+    let change_ids = work_dir
+        .run_jj([
+            "log",
+            "--no-graph",
+            "-r=@-|@--",
+            "-T=change_id.short() ++ ' '",
+        ])
+        .success()
+        .stdout
+        .into_raw();
+    let [b_change_id, a_change_id] = change_ids.split_whitespace().collect::<Vec<_>>()[..] else {
+        panic!("unexpected change ids: {change_ids}");
+    };
+    let api_dir = test_env.env_root().join("api");
+    std::fs::create_dir_all(api_dir.join("repos/owner/repo")).unwrap();
+    std::fs::write(
+        api_dir.join("repos/owner/repo/pulls"),
+        format!(
+            r#"[
+                {{
+                    "number": 2,
+                    "title": "Add B",
+                    "body": "Details.\n\n<!-- jj stack -->\nStack:\n* #2 (this pull request)\n* #1\n<!-- /jj stack -->",
+                    "html_url": "https://github.com/owner/repo/pull/2",
+                    "state": "open",
+                    "merged_at": null,
+                    "head": {{"ref": "push-{b_change_id}"}},
+                    "base": {{"ref": "push-{a_change_id}"}}
+                }},
+                {{
+                    "number": 1,
+                    "title": "Add A",
+                    "body": "<!-- jj stack -->\nStack:\n* #2\n* #1 (this pull request)\n<!-- /jj stack -->",
+                    "html_url": "https://github.com/owner/repo/pull/1",
+                    "state": "open",
+                    "merged_at": null,
+                    "head": {{"ref": "push-{a_change_id}"}},
+                    "base": {{"ref": "main"}}
+                }}
+            ]"#
+        ),
+    )
+    .unwrap();
+    test_env.add_config(format!(
+        r#"
+        github.api-url = "file://{}"
+        github.token = "secret"
+        "#,
+        api_dir.display()
+    ));
+
+    let output = work_dir.run_jj(["stack", "submit", "--base=main"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Creating bookmark push-kkmpptxzrspx for revision kkmpptxzrspx
+    Creating bookmark push-zsuskulnrvyr for revision zsuskulnrvyr
+    Changes to push to origin:
+      Add bookmark push-kkmpptxzrspx to acc3ba0d2f0f (1 new commits)
+      Add bookmark push-zsuskulnrvyr to 61070e86ab9e (2 new commits)
+    push-kkmpptxzrspx: https://github.com/owner/repo/pull/1
+    push-zsuskulnrvyr: https://github.com/owner/repo/pull/2
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "--all-remotes"]);
+    insta::assert_snapshot!(output, @r"
+    main@origin: umzturwl 23adfa68 main
+    push-kkmpptxzrspx: kkmpptxz acc3ba0d Add A
+      @origin: kkmpptxz acc3ba0d Add A
+    push-zsuskulnrvyr: zsuskuln 61070e86 Add B
+      @origin: zsuskuln 61070e86 Add B
+    [EOF]
+    ");
+
+    // Submitting again doesn't push anything
+    let output = work_dir.run_jj(["stack", "submit", "--base=main"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    push-kkmpptxzrspx: https://github.com/owner/repo/pull/1
+    push-zsuskulnrvyr: https://github.com/owner/repo/pull/2
+    [EOF]
+    ");
+}
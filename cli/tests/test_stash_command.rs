@@ -0,0 +1,206 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools as _;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_stash_push_pop() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.run_jj(["describe", "-m", "wip"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.write_file("file2", "b\n");
+
+    let output = work_dir.run_jj(["stash", "push", "-m", "my changes"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Saved stash stash-1: my changes
+    Working copy  (@) now at: wip
+    Parent commit (@-)      : base
+    Added 0 files, modified 1 files, removed 1 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["stash", "list"]);
+    insta::assert_snapshot!(output, @r"
+    stash-1: my changes
+    [EOF]
+    ");
+    // The stashed commit is hidden, but can be selected by stashes()
+    let output = work_dir.run_jj(["log", "-r=stashes()", "--no-graph", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    my changes
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", r#"description ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    wip
+    base
+
+    [EOF]
+    ");
+
+    // The changes are applied on top of the current working-copy changes
+    work_dir.write_file("file3", "c\n");
+    let output = work_dir.run_jj(["stash", "pop"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Applied stash stash-1: my changes
+    Working copy  (@) now at: wip
+    Parent commit (@-)      : base
+    Added 1 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    A file2
+    A file3
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["stash", "list"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Nothing left to pop
+    let output = work_dir.run_jj(["stash", "pop"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No stashes
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_stash_names() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Without changes, nothing is stashed
+    let output = work_dir.run_jj(["stash", "push"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No changes to stash.
+    [EOF]
+    ");
+
+    work_dir.write_file("file", "1\n");
+    work_dir.run_jj(["stash", "push", "-m", "first"]).success();
+    work_dir.write_file("file", "2\n");
+    work_dir
+        .run_jj(["stash", "push", "-m", "second", "--name", "foo"])
+        .success();
+    work_dir.write_file("file", "3\n");
+    // The description of the working-copy commit is used by default
+    work_dir.run_jj(["describe", "-m", "third"]).success();
+    work_dir.run_jj(["stash", "push"]).success();
+
+    let output = work_dir.run_jj(["stash", "list"]);
+    insta::assert_snapshot!(output, @r"
+    stash-2: third
+    foo: second
+    stash-1: first
+    [EOF]
+    ");
+
+    work_dir.write_file("file", "4\n");
+    let output = work_dir.run_jj(["stash", "push", "--name", "foo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Stash already exists: foo
+    [EOF]
+    [exit status: 1]
+    ");
+    work_dir.run_jj(["restore"]).success();
+
+    // Drops the most recent stash by default
+    let output = work_dir.run_jj(["stash", "drop"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Dropped stash stash-2.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["stash", "drop", "nope"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such stash: nope
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Applying keeps the stash
+    work_dir.run_jj(["stash", "apply", "foo"]).success();
+    assert_eq!(work_dir.read_file("file"), "2\n");
+    let output = work_dir.run_jj(["stash", "list"]);
+    insta::assert_snapshot!(output, @r"
+    foo: second
+    stash-1: first
+    [EOF]
+    ");
+
+    // The operation can be undone like any other
+    work_dir.run_jj(["undo"]).success();
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj(["stash", "list"]);
+    insta::assert_snapshot!(output, @r"
+    stash-2: third
+    foo: second
+    stash-1: first
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_stash_pop_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.run_jj(["describe", "-m", "wip"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["stash", "push"]).success();
+    work_dir.write_file("file", "c\n");
+
+    // The stash is kept if it doesn't apply cleanly
+    let output = work_dir.run_jj(["stash", "pop"]);
+    let stderr_head = output.stderr.normalized().lines().take(3).join("\n");
+    insta::assert_snapshot!(stderr_head, @r"
+    Applied stash stash-1: wip
+    Warning: The stash was kept because applying it resulted in conflicts.
+    Hint: Run `jj stash drop stash-1` once the conflicts are resolved.
+    ");
+    let output = work_dir.run_jj(["resolve", "--list"]);
+    insta::assert_snapshot!(output, @r"
+    file    2-sided conflict
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["stash", "list"]);
+    insta::assert_snapshot!(output, @r"
+    stash-1: wip
+    [EOF]
+    ");
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use testutils::git;
+
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
@@ -99,6 +101,108 @@ fn test_tag_set_delete() {
     ");
 }
 
+#[test]
+fn test_tag_create_annotated() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::open(work_dir.root().join(".jj/repo/store/git"));
+
+    work_dir.run_jj(["commit", "-mcommit1"]).success();
+    let output = work_dir.run_jj(["tag", "create", "-r@-", "-mRelease 1", "v1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 tags pointing to qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  bbc749308d7f
+    ◆  b876c5f49546 v1
+    ◆  000000000000
+    [EOF]
+    ");
+    let tag = git_repo
+        .find_reference("refs/tags/v1")
+        .unwrap()
+        .id()
+        .object()
+        .unwrap()
+        .into_tag();
+    let tag = tag.decode().unwrap();
+    assert_eq!(tag.name, "v1");
+    assert_eq!(tag.message, "Release 1\n");
+    assert!(tag.target().to_string().starts_with("b876c5f49546"));
+
+    // Existing tags aren't moved
+    let output = work_dir.run_jj(["tag", "create", "-mRelease 2", "v1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Tag already exists: v1
+    Hint: Use `jj tag set --allow-move` to update existing tags.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Signing requires a backend
+    let output = work_dir.run_jj([
+        "tag",
+        "create",
+        "-r@-",
+        "--sign",
+        "-mRelease 1",
+        "v1-signed",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No signing backend configured
+    Hint: For configuring a signing backend, see https://jj-vcs.github.io/jj/latest/config/#commit-signing
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj([
+        "tag",
+        "create",
+        "-r@-",
+        "--sign",
+        "-mRelease 1",
+        "v1-signed",
+        "--config=signing.backend=test",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 tags pointing to qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    let tag = git_repo
+        .find_reference("refs/tags/v1-signed")
+        .unwrap()
+        .id()
+        .object()
+        .unwrap()
+        .into_tag();
+    let message = tag.decode().unwrap().message.to_string();
+    assert!(message.starts_with("Release 1\n--- JJ-TEST-SIGNATURE ---\n"));
+
+    // Without a message, the editor is opened
+    std::fs::write(&edit_script, "write\nRelease 3").unwrap();
+    let output = work_dir.run_jj(["tag", "create", "-r@-", "--annotate", "v3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 tags pointing to qpvuntsm b876c5f4 (empty) commit1
+    [EOF]
+    ");
+    std::fs::write(&edit_script, "write\n").unwrap();
+    let output = work_dir.run_jj(["tag", "create", "-r@-", "--annotate", "v4"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Aborting due to empty tag message
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_tag_at_root() {
     let test_env = TestEnvironment::default();
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::common::TestEnvironment;
+use crate::common::force_interactive;
 
 #[test]
 fn test_undo_root_operation() {
@@ -55,6 +56,69 @@ fn test_undo_merge_operation() {
     ");
 }
 
+#[test]
+fn test_undo_preview() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"templates.commit_summary = "description.first_line()""#);
+    test_env.add_config(r#"templates.op_summary = "description.first_line().substr(0, 15)""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+
+    // The preview doesn't change anything
+    let output = work_dir.run_jj(["undo", "--preview"]);
+    insta::assert_snapshot!(output, @r"
+    Would restore to operation: describe commit
+
+    Changed local bookmarks:
+    foo:
+    + (absent)
+    - first
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    foo
+    [EOF]
+    ");
+
+    // Decline to undo
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["undo", "--interactive"])
+            .write_stdin("n\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    Would restore to operation: describe commit
+
+    Changed local bookmarks:
+    foo:
+    + (absent)
+    - first
+    [EOF]
+    ------- stderr -------
+    Undo these changes? (yn): Nothing changed.
+    [EOF]
+    ");
+
+    // Accept to undo
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["undo", "--interactive"])
+            .write_stdin("y\n")
+    });
+    insta::assert_snapshot!(output.stderr, @r"
+    Undo these changes? (yn): Restored to operation: describe commit
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @"");
+}
+
 #[test]
 fn test_undo_push_operation() {
     let test_env = TestEnvironment::default();
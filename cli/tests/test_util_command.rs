@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use insta::assert_snapshot;
+use regex::Regex;
 
 use crate::common::TestEnvironment;
 
@@ -45,7 +46,11 @@ fn test_gc_args() {
     let work_dir = test_env.work_dir("repo");
 
     let output = work_dir.run_jj(["util", "gc"]);
-    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize_sizes), @r"
+    ------- stderr -------
+    Reclaimed <SIZE>: <SIZE> from the operation log, <SIZE> from the index, <SIZE> from commit storage.
+    [EOF]
+    ");
 
     let output = work_dir.run_jj(["util", "gc", "--at-op=@-"]);
     insta::assert_snapshot!(output, @r"
@@ -62,6 +67,15 @@ fn test_gc_args() {
     [EOF]
     [exit status: 1]
     ");
+
+    let output = work_dir.run_jj(["util", "gc", "--keep-since=invalid date"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid date: invalid date
+    Caused by: expected unsupported identifier as position 0..7
+    [EOF]
+    [exit status: 1]
+    ");
 }
 
 #[test]
@@ -103,6 +117,57 @@ fn test_gc_operation_log() {
     ");
 }
 
+#[test]
+fn test_gc_keep_since() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a change\n");
+    work_dir.run_jj(["commit", "-m", "a change"]).success();
+    let op_to_remove = work_dir.current_operation_id();
+    work_dir.write_file("file", "another change\n");
+    work_dir
+        .run_jj(["commit", "-m", "another change"])
+        .success();
+    work_dir.run_jj(["operation", "abandon", "..@-"]).success();
+
+    // The operation was modified recently, so it's kept.
+    let output = work_dir.run_jj(["util", "gc", "--keep-since=1 hour ago"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(normalize_sizes), @r"
+    ------- stderr -------
+    Reclaimed <SIZE>: <SIZE> from the operation log, <SIZE> from the index, <SIZE> from commit storage.
+    [EOF]
+    ");
+    work_dir
+        .run_jj(["debug", "object", "operation", &op_to_remove])
+        .success();
+
+    // Dates in the future don't keep anything.
+    work_dir
+        .run_jj(["util", "gc", "--keep-since=2100-01-01"])
+        .success();
+    let output = work_dir.run_jj(["debug", "object", "operation", &op_to_remove]);
+    assert!(!output.status.success(), "{output}");
+
+    let output = work_dir.run_jj(["util", "gc", "--expire=now", "--keep-since=1 day ago"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--expire <EXPIRE>' cannot be used with '--keep-since <DATE>'
+
+    Usage: jj util gc --expire <EXPIRE>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+fn normalize_sizes(text: String) -> String {
+    let re = Regex::new(r"\d+\.\d (Ki|Mi|Gi)?B").unwrap();
+    re.replace_all(&text, "<SIZE>").into_owned()
+}
+
 #[test]
 fn test_shell_completions() {
     #[track_caller]
@@ -206,3 +271,39 @@ fn test_util_exec_sets_env() {
     [EOF]
     "###);
 }
+
+#[cfg(unix)]
+#[test]
+fn test_util_exec_without_repo() {
+    let test_env = TestEnvironment::default();
+    let script = r#"echo "${JJ_WORKSPACE_ROOT-unset} ${JJ_CHANGE_ID-unset}""#;
+
+    // Outside a workspace, neither variable is set
+    let output = test_env.run_jj_in(".", ["util", "exec", "--", "/bin/sh", "-c", script]);
+    insta::assert_snapshot!(output, @r"
+    unset unset
+    [EOF]
+    ");
+
+    // If the repo can't be loaded, the command still runs without JJ_CHANGE_ID
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let output = test_env.run_jj_in(
+        ".",
+        [
+            "-R",
+            "repo",
+            "--at-op",
+            "ffffffffffff",
+            "util",
+            "exec",
+            "--",
+            "/bin/sh",
+            "-c",
+            script,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    $TEST_ENV/repo unset
+    [EOF]
+    ");
+}
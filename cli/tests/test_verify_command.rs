@@ -0,0 +1,70 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_verify() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+[signing]
+behavior = "drop"
+backend = "test"
+
+[template-aliases]
+'format_commit_summary_with_refs(commit, refs)' = 'commit.description().first_line()'
+"#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "one"]).success();
+    work_dir.run_jj(["commit", "-m", "two"]).success();
+    work_dir.run_jj(["sign", "-r", "@--"]).success();
+
+    // Unsigned revisions fail verification by default
+    let output = work_dir.run_jj(["verify", "-r", "::@-"]);
+    insta::assert_snapshot!(output, @r"
+    unsigned two
+    good     one
+    [EOF]
+    ------- stderr -------
+    Checked 2 revisions: 1 good, 0 unknown, 0 bad, 1 unsigned
+    Error: 1 revisions failed signature verification
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["verify", "-r", "::@-", "--allow-unsigned"]);
+    insta::assert_snapshot!(output, @r"
+    unsigned two
+    good     one
+    [EOF]
+    ------- stderr -------
+    Checked 2 revisions: 1 good, 0 unknown, 0 bad, 1 unsigned
+    [EOF]
+    ");
+
+    // Custom template
+    let template =
+        r#"description.first_line() ++ ": " ++ if(signature, signature.display(), "-") ++ "\n""#;
+    let output = work_dir.run_jj(["verify", "-r", "@--", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    one: test-display
+    [EOF]
+    ------- stderr -------
+    Checked 1 revisions: 1 good, 0 unknown, 0 bad, 0 unsigned
+    [EOF]
+    ");
+}
@@ -0,0 +1,166 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::net::TcpStream;
+use std::process::Child;
+use std::process::Stdio;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+/// Running `jj web` server, killed when dropped.
+struct WebServer {
+    child: Child,
+    addr: String,
+}
+
+impl WebServer {
+    fn start(test_env: &TestEnvironment, work_dir: &TestWorkDir) -> Self {
+        let jj_cmd = test_env.new_jj_cmd();
+        let mut cmd = std::process::Command::new(jj_cmd.get_program());
+        cmd.env_clear();
+        for (key, value) in jj_cmd.get_envs() {
+            if let Some(value) = value {
+                cmd.env(key, value);
+            }
+        }
+        let mut child = cmd
+            .current_dir(work_dir.root())
+            .args(["web", "--port=0"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stderr.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let addr = line
+            .trim_end()
+            .strip_prefix("Serving the repo at http://")
+            .and_then(|url| url.strip_suffix('/'))
+            .unwrap_or_else(|| panic!("unexpected output: {line:?}"))
+            .to_owned();
+        Self { child, addr }
+    }
+
+    /// Sends a request and returns the status line and the body.
+    fn request(&self, request_line: &str) -> (String, String) {
+        self.request_to_host(request_line, &self.addr)
+    }
+
+    /// Sends a request with the given `Host` header.
+    fn request_to_host(&self, request_line: &str, host: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(&self.addr).unwrap();
+        write!(stream, "{request_line}\r\nHost: {host}\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (headers, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = headers.lines().next().unwrap().to_owned();
+        (status, body.to_owned())
+    }
+
+    fn get(&self, target: &str) -> (String, String) {
+        self.request(&format!("GET {target} HTTP/1.1"))
+    }
+}
+
+impl Drop for WebServer {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+#[test]
+fn test_web_log_and_show() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a <b>\n");
+    work_dir.run_jj(["commit", "-m", "first & only"]).success();
+    let first_id = work_dir
+        .run_jj(["log", "--no-graph", "-r@-", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+
+    let server = WebServer::start(&test_env, &work_dir);
+
+    let (status, body) = server.get("/");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("<title>Log - jj</title>"), "{body}");
+    assert!(
+        body.contains(&format!("<a href=\"/show/{first_id}\">")),
+        "{body}"
+    );
+    assert!(body.contains("first &amp; only"), "{body}");
+
+    // The revset from the query string limits the graph
+    let (status, body) = server.get("/log?r=root%28%29");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("value=\"root()\""), "{body}");
+    assert!(!body.contains(&first_id), "{body}");
+
+    let (status, body) = server.get(&format!("/show/{first_id}"));
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("first &amp; only"), "{body}");
+    assert!(body.contains("diff --git a/file b/file"), "{body}");
+    assert!(body.contains("+a &lt;b&gt;"), "{body}");
+}
+
+#[test]
+fn test_web_other_pages() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let server = WebServer::start(&test_env, &work_dir);
+
+    let (status, body) = server.get("/ops");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains("test-username@host.example.com"), "{body}");
+
+    let (status, body) = server.get("/conflicts");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(
+        body.contains("There are no conflicted revisions."),
+        "{body}"
+    );
+
+    let (status, _) = server.get("/show/not-hex");
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+    let (status, _) = server.get("/nonexistent");
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+    let (status, body) = server.get("/log?r=bad%28");
+    assert_eq!(status, "HTTP/1.1 500 Internal Server Error");
+    assert!(body.contains("<pre>"), "{body}");
+    let (status, _) = server.request("POST / HTTP/1.1");
+    assert_eq!(status, "HTTP/1.1 405 Method Not Allowed");
+
+    // Requests addressed to other host names are rejected
+    let (status, _) = server.request_to_host("GET / HTTP/1.1", "evil.example.com");
+    assert_eq!(status, "HTTP/1.1 403 Forbidden");
+    let (status, _) = server.request_to_host("GET / HTTP/1.1", "localhost");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+
+    // A client that doesn't send its request doesn't block the others
+    let _idle_stream = TcpStream::connect(&server.addr).unwrap();
+    let (status, _) = server.get("/ops");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+}
@@ -261,6 +261,54 @@ fn test_materialize_and_snapshot_different_conflict_markers() {
     ");
 }
 
+#[test]
+fn test_materialize_conflict_marker_style_per_path() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Use Git-style conflict markers only for *.git files
+    test_env.add_config(indoc! {r#"
+        [ui.conflict-marker-styles.git]
+        patterns = ["glob:'*.git'"]
+        style = "git"
+    "#});
+
+    work_dir.write_file("file.git", "base\n");
+    work_dir.write_file("file.txt", "base\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("file.git", "a\n");
+    work_dir.write_file("file.txt", "a\n");
+    work_dir.run_jj(["commit", "-m", "side-a"]).success();
+    work_dir
+        .run_jj(["new", "description(base)", "-m", "side-b"])
+        .success();
+    work_dir.write_file("file.git", "b\n");
+    work_dir.write_file("file.txt", "b\n");
+    work_dir
+        .run_jj(["new", "description(side-a)", "description(side-b)"])
+        .success();
+
+    insta::assert_snapshot!(work_dir.read_file("file.git"), @r"
+    <<<<<<< Side #1 (Conflict 1 of 1)
+    a
+    ||||||| Base
+    base
+    =======
+    b
+    >>>>>>> Side #2 (Conflict 1 of 1 ends)
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file.txt"), @r"
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -base
+    +a
+    +++++++ Contents of side #2
+    b
+    >>>>>>> Conflict 1 of 1 ends
+    ");
+}
+
 #[test]
 fn test_snapshot_invalid_ignore_pattern() {
     let test_env = TestEnvironment::default();
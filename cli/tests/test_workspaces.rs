@@ -127,6 +127,90 @@ fn test_workspaces_sparse_patterns() {
     insta::assert_snapshot!(output, @"");
 }
 
+/// Test `--sparse-from` and `--preset`
+#[test]
+fn test_workspaces_add_sparse_from_and_preset() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let ws2_dir = test_env.work_dir("ws2");
+    let ws3_dir = test_env.work_dir("ws3");
+    let ws4_dir = test_env.work_dir("ws4");
+    let ws5_dir = test_env.work_dir("ws5");
+
+    main_dir.write_file("file", "contents");
+    main_dir.run_jj(["commit", "-m", "first"]).success();
+    main_dir.run_jj(["commit", "-m", "second"]).success();
+    main_dir
+        .run_jj(["sparse", "set", "--clear", "--add=foo"])
+        .success();
+
+    // Copy the patterns from another workspace
+    main_dir
+        .run_jj(["workspace", "add", "--sparse-patterns=full", "../ws2"])
+        .success();
+    ws2_dir
+        .run_jj(["workspace", "add", "--sparse-from=../main", "../ws3"])
+        .success();
+    let output = ws3_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    foo
+    [EOF]
+    ");
+
+    // Read the patterns from a file, and the revisions from a preset
+    test_env.add_config(
+        r#"
+        [workspace-presets.ci]
+        revisions = ["description(first)"]
+        sparse-from = "../patterns"
+        "#,
+    );
+    std::fs::write(test_env.env_root().join("patterns"), "bar\n\nbaz/qux\n").unwrap();
+    main_dir
+        .run_jj(["workspace", "add", "--preset=ci", "../ws4"])
+        .success();
+    let output = ws4_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    bar
+    baz/qux
+    [EOF]
+    ");
+    let output = ws4_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    first
+    [EOF]
+    ");
+
+    // Command-line options override the preset
+    main_dir
+        .run_jj([
+            "workspace",
+            "add",
+            "--preset=ci",
+            "--sparse-patterns=empty",
+            "-r@-",
+            "../ws5",
+        ])
+        .success();
+    let output = ws5_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @"");
+    let output = ws5_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    second
+    [EOF]
+    ");
+
+    let output = main_dir.run_jj(["workspace", "add", "--preset=unknown", "../ws6"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No workspace preset named 'unknown'
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(!test_env.env_root().join("ws6").exists());
+}
+
 /// Test adding a second workspace while the current workspace is editing a
 /// merge
 #[test]
@@ -1169,6 +1253,89 @@ fn test_workspaces_forget_abandon_commits() {
     ");
 }
 
+/// Test forgetting workspaces and deleting their files
+#[test]
+fn test_workspaces_forget_delete_files() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+    main_dir.run_jj(["workspace", "add", "../third"]).success();
+
+    // Changes that haven't been snapshotted block the deletion
+    secondary_dir.write_file("file", "contents");
+    let output = main_dir.run_jj([
+        "workspace",
+        "forget",
+        "--delete-files",
+        "secondary",
+        "third",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace secondary has changes that haven't been snapshotted
+    Hint: Run `jj status` in the workspace to snapshot them, or use `--force` to delete them.
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(test_env.env_root().join("secondary").exists());
+    assert!(test_env.env_root().join("third").exists());
+
+    let output = main_dir.run_jj([
+        "workspace",
+        "forget",
+        "--delete-files",
+        "--force",
+        "secondary",
+    ]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Deleted workspace directory ../secondary
+    [EOF]
+    ");
+    assert!(!test_env.env_root().join("secondary").exists());
+
+    let output = main_dir.run_jj(["workspace", "forget", "--delete-files", "third"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Deleted workspace directory ../third
+    [EOF]
+    ");
+    assert!(!test_env.env_root().join("third").exists());
+
+    let output = main_dir.run_jj(["workspace", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default: qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    // The workspace that contains the repo can't be deleted
+    let output = main_dir.run_jj(["workspace", "forget", "--delete-files"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace default contains the repo and can't be deleted
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Even with `--force`, a directory that replaced the workspace isn't deleted
+    main_dir.run_jj(["workspace", "add", "../fourth"]).success();
+    let fourth_path = test_env.env_root().join("fourth");
+    std::fs::remove_dir_all(&fourth_path).unwrap();
+    std::fs::create_dir(&fourth_path).unwrap();
+    let output = main_dir.run_jj(["workspace", "forget", "--delete-files", "--force", "fourth"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r#"
+    ------- stderr -------
+    Error: There is no jj repo in "$TEST_ENV/fourth"
+    [EOF]
+    [exit status: 1]
+    "#);
+    assert!(fourth_path.exists());
+}
+
 /// Test context of commit summary template
 #[test]
 fn test_list_workspaces_template() {
@@ -1215,6 +1382,69 @@ fn test_list_workspaces_template() {
     ");
 }
 
+/// Test running a command in each workspace
+#[test]
+fn test_workspaces_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+    main_dir
+        .run_jj(["workspace", "add", "--name", "second", "../secondary"])
+        .success();
+    let formatter_path = assert_cmd::cargo::cargo_bin!("fake-formatter");
+    let formatter_path = formatter_path.to_str().unwrap();
+
+    let output = main_dir.run_jj([
+        "workspace",
+        "run",
+        "--",
+        formatter_path,
+        "--stdout=hello\n",
+        "--tee=marker",
+    ]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    hello
+    hello
+    [EOF]
+    ------- stderr -------
+    Running in workspace default (.):
+    Running in workspace second (../secondary):
+    [EOF]
+    ");
+    assert_eq!(main_dir.read_file("marker"), "hello\n");
+    assert_eq!(secondary_dir.read_file("marker"), "hello\n");
+
+    // Select workspaces, and aggregate failures
+    let output = secondary_dir.run_jj([
+        "workspace",
+        "run",
+        "--parallel",
+        "--workspace=second",
+        "--",
+        formatter_path,
+        "--stdout=bye\n",
+        "--fail",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    bye
+    [EOF]
+    ------- stderr -------
+    Running in workspace second (.):
+    Error: Command failed in 1 of 1 workspaces: second
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = main_dir.run_jj(["workspace", "run", "--workspace=nonexistent", "--", "true"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such workspace: nonexistent
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 /// Test getting the workspace root from primary and secondary workspaces
 #[test]
 fn test_workspaces_root() {
@@ -38,6 +38,7 @@ use crate::conflicts::materialized_diff_stream;
 use crate::copies::CopyRecords;
 use crate::diff::ContentDiff;
 use crate::diff::DiffHunkKind;
+use crate::fileset::FilesetExpression;
 use crate::matchers::Matcher;
 use crate::merge::Merge;
 use crate::merged_tree::MergedTree;
@@ -46,7 +47,9 @@ use crate::repo::MutableRepo;
 use crate::repo::Repo;
 use crate::repo_path::RepoPathBuf;
 use crate::revset::ResolvedRevsetExpression;
+use crate::revset::RevsetContainingFn;
 use crate::revset::RevsetEvaluationError;
+use crate::revset::RevsetFilterPredicate;
 
 /// The source commit to absorb into its ancestry.
 #[derive(Clone, Debug)]
@@ -77,6 +80,16 @@ pub enum AbsorbError {
     RevsetEvaluation(#[from] RevsetEvaluationError),
 }
 
+/// Options for [`split_hunks_to_trees()`].
+#[derive(Clone, Debug, Default)]
+pub struct AbsorbOptions {
+    /// Also absorb added and deleted files and changes to the executable bit.
+    ///
+    /// These changes are moved to the closest destination commit that
+    /// modified the file, or for added files, any file in the same directory.
+    pub file_changes: bool,
+}
+
 /// An absorb 'plan' indicating which commits should be modified and what they
 /// should be modified to.
 #[derive(Default)]
@@ -94,6 +107,7 @@ pub async fn split_hunks_to_trees(
     source: &AbsorbSource,
     destinations: &Arc<ResolvedRevsetExpression>,
     matcher: &dyn Matcher,
+    options: &AbsorbOptions,
 ) -> Result<SelectedTrees, AbsorbError> {
     let mut selected_trees = SelectedTrees::default();
 
@@ -103,18 +117,46 @@ pub async fn split_hunks_to_trees(
     let copy_records = CopyRecords::default();
     let tree_diff = left_tree.diff_stream_with_copies(&right_tree, matcher, &copy_records);
     let mut diff_stream = materialized_diff_stream(repo.store(), tree_diff);
+    let destination_revset = destinations.clone().evaluate(repo)?;
+    let is_destination = destination_revset.containing_fn();
     while let Some(entry) = diff_stream.next().await {
         let left_path = entry.path.source();
         let right_path = entry.path.target();
         let (left_value, right_value) = entry.values?;
-        let (left_text, executable, copy_id) = match to_file_value(left_value) {
+        let (left_text, left_id, executable, copy_id) = match to_file_value(left_value) {
             Ok(Some(mut value)) => (
                 value.read_all(left_path).await?,
+                value.id.clone(),
                 value.executable,
                 value.copy_id,
             ),
-            // New file should have no destinations
-            Ok(None) => continue,
+            // New file has no destinations based on content, but may be added
+            // to the commit that owns the directory. Files at the root
+            // directory are left in the source since every commit owns it.
+            Ok(None) => {
+                if options.file_changes
+                    && let Some(dir) = right_path.parent()
+                    && !dir.is_root()
+                    && let Ok(Some(value)) = to_file_value(right_value)
+                {
+                    let expression = FilesetExpression::prefix_path(dir.to_owned());
+                    if let Some(commit_id) =
+                        find_owner_commit(repo, source, destinations, &is_destination, expression)?
+                    {
+                        let tree_value = Merge::normal(TreeValue::File {
+                            id: value.id,
+                            executable: value.executable,
+                            copy_id: value.copy_id,
+                        });
+                        selected_trees
+                            .target_commits
+                            .entry(commit_id)
+                            .or_insert_with(|| MergedTreeBuilder::new(left_tree.clone()))
+                            .set_or_remove(right_path.to_owned(), tree_value);
+                    }
+                }
+                continue;
+            }
             Err(reason) => {
                 selected_trees
                     .skipped_paths
@@ -122,9 +164,9 @@ pub async fn split_hunks_to_trees(
                 continue;
             }
         };
-        let (right_text, deleted) = match to_file_value(right_value) {
-            Ok(Some(mut value)) => (value.read_all(right_path).await?, false),
-            Ok(None) => (vec![], true),
+        let (right_text, right_executable, deleted) = match to_file_value(right_value) {
+            Ok(Some(mut value)) => (value.read_all(right_path).await?, value.executable, false),
+            Ok(None) => (vec![], executable, true),
             Err(reason) => {
                 selected_trees
                     .skipped_paths
@@ -133,6 +175,23 @@ pub async fn split_hunks_to_trees(
             }
         };
 
+        // Deletion and executable bit changes are moved to the commit that
+        // modified the file last.
+        let owner_id = if options.file_changes && (deleted || executable != right_executable) {
+            let expression = FilesetExpression::file_path(left_path.to_owned());
+            find_owner_commit(repo, source, destinations, &is_destination, expression)?
+        } else {
+            None
+        };
+        if deleted && let Some(commit_id) = owner_id {
+            selected_trees
+                .target_commits
+                .entry(commit_id)
+                .or_insert_with(|| MergedTreeBuilder::new(left_tree.clone()))
+                .set_or_remove(left_path.to_owned(), Merge::absent());
+            continue;
+        }
+
         // Compute annotation of parent (= left) content to map right hunks
         let mut annotator =
             FileAnnotator::with_file_content(source.commit.id(), left_path, left_text.clone());
@@ -163,17 +222,60 @@ pub async fn split_hunks_to_trees(
                     .await?;
                 Merge::normal(TreeValue::File {
                     id,
-                    executable,
+                    executable: if owner_id.as_ref() == Some(commit_id) {
+                        right_executable
+                    } else {
+                        executable
+                    },
                     copy_id: copy_id.clone(),
                 })
             };
             tree_builder.set_or_remove(left_path.to_owned(), new_tree_value);
         }
+        // Executable bit change without content changes to the owner commit
+        if let Some(commit_id) = owner_id
+            && !selected_ranges.contains_key(&commit_id)
+        {
+            let new_tree_value = Merge::normal(TreeValue::File {
+                id: left_id,
+                executable: right_executable,
+                copy_id,
+            });
+            selected_trees
+                .target_commits
+                .entry(commit_id)
+                .or_insert_with(|| MergedTreeBuilder::new(left_tree.clone()))
+                .set_or_remove(left_path.to_owned(), new_tree_value);
+        }
     }
 
     Ok(selected_trees)
 }
 
+/// Finds the closest destination commit that modified the files matched by
+/// `expression`. Returns `None` if the closest commit isn't a destination, or
+/// if there are multiple closest commits. Only commits descending from the
+/// `destinations` are searched.
+fn find_owner_commit(
+    repo: &dyn Repo,
+    source: &AbsorbSource,
+    destinations: &Arc<ResolvedRevsetExpression>,
+    is_destination: &RevsetContainingFn,
+    expression: FilesetExpression,
+) -> Result<Option<CommitId>, RevsetEvaluationError> {
+    let candidates = destinations
+        .dag_range_to(&ResolvedRevsetExpression::commits(
+            source.commit.parent_ids().to_vec(),
+        ))
+        .filtered(RevsetFilterPredicate::File(expression))
+        .heads();
+    let head_ids: Vec<_> = candidates.evaluate(repo)?.iter().try_collect()?;
+    let Ok([head_id]) = <[CommitId; 1]>::try_from(head_ids) else {
+        return Ok(None);
+    };
+    Ok(is_destination(&head_id)?.then_some(head_id))
+}
+
 type SelectedRange = (Range<usize>, Range<usize>);
 
 /// Maps `diff` hunks to commits based on the left `annotation_ranges`. The
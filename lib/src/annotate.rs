@@ -448,10 +448,12 @@ async fn get_file_contents(
             // TODO: track line origins without materializing
             let options = ConflictMaterializeOptions {
                 marker_style: ConflictMarkerStyle::Diff,
+                marker_style_overrides: Default::default(),
                 marker_len: None,
                 merge: MergeOptions {
                     hunk_level: FileMergeHunkLevel::Line,
                     same_change: SameChange::Accept,
+                    drivers: Default::default(),
                 },
             };
             Ok(materialize_merge_result_to_bytes(&file.contents, &options))
@@ -20,6 +20,8 @@ use std::sync::Arc;
 
 use itertools::Itertools as _;
 use serde::Deserialize as _;
+use serde::Deserializer;
+use serde::de::Error as _;
 use serde::de::IntoDeserializer as _;
 use thiserror::Error;
 use toml_edit::DocumentMut;
@@ -32,6 +34,7 @@ use crate::config::ConfigUpdateError;
 use crate::config::ConfigValue;
 use crate::config::StackedConfig;
 use crate::config::ToConfigNamePath;
+use crate::str_util::StringPattern;
 
 // Prefixed by "--" so these keys look unusual. It's also nice that "-" is
 // placed earlier than the other keys in lexicographical order.
@@ -52,6 +55,8 @@ pub struct ConfigResolutionContext<'a> {
     pub command: Option<&'a str>,
     /// Hostname
     pub hostname: &'a str,
+    /// Fetch URLs of the remotes configured for the repository.
+    pub remote_urls: &'a [String],
 }
 
 /// Conditions to enable the parent table.
@@ -78,6 +83,9 @@ struct ScopeCondition {
     pub platforms: Option<Vec<String>>,
     /// Hostnames to match the hostname.
     pub hostnames: Option<Vec<String>>,
+    /// Glob patterns to match any of the remote URLs.
+    #[serde(deserialize_with = "deserialize_glob_patterns")]
+    pub remotes: Option<Vec<StringPattern>>,
 }
 
 impl ScopeCondition {
@@ -113,9 +121,23 @@ impl ScopeCondition {
             && matches_platform(self.platforms.as_deref())
             && matches_hostname(self.hostnames.as_deref(), context.hostname)
             && matches_command(self.commands.as_deref(), context.command)
+            && matches_remote_url(self.remotes.as_deref(), context.remote_urls)
     }
 }
 
+fn deserialize_glob_patterns<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<StringPattern>>, D::Error> {
+    let Some(sources) = Option::<Vec<String>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    sources
+        .iter()
+        .map(|source| StringPattern::glob(source).map_err(D::Error::custom))
+        .try_collect()
+        .map(Some)
+}
+
 fn expand_home(path: &Path, home_dir: Option<&Path>) -> Result<Option<PathBuf>, &'static str> {
     match path.strip_prefix("~") {
         Ok(tail) => {
@@ -146,6 +168,14 @@ fn matches_hostname(candidates: Option<&[String]>, actual: &str) -> bool {
     candidates.is_none_or(|candidates| candidates.iter().any(|candidate| actual == candidate))
 }
 
+fn matches_remote_url(candidates: Option<&[StringPattern]>, actual: &[String]) -> bool {
+    candidates.is_none_or(|candidates| {
+        candidates
+            .iter()
+            .any(|pattern| actual.iter().any(|url| pattern.is_match(url)))
+    })
+}
+
 fn matches_command(candidates: Option<&[String]>, actual: Option<&str>) -> bool {
     match (candidates, actual) {
         (Some(candidates), Some(actual)) => candidates.iter().any(|candidate| {
@@ -461,6 +491,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -469,6 +500,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(condition.matches(&context));
     }
@@ -481,6 +513,7 @@ mod tests {
             commands: None,
             platforms: None,
             hostnames: None,
+            remotes: None,
         };
 
         let context = ConfigResolutionContext {
@@ -489,6 +522,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(!condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -497,6 +531,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -505,6 +540,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(!condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -513,6 +549,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -521,6 +558,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(condition.matches(&context));
     }
@@ -533,6 +571,7 @@ mod tests {
             commands: None,
             platforms: None,
             hostnames: None,
+            remotes: None,
         };
 
         let context = ConfigResolutionContext {
@@ -541,6 +580,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert_eq!(condition.matches(&context), cfg!(windows));
         let context = ConfigResolutionContext {
@@ -549,6 +589,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert_eq!(condition.matches(&context), cfg!(windows));
         let context = ConfigResolutionContext {
@@ -557,6 +598,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(!condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -565,6 +607,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert_eq!(condition.matches(&context), cfg!(windows));
     }
@@ -577,6 +620,7 @@ mod tests {
             workspaces: None,
             commands: None,
             platforms: None,
+            remotes: None,
         };
 
         let context = ConfigResolutionContext {
@@ -585,6 +629,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert!(!condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -593,6 +638,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "host-a",
+            remote_urls: &[],
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -601,6 +647,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "host-b",
+            remote_urls: &[],
         };
         assert!(condition.matches(&context));
         let context = ConfigResolutionContext {
@@ -609,6 +656,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "host-c",
+            remote_urls: &[],
         };
         assert!(!condition.matches(&context));
     }
@@ -629,6 +677,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -668,6 +717,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 7);
@@ -709,6 +759,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -720,6 +771,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 3);
@@ -733,6 +785,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -745,6 +798,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -781,6 +835,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -792,6 +847,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "host-a",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 3);
@@ -805,6 +861,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "host-b",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -817,6 +874,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "host-c",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -824,6 +882,67 @@ mod tests {
         insta::assert_snapshot!(resolved_config.layers()[1].data, @"a = 'a #1 host-c'");
     }
 
+    #[test]
+    fn test_resolve_remote_url() {
+        let mut source_config = StackedConfig::empty();
+        source_config.add_layer(new_user_layer(indoc! {"
+            a = 'a #0'
+            [[--scope]]
+            --when.remotes = ['git@github.com:work/*']
+            a = 'a #0.1 work'
+            [[--scope]]
+            --when.remotes = ['*github.com*']
+            a = 'a #0.2 github'
+            [[--scope]]
+            --when.remotes = []
+            a = 'a #0.3 none'
+        "}));
+
+        let context = ConfigResolutionContext {
+            home_dir: Some(Path::new("/home/dir")),
+            repo_path: None,
+            workspace_path: None,
+            command: None,
+            hostname: "",
+            remote_urls: &[],
+        };
+        let resolved_config = resolve(&source_config, &context).unwrap();
+        assert_eq!(resolved_config.layers().len(), 1);
+        insta::assert_snapshot!(resolved_config.layers()[0].data, @"a = 'a #0'");
+
+        let remote_urls = [
+            "https://example.com/upstream.git".to_owned(),
+            "git@github.com:work/repo.git".to_owned(),
+        ];
+        let context = ConfigResolutionContext {
+            home_dir: Some(Path::new("/home/dir")),
+            repo_path: None,
+            workspace_path: None,
+            command: None,
+            hostname: "",
+            remote_urls: &remote_urls,
+        };
+        let resolved_config = resolve(&source_config, &context).unwrap();
+        assert_eq!(resolved_config.layers().len(), 3);
+        insta::assert_snapshot!(resolved_config.layers()[0].data, @"a = 'a #0'");
+        insta::assert_snapshot!(resolved_config.layers()[1].data, @"a = 'a #0.1 work'");
+        insta::assert_snapshot!(resolved_config.layers()[2].data, @"a = 'a #0.2 github'");
+
+        let remote_urls = ["https://github.com/personal/repo.git".to_owned()];
+        let context = ConfigResolutionContext {
+            home_dir: Some(Path::new("/home/dir")),
+            repo_path: None,
+            workspace_path: None,
+            command: None,
+            hostname: "",
+            remote_urls: &remote_urls,
+        };
+        let resolved_config = resolve(&source_config, &context).unwrap();
+        assert_eq!(resolved_config.layers().len(), 2);
+        insta::assert_snapshot!(resolved_config.layers()[0].data, @"a = 'a #0'");
+        insta::assert_snapshot!(resolved_config.layers()[1].data, @"a = 'a #0.2 github'");
+    }
+
     #[test]
     fn test_resolve_workspace_path() {
         let mut source_config = StackedConfig::empty();
@@ -853,6 +972,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -864,6 +984,7 @@ mod tests {
             workspace_path: Some(Path::new("/foo")),
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 3);
@@ -877,6 +998,7 @@ mod tests {
             workspace_path: Some(Path::new("/bar")),
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -889,6 +1011,7 @@ mod tests {
             workspace_path: Some(Path::new("/home/dir/baz")),
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -921,6 +1044,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -932,6 +1056,7 @@ mod tests {
             workspace_path: None,
             command: Some("foo"),
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 3);
@@ -945,6 +1070,7 @@ mod tests {
             workspace_path: None,
             command: Some("bar"),
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -957,6 +1083,7 @@ mod tests {
             workspace_path: None,
             command: Some("foo baz"),
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 4);
@@ -972,6 +1099,7 @@ mod tests {
             workspace_path: None,
             command: Some("fooqux"),
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -1004,6 +1132,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         insta::assert_snapshot!(resolved_config.layers()[0].data, @r#"
@@ -1046,6 +1175,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -1058,6 +1188,7 @@ mod tests {
             workspace_path: None,
             command: Some("other"),
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -1070,6 +1201,7 @@ mod tests {
             workspace_path: None,
             command: Some("ABC"),
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 1);
@@ -1082,6 +1214,7 @@ mod tests {
             workspace_path: None,
             command: Some("DEF"),
             hostname: "",
+            remote_urls: &[],
         };
         let resolved_config = resolve(&source_config, &context).unwrap();
         assert_eq!(resolved_config.layers().len(), 2);
@@ -1102,11 +1235,16 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert_matches!(
             resolve(&new_config("--when.repositories = 0"), &context),
             Err(ConfigGetError::Type { .. })
         );
+        assert_matches!(
+            resolve(&new_config("--when.remotes = ['[']"), &context),
+            Err(ConfigGetError::Type { .. })
+        );
     }
 
     #[test]
@@ -1122,6 +1260,7 @@ mod tests {
             workspace_path: None,
             command: None,
             hostname: "",
+            remote_urls: &[],
         };
         assert_matches!(
             resolve(&new_config("[--scope]"), &context),
@@ -18,6 +18,7 @@ use std::io;
 use std::io::Write;
 use std::iter::zip;
 use std::pin::Pin;
+use std::sync::Arc;
 
 use bstr::BString;
 use bstr::ByteSlice as _;
@@ -38,6 +39,7 @@ use crate::backend::FileId;
 use crate::backend::SymlinkId;
 use crate::backend::TreeId;
 use crate::backend::TreeValue;
+use crate::config::ConfigGetError;
 use crate::copies::CopiesTreeDiffEntry;
 use crate::copies::CopiesTreeDiffEntryPath;
 use crate::diff::ContentDiff;
@@ -45,10 +47,13 @@ use crate::diff::DiffHunk;
 use crate::diff::DiffHunkKind;
 use crate::files;
 use crate::files::MergeResult;
+use crate::fileset;
+use crate::matchers::Matcher;
 use crate::merge::Merge;
 use crate::merge::MergedTreeValue;
 use crate::merge::SameChange;
 use crate::repo_path::RepoPath;
+use crate::settings::UserSettings;
 use crate::store::Store;
 use crate::tree_merge::MergeOptions;
 
@@ -304,14 +309,77 @@ impl ConflictMarkerStyle {
     }
 }
 
+/// Conflict marker styles configured for specific files by
+/// `ui.conflict-marker-styles`.
+#[derive(Clone, Debug, Default)]
+pub struct ConflictMarkerStyleOverrides {
+    overrides: Vec<(Arc<dyn Matcher>, ConflictMarkerStyle)>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RawConflictMarkerStyleOverride {
+    patterns: Vec<String>,
+    style: ConflictMarkerStyle,
+}
+
+impl ConflictMarkerStyleOverrides {
+    /// Loads the per-path conflict marker styles from `settings`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let overrides = settings
+            .table_keys("ui.conflict-marker-styles")
+            // Sort keys so the first matching override is deterministic.
+            .sorted()
+            .map(|name| {
+                let raw: RawConflictMarkerStyleOverride =
+                    settings.get(["ui", "conflict-marker-styles", name])?;
+                let expression = fileset::parse_config_patterns(&raw.patterns).map_err(|err| {
+                    ConfigGetError::Type {
+                        name: format!("ui.conflict-marker-styles.{name}.patterns"),
+                        error: err.into(),
+                        source_path: None,
+                    }
+                })?;
+                Ok((expression.to_matcher().into(), raw.style))
+            })
+            .try_collect()?;
+        Ok(Self { overrides })
+    }
+
+    /// Returns the marker style for the file at `path`, or `default` if no
+    /// override matches.
+    pub fn style_for(&self, path: &RepoPath, default: ConflictMarkerStyle) -> ConflictMarkerStyle {
+        self.overrides
+            .iter()
+            .find(|(matcher, _)| matcher.matches(path))
+            .map_or(default, |&(_, style)| style)
+    }
+}
+
 /// Options for conflict materialization.
 #[derive(Clone, Debug)]
 pub struct ConflictMaterializeOptions {
     pub marker_style: ConflictMarkerStyle,
+    /// Marker styles taking precedence over `marker_style` for specific files.
+    pub marker_style_overrides: ConflictMarkerStyleOverrides,
     pub marker_len: Option<usize>,
     pub merge: MergeOptions,
 }
 
+impl ConflictMaterializeOptions {
+    /// Returns options to materialize the file at `path` with, using the
+    /// marker style configured for that file.
+    pub fn for_path(&self, path: &RepoPath) -> Self {
+        Self {
+            marker_style: self
+                .marker_style_overrides
+                .style_for(path, self.marker_style),
+            marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+            marker_len: self.marker_len,
+            merge: self.merge.clone(),
+        }
+    }
+}
+
 /// Characters which can be repeated to form a conflict marker line when
 /// materializing and parsing conflicts.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -993,7 +1061,7 @@ pub async fn update_from_content(
                 content.extend_from_slice(slice);
             }
         } else {
-            for (content, slice) in zip(contents.iter_mut(), hunk.into_iter()) {
+            for (content, slice) in zip(contents.iter_mut(), hunk) {
                 content.extend(Vec::from(slice));
             }
         }
@@ -18,10 +18,15 @@ use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fs::File;
+use std::io;
 use std::io::Read;
+use std::io::Seek as _;
+use std::io::SeekFrom;
 use std::io::Write as _;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::OnceLock;
 
 use blake2::Blake2b512;
 use digest::Digest as _;
@@ -50,6 +55,9 @@ use crate::tree_merge::resolve_file_values;
 /// Current format version of the changed-path index segment file.
 const FILE_FORMAT_VERSION: u32 = 0;
 
+/// Size of the file format version and the entry counts in bytes.
+const HEADER_SIZE: u64 = 5 * 4;
+
 id_type!(pub(super) ChangedPathIndexSegmentId { hex() });
 
 /// Commit position within a changed-path index segment.
@@ -98,6 +106,8 @@ struct PathPosition(u32);
 ///   entries by exact path or path prefix if needed.
 /// * Path components aren't split nor compressed so we can borrow `&RepoPath`
 ///   from the index data.
+/// * Only the header is read when the segment is loaded from file. The tables
+///   are read on first access since most commands never query changed paths.
 ///
 /// Ideas for future improvements:
 /// * Multi-level index based on the paths? Since indexing is slow, it might
@@ -113,7 +123,11 @@ pub(super) struct ReadonlyChangedPathIndexSegment {
     changed_path_lookup_base: usize,
     path_lookup_base: usize,
     path_bytes_base: usize,
-    data: Vec<u8>,
+    data_len: usize,
+    // Tables following the header, or None if they couldn't be read
+    data: OnceLock<Option<Vec<u8>>>,
+    // File to read the tables from if they aren't loaded yet
+    file_path: Option<PathBuf>,
 }
 
 impl Debug for ReadonlyChangedPathIndexSegment {
@@ -125,19 +139,57 @@ impl Debug for ReadonlyChangedPathIndexSegment {
 }
 
 impl ReadonlyChangedPathIndexSegment {
+    /// Loads the header from the given file. The tables are read lazily.
     pub(super) fn load(
         dir: &Path,
         id: ChangedPathIndexSegmentId,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
-        let mut file = File::open(dir.join(id.hex()))
+        let file_path = dir.join(id.hex());
+        let mut file = File::open(&file_path)
             .map_err(|err| ReadonlyIndexLoadError::from_io_err("changed-path", id.hex(), err))?;
-        Self::load_from(&mut file, id)
+        let mut segment = Self::load_header(&mut file, id)?;
+        let file_len = file
+            .metadata()
+            .map_err(|err| {
+                ReadonlyIndexLoadError::from_io_err("changed-path", segment.id.hex(), err)
+            })?
+            .len();
+        if file_len != HEADER_SIZE + segment.data_len as u64 {
+            return Err(ReadonlyIndexLoadError::invalid_data(
+                "changed-path",
+                segment.id.hex(),
+                "unexpected data length",
+            ));
+        }
+        segment.file_path = Some(file_path);
+        Ok(Arc::new(segment))
     }
 
+    /// Loads both the header and the tables from the given `file`.
     pub(super) fn load_from(
         file: &mut dyn Read,
         id: ChangedPathIndexSegmentId,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
+        let mut segment = Self::load_header(file, id)?;
+        let mut data = vec![];
+        file.read_to_end(&mut data).map_err(|err| {
+            ReadonlyIndexLoadError::from_io_err("changed-path", segment.id.hex(), err)
+        })?;
+        if data.len() != segment.data_len {
+            return Err(ReadonlyIndexLoadError::invalid_data(
+                "changed-path",
+                segment.id.hex(),
+                "unexpected data length",
+            ));
+        }
+        segment.data = OnceLock::from(Some(data));
+        Ok(Arc::new(segment))
+    }
+
+    fn load_header(
+        file: &mut dyn Read,
+        id: ChangedPathIndexSegmentId,
+    ) -> Result<Self, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err("changed-path", id.hex(), err);
         let read_u32 = |file: &mut dyn Read| {
             let mut buf = [0; 4];
@@ -158,8 +210,6 @@ impl ReadonlyChangedPathIndexSegment {
         let num_changed_paths = read_u32(file)?;
         let num_paths = read_u32(file)?;
         let num_path_bytes = read_u32(file)?;
-        let mut data = vec![];
-        file.read_to_end(&mut data).map_err(from_io_err)?;
 
         let commit_lookup_size = (num_local_commits as usize + 1) * 4;
         let changed_path_lookup_size = (num_changed_paths as usize) * 4;
@@ -169,17 +219,9 @@ impl ReadonlyChangedPathIndexSegment {
         let changed_path_lookup_base = commit_lookup_base + commit_lookup_size;
         let path_lookup_base = changed_path_lookup_base + changed_path_lookup_size;
         let path_bytes_base = path_lookup_base + path_lookup_size;
-        let expected_size = path_bytes_base + (num_path_bytes as usize);
-
-        if data.len() != expected_size {
-            return Err(ReadonlyIndexLoadError::invalid_data(
-                "changed-path",
-                id.hex(),
-                "unexpected data length",
-            ));
-        }
+        let data_len = path_bytes_base + (num_path_bytes as usize);
 
-        Ok(Arc::new(Self {
+        Ok(Self {
             id,
             num_local_commits,
             num_changed_paths,
@@ -188,8 +230,46 @@ impl ReadonlyChangedPathIndexSegment {
             changed_path_lookup_base,
             path_lookup_base,
             path_bytes_base,
-            data,
-        }))
+            data_len,
+            data: OnceLock::new(),
+            file_path: None,
+        })
+    }
+
+    /// Returns the tables following the header, reading them from file if
+    /// needed. Returns `None` if the file couldn't be read.
+    fn data(&self) -> Option<&[u8]> {
+        self.data
+            .get_or_init(|| match self.read_data() {
+                Ok(data) => Some(data),
+                Err(err) => {
+                    tracing::warn!(
+                        ?err,
+                        id = self.id.hex(),
+                        "failed to read changed-path index"
+                    );
+                    None
+                }
+            })
+            .as_deref()
+    }
+
+    fn read_data(&self) -> io::Result<Vec<u8>> {
+        let file_path = self
+            .file_path
+            .as_ref()
+            .expect("unloaded segment should have file");
+        let mut file = File::open(file_path)?;
+        file.seek(SeekFrom::Start(HEADER_SIZE))?;
+        let mut data = Vec::with_capacity(self.data_len);
+        file.read_to_end(&mut data)?;
+        if data.len() != self.data_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unexpected data length",
+            ));
+        }
+        Ok(data)
     }
 
     pub(super) fn id(&self) -> &ChangedPathIndexSegmentId {
@@ -208,46 +288,54 @@ impl ReadonlyChangedPathIndexSegment {
         self.num_paths
     }
 
-    fn changed_paths(&self, pos: CommitPosition) -> impl ExactSizeIterator<Item = &RepoPath> {
-        let table = self.changed_paths_table(pos);
+    /// Returns iterator over paths changed at the specified commit, or `None`
+    /// if the index file couldn't be read.
+    fn changed_paths(
+        &self,
+        pos: CommitPosition,
+    ) -> Option<impl ExactSizeIterator<Item = &RepoPath>> {
+        let data = self.data()?;
+        let table = self.changed_paths_table(data, pos);
         let (chunks, _remainder) = table.as_chunks();
-        chunks
+        let paths = chunks
             .iter()
             .map(|&chunk: &[u8; 4]| PathPosition(u32::from_le_bytes(chunk)))
-            .map(|pos| self.path(pos))
+            .map(|pos| self.path(data, pos));
+        Some(paths)
     }
 
-    fn changed_paths_table(&self, pos: CommitPosition) -> &[u8] {
-        let table = &self.data[self.commit_lookup_base..self.changed_path_lookup_base];
+    fn changed_paths_table<'a>(&self, data: &'a [u8], pos: CommitPosition) -> &'a [u8] {
+        let table = &data[self.commit_lookup_base..self.changed_path_lookup_base];
         let offset = pos.0 as usize * 4;
         let start = u32::from_le_bytes(table[offset..][0..4].try_into().unwrap());
         let end = u32::from_le_bytes(table[offset..][4..8].try_into().unwrap());
 
-        let table = &self.data[self.changed_path_lookup_base..self.path_lookup_base];
+        let table = &data[self.changed_path_lookup_base..self.path_lookup_base];
         &table[(start as usize) * 4..(end as usize) * 4]
     }
 
-    fn path(&self, pos: PathPosition) -> &RepoPath {
-        let bytes = self.path_bytes(pos);
+    fn path<'a>(&self, data: &'a [u8], pos: PathPosition) -> &'a RepoPath {
+        let bytes = self.path_bytes(data, pos);
         RepoPath::from_internal_string(
             str::from_utf8(bytes).expect("indexed path should be valid utf-8"),
         )
         .expect("indexed path should be valid")
     }
 
-    fn path_bytes(&self, pos: PathPosition) -> &[u8] {
-        let table = &self.data[self.path_lookup_base..self.path_bytes_base];
+    fn path_bytes<'a>(&self, data: &'a [u8], pos: PathPosition) -> &'a [u8] {
+        let table = &data[self.path_lookup_base..self.path_bytes_base];
         let offset = pos.0 as usize * 4;
         let start = u32::from_le_bytes(table[offset..][0..4].try_into().unwrap());
         let end = u32::from_le_bytes(table[offset..][4..8].try_into().unwrap());
 
-        let bytes = &self.data[self.path_bytes_base..];
+        let bytes = &data[self.path_bytes_base..];
         &bytes[start as usize..end as usize]
     }
 
     #[cfg(test)]
     fn paths(&self) -> impl ExactSizeIterator<Item = &RepoPath> {
-        (0..self.num_paths).map(|pos| self.path(PathPosition(pos)))
+        let data = self.data().unwrap();
+        (0..self.num_paths).map(|pos| self.path(data, PathPosition(pos)))
     }
 }
 
@@ -292,6 +380,7 @@ impl MutableChangedPathIndexSegment {
         for pos in (0..other_segment.num_local_commits()).map(CommitPosition) {
             let paths = other_segment
                 .changed_paths(pos)
+                .expect("segment to squash should be readable")
                 .map(|path| path.to_owned())
                 .collect();
             self.add_changed_paths(paths);
@@ -495,10 +584,10 @@ impl CompositeChangedPathIndex {
         global_pos: GlobalCommitPosition,
     ) -> Option<impl ExactSizeIterator<Item = &RepoPath>> {
         let (local_pos, segment) = self.find_segment(global_pos)?;
-        Some(segment.map_either(
-            |x| x.changed_paths(local_pos),
-            |x| x.changed_paths(local_pos),
-        ))
+        match segment {
+            Either::Left(x) => x.changed_paths(local_pos).map(Either::Left),
+            Either::Right(x) => Some(Either::Right(x.changed_paths(local_pos))),
+        }
     }
 
     /// Adds changed paths of the next commit.
@@ -539,6 +628,13 @@ impl CompositeChangedPathIndex {
         if squash_start == self.readonly_segments.len() {
             return;
         }
+        // Keep the segments as they are if any of them can't be read.
+        if self.readonly_segments[squash_start..]
+            .iter()
+            .any(|segment| segment.data().is_none())
+        {
+            return;
+        }
         let mut squashed_segment = Box::new(MutableChangedPathIndexSegment::empty());
         for segment in self.readonly_segments.drain(squash_start..) {
             squashed_segment.extend_from_readonly_segment(&segment);
@@ -726,6 +822,37 @@ mod tests {
         assert_eq!(collect_changed_paths(&index, GlobalCommitPosition(0)), None);
     }
 
+    #[test]
+    fn test_composite_lazy_load() {
+        let temp_dir = new_temp_dir();
+        let start_commit_pos = GlobalCommitPosition(0);
+        let mut index = CompositeChangedPathIndex::empty(start_commit_pos);
+        index.make_mutable();
+        index.add_changed_paths(vec![repo_path_buf("foo")]);
+        index.save_in(temp_dir.path()).unwrap();
+        let ids = index
+            .readonly_segments()
+            .iter()
+            .map(|segment| segment.id().clone())
+            .collect_vec();
+
+        // The tables are read on first access
+        let index =
+            CompositeChangedPathIndex::load(temp_dir.path(), start_commit_pos, &ids).unwrap();
+        assert_eq!(index.num_commits(), 1);
+        assert_eq!(
+            collect_changed_paths(&index, GlobalCommitPosition(0)),
+            Some(vec![repo_path("foo")])
+        );
+
+        // If the file disappears before the first access, the commit is
+        // treated as not indexed
+        let index =
+            CompositeChangedPathIndex::load(temp_dir.path(), start_commit_pos, &ids).unwrap();
+        std::fs::remove_file(temp_dir.path().join(ids[0].hex())).unwrap();
+        assert_eq!(collect_changed_paths(&index, GlobalCommitPosition(0)), None);
+    }
+
     #[test]
     fn test_composite_empty_changed_paths() {
         let temp_dir = new_temp_dir();
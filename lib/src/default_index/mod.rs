@@ -38,6 +38,7 @@ pub use self::readonly::DefaultReadonlyIndex;
 pub use self::readonly::DefaultReadonlyIndexRevset;
 pub use self::readonly::IndexStats;
 pub use self::readonly::ReadonlyIndexLoadError;
+pub use self::revset_engine::RevsetProfileNode;
 pub use self::store::DefaultIndexStore;
 pub use self::store::DefaultIndexStoreError;
 pub use self::store::DefaultIndexStoreInitError;
@@ -74,6 +75,7 @@ mod tests {
     use crate::default_index::readonly::FieldLengths;
     use crate::index::Index as _;
     use crate::object_id::HexPrefix;
+    use crate::object_id::ObjectId as _;
     use crate::object_id::PrefixResolution;
     use crate::revset::PARENTS_RANGE_FULL;
     use crate::tests::new_temp_dir;
@@ -1026,6 +1028,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn index_lazy_load() {
+        let temp_dir = new_temp_dir();
+        let mut new_change_id = change_id_generator();
+        let mut mutable_segment = MutableCommitIndexSegment::full(TEST_FIELD_LENGTHS);
+        let id_0 = CommitId::from_hex("000000");
+        let id_1 = CommitId::from_hex("111111");
+        mutable_segment.add_commit_data(id_0.clone(), new_change_id(), &[]);
+        let initial_file = mutable_segment.save_in(temp_dir.path()).unwrap();
+        let initial_path = temp_dir.path().join(initial_file.id().hex());
+        mutable_segment = MutableCommitIndexSegment::incremental(initial_file);
+        let change_id1 = new_change_id();
+        mutable_segment.add_commit_data(id_1.clone(), change_id1.clone(), &[id_0.clone()]);
+        let saved_file = mutable_segment.save_in(temp_dir.path()).unwrap();
+
+        let loaded_file = ReadonlyCommitIndexSegment::load(
+            temp_dir.path(),
+            saved_file.id().clone(),
+            TEST_FIELD_LENGTHS,
+        )
+        .unwrap();
+        // Commit ids are looked up without loading the tables
+        let index = loaded_file.as_composite();
+        assert_eq!(index.num_commits(), 2);
+        assert_eq!(index.commit_id_to_pos(&id_1), Some(GlobalCommitPosition(1)));
+        assert_eq!(index.commit_id_to_pos(&CommitId::from_hex("222222")), None);
+        assert!(!loaded_file.is_loaded());
+        assert!(!loaded_file.parent_file().unwrap().is_loaded());
+
+        // The parent segment isn't needed when looking up commits in the
+        // newer segment
+        std::fs::remove_file(&initial_path).unwrap();
+        let entry_1 = index.entry_by_id(&id_1).unwrap();
+        assert_eq!(entry_1.position(), GlobalCommitPosition(1));
+        assert_eq!(entry_1.change_id(), change_id1);
+        assert_eq!(entry_1.generation_number(), 1);
+        assert_eq!(
+            entry_1.parent_positions(),
+            smallvec_inline![GlobalCommitPosition(0)]
+        );
+        assert!(loaded_file.is_loaded());
+        assert!(!loaded_file.parent_file().unwrap().is_loaded());
+        // The removed file can still be read since it was opened on load
+        assert_eq!(
+            index.entry_by_id(&id_0).unwrap().position(),
+            GlobalCommitPosition(0)
+        );
+
+        // Truncated file is detected without reading the entries
+        let saved_path = temp_dir.path().join(saved_file.id().hex());
+        let data = std::fs::read(&saved_path).unwrap();
+        std::fs::write(&saved_path, &data[..data.len() - 1]).unwrap();
+        let result = ReadonlyCommitIndexSegment::load(
+            temp_dir.path(),
+            saved_file.id().clone(),
+            TEST_FIELD_LENGTHS,
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_is_ancestor() {
         let mut new_change_id = change_id_generator();
@@ -347,8 +347,10 @@ impl MutableCommitIndexSegment {
     }
 
     pub(super) fn save_in(self, dir: &Path) -> Result<Arc<ReadonlyCommitIndexSegment>, PathError> {
-        if self.num_local_commits() == 0 && self.parent_file.is_some() {
-            return Ok(self.parent_file.unwrap());
+        if self.num_local_commits() == 0
+            && let Some(parent_file) = self.parent_file
+        {
+            return Ok(parent_file);
         }
 
         let mut buf = Vec::new();
@@ -21,10 +21,14 @@ use std::fmt::Debug;
 use std::fs::File;
 use std::io;
 use std::io::Read;
+use std::io::Seek as _;
+use std::io::SeekFrom;
 use std::iter;
 use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
 
 use itertools::Itertools as _;
 use smallvec::smallvec;
@@ -44,6 +48,7 @@ use super::entry::SmallLocalCommitPositionsVec;
 use super::mutable::DefaultMutableIndex;
 use super::revset_engine;
 use super::revset_engine::RevsetImpl;
+use super::revset_engine::RevsetProfileNode;
 use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::graph::GraphNode;
@@ -245,6 +250,13 @@ impl CommitGraphEntry<'_> {
 ///
 /// Note that u32 fields are 4-byte aligned so long as the parent file name
 /// (which is hexadecimal hash) and commit/change ids aren't of exotic length.
+///
+/// Only the headers of the segment files are read when the index is loaded.
+/// The tables of each segment are read on first access, so commands looking up
+/// recent commits don't have to read the older segments. Until then, commit ids
+/// are looked up by reading only the probed table entries. The files are kept
+/// open from load time so the tables can still be read if the files are
+/// removed by gc in the meantime.
 // TODO: replace the table by a trie so we don't have to repeat the full commit
 //       ids
 // TODO: add a fanout table like git's commit graph has?
@@ -263,7 +275,12 @@ pub(super) struct ReadonlyCommitIndexSegment {
     change_pos_table_base: usize,
     parent_overflow_base: usize,
     change_overflow_base: usize,
-    data: Vec<u8>,
+    // Offset and length of the tables in the segment file
+    data_offset: u64,
+    data_len: usize,
+    data: OnceLock<Vec<u8>>,
+    // File to read the tables from if they aren't loaded yet
+    file: Option<Mutex<File>>,
 }
 
 impl Debug for ReadonlyCommitIndexSegment {
@@ -276,7 +293,8 @@ impl Debug for ReadonlyCommitIndexSegment {
 }
 
 impl ReadonlyCommitIndexSegment {
-    /// Loads both parent segments and local entries from the given file `name`.
+    /// Loads the headers of the segment file `id` and its parent segments. The
+    /// local entries are read lazily.
     pub(super) fn load(
         dir: &Path,
         id: CommitIndexSegmentId,
@@ -284,16 +302,38 @@ impl ReadonlyCommitIndexSegment {
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
         let mut file = File::open(dir.join(id.hex()))
             .map_err(|err| ReadonlyIndexLoadError::from_io_err("commit", id.hex(), err))?;
-        Self::load_from(&mut file, dir, id, lengths)
+        let mut segment = Self::load_header(&mut file, dir, id, lengths)?;
+        let file_len = file
+            .metadata()
+            .map_err(|err| ReadonlyIndexLoadError::from_io_err("commit", segment.id.hex(), err))?
+            .len();
+        if file_len != segment.data_offset + segment.data_len as u64 {
+            return Err(ReadonlyIndexLoadError::invalid_data(
+                "commit",
+                segment.id.hex(),
+                "unexpected data length",
+            ));
+        }
+        segment.file = Some(Mutex::new(file));
+        Ok(Arc::new(segment))
     }
 
-    /// Loads both parent segments and local entries from the given `file`.
-    pub(super) fn load_from(
-        file: &mut dyn Read,
+    /// Reads the id of the parent segment of the segment file `id`.
+    pub(super) fn load_parent_id(
         dir: &Path,
-        id: CommitIndexSegmentId,
-        lengths: FieldLengths,
-    ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
+        id: &CommitIndexSegmentId,
+    ) -> Result<Option<CommitIndexSegmentId>, ReadonlyIndexLoadError> {
+        let mut file = File::open(dir.join(id.hex()))
+            .map_err(|err| ReadonlyIndexLoadError::from_io_err("commit", id.hex(), err))?;
+        Self::read_parent_id(&mut file, id)
+    }
+
+    /// Reads the format version and the parent segment id from the given
+    /// `file`.
+    fn read_parent_id(
+        file: &mut dyn Read,
+        id: &CommitIndexSegmentId,
+    ) -> Result<Option<CommitIndexSegmentId>, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err("commit", id.hex(), err);
         let read_u32 = |file: &mut dyn Read| {
             let mut buf = [0; 4];
@@ -309,24 +349,39 @@ impl ReadonlyCommitIndexSegment {
             });
         }
         let parent_filename_len = read_u32(file)?;
-        let maybe_parent_file = if parent_filename_len > 0 {
-            let mut parent_filename_bytes = vec![0; parent_filename_len as usize];
-            file.read_exact(&mut parent_filename_bytes)
-                .map_err(from_io_err)?;
-            let parent_file_id = CommitIndexSegmentId::try_from_hex(parent_filename_bytes)
-                .ok_or_else(|| {
-                    ReadonlyIndexLoadError::invalid_data(
-                        "commit",
-                        id.hex(),
-                        "parent file name is not valid hex",
-                    )
-                })?;
-            let parent_file = Self::load(dir, parent_file_id, lengths)?;
-            Some(parent_file)
-        } else {
-            None
-        };
-        Self::load_with_parent_file(file, id, maybe_parent_file, lengths)
+        if parent_filename_len == 0 {
+            return Ok(None);
+        }
+        let mut parent_filename_bytes = vec![0; parent_filename_len as usize];
+        file.read_exact(&mut parent_filename_bytes)
+            .map_err(from_io_err)?;
+        let parent_file_id =
+            CommitIndexSegmentId::try_from_hex(parent_filename_bytes).ok_or_else(|| {
+                ReadonlyIndexLoadError::invalid_data(
+                    "commit",
+                    id.hex(),
+                    "parent file name is not valid hex",
+                )
+            })?;
+        Ok(Some(parent_file_id))
+    }
+
+    /// Loads the parent segments and the header of local entries from the
+    /// given `file`.
+    fn load_header(
+        file: &mut dyn Read,
+        dir: &Path,
+        id: CommitIndexSegmentId,
+        lengths: FieldLengths,
+    ) -> Result<Self, ReadonlyIndexLoadError> {
+        let maybe_parent_id = Self::read_parent_id(file, &id)?;
+        let parent_filename_len = maybe_parent_id.as_ref().map_or(0, |id| id.hex().len());
+        let maybe_parent_file = maybe_parent_id
+            .map(|parent_id| Self::load(dir, parent_id, lengths))
+            .transpose()?;
+        let mut segment = Self::load_local_header(file, id, maybe_parent_file, lengths)?;
+        segment.data_offset = 4 + 4 + parent_filename_len as u64 + 4 * 4;
+        Ok(segment)
     }
 
     /// Loads local entries from the given `file`, returns new segment linked to
@@ -337,6 +392,27 @@ impl ReadonlyCommitIndexSegment {
         parent_file: Option<Arc<Self>>,
         lengths: FieldLengths,
     ) -> Result<Arc<Self>, ReadonlyIndexLoadError> {
+        let mut segment = Self::load_local_header(file, id, parent_file, lengths)?;
+        let mut data = vec![];
+        file.read_to_end(&mut data)
+            .map_err(|err| ReadonlyIndexLoadError::from_io_err("commit", segment.id.hex(), err))?;
+        if data.len() != segment.data_len {
+            return Err(ReadonlyIndexLoadError::invalid_data(
+                "commit",
+                segment.id.hex(),
+                "unexpected data length",
+            ));
+        }
+        segment.data = OnceLock::from(data);
+        Ok(Arc::new(segment))
+    }
+
+    fn load_local_header(
+        file: &mut dyn Read,
+        id: CommitIndexSegmentId,
+        parent_file: Option<Arc<Self>>,
+        lengths: FieldLengths,
+    ) -> Result<Self, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err("commit", id.hex(), err);
         let read_u32 = |file: &mut dyn Read| {
             let mut buf = [0; 4];
@@ -350,8 +426,6 @@ impl ReadonlyCommitIndexSegment {
         let num_local_change_ids = read_u32(file)?;
         let num_parent_overflow_entries = read_u32(file)?;
         let num_change_overflow_entries = read_u32(file)?;
-        let mut data = vec![];
-        file.read_to_end(&mut data).map_err(from_io_err)?;
 
         let commit_graph_entry_size = CommitGraphEntry::size(lengths.commit_id);
         let graph_size = (num_local_commits as usize) * commit_graph_entry_size;
@@ -367,17 +441,9 @@ impl ReadonlyCommitIndexSegment {
         let change_pos_table_base = change_id_table_base + change_id_table_size;
         let parent_overflow_base = change_pos_table_base + change_pos_table_size;
         let change_overflow_base = parent_overflow_base + parent_overflow_size;
-        let expected_size = change_overflow_base + change_overflow_size;
+        let data_len = change_overflow_base + change_overflow_size;
 
-        if data.len() != expected_size {
-            return Err(ReadonlyIndexLoadError::invalid_data(
-                "commit",
-                id.hex(),
-                "unexpected data length",
-            ));
-        }
-
-        Ok(Arc::new(Self {
+        Ok(Self {
             parent_file,
             num_parent_commits,
             id,
@@ -390,8 +456,46 @@ impl ReadonlyCommitIndexSegment {
             change_pos_table_base,
             parent_overflow_base,
             change_overflow_base,
-            data,
-        }))
+            data_offset: 0,
+            data_len,
+            data: OnceLock::new(),
+            file: None,
+        })
+    }
+
+    /// Returns the local entries, reading them from file if needed.
+    ///
+    /// The file was opened and its size was verified when the segment was
+    /// loaded, so the read can only fail due to an I/O error.
+    fn data(&self) -> &[u8] {
+        self.data.get_or_init(|| {
+            self.read_data().unwrap_or_else(|err| {
+                panic!(
+                    "failed to read commit index segment {}: {err}",
+                    self.id.hex()
+                )
+            })
+        })
+    }
+
+    fn read_data(&self) -> io::Result<Vec<u8>> {
+        tracing::debug!(id = self.id.hex(), "reading commit index segment");
+        let mut file = self
+            .file
+            .as_ref()
+            .expect("unloaded segment should have file")
+            .lock()
+            .unwrap();
+        file.seek(SeekFrom::Start(self.data_offset))?;
+        let mut data = Vec::with_capacity(self.data_len);
+        file.read_to_end(&mut data)?;
+        if data.len() != self.data_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unexpected data length",
+            ));
+        }
+        Ok(data)
     }
 
     pub(super) fn as_composite(&self) -> &CompositeCommitIndex {
@@ -407,7 +511,7 @@ impl ReadonlyCommitIndexSegment {
     }
 
     fn graph_entry(&self, local_pos: LocalCommitPosition) -> CommitGraphEntry<'_> {
-        let table = &self.data[..self.commit_lookup_base];
+        let table = &self.data()[..self.commit_lookup_base];
         let entry_size = CommitGraphEntry::size(self.field_lengths.commit_id);
         let offset = (local_pos.0 as usize) * entry_size;
         CommitGraphEntry {
@@ -416,7 +520,7 @@ impl ReadonlyCommitIndexSegment {
     }
 
     fn commit_lookup_pos(&self, lookup_pos: u32) -> LocalCommitPosition {
-        let table = &self.data[self.commit_lookup_base..self.change_id_table_base];
+        let table = &self.data()[self.commit_lookup_base..self.change_id_table_base];
         let offset = (lookup_pos as usize) * 4;
         LocalCommitPosition(u32::from_le_bytes(table[offset..][..4].try_into().unwrap()))
     }
@@ -427,13 +531,13 @@ impl ReadonlyCommitIndexSegment {
 
     // might be better to add borrowed version of ChangeId
     fn change_lookup_id_bytes(&self, lookup_pos: u32) -> &[u8] {
-        let table = &self.data[self.change_id_table_base..self.change_pos_table_base];
+        let table = &self.data()[self.change_id_table_base..self.change_pos_table_base];
         let offset = (lookup_pos as usize) * self.field_lengths.change_id;
         &table[offset..][..self.field_lengths.change_id]
     }
 
     fn change_lookup_pos(&self, lookup_pos: u32) -> ChangeLocalPosition {
-        let table = &self.data[self.change_pos_table_base..self.parent_overflow_base];
+        let table = &self.data()[self.change_pos_table_base..self.parent_overflow_base];
         let offset = (lookup_pos as usize) * 4;
         ChangeLocalPosition(u32::from_le_bytes(table[offset..][..4].try_into().unwrap()))
     }
@@ -443,7 +547,7 @@ impl ReadonlyCommitIndexSegment {
         overflow_pos: u32,
         num_parents: u32,
     ) -> SmallGlobalCommitPositionsVec {
-        let table = &self.data[self.parent_overflow_base..self.change_overflow_base];
+        let table = &self.data()[self.parent_overflow_base..self.change_overflow_base];
         let offset = (overflow_pos as usize) * 4;
         let size = (num_parents as usize) * 4;
         let (chunks, _remainder) = table[offset..][..size].as_chunks();
@@ -458,7 +562,7 @@ impl ReadonlyCommitIndexSegment {
         &self,
         overflow_pos: u32,
     ) -> impl Iterator<Item = LocalCommitPosition> {
-        let table = &self.data[self.change_overflow_base..];
+        let table = &self.data()[self.change_overflow_base..];
         let offset = (overflow_pos as usize) * 4;
         let (chunks, _remainder) = table[offset..].as_chunks();
         chunks
@@ -466,6 +570,46 @@ impl ReadonlyCommitIndexSegment {
             .map(|&chunk: &[u8; 4]| LocalCommitPosition(u32::from_le_bytes(chunk)))
     }
 
+    /// Binary searches `commit_id` by reading the probed entries from file,
+    /// without loading the tables.
+    fn read_commit_id_to_pos(
+        &self,
+        commit_id: &CommitId,
+    ) -> io::Result<Option<LocalCommitPosition>> {
+        let mut file = self
+            .file
+            .as_ref()
+            .expect("unloaded segment should have file")
+            .lock()
+            .unwrap();
+        let mut read_at = |offset: usize, buf: &mut [u8]| {
+            file.seek(SeekFrom::Start(self.data_offset + offset as u64))?;
+            file.read_exact(buf)
+        };
+        let entry_size = CommitGraphEntry::size(self.field_lengths.commit_id);
+        let mut pos_buf = [0; 4];
+        let mut id_buf = vec![0; self.field_lengths.commit_id];
+        let mut low = 0;
+        let mut high = self.num_local_commits;
+        while low < high {
+            let mid = (low + high) / 2;
+            read_at(self.commit_lookup_base + (mid as usize) * 4, &mut pos_buf)?;
+            let local_pos = LocalCommitPosition(u32::from_le_bytes(pos_buf));
+            read_at((local_pos.0 as usize) * entry_size + 16, &mut id_buf)?;
+            match id_buf.as_slice().cmp(commit_id.as_bytes()) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(Some(local_pos)),
+            }
+        }
+        Ok(None)
+    }
+
+    #[cfg(test)]
+    pub(super) fn is_loaded(&self) -> bool {
+        self.data.get().is_some()
+    }
+
     /// Binary searches commit id by `prefix`. Returns the lookup position.
     fn commit_id_byte_prefix_to_lookup_pos(&self, prefix: &[u8]) -> PositionLookupResult {
         binary_search_pos_by(self.num_local_commits, |pos| {
@@ -498,6 +642,14 @@ impl CommitIndexSegment for ReadonlyCommitIndexSegment {
     }
 
     fn commit_id_to_pos(&self, commit_id: &CommitId) -> Option<LocalCommitPosition> {
+        if self.data.get().is_none() && self.file.is_some() {
+            match self.read_commit_id_to_pos(commit_id) {
+                Ok(pos) => return pos,
+                Err(err) => {
+                    tracing::warn!(?err, id = self.id.hex(), "failed to look up commit index");
+                }
+            }
+        }
         self.commit_id_byte_prefix_to_lookup_pos(commit_id.as_bytes())
             .ok()
             .map(|pos| self.commit_lookup_pos(pos))
@@ -704,6 +856,19 @@ impl DefaultReadonlyIndex {
         Ok(DefaultReadonlyIndexRevset { inner })
     }
 
+    /// Evaluates the `expression`, collecting per-node statistics.
+    ///
+    /// The returned statistics are updated as the revset is iterated.
+    pub fn evaluate_revset_with_profile(
+        &self,
+        expression: &ResolvedExpression,
+        store: &Arc<Store>,
+    ) -> Result<(DefaultReadonlyIndexRevset, RevsetProfileNode), RevsetEvaluationError> {
+        let (inner, profile) =
+            revset_engine::evaluate_with_profile(expression, store, self.clone())?;
+        Ok((DefaultReadonlyIndexRevset { inner }, profile))
+    }
+
     pub(super) fn start_modification(&self) -> DefaultMutableIndex {
         DefaultMutableIndex::incremental(self)
     }
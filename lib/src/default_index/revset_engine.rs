@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::cmp::Reverse;
@@ -23,6 +24,8 @@ use std::iter;
 use std::ops::Range;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use bstr::BString;
 use futures::StreamExt as _;
@@ -98,6 +101,9 @@ trait InternalRevset: fmt::Debug + ToPredicateFn {
     fn positions<'a>(&self) -> BoxedRevWalk<'a>
     where
         Self: 'a;
+
+    /// Name of the evaluation strategy, reported by `RevsetProfileNode`.
+    fn strategy_name(&self) -> &'static str;
 }
 
 impl<T: InternalRevset + ?Sized> InternalRevset for Box<T> {
@@ -107,6 +113,10 @@ impl<T: InternalRevset + ?Sized> InternalRevset for Box<T> {
     {
         <T as InternalRevset>::positions(self)
     }
+
+    fn strategy_name(&self) -> &'static str {
+        <T as InternalRevset>::strategy_name(self)
+    }
 }
 
 pub(super) struct RevsetImpl<I> {
@@ -300,6 +310,10 @@ impl InternalRevset for EagerRevset {
         let walk = EagerRevWalk::new(self.positions.clone().into_iter());
         Box::new(walk.map(|_index, pos| Ok(pos)))
     }
+
+    fn strategy_name(&self) -> &'static str {
+        "EagerRevset"
+    }
 }
 
 impl ToPredicateFn for EagerRevset {
@@ -333,6 +347,10 @@ where
     {
         Box::new(self.walk.clone().map(|_index, pos| Ok(pos)))
     }
+
+    fn strategy_name(&self) -> &'static str {
+        "RevWalkRevset"
+    }
 }
 
 impl<W> ToPredicateFn for RevWalkRevset<W>
@@ -381,6 +399,10 @@ where
                 .transpose()
         }))
     }
+
+    fn strategy_name(&self) -> &'static str {
+        "FilterRevset"
+    }
 }
 
 impl<S, P> ToPredicateFn for FilterRevset<S, P>
@@ -432,6 +454,10 @@ where
             |pos1, pos2| pos1.cmp(pos2).reverse(),
         ))
     }
+
+    fn strategy_name(&self) -> &'static str {
+        "UnionRevset"
+    }
 }
 
 impl<S1, S2> ToPredicateFn for UnionRevset<S1, S2>
@@ -520,6 +546,10 @@ where
             |pos1, pos2| pos1.cmp(pos2).reverse(),
         ))
     }
+
+    fn strategy_name(&self) -> &'static str {
+        "IntersectionRevset"
+    }
 }
 
 impl<S1, S2> ToPredicateFn for IntersectionRevset<S1, S2>
@@ -628,6 +658,10 @@ where
             |pos1, pos2| pos1.cmp(pos2).reverse(),
         ))
     }
+
+    fn strategy_name(&self) -> &'static str {
+        "DifferenceRevset"
+    }
 }
 
 impl<S1, S2> ToPredicateFn for DifferenceRevset<S1, S2>
@@ -713,6 +747,142 @@ where
     }
 }
 
+/// Statistics of an evaluated expression node.
+///
+/// The counters are shared with the evaluated revset, and are updated as the
+/// revset is iterated.
+#[derive(Debug)]
+pub struct RevsetProfileNode {
+    label: String,
+    strategy: &'static str,
+    stats: Rc<ProfileStats>,
+    children: Vec<Self>,
+}
+
+impl RevsetProfileNode {
+    /// Kind and parameters of the expression node.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Name of the strategy the node was evaluated with.
+    pub fn strategy(&self) -> &str {
+        self.strategy
+    }
+
+    /// Number of commits emitted or matched by the node.
+    pub fn rows(&self) -> usize {
+        self.stats.rows.get()
+    }
+
+    /// Time spent in building and iterating the node, including the time
+    /// spent in its children.
+    pub fn time(&self) -> Duration {
+        self.stats.time.get()
+    }
+
+    /// Nodes evaluated as inputs of this node.
+    pub fn children(&self) -> &[Self] {
+        &self.children
+    }
+}
+
+#[derive(Debug, Default)]
+struct ProfileStats {
+    rows: Cell<usize>,
+    time: Cell<Duration>,
+}
+
+impl ProfileStats {
+    fn record<T>(&self, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = f();
+        self.time.set(self.time.get() + start.elapsed());
+        value
+    }
+}
+
+/// Collects `RevsetProfileNode`s while the expression is evaluated.
+#[derive(Debug)]
+struct RevsetProfiler {
+    // Nodes evaluated at each nesting level, the innermost last.
+    levels: RefCell<Vec<Vec<RevsetProfileNode>>>,
+}
+
+impl RevsetProfiler {
+    fn new() -> Self {
+        Self {
+            levels: RefCell::new(vec![vec![]]),
+        }
+    }
+
+    fn into_root(self) -> RevsetProfileNode {
+        let mut levels = self.levels.into_inner();
+        assert_eq!(levels.len(), 1);
+        let mut nodes = levels.pop().unwrap();
+        assert_eq!(nodes.len(), 1);
+        nodes.pop().unwrap()
+    }
+}
+
+/// Wrapper that counts rows and time spent in the inner revset.
+#[derive(Debug)]
+struct ProfiledRevset {
+    inner: Box<dyn InternalRevset>,
+    stats: Rc<ProfileStats>,
+}
+
+impl InternalRevset for ProfiledRevset {
+    fn positions<'a>(&self) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        let walk = self.stats.record(|| self.inner.positions());
+        Box::new(ProfiledRevWalk {
+            walk,
+            stats: self.stats.clone(),
+        })
+    }
+
+    fn strategy_name(&self) -> &'static str {
+        self.inner.strategy_name()
+    }
+}
+
+impl ToPredicateFn for ProfiledRevset {
+    fn to_predicate_fn<'a>(&self) -> BoxedPredicateFn<'a>
+    where
+        Self: 'a,
+    {
+        let mut predicate = self.stats.record(|| self.inner.to_predicate_fn());
+        let stats = self.stats.clone();
+        Box::new(move |index, pos| {
+            let matched = stats.record(|| predicate(index, pos))?;
+            if matched {
+                stats.rows.set(stats.rows.get() + 1);
+            }
+            Ok(matched)
+        })
+    }
+}
+
+struct ProfiledRevWalk<'a> {
+    walk: BoxedRevWalk<'a>,
+    stats: Rc<ProfileStats>,
+}
+
+impl RevWalk<CompositeIndex> for ProfiledRevWalk<'_> {
+    type Item = Result<GlobalCommitPosition, RevsetEvaluationError>;
+
+    fn next(&mut self, index: &CompositeIndex) -> Option<Self::Item> {
+        let item = self.stats.record(|| self.walk.next(index));
+        if let Some(Ok(_)) = item {
+            self.stats.rows.set(self.stats.rows.get() + 1);
+        }
+        item
+    }
+}
+
 pub(super) fn evaluate<I: AsCompositeIndex + Clone>(
     expression: &ResolvedExpression,
     store: &Arc<Store>,
@@ -721,14 +891,32 @@ pub(super) fn evaluate<I: AsCompositeIndex + Clone>(
     let context = EvaluationContext {
         store: store.clone(),
         index: index.as_composite(),
+        profiler: None,
     };
     let internal_revset = context.evaluate(expression)?;
     Ok(RevsetImpl::new(internal_revset, index))
 }
 
+/// Evaluates the `expression`, instrumenting each node of it.
+pub(super) fn evaluate_with_profile<I: AsCompositeIndex + Clone>(
+    expression: &ResolvedExpression,
+    store: &Arc<Store>,
+    index: I,
+) -> Result<(RevsetImpl<I>, RevsetProfileNode), RevsetEvaluationError> {
+    let context = EvaluationContext {
+        store: store.clone(),
+        index: index.as_composite(),
+        profiler: Some(RevsetProfiler::new()),
+    };
+    let internal_revset = context.evaluate(expression)?;
+    let profile = context.profiler.unwrap().into_root();
+    Ok((RevsetImpl::new(internal_revset, index), profile))
+}
+
 struct EvaluationContext<'index> {
     store: Arc<Store>,
     index: &'index CompositeIndex,
+    profiler: Option<RevsetProfiler>,
 }
 
 fn to_u32_generation_range(range: &Range<u64>) -> Result<Range<u32>, RevsetEvaluationError> {
@@ -745,6 +933,28 @@ impl EvaluationContext<'_> {
     fn evaluate(
         &self,
         expression: &ResolvedExpression,
+    ) -> Result<Box<dyn InternalRevset>, RevsetEvaluationError> {
+        let Some(profiler) = &self.profiler else {
+            return self.evaluate_node(expression);
+        };
+        let stats = Rc::new(ProfileStats::default());
+        profiler.levels.borrow_mut().push(vec![]);
+        let result = stats.record(|| self.evaluate_node(expression));
+        let children = profiler.levels.borrow_mut().pop().unwrap();
+        let inner = result?;
+        let node = RevsetProfileNode {
+            label: profile_label(expression),
+            strategy: inner.strategy_name(),
+            stats: stats.clone(),
+            children,
+        };
+        profiler.levels.borrow_mut().last_mut().unwrap().push(node);
+        Ok(Box::new(ProfiledRevset { inner, stats }))
+    }
+
+    fn evaluate_node(
+        &self,
+        expression: &ResolvedExpression,
     ) -> Result<Box<dyn InternalRevset>, RevsetEvaluationError> {
         let index = self.index;
         match expression {
@@ -1155,6 +1365,78 @@ impl EvaluationContext<'_> {
     }
 }
 
+fn profile_label(expression: &ResolvedExpression) -> String {
+    match expression {
+        ResolvedExpression::Commits(commit_ids) => format!("Commits [{}]", commit_ids.len()),
+        ResolvedExpression::Ancestors {
+            heads: _,
+            generation,
+            parents_range,
+        } => format!("Ancestors generation={generation:?} parents={parents_range:?}"),
+        ResolvedExpression::Range {
+            roots: _,
+            heads: _,
+            generation,
+            parents_range,
+        } => format!("Range generation={generation:?} parents={parents_range:?}"),
+        ResolvedExpression::DagRange {
+            roots: _,
+            heads: _,
+            generation_from_roots,
+        } => format!("DagRange generation_from_roots={generation_from_roots:?}"),
+        ResolvedExpression::Reachable { .. } => "Reachable".to_owned(),
+        ResolvedExpression::Heads(_) => "Heads".to_owned(),
+        ResolvedExpression::HeadsRange {
+            roots: _,
+            heads: _,
+            parents_range,
+            filter,
+        } => format!(
+            "HeadsRange parents={parents_range:?} filtered={}",
+            filter.is_some()
+        ),
+        ResolvedExpression::Roots(_) => "Roots".to_owned(),
+        ResolvedExpression::ForkPoint(_) => "ForkPoint".to_owned(),
+        ResolvedExpression::Bisect(_) => "Bisect".to_owned(),
+        ResolvedExpression::HasSize {
+            candidates: _,
+            count,
+        } => format!("HasSize count={count}"),
+        ResolvedExpression::Latest {
+            candidates: _,
+            count,
+        } => format!("Latest count={count}"),
+        ResolvedExpression::Coalesce(_, _) => "Coalesce".to_owned(),
+        ResolvedExpression::Union(_, _) => "Union".to_owned(),
+        ResolvedExpression::FilterWithin {
+            candidates: _,
+            predicate,
+        } => format!("FilterWithin {}", predicate_label(predicate)),
+        ResolvedExpression::Intersection(_, _) => "Intersection".to_owned(),
+        ResolvedExpression::Difference(_, _) => "Difference".to_owned(),
+    }
+}
+
+fn predicate_label(predicate: &ResolvedPredicateExpression) -> String {
+    match predicate {
+        ResolvedPredicateExpression::Filter(filter) => format!("{filter:?}"),
+        ResolvedPredicateExpression::Set(_) => "Set".to_owned(),
+        ResolvedPredicateExpression::NotIn(predicate) => {
+            format!("NotIn({})", predicate_label(predicate))
+        }
+        ResolvedPredicateExpression::Union(predicate1, predicate2) => format!(
+            "Union({}, {})",
+            predicate_label(predicate1),
+            predicate_label(predicate2)
+        ),
+        ResolvedPredicateExpression::Intersection(predicate1, predicate2) => format!(
+            "Intersection({}, {})",
+            predicate_label(predicate1),
+            predicate_label(predicate2)
+        ),
+    }
+}
+
 struct PurePredicateFn<F>(F);
 
 impl<F> fmt::Debug for PurePredicateFn<F> {
@@ -1853,6 +2135,7 @@ mod tests {
             let options = MergeOptions {
                 hunk_level: FileMergeHunkLevel::Line,
                 same_change: SameChange::Accept,
+                drivers: Default::default(),
             };
             diff_match_lines(&left1, &left2, &matcher, &options).unwrap()
         };
@@ -1878,6 +2161,7 @@ mod tests {
             let options = MergeOptions {
                 hunk_level: FileMergeHunkLevel::Line,
                 same_change: SameChange::Accept,
+                drivers: Default::default(),
             };
             diff_match_lines(&conflict1, &conflict2, &matcher, &options).unwrap()
         };
@@ -1907,6 +2191,7 @@ mod tests {
             let options = MergeOptions {
                 hunk_level: FileMergeHunkLevel::Line,
                 same_change: SameChange::Accept,
+                drivers: Default::default(),
             };
             diff_match_lines(&base, &conflict2, &matcher, &options).unwrap()
         };
@@ -23,6 +23,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::slice;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
@@ -56,6 +57,7 @@ use crate::index::IndexStoreResult;
 use crate::index::MutableIndex;
 use crate::index::ReadonlyIndex;
 use crate::object_id::ObjectId as _;
+use crate::op_store::OpStore;
 use crate::op_store::OpStoreError;
 use crate::op_store::OperationId;
 use crate::op_walk;
@@ -96,6 +98,15 @@ pub enum DefaultIndexStoreError {
     },
     #[error(transparent)]
     OpStore(#[from] OpStoreError),
+    #[error("Failed to remove unused index files")]
+    Gc(#[source] PathError),
+}
+
+/// Segments associated with an operation.
+struct OpLink {
+    commit_segment_id: CommitIndexSegmentId,
+    changed_path_start_commit_pos: Option<GlobalCommitPosition>,
+    changed_path_segment_ids: Vec<ChangedPathIndexSegmentId>,
 }
 
 #[derive(Debug)]
@@ -182,6 +193,28 @@ impl DefaultIndexStore {
         op_id: &OperationId,
         lengths: FieldLengths,
     ) -> Result<DefaultReadonlyIndex, DefaultIndexStoreError> {
+        let op_link = self.read_op_link(op_id)?;
+        let commits = ReadonlyCommitIndexSegment::load(
+            &self.commit_segments_dir(),
+            op_link.commit_segment_id,
+            lengths,
+        )
+        .map_err(DefaultIndexStoreError::LoadIndex)?;
+        let changed_paths = if let Some(start_commit_pos) = op_link.changed_path_start_commit_pos {
+            CompositeChangedPathIndex::load(
+                &self.changed_path_segments_dir(),
+                start_commit_pos,
+                &op_link.changed_path_segment_ids,
+            )
+            .map_err(DefaultIndexStoreError::LoadIndex)?
+        } else {
+            CompositeChangedPathIndex::null()
+        };
+        Ok(DefaultReadonlyIndex::from_segment(commits, changed_paths))
+    }
+
+    /// Reads the names of the segments associated with the operation.
+    fn read_op_link(&self, op_id: &OperationId) -> Result<OpLink, DefaultIndexStoreError> {
         let commit_segment_id;
         let changed_path_start_commit_pos;
         let changed_path_segment_ids;
@@ -219,25 +252,114 @@ impl DefaultIndexStore {
             }
             Err(err) => return Err(DefaultIndexStoreError::LoadAssociation(err)),
         };
-
-        let commits = ReadonlyCommitIndexSegment::load(
-            &self.commit_segments_dir(),
+        Ok(OpLink {
             commit_segment_id,
-            lengths,
-        )
-        .map_err(DefaultIndexStoreError::LoadIndex)?;
-        // TODO: lazy load or mmap?
-        let changed_paths = if let Some(start_commit_pos) = changed_path_start_commit_pos {
-            CompositeChangedPathIndex::load(
-                &self.changed_path_segments_dir(),
-                start_commit_pos,
-                &changed_path_segment_ids,
-            )
-            .map_err(DefaultIndexStoreError::LoadIndex)?
-        } else {
-            CompositeChangedPathIndex::null()
+            changed_path_start_commit_pos,
+            changed_path_segment_ids,
+        })
+    }
+
+    /// Removes the index files that are no longer used.
+    ///
+    /// Links to operations that no longer exist in the `op_store` are removed
+    /// first, then the segment files that aren't reachable from the remaining
+    /// links. Files modified after `keep_newer` are preserved.
+    pub fn gc(
+        &self,
+        op_store: &dyn OpStore,
+        keep_newer: SystemTime,
+    ) -> Result<(), DefaultIndexStoreError> {
+        let is_new = |entry: &fs::DirEntry| -> Result<bool, PathError> {
+            let metadata = entry.metadata().context(entry.path())?;
+            let mtime = metadata.modified().context(entry.path())?;
+            Ok(mtime > keep_newer)
         };
-        Ok(DefaultReadonlyIndex::from_segment(commits, changed_paths))
+        let remove_file = |entry: &fs::DirEntry| -> Result<(), PathError> {
+            let path = entry.path();
+            tracing::trace!(?path, "removing");
+            fs::remove_file(&path).context(&path)
+        };
+        // Directories may be missing if the store was initialized by old jj.
+        self.ensure_base_dirs()
+            .map_err(DefaultIndexStoreError::Gc)?;
+
+        let mut live_op_ids = vec![];
+        for dir in [self.op_links_dir(), self.legacy_operations_dir()] {
+            for entry in dir
+                .read_dir()
+                .context(&dir)
+                .map_err(DefaultIndexStoreError::Gc)?
+            {
+                let entry = entry.context(&dir).map_err(DefaultIndexStoreError::Gc)?;
+                let Some(op_id) = entry
+                    .file_name()
+                    .to_str()
+                    .and_then(OperationId::try_from_hex)
+                else {
+                    continue;
+                };
+                match op_store.read_operation(&op_id).block_on() {
+                    Ok(_) => live_op_ids.push(op_id),
+                    Err(OpStoreError::ObjectNotFound { .. }) => {
+                        if is_new(&entry).map_err(DefaultIndexStoreError::Gc)? {
+                            live_op_ids.push(op_id);
+                        } else {
+                            remove_file(&entry).map_err(DefaultIndexStoreError::Gc)?;
+                        }
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+
+        let mut reachable_segment_names = HashSet::new();
+        for op_id in live_op_ids.iter().unique() {
+            let op_link = match self.read_op_link(op_id) {
+                Ok(op_link) => op_link,
+                // The link may have been removed by the loop above.
+                Err(DefaultIndexStoreError::LoadAssociation(PathError { source, .. }))
+                    if source.kind() == io::ErrorKind::NotFound =>
+                {
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let mut maybe_segment_id = Some(op_link.commit_segment_id);
+            while let Some(segment_id) = maybe_segment_id {
+                if !reachable_segment_names.insert(segment_id.hex()) {
+                    break;
+                }
+                maybe_segment_id = ReadonlyCommitIndexSegment::load_parent_id(
+                    &self.commit_segments_dir(),
+                    &segment_id,
+                )
+                .map_err(DefaultIndexStoreError::LoadIndex)?;
+            }
+            reachable_segment_names
+                .extend(op_link.changed_path_segment_ids.iter().map(|id| id.hex()));
+        }
+
+        for dir in [self.commit_segments_dir(), self.changed_path_segments_dir()] {
+            for entry in dir
+                .read_dir()
+                .context(&dir)
+                .map_err(DefaultIndexStoreError::Gc)?
+            {
+                let entry = entry.context(&dir).map_err(DefaultIndexStoreError::Gc)?;
+                let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+                    continue;
+                };
+                // Skip temporary files, etc.
+                if name.len() != SEGMENT_FILE_NAME_LENGTH
+                    || reachable_segment_names.contains(&name)
+                    || is_new(&entry).map_err(DefaultIndexStoreError::Gc)?
+                {
+                    continue;
+                }
+                remove_file(&entry).map_err(DefaultIndexStoreError::Gc)?;
+            }
+        }
+        Ok(())
     }
 
     /// Rebuilds index for the given `operation`.
@@ -19,6 +19,8 @@ use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt as _;
 
 use crate::config::ConfigGetError;
+use crate::gitattributes::AttributeState;
+use crate::gitattributes::ContentAttributes;
 use crate::settings::UserSettings;
 
 fn is_binary(bytes: &[u8]) -> bool {
@@ -29,11 +31,7 @@ fn is_binary(bytes: &[u8]) -> bool {
     while let Some(byte) = bytes.next() {
         match *byte {
             b'\0' => return true,
-            b'\r' => {
-                if bytes.peek() != Some(&&b'\n') {
-                    return true;
-                }
-            }
+            b'\r' if bytes.peek() != Some(&&b'\n') => return true,
             _ => {}
         }
     }
@@ -45,6 +43,17 @@ pub(crate) struct TargetEolStrategy {
     eol_conversion_mode: EolConversionMode,
 }
 
+/// How a file should be treated for EOL conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TextMode {
+    /// Never convert.
+    Binary,
+    /// Always convert as text.
+    Text,
+    /// Probe the content to decide whether it's text.
+    Auto,
+}
+
 impl TargetEolStrategy {
     pub(crate) fn new(eol_conversion_mode: EolConversionMode) -> Self {
         Self {
@@ -55,51 +64,75 @@ impl TargetEolStrategy {
     /// The limit is to probe whether the file is binary is 8KB.
     const PROBE_LIMIT: u64 = 8 << 10;
 
-    pub(crate) async fn convert_eol_for_snapshot<'a>(
-        &self,
-        mut contents: impl AsyncRead + Send + Unpin + 'a,
-    ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
-        match self.eol_conversion_mode {
-            EolConversionMode::None => Ok(Box::new(contents)),
-            EolConversionMode::Input | EolConversionMode::InputOutput => {
-                let mut peek = vec![];
-                (&mut contents)
-                    .take(Self::PROBE_LIMIT)
-                    .read_to_end(&mut peek)
-                    .await?;
-                let target_eol = if is_binary(&peek) {
-                    TargetEol::PassThrough
+    /// Decides whether to convert based on the `text` and `eol` attributes,
+    /// falling back to the configured mode if they are unspecified.
+    fn text_mode(&self, attributes: &ContentAttributes) -> TextMode {
+        match &attributes.text {
+            AttributeState::Unset => TextMode::Binary,
+            AttributeState::Set => TextMode::Text,
+            AttributeState::Value(value) if value == "auto" => TextMode::Auto,
+            AttributeState::Value(_) | AttributeState::Unspecified => {
+                if attributes.eol.as_value().is_some() {
+                    TextMode::Text
+                } else if self.eol_conversion_mode == EolConversionMode::None {
+                    TextMode::Binary
                 } else {
-                    TargetEol::Lf
-                };
-                let peek = Cursor::new(peek);
-                let contents = peek.chain(contents);
-                convert_eol(contents, target_eol).await
+                    TextMode::Auto
+                }
             }
         }
     }
 
+    pub(crate) async fn convert_eol_for_snapshot<'a>(
+        &self,
+        contents: impl AsyncRead + Send + Unpin + 'a,
+        attributes: &ContentAttributes,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
+        convert_eol_with_mode(contents, self.text_mode(attributes), TargetEol::Lf).await
+    }
+
     pub(crate) async fn convert_eol_for_update<'a>(
         &self,
-        mut contents: impl AsyncRead + Send + Unpin + 'a,
+        contents: impl AsyncRead + Send + Unpin + 'a,
+        attributes: &ContentAttributes,
     ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
-        match self.eol_conversion_mode {
-            EolConversionMode::None | EolConversionMode::Input => Ok(Box::new(contents)),
-            EolConversionMode::InputOutput => {
-                let mut peek = vec![];
-                (&mut contents)
-                    .take(Self::PROBE_LIMIT)
-                    .read_to_end(&mut peek)
-                    .await?;
-                let target_eol = if is_binary(&peek) {
-                    TargetEol::PassThrough
-                } else {
-                    TargetEol::Crlf
-                };
-                let peek = Cursor::new(peek);
-                let contents = peek.chain(contents);
-                convert_eol(contents, target_eol).await
+        let target_eol = match attributes.eol.as_value() {
+            Some("crlf") => TargetEol::Crlf,
+            Some("lf") => TargetEol::Lf,
+            _ => match self.eol_conversion_mode {
+                EolConversionMode::None | EolConversionMode::Input => TargetEol::PassThrough,
+                EolConversionMode::InputOutput => TargetEol::Crlf,
+            },
+        };
+        convert_eol_with_mode(contents, self.text_mode(attributes), target_eol).await
+    }
+}
+
+async fn convert_eol_with_mode<'a>(
+    mut contents: impl AsyncRead + Send + Unpin + 'a,
+    text_mode: TextMode,
+    target_eol: TargetEol,
+) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
+    match text_mode {
+        TextMode::Binary => Ok(Box::new(contents)),
+        TextMode::Text => convert_eol(contents, target_eol).await,
+        TextMode::Auto => {
+            if target_eol == TargetEol::PassThrough {
+                return Ok(Box::new(contents));
             }
+            let mut peek = vec![];
+            (&mut contents)
+                .take(TargetEolStrategy::PROBE_LIMIT)
+                .read_to_end(&mut peek)
+                .await?;
+            let target_eol = if is_binary(&peek) {
+                TargetEol::PassThrough
+            } else {
+                target_eol
+            };
+            let peek = Cursor::new(peek);
+            let contents = peek.chain(contents);
+            convert_eol(contents, target_eol).await
         }
     }
 }
@@ -284,7 +317,7 @@ mod tests {
     ) {
         let mut actual_output = vec![];
         strategy
-            .convert_eol_for_snapshot(contents)
+            .convert_eol_for_snapshot(contents, &ContentAttributes::default())
             .await
             .unwrap()
             .read_to_end(&mut actual_output)
@@ -316,7 +349,71 @@ mod tests {
     ) {
         let mut actual_output = vec![];
         strategy
-            .convert_eol_for_update(contents)
+            .convert_eol_for_update(contents, &ContentAttributes::default())
+            .await
+            .unwrap()
+            .read_to_end(&mut actual_output)
+            .await
+            .unwrap();
+        assert_eq!(actual_output, expected_output);
+    }
+
+    fn attributes(text: AttributeState, eol: AttributeState) -> ContentAttributes {
+        ContentAttributes {
+            text,
+            eol,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::main(flavor = "current_thread")]
+    #[test_case(EolConversionMode::None, AttributeState::Set, AttributeState::Unspecified,
+        b"a\r\n", b"a\n"; "text attribute overrides none settings")]
+    #[test_case(EolConversionMode::Input, AttributeState::Unset, AttributeState::Unspecified,
+        b"a\r\n", b"a\r\n"; "unset text attribute overrides input settings")]
+    #[test_case(EolConversionMode::None, AttributeState::Unspecified,
+        AttributeState::Value("crlf".to_owned()), b"a\r\n", b"a\n"; "eol attribute implies text")]
+    #[test_case(EolConversionMode::None, AttributeState::Set, AttributeState::Unspecified,
+        b"\0\r\n", b"\0\n"; "text attribute skips binary probe")]
+    #[test_case(EolConversionMode::None, AttributeState::Value("auto".to_owned()),
+        AttributeState::Unspecified, b"\0\r\n", b"\0\r\n"; "auto text attribute probes binary")]
+    async fn test_eol_strategy_convert_eol_for_snapshot_with_attributes(
+        mode: EolConversionMode,
+        text: AttributeState,
+        eol: AttributeState,
+        contents: &[u8],
+        expected_output: &[u8],
+    ) {
+        let mut actual_output = vec![];
+        TargetEolStrategy::new(mode)
+            .convert_eol_for_snapshot(contents, &attributes(text, eol))
+            .await
+            .unwrap()
+            .read_to_end(&mut actual_output)
+            .await
+            .unwrap();
+        assert_eq!(actual_output, expected_output);
+    }
+
+    #[tokio::main(flavor = "current_thread")]
+    #[test_case(EolConversionMode::None, AttributeState::Unspecified,
+        AttributeState::Value("crlf".to_owned()), b"a\n", b"a\r\n"; "eol crlf attribute")]
+    #[test_case(EolConversionMode::InputOutput, AttributeState::Set,
+        AttributeState::Value("lf".to_owned()), b"a\n", b"a\n"; "eol lf attribute")]
+    #[test_case(EolConversionMode::InputOutput, AttributeState::Unset,
+        AttributeState::Unspecified, b"a\n", b"a\n"; "unset text attribute")]
+    #[test_case(EolConversionMode::Input, AttributeState::Set,
+        AttributeState::Unspecified, b"a\n", b"a\n"; "text attribute with input settings")]
+    async fn test_eol_strategy_convert_eol_for_update_with_attributes(
+        mode: EolConversionMode,
+        text: AttributeState,
+        eol: AttributeState,
+        contents: &[u8],
+        expected_output: &[u8],
+    ) {
+        let mut actual_output = vec![];
+        TargetEolStrategy::new(mode)
+            .convert_eol_for_update(contents, &attributes(text, eol))
             .await
             .unwrap()
             .read_to_end(&mut actual_output)
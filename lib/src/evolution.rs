@@ -33,6 +33,7 @@ use crate::op_store::OpStoreError;
 use crate::op_store::OpStoreResult;
 use crate::op_walk;
 use crate::operation::Operation;
+use crate::repo::MutableRepo;
 use crate::repo::ReadonlyRepo;
 use crate::repo::Repo as _;
 
@@ -299,6 +300,19 @@ pub fn accumulate_predecessors(
         .map_err(|id| WalkPredecessorsError::CycleDetected(id.clone()))
 }
 
+/// Collects predecessor records of the commits created or rewritten in the
+/// uncommitted transaction, and resolves transitive entries.
+///
+/// The returned mapping is the same as [`accumulate_predecessors()`] would
+/// return for the operation the transaction would create.
+pub fn accumulate_transaction_predecessors(
+    mut_repo: &MutableRepo,
+) -> Result<BTreeMap<CommitId, Vec<CommitId>>, WalkPredecessorsError> {
+    let map = mut_repo.commit_predecessors();
+    resolve_transitive_edges(map, map.keys())
+        .map_err(|id| WalkPredecessorsError::CycleDetected(id.clone()))
+}
+
 fn try_collect_predecessors_into(
     collected: &mut BTreeMap<CommitId, Vec<CommitId>>,
     ops: impl IntoIterator<Item = OpStoreResult<Operation>>,
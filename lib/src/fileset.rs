@@ -617,6 +617,28 @@ pub fn parse_maybe_bare(
     resolve_expression(diagnostics, path_converter, &node)
 }
 
+/// Parses config `patterns` into the union of the expressions. Paths are
+/// relative to the workspace root.
+pub fn parse_config_patterns(
+    patterns: &[impl AsRef<str>],
+) -> FilesetParseResult<FilesetExpression> {
+    let path_converter = RepoPathUiConverter::Fs {
+        cwd: "".into(),
+        base: "".into(),
+    };
+    let expressions: Vec<_> = patterns
+        .iter()
+        .map(|pattern| {
+            parse(
+                &mut FilesetDiagnostics::new(),
+                pattern.as_ref(),
+                &path_converter,
+            )
+        })
+        .try_collect()?;
+    Ok(FilesetExpression::union_all(expressions))
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
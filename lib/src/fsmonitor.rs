@@ -34,12 +34,24 @@ pub struct WatchmanConfig {
     pub register_trigger: bool,
 }
 
+/// Config for the built-in filesystem monitor.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct BuiltinConfig {
+    /// Whether to snapshot the working copy in the background whenever the
+    /// filesystem has been quiet for a moment.
+    pub auto_snapshot: bool,
+}
+
 /// The recognized kinds of filesystem monitors.
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub enum FsmonitorSettings {
     /// The Watchman filesystem monitor (<https://facebook.github.io/watchman/>).
     Watchman(WatchmanConfig),
 
+    /// The built-in filesystem monitor, which watches the working copy from a
+    /// background `jj` process.
+    Builtin(BuiltinConfig),
+
     /// Only used in tests.
     Test {
         /// The set of changed files to pretend that the filesystem monitor is
@@ -63,6 +75,9 @@ impl FsmonitorSettings {
                 register_trigger: settings
                     .get_bool("fsmonitor.watchman.register-snapshot-trigger")?,
             })),
+            "builtin" => Ok(Self::Builtin(BuiltinConfig {
+                auto_snapshot: settings.get_bool("fsmonitor.builtin.auto-snapshot")?,
+            })),
             "test" => Err(ConfigGetError::Type {
                 name: name.to_owned(),
                 error: "Cannot use test fsmonitor in real repository".into(),
@@ -333,3 +348,449 @@ pub mod watchman {
         }
     }
 }
+
+/// Built-in filesystem monitor, which doesn't depend on any external tool.
+///
+/// A daemon process watches the working copy with the platform's native file
+/// notification API and remembers which paths changed. Snapshots query the
+/// daemon over a Unix socket for the paths changed since the previous query, so
+/// only those need to be checked.
+#[cfg(all(feature = "notify", unix))]
+pub mod builtin {
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::ffi::OsStr;
+    use std::fs;
+    use std::io;
+    use std::io::BufRead as _;
+    use std::io::BufReader;
+    use std::io::BufWriter;
+    use std::io::Read as _;
+    use std::io::Write;
+    use std::mem;
+    use std::os::unix::ffi::OsStrExt as _;
+    use std::os::unix::net::UnixListener;
+    use std::os::unix::net::UnixStream;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::process;
+    use std::sync::Arc;
+    use std::sync::Condvar;
+    use std::sync::Mutex;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    use notify::Watcher as _;
+    use thiserror::Error;
+    use tracing::info;
+    use tracing::instrument;
+
+    /// How long a query waits for the daemon to catch up with the filesystem.
+    const SYNC_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// How long the working copy must be left alone before it is
+    /// automatically snapshotted.
+    const AUTO_SNAPSHOT_DELAY: Duration = Duration::from_secs(1);
+
+    /// Prefix of the files the daemon creates to catch up with the filesystem.
+    const COOKIE_PREFIX: &str = "fsmonitor-cookie-";
+
+    /// Directories whose contents are never reported as changed.
+    const EXCLUDED_DIRS: [&str; 2] = [".git", ".jj"];
+
+    /// Represents an instance in time from the perspective of the daemon.
+    ///
+    /// Clocks are only meaningful to the daemon that issued them. If the daemon
+    /// has been restarted since, or might have missed some events, the next
+    /// query asks the caller to crawl the entire working copy.
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    pub struct Clock(String);
+
+    impl From<crate::protos::local_working_copy::WatchmanClock> for Clock {
+        fn from(clock: crate::protos::local_working_copy::WatchmanClock) -> Self {
+            use crate::protos::local_working_copy::watchman_clock::WatchmanClock;
+            match clock.watchman_clock {
+                Some(WatchmanClock::StringClock(string_clock)) => Self(string_clock),
+                // Left by another filesystem monitor
+                Some(WatchmanClock::UnixTimestamp(_)) | None => Self::default(),
+            }
+        }
+    }
+
+    impl From<Clock> for crate::protos::local_working_copy::WatchmanClock {
+        fn from(clock: Clock) -> Self {
+            use crate::protos::local_working_copy::watchman_clock;
+            let Clock(clock) = clock;
+            Self {
+                watchman_clock: Some(watchman_clock::WatchmanClock::StringClock(clock)),
+            }
+        }
+    }
+
+    #[expect(missing_docs)]
+    #[derive(Debug, Error)]
+    pub enum Error {
+        #[error("Could not canonicalize working copy root path")]
+        CanonicalizeRootError(#[source] io::Error),
+
+        #[error("Could not connect to the filesystem monitor daemon")]
+        ConnectError(#[source] io::Error),
+
+        #[error("Failed to query the filesystem monitor daemon")]
+        QueryError(#[source] io::Error),
+
+        #[error("Invalid response from the filesystem monitor daemon")]
+        InvalidResponse,
+
+        #[error("A filesystem monitor daemon is already running for this working copy")]
+        AlreadyRunning,
+
+        #[error("Failed to listen for queries")]
+        ListenError(#[source] io::Error),
+
+        #[error("Failed to watch the working copy")]
+        WatchError(#[source] notify::Error),
+    }
+
+    /// Returns the path of the socket the daemon for `working_copy_path`
+    /// listens on.
+    pub fn socket_path(working_copy_path: &Path) -> PathBuf {
+        working_copy_path.join(".jj").join("fsmonitor.sock")
+    }
+
+    /// Returns whether a daemon is running for `working_copy_path`.
+    pub fn is_daemon_running(working_copy_path: &Path) -> bool {
+        UnixStream::connect(socket_path(working_copy_path)).is_ok()
+    }
+
+    /// Query the daemon for changed files since the previous point in time.
+    ///
+    /// The returned list of paths is relative to the `working_copy_path`, and
+    /// may include directories that were created, removed, or renamed as a
+    /// whole. If it is `None`, then the caller must crawl the entire working
+    /// copy themselves.
+    #[instrument]
+    pub fn query_changed_files(
+        working_copy_path: &Path,
+        previous_clock: Option<Clock>,
+    ) -> Result<(Clock, Option<Vec<PathBuf>>), Error> {
+        info!("Querying the built-in filesystem monitor for changed files...");
+        let mut stream =
+            UnixStream::connect(socket_path(working_copy_path)).map_err(Error::ConnectError)?;
+        let Clock(previous_clock) = previous_clock.unwrap_or_default();
+        writeln!(stream, "{previous_clock}").map_err(Error::QueryError)?;
+        let mut response = vec![];
+        stream
+            .read_to_end(&mut response)
+            .map_err(Error::QueryError)?;
+        parse_response(&response).ok_or(Error::InvalidResponse)
+    }
+
+    /// Watches the working copy and answers queries.
+    ///
+    /// If `auto_snapshot` is set, it is called on a background thread whenever
+    /// the working copy has changed and then stayed unchanged for a moment.
+    /// Changes made while it runs lead to another call once it has returned.
+    ///
+    /// This runs until the workspace is deleted, at which point the process
+    /// exits.
+    #[instrument(skip(auto_snapshot))]
+    pub fn run_daemon(
+        working_copy_path: &Path,
+        auto_snapshot: Option<Box<dyn Fn() + Send>>,
+    ) -> Result<(), Error> {
+        let root = dunce::canonicalize(working_copy_path).map_err(Error::CanonicalizeRootError)?;
+        let socket_path = socket_path(&root);
+        if is_daemon_running(&root) {
+            return Err(Error::AlreadyRunning);
+        }
+        // A daemon that was killed leaves its socket behind.
+        match fs::remove_file(&socket_path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(Error::ListenError(err)),
+        }
+        let listener = UnixListener::bind(&socket_path).map_err(Error::ListenError)?;
+
+        let shared = Arc::new((Mutex::new(State::new()), Condvar::new()));
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(Error::WatchError)?;
+        watcher
+            .watch(&root, notify::RecursiveMode::Recursive)
+            .map_err(Error::WatchError)?;
+        info!(?root, "Watching working copy");
+        let snapshot_requests = auto_snapshot.map(|snapshot| {
+            // A single pending request covers any number of changes.
+            let (sender, receiver) = mpsc::sync_channel(1);
+            thread::spawn(move || {
+                for () in receiver {
+                    snapshot();
+                }
+            });
+            sender
+        });
+        thread::spawn({
+            let root = root.clone();
+            let shared = shared.clone();
+            move || process_events(&root, &shared, receiver, snapshot_requests)
+        });
+
+        for (query_number, stream) in listener.incoming().enumerate() {
+            let result =
+                stream.and_then(|stream| answer_query(&root, &shared, &stream, query_number));
+            if let Err(err) = result {
+                tracing::warn!(?err, "Failed to answer query");
+            }
+        }
+        Ok(())
+    }
+
+    struct State {
+        /// Identifies the set of events the `changed_paths` are based on. It
+        /// changes whenever events might have been missed.
+        instance: String,
+        sequence: u64,
+        /// The paths that changed, and the `sequence` at their last change.
+        changed_paths: HashMap<PathBuf, u64>,
+        seen_cookies: HashSet<PathBuf>,
+    }
+
+    impl State {
+        fn new() -> Self {
+            let mut state = Self {
+                instance: String::new(),
+                sequence: 0,
+                changed_paths: HashMap::new(),
+                seen_cookies: HashSet::new(),
+            };
+            state.reset();
+            state
+        }
+
+        fn reset(&mut self) {
+            let timestamp = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default();
+            self.instance = format!("{}-{}", process::id(), timestamp.as_nanos());
+            self.sequence = 0;
+            self.changed_paths.clear();
+        }
+
+        fn clock(&self) -> Clock {
+            Clock(format!("builtin:{}:{}", self.instance, self.sequence))
+        }
+
+        /// Returns the paths changed since `clock`, or `None` if the changes
+        /// aren't known.
+        fn changed_since(&self, Clock(clock): &Clock) -> Option<Vec<&Path>> {
+            let (instance, sequence) = clock.strip_prefix("builtin:")?.rsplit_once(':')?;
+            let sequence: u64 = sequence.parse().ok()?;
+            (instance == self.instance).then(|| {
+                self.changed_paths
+                    .iter()
+                    .filter(|&(_, &changed)| changed > sequence)
+                    .map(|(path, _)| path.as_path())
+                    .collect()
+            })
+        }
+    }
+
+    fn process_events(
+        root: &Path,
+        shared: &(Mutex<State>, Condvar),
+        receiver: mpsc::Receiver<notify::Result<notify::Event>>,
+        snapshot_requests: Option<mpsc::SyncSender<()>>,
+    ) {
+        let (state, cookie_seen) = shared;
+        let jj_dir = root.join(".jj");
+        let mut changed_since_snapshot = false;
+        loop {
+            let event = if let Some(snapshot_requests) = &snapshot_requests {
+                match receiver.recv_timeout(AUTO_SNAPSHOT_DELAY) {
+                    Ok(event) => event,
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        if mem::take(&mut changed_since_snapshot) {
+                            // If the channel is full, the pending snapshot
+                            // will include these changes anyway.
+                            snapshot_requests.try_send(()).ok();
+                        }
+                        continue;
+                    }
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            } else {
+                let Ok(event) = receiver.recv() else {
+                    break;
+                };
+                event
+            };
+            let mut state = state.lock().unwrap();
+            let event = match event {
+                Ok(event) if !event.need_rescan() => event,
+                _ => {
+                    // Some events were dropped, so the next query must crawl
+                    // the whole working copy.
+                    state.reset();
+                    changed_since_snapshot = true;
+                    continue;
+                }
+            };
+            for path in event.paths {
+                let Ok(relative_path) = path.strip_prefix(root) else {
+                    continue;
+                };
+                let Some(first) = relative_path.components().next() else {
+                    continue;
+                };
+                if !EXCLUDED_DIRS.iter().any(|&dir| first.as_os_str() == dir) {
+                    changed_since_snapshot = true;
+                    state.sequence += 1;
+                    let sequence = state.sequence;
+                    state
+                        .changed_paths
+                        .insert(relative_path.to_owned(), sequence);
+                } else if !jj_dir.exists() {
+                    // The workspace has been deleted.
+                    process::exit(0);
+                } else if path.parent() == Some(&jj_dir)
+                    && path
+                        .file_name()
+                        .is_some_and(|name| name.as_bytes().starts_with(COOKIE_PREFIX.as_bytes()))
+                    && path.exists()
+                {
+                    state.seen_cookies.insert(path);
+                    cookie_seen.notify_all();
+                }
+            }
+        }
+    }
+
+    fn answer_query(
+        root: &Path,
+        shared: &(Mutex<State>, Condvar),
+        stream: &UnixStream,
+        query_number: usize,
+    ) -> io::Result<()> {
+        let mut previous_clock = String::new();
+        if BufReader::new(stream).read_line(&mut previous_clock)? == 0 {
+            // Just checking whether the daemon is running
+            return Ok(());
+        }
+        let previous_clock = Clock(previous_clock.trim_end().to_owned());
+
+        // Events are delivered asynchronously. Once the event for a file
+        // created now has been seen, all earlier changes are known as well.
+        let cookie_path = root
+            .join(".jj")
+            .join(format!("{COOKIE_PREFIX}{}-{query_number}", process::id()));
+        fs::write(&cookie_path, b"")?;
+        let (state, cookie_seen) = shared;
+        let (mut state, wait_result) = cookie_seen
+            .wait_timeout_while(state.lock().unwrap(), SYNC_TIMEOUT, |state| {
+                !state.seen_cookies.contains(&cookie_path)
+            })
+            .unwrap();
+        state.seen_cookies.remove(&cookie_path);
+        if wait_result.timed_out() {
+            state.reset();
+        }
+        let clock = state.clock();
+        let changed_paths = state.changed_since(&previous_clock);
+        let mut writer = BufWriter::new(stream);
+        write_response(&mut writer, &clock, changed_paths.as_deref())?;
+        drop(state);
+        fs::remove_file(&cookie_path)?;
+        writer.flush()
+    }
+
+    fn write_response(
+        writer: &mut impl Write,
+        Clock(clock): &Clock,
+        changed_paths: Option<&[&Path]>,
+    ) -> io::Result<()> {
+        writeln!(writer, "{clock}")?;
+        let Some(changed_paths) = changed_paths else {
+            return writeln!(writer, "fresh");
+        };
+        writeln!(writer, "changed")?;
+        for path in changed_paths {
+            writer.write_all(path.as_os_str().as_bytes())?;
+            writer.write_all(b"\0")?;
+        }
+        Ok(())
+    }
+
+    fn parse_response(response: &[u8]) -> Option<(Clock, Option<Vec<PathBuf>>)> {
+        let (clock, rest) = split_line(response)?;
+        let clock = Clock(std::str::from_utf8(clock).ok()?.to_owned());
+        let (kind, paths) = split_line(rest)?;
+        let changed_paths = match kind {
+            b"fresh" => None,
+            b"changed" => Some(
+                paths
+                    .split(|&b| b == b'\0')
+                    .filter(|path| !path.is_empty())
+                    .map(|path| PathBuf::from(OsStr::from_bytes(path)))
+                    .collect(),
+            ),
+            _ => return None,
+        };
+        Some((clock, changed_paths))
+    }
+
+    fn split_line(data: &[u8]) -> Option<(&[u8], &[u8])> {
+        let pos = data.iter().position(|&b| b == b'\n')?;
+        Some((&data[..pos], &data[pos + 1..]))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_changed_since() {
+            let mut state = State::new();
+            let clock0 = state.clock();
+            for path in ["a", "b"] {
+                state.sequence += 1;
+                state.changed_paths.insert(path.into(), state.sequence);
+            }
+            let clock1 = state.clock();
+            state.sequence += 1;
+            state.changed_paths.insert("a".into(), state.sequence);
+
+            let mut changed = state.changed_since(&clock0).unwrap();
+            changed.sort();
+            assert_eq!(changed, [Path::new("a"), Path::new("b")]);
+            assert_eq!(state.changed_since(&clock1).unwrap(), [Path::new("a")]);
+            assert_eq!(
+                state.changed_since(&state.clock()).unwrap(),
+                [] as [&Path; 0]
+            );
+            assert_eq!(state.changed_since(&Clock::default()), None);
+
+            state.reset();
+            assert_eq!(state.changed_since(&clock1), None);
+        }
+
+        #[test]
+        fn test_response_round_trip() {
+            let clock = Clock("builtin:1-2:3".to_owned());
+            let mut response = vec![];
+            write_response(&mut response, &clock, None).unwrap();
+            assert_eq!(parse_response(&response), Some((clock.clone(), None)));
+
+            let paths = [Path::new("dir/file"), Path::new("with\nnewline")];
+            let mut response = vec![];
+            write_response(&mut response, &clock, Some(&paths[..])).unwrap();
+            assert_eq!(
+                parse_response(&response),
+                Some((clock, Some(paths.map(Path::to_owned).to_vec())))
+            );
+
+            assert_eq!(parse_response(b"builtin:1-2:3\nunknown\n"), None);
+        }
+    }
+}
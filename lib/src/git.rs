@@ -19,14 +19,20 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::default::Default;
+use std::fs;
 use std::fs::File;
+use std::io;
+use std::iter;
 use std::num::NonZeroU32;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use bstr::BStr;
 use bstr::BString;
 use futures::StreamExt as _;
+use gix::date::parse::TimeBuf;
+use gix::objs::WriteTo as _;
 use gix::refspec::Instruction;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
@@ -35,12 +41,14 @@ use thiserror::Error;
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::CommitId;
+use crate::backend::Signature;
 use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::config::ConfigGetError;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
 use crate::git_backend::GitBackend;
+use crate::git_backend::signature_to_git;
 use crate::git_subprocess::GitSubprocessContext;
 use crate::git_subprocess::GitSubprocessError;
 use crate::index::IndexError;
@@ -64,12 +72,15 @@ use crate::refs::BookmarkPushUpdate;
 use crate::repo::MutableRepo;
 use crate::repo::Repo;
 use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
 use crate::revset::RevsetExpression;
 use crate::settings::RemoteSettings;
 use crate::settings::UserSettings;
+use crate::signing::SignError;
 use crate::store::Store;
 use crate::str_util::StringPattern;
 use crate::view::View;
+use crate::working_copy::SparseMode;
 
 /// Reserved remote name for the backing Git repo.
 pub const REMOTE_NAME_FOR_LOCAL_GIT_REPO: &RemoteName = RemoteName::new("git");
@@ -87,7 +98,9 @@ pub struct GitSettings {
     pub auto_local_bookmark: bool,
     pub abandon_unreachable_commits: bool,
     pub executable_path: PathBuf,
+    pub credential_helpers: Vec<String>,
     pub write_change_id_header: bool,
+    pub write_commit_graph: bool,
     pub remotes: HashMap<RemoteNameBuf, RemoteSettings>,
 }
 
@@ -97,7 +110,9 @@ impl GitSettings {
             auto_local_bookmark: settings.get_bool("git.auto-local-bookmark")?,
             abandon_unreachable_commits: settings.get_bool("git.abandon-unreachable-commits")?,
             executable_path: settings.get("git.executable-path")?,
+            credential_helpers: settings.get("git.credential-helpers")?,
             write_change_id_header: settings.get("git.write-change-id-header")?,
+            write_commit_graph: settings.get_bool("git.write-commit-graph")?,
             remotes: RemoteSettings::table_from_settings(settings)?,
         })
     }
@@ -1981,6 +1996,42 @@ pub fn add_remote(
     Ok(())
 }
 
+/// Configures `remote_name` as the promisor remote of a partial clone.
+/// Subsequent fetches from the remote omit the objects excluded by `filter`
+/// (e.g. `blob:none`), which are fetched on demand instead.
+///
+/// Note that the configuration changes are not visible to the `store` until
+/// the repository is reloaded.
+pub fn set_partial_clone_filter(
+    store: &Store,
+    remote_name: &RemoteName,
+    filter: &str,
+) -> Result<(), GitRemoteManagementError> {
+    let git_repo = get_git_repo(store)?;
+    if git_repo.try_find_remote(remote_name.as_str()).is_none() {
+        return Err(GitRemoteManagementError::NoSuchRemote(
+            remote_name.to_owned(),
+        ));
+    }
+
+    let mut config = git_repo.config_snapshot().clone();
+    let meta = config.meta().clone();
+    let remote_section = Some(BStr::new(remote_name.as_str()));
+    for (section, subsection, key, value) in [
+        // Extensions are only recognized by repository format version 1.
+        ("core", None, "repositoryformatversion", "1"),
+        ("extensions", None, "partialClone", remote_name.as_str()),
+        ("remote", remote_section, "promisor", "true"),
+        ("remote", remote_section, "partialclonefilter", filter),
+    ] {
+        config
+            .set_raw_value_filter_by(section, subsection, key, value, |m| *m == meta)
+            .map_err(GitRemoteManagementError::from_git)?;
+    }
+    save_git_config(&config).map_err(GitRemoteManagementError::GitConfigSaveError)?;
+    Ok(())
+}
+
 pub fn remove_remote(
     mut_repo: &mut MutableRepo,
     remote_name: &RemoteName,
@@ -2236,6 +2287,10 @@ pub enum GitFetchError {
     RemoteName(#[from] GitRemoteNameError),
     #[error(transparent)]
     Subprocess(#[from] GitSubprocessError),
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
 }
 
 #[derive(Error, Debug)]
@@ -2244,6 +2299,12 @@ pub enum GitDefaultRefspecError {
     NoSuchRemote(RemoteNameBuf),
     #[error("Invalid configuration for remote `{}`", .0.as_symbol())]
     InvalidRemoteConfiguration(RemoteNameBuf, #[source] Box<gix::remote::find::Error>),
+    #[error("Unexpected {direction} refspec `{refspec}` for remote `{}`", remote.as_symbol())]
+    UnexpectedRefspecDirection {
+        remote: RemoteNameBuf,
+        refspec: BString,
+        direction: &'static str,
+    },
 }
 
 struct FetchedBranches {
@@ -2338,6 +2399,7 @@ pub fn expand_default_fetch_refspecs(
     let mut ignored_refspecs = Vec::with_capacity(remote_refspecs.len());
     let mut expected_branch_names = Vec::with_capacity(remote_refspecs.len());
     let mut negative_refspecs = Vec::new();
+    let mut push_refspec = None;
 
     let refspecs = remote_refspecs
         .iter()
@@ -2357,8 +2419,10 @@ pub fn expand_default_fetch_refspecs(
             };
 
             let (src, dst) = match refspec.instruction() {
-                // Already filtered out above
-                Instruction::Push(_) => unreachable!(),
+                Instruction::Push(_) => {
+                    push_refspec.get_or_insert_with(|| refspec.to_bstring());
+                    return None;
+                }
                 Instruction::Fetch(fetch) => match fetch {
                     gix::refspec::instruction::Fetch::Only { src: _ } => {
                         ignored_refspecs.push(IgnoredRefspec {
@@ -2442,6 +2506,13 @@ pub fn expand_default_fetch_refspecs(
             Some(RefSpec::forced(src, dst))
         })
         .collect();
+    if let Some(refspec) = push_refspec {
+        return Err(GitDefaultRefspecError::UnexpectedRefspecDirection {
+            remote: remote_name.into(),
+            refspec,
+            direction: "push",
+        });
+    }
 
     Ok((
         IgnoredRefspecs(ignored_refspecs),
@@ -2453,6 +2524,121 @@ pub fn expand_default_fetch_refspecs(
     ))
 }
 
+/// Represents the bookmarks which may be pushed to a remote
+#[derive(Debug)]
+pub struct ExpandedPushRefSpecs {
+    // None if the remote doesn't restrict the bookmarks to push.
+    allowed_bookmarks: Option<Vec<StringPattern>>,
+    excluded_bookmarks: Vec<RefNameBuf>,
+}
+
+impl ExpandedPushRefSpecs {
+    /// Returns true if the bookmark `name` may be pushed to the remote.
+    pub fn is_allowed(&self, name: &RefName) -> bool {
+        let included = self.allowed_bookmarks.as_ref().is_none_or(|patterns| {
+            patterns
+                .iter()
+                .any(|pattern| pattern.is_match(name.as_str()))
+        });
+        included
+            && !self
+                .excluded_bookmarks
+                .iter()
+                .any(|excluded| excluded == name)
+    }
+}
+
+/// Expand the remote's configured push refspecs (`remote.<name>.push`)
+///
+/// Since bookmarks are pushed to the same name on the remote, the refspecs only
+/// restrict which bookmarks may be pushed. Negative refspecs exclude
+/// bookmarks. All bookmarks may be pushed if no positive refspecs are
+/// configured.
+pub fn expand_default_push_refspecs(
+    remote: &RemoteName,
+    git_repo: &gix::Repository,
+) -> Result<(IgnoredRefspecs, ExpandedPushRefSpecs), GitDefaultRefspecError> {
+    let remote_name = remote.as_str();
+    let remote = git_repo
+        .try_find_remote(remote_name)
+        .ok_or_else(|| GitDefaultRefspecError::NoSuchRemote(remote.to_owned()))?
+        .map_err(|e| {
+            GitDefaultRefspecError::InvalidRemoteConfiguration(remote.to_owned(), Box::new(e))
+        })?;
+
+    let remote_refspecs = remote.refspecs(gix::remote::Direction::Push);
+
+    let mut ignored_refspecs = Vec::new();
+    let mut allowed_bookmarks = Vec::with_capacity(remote_refspecs.len());
+    let mut excluded_bookmarks = Vec::new();
+
+    for refspec in remote_refspecs {
+        let refspec = refspec.to_ref();
+        let mut ignore = |reason| {
+            ignored_refspecs.push(IgnoredRefspec {
+                refspec: refspec.to_bstring(),
+                reason,
+            });
+        };
+        let (src, dst) = match refspec.instruction() {
+            Instruction::Fetch(_) => {
+                return Err(GitDefaultRefspecError::UnexpectedRefspecDirection {
+                    remote: remote_name.into(),
+                    refspec: refspec.to_bstring(),
+                    direction: "fetch",
+                });
+            }
+            Instruction::Push(push) => match push {
+                gix::refspec::instruction::Push::AllMatchingBranches { .. } => {
+                    ignore("matching refspecs are not supported");
+                    continue;
+                }
+                gix::refspec::instruction::Push::Delete { .. } => {
+                    ignore("delete refspecs are not supported");
+                    continue;
+                }
+                gix::refspec::instruction::Push::Matching { src, dst, .. } => (src, dst),
+                gix::refspec::instruction::Push::Exclude { src } => {
+                    match str::from_utf8(src)
+                        .ok()
+                        .and_then(|src| src.strip_prefix("refs/heads/"))
+                    {
+                        Some(name) => excluded_bookmarks.push(name.into()),
+                        None => ignore("only refs/heads/ is supported for negative refspecs"),
+                    }
+                    continue;
+                }
+            },
+        };
+
+        let (Ok(src), Ok(dst)) = (str::from_utf8(src), str::from_utf8(dst)) else {
+            ignore("invalid UTF-8");
+            continue;
+        };
+        let Some(src_branch) = src.strip_prefix("refs/heads/") else {
+            ignore("only refs/heads/ is supported for refspec sources");
+            continue;
+        };
+        if src != dst {
+            ignore("renaming is not supported");
+            continue;
+        }
+        let Ok(branch) = StringPattern::glob(src_branch) else {
+            ignore("invalid pattern");
+            continue;
+        };
+        allowed_bookmarks.push(branch);
+    }
+
+    Ok((
+        IgnoredRefspecs(ignored_refspecs),
+        ExpandedPushRefSpecs {
+            allowed_bookmarks: (!allowed_bookmarks.is_empty()).then_some(allowed_bookmarks),
+            excluded_bookmarks,
+        },
+    ))
+}
+
 /// Helper struct to execute multiple `git fetch` operations
 pub struct GitFetch<'a> {
     mut_repo: &'a mut MutableRepo,
@@ -2469,8 +2655,7 @@ impl<'a> GitFetch<'a> {
     ) -> Result<Self, UnexpectedGitBackendError> {
         let git_backend = get_git_backend(mut_repo.store())?;
         let git_repo = Box::new(git_backend.git_repo());
-        let git_ctx =
-            GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+        let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
         Ok(GitFetch {
             mut_repo,
             git_repo,
@@ -2495,7 +2680,7 @@ impl<'a> GitFetch<'a> {
             negative_refspecs,
         }: ExpandedFetchRefSpecs,
         mut callbacks: RemoteCallbacks,
-        depth: Option<NonZeroU32>,
+        depth: &FetchDepth,
         fetch_tags_override: Option<FetchTagsOverride>,
     ) -> Result<(), GitFetchError> {
         validate_remote_name(remote_name)?;
@@ -2632,6 +2817,14 @@ pub struct GitRefUpdate {
     pub new_target: Option<CommitId>,
 }
 
+/// Extra options for [`push_updates()`].
+#[derive(Clone, Debug, Default)]
+pub struct GitPushOptions {
+    /// Strings transmitted to the remote with `git push --push-option`, which
+    /// are interpreted by the server (e.g. `topic=foo` for Gerrit).
+    pub remote_push_options: Vec<String>,
+}
+
 /// Pushes the specified branches and updates the repo view accordingly.
 pub fn push_branches(
     mut_repo: &mut MutableRepo,
@@ -2652,7 +2845,14 @@ pub fn push_branches(
         })
         .collect_vec();
 
-    let push_stats = push_updates(mut_repo, git_settings, remote, &ref_updates, callbacks)?;
+    let push_stats = push_updates(
+        mut_repo,
+        git_settings,
+        remote,
+        &ref_updates,
+        callbacks,
+        &GitPushOptions::default(),
+    )?;
     tracing::debug!(?push_stats);
 
     // TODO: add support for partially pushed refs? we could update the view
@@ -2685,6 +2885,7 @@ pub fn push_updates(
     remote_name: &RemoteName,
     updates: &[GitRefUpdate],
     mut callbacks: RemoteCallbacks,
+    options: &GitPushOptions,
 ) -> Result<GitPushStats, GitPushError> {
     let mut qualified_remote_refs_expected_locations = HashMap::new();
     let mut refspecs = vec![];
@@ -2705,11 +2906,76 @@ pub fn push_updates(
             refspecs.push(RefSpec::delete(&update.qualified_name));
         }
     }
+    push_refspecs(
+        repo,
+        git_settings,
+        remote_name,
+        &refspecs,
+        &qualified_remote_refs_expected_locations,
+        &mut callbacks,
+        options,
+    )
+}
+
+/// Pushes the specified tags, which must not exist on the remote yet, without
+/// updating the repo view.
+///
+/// If the Git ref of a tag already resolves to the tag's target, the ref is
+/// pushed as is, so annotated tag objects are preserved. Otherwise, a
+/// lightweight tag pointing to the target commit is created on the remote.
+pub fn push_tags(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    tags: &[(RefNameBuf, CommitId)],
+    mut callbacks: RemoteCallbacks,
+) -> Result<GitPushStats, GitPushError> {
+    validate_remote_name(remote_name)?;
+    let git_repo = get_git_repo(repo.store())?;
+    let qualified_names: Vec<GitRefNameBuf> = tags
+        .iter()
+        .map(|(name, _)| format!("refs/tags/{name}", name = name.as_str()).into())
+        .collect();
+    let mut expected_locations = HashMap::new();
+    let mut refspecs = vec![];
+    for ((_, target), qualified_name) in iter::zip(tags, &qualified_names) {
+        expected_locations.insert(qualified_name.as_ref(), None);
+        let is_git_ref_at_target = git_repo
+            .try_find_reference(qualified_name.as_str())
+            .ok()
+            .flatten()
+            .and_then(|mut git_ref| git_ref.peel_to_id().ok())
+            .is_some_and(|id| CommitId::from_bytes(id.as_bytes()) == *target);
+        let source = if is_git_ref_at_target {
+            qualified_name.as_str().to_owned()
+        } else {
+            target.hex()
+        };
+        refspecs.push(RefSpec::forced(source, qualified_name.as_str()));
+    }
+    push_refspecs(
+        repo,
+        git_settings,
+        remote_name,
+        &refspecs,
+        &expected_locations,
+        &mut callbacks,
+        &GitPushOptions::default(),
+    )
+}
 
+fn push_refspecs(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    refspecs: &[RefSpec],
+    expected_locations: &HashMap<&GitRefName, Option<&CommitId>>,
+    callbacks: &mut RemoteCallbacks<'_>,
+    options: &GitPushOptions,
+) -> Result<GitPushStats, GitPushError> {
     let git_backend = get_git_backend(repo.store())?;
     let git_repo = git_backend.git_repo();
-    let git_ctx =
-        GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+    let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
 
     // check the remote exists
     if git_repo.try_find_remote(remote_name.as_str()).is_none() {
@@ -2718,10 +2984,15 @@ pub fn push_updates(
 
     let refs_to_push: Vec<RefToPush> = refspecs
         .iter()
-        .map(|full_refspec| RefToPush::new(full_refspec, &qualified_remote_refs_expected_locations))
+        .map(|full_refspec| RefToPush::new(full_refspec, expected_locations))
         .collect();
 
-    let mut push_stats = git_ctx.spawn_push(remote_name, &refs_to_push, &mut callbacks)?;
+    let mut push_stats = git_ctx.spawn_push(
+        remote_name,
+        &refs_to_push,
+        &options.remote_push_options,
+        callbacks,
+    )?;
     push_stats.pushed.sort();
     push_stats.rejected.sort();
     push_stats.remote_rejected.sort();
@@ -2746,6 +3017,148 @@ pub struct Progress {
     pub overall: f32,
 }
 
+/// Queries the current positions of the branches on a remote without fetching
+/// them.
+pub fn list_remote_branches(
+    store: &Store,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+) -> Result<Vec<(RefNameBuf, CommitId)>, GitPushError> {
+    let git_backend = get_git_backend(store)?;
+    if git_backend
+        .git_repo()
+        .try_find_remote(remote_name.as_str())
+        .is_none()
+    {
+        return Err(GitPushError::NoSuchRemote(remote_name.to_owned()));
+    }
+    let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
+    Ok(git_ctx.spawn_ls_remote_branches(remote_name)?)
+}
+
+/// Queries the refs matching the patterns on a remote without fetching them.
+pub fn list_remote_refs(
+    store: &Store,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    patterns: &[&str],
+) -> Result<Vec<(GitRefNameBuf, CommitId)>, GitFetchError> {
+    let git_backend = get_git_backend(store)?;
+    if git_backend
+        .git_repo()
+        .try_find_remote(remote_name.as_str())
+        .is_none()
+    {
+        return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+    }
+    let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
+    Ok(git_ctx.spawn_ls_remote(remote_name, patterns)?)
+}
+
+/// Fetches the commits that the refs on a remote point to, and adds them to
+/// the repo as visible heads. No local refs are created or updated.
+///
+/// The `refs` are pairs of full ref names on the remote and the commits they
+/// are expected to point to, as returned by [`list_remote_refs()`].
+pub fn fetch_commits(
+    mut_repo: &mut MutableRepo,
+    git_settings: &GitSettings,
+    remote_name: &RemoteName,
+    refs: &[(GitRefNameBuf, CommitId)],
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<Vec<Commit>, GitFetchError> {
+    validate_remote_name(remote_name)?;
+    let git_backend = get_git_backend(mut_repo.store())?;
+    if git_backend
+        .git_repo()
+        .try_find_remote(remote_name.as_str())
+        .is_none()
+    {
+        return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+    }
+    let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
+    let ref_names = refs.iter().map(|(name, _)| name.as_str()).collect_vec();
+    git_ctx.spawn_fetch_objects(remote_name, &ref_names, &mut callbacks)?;
+
+    git_backend.import_head_commits(refs.iter().map(|(_, id)| id))?;
+    let commits: Vec<Commit> = refs
+        .iter()
+        .map(|(_, id)| mut_repo.store().get_commit(id))
+        .try_collect()?;
+    for commit in &commits {
+        mut_repo.add_head(commit)?;
+    }
+    Ok(commits)
+}
+
+#[derive(Error, Debug)]
+pub enum GitBundleError {
+    #[error("Bookmark {} is not exported to the underlying Git repo", .0.as_symbol())]
+    RefNotExported(RefNameBuf),
+    #[error(transparent)]
+    Subprocess(#[from] GitSubprocessError),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+/// Writes a bundle file to `path` containing the history of the `bookmarks`.
+///
+/// Commits reachable from the `prerequisites` are omitted. A repository
+/// fetching from the bundle must already contain them.
+pub fn create_bundle(
+    repo: &dyn Repo,
+    git_settings: &GitSettings,
+    path: &Path,
+    bookmarks: &[(RefNameBuf, CommitId)],
+    prerequisites: &[CommitId],
+) -> Result<(), GitBundleError> {
+    let git_backend = get_git_backend(repo.store())?;
+    let git_repo = git_backend.git_repo();
+    let mut git_refs = Vec::with_capacity(bookmarks.len());
+    for (name, commit_id) in bookmarks {
+        let git_ref_name = format!("refs/heads/{}", name.as_str());
+        // The bundle records the refs in the Git repo, which must point to the
+        // same commits as the bookmarks.
+        let exported = git_repo
+            .try_find_reference(&git_ref_name)
+            .ok()
+            .flatten()
+            .and_then(|mut git_ref| git_ref.peel_to_id().ok())
+            .is_some_and(|oid| oid.as_bytes() == commit_id.as_bytes());
+        if !exported {
+            return Err(GitBundleError::RefNotExported(name.clone()));
+        }
+        git_refs.push(git_ref_name);
+    }
+    let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
+    git_ctx.spawn_bundle_create(path, &git_refs, prerequisites)?;
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum GitCommitGraphError {
+    #[error(transparent)]
+    Subprocess(#[from] GitSubprocessError),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+/// Updates the commit-graph files of the underlying Git repo.
+///
+/// Git and gitoxide use the commit-graph to look up parents and generation
+/// numbers without parsing the commit objects, and fall back to the objects
+/// where the graph is absent or out of date. The revsets evaluated by jj use
+/// its own index, which records generation numbers already.
+pub fn write_commit_graph(
+    store: &Store,
+    git_settings: &GitSettings,
+) -> Result<(), GitCommitGraphError> {
+    let git_backend = get_git_backend(store)?;
+    let git_ctx = GitSubprocessContext::from_git_backend(git_backend, git_settings);
+    git_ctx.spawn_commit_graph_write()?;
+    Ok(())
+}
+
 /// Allows temporarily overriding the behavior of a single `git fetch`
 /// operation as to whether tags are fetched
 #[derive(Copy, Clone, Debug)]
@@ -2757,3 +3170,460 @@ pub enum FetchTagsOverride {
     /// remote's `tagOpt` is configured to
     NoTags,
 }
+
+/// Limits the history fetched by a single `git fetch` operation
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum FetchDepth {
+    /// Fetch all history reachable from the fetched refs. The boundary of a
+    /// shallow repository is kept as is.
+    #[default]
+    Full,
+    /// Fetch at most the given number of commits from the tip of each ref
+    Depth(NonZeroU32),
+    /// Fetch commits newer than the given date
+    ShallowSince(String),
+    /// Extend the history of a shallow repository by the given number of
+    /// commits
+    Deepen(NonZeroU32),
+}
+
+/// Default ref that Git stores commit notes in.
+pub const DEFAULT_NOTES_REF: &str = "refs/notes/commits";
+
+#[derive(Error, Debug)]
+pub enum GitNotesError {
+    #[error("Invalid notes ref {0}")]
+    InvalidRef(String),
+    #[error("Failed to read notes from {notes_ref}")]
+    Read {
+        notes_ref: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Failed to write notes to {notes_ref}")]
+    Write {
+        notes_ref: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+/// Reads all notes in `notes_ref`, keyed by the annotated commit.
+///
+/// Notes of objects other than commits are included too, since they can't be
+/// told apart by their IDs. Returns an empty map if the ref doesn't exist.
+pub fn read_notes(
+    store: &Store,
+    notes_ref: &str,
+) -> Result<HashMap<CommitId, String>, GitNotesError> {
+    let git_repo = get_git_repo(store)?;
+    let blob_ids = read_note_blob_ids(&git_repo, notes_ref)?;
+    let read_err = |err: Box<dyn std::error::Error + Send + Sync>| GitNotesError::Read {
+        notes_ref: notes_ref.to_owned(),
+        source: err,
+    };
+    blob_ids
+        .into_iter()
+        .map(|(commit_id, blob_id)| {
+            let blob = git_repo
+                .find_blob(blob_id)
+                .map_err(|err| read_err(err.into()))?;
+            let note = String::from_utf8_lossy(&blob.data).into_owned();
+            Ok((commit_id, note))
+        })
+        .try_collect()
+}
+
+/// Sets the note of `commit_id` in `notes_ref`, or removes it if `note` is
+/// `None`, by committing a new notes tree like `git notes` does.
+///
+/// Notes are written without fan-out directories, which Git reads fine and
+/// reorganizes when it rewrites the notes tree.
+pub fn write_note(
+    store: &Store,
+    notes_ref: &str,
+    commit_id: &CommitId,
+    note: Option<&str>,
+    signature: &Signature,
+) -> Result<(), GitNotesError> {
+    let git_repo = get_git_repo(store)?;
+    let write_err = |err: Box<dyn std::error::Error + Send + Sync>| GitNotesError::Write {
+        notes_ref: notes_ref.to_owned(),
+        source: err,
+    };
+    let parent_id = find_notes_commit_id(&git_repo, notes_ref)?;
+    let mut blob_ids = read_note_blob_ids(&git_repo, notes_ref)?;
+    match note {
+        Some(note) => {
+            let mut content = note.to_owned();
+            if !content.ends_with('\n') {
+                content.push('\n');
+            }
+            let blob_id = git_repo
+                .write_blob(content.as_bytes())
+                .map_err(|err| write_err(err.into()))?;
+            blob_ids.insert(commit_id.clone(), blob_id.detach());
+        }
+        None => {
+            if blob_ids.remove(commit_id).is_none() {
+                return Ok(());
+            }
+        }
+    }
+    let entries = blob_ids
+        .iter()
+        .map(|(commit_id, blob_id)| gix::objs::tree::Entry {
+            mode: gix::object::tree::EntryKind::Blob.into(),
+            filename: commit_id.hex().into(),
+            oid: *blob_id,
+        })
+        .sorted_unstable()
+        .collect();
+    let tree_id = git_repo
+        .write_object(gix::objs::Tree { entries })
+        .map_err(|err| write_err(err.into()))?;
+    let message = match note {
+        Some(_) => "Notes added by 'jj note'",
+        None => "Notes removed by 'jj note'",
+    };
+    let git_signature = signature_to_git(signature);
+    git_repo
+        .commit_as(
+            git_signature.to_ref(&mut TimeBuf::default()),
+            git_signature.to_ref(&mut TimeBuf::default()),
+            notes_ref,
+            message,
+            tree_id,
+            parent_id,
+        )
+        .map_err(|err| write_err(err.into()))?;
+    Ok(())
+}
+
+fn find_notes_commit_id(
+    git_repo: &gix::Repository,
+    notes_ref: &str,
+) -> Result<Option<gix::ObjectId>, GitNotesError> {
+    if gix::refs::FullName::try_from(notes_ref).is_err() || !notes_ref.starts_with("refs/notes/") {
+        return Err(GitNotesError::InvalidRef(notes_ref.to_owned()));
+    }
+    let reference = git_repo
+        .try_find_reference(notes_ref)
+        .map_err(|err| GitNotesError::Read {
+            notes_ref: notes_ref.to_owned(),
+            source: err.into(),
+        })?;
+    let Some(mut reference) = reference else {
+        return Ok(None);
+    };
+    let id = reference.peel_to_id().map_err(|err| GitNotesError::Read {
+        notes_ref: notes_ref.to_owned(),
+        source: err.into(),
+    })?;
+    Ok(Some(id.detach()))
+}
+
+/// Returns the blob IDs of the notes in `notes_ref`, flattening fan-out
+/// directories such as `ab/cdef...`.
+fn read_note_blob_ids(
+    git_repo: &gix::Repository,
+    notes_ref: &str,
+) -> Result<HashMap<CommitId, gix::ObjectId>, GitNotesError> {
+    let read_err = |err: Box<dyn std::error::Error + Send + Sync>| GitNotesError::Read {
+        notes_ref: notes_ref.to_owned(),
+        source: err,
+    };
+    let Some(commit_id) = find_notes_commit_id(git_repo, notes_ref)? else {
+        return Ok(HashMap::new());
+    };
+    let tree = git_repo
+        .find_commit(commit_id)
+        .map_err(|err| read_err(err.into()))?
+        .tree()
+        .map_err(|err| read_err(err.into()))?;
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    tree.traverse()
+        .breadthfirst(&mut recorder)
+        .map_err(|err| read_err(err.into()))?;
+    let blob_ids = recorder
+        .records
+        .into_iter()
+        .filter(|entry| entry.mode.is_blob())
+        .filter_map(|entry| {
+            let hex: BString = entry
+                .filepath
+                .iter()
+                .filter(|&&b| b != b'/')
+                .copied()
+                .collect();
+            let commit_id = CommitId::try_from_hex(hex)?;
+            Some((commit_id, entry.oid))
+        })
+        .collect();
+    Ok(blob_ids)
+}
+
+#[derive(Error, Debug)]
+pub enum GitTagError {
+    #[error("Failed to sign tag {name}")]
+    Sign {
+        name: String,
+        #[source]
+        source: SignError,
+    },
+    #[error("Failed to write tag {name}")]
+    Write {
+        name: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+/// Creates an annotated tag object pointing to `target`, and points both the
+/// local tag `name` and its Git ref to it. Existing tags are overwritten.
+///
+/// If `sign` is true, the tag object is signed with the store's signer like
+/// `git tag -s` does. The caller must check that the signer can sign.
+pub fn write_annotated_tag(
+    mut_repo: &mut MutableRepo,
+    name: &RefName,
+    target: &CommitId,
+    message: &str,
+    tagger: &Signature,
+    sign: bool,
+    sign_key: Option<&str>,
+) -> Result<(), GitTagError> {
+    let git_repo = get_git_repo(mut_repo.store())?;
+    let write_err = |err: Box<dyn std::error::Error + Send + Sync>| GitTagError::Write {
+        name: name.as_str().to_owned(),
+        source: err,
+    };
+    let mut message = message.to_owned();
+    if !message.ends_with('\n') {
+        message.push('\n');
+    }
+    let mut tag = gix::objs::Tag {
+        target: gix::ObjectId::from_bytes_or_panic(target.as_bytes()),
+        target_kind: gix::object::Kind::Commit,
+        name: name.as_str().into(),
+        tagger: Some(signature_to_git(tagger)),
+        message: message.into(),
+        pgp_signature: None,
+    };
+    if sign {
+        let mut data = vec![];
+        tag.write_to(&mut data)
+            .map_err(|err| write_err(err.into()))?;
+        let signature = mut_repo
+            .store()
+            .signer()
+            .sign(&data, sign_key)
+            .map_err(|source| GitTagError::Sign {
+                name: name.as_str().to_owned(),
+                source,
+            })?;
+        // Unlike commits, tags carry the signature at the end of the message.
+        tag.message.extend_from_slice(&signature);
+    }
+    let tag_id = git_repo
+        .write_object(tag)
+        .map_err(|err| write_err(err.into()))?;
+    let git_ref_name: GitRefNameBuf = format!("refs/tags/{name}", name = name.as_str()).into();
+    git_repo
+        .reference(
+            git_ref_name.as_str(),
+            tag_id.detach(),
+            gix::refs::transaction::PreviousValue::Any,
+            "jj: create annotated tag",
+        )
+        .map_err(|err| write_err(err.into()))?;
+    mut_repo.set_local_tag_target(name, RefTarget::normal(target.clone()));
+    mut_repo.set_git_ref_target(&git_ref_name, RefTarget::normal(target.clone()));
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum GitSparseCheckoutError {
+    #[error(transparent)]
+    Io(#[from] PathError),
+    #[error("Unsupported sparse-checkout pattern: {0}")]
+    UnsupportedPattern(String),
+    #[error("Failed to update Git configuration")]
+    Config(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+fn sparse_checkout_path(git_repo: &gix::Repository) -> PathBuf {
+    git_repo.path().join("info").join("sparse-checkout")
+}
+
+/// Reads the patterns from the Git repository's `info/sparse-checkout` file,
+/// or returns `None` if there's no such file.
+///
+/// Patterns in Git's cone mode are read as [`SparseMode::Cone`] directories.
+/// Otherwise, only patterns selecting a path and everything under it (such as
+/// `/foo/bar`) are supported.
+pub fn read_sparse_checkout(
+    store: &Store,
+) -> Result<Option<(Vec<RepoPathBuf>, SparseMode)>, GitSparseCheckoutError> {
+    let git_repo = get_git_repo(store)?;
+    let path = sparse_checkout_path(&git_repo);
+    let content = match fs::read_to_string(&path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        result => result.context(&path)?,
+    };
+    let lines = content
+        .lines()
+        .map(|line| line.trim_end())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect_vec();
+    if let Some(dirs) = parse_cone_sparse_checkout(&lines)? {
+        return Ok(Some((dirs, SparseMode::Cone)));
+    }
+    let prefixes = lines
+        .iter()
+        .map(|&line| {
+            let unsupported = || GitSparseCheckoutError::UnsupportedPattern(line.to_owned());
+            if line == "/*" {
+                return Ok(RepoPathBuf::root());
+            }
+            let path = line.strip_prefix('/').ok_or_else(unsupported)?;
+            let path = path.strip_suffix('/').unwrap_or(path);
+            let path = unescape_sparse_checkout_path(path).ok_or_else(unsupported)?;
+            RepoPathBuf::from_internal_string(path).map_err(|_| unsupported())
+        })
+        .try_collect()?;
+    Ok(Some((prefixes, SparseMode::Prefix)))
+}
+
+/// Parses sparse-checkout `lines` written in Git's cone mode. Returns `None`
+/// if they aren't in cone mode.
+fn parse_cone_sparse_checkout(
+    lines: &[&str],
+) -> Result<Option<Vec<RepoPathBuf>>, GitSparseCheckoutError> {
+    let Some((&"/*", lines)) = lines.split_first() else {
+        return Ok(None);
+    };
+    let mut dirs = vec![];
+    let mut parent_dirs = HashSet::new();
+    for &line in lines {
+        if line == "!/*/" {
+            parent_dirs.insert("");
+        } else if let Some(dir) = line.strip_prefix("!/").and_then(|s| s.strip_suffix("/*/")) {
+            parent_dirs.insert(dir);
+        } else if let Some(dir) = line.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            dirs.push((line, dir));
+        } else {
+            return Ok(None);
+        }
+    }
+    if !parent_dirs.contains("") {
+        return Ok(Some(vec![RepoPathBuf::root()]));
+    }
+    dirs.into_iter()
+        .filter(|(_, dir)| !parent_dirs.contains(dir))
+        .map(|(line, dir)| {
+            unescape_sparse_checkout_path(dir)
+                .and_then(|dir| RepoPathBuf::from_internal_string(dir).ok())
+                .ok_or_else(|| GitSparseCheckoutError::UnsupportedPattern(line.to_owned()))
+        })
+        .try_collect()
+        .map(Some)
+}
+
+/// Removes backslash escapes from a sparse-checkout `path`. Returns `None` if
+/// it contains unescaped wildcards.
+fn unescape_sparse_checkout_path(path: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.push(chars.next()?),
+            '*' | '?' | '[' => return None,
+            _ => unescaped.push(c),
+        }
+    }
+    Some(unescaped)
+}
+
+/// Writes `patterns` to the Git repository's `info/sparse-checkout` file, and
+/// enables sparse checkouts in the Git configuration.
+///
+/// Git doesn't update its working tree by itself. Run `git sparse-checkout
+/// reapply` to make it aware of the files that aren't checked out.
+pub fn write_sparse_checkout(
+    store: &Store,
+    patterns: &[RepoPathBuf],
+    mode: SparseMode,
+) -> Result<(), GitSparseCheckoutError> {
+    let git_repo = get_git_repo(store)?;
+    let path = sparse_checkout_path(&git_repo);
+    let dir = path.parent().unwrap();
+    fs::create_dir_all(dir).context(dir)?;
+    fs::write(&path, format_sparse_checkout(patterns, mode)).context(&path)?;
+
+    let mut config = git_repo.config_snapshot().clone();
+    let meta = config.meta().clone();
+    let cone = match mode {
+        SparseMode::Prefix => "false",
+        SparseMode::Cone => "true",
+    };
+    for (key, value) in [("sparseCheckout", "true"), ("sparseCheckoutCone", cone)] {
+        config
+            .set_raw_value_filter_by("core", None, key, value, |m| *m == meta)
+            .map_err(|err| GitSparseCheckoutError::Config(err.into()))?;
+    }
+    save_git_config(&config).map_err(|err| GitSparseCheckoutError::Config(err.into()))?;
+    Ok(())
+}
+
+fn format_sparse_checkout(patterns: &[RepoPathBuf], mode: SparseMode) -> String {
+    let escape = |path: &RepoPath| {
+        let mut escaped = String::new();
+        for c in path.as_internal_file_string().chars() {
+            if matches!(c, '*' | '?' | '[' | '\\') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    };
+    let mut content = String::new();
+    if patterns.iter().any(|path| path.is_root()) {
+        content.push_str("/*\n");
+        return content;
+    }
+    match mode {
+        SparseMode::Prefix => {
+            for path in patterns {
+                content.push_str(&format!("/{}\n", escape(path)));
+            }
+        }
+        SparseMode::Cone => {
+            content.push_str("/*\n!/*/\n");
+            let dirs: HashSet<&RepoPath> = patterns.iter().map(AsRef::as_ref).collect();
+            let mut parent_dirs = HashSet::new();
+            for dir in patterns.iter().sorted() {
+                let mut ancestors = dir.ancestors().skip(1).collect_vec();
+                // Directories under another directory are already included.
+                if ancestors.iter().any(|ancestor| dirs.contains(ancestor)) {
+                    continue;
+                }
+                ancestors.reverse();
+                for ancestor in ancestors.into_iter().skip(1) {
+                    if parent_dirs.insert(ancestor) {
+                        let ancestor = escape(ancestor);
+                        content.push_str(&format!("/{ancestor}/\n!/{ancestor}/*/\n"));
+                    }
+                }
+                content.push_str(&format!("/{}/\n", escape(dir)));
+            }
+        }
+    }
+    content
+}
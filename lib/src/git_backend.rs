@@ -14,6 +14,7 @@
 
 #![expect(missing_docs)]
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fmt::Debug;
@@ -76,6 +77,7 @@ use crate::file_util::BadPathEncoding;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
 use crate::git::GitSettings;
+use crate::git_subprocess::GitSubprocessContext;
 use crate::index::Index;
 use crate::lock::FileLock;
 use crate::merge::Merge;
@@ -175,6 +177,9 @@ pub struct GitBackend {
     cached_extra_metadata: Mutex<Option<Arc<ReadonlyTable>>>,
     git_executable: PathBuf,
     write_change_id_header: bool,
+    /// Blobs missing from a partial clone, keyed by the directory of the trees
+    /// that were read. They are fetched together when one of them is read.
+    missing_blobs: Mutex<HashMap<RepoPathBuf, HashSet<gix::ObjectId>>>,
 }
 
 impl GitBackend {
@@ -202,6 +207,7 @@ impl GitBackend {
             cached_extra_metadata: Mutex::new(None),
             git_executable: git_settings.executable_path,
             write_change_id_header: git_settings.write_change_id_header,
+            missing_blobs: Mutex::new(HashMap::new()),
         }
     }
 
@@ -446,12 +452,97 @@ impl GitBackend {
         self.save_extra_metadata_table(mut_table, &table_lock)
     }
 
-    fn read_file_sync(&self, id: &FileId) -> BackendResult<Vec<u8>> {
+    /// Returns true if the repository is a partial clone, in which case
+    /// objects may be missing locally but available from the promisor remote.
+    pub fn is_partial_clone(&self) -> bool {
+        is_partial_clone(&self.git_repo())
+    }
+
+    /// Records the blobs referenced by the tree at `dir` which are missing
+    /// from a partial clone, so they can be fetched in one batch later.
+    fn record_missing_blobs(&self, repo: &gix::Repository, dir: &RepoPath, tree: &Tree) {
+        if !is_partial_clone(repo) {
+            return;
+        }
+        let missing = tree
+            .entries()
+            .filter_map(|entry| match entry.value() {
+                TreeValue::File { id, .. } => Some(id.as_bytes()),
+                TreeValue::Symlink(id) => Some(id.as_bytes()),
+                _ => None,
+            })
+            .map(gix::ObjectId::from_bytes_or_panic)
+            .filter(|git_id| !repo.has_object(git_id))
+            .collect_vec();
+        if !missing.is_empty() {
+            let mut missing_blobs = self.missing_blobs.lock().unwrap();
+            missing_blobs
+                .entry(dir.to_owned())
+                .or_default()
+                .extend(missing);
+        }
+    }
+
+    /// Fetches the blob `git_id` at `path` if it's missing from a partial
+    /// clone, along with the other missing blobs of the trees read at the same
+    /// directory, using a single git process.
+    fn prefetch_missing_blobs(
+        &self,
+        repo: &gix::Repository,
+        path: &RepoPath,
+        git_id: gix::ObjectId,
+        id: &impl ObjectId,
+    ) -> BackendResult<()> {
+        if repo.has_object(git_id) {
+            return Ok(());
+        }
+        let Some(remote_name) = partial_clone_remote(repo) else {
+            return Ok(());
+        };
+        let dir = path.parent().unwrap_or(RepoPath::root());
+        let mut git_ids = self
+            .missing_blobs
+            .lock()
+            .unwrap()
+            .remove(dir)
+            .unwrap_or_default();
+        git_ids.insert(git_id);
+        let hex_ids = git_ids
+            .iter()
+            .map(|git_id| git_id.to_hex().to_string())
+            .sorted()
+            .collect_vec();
+        GitSubprocessContext::new(self.git_repo_path(), &self.git_executable)
+            .spawn_fetch_missing_objects(&remote_name, &hex_ids)
+            .map_err(|err| to_read_object_err(err, id))
+    }
+
+    /// Looks up an object. If the object is missing from a partial clone, it
+    /// is fetched from the promisor remote.
+    fn find_object<'repo>(
+        &self,
+        repo: &'repo gix::Repository,
+        git_id: gix::ObjectId,
+        id: &impl ObjectId,
+    ) -> BackendResult<gix::Object<'repo>> {
+        match repo.find_object(git_id) {
+            Err(gix::object::find::existing::Error::NotFound { .. }) if is_partial_clone(repo) => {
+                GitSubprocessContext::new(self.git_repo_path(), &self.git_executable)
+                    .spawn_fetch_missing_object(&git_id.to_hex().to_string())
+                    .map_err(|err| to_read_object_err(err, id))?;
+                repo.find_object(git_id)
+                    .map_err(|err| map_not_found_err(err, id))
+            }
+            result => result.map_err(|err| map_not_found_err(err, id)),
+        }
+    }
+
+    fn read_file_sync(&self, path: &RepoPath, id: &FileId) -> BackendResult<Vec<u8>> {
         let git_blob_id = validate_git_object_id(id)?;
         let locked_repo = self.lock_git_repo();
-        let mut blob = locked_repo
-            .find_object(git_blob_id)
-            .map_err(|err| map_not_found_err(err, id))?
+        self.prefetch_missing_blobs(&locked_repo, path, git_blob_id, id)?;
+        let mut blob = self
+            .find_object(&locked_repo, git_blob_id, id)?
             .try_into_blob()
             .map_err(|err| to_read_object_err(err, id))?;
         Ok(blob.take_data())
@@ -567,8 +658,8 @@ fn commit_from_git_without_root_parent(
         .unwrap_or_else(|| synthetic_change_id_from_git_commit_id(id));
 
     // shallow commits don't have parents their parents actually fetched, so we
-    // discard them here
-    // TODO: This causes issues when a shallow repository is deepened/unshallowed
+    // discard them here. The commit index has to be rebuilt when a shallow
+    // repository is deepened/unshallowed.
     let parents = if is_shallow {
         vec![]
     } else {
@@ -687,7 +778,7 @@ fn signature_from_git(signature: gix::actor::SignatureRef) -> Signature {
     }
 }
 
-fn signature_to_git(signature: &Signature) -> gix::actor::Signature {
+pub(crate) fn signature_to_git(signature: &Signature) -> gix::actor::Signature {
     // git does not support empty names or emails
     let name = if !signature.name.is_empty() {
         &signature.name
@@ -884,6 +975,18 @@ fn validate_git_object_id(id: &impl ObjectId) -> BackendResult<gix::ObjectId> {
     Ok(gix::ObjectId::from_bytes_or_panic(id.as_bytes()))
 }
 
+fn is_partial_clone(repo: &gix::Repository) -> bool {
+    partial_clone_remote(repo).is_some()
+}
+
+/// Returns the name of the promisor remote if the repository is a partial
+/// clone.
+fn partial_clone_remote(repo: &gix::Repository) -> Option<String> {
+    repo.config_snapshot()
+        .string("extensions.partialClone")
+        .map(|name| name.to_string())
+}
+
 fn map_not_found_err(err: gix::object::find::existing::Error, id: &impl ObjectId) -> BackendError {
     if matches!(err, gix::object::find::existing::Error::NotFound { .. }) {
         BackendError::ObjectNotFound {
@@ -985,12 +1088,13 @@ impl Backend for GitBackend {
         1
     }
 
+    #[tracing::instrument(skip(self))]
     async fn read_file(
         &self,
-        _path: &RepoPath,
+        path: &RepoPath,
         id: &FileId,
     ) -> BackendResult<Pin<Box<dyn AsyncRead + Send>>> {
-        let data = self.read_file_sync(id)?;
+        let data = self.read_file_sync(path, id)?;
         Ok(Box::pin(Cursor::new(data)))
     }
 
@@ -1011,12 +1115,12 @@ impl Backend for GitBackend {
         Ok(FileId::new(oid.as_bytes().to_vec()))
     }
 
-    async fn read_symlink(&self, _path: &RepoPath, id: &SymlinkId) -> BackendResult<String> {
+    async fn read_symlink(&self, path: &RepoPath, id: &SymlinkId) -> BackendResult<String> {
         let git_blob_id = validate_git_object_id(id)?;
         let locked_repo = self.lock_git_repo();
-        let mut blob = locked_repo
-            .find_object(git_blob_id)
-            .map_err(|err| map_not_found_err(err, id))?
+        self.prefetch_missing_blobs(&locked_repo, path, git_blob_id, id)?;
+        let mut blob = self
+            .find_object(&locked_repo, git_blob_id, id)?
             .try_into_blob()
             .map_err(|err| to_read_object_err(err, id))?;
         let target = String::from_utf8(blob.take_data())
@@ -1054,16 +1158,16 @@ impl Backend for GitBackend {
         ))
     }
 
-    async fn read_tree(&self, _path: &RepoPath, id: &TreeId) -> BackendResult<Tree> {
+    #[tracing::instrument(skip(self))]
+    async fn read_tree(&self, path: &RepoPath, id: &TreeId) -> BackendResult<Tree> {
         if id == &self.empty_tree_id {
             return Ok(Tree::default());
         }
         let git_tree_id = validate_git_object_id(id)?;
 
         let locked_repo = self.lock_git_repo();
-        let git_tree = locked_repo
-            .find_object(git_tree_id)
-            .map_err(|err| map_not_found_err(err, id))?
+        let git_tree = self
+            .find_object(&locked_repo, git_tree_id, id)?
             .try_into_tree()
             .map_err(|err| to_read_object_err(err, id))?;
         let mut entries: Vec<_> = git_tree
@@ -1112,9 +1216,12 @@ impl Backend for GitBackend {
         if !entries.is_sorted_by_key(|(name, _)| name) {
             entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
         }
-        Ok(Tree::from_sorted_entries(entries))
+        let tree = Tree::from_sorted_entries(entries);
+        self.record_missing_blobs(&locked_repo, path, &tree);
+        Ok(tree)
     }
 
+    #[tracing::instrument(skip(self, contents))]
     async fn write_tree(&self, _path: &RepoPath, contents: &Tree) -> BackendResult<TreeId> {
         // Tree entries to be written must be sorted by Entry::filename(), which
         // is slightly different from the order of our backend::Tree.
@@ -1209,6 +1316,7 @@ impl Backend for GitBackend {
         Ok(commit)
     }
 
+    #[tracing::instrument(skip_all)]
     async fn write_commit(
         &self,
         mut contents: Commit,
@@ -1429,7 +1537,9 @@ impl Backend for GitBackend {
         // TODO: remove unreachable entries from extras table if segment file
         // mtime <= keep_newer? (it won't be consistent with no-gc refs
         // preserved by the keep_newer timestamp though)
-        // TODO: remove unreachable extras table segments
+        self.extra_metadata_store
+            .gc(keep_newer)
+            .map_err(GitBackendError::WriteMetadata)?;
         run_git_gc(
             self.git_executable.as_ref(),
             self.git_repo_path(),
@@ -0,0 +1,340 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for files stored in Git LFS.
+//!
+//! Files with the `filter=lfs` attribute are committed as small pointer files
+//! referring to objects in `<git_dir>/lfs/objects`. Objects missing from the
+//! local object store are downloaded on demand by `git lfs`.
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use sha2::Digest as _;
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::config::ConfigGetError;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::hex_util::encode_hex;
+use crate::settings::UserSettings;
+
+/// The version line of pointer files.
+pub const LFS_POINTER_VERSION: &str = "https://git-lfs.github.com/spec/v1";
+
+/// Pointer files are never larger than this.
+const MAX_POINTER_SIZE: usize = 1024;
+
+/// Error while reading or writing LFS objects.
+#[derive(Debug, Error)]
+pub enum LfsError {
+    /// Failed to access the local object store.
+    #[error(transparent)]
+    Io(#[from] PathError),
+    /// The object content doesn't match the pointer.
+    #[error("LFS object {oid} is corrupt")]
+    CorruptObject {
+        /// The object id.
+        oid: String,
+    },
+    /// Failed to spawn `git lfs`.
+    #[error("Failed to run git lfs")]
+    Spawn(#[source] io::Error),
+    /// `git lfs` exited with an error.
+    #[error("git lfs failed: {0}")]
+    Command(String),
+}
+
+/// Settings for Git LFS support.
+#[derive(Clone, Debug)]
+pub struct GitLfsSettings {
+    /// The `git` executable used to run `git lfs`.
+    pub executable_path: PathBuf,
+}
+
+impl GitLfsSettings {
+    /// Loads the settings. Returns `None` if LFS support is disabled.
+    pub fn from_settings(settings: &UserSettings) -> Result<Option<Self>, ConfigGetError> {
+        if !settings.get_bool("git.lfs.enabled")? {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            executable_path: settings.get("git.executable-path")?,
+        }))
+    }
+}
+
+/// A parsed Git LFS pointer file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LfsPointer {
+    /// Hex-encoded SHA-256 of the object content.
+    pub oid: String,
+    /// Size of the object content in bytes.
+    pub size: u64,
+}
+
+impl LfsPointer {
+    /// Parses `content` as a pointer file. Returns `None` if the content isn't
+    /// a pointer.
+    pub fn parse(content: &[u8]) -> Option<Self> {
+        if content.len() > MAX_POINTER_SIZE {
+            return None;
+        }
+        let text = str::from_utf8(content).ok()?;
+        let mut lines = text.lines();
+        if lines.next()?.strip_prefix("version ")? != LFS_POINTER_VERSION {
+            return None;
+        }
+        let mut oid = None;
+        let mut size = None;
+        for line in lines {
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "oid" => {
+                    let hex = value.strip_prefix("sha256:")?;
+                    if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        return None;
+                    }
+                    oid = Some(hex.to_ascii_lowercase());
+                }
+                "size" => size = Some(value.parse().ok()?),
+                // Extension and other keys don't affect the content.
+                _ => {}
+            }
+        }
+        Some(Self {
+            oid: oid?,
+            size: size?,
+        })
+    }
+
+    /// Creates the pointer for `content`.
+    pub fn for_content(content: &[u8]) -> Self {
+        Self {
+            oid: encode_hex(&Sha256::digest(content)),
+            size: content.len() as u64,
+        }
+    }
+
+    /// Serializes the pointer in the canonical format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        format!(
+            "version {LFS_POINTER_VERSION}\noid sha256:{}\nsize {}\n",
+            self.oid, self.size
+        )
+        .into_bytes()
+    }
+
+    fn matches(&self, content: &[u8]) -> bool {
+        content.len() as u64 == self.size && *self == Self::for_content(content)
+    }
+}
+
+/// The LFS object store of a Git repository.
+#[derive(Clone, Debug)]
+pub struct LfsObjectStore {
+    git_dir: PathBuf,
+    git_executable_path: PathBuf,
+}
+
+impl LfsObjectStore {
+    /// Creates a store for the Git repository at `git_dir`. Missing objects
+    /// are downloaded by running `git lfs` from `git_executable_path`.
+    pub fn new(git_dir: PathBuf, git_executable_path: PathBuf) -> Self {
+        Self {
+            git_dir,
+            git_executable_path,
+        }
+    }
+
+    /// Returns the path of the object `oid` in the local object store.
+    pub fn object_path(&self, oid: &str) -> PathBuf {
+        self.git_dir
+            .join("lfs")
+            .join("objects")
+            .join(&oid[0..2])
+            .join(&oid[2..4])
+            .join(oid)
+    }
+
+    /// Stores `content` in the local object store and returns its pointer.
+    pub fn write(&self, content: &[u8]) -> Result<LfsPointer, LfsError> {
+        let pointer = LfsPointer::for_content(content);
+        let path = self.object_path(&pointer.oid);
+        if path.exists() {
+            return Ok(pointer);
+        }
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir).context(dir)?;
+        let mut temp_file = tempfile::NamedTempFile::new_in(dir).context(dir)?;
+        temp_file.write_all(content).context(temp_file.path())?;
+        persist_content_addressed_temp_file(temp_file, &path).context(&path)?;
+        Ok(pointer)
+    }
+
+    /// Reads the object for `pointer` from the local object store. Returns
+    /// `None` if the object hasn't been downloaded.
+    pub fn read(&self, pointer: &LfsPointer) -> Result<Option<Vec<u8>>, LfsError> {
+        let path = self.object_path(&pointer.oid);
+        let content = match fs::read(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).context(&path)?,
+        };
+        if !pointer.matches(&content) {
+            return Err(LfsError::CorruptObject {
+                oid: pointer.oid.clone(),
+            });
+        }
+        Ok(Some(content))
+    }
+
+    /// Converts working-copy `content` to the pointer to be committed. The
+    /// content is passed through if it is already a pointer.
+    pub fn clean(&self, content: Vec<u8>) -> Result<Vec<u8>, LfsError> {
+        if LfsPointer::parse(&content).is_some() {
+            return Ok(content);
+        }
+        Ok(self.write(&content)?.to_bytes())
+    }
+
+    /// Converts committed `content` to the content of the working-copy file.
+    /// Objects missing from the local object store are downloaded. Content
+    /// which isn't a pointer is passed through.
+    pub fn smudge(&self, path: &Path, content: Vec<u8>) -> Result<Vec<u8>, LfsError> {
+        let Some(pointer) = LfsPointer::parse(&content) else {
+            return Ok(content);
+        };
+        if let Some(object) = self.read(&pointer)? {
+            return Ok(object);
+        }
+        let object = self.download(path, &content)?;
+        if !pointer.matches(&object) {
+            return Err(LfsError::CorruptObject { oid: pointer.oid });
+        }
+        self.write(&object)?;
+        Ok(object)
+    }
+
+    fn download(&self, path: &Path, pointer_content: &[u8]) -> Result<Vec<u8>, LfsError> {
+        let mut child = self
+            .git_command()
+            .args(["lfs", "smudge", "--"])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(LfsError::Spawn)?;
+        // The pointer is small enough to fit in the pipe buffer.
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(pointer_content)
+            .map_err(LfsError::Spawn)?;
+        let output = child.wait_with_output().map_err(LfsError::Spawn)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(LfsError::Command(stderr.trim_end().to_owned()));
+        }
+        Ok(output.stdout)
+    }
+
+    /// Returns a `git` command running in the repository.
+    pub fn git_command(&self) -> Command {
+        let mut cmd = Command::new(&self.git_executable_path);
+        cmd.arg("--git-dir").arg(&self.git_dir);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pointer() {
+        let content = b"version https://git-lfs.github.com/spec/v1\n\
+            oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+            size 12345\n";
+        assert_eq!(
+            LfsPointer::parse(content),
+            Some(LfsPointer {
+                oid: "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393".to_owned(),
+                size: 12345,
+            })
+        );
+        assert_eq!(LfsPointer::parse(content).unwrap().to_bytes(), content);
+
+        assert_eq!(LfsPointer::parse(b""), None);
+        assert_eq!(LfsPointer::parse(b"hello\n"), None);
+        // Missing size
+        assert_eq!(
+            LfsPointer::parse(
+                b"version https://git-lfs.github.com/spec/v1\n\
+                  oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n"
+            ),
+            None
+        );
+        // Short oid
+        assert_eq!(
+            LfsPointer::parse(
+                b"version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\nsize 1\n"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clean_and_smudge() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = LfsObjectStore::new(temp_dir.path().to_owned(), "git".into());
+        let content = b"large binary content".to_vec();
+
+        let pointer_content = store.clean(content.clone()).unwrap();
+        let pointer = LfsPointer::parse(&pointer_content).unwrap();
+        assert_eq!(pointer, LfsPointer::for_content(&content));
+        assert!(store.object_path(&pointer.oid).exists());
+        // Cleaning a pointer is a no-op.
+        assert_eq!(
+            store.clean(pointer_content.clone()).unwrap(),
+            pointer_content
+        );
+
+        let path = Path::new("file.bin");
+        assert_eq!(store.smudge(path, pointer_content).unwrap(), content);
+        // Content which isn't a pointer is passed through.
+        assert_eq!(store.smudge(path, b"foo".to_vec()).unwrap(), b"foo");
+    }
+
+    #[test]
+    fn test_read_corrupt_object() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = LfsObjectStore::new(temp_dir.path().to_owned(), "git".into());
+        let pointer = store.write(b"content").unwrap();
+        fs::write(store.object_path(&pointer.oid), b"tampered").unwrap();
+        assert!(matches!(
+            store.read(&pointer),
+            Err(LfsError::CorruptObject { .. })
+        ));
+    }
+}
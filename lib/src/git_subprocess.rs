@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::io;
 use std::io::BufReader;
 use std::io::Read;
-use std::num::NonZeroU32;
+use std::io::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Child;
@@ -28,17 +29,23 @@ use bstr::ByteSlice as _;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::backend::CommitId;
+use crate::git::FetchDepth;
 use crate::git::FetchTagsOverride;
 use crate::git::GitPushStats;
+use crate::git::GitSettings;
 use crate::git::NegativeRefSpec;
 use crate::git::Progress;
 use crate::git::RefSpec;
 use crate::git::RefToPush;
 use crate::git::RemoteCallbacks;
 use crate::git_backend::GitBackend;
+use crate::object_id::ObjectId as _;
 use crate::ref_name::GitRefNameBuf;
 use crate::ref_name::RefNameBuf;
 use crate::ref_name::RemoteName;
+use crate::ref_name::RemoteNameBuf;
+use crate::settings::RemoteSettings;
 
 // This is not the minimum required version, that would be 2.29.0, which
 // introduced the `--no-write-fetch-head` option. However, that by itself
@@ -80,6 +87,8 @@ pub enum GitSubprocessError {
 pub(crate) struct GitSubprocessContext<'a> {
     git_dir: PathBuf,
     git_executable_path: &'a Path,
+    credential_helpers: &'a [String],
+    remote_settings: Option<&'a HashMap<RemoteNameBuf, RemoteSettings>>,
 }
 
 impl<'a> GitSubprocessContext<'a> {
@@ -87,14 +96,20 @@ impl<'a> GitSubprocessContext<'a> {
         Self {
             git_dir: git_dir.into(),
             git_executable_path,
+            credential_helpers: &[],
+            remote_settings: None,
         }
     }
 
     pub(crate) fn from_git_backend(
         git_backend: &GitBackend,
-        git_executable_path: &'a Path,
+        git_settings: &'a GitSettings,
     ) -> Self {
-        Self::new(git_backend.git_repo_path(), git_executable_path)
+        Self {
+            credential_helpers: &git_settings.credential_helpers,
+            remote_settings: Some(&git_settings.remotes),
+            ..Self::new(git_backend.git_repo_path(), &git_settings.executable_path)
+        }
     }
 
     /// Create the Git command
@@ -128,6 +143,13 @@ impl<'a> GitSubprocessContext<'a> {
             // user has `submodule.recurse` configured to true in their Git
             // config (#7565).
             .args(["-c", "submodule.recurse=false"])
+            // Credential helpers configured in jj are consulted after the ones
+            // configured in Git.
+            .args(
+                self.credential_helpers
+                    .iter()
+                    .flat_map(|helper| ["-c".to_owned(), format!("credential.helper={helper}")]),
+            )
             .arg("--git-dir")
             .arg(&self.git_dir)
             // Disable translation and other locale-dependent behavior so we can
@@ -139,6 +161,21 @@ impl<'a> GitSubprocessContext<'a> {
         git_cmd
     }
 
+    /// Create the Git command which connects to the remote
+    fn create_remote_command(&self, remote_name: &RemoteName) -> Command {
+        let mut git_cmd = self.create_command();
+        if let Some(ssh_command) = self
+            .remote_settings
+            .and_then(|remotes| remotes.get(remote_name))
+            .and_then(|settings| settings.ssh_command())
+        {
+            // Takes precedence over core.sshCommand and GIT_SSH_COMMAND set
+            // by the user.
+            git_cmd.env("GIT_SSH_COMMAND", ssh_command);
+        }
+        git_cmd
+    }
+
     /// Spawn the git command
     fn spawn_cmd(&self, mut git_cmd: Command) -> Result<Child, GitSubprocessError> {
         tracing::debug!(cmd = ?git_cmd, "spawning a git subprocess");
@@ -167,13 +204,13 @@ impl<'a> GitSubprocessContext<'a> {
         refspecs: &[RefSpec],
         negative_refspecs: &[NegativeRefSpec],
         callbacks: &mut RemoteCallbacks<'_>,
-        depth: Option<NonZeroU32>,
+        depth: &FetchDepth,
         fetch_tags_override: Option<FetchTagsOverride>,
     ) -> Result<Option<String>, GitSubprocessError> {
         if refspecs.is_empty() {
             return Ok(None);
         }
-        let mut command = self.create_command();
+        let mut command = self.create_remote_command(remote_name);
         command.stdout(Stdio::piped());
         // attempt to prune stale refs with --prune
         // --no-write-fetch-head ensures our request is invisible to other parties
@@ -181,8 +218,17 @@ impl<'a> GitSubprocessContext<'a> {
         if callbacks.progress.is_some() {
             command.arg("--progress");
         }
-        if let Some(d) = depth {
-            command.arg(format!("--depth={d}"));
+        match depth {
+            FetchDepth::Full => {}
+            FetchDepth::Depth(d) => {
+                command.arg(format!("--depth={d}"));
+            }
+            FetchDepth::ShallowSince(date) => {
+                command.arg(format!("--shallow-since={date}"));
+            }
+            FetchDepth::Deepen(d) => {
+                command.arg(format!("--deepen={d}"));
+            }
         }
         match fetch_tags_override {
             Some(FetchTagsOverride::AllTags) => {
@@ -206,6 +252,99 @@ impl<'a> GitSubprocessContext<'a> {
         parse_git_fetch_output(output)
     }
 
+    /// Fetch an object missing from a partial clone
+    ///
+    /// Looking up the object makes git fetch it from the promisor remote.
+    pub(crate) fn spawn_fetch_missing_object(&self, id: &str) -> Result<(), GitSubprocessError> {
+        tracing::debug!(id, "fetching missing object");
+        let mut command = self.create_command();
+        command.stdout(Stdio::null());
+        command.args(["cat-file", "-t", id]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        Ok(())
+    }
+
+    /// Fetch objects missing from a partial clone in one batch
+    ///
+    /// This runs the same command git uses to fetch a missing object on
+    /// demand, but with all the `ids` at once.
+    pub(crate) fn spawn_fetch_missing_objects(
+        &self,
+        remote_name: &str,
+        ids: &[String],
+    ) -> Result<(), GitSubprocessError> {
+        tracing::debug!(remote_name, count = ids.len(), "fetching missing objects");
+        let mut command = self.create_command();
+        command.stdin(Stdio::piped());
+        command.stdout(Stdio::null());
+        command.args([
+            "-c",
+            "fetch.negotiationAlgorithm=noop",
+            "fetch",
+            remote_name,
+            "--no-tags",
+            "--no-write-fetch-head",
+            "--recurse-submodules=no",
+            "--filter=blob:none",
+            "--stdin",
+        ]);
+        let mut child = self.spawn_cmd(command)?;
+        let mut stdin = child.stdin.take().unwrap();
+        // Write from another thread so git doesn't block on a full stderr pipe
+        let ids = ids.join("\n");
+        let writer = thread::spawn(move || writeln!(stdin, "{ids}"));
+        let output = wait_with_output(child)?;
+        writer.join().unwrap().map_err(GitSubprocessError::Wait)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        Ok(())
+    }
+
+    /// Create a bundle file containing the history of the refs, excluding
+    /// the history of the prerequisites
+    pub(crate) fn spawn_bundle_create(
+        &self,
+        path: &Path,
+        refs: &[String],
+        prerequisites: &[CommitId],
+    ) -> Result<(), GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::null());
+        command.args(["bundle", "create", "--quiet"]);
+        command.arg(path);
+        command.args(refs);
+        command.args(prerequisites.iter().map(|id| format!("^{}", id.hex())));
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        Ok(())
+    }
+
+    /// Write an incremental commit-graph covering the commits reachable from
+    /// the refs
+    pub(crate) fn spawn_commit_graph_write(&self) -> Result<(), GitSubprocessError> {
+        tracing::debug!("writing commit-graph");
+        let mut command = self.create_command();
+        command.stdout(Stdio::null());
+        command.args([
+            "commit-graph",
+            "write",
+            "--reachable",
+            "--split",
+            "--no-progress",
+        ]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        Ok(())
+    }
+
     /// Prune particular branches
     pub(crate) fn spawn_branch_prune(
         &self,
@@ -238,7 +377,7 @@ impl<'a> GitSubprocessContext<'a> {
         &self,
         remote_name: &RemoteName,
     ) -> Result<Option<RefNameBuf>, GitSubprocessError> {
-        let mut command = self.create_command();
+        let mut command = self.create_remote_command(remote_name);
         command.stdout(Stdio::piped());
         command.args(["remote", "show", "--", remote_name.as_str()]);
         let output = wait_with_output(self.spawn_cmd(command)?)?;
@@ -250,6 +389,71 @@ impl<'a> GitSubprocessContext<'a> {
         Ok(maybe_branch.map(Into::into))
     }
 
+    /// List the branches on a remote without fetching them
+    ///
+    /// Returns the branch names along with the commits they point to
+    pub(crate) fn spawn_ls_remote_branches(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Vec<(RefNameBuf, CommitId)>, GitSubprocessError> {
+        let mut command = self.create_remote_command(remote_name);
+        command.stdout(Stdio::piped());
+        command.args(["ls-remote", "--heads", "--", remote_name.as_str()]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        let refs = parse_git_ls_remote_output(&output.stdout)?;
+        Ok(refs
+            .into_iter()
+            .filter_map(|(name, id)| Some((name.as_str().strip_prefix("refs/heads/")?.into(), id)))
+            .collect())
+    }
+
+    /// List the refs on a remote matching the patterns without fetching them
+    ///
+    /// Returns the full ref names along with the commits they point to
+    pub(crate) fn spawn_ls_remote(
+        &self,
+        remote_name: &RemoteName,
+        patterns: &[&str],
+    ) -> Result<Vec<(GitRefNameBuf, CommitId)>, GitSubprocessError> {
+        let mut command = self.create_remote_command(remote_name);
+        command.stdout(Stdio::piped());
+        command.args(["ls-remote", "--", remote_name.as_str()]);
+        command.args(patterns);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        parse_git_ls_remote_output(&output.stdout)
+    }
+
+    /// Fetch the objects of the refs on a remote without updating any local
+    /// refs
+    pub(crate) fn spawn_fetch_objects(
+        &self,
+        remote_name: &RemoteName,
+        refs: &[&str],
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<(), GitSubprocessError> {
+        let mut command = self.create_remote_command(remote_name);
+        command.stdout(Stdio::piped());
+        command.args(["fetch", "--no-write-fetch-head", "--no-tags"]);
+        if callbacks.progress.is_some() {
+            command.arg("--progress");
+        }
+        command.arg("--").arg(remote_name.as_str());
+        command.args(refs);
+        let output = wait_with_progress(self.spawn_cmd(command)?, callbacks)?;
+        match parse_git_fetch_output(output)? {
+            None => Ok(()),
+            Some(missing_ref) => Err(GitSubprocessError::External(format!(
+                "Remote ref {missing_ref} doesn't exist"
+            ))),
+        }
+    }
+
     /// Push references to git
     ///
     /// All pushes are forced, using --force-with-lease to perform a test&set
@@ -262,9 +466,10 @@ impl<'a> GitSubprocessContext<'a> {
         &self,
         remote_name: &RemoteName,
         references: &[RefToPush],
+        push_options: &[String],
         callbacks: &mut RemoteCallbacks<'_>,
     ) -> Result<GitPushStats, GitSubprocessError> {
-        let mut command = self.create_command();
+        let mut command = self.create_remote_command(remote_name);
         command.stdout(Stdio::piped());
         // Currently jj does not support commit hooks, so we prevent git from running
         // them
@@ -280,6 +485,11 @@ impl<'a> GitSubprocessContext<'a> {
                 .iter()
                 .map(|reference| format!("--force-with-lease={}", reference.to_git_lease())),
         );
+        command.args(
+            push_options
+                .iter()
+                .map(|option| format!("--push-option={option}")),
+        );
         command.args(["--", remote_name.as_str()]);
         // with --force-with-lease we cannot have the forced refspec,
         // as it ignores the lease
@@ -410,6 +620,27 @@ fn parse_git_fetch_output(output: Output) -> Result<Option<String>, GitSubproces
     Err(external_git_error(&output.stderr))
 }
 
+fn parse_git_ls_remote_output(
+    stdout: &[u8],
+) -> Result<Vec<(GitRefNameBuf, CommitId)>, GitSubprocessError> {
+    stdout
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let parsed = line.split_once_str("\t").and_then(|(hex, reference)| {
+                let name = reference.to_str().ok()?;
+                Some((name.into(), CommitId::try_from_hex(hex)?))
+            });
+            parsed.ok_or_else(|| {
+                GitSubprocessError::External(format!(
+                    "Line #{idx} of git-ls-remote has unknown format: {}",
+                    line.to_str_lossy()
+                ))
+            })
+        })
+        .collect()
+}
+
 fn parse_git_branch_prune_output(output: Output) -> Result<(), GitSubprocessError> {
     if output.status.success() {
         return Ok(());
@@ -1005,4 +1236,36 @@ Done";
     fn test_initial_overall_progress_is_zero() {
         assert_eq!(GitProgress::default().to_progress().overall, 0.0);
     }
+
+    #[test]
+    fn test_create_command_with_credential_helpers() {
+        let credential_helpers = [
+            "osxkeychain".to_owned(),
+            "!gh auth git-credential".to_owned(),
+        ];
+        let git_ctx = GitSubprocessContext {
+            credential_helpers: &credential_helpers,
+            ..GitSubprocessContext::new("repo/.git", Path::new("git"))
+        };
+        let cmd = git_ctx.create_command();
+        let args = cmd
+            .get_args()
+            .map(|arg| arg.to_str().unwrap())
+            .collect_vec();
+        assert_eq!(
+            args,
+            [
+                "-c",
+                "core.fsmonitor=false",
+                "-c",
+                "submodule.recurse=false",
+                "-c",
+                "credential.helper=osxkeychain",
+                "-c",
+                "credential.helper=!gh auth git-credential",
+                "--git-dir",
+                "repo/.git",
+            ]
+        );
+    }
 }
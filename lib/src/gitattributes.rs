@@ -0,0 +1,391 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Matching of paths against `.gitattributes` files.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use ignore::gitignore;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::TreeValue;
+use crate::merged_tree::MergedTree;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+use crate::repo_path::RepoPathComponent;
+
+/// Error while loading `.gitattributes` files.
+#[derive(Debug, Error)]
+pub enum GitAttributesError {
+    /// Failed to read a `.gitattributes` file from disk.
+    #[error("Failed to read attributes from file {path}")]
+    ReadFile {
+        /// Path to the file.
+        path: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+}
+
+/// State of a single attribute for a path.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum AttributeState {
+    /// No pattern specifies the attribute, or it was reset by `!attr`.
+    #[default]
+    Unspecified,
+    /// The attribute is set (`attr`).
+    Set,
+    /// The attribute is unset (`-attr`).
+    Unset,
+    /// The attribute is set to a value (`attr=value`).
+    Value(String),
+}
+
+impl AttributeState {
+    /// Returns the value if the attribute is set to a value.
+    pub fn as_value(&self) -> Option<&str> {
+        match self {
+            Self::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the attribute is not specified.
+    pub fn is_unspecified(&self) -> bool {
+        matches!(self, Self::Unspecified)
+    }
+}
+
+#[derive(Debug)]
+struct AttributesLine {
+    matcher: gitignore::Gitignore,
+    // Expanded attribute assignments in the order they appear.
+    assignments: Vec<(String, AttributeState)>,
+}
+
+impl AttributesLine {
+    fn get(&self, name: &str) -> Option<&AttributeState> {
+        self.assignments
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, state)| state)
+    }
+}
+
+/// Models the effective contents of multiple `.gitattributes` files.
+#[derive(Debug)]
+pub struct GitAttributesFile {
+    parent: Option<Arc<Self>>,
+    prefix: String,
+    lines: Vec<AttributesLine>,
+}
+
+impl GitAttributesFile {
+    /// Creates an empty attributes file which matches no paths.
+    pub fn empty() -> Arc<Self> {
+        Arc::new(Self {
+            parent: None,
+            prefix: String::new(),
+            lines: vec![],
+        })
+    }
+
+    /// Concatenates new `.gitattributes` content at the `prefix` directory.
+    ///
+    /// The `prefix` should be a slash-separated path relative to the workspace
+    /// root. Lines that can't be parsed are skipped, as Git does.
+    pub fn chain(self: &Arc<Self>, prefix: &str, input: &[u8]) -> Arc<Self> {
+        let lines = input
+            .split(|b| *b == b'\n')
+            .filter_map(|line| str::from_utf8(line).ok())
+            .filter_map(|line| parse_line(prefix, line))
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            return self.clone();
+        }
+        let parent = if self.lines.is_empty() {
+            self.parent.clone() // omit the empty root
+        } else {
+            Some(self.clone())
+        };
+        Arc::new(Self {
+            parent,
+            prefix: prefix.to_owned(),
+            lines,
+        })
+    }
+
+    /// Concatenates new `.gitattributes` file at the `prefix` directory.
+    ///
+    /// The `prefix` should be a slash-separated path relative to the workspace
+    /// root.
+    pub fn chain_with_file(
+        self: &Arc<Self>,
+        prefix: &str,
+        file: PathBuf,
+    ) -> Result<Arc<Self>, GitAttributesError> {
+        if file.is_file() {
+            let buf = fs::read(&file).map_err(|err| GitAttributesError::ReadFile {
+                path: file.clone(),
+                source: err,
+            })?;
+            Ok(self.chain(prefix, &buf))
+        } else {
+            Ok(self.clone())
+        }
+    }
+
+    /// Looks up the state of the attribute `name` for the file at `path`.
+    ///
+    /// Patterns in files closer to the path take precedence, and later lines
+    /// in the same file take precedence over earlier ones.
+    pub fn get(&self, path: &str, name: &str) -> AttributeState {
+        iter::successors(Some(self), |file| file.parent.as_deref())
+            // Patterns only apply to paths under the directory of the file.
+            .filter(|file| path.starts_with(&file.prefix))
+            .flat_map(|file| file.lines.iter().rev())
+            .filter(|line| line.get(name).is_some())
+            .find(|line| line.matcher.matched(path, false).is_ignore())
+            .and_then(|line| line.get(name).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Looks up the attributes affecting the content of the file at `path`.
+    pub fn content_attributes(&self, path: &str) -> ContentAttributes {
+        ContentAttributes {
+            text: self.get(path, "text"),
+            eol: self.get(path, "eol"),
+            diff: self.get(path, "diff"),
+            filter: self.get(path, "filter"),
+        }
+    }
+}
+
+fn parse_line(prefix: &str, line: &str) -> Option<AttributesLine> {
+    let line = line.trim();
+    // Quoted patterns and macro definitions aren't supported.
+    if line.is_empty() || line.starts_with(['#', '"', '[']) {
+        return None;
+    }
+    let mut words = line.split_ascii_whitespace();
+    let pattern = words.next()?;
+    // Negative patterns are forbidden in .gitattributes.
+    if pattern.starts_with('!') {
+        return None;
+    }
+    let mut builder = gitignore::GitignoreBuilder::new(prefix);
+    builder.add_line(None, pattern).ok()?;
+    let matcher = builder.build().ok()?;
+    let mut assignments = vec![];
+    for word in words {
+        if let Some(name) = word.strip_prefix('-') {
+            assignments.push((name.to_owned(), AttributeState::Unset));
+        } else if let Some(name) = word.strip_prefix('!') {
+            assignments.push((name.to_owned(), AttributeState::Unspecified));
+        } else if let Some((name, value)) = word.split_once('=') {
+            assignments.push((name.to_owned(), AttributeState::Value(value.to_owned())));
+        } else if word == "binary" {
+            // The only built-in macro attribute.
+            assignments.push((word.to_owned(), AttributeState::Set));
+            for name in ["diff", "merge", "text"] {
+                assignments.push((name.to_owned(), AttributeState::Unset));
+            }
+        } else {
+            assignments.push((word.to_owned(), AttributeState::Set));
+        }
+    }
+    Some(AttributesLine {
+        matcher,
+        assignments,
+    })
+}
+
+/// Attributes which affect how the content of a file is converted and
+/// presented.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ContentAttributes {
+    /// The `text` attribute.
+    pub text: AttributeState,
+    /// The `eol` attribute.
+    pub eol: AttributeState,
+    /// The `diff` attribute.
+    pub diff: AttributeState,
+    /// The `filter` attribute.
+    pub filter: AttributeState,
+}
+
+impl ContentAttributes {
+    /// Returns true if the file should be presented as binary in diffs.
+    pub fn is_diff_disabled(&self) -> bool {
+        self.diff == AttributeState::Unset
+    }
+
+    /// Returns true if the file content is stored as a Git LFS pointer.
+    pub fn is_lfs(&self) -> bool {
+        self.filter.as_value() == Some("lfs")
+    }
+}
+
+/// Loads `.gitattributes` files from a tree on demand.
+#[derive(Debug)]
+pub struct TreeGitAttributes {
+    tree: MergedTree,
+    files: Mutex<HashMap<RepoPathBuf, Arc<GitAttributesFile>>>,
+}
+
+impl TreeGitAttributes {
+    /// Creates a loader for `.gitattributes` files in the `tree`.
+    pub fn new(tree: MergedTree) -> Self {
+        Self {
+            tree,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the effective attributes file for paths in the directory `dir`.
+    pub async fn file_for_dir(&self, dir: &RepoPath) -> BackendResult<Arc<GitAttributesFile>> {
+        let mut dirs = dir.ancestors().collect::<Vec<_>>();
+        dirs.reverse();
+        let mut file = GitAttributesFile::empty();
+        for dir in dirs {
+            if let Some(cached) = self.files.lock().unwrap().get(dir) {
+                file = cached.clone();
+                continue;
+            }
+            let name = RepoPathComponent::new(".gitattributes").unwrap();
+            let path = dir.join(name);
+            if let Some(Some(TreeValue::File { id, .. })) =
+                self.tree.path_value_async(&path).await?.as_resolved()
+            {
+                let mut content = vec![];
+                self.tree
+                    .store()
+                    .read_file(&path, id)
+                    .await?
+                    .read_to_end(&mut content)
+                    .await
+                    .map_err(|err| BackendError::ReadFile {
+                        path: path.clone(),
+                        id: id.clone(),
+                        source: err.into(),
+                    })?;
+                file = file.chain(&dir.to_internal_dir_string(), &content);
+            }
+            self.files
+                .lock()
+                .unwrap()
+                .insert(dir.to_owned(), file.clone());
+        }
+        Ok(file)
+    }
+
+    /// Looks up the attributes affecting the content of the file at `path`.
+    pub async fn content_attributes(&self, path: &RepoPath) -> BackendResult<ContentAttributes> {
+        let Some(dir) = path.parent() else {
+            return Ok(ContentAttributes::default());
+        };
+        let file = self.file_for_dir(dir).await?;
+        Ok(file.content_attributes(path.as_internal_file_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(input: &[u8], path: &str, name: &str) -> AttributeState {
+        GitAttributesFile::empty().chain("", input).get(path, name)
+    }
+
+    #[test]
+    fn test_gitattributes_empty_file() {
+        let file = GitAttributesFile::empty();
+        assert_eq!(file.get("foo", "text"), AttributeState::Unspecified);
+    }
+
+    #[test]
+    fn test_gitattributes_states() {
+        let input = b"*.txt text eol=crlf -diff\n*.md !text\n";
+        assert_eq!(get(input, "a.txt", "text"), AttributeState::Set);
+        assert_eq!(
+            get(input, "a.txt", "eol"),
+            AttributeState::Value("crlf".to_owned())
+        );
+        assert_eq!(get(input, "a.txt", "diff"), AttributeState::Unset);
+        assert_eq!(get(input, "a.md", "text"), AttributeState::Unspecified);
+        assert_eq!(get(input, "a.rs", "text"), AttributeState::Unspecified);
+    }
+
+    #[test]
+    fn test_gitattributes_binary_macro() {
+        let input = b"*.png binary\n";
+        assert_eq!(get(input, "dir/a.png", "binary"), AttributeState::Set);
+        assert_eq!(get(input, "dir/a.png", "text"), AttributeState::Unset);
+        assert_eq!(get(input, "dir/a.png", "diff"), AttributeState::Unset);
+        assert_eq!(get(input, "dir/a.png", "merge"), AttributeState::Unset);
+    }
+
+    #[test]
+    fn test_gitattributes_later_line_wins() {
+        let input = b"* text=auto\n*.bat eol=crlf\n*.bin -text\n";
+        assert_eq!(
+            get(input, "a.bat", "text"),
+            AttributeState::Value("auto".to_owned())
+        );
+        assert_eq!(
+            get(input, "a.bat", "eol"),
+            AttributeState::Value("crlf".to_owned())
+        );
+        assert_eq!(get(input, "a.bin", "text"), AttributeState::Unset);
+    }
+
+    #[test]
+    fn test_gitattributes_ignores_invalid_lines() {
+        let input = b"# comment\n!*.txt text\n\"quoted name\" text\n[attr]foo text\n*.c text\n";
+        assert_eq!(get(input, "a.txt", "text"), AttributeState::Unspecified);
+        assert_eq!(get(input, "a.c", "text"), AttributeState::Set);
+    }
+
+    #[test]
+    fn test_gitattributes_chained_with_prefix() {
+        let file = GitAttributesFile::empty()
+            .chain("", b"*.txt text\n")
+            .chain("dir/", b"*.txt -text\n/root.c text\n");
+        assert_eq!(file.get("a.txt", "text"), AttributeState::Set);
+        assert_eq!(file.get("dir/a.txt", "text"), AttributeState::Unset);
+        assert_eq!(file.get("dir/sub/a.txt", "text"), AttributeState::Unset);
+        assert_eq!(file.get("dir/root.c", "text"), AttributeState::Set);
+        assert_eq!(
+            file.get("dir/sub/root.c", "text"),
+            AttributeState::Unspecified
+        );
+    }
+
+    #[test]
+    fn test_gitattributes_directory_pattern() {
+        let input = b"vendor/** -diff\n";
+        assert_eq!(get(input, "vendor/a/b.c", "diff"), AttributeState::Unset);
+        assert_eq!(get(input, "src/b.c", "diff"), AttributeState::Unspecified);
+    }
+}
@@ -30,6 +30,7 @@ use crate::signing::SigStatus;
 use crate::signing::SignError;
 use crate::signing::SigningBackend;
 use crate::signing::Verification;
+use crate::sigstore_signing;
 
 // Search for one of the:
 //  [GNUPG:] GOODSIG <long keyid> <primary uid..>
@@ -38,7 +39,7 @@ use crate::signing::Verification;
 //  [GNUPG:] BADSIG <long keyid> <primary uid..>
 // in the output from --status-fd=1
 // Assume signature is invalid if none of the above was found
-fn parse_gpg_verify_output(
+pub(crate) fn parse_gpg_verify_output(
     output: &[u8],
     allow_expired_keys: bool,
 ) -> Result<Verification, SignError> {
@@ -77,7 +78,7 @@ fn parse_gpg_verify_output(
         .ok_or(SignError::InvalidSignatureFormat)
 }
 
-fn run_sign_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgError> {
+pub(crate) fn run_sign_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgError> {
     tracing::info!(?command, "running GPG signing command");
     let process = command.stderr(Stdio::piped()).spawn()?;
     let write_result = process.stdin.as_ref().unwrap().write_all(input);
@@ -94,7 +95,7 @@ fn run_sign_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgE
     }
 }
 
-fn run_verify_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgError> {
+pub(crate) fn run_verify_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, GpgError> {
     tracing::info!(?command, "running GPG signing command");
     let process = command.stderr(Stdio::null()).spawn()?;
     let write_result = process.stdin.as_ref().unwrap().write_all(input);
@@ -269,7 +270,10 @@ impl SigningBackend for GpgsmBackend {
     }
 
     fn can_read(&self, signature: &[u8]) -> bool {
+        // Sigstore signatures have the same format, but can't be verified by
+        // gpgsm.
         signature.starts_with(b"-----BEGIN SIGNED MESSAGE-----")
+            && !sigstore_signing::is_sigstore_signature(signature)
     }
 
     fn sign(&self, data: &[u8], key: Option<&str>) -> Result<Vec<u8>, SignError> {
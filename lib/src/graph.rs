@@ -19,6 +19,8 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::hash::Hash;
 
+use itertools::Itertools as _;
+
 /// Node and edges pair of type `N` and `ID` respectively.
 ///
 /// `ID` uniquely identifies a node within the graph. It's usually cheap to
@@ -113,6 +115,70 @@ pub fn reverse_graph<N, ID: Clone + Eq + Hash, E>(
     Ok(items)
 }
 
+/// Compacted graph and the elided nodes keyed by the target of the substituted
+/// indirect edge.
+pub type CompactedGraph<N, ID> = (Vec<GraphNode<N, ID>>, HashMap<ID, Vec<N>>);
+
+/// Collapses runs of linear history into indirect edges.
+///
+/// A node is part of a linear run if it is the only child of the previous node
+/// and has a single direct edge to the next node, of which it is the only
+/// child. Runs of at least `min_elided` such nodes are removed from the graph,
+/// and the preceding node gets an indirect edge to the node following the run.
+/// Nodes for which `can_elide` returns false are never removed.
+pub fn compact_linear_runs<N, ID: Clone + Eq + Hash>(
+    nodes: Vec<GraphNode<N, ID>>,
+    as_id: impl Fn(&N) -> &ID,
+    can_elide: impl Fn(&N) -> bool,
+    min_elided: usize,
+) -> CompactedGraph<N, ID> {
+    let mut num_incoming: HashMap<&ID, usize> = HashMap::new();
+    for target in nodes.iter().flat_map(|(_, edges)| reachable_targets(edges)) {
+        *num_incoming.entry(target).or_default() += 1;
+    }
+    // links[i] is true if nodes[i] and nodes[i + 1] are linearly connected.
+    let links = nodes
+        .windows(2)
+        .map(|pair| {
+            let [(_, edges), (next, _)] = pair else {
+                unreachable!()
+            };
+            let next_id = as_id(next);
+            matches!(edges.as_slice(), [edge] if edge.is_direct() && edge.target == *next_id)
+                && num_incoming.get(next_id) == Some(&1)
+        })
+        .collect_vec();
+    let elidable = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, (node, _))| {
+            i > 0 && links[i - 1] && links.get(i).copied().unwrap_or(false) && can_elide(node)
+        })
+        .collect_vec();
+
+    let mut output: Vec<GraphNode<N, ID>> = vec![];
+    let mut elided_runs = HashMap::new();
+    let mut run = vec![];
+    for (node, is_elidable) in nodes.into_iter().zip(elidable) {
+        if is_elidable {
+            run.push(node);
+            continue;
+        }
+        if run.len() >= min_elided {
+            // The first node is never elidable, so there must be a preceding node.
+            let (_, prev_edges) = output.last_mut().unwrap();
+            let target = as_id(&node.0).clone();
+            *prev_edges = vec![GraphEdge::indirect(target.clone())];
+            let run_nodes = run.drain(..).map(|(node, _)| node).collect();
+            elided_runs.insert(target, run_nodes);
+        } else {
+            output.append(&mut run);
+        }
+        output.push(node);
+    }
+    (output, elided_runs)
+}
+
 /// Graph iterator adapter to group topological branches.
 ///
 /// Basic idea is DFS from the heads. At fork point, the other descendant
@@ -1877,4 +1943,65 @@ mod tests {
         assert!(iter.next().is_none());
         assert!(iter.emittable_ids.is_empty());
     }
+
+    #[test]
+    fn test_compact_linear_runs() {
+        let graph = vec![
+            ('G', vec![direct('F')]),
+            ('F', vec![direct('E')]),
+            ('E', vec![direct('D')]),
+            ('D', vec![direct('C')]),
+            ('C', vec![direct('B')]),
+            ('B', vec![direct('A')]),
+            ('A', vec![]),
+        ];
+        let (compacted, elided) = compact_linear_runs(graph.clone(), |c| c, |_| true, 2);
+        insta::assert_snapshot!(format_graph(compacted.into_iter().map(Ok)), @r"
+        G  indirect(A)
+        ╷
+        A
+        ");
+        assert_eq!(
+            elided,
+            HashMap::from([('A', vec!['F', 'E', 'D', 'C', 'B'])])
+        );
+
+        // Unelidable node splits the run
+        let (compacted, elided) = compact_linear_runs(graph.clone(), |c| c, |&c| c != 'D', 2);
+        insta::assert_snapshot!(format_graph(compacted.into_iter().map(Ok)), @r"
+        G  indirect(D)
+        ╷
+        D  indirect(A)
+        ╷
+        A
+        ");
+        assert_eq!(
+            elided,
+            HashMap::from([('D', vec!['F', 'E']), ('A', vec!['C', 'B'])])
+        );
+
+        // Runs shorter than the threshold are kept
+        let (compacted, elided) = compact_linear_runs(graph, |c| c, |&c| c != 'D', 3);
+        assert_eq!(
+            compacted.iter().map(|(c, _)| *c).collect_vec(),
+            "GFEDCBA".chars().collect_vec()
+        );
+        assert!(elided.is_empty());
+    }
+
+    #[test]
+    fn test_compact_linear_runs_fork_and_merge() {
+        // Nodes adjacent to forks and merges aren't part of linear runs
+        let graph = vec![
+            ('F', vec![direct('E')]),
+            ('E', vec![direct('D'), direct('B')]),
+            ('D', vec![direct('C')]),
+            ('C', vec![direct('B')]),
+            ('B', vec![direct('A')]),
+            ('A', vec![]),
+        ];
+        let (compacted, elided) = compact_linear_runs(graph.clone(), |c| c, |_| true, 1);
+        assert_eq!(compacted, graph);
+        assert!(elided.is_empty());
+    }
 }
@@ -27,6 +27,7 @@ use std::io::Read as _;
 use std::io::Write as _;
 use std::iter;
 use std::mem;
+use std::num::NonZeroUsize;
 use std::ops::Range;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt as _;
@@ -66,8 +67,10 @@ use crate::backend::TreeId;
 use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::config::ConfigGetError;
+use crate::config::ConfigGetResultExt as _;
 use crate::conflicts;
 use crate::conflicts::ConflictMarkerStyle;
+use crate::conflicts::ConflictMarkerStyleOverrides;
 use crate::conflicts::ConflictMaterializeOptions;
 use crate::conflicts::MIN_CONFLICT_MARKER_LEN;
 use crate::conflicts::MaterializedTreeValue;
@@ -84,10 +87,18 @@ use crate::file_util::try_symlink;
 use crate::fsmonitor::FsmonitorSettings;
 #[cfg(feature = "watchman")]
 use crate::fsmonitor::WatchmanConfig;
+#[cfg(all(feature = "notify", unix))]
+use crate::fsmonitor::builtin;
 #[cfg(feature = "watchman")]
 use crate::fsmonitor::watchman;
+use crate::git_lfs::GitLfsSettings;
+use crate::git_lfs::LfsObjectStore;
+use crate::gitattributes::ContentAttributes;
+use crate::gitattributes::GitAttributesFile;
+use crate::gitattributes::TreeGitAttributes;
 use crate::gitignore::GitIgnoreFile;
 use crate::lock::FileLock;
+use crate::matchers::ConeMatcher;
 use crate::matchers::DifferenceMatcher;
 use crate::matchers::EverythingMatcher;
 use crate::matchers::FilesMatcher;
@@ -118,6 +129,7 @@ use crate::working_copy::SnapshotError;
 use crate::working_copy::SnapshotOptions;
 use crate::working_copy::SnapshotProgress;
 use crate::working_copy::SnapshotStats;
+use crate::working_copy::SparseMode;
 use crate::working_copy::UntrackedReason;
 use crate::working_copy::WorkingCopy;
 use crate::working_copy::WorkingCopyFactory;
@@ -526,6 +538,16 @@ fn sparse_patterns_from_proto(
     sparse_patterns
 }
 
+fn new_sparse_matcher(
+    sparse_patterns: &[RepoPathBuf],
+    sparse_mode: SparseMode,
+) -> Box<dyn Matcher> {
+    match sparse_mode {
+        SparseMode::Prefix => Box::new(PrefixMatcher::new(sparse_patterns)),
+        SparseMode::Cone => Box::new(ConeMatcher::new(sparse_patterns)),
+    }
+}
+
 /// Creates intermediate directories from the `working_copy_path` to the
 /// `repo_path` parent. Returns disk path for the `repo_path` file.
 ///
@@ -802,12 +824,21 @@ pub struct TreeStateSettings {
     /// Conflict marker style to use when materializing files or when checking
     /// changed files.
     pub conflict_marker_style: ConflictMarkerStyle,
+    /// Conflict marker styles to use instead of `conflict_marker_style` for
+    /// specific files.
+    pub conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
     /// Configuring auto-converting CRLF line endings into LF when you add a
     /// file to the backend, and vice versa when it checks out code onto your
     /// filesystem.
     pub eol_conversion_mode: EolConversionMode,
     /// The fsmonitor (e.g. Watchman) to use, if any.
     pub fsmonitor_settings: FsmonitorSettings,
+    /// Settings for converting files with the `filter=lfs` attribute, or
+    /// `None` if Git LFS support is disabled.
+    pub git_lfs_settings: Option<GitLfsSettings>,
+    /// Number of threads to use when snapshotting or checking out files, or
+    /// `None` to use one per CPU.
+    pub jobs: Option<NonZeroUsize>,
 }
 
 impl TreeStateSettings {
@@ -815,20 +846,38 @@ impl TreeStateSettings {
     pub fn try_from_user_settings(user_settings: &UserSettings) -> Result<Self, ConfigGetError> {
         Ok(Self {
             conflict_marker_style: user_settings.get("ui.conflict-marker-style")?,
+            conflict_marker_style_overrides: ConflictMarkerStyleOverrides::from_settings(
+                user_settings,
+            )?,
             eol_conversion_mode: EolConversionMode::try_from_settings(user_settings)?,
             fsmonitor_settings: FsmonitorSettings::from_settings(user_settings)?,
+            git_lfs_settings: GitLfsSettings::from_settings(user_settings)?,
+            jobs: user_settings.get("working-copy.jobs").optional()?,
         })
     }
 }
 
+/// Returns the LFS object store of the Git repository backing `store`, if any.
+fn lfs_object_store(store: &Store, settings: &GitLfsSettings) -> Option<LfsObjectStore> {
+    #[cfg(feature = "git")]
+    if let Some(git_backend) = store.backend_impl::<crate::git_backend::GitBackend>() {
+        return Some(LfsObjectStore::new(
+            git_backend.git_repo_path().to_owned(),
+            settings.executable_path.clone(),
+        ));
+    }
+    let _ = (store, settings);
+    None
+}
+
 pub struct TreeState {
     store: Arc<Store>,
     working_copy_path: PathBuf,
     state_path: PathBuf,
     tree: MergedTree,
     file_states: FileStatesMap,
-    // Currently only path prefixes
     sparse_patterns: Vec<RepoPathBuf>,
+    sparse_mode: SparseMode,
     own_mtime: MillisSinceEpoch,
     symlink_support: bool,
 
@@ -838,8 +887,11 @@ pub struct TreeState {
     watchman_clock: Option<crate::protos::local_working_copy::WatchmanClock>,
 
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
     fsmonitor_settings: FsmonitorSettings,
     target_eol_strategy: TargetEolStrategy,
+    lfs_store: Option<LfsObjectStore>,
+    jobs: Option<NonZeroUsize>,
 }
 
 #[derive(Debug, Error)]
@@ -876,8 +928,29 @@ impl TreeState {
         &self.sparse_patterns
     }
 
+    pub fn sparse_mode(&self) -> SparseMode {
+        self.sparse_mode
+    }
+
     fn sparse_matcher(&self) -> Box<dyn Matcher> {
-        Box::new(PrefixMatcher::new(&self.sparse_patterns))
+        new_sparse_matcher(&self.sparse_patterns, self.sparse_mode)
+    }
+
+    /// Runs `op` on the current thread with a scope for spawning jobs onto a
+    /// thread pool of the configured size.
+    fn in_place_scope<'scope, R>(
+        &self,
+        op: impl FnOnce(&rayon::Scope<'scope>) -> R,
+    ) -> Result<R, rayon::ThreadPoolBuildError> {
+        match self.jobs {
+            None => Ok(rayon::in_place_scope(op)),
+            Some(jobs) => {
+                let thread_pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(jobs.get())
+                    .build()?;
+                Ok(thread_pool.in_place_scope(op))
+            }
+        }
     }
 
     pub fn init(
@@ -897,10 +970,16 @@ impl TreeState {
         state_path: PathBuf,
         &TreeStateSettings {
             conflict_marker_style,
+            ref conflict_marker_style_overrides,
             eol_conversion_mode,
             ref fsmonitor_settings,
+            ref git_lfs_settings,
+            jobs,
         }: &TreeStateSettings,
     ) -> Self {
+        let lfs_store = git_lfs_settings
+            .as_ref()
+            .and_then(|settings| lfs_object_store(&store, settings));
         Self {
             store: store.clone(),
             working_copy_path,
@@ -908,12 +987,16 @@ impl TreeState {
             tree: store.empty_merged_tree(),
             file_states: FileStatesMap::new(),
             sparse_patterns: vec![RepoPathBuf::root()],
+            sparse_mode: SparseMode::Prefix,
             own_mtime: MillisSinceEpoch(0),
             symlink_support: check_symlink_support().unwrap_or(false),
             watchman_clock: None,
             conflict_marker_style,
+            conflict_marker_style_overrides: conflict_marker_style_overrides.clone(),
             fsmonitor_settings: fsmonitor_settings.clone(),
             target_eol_strategy: TargetEolStrategy::new(eol_conversion_mode),
+            lfs_store,
+            jobs,
         }
     }
 
@@ -981,6 +1064,15 @@ impl TreeState {
         self.file_states =
             FileStatesMap::from_proto(proto.file_states, proto.is_file_states_sorted);
         self.sparse_patterns = sparse_patterns_from_proto(proto.sparse_patterns.as_ref());
+        self.sparse_mode = if proto
+            .sparse_patterns
+            .as_ref()
+            .is_some_and(|proto| proto.cone)
+        {
+            SparseMode::Cone
+        } else {
+            SparseMode::Prefix
+        };
         self.watchman_clock = proto.watchman_clock;
         Ok(())
     }
@@ -1003,6 +1095,7 @@ impl TreeState {
                 .prefixes
                 .push(path.as_internal_file_string().to_owned());
         }
+        sparse_patterns.cone = self.sparse_mode == SparseMode::Cone;
         proto.sparse_patterns = Some(sparse_patterns);
         proto.watchman_clock = self.watchman_clock.clone();
 
@@ -1052,6 +1145,16 @@ impl TreeState {
         Ok(changed_files)
     }
 
+    #[cfg(all(feature = "notify", unix))]
+    #[instrument(skip(self))]
+    pub fn query_builtin_fsmonitor(
+        &self,
+    ) -> Result<(builtin::Clock, Option<Vec<PathBuf>>), TreeStateError> {
+        let previous_clock = self.watchman_clock.clone().map(builtin::Clock::from);
+        builtin::query_changed_files(&self.working_copy_path, previous_clock)
+            .map_err(|err| TreeStateError::Fsmonitor(Box::new(err)))
+    }
+
     #[cfg(feature = "watchman")]
     #[tokio::main(flavor = "current_thread")]
     #[instrument(skip(self))]
@@ -1131,14 +1234,19 @@ impl TreeState {
                 dir: RepoPathBuf::root(),
                 disk_dir: self.working_copy_path.clone(),
                 git_ignore: base_ignores.clone(),
+                git_attributes: GitAttributesFile::empty(),
                 file_states: self.file_states.all(),
             };
             // Here we use scope as a queue of per-directory jobs.
-            rayon::scope(|scope| {
+            self.in_place_scope(|scope| {
                 snapshotter.spawn_ok(scope, |scope| {
                     snapshotter.visit_directory(directory_to_visit, scope)
                 });
-            });
+            })
+            .map_err(|err| SnapshotError::Other {
+                message: "Failed to start snapshot threads".to_string(),
+                err: err.into(),
+            })?;
             snapshotter.into_result()
         })?;
 
@@ -1220,6 +1328,24 @@ impl TreeState {
                         .into(),
                 });
             }
+            #[cfg(all(feature = "notify", unix))]
+            FsmonitorSettings::Builtin(_) => match self.query_builtin_fsmonitor() {
+                Ok((clock, changed_files)) => (Some(clock.into()), changed_files),
+                Err(err) => {
+                    tracing::warn!(?err, "Failed to query filesystem monitor");
+                    (None, None)
+                }
+            },
+            #[cfg(not(all(feature = "notify", unix)))]
+            FsmonitorSettings::Builtin(_) => {
+                return Err(SnapshotError::Other {
+                    message: "Failed to query the filesystem monitor".to_string(),
+                    err: "The built-in filesystem monitor requires a Unix platform and a jj \
+                          compiled with the `notify` feature (consider disabling \
+                          `fsmonitor.backend`)"
+                        .into(),
+                });
+            }
         };
         let matcher: Option<Box<dyn Matcher>> = match changed_files {
             None => None,
@@ -1231,7 +1357,14 @@ impl TreeState {
                         .collect_vec()
                 });
 
-                Some(Box::new(FilesMatcher::new(repo_paths)))
+                if matches!(fsmonitor_settings, FsmonitorSettings::Builtin(_)) {
+                    // The built-in monitor reports directories that were
+                    // created, removed, or renamed as a whole, so everything
+                    // under the reported paths has to be checked.
+                    Some(Box::new(PrefixMatcher::new(repo_paths)))
+                } else {
+                    Some(Box::new(FilesMatcher::new(repo_paths)))
+                }
             }
         };
         Ok(FsmonitorMatcher {
@@ -1245,6 +1378,7 @@ struct DirectoryToVisit<'a> {
     dir: RepoPathBuf,
     disk_dir: PathBuf,
     git_ignore: Arc<GitIgnoreFile>,
+    git_attributes: Arc<GitAttributesFile>,
     file_states: FileStates<'a>,
 }
 
@@ -1311,11 +1445,16 @@ impl FileSnapshotter<'_> {
             dir,
             disk_dir,
             git_ignore,
+            git_attributes,
             file_states,
         } = directory_to_visit;
 
         let git_ignore = git_ignore
             .chain_with_file(&dir.to_internal_dir_string(), disk_dir.join(".gitignore"))?;
+        let git_attributes = git_attributes.chain_with_file(
+            &dir.to_internal_dir_string(),
+            disk_dir.join(".gitattributes"),
+        )?;
         let dir_entries: Vec<_> = disk_dir
             .read_dir()
             .and_then(|entries| entries.try_collect())
@@ -1329,8 +1468,15 @@ impl FileSnapshotter<'_> {
             // sequential scan should be fast enough.
             .with_min_len(100)
             .filter_map(|entry| {
-                self.process_dir_entry(&dir, &git_ignore, file_states, &entry, scope)
-                    .transpose()
+                self.process_dir_entry(
+                    &dir,
+                    &git_ignore,
+                    &git_attributes,
+                    file_states,
+                    &entry,
+                    scope,
+                )
+                .transpose()
             })
             .map(|item| match item {
                 Ok((PresentDirEntryKind::Dir, name)) => Ok(Either::Left(name)),
@@ -1347,6 +1493,7 @@ impl FileSnapshotter<'_> {
         &'scope self,
         dir: &RepoPath,
         git_ignore: &Arc<GitIgnoreFile>,
+        git_attributes: &Arc<GitAttributesFile>,
         file_states: FileStates<'scope>,
         entry: &DirEntry,
         scope: &rayon::Scope<'scope>,
@@ -1379,12 +1526,16 @@ impl FileSnapshotter<'_> {
                 // ignored directory must be ignored. It's also more efficient.
                 // start_tracking_matcher is NOT tested here because we need to
                 // scan directory entries to report untracked paths.
-                self.spawn_ok(scope, move |_| self.visit_tracked_files(file_states));
+                let git_attributes = git_attributes.clone();
+                self.spawn_ok(scope, move |_| {
+                    self.visit_tracked_files(&git_attributes, file_states)
+                });
             } else if !self.matcher.visit(&path).is_nothing() {
                 let directory_to_visit = DirectoryToVisit {
                     dir: path,
                     disk_dir: entry.path(),
                     git_ignore: git_ignore.clone(),
+                    git_attributes: git_attributes.clone(),
                     file_states,
                 };
                 self.spawn_ok(scope, |scope| {
@@ -1433,6 +1584,7 @@ impl FileSnapshotter<'_> {
                     self.process_present_file(
                         path,
                         &entry.path(),
+                        git_attributes,
                         maybe_current_file_state.as_ref(),
                         new_file_state,
                     )?;
@@ -1448,7 +1600,11 @@ impl FileSnapshotter<'_> {
     }
 
     /// Visits only paths we're already tracking.
-    fn visit_tracked_files(&self, file_states: FileStates<'_>) -> Result<(), SnapshotError> {
+    fn visit_tracked_files(
+        &self,
+        git_attributes: &GitAttributesFile,
+        file_states: FileStates<'_>,
+    ) -> Result<(), SnapshotError> {
         for (tracked_path, current_file_state) in file_states {
             if current_file_state.file_type == FileType::GitSubmodule {
                 continue;
@@ -1471,6 +1627,7 @@ impl FileSnapshotter<'_> {
                 self.process_present_file(
                     tracked_path.to_owned(),
                     &disk_path,
+                    git_attributes,
                     Some(&current_file_state),
                     new_file_state,
                 )?;
@@ -1485,12 +1642,14 @@ impl FileSnapshotter<'_> {
         &self,
         path: RepoPathBuf,
         disk_path: &Path,
+        git_attributes: &GitAttributesFile,
         maybe_current_file_state: Option<&FileState>,
         mut new_file_state: FileState,
     ) -> Result<(), SnapshotError> {
         let update = self.get_updated_tree_value(
             &path,
             disk_path,
+            git_attributes,
             maybe_current_file_state,
             &new_file_state,
         )?;
@@ -1547,6 +1706,7 @@ impl FileSnapshotter<'_> {
         &self,
         repo_path: &RepoPath,
         disk_path: &Path,
+        git_attributes: &GitAttributesFile,
         maybe_current_file_state: Option<&FileState>,
         new_file_state: &FileState,
     ) -> Result<Option<MergedTreeValue>, SnapshotError> {
@@ -1582,6 +1742,7 @@ impl FileSnapshotter<'_> {
                     .write_path_to_store(
                         repo_path,
                         disk_path,
+                        &git_attributes.content_attributes(repo_path.as_internal_file_string()),
                         &current_tree_values,
                         executable,
                         maybe_current_file_state.and_then(|state| state.materialized_conflict_data),
@@ -1611,12 +1772,15 @@ impl FileSnapshotter<'_> {
         &self,
         repo_path: &RepoPath,
         disk_path: &Path,
+        attributes: &ContentAttributes,
         current_tree_values: &MergedTreeValue,
         executable: FileExecutableFlag,
         materialized_conflict_data: Option<MaterializedConflictData>,
     ) -> Result<MergedTreeValue, SnapshotError> {
         if let Some(current_tree_value) = current_tree_values.as_resolved() {
-            let id = self.write_file_to_store(repo_path, disk_path).await?;
+            let id = self
+                .write_file_to_store(repo_path, disk_path, attributes)
+                .await?;
             // On Windows, we preserve the executable bit from the current tree.
             let executable = executable.unwrap_or_else(|| {
                 if let Some(TreeValue::File {
@@ -1663,7 +1827,7 @@ impl FileSnapshotter<'_> {
             })?;
             self.tree_state
                 .target_eol_strategy
-                .convert_eol_for_snapshot(BlockingAsyncReader::new(file))
+                .convert_eol_for_snapshot(BlockingAsyncReader::new(file), attributes)
                 .await
                 .map_err(|err| SnapshotError::Other {
                     message: "Failed to convert the EOL".to_string(),
@@ -1721,7 +1885,26 @@ impl FileSnapshotter<'_> {
         &self,
         path: &RepoPath,
         disk_path: &Path,
+        attributes: &ContentAttributes,
     ) -> Result<FileId, SnapshotError> {
+        if let Some(lfs_store) = self.tree_state.lfs_store.as_ref()
+            && attributes.is_lfs()
+        {
+            let content = fs::read(disk_path).map_err(|err| SnapshotError::Other {
+                message: format!("Failed to read file {}", disk_path.display()),
+                err: err.into(),
+            })?;
+            let pointer = lfs_store
+                .clean(content)
+                .map_err(|err| SnapshotError::Other {
+                    message: format!("Failed to store {} in Git LFS", disk_path.display()),
+                    err: err.into(),
+                })?;
+            return Ok(self
+                .store()
+                .write_file(path, &mut pointer.as_slice())
+                .await?);
+        }
         let file = File::open(disk_path).map_err(|err| SnapshotError::Other {
             message: format!("Failed to open file {}", disk_path.display()),
             err: err.into(),
@@ -1729,7 +1912,7 @@ impl FileSnapshotter<'_> {
         let mut contents = self
             .tree_state
             .target_eol_strategy
-            .convert_eol_for_snapshot(BlockingAsyncReader::new(file))
+            .convert_eol_for_snapshot(BlockingAsyncReader::new(file), attributes)
             .await
             .map_err(|err| SnapshotError::Other {
                 message: "Failed to convert the EOL".to_string(),
@@ -1776,7 +1959,7 @@ impl TreeState {
         disk_path: &Path,
         contents: impl AsyncRead + Send + Unpin,
         executable: bool,
-        apply_eol_conversion: bool,
+        eol_attributes: Option<&ContentAttributes>,
     ) -> Result<FileState, CheckoutError> {
         let mut file = File::options()
             .write(true)
@@ -1786,9 +1969,9 @@ impl TreeState {
                 message: format!("Failed to open file {} for writing", disk_path.display()),
                 err: err.into(),
             })?;
-        let contents = if apply_eol_conversion {
+        let contents = if let Some(attributes) = eol_attributes {
             self.target_eol_strategy
-                .convert_eol_for_update(contents)
+                .convert_eol_for_update(contents, attributes)
                 .await
                 .map_err(|err| CheckoutError::Other {
                     message: "Failed to convert the EOL for the content".to_string(),
@@ -1817,6 +2000,25 @@ impl TreeState {
         Ok(FileState::for_file(executable, size as u64, &metadata))
     }
 
+    /// Replaces LFS pointer `content` with the object content. If the object
+    /// can't be fetched, the pointer is written as is, and it will be
+    /// snapshotted unchanged.
+    fn smudge_lfs_file(
+        &self,
+        lfs_store: &LfsObjectStore,
+        path: &RepoPath,
+        content: Vec<u8>,
+    ) -> Vec<u8> {
+        let disk_path = path.to_fs_path_unchecked(Path::new(""));
+        match lfs_store.smudge(&disk_path, content.clone()) {
+            Ok(object) => object,
+            Err(err) => {
+                tracing::warn!(?err, ?path, "failed to fetch Git LFS object");
+                content
+            }
+        }
+    }
+
     fn write_symlink(&self, disk_path: &Path, target: String) -> Result<FileState, CheckoutError> {
         let target = PathBuf::from(&target);
         try_symlink(&target, disk_path).map_err(|err| CheckoutError::Other {
@@ -1838,10 +2040,11 @@ impl TreeState {
         disk_path: &Path,
         contents: &[u8],
         executable: bool,
+        attributes: &ContentAttributes,
     ) -> Result<FileState, CheckoutError> {
         let contents = self
             .target_eol_strategy
-            .convert_eol_for_update(contents)
+            .convert_eol_for_update(contents, attributes)
             .await
             .map_err(|err| CheckoutError::Other {
                 message: "Failed to convert the EOL when writing a merge conflict".to_string(),
@@ -1881,9 +2084,7 @@ impl TreeState {
 
     pub fn check_out(&mut self, new_tree: &MergedTree) -> Result<CheckoutStats, CheckoutError> {
         let old_tree = self.tree.clone();
-        let stats = self
-            .update(&old_tree, new_tree, self.sparse_matcher().as_ref())
-            .block_on()?;
+        let stats = self.update(&old_tree, new_tree, self.sparse_matcher().as_ref())?;
         self.tree = new_tree.clone();
         Ok(stats)
     }
@@ -1891,18 +2092,25 @@ impl TreeState {
     pub fn set_sparse_patterns(
         &mut self,
         sparse_patterns: Vec<RepoPathBuf>,
+    ) -> Result<CheckoutStats, CheckoutError> {
+        self.set_sparse_patterns_with_mode(sparse_patterns, self.sparse_mode)
+    }
+
+    pub fn set_sparse_patterns_with_mode(
+        &mut self,
+        sparse_patterns: Vec<RepoPathBuf>,
+        sparse_mode: SparseMode,
     ) -> Result<CheckoutStats, CheckoutError> {
         let tree = self.tree.clone();
-        let old_matcher = PrefixMatcher::new(&self.sparse_patterns);
-        let new_matcher = PrefixMatcher::new(&sparse_patterns);
+        let old_matcher = self.sparse_matcher();
+        let new_matcher = new_sparse_matcher(&sparse_patterns, sparse_mode);
         let added_matcher = DifferenceMatcher::new(&new_matcher, &old_matcher);
         let removed_matcher = DifferenceMatcher::new(&old_matcher, &new_matcher);
         let empty_tree = self.store.empty_merged_tree();
-        let added_stats = self.update(&empty_tree, &tree, &added_matcher).block_on()?;
-        let removed_stats = self
-            .update(&tree, &empty_tree, &removed_matcher)
-            .block_on()?;
+        let added_stats = self.update(&empty_tree, &tree, &added_matcher)?;
+        let removed_stats = self.update(&tree, &empty_tree, &removed_matcher)?;
         self.sparse_patterns = sparse_patterns;
+        self.sparse_mode = sparse_mode;
         assert_eq!(added_stats.updated_files, 0);
         assert_eq!(added_stats.removed_files, 0);
         assert_eq!(removed_stats.updated_files, 0);
@@ -1916,7 +2124,7 @@ impl TreeState {
         })
     }
 
-    async fn update(
+    fn update(
         &mut self,
         old_tree: &MergedTree,
         new_tree: &MergedTree,
@@ -1932,123 +2140,197 @@ impl TreeState {
         };
         let mut changed_file_states = Vec::new();
         let mut deleted_files = HashSet::new();
-        let mut diff_stream = old_tree
-            .diff_stream_for_file_system(new_tree, matcher)
-            .map(async |TreeDiffEntry { path, values }| match values {
-                Ok(diff) => {
-                    let result = materialize_tree_value(&self.store, &path, diff.after).await;
-                    (path, result.map(|value| (diff.before, value)))
-                }
-                Err(err) => (path, Err(err)),
-            })
-            .buffered(self.store.concurrency().max(1));
-        while let Some((path, data)) = diff_stream.next().await {
-            let (before, after) = data?;
-            if after.is_absent() {
-                stats.removed_files += 1;
-            } else if before.is_absent() {
-                stats.added_files += 1;
-            } else {
-                stats.updated_files += 1;
-            }
+        // Directories that files are being written to by jobs. They shouldn't
+        // be removed even if they look empty.
+        let mut written_dirs = HashSet::new();
+        let git_attributes = TreeGitAttributes::new(new_tree.clone());
+        let (written_files_tx, written_files_rx) = channel();
+        // Files are read from the store and checked on this thread, and then
+        // written to disk by jobs on the thread pool.
+        let this = &*self;
+        let result = this
+            .in_place_scope(|scope| -> Result<(), CheckoutError> {
+                let mut diff_stream = old_tree
+                    .diff_stream_for_file_system(new_tree, matcher)
+                    .map(async |TreeDiffEntry { path, values }| match values {
+                        Ok(diff) => {
+                            let result =
+                                materialize_tree_value(&this.store, &path, diff.after).await;
+                            (path, result.map(|value| (diff.before, value)))
+                        }
+                        Err(err) => (path, Err(err)),
+                    })
+                    .buffered(this.store.concurrency().max(1));
+                while let Some((path, data)) = diff_stream.next().block_on() {
+                    let (before, after) = data?;
+                    if after.is_absent() {
+                        stats.removed_files += 1;
+                    } else if before.is_absent() {
+                        stats.added_files += 1;
+                    } else {
+                        stats.updated_files += 1;
+                    }
 
-            // Existing Git submodule can be a non-empty directory on disk. We
-            // shouldn't attempt to manage it as a tracked path.
-            //
-            // TODO: It might be better to add general support for paths not
-            // tracked by jj than processing submodules specially. For example,
-            // paths excluded by .gitignore can be marked as such so that
-            // newly-"unignored" paths won't be snapshotted automatically.
-            if matches!(before.as_normal(), Some(TreeValue::GitSubmodule(_)))
-                && matches!(after, MaterializedTreeValue::GitSubmodule(_))
-            {
-                eprintln!("ignoring git submodule at {path:?}");
-                // Not updating the file state as if there were no diffs. Leave
-                // the state type as FileType::GitSubmodule if it was before.
-                continue;
-            }
+                    // Existing Git submodule can be a non-empty directory on disk. We
+                    // shouldn't attempt to manage it as a tracked path.
+                    //
+                    // TODO: It might be better to add general support for paths not
+                    // tracked by jj than processing submodules specially. For example,
+                    // paths excluded by .gitignore can be marked as such so that
+                    // newly-"unignored" paths won't be snapshotted automatically.
+                    if matches!(before.as_normal(), Some(TreeValue::GitSubmodule(_)))
+                        && matches!(after, MaterializedTreeValue::GitSubmodule(_))
+                    {
+                        eprintln!("ignoring git submodule at {path:?}");
+                        // Not updating the file state as if there were no diffs. Leave
+                        // the state type as FileType::GitSubmodule if it was before.
+                        continue;
+                    }
 
-            // Create parent directories no matter if after.is_present(). This
-            // ensures that the path never traverses symlinks.
-            let Some(disk_path) = create_parent_dirs(&self.working_copy_path, &path)? else {
-                changed_file_states.push((path, FileState::placeholder()));
-                stats.skipped_files += 1;
-                continue;
-            };
-            // If the path was present, check reserved path first and delete it.
-            let present_file_deleted = before.is_present() && remove_old_file(&disk_path)?;
-            // If not, create temporary file to test the path validity.
-            if !present_file_deleted && !can_create_new_file(&disk_path)? {
-                changed_file_states.push((path, FileState::placeholder()));
-                stats.skipped_files += 1;
-                continue;
-            }
+                    // Create parent directories no matter if after.is_present(). This
+                    // ensures that the path never traverses symlinks.
+                    let Some(disk_path) = create_parent_dirs(&this.working_copy_path, &path)?
+                    else {
+                        changed_file_states.push((path, FileState::placeholder()));
+                        stats.skipped_files += 1;
+                        continue;
+                    };
+                    // If the path was present, check reserved path first and delete it.
+                    let present_file_deleted = before.is_present() && remove_old_file(&disk_path)?;
+                    // If not, create temporary file to test the path validity.
+                    if !present_file_deleted && !can_create_new_file(&disk_path)? {
+                        changed_file_states.push((path, FileState::placeholder()));
+                        stats.skipped_files += 1;
+                        continue;
+                    }
 
-            // TODO: Check that the file has not changed before overwriting/removing it.
-            let file_state = match after {
-                MaterializedTreeValue::Absent | MaterializedTreeValue::AccessDenied(_) => {
-                    let mut parent_dir = disk_path.parent().unwrap();
-                    loop {
-                        if fs::remove_dir(parent_dir).is_err() {
-                            break;
+                    // TODO: Check that the file has not changed before overwriting/removing it.
+                    let attributes = match after {
+                        MaterializedTreeValue::Absent | MaterializedTreeValue::AccessDenied(_) => {
+                            let mut parent_dir = disk_path.parent().unwrap();
+                            loop {
+                                if written_dirs.contains(parent_dir)
+                                    || fs::remove_dir(parent_dir).is_err()
+                                {
+                                    break;
+                                }
+                                parent_dir = parent_dir.parent().unwrap();
+                            }
+                            deleted_files.insert(path);
+                            continue;
                         }
-                        parent_dir = parent_dir.parent().unwrap();
-                    }
-                    deleted_files.insert(path);
-                    continue;
-                }
-                MaterializedTreeValue::File(file) => {
-                    self.write_file(&disk_path, file.reader, file.executable, true)
-                        .await?
-                }
-                MaterializedTreeValue::Symlink { id: _, target } => {
-                    if self.symlink_support {
-                        self.write_symlink(&disk_path, target)?
-                    } else {
-                        self.write_file(&disk_path, target.as_bytes(), false, false)
-                            .await?
-                    }
-                }
-                MaterializedTreeValue::GitSubmodule(_) => {
-                    eprintln!("ignoring git submodule at {path:?}");
-                    FileState::for_gitsubmodule()
-                }
-                MaterializedTreeValue::Tree(_) => {
-                    panic!("unexpected tree entry in diff at {path:?}");
-                }
-                MaterializedTreeValue::FileConflict(file) => {
-                    let conflict_marker_len =
-                        choose_materialized_conflict_marker_len(&file.contents);
-                    let options = ConflictMaterializeOptions {
-                        marker_style: self.conflict_marker_style,
-                        marker_len: Some(conflict_marker_len),
-                        merge: self.store.merge_options().clone(),
+                        MaterializedTreeValue::File(_) | MaterializedTreeValue::FileConflict(_) => {
+                            git_attributes.content_attributes(&path).block_on()?
+                        }
+                        _ => ContentAttributes::default(),
                     };
-                    let contents = materialize_merge_result_to_bytes(&file.contents, &options);
-                    let mut file_state = self
-                        .write_conflict(&disk_path, &contents, file.executable.unwrap_or(false))
-                        .await?;
-                    file_state.materialized_conflict_data = Some(MaterializedConflictData {
-                        conflict_marker_len: conflict_marker_len.try_into().unwrap_or(u32::MAX),
+                    written_dirs.insert(disk_path.parent().unwrap().to_owned());
+                    let written_files_tx = written_files_tx.clone();
+                    scope.spawn(move |_| {
+                        let result = this
+                            .write_tree_value(&path, &disk_path, after, &attributes)
+                            .block_on();
+                        written_files_tx.send((path, result)).ok();
                     });
-                    file_state
                 }
-                MaterializedTreeValue::OtherConflict { id } => {
-                    // Unless all terms are regular files, we can't do much
-                    // better than trying to describe the merge.
-                    let contents = id.describe();
-                    let executable = false;
-                    self.write_conflict(&disk_path, contents.as_bytes(), executable)
-                        .await?
-                }
-            };
-            changed_file_states.push((path, file_state));
+                Ok(())
+            })
+            .map_err(|err| CheckoutError::Other {
+                message: "Failed to start checkout threads".to_string(),
+                err: err.into(),
+            })?;
+        drop(written_files_tx);
+        result?;
+        for (path, file_state) in written_files_rx {
+            changed_file_states.push((path, file_state?));
         }
         self.file_states
             .merge_in(changed_file_states, &deleted_files);
         Ok(stats)
     }
 
+    /// Writes the materialized `value` of `path` to `disk_path`, which must
+    /// not exist yet.
+    async fn write_tree_value(
+        &self,
+        path: &RepoPath,
+        disk_path: &Path,
+        value: MaterializedTreeValue,
+        attributes: &ContentAttributes,
+    ) -> Result<FileState, CheckoutError> {
+        let file_state = match value {
+            MaterializedTreeValue::Absent | MaterializedTreeValue::AccessDenied(_) => {
+                panic!("absent entry should have been removed at {path:?}");
+            }
+            MaterializedTreeValue::File(mut file) => {
+                if let Some(lfs_store) = self.lfs_store.as_ref()
+                    && attributes.is_lfs()
+                {
+                    let content = file.read_all(path).await?;
+                    let content = self.smudge_lfs_file(lfs_store, path, content);
+                    // LFS objects are binary, so EOL conversion isn't applied.
+                    self.write_file(disk_path, content.as_slice(), file.executable, None)
+                        .await?
+                } else {
+                    self.write_file(disk_path, file.reader, file.executable, Some(attributes))
+                        .await?
+                }
+            }
+            MaterializedTreeValue::Symlink { id: _, target } => {
+                if self.symlink_support {
+                    self.write_symlink(disk_path, target)?
+                } else {
+                    self.write_file(disk_path, target.as_bytes(), false, None)
+                        .await?
+                }
+            }
+            MaterializedTreeValue::GitSubmodule(_) => {
+                eprintln!("ignoring git submodule at {path:?}");
+                FileState::for_gitsubmodule()
+            }
+            MaterializedTreeValue::Tree(_) => {
+                panic!("unexpected tree entry in diff at {path:?}");
+            }
+            MaterializedTreeValue::FileConflict(file) => {
+                let conflict_marker_len = choose_materialized_conflict_marker_len(&file.contents);
+                let options = ConflictMaterializeOptions {
+                    marker_style: self.conflict_marker_style,
+                    marker_style_overrides: self.conflict_marker_style_overrides.clone(),
+                    marker_len: Some(conflict_marker_len),
+                    merge: self.store.merge_options().clone(),
+                }
+                .for_path(path);
+                let contents = materialize_merge_result_to_bytes(&file.contents, &options);
+                let mut file_state = self
+                    .write_conflict(
+                        disk_path,
+                        &contents,
+                        file.executable.unwrap_or(false),
+                        attributes,
+                    )
+                    .await?;
+                file_state.materialized_conflict_data = Some(MaterializedConflictData {
+                    conflict_marker_len: conflict_marker_len.try_into().unwrap_or(u32::MAX),
+                });
+                file_state
+            }
+            MaterializedTreeValue::OtherConflict { id } => {
+                // Unless all terms are regular files, we can't do much
+                // better than trying to describe the merge.
+                let contents = id.describe();
+                let executable = false;
+                self.write_conflict(
+                    disk_path,
+                    contents.as_bytes(),
+                    executable,
+                    &ContentAttributes::default(),
+                )
+                .await?
+            }
+        };
+        Ok(file_state)
+    }
+
     pub async fn reset(&mut self, new_tree: &MergedTree) -> Result<(), ResetError> {
         let matcher = self.sparse_matcher();
         let mut changed_file_states = Vec::new();
@@ -2197,6 +2479,10 @@ impl WorkingCopy for LocalWorkingCopy {
         Ok(self.tree_state()?.sparse_patterns())
     }
 
+    fn sparse_mode(&self) -> Result<SparseMode, WorkingCopyStateError> {
+        Ok(self.tree_state()?.sparse_mode())
+    }
+
     fn start_mutation(&self) -> Result<Box<dyn LockedWorkingCopy>, WorkingCopyStateError> {
         let lock_path = self.state_path.join("working_copy.lock");
         let lock = FileLock::lock(lock_path).map_err(|err| WorkingCopyStateError {
@@ -2472,6 +2758,23 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         Ok(stats)
     }
 
+    fn sparse_mode(&self) -> Result<SparseMode, WorkingCopyStateError> {
+        self.wc.sparse_mode()
+    }
+
+    async fn set_sparse_patterns_with_mode(
+        &mut self,
+        new_sparse_patterns: Vec<RepoPathBuf>,
+        mode: SparseMode,
+    ) -> Result<CheckoutStats, CheckoutError> {
+        let stats = self
+            .wc
+            .tree_state_mut()?
+            .set_sparse_patterns_with_mode(new_sparse_patterns, mode)?;
+        self.tree_state_dirty = true;
+        Ok(stats)
+    }
+
     #[instrument(skip_all)]
     async fn finish(
         mut self: Box<Self>,
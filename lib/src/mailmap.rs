@@ -0,0 +1,272 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for `.mailmap` files, which map the names and email addresses
+//! recorded in commits to canonical identities.
+//!
+//! See <https://git-scm.com/docs/gitmailmap> for the file format.
+
+use std::collections::HashMap;
+
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::Signature;
+use crate::backend::TreeValue;
+use crate::merged_tree::MergedTree;
+use crate::repo_path::RepoPath;
+
+/// Path of the mailmap file relative to the workspace root.
+pub const MAILMAP_PATH: &str = ".mailmap";
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct MailmapEntry {
+    /// Commit name to match (lowercased), or `None` to match any name.
+    commit_name: Option<String>,
+    proper_name: Option<String>,
+    proper_email: Option<String>,
+}
+
+/// Parsed mailmap.
+#[derive(Clone, Debug, Default)]
+pub struct Mailmap {
+    /// Entries indexed by lowercased commit email.
+    entries: HashMap<String, Vec<MailmapEntry>>,
+}
+
+impl Mailmap {
+    /// Creates an empty mailmap that maps every identity to itself.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the mailmap contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Parses the content of a `.mailmap` file. Malformed lines are ignored,
+    /// as Git does.
+    pub fn parse(input: &[u8]) -> Self {
+        let mut mailmap = Self::empty();
+        for line in input.split(|&b| b == b'\n') {
+            let line = String::from_utf8_lossy(line);
+            if let Some((commit_email, entry)) = parse_line(&line) {
+                mailmap.add_entry(commit_email, entry);
+            }
+        }
+        mailmap
+    }
+
+    /// Loads the `.mailmap` file from the root of the given tree. Returns an
+    /// empty mailmap if the file doesn't exist or is conflicted.
+    pub async fn load_from_tree(tree: &MergedTree) -> BackendResult<Self> {
+        let path = RepoPath::from_internal_string(MAILMAP_PATH).unwrap();
+        let Ok(Some(TreeValue::File { id, .. })) =
+            tree.path_value_async(path).await?.into_resolved()
+        else {
+            return Ok(Self::empty());
+        };
+        let mut content = vec![];
+        let mut reader = tree.store().read_file(path, &id).await?;
+        reader
+            .read_to_end(&mut content)
+            .await
+            .map_err(|err| BackendError::ReadFile {
+                path: path.to_owned(),
+                id,
+                source: err.into(),
+            })?;
+        Ok(Self::parse(&content))
+    }
+
+    fn add_entry(&mut self, commit_email: String, entry: MailmapEntry) {
+        let entries = self.entries.entry(commit_email).or_default();
+        // Later lines override earlier ones matching the same identity.
+        if let Some(existing) = entries
+            .iter_mut()
+            .find(|existing| existing.commit_name == entry.commit_name)
+        {
+            if entry.proper_name.is_some() {
+                existing.proper_name = entry.proper_name;
+            }
+            if entry.proper_email.is_some() {
+                existing.proper_email = entry.proper_email;
+            }
+        } else {
+            entries.push(entry);
+        }
+    }
+
+    /// Returns the canonical name and email for the given commit identity.
+    pub fn resolve(&self, name: &str, email: &str) -> (String, String) {
+        let entry = self.entries.get(&email.to_lowercase()).and_then(|entries| {
+            let name = name.to_lowercase();
+            entries
+                .iter()
+                .find(|entry| entry.commit_name.as_ref() == Some(&name))
+                .or_else(|| entries.iter().find(|entry| entry.commit_name.is_none()))
+        });
+        let Some(entry) = entry else {
+            return (name.to_owned(), email.to_owned());
+        };
+        (
+            entry.proper_name.as_deref().unwrap_or(name).to_owned(),
+            entry.proper_email.as_deref().unwrap_or(email).to_owned(),
+        )
+    }
+
+    /// Returns the signature with its name and email replaced by the canonical
+    /// identity. The timestamp is preserved.
+    pub fn map_signature(&self, signature: &Signature) -> Signature {
+        let (name, email) = self.resolve(&signature.name, &signature.email);
+        Signature {
+            name,
+            email,
+            timestamp: signature.timestamp,
+        }
+    }
+}
+
+/// Parses a single mailmap line into the commit email and the mapping entry.
+fn parse_line(line: &str) -> Option<(String, MailmapEntry)> {
+    let line = line.split_once('#').map_or(line, |(line, _)| line);
+    let (name1, email1, rest) = parse_name_and_email(line)?;
+    let (name2, email2) = match parse_name_and_email(rest) {
+        Some((name2, email2, _)) => (name2, Some(email2)),
+        None => (None, None),
+    };
+    let entry = if let Some(email2) = email2 {
+        // "Proper Name <proper@email> [Commit Name] <commit@email>"
+        let entry = MailmapEntry {
+            commit_name: name2.map(|name| name.to_lowercase()),
+            proper_name: name1.map(str::to_owned),
+            proper_email: Some(email1.to_owned()),
+        };
+        (email2.to_lowercase(), entry)
+    } else {
+        // "Proper Name <commit@email>"
+        let entry = MailmapEntry {
+            commit_name: None,
+            proper_name: Some(name1?.to_owned()),
+            proper_email: None,
+        };
+        (email1.to_lowercase(), entry)
+    };
+    Some(entry)
+}
+
+/// Parses `[Name] <email>` from the start of the input, returning the
+/// remainder after the closing `>`.
+fn parse_name_and_email(input: &str) -> Option<(Option<&str>, &str, &str)> {
+    let (name, rest) = input.split_once('<')?;
+    let (email, rest) = rest.split_once('>')?;
+    let name = name.trim();
+    let name = (!name.is_empty()).then_some(name);
+    Some((name, email.trim(), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(mailmap: &Mailmap, name: &str, email: &str) -> (String, String) {
+        mailmap.resolve(name, email)
+    }
+
+    #[test]
+    fn test_empty_mailmap() {
+        let mailmap = Mailmap::parse(b"");
+        assert!(mailmap.is_empty());
+        assert_eq!(
+            resolve(&mailmap, "Some One", "some@example.com"),
+            ("Some One".to_owned(), "some@example.com".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_replace_name() {
+        let mailmap = Mailmap::parse(b"Proper Name <commit@example.com>\n");
+        assert_eq!(
+            resolve(&mailmap, "nick", "Commit@Example.com"),
+            ("Proper Name".to_owned(), "Commit@Example.com".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_replace_email() {
+        let mailmap = Mailmap::parse(b"<proper@example.com> <commit@example.com>\n");
+        assert_eq!(
+            resolve(&mailmap, "nick", "commit@example.com"),
+            ("nick".to_owned(), "proper@example.com".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_replace_name_and_email() {
+        let mailmap = Mailmap::parse(
+            b"# comment\n\
+              Proper Name <proper@example.com> <commit@example.com> # trailing\n",
+        );
+        assert_eq!(
+            resolve(&mailmap, "nick", "commit@example.com"),
+            ("Proper Name".to_owned(), "proper@example.com".to_owned())
+        );
+        assert_eq!(
+            resolve(&mailmap, "nick", "other@example.com"),
+            ("nick".to_owned(), "other@example.com".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_match_commit_name() {
+        let mailmap = Mailmap::parse(
+            b"Alice <alice@example.com> alice <shared@example.com>\n\
+              Bob <bob@example.com> Bob <shared@example.com>\n\
+              Nobody <nobody@example.com> <shared@example.com>\n",
+        );
+        assert_eq!(
+            resolve(&mailmap, "Alice", "shared@example.com"),
+            ("Alice".to_owned(), "alice@example.com".to_owned())
+        );
+        assert_eq!(
+            resolve(&mailmap, "bob", "shared@example.com"),
+            ("Bob".to_owned(), "bob@example.com".to_owned())
+        );
+        assert_eq!(
+            resolve(&mailmap, "Carol", "shared@example.com"),
+            ("Nobody".to_owned(), "nobody@example.com".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_later_line_overrides() {
+        let mailmap = Mailmap::parse(
+            b"Old Name <commit@example.com>\n\
+              <proper@example.com> <commit@example.com>\n\
+              New Name <commit@example.com>\n",
+        );
+        assert_eq!(
+            resolve(&mailmap, "nick", "commit@example.com"),
+            ("New Name".to_owned(), "proper@example.com".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_malformed_lines_are_ignored() {
+        let mailmap = Mailmap::parse(b"no email here\n<only-email@example.com>\n<unterminated\n");
+        assert!(mailmap.is_empty());
+    }
+}
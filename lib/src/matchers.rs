@@ -244,6 +244,51 @@ fn prefix_tree_to_visit_sets(tree: &RepoPathTree<PrefixNodeKind>) -> Visit {
     Visit::sets(dirs, files)
 }
 
+/// Matches everything in the given directories like [`PrefixMatcher`], and
+/// also the files directly in the root directory and in the ancestors of the
+/// given directories. This is the same as Git's sparse-checkout cone mode.
+#[derive(Debug)]
+pub struct ConeMatcher {
+    tree: RepoPathTree<PrefixNodeKind>,
+}
+
+impl ConeMatcher {
+    #[instrument(skip(dirs))]
+    pub fn new(dirs: impl IntoIterator<Item = impl AsRef<RepoPath>>) -> Self {
+        let mut tree = RepoPathTree::default();
+        for dir in dirs {
+            tree.add(dir.as_ref()).value = PrefixNodeKind::Prefix;
+        }
+        Self { tree }
+    }
+}
+
+impl Matcher for ConeMatcher {
+    fn matches(&self, file: &RepoPath) -> bool {
+        self.tree.walk_to(file).any(|(sub, tail_path)| {
+            sub.value == PrefixNodeKind::Prefix || tail_path.components().nth(1).is_none()
+        })
+    }
+
+    fn visit(&self, dir: &RepoPath) -> Visit {
+        for (sub, tail_path) in self.tree.walk_to(dir) {
+            // ancestor of 'dir' matches prefix paths
+            if sub.value == PrefixNodeKind::Prefix {
+                return Visit::AllRecursively;
+            }
+            // 'dir' found, and is an ancestor of prefix paths, so all files in
+            // it match
+            if tail_path.is_root() {
+                return Visit::Specific {
+                    dirs: VisitDirs::Set(sub.entries.keys().cloned().collect()),
+                    files: VisitFiles::All,
+                };
+            }
+        }
+        Visit::Nothing
+    }
+}
+
 /// Matches file or prefix paths with glob patterns.
 #[derive(Clone, Debug)]
 pub struct GlobsMatcher {
@@ -760,6 +805,56 @@ mod tests {
         assert_eq!(m.visit(repo_path("foo/bar/baz")), Visit::AllRecursively);
     }
 
+    #[test]
+    fn test_cone_matcher_empty() {
+        let m = ConeMatcher::new([] as [&RepoPath; 0]);
+        // Files in the root directory are always included
+        assert!(m.matches(repo_path("file")));
+        assert!(!m.matches(repo_path("dir/file")));
+        assert_eq!(
+            m.visit(RepoPath::root()),
+            Visit::Specific {
+                dirs: VisitDirs::Set(hashset! {}),
+                files: VisitFiles::All,
+            }
+        );
+        assert_eq!(m.visit(repo_path("dir")), Visit::Nothing);
+    }
+
+    #[test]
+    fn test_cone_matcher_nested_dir() {
+        let m = ConeMatcher::new([repo_path("foo/bar")]);
+
+        // Files in the root and in ancestor directories match
+        assert!(m.matches(repo_path("file")));
+        assert!(m.matches(repo_path("foo/file")));
+        // Everything in the directory matches
+        assert!(m.matches(repo_path("foo/bar/baz")));
+        assert!(m.matches(repo_path("foo/bar/baz/qux")));
+        // Sibling directories don't match
+        assert!(!m.matches(repo_path("foo/baz/file")));
+        assert!(!m.matches(repo_path("baz/file")));
+
+        assert_eq!(
+            m.visit(RepoPath::root()),
+            Visit::Specific {
+                dirs: VisitDirs::Set(hashset! {repo_path_component_buf("foo")}),
+                files: VisitFiles::All,
+            }
+        );
+        assert_eq!(
+            m.visit(repo_path("foo")),
+            Visit::Specific {
+                dirs: VisitDirs::Set(hashset! {repo_path_component_buf("bar")}),
+                files: VisitFiles::All,
+            }
+        );
+        assert_eq!(m.visit(repo_path("foo/bar")), Visit::AllRecursively);
+        assert_eq!(m.visit(repo_path("foo/bar/baz")), Visit::AllRecursively);
+        assert_eq!(m.visit(repo_path("foo/baz")), Visit::Nothing);
+        assert_eq!(m.visit(repo_path("baz")), Visit::Nothing);
+    }
+
     #[test]
     fn test_file_globs_matcher_rooted() {
         let m = new_file_globs_matcher(&[(RepoPath::root(), glob("*.rs"))]);
@@ -0,0 +1,317 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-path merge drivers configured by `merge.drivers`.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use bstr::BString;
+use itertools::Itertools as _;
+use thiserror::Error;
+
+use crate::config::ConfigGetError;
+use crate::files;
+use crate::files::MergeResult;
+use crate::fileset;
+use crate::matchers::Matcher;
+use crate::merge::Merge;
+use crate::repo_path::RepoPath;
+use crate::settings::UserSettings;
+use crate::tree_merge::MergeOptions;
+
+/// Built-in merge strategy.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeStrategy {
+    /// Keeps the lines from all sides of conflicting hunks.
+    Union,
+    /// Takes the first side of conflicting hunks.
+    Ours,
+    /// Takes the last side of conflicting hunks.
+    Theirs,
+}
+
+/// How the contents of a matched file are merged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeDriverKind {
+    /// Built-in strategy applied to conflicting hunks.
+    Strategy(MergeStrategy),
+    /// External program invoked with `$base`, `$left`, `$right`, `$output`,
+    /// and `$path` substituted in the arguments.
+    Command(Vec<String>),
+}
+
+/// Merge driver applied to files matching the patterns.
+#[derive(Clone)]
+pub struct MergeDriver {
+    /// Name of the driver in the config.
+    pub name: String,
+    /// How the contents are merged.
+    pub kind: MergeDriverKind,
+    matcher: Arc<dyn Matcher>,
+}
+
+impl fmt::Debug for MergeDriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeDriver")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error while running a merge driver.
+#[derive(Debug, Error)]
+pub enum MergeDriverError {
+    /// Failed to set up or spawn the external program.
+    #[error("Failed to run merge driver {name}")]
+    Io {
+        /// Name of the driver.
+        name: String,
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// The external program exited with a non-zero status.
+    #[error("Merge driver {name} exited with {status}")]
+    Failed {
+        /// Name of the driver.
+        name: String,
+        /// Exit status of the program.
+        status: std::process::ExitStatus,
+    },
+}
+
+impl MergeDriver {
+    /// Returns true if the driver applies to the `path`.
+    pub fn matches(&self, path: &RepoPath) -> bool {
+        self.matcher.matches(path)
+    }
+
+    /// Merges the file `contents` at `path`. Returns `None` if the driver
+    /// doesn't support the number of sides.
+    pub fn merge(
+        &self,
+        path: &RepoPath,
+        contents: &Merge<Vec<u8>>,
+        options: &MergeOptions,
+    ) -> Result<Option<BString>, MergeDriverError> {
+        match &self.kind {
+            MergeDriverKind::Strategy(strategy) => {
+                Ok(Some(merge_with_strategy(contents, *strategy, options)))
+            }
+            MergeDriverKind::Command(command) => {
+                let [left, base, right] = contents.as_slice() else {
+                    return Ok(None);
+                };
+                self.run_command(command, path, base, left, right).map(Some)
+            }
+        }
+    }
+
+    fn run_command(
+        &self,
+        command: &[String],
+        path: &RepoPath,
+        base: &[u8],
+        left: &[u8],
+        right: &[u8],
+    ) -> Result<BString, MergeDriverError> {
+        let to_io_err = |source| MergeDriverError::Io {
+            name: self.name.clone(),
+            source,
+        };
+        let temp_dir = tempfile::Builder::new()
+            .prefix("jj-merge-driver-")
+            .tempdir()
+            .map_err(to_io_err)?;
+        let write = |name: &str, contents: &[u8]| {
+            let file_path = temp_dir.path().join(name);
+            fs::write(&file_path, contents)?;
+            io::Result::Ok(file_path.to_string_lossy().into_owned())
+        };
+        let base_path = write("base", base).map_err(to_io_err)?;
+        let left_path = write("left", left).map_err(to_io_err)?;
+        let right_path = write("right", right).map_err(to_io_err)?;
+        // The output starts with the left side so drivers can edit it in place.
+        let output_path = write("output", left).map_err(to_io_err)?;
+        let args = command
+            .iter()
+            .map(|arg| {
+                arg.replace("$base", &base_path)
+                    .replace("$left", &left_path)
+                    .replace("$right", &right_path)
+                    .replace("$output", &output_path)
+                    .replace("$path", path.as_internal_file_string())
+            })
+            .collect_vec();
+        let (program, args) = args.split_first().ok_or_else(|| {
+            to_io_err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "empty merge driver command",
+            ))
+        })?;
+        tracing::info!(?program, ?args, "running merge driver");
+        let status = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .status()
+            .map_err(to_io_err)?;
+        if !status.success() {
+            return Err(MergeDriverError::Failed {
+                name: self.name.clone(),
+                status,
+            });
+        }
+        let output = fs::read(&output_path).map_err(to_io_err)?;
+        Ok(output.into())
+    }
+}
+
+/// Merges `contents`, resolving conflicting hunks with the `strategy`.
+pub fn merge_with_strategy<T: AsRef<[u8]>>(
+    contents: &Merge<T>,
+    strategy: MergeStrategy,
+    options: &MergeOptions,
+) -> BString {
+    match files::merge_hunks(contents, options) {
+        MergeResult::Resolved(content) => content,
+        MergeResult::Conflict(hunks) => {
+            let mut content = BString::default();
+            for hunk in &hunks {
+                if let Some(resolved) = hunk.as_resolved() {
+                    content.extend_from_slice(resolved);
+                    continue;
+                }
+                match strategy {
+                    MergeStrategy::Union => {
+                        for side in hunk.adds() {
+                            content.extend_from_slice(side);
+                        }
+                    }
+                    MergeStrategy::Ours => content.extend_from_slice(hunk.first()),
+                    MergeStrategy::Theirs => {
+                        content.extend_from_slice(hunk.adds().last().unwrap());
+                    }
+                }
+            }
+            content
+        }
+    }
+}
+
+/// Merge drivers loaded from the `merge.drivers` config table.
+#[derive(Clone, Debug, Default)]
+pub struct MergeDrivers {
+    drivers: Vec<MergeDriver>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawMergeDriverConfig {
+    patterns: Vec<String>,
+    strategy: Option<MergeStrategy>,
+    command: Option<Vec<String>>,
+}
+
+impl MergeDrivers {
+    /// Loads merge drivers from `settings`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let drivers = settings
+            .table_keys("merge.drivers")
+            // Sort keys so the first matching driver is deterministic.
+            .sorted()
+            .map(|name| {
+                let config_name = format!("merge.drivers.{name}");
+                let config_error =
+                    |error: Box<dyn std::error::Error + Send + Sync>| ConfigGetError::Type {
+                        name: config_name.clone(),
+                        error,
+                        source_path: None,
+                    };
+                let raw: RawMergeDriverConfig = settings.get(["merge", "drivers", name])?;
+                let kind = match (raw.strategy, raw.command) {
+                    (Some(strategy), None) => MergeDriverKind::Strategy(strategy),
+                    (None, Some(command)) if !command.is_empty() => {
+                        MergeDriverKind::Command(command)
+                    }
+                    (None, Some(_)) => {
+                        return Err(config_error("command must not be empty".into()));
+                    }
+                    _ => {
+                        return Err(config_error(
+                            "exactly one of strategy or command must be set".into(),
+                        ));
+                    }
+                };
+                let expression = fileset::parse_config_patterns(&raw.patterns)
+                    .map_err(|err| config_error(err.into()))?;
+                Ok(MergeDriver {
+                    name: name.to_owned(),
+                    kind,
+                    matcher: expression.to_matcher().into(),
+                })
+            })
+            .try_collect()?;
+        Ok(Self { drivers })
+    }
+
+    /// Returns true if no drivers are configured.
+    pub fn is_empty(&self) -> bool {
+        self.drivers.is_empty()
+    }
+
+    /// Finds the first driver that applies to the `path`.
+    pub fn find(&self, path: &RepoPath) -> Option<&MergeDriver> {
+        self.drivers.iter().find(|driver| driver.matches(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::FileMergeHunkLevel;
+    use crate::merge::SameChange;
+
+    fn options() -> MergeOptions {
+        MergeOptions {
+            hunk_level: FileMergeHunkLevel::Line,
+            same_change: SameChange::Accept,
+            drivers: MergeDrivers::default(),
+        }
+    }
+
+    #[test]
+    fn test_merge_with_strategy() {
+        let contents = Merge::from_vec(vec!["a\nB\nc\nd\n", "a\nb\nc\n", "a\nX\nc\n"]);
+        let merge = |strategy| merge_with_strategy(&contents, strategy, &options());
+        assert_eq!(merge(MergeStrategy::Union), "a\nB\nX\nc\nd\n");
+        assert_eq!(merge(MergeStrategy::Ours), "a\nB\nc\nd\n");
+        assert_eq!(merge(MergeStrategy::Theirs), "a\nX\nc\nd\n");
+    }
+
+    #[test]
+    fn test_merge_with_strategy_resolved() {
+        let contents = Merge::from_vec(vec!["A\nb\nc\n", "a\nb\nc\n", "a\nb\nC\n"]);
+        assert_eq!(
+            merge_with_strategy(&contents, MergeStrategy::Ours, &options()),
+            "A\nb\nC\n"
+        );
+    }
+}
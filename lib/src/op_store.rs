@@ -16,6 +16,7 @@
 
 use std::any::Any;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -31,6 +32,7 @@ use crate::backend::CommitId;
 use crate::backend::MillisSinceEpoch;
 use crate::backend::Timestamp;
 use crate::content_hash::ContentHash;
+use crate::content_hash::DigestUpdate;
 use crate::merge::Merge;
 use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId as _;
@@ -262,6 +264,49 @@ pub struct View {
     // (.jj/working_copy/) has the source of truth about which commit *is* checked out (to be
     // precise: the commit to which we most recently completed an update to).
     pub wc_commit_ids: BTreeMap<WorkspaceNameBuf, CommitId>,
+    /// Local bookmarks hidden from the default listing.
+    pub archived_bookmarks: ArchivedBookmarks,
+    /// Named changes set aside from the working copy.
+    pub stashes: Stashes,
+}
+
+/// Names of archived local bookmarks.
+///
+/// Unlike other collections, an empty set doesn't contribute to the content
+/// hash, so the ids of views without archived bookmarks are the same as before
+/// archiving was introduced.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ArchivedBookmarks(pub BTreeSet<RefNameBuf>);
+
+impl ContentHash for ArchivedBookmarks {
+    fn hash(&self, state: &mut impl DigestUpdate) {
+        if !self.0.is_empty() {
+            state.update(&(self.0.len() as u64).to_le_bytes());
+            for name in &self.0 {
+                name.hash(state);
+            }
+        }
+    }
+}
+
+/// Named stashes pointing to hidden commits.
+///
+/// The stashed commits aren't visible heads, but they are retained as long as
+/// the stash exists. Like [`ArchivedBookmarks`], an empty map doesn't
+/// contribute to the content hash.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stashes(pub BTreeMap<RefNameBuf, RefTarget>);
+
+impl ContentHash for Stashes {
+    fn hash(&self, state: &mut impl DigestUpdate) {
+        if !self.0.is_empty() {
+            state.update(&(self.0.len() as u64).to_le_bytes());
+            for (name, target) in &self.0 {
+                name.hash(state);
+                target.hash(state);
+            }
+        }
+    }
 }
 
 impl View {
@@ -275,6 +320,8 @@ impl View {
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
             wc_commit_ids: BTreeMap::new(),
+            archived_bookmarks: ArchivedBookmarks::default(),
+            stashes: Stashes::default(),
         }
     }
 }
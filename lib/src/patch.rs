@@ -0,0 +1,839 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing and application of patches in the unified diff format, as produced
+//! by `git diff` and `diff -u`.
+
+use bstr::ByteSlice as _;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::CopyId;
+use crate::backend::TreeValue;
+use crate::merge::Merge;
+use crate::merged_tree::MergedTree;
+use crate::merged_tree::MergedTreeBuilder;
+use crate::repo_path::RepoPathBuf;
+
+/// Error while parsing a patch.
+#[derive(Debug, Error)]
+#[error("Malformed patch at line {line}: {message}")]
+pub struct PatchParseError {
+    /// 1-based line number in the patch.
+    pub line: usize,
+    /// What is wrong with the line.
+    pub message: String,
+}
+
+/// A line in a [`Hunk`], including its line terminator if any.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HunkLine {
+    /// A line that is kept.
+    Context(Vec<u8>),
+    /// A line that is only in the old file.
+    Removed(Vec<u8>),
+    /// A line that is only in the new file.
+    Added(Vec<u8>),
+}
+
+impl HunkLine {
+    fn content_mut(&mut self) -> &mut Vec<u8> {
+        match self {
+            Self::Context(content) | Self::Removed(content) | Self::Added(content) => content,
+        }
+    }
+}
+
+/// A `@@ -a,b +c,d @@` section of a file patch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hunk {
+    /// 1-based line number of the first line in the old file, or the line
+    /// before if the range is empty.
+    pub old_start: usize,
+    /// 1-based line number of the first line in the new file, or the line
+    /// before if the range is empty.
+    pub new_start: usize,
+    /// Lines of the hunk.
+    pub lines: Vec<HunkLine>,
+}
+
+impl Hunk {
+    fn old_lines(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines.iter().filter_map(|line| match line {
+            HunkLine::Context(content) | HunkLine::Removed(content) => Some(content.as_slice()),
+            HunkLine::Added(_) => None,
+        })
+    }
+
+    fn new_lines(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines.iter().filter_map(|line| match line {
+            HunkLine::Context(content) | HunkLine::Added(content) => Some(content.as_slice()),
+            HunkLine::Removed(_) => None,
+        })
+    }
+}
+
+/// Changes to a single file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FilePatch {
+    /// Path of the file before the change, or `None` if the file is created.
+    pub old_path: Option<RepoPathBuf>,
+    /// Path of the file after the change, or `None` if the file is deleted.
+    pub new_path: Option<RepoPathBuf>,
+    /// Whether the old file is kept in place when renamed, as in a copy.
+    pub is_copy: bool,
+    /// Old executable bit of the file, if recorded in the patch.
+    pub old_executable: Option<bool>,
+    /// New executable bit of the file, if set by the patch.
+    pub executable: Option<bool>,
+    /// Whether the patch changes binary content, which can't be applied.
+    pub is_binary: bool,
+    /// Content changes.
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// Returns the patch that undoes this patch.
+    ///
+    /// The reverse of a copy deletes the copied file.
+    pub fn reversed(&self) -> Self {
+        let hunks = self
+            .hunks
+            .iter()
+            .map(|hunk| Hunk {
+                old_start: hunk.new_start,
+                new_start: hunk.old_start,
+                lines: hunk
+                    .lines
+                    .iter()
+                    .map(|line| match line {
+                        HunkLine::Context(content) => HunkLine::Context(content.clone()),
+                        HunkLine::Removed(content) => HunkLine::Added(content.clone()),
+                        HunkLine::Added(content) => HunkLine::Removed(content.clone()),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self {
+            old_path: self.new_path.clone(),
+            new_path: if self.is_copy {
+                None
+            } else {
+                self.old_path.clone()
+            },
+            is_copy: false,
+            old_executable: self.executable,
+            executable: self.old_executable,
+            is_binary: self.is_binary,
+            hunks,
+        }
+    }
+
+    /// The path the patch is reported by.
+    pub fn path(&self) -> &RepoPathBuf {
+        self.new_path
+            .as_ref()
+            .or(self.old_path.as_ref())
+            .expect("file patch should have a path")
+    }
+}
+
+/// Parses the file patches in `input`, ignoring any text between them.
+///
+/// `strip` is the number of leading path components to remove from the paths
+/// in the patch, like `patch -p<strip>`. Git patches use `1`, which removes
+/// the `a/` and `b/` prefixes.
+pub fn parse_patch(input: &[u8], strip: usize) -> Result<Vec<FilePatch>, PatchParseError> {
+    let mut parser = PatchParser {
+        lines: input.lines_with_terminator().enumerate().peekable(),
+        strip,
+    };
+    let mut patches = vec![];
+    while let Some((index, line)) = parser.lines.next() {
+        if let Some(header) = line.strip_prefix(b"diff --git ") {
+            patches.push(parser.parse_git_file_patch(index, header)?);
+        } else if line.starts_with(b"--- ")
+            && parser
+                .lines
+                .peek()
+                .is_some_and(|(_, next)| next.starts_with(b"+++ "))
+        {
+            let mut patch = FilePatch::default();
+            parser.parse_paths_and_hunks(index, line, &mut patch)?;
+            patches.push(patch);
+        }
+    }
+    Ok(patches)
+}
+
+struct PatchParser<'a, I: Iterator<Item = (usize, &'a [u8])>> {
+    lines: std::iter::Peekable<I>,
+    strip: usize,
+}
+
+impl<'a, I: Iterator<Item = (usize, &'a [u8])>> PatchParser<'a, I> {
+    fn parse_git_file_patch(
+        &mut self,
+        index: usize,
+        header: &[u8],
+    ) -> Result<FilePatch, PatchParseError> {
+        let mut patch = FilePatch::default();
+        // Paths in the header are ambiguous if they contain spaces. They are
+        // only used if there are no `---`/`+++` or rename lines.
+        let header = trim_line(header);
+        if let Some((old, new)) = header.split_once_str(" b/") {
+            let new = [b"b/".as_slice(), new].concat();
+            patch.old_path = Some(self.parse_path(index, old, self.strip)?);
+            patch.new_path = Some(self.parse_path(index, &new, self.strip)?);
+        }
+        while let Some(&(index, line)) = self.lines.peek() {
+            let text = trim_line(line);
+            if text.starts_with(b"diff --git ") {
+                break;
+            }
+            self.lines.next();
+            if let Some(mode) = text.strip_prefix(b"new file mode ") {
+                patch.old_path = None;
+                patch.executable = Some(parse_executable(index, mode)?);
+            } else if let Some(mode) = text.strip_prefix(b"deleted file mode ") {
+                patch.new_path = None;
+                patch.old_executable = Some(parse_executable(index, mode)?);
+            } else if let Some(mode) = text.strip_prefix(b"old mode ") {
+                patch.old_executable = Some(parse_executable(index, mode)?);
+            } else if let Some(mode) = text.strip_prefix(b"new mode ") {
+                patch.executable = Some(parse_executable(index, mode)?);
+            } else if let Some(path) = text.strip_prefix(b"rename from ") {
+                patch.old_path = Some(self.parse_path(index, path, 0)?);
+            } else if let Some(path) = text.strip_prefix(b"rename to ") {
+                patch.new_path = Some(self.parse_path(index, path, 0)?);
+            } else if let Some(path) = text.strip_prefix(b"copy from ") {
+                patch.old_path = Some(self.parse_path(index, path, 0)?);
+                patch.is_copy = true;
+            } else if let Some(path) = text.strip_prefix(b"copy to ") {
+                patch.new_path = Some(self.parse_path(index, path, 0)?);
+            } else if text.starts_with(b"Binary files ") || text == b"GIT binary patch" {
+                patch.is_binary = true;
+            } else if text.starts_with(b"--- ") {
+                self.parse_paths_and_hunks(index, line, &mut patch)?;
+                break;
+            } else if text.starts_with(b"@@ ") {
+                return Err(PatchParseError {
+                    line: index + 1,
+                    message: "hunk without file header".to_owned(),
+                });
+            }
+            // Other lines such as "index" and "similarity index"
+            // don't affect how the patch is applied.
+        }
+        if patch.old_path.is_none() && patch.new_path.is_none() {
+            return Err(PatchParseError {
+                line: index + 1,
+                message: "missing file name".to_owned(),
+            });
+        }
+        Ok(patch)
+    }
+
+    /// Parses `--- old` and `+++ new` lines followed by hunks.
+    fn parse_paths_and_hunks(
+        &mut self,
+        index: usize,
+        old_line: &[u8],
+        patch: &mut FilePatch,
+    ) -> Result<(), PatchParseError> {
+        let old = trim_line(&old_line[4..]);
+        let Some((new_index, new_line)) = self.lines.next_if(|(_, line)| line.starts_with(b"+++ "))
+        else {
+            return Err(PatchParseError {
+                line: index + 2,
+                message: "expected `+++` line".to_owned(),
+            });
+        };
+        let new = trim_line(&new_line[4..]);
+        patch.old_path = self.parse_patch_path(index, old)?;
+        patch.new_path = self.parse_patch_path(new_index, new)?;
+        if patch.old_path.is_none() && patch.new_path.is_none() {
+            return Err(PatchParseError {
+                line: index + 1,
+                message: "both files are /dev/null".to_owned(),
+            });
+        }
+        while let Some((index, line)) = self.lines.next_if(|(_, line)| line.starts_with(b"@@ ")) {
+            patch.hunks.push(self.parse_hunk(index, line)?);
+        }
+        Ok(())
+    }
+
+    fn parse_hunk(&mut self, index: usize, header: &[u8]) -> Result<Hunk, PatchParseError> {
+        let invalid_header = || PatchParseError {
+            line: index + 1,
+            message: "invalid hunk header".to_owned(),
+        };
+        let ranges = header[3..]
+            .split_str(" @@")
+            .next()
+            .ok_or_else(invalid_header)?;
+        let (old_range, new_range) = ranges
+            .to_str()
+            .ok()
+            .and_then(|ranges| ranges.split_once(' '))
+            .ok_or_else(invalid_header)?;
+        let (old_start, mut old_len) = old_range
+            .strip_prefix('-')
+            .and_then(parse_hunk_range)
+            .ok_or_else(invalid_header)?;
+        let (new_start, mut new_len) = new_range
+            .strip_prefix('+')
+            .and_then(parse_hunk_range)
+            .ok_or_else(invalid_header)?;
+        let mut lines: Vec<HunkLine> = vec![];
+        while old_len > 0 || new_len > 0 {
+            let Some((index, line)) = self.lines.next() else {
+                return Err(PatchParseError {
+                    line: index + lines.len() + 2,
+                    message: "unexpected end of hunk".to_owned(),
+                });
+            };
+            let (sigil, content) = match line.split_first() {
+                Some((&sigil, content)) if sigil != b'\n' && sigil != b'\r' => (sigil, content),
+                // Some tools strip the trailing whitespace of empty context
+                // lines
+                _ => (b' ', line),
+            };
+            let content = content.to_vec();
+            match sigil {
+                b' ' if old_len > 0 && new_len > 0 => {
+                    old_len -= 1;
+                    new_len -= 1;
+                    lines.push(HunkLine::Context(content));
+                }
+                b'-' if old_len > 0 => {
+                    old_len -= 1;
+                    lines.push(HunkLine::Removed(content));
+                }
+                b'+' if new_len > 0 => {
+                    new_len -= 1;
+                    lines.push(HunkLine::Added(content));
+                }
+                b'\\' => self.strip_last_newline(&mut lines),
+                _ => {
+                    return Err(PatchParseError {
+                        line: index + 1,
+                        message: "hunk doesn't match the line counts in its header".to_owned(),
+                    });
+                }
+            }
+        }
+        if self
+            .lines
+            .next_if(|(_, line)| line.starts_with(b"\\"))
+            .is_some()
+        {
+            self.strip_last_newline(&mut lines);
+        }
+        Ok(Hunk {
+            old_start,
+            new_start,
+            lines,
+        })
+    }
+
+    /// Applies a `\ No newline at end of file` marker to the preceding line.
+    fn strip_last_newline(&self, lines: &mut [HunkLine]) {
+        if let Some(line) = lines.last_mut() {
+            let content = line.content_mut();
+            if content.ends_with(b"\n") {
+                content.pop();
+                if content.ends_with(b"\r") {
+                    content.pop();
+                }
+            }
+        }
+    }
+
+    /// Parses the path of a `---` or `+++` line, returning `None` for
+    /// `/dev/null`.
+    fn parse_patch_path(
+        &self,
+        index: usize,
+        path: &[u8],
+    ) -> Result<Option<RepoPathBuf>, PatchParseError> {
+        // Traditional diffs may have a timestamp after a tab
+        let path = path.split_str("\t").next().unwrap();
+        if path == b"/dev/null" {
+            return Ok(None);
+        }
+        self.parse_path(index, path, self.strip).map(Some)
+    }
+
+    fn parse_path(
+        &self,
+        index: usize,
+        path: &[u8],
+        strip: usize,
+    ) -> Result<RepoPathBuf, PatchParseError> {
+        let invalid_path = |message: &str| PatchParseError {
+            line: index + 1,
+            message: format!("{message}: {}", path.to_str_lossy()),
+        };
+        if path.starts_with(b"\"") {
+            return Err(invalid_path("quoted paths are not supported"));
+        }
+        let path = path
+            .to_str()
+            .map_err(|_| invalid_path("invalid path"))?
+            .trim_end();
+        let stripped = path.splitn(strip + 1, '/').nth(strip).ok_or_else(|| {
+            invalid_path(&format!("can't strip {strip} leading components from path"))
+        })?;
+        RepoPathBuf::from_internal_string(stripped).map_err(|_| invalid_path("invalid path"))
+    }
+}
+
+fn trim_line(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn parse_executable(index: usize, mode: &[u8]) -> Result<bool, PatchParseError> {
+    match mode {
+        b"100644" => Ok(false),
+        b"100755" => Ok(true),
+        _ => Err(PatchParseError {
+            line: index + 1,
+            message: format!("unsupported file mode {}", mode.to_str_lossy()),
+        }),
+    }
+}
+
+fn parse_hunk_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// Applies `hunks` to `content`, returning the new content and the indices of
+/// the hunks that couldn't be applied.
+///
+/// Like `patch`, a hunk is applied at the closest position to the line
+/// numbers in its header where its context and removed lines match. The
+/// offset at which a hunk was applied carries over to the following hunks.
+pub fn apply_hunks(content: &[u8], hunks: &[Hunk]) -> (Vec<u8>, Vec<usize>) {
+    let old_lines = content.lines_with_terminator().collect::<Vec<_>>();
+    let mut new_content = Vec::with_capacity(content.len());
+    let mut rejected = vec![];
+    // Position in old_lines up to which the content was copied
+    let mut copied = 0;
+    // Difference between the expected and the actual positions of hunks
+    let mut offset: isize = 0;
+    for (hunk_index, hunk) in hunks.iter().enumerate() {
+        let expected = hunk.old_lines().collect::<Vec<_>>();
+        let start = if expected.is_empty() {
+            // The range is empty, so old_start is the line before
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let start = start.saturating_add_signed(offset);
+        let matches_at = |pos: usize| {
+            pos >= copied
+                && pos + expected.len() <= old_lines.len()
+                && old_lines[pos..pos + expected.len()] == expected[..]
+        };
+        let search_range = old_lines.len().max(start) + 1;
+        let Some(pos) = (0..search_range)
+            .flat_map(|distance| [start.checked_add(distance), start.checked_sub(distance)])
+            .flatten()
+            .find(|&pos| matches_at(pos))
+        else {
+            rejected.push(hunk_index);
+            continue;
+        };
+        for line in &old_lines[copied..pos] {
+            new_content.extend_from_slice(line);
+        }
+        for line in hunk.new_lines() {
+            new_content.extend_from_slice(line);
+        }
+        copied = pos + expected.len();
+        offset = pos as isize - (start as isize - offset);
+    }
+    for line in &old_lines[copied..] {
+        new_content.extend_from_slice(line);
+    }
+    (new_content, rejected)
+}
+
+/// Why a [`FilePatch`] couldn't be applied, fully or partially.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PatchRejectionReason {
+    /// The file to change doesn't exist.
+    Missing,
+    /// The file to create already exists.
+    AlreadyExists,
+    /// The file is a conflict, a symlink, or otherwise not a regular file.
+    NotAFile,
+    /// The patch contains binary changes.
+    Binary,
+    /// The hunks with these indices didn't match the content of the file.
+    Hunks(Vec<usize>),
+}
+
+/// A [`FilePatch`] that couldn't be applied, fully or partially.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatchRejection {
+    /// Index of the file patch.
+    pub index: usize,
+    /// Path of the file.
+    pub path: RepoPathBuf,
+    /// Why the patch couldn't be applied.
+    pub reason: PatchRejectionReason,
+}
+
+/// Result of [`apply_patch_to_tree()`].
+#[derive(Debug)]
+pub struct PatchApplication {
+    /// The tree with the applied changes.
+    pub tree: MergedTree,
+    /// File patches that couldn't be applied. If a file patch has rejected
+    /// hunks, the other hunks of the file are applied.
+    pub rejections: Vec<PatchRejection>,
+}
+
+/// Applies `patches` to the files in `tree`.
+pub async fn apply_patch_to_tree(
+    tree: &MergedTree,
+    patches: &[FilePatch],
+) -> BackendResult<PatchApplication> {
+    let store = tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(tree.clone());
+    let mut rejections = vec![];
+    for (index, patch) in patches.iter().enumerate() {
+        let reject = |reason| PatchRejection {
+            index,
+            path: patch.path().clone(),
+            reason,
+        };
+        if patch.is_binary {
+            rejections.push(reject(PatchRejectionReason::Binary));
+            continue;
+        }
+        // Read the old file, or start from an empty file if it's created
+        let (old_content, old_executable, copy_id) = match &patch.old_path {
+            Some(path) => match tree.path_value_async(path).await?.into_resolved() {
+                Ok(None) => {
+                    rejections.push(reject(PatchRejectionReason::Missing));
+                    continue;
+                }
+                Ok(Some(TreeValue::File {
+                    id,
+                    executable,
+                    copy_id,
+                })) => {
+                    let mut content = vec![];
+                    let mut reader = store.read_file(path, &id).await?;
+                    reader.read_to_end(&mut content).await.map_err(|err| {
+                        BackendError::ReadFile {
+                            path: path.clone(),
+                            id,
+                            source: err.into(),
+                        }
+                    })?;
+                    (content, executable, copy_id)
+                }
+                _ => {
+                    rejections.push(reject(PatchRejectionReason::NotAFile));
+                    continue;
+                }
+            },
+            None => (vec![], false, CopyId::placeholder()),
+        };
+        if let Some(new_path) = &patch.new_path
+            && patch.old_path.as_ref() != Some(new_path)
+            && tree.path_value_async(new_path).await?.is_present()
+        {
+            rejections.push(reject(PatchRejectionReason::AlreadyExists));
+            continue;
+        }
+        let (new_content, rejected_hunks) = apply_hunks(&old_content, &patch.hunks);
+        if !rejected_hunks.is_empty() {
+            rejections.push(reject(PatchRejectionReason::Hunks(rejected_hunks)));
+        }
+        if let Some(old_path) = &patch.old_path
+            && !patch.is_copy
+            && patch.new_path.as_ref() != Some(old_path)
+        {
+            tree_builder.set_or_remove(old_path.clone(), Merge::absent());
+        }
+        if let Some(new_path) = &patch.new_path {
+            let id = store
+                .write_file(new_path, &mut new_content.as_slice())
+                .await?;
+            let value = TreeValue::File {
+                id,
+                executable: patch.executable.unwrap_or(old_executable),
+                copy_id,
+            };
+            tree_builder.set_or_remove(new_path.clone(), Merge::normal(value));
+        }
+    }
+    Ok(PatchApplication {
+        tree: tree_builder.write_tree()?,
+        rejections,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn repo_path(value: &str) -> RepoPathBuf {
+        RepoPathBuf::from_internal_string(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_git_patch() {
+        let patch = indoc! {b"
+            Some description
+
+            diff --git a/file b/file
+            index 257cc56..5716ca5 100644
+            --- a/file
+            +++ b/file
+            @@ -1,2 +1,2 @@
+             a
+            -b
+            +c
+            diff --git a/new b/new
+            new file mode 100755
+            index 0000000..e69de29
+            diff --git a/old b/renamed
+            similarity index 100%
+            rename from old
+            rename to renamed
+            diff --git a/gone b/gone
+            deleted file mode 100644
+            --- a/gone
+            +++ /dev/null
+            @@ -1 +0,0 @@
+            -x
+            \\ No newline at end of file
+        "};
+        let patches = parse_patch(patch, 1).unwrap();
+        assert_eq!(
+            patches,
+            vec![
+                FilePatch {
+                    old_path: Some(repo_path("file")),
+                    new_path: Some(repo_path("file")),
+                    hunks: vec![Hunk {
+                        old_start: 1,
+                        new_start: 1,
+                        lines: vec![
+                            HunkLine::Context(b"a\n".to_vec()),
+                            HunkLine::Removed(b"b\n".to_vec()),
+                            HunkLine::Added(b"c\n".to_vec()),
+                        ],
+                    }],
+                    ..Default::default()
+                },
+                FilePatch {
+                    old_path: None,
+                    new_path: Some(repo_path("new")),
+                    executable: Some(true),
+                    ..Default::default()
+                },
+                FilePatch {
+                    old_path: Some(repo_path("old")),
+                    new_path: Some(repo_path("renamed")),
+                    ..Default::default()
+                },
+                FilePatch {
+                    old_path: Some(repo_path("gone")),
+                    new_path: None,
+                    old_executable: Some(false),
+                    hunks: vec![Hunk {
+                        old_start: 1,
+                        new_start: 0,
+                        lines: vec![HunkLine::Removed(b"x".to_vec())],
+                    }],
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_plain_patch() {
+        let patch = indoc! {b"
+            --- dir/file\t2001-02-03 04:05:06
+            +++ dir/file\t2001-02-03 04:05:07
+            @@ -1,2 +1,3 @@
+             a
+
+            +b
+        "};
+        assert_eq!(
+            parse_patch(patch, 0).unwrap(),
+            vec![FilePatch {
+                old_path: Some(repo_path("dir/file")),
+                new_path: Some(repo_path("dir/file")),
+                hunks: vec![Hunk {
+                    old_start: 1,
+                    new_start: 1,
+                    lines: vec![
+                        HunkLine::Context(b"a\n".to_vec()),
+                        HunkLine::Context(b"\n".to_vec()),
+                        HunkLine::Added(b"b\n".to_vec()),
+                    ],
+                }],
+                ..Default::default()
+            }]
+        );
+        assert_eq!(
+            parse_patch(patch, 1).unwrap()[0].old_path,
+            Some(repo_path("file"))
+        );
+        insta::assert_snapshot!(
+            parse_patch(patch, 2).unwrap_err(),
+            @"Malformed patch at line 1: can't strip 2 leading components from path: dir/file"
+        );
+    }
+
+    #[test]
+    fn test_parse_patch_errors() {
+        let truncated = indoc! {b"
+            --- a/file
+            +++ b/file
+            @@ -1,2 +1,2 @@
+             a
+        "};
+        insta::assert_snapshot!(
+            parse_patch(truncated, 1).unwrap_err(),
+            @"Malformed patch at line 5: unexpected end of hunk"
+        );
+        let miscounted = indoc! {b"
+            --- a/file
+            +++ b/file
+            @@ -1 +1 @@
+            -a
+            -b
+        "};
+        insta::assert_snapshot!(
+            parse_patch(miscounted, 1).unwrap_err(),
+            @"Malformed patch at line 5: hunk doesn't match the line counts in its header"
+        );
+        let bad_header = indoc! {b"
+            --- a/file
+            +++ b/file
+            @@ -1 +x @@
+        "};
+        insta::assert_snapshot!(
+            parse_patch(bad_header, 1).unwrap_err(),
+            @"Malformed patch at line 3: invalid hunk header"
+        );
+    }
+
+    #[test]
+    fn test_apply_hunks() {
+        let patch = indoc! {b"
+            --- a/file
+            +++ b/file
+            @@ -2,3 +2,3 @@
+             b
+            -c
+            +C
+             d
+            @@ -7,2 +7,3 @@
+             g
+             h
+            +i
+        "};
+        let hunks = &parse_patch(patch, 1).unwrap()[0].hunks;
+
+        // Exact position
+        let (content, rejected) = apply_hunks(b"a\nb\nc\nd\ne\nf\ng\nh\n", hunks);
+        assert_eq!(content.to_str_lossy(), "a\nb\nC\nd\ne\nf\ng\nh\ni\n");
+        assert!(rejected.is_empty());
+
+        // Shifted by lines added at the start and removed in the middle
+        let (content, rejected) = apply_hunks(b"0\n0\na\nb\nc\nd\ng\nh\n", hunks);
+        assert_eq!(content.to_str_lossy(), "0\n0\na\nb\nC\nd\ng\nh\ni\n");
+        assert!(rejected.is_empty());
+
+        // Second hunk doesn't match
+        let (content, rejected) = apply_hunks(b"a\nb\nc\nd\ne\nf\ng\nx\n", hunks);
+        assert_eq!(content.to_str_lossy(), "a\nb\nC\nd\ne\nf\ng\nx\n");
+        assert_eq!(rejected, vec![1]);
+    }
+
+    #[test]
+    fn test_reversed_patch() {
+        let patch = indoc! {b"
+            diff --git a/old b/new
+            old mode 100644
+            new mode 100755
+            rename from old
+            rename to new
+            --- a/old
+            +++ b/new
+            @@ -1,2 +1,2 @@
+             a
+            -b
+            +c
+        "};
+        let patch = &parse_patch(patch, 1).unwrap()[0];
+        assert_eq!(
+            patch.reversed(),
+            FilePatch {
+                old_path: Some(repo_path("new")),
+                new_path: Some(repo_path("old")),
+                old_executable: Some(true),
+                executable: Some(false),
+                hunks: vec![Hunk {
+                    old_start: 1,
+                    new_start: 1,
+                    lines: vec![
+                        HunkLine::Context(b"a\n".to_vec()),
+                        HunkLine::Added(b"b\n".to_vec()),
+                        HunkLine::Removed(b"c\n".to_vec()),
+                    ],
+                }],
+                ..Default::default()
+            }
+        );
+        assert_eq!(&patch.reversed().reversed(), patch);
+    }
+
+    #[test]
+    fn test_apply_hunks_to_empty_file() {
+        let patch = indoc! {b"
+            --- /dev/null
+            +++ b/file
+            @@ -0,0 +1,2 @@
+            +a
+            +b
+            \\ No newline at end of file
+        "};
+        let hunks = &parse_patch(patch, 1).unwrap()[0].hunks;
+        let (content, rejected) = apply_hunks(b"", hunks);
+        assert_eq!(content.to_str_lossy(), "a\nb");
+        assert!(rejected.is_empty());
+    }
+}
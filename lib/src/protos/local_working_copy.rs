@@ -27,6 +27,10 @@ pub struct FileStateEntry {
 pub struct SparsePatterns {
     #[prost(string, repeated, tag = "1")]
     pub prefixes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Whether the files in the ancestor directories of the prefixes are
+    /// included as well (Git's cone mode)
+    #[prost(bool, tag = "2")]
+    pub cone: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TreeState {
@@ -137,6 +137,12 @@ pub struct View {
     /// Whether "@git" tags have been migrated to remote_views.
     #[prost(bool, tag = "12")]
     pub has_git_refs_migrated_to_remote_tags: bool,
+    /// Names of archived local bookmarks.
+    #[prost(string, repeated, tag = "13")]
+    pub archived_bookmarks: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Named stashes of working-copy changes.
+    #[prost(message, repeated, tag = "14")]
+    pub stashes: ::prost::alloc::vec::Vec<Tag>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RemoteView {
@@ -954,6 +954,12 @@ impl MutableRepo {
         // `self.rewritten_commits`
     }
 
+    /// Mapping from commits created or rewritten in this transaction to their
+    /// direct predecessors.
+    pub(crate) fn commit_predecessors(&self) -> &BTreeMap<CommitId, Vec<CommitId>> {
+        &self.commit_predecessors
+    }
+
     pub(crate) fn set_predecessors(&mut self, id: CommitId, predecessors: Vec<CommitId>) {
         self.commit_predecessors.insert(id, predecessors);
     }
@@ -1692,6 +1698,10 @@ impl MutableRepo {
         Ok(())
     }
 
+    pub fn set_bookmark_archived(&mut self, name: &RefName, archived: bool) {
+        self.view_mut().set_bookmark_archived(name, archived);
+    }
+
     pub fn get_remote_bookmark(&self, symbol: RemoteRefSymbol<'_>) -> RemoteRef {
         self.view
             .with_ref(|v| v.get_remote_bookmark(symbol).clone())
@@ -1767,6 +1777,28 @@ impl MutableRepo {
         Ok(())
     }
 
+    pub fn get_stash(&self, name: &RefName) -> RefTarget {
+        self.view.with_ref(|v| v.get_stash(name).clone())
+    }
+
+    pub fn set_stash_target(&mut self, name: &RefName, target: RefTarget) {
+        self.view_mut().set_stash_target(name, target);
+    }
+
+    pub fn merge_stash(
+        &mut self,
+        name: &RefName,
+        base_target: &RefTarget,
+        other_target: &RefTarget,
+    ) -> IndexResult<()> {
+        let view = self.view.get_mut();
+        let index = self.index.as_index();
+        let self_target = view.get_stash(name);
+        let new_target = merge_ref_targets(index, self_target, base_target, other_target)?;
+        view.set_stash_target(name, new_target);
+        Ok(())
+    }
+
     pub fn get_remote_tag(&self, symbol: RemoteRefSymbol<'_>) -> RemoteRef {
         self.view.with_ref(|v| v.get_remote_tag(symbol).clone())
     }
@@ -1882,11 +1914,23 @@ impl MutableRepo {
             self.merge_local_bookmark(name, base_target, other_target)?;
         }
 
+        let base_archived: HashSet<&RefName> = base.archived_bookmarks().collect();
+        let other_archived: HashSet<&RefName> = other.archived_bookmarks().collect();
+        for &name in base_archived.symmetric_difference(&other_archived) {
+            let archived = other_archived.contains(name);
+            self.set_bookmark_archived(name, archived);
+        }
+
         let changed_local_tags = diff_named_ref_targets(base.local_tags(), other.local_tags());
         for (name, (base_target, other_target)) in changed_local_tags {
             self.merge_local_tag(name, base_target, other_target)?;
         }
 
+        let changed_stashes = diff_named_ref_targets(base.stashes(), other.stashes());
+        for (name, (base_target, other_target)) in changed_stashes {
+            self.merge_stash(name, base_target, other_target)?;
+        }
+
         let changed_git_refs = diff_named_ref_targets(base.git_refs(), other.git_refs());
         for (name, (base_target, other_target)) in changed_git_refs {
             self.merge_git_ref(name, base_target, other_target)?;
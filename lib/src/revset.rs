@@ -153,12 +153,21 @@ pub enum RevsetCommitRef {
     ChangeId(HexPrefix),
     CommitId(HexPrefix),
     Bookmarks(StringExpression),
+    ArchivedBookmarks(StringExpression),
     RemoteBookmarks {
         bookmark: StringExpression,
         remote: StringExpression,
         remote_ref_state: Option<RemoteRefState>,
     },
+    /// Commits on local bookmarks that aren't on their tracked remote
+    /// bookmarks if `ahead`, or the other way around.
+    TrackedRemoteBookmarkRanges {
+        bookmark: StringExpression,
+        remote: StringExpression,
+        ahead: bool,
+    },
     Tags(StringExpression),
+    Stashes(StringExpression),
     GitRefs,
     GitHead,
 }
@@ -412,6 +421,12 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
         Arc::new(Self::CommitRef(RevsetCommitRef::Bookmarks(expression)))
     }
 
+    pub fn archived_bookmarks(expression: StringExpression) -> Arc<Self> {
+        Arc::new(Self::CommitRef(RevsetCommitRef::ArchivedBookmarks(
+            expression,
+        )))
+    }
+
     pub fn remote_bookmarks(
         bookmark: StringExpression,
         remote: StringExpression,
@@ -424,10 +439,28 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
         }))
     }
 
+    pub fn tracked_remote_bookmark_ranges(
+        bookmark: StringExpression,
+        remote: StringExpression,
+        ahead: bool,
+    ) -> Arc<Self> {
+        Arc::new(Self::CommitRef(
+            RevsetCommitRef::TrackedRemoteBookmarkRanges {
+                bookmark,
+                remote,
+                ahead,
+            },
+        ))
+    }
+
     pub fn tags(expression: StringExpression) -> Arc<Self> {
         Arc::new(Self::CommitRef(RevsetCommitRef::Tags(expression)))
     }
 
+    pub fn stashes(expression: StringExpression) -> Arc<Self> {
+        Arc::new(Self::CommitRef(RevsetCommitRef::Stashes(expression)))
+    }
+
     pub fn git_refs() -> Arc<Self> {
         Arc::new(Self::CommitRef(RevsetCommitRef::GitRefs))
     }
@@ -647,6 +680,7 @@ impl UserRevsetExpression {
 
 impl ResolvedRevsetExpression {
     /// Optimizes and evaluates this expression.
+    #[tracing::instrument(skip_all)]
     pub fn evaluate<'index>(
         self: Arc<Self>,
         repo: &'index dyn Repo,
@@ -659,6 +693,7 @@ impl ResolvedRevsetExpression {
     ///
     /// Use this function if `self` is already optimized, or to debug
     /// optimization pass.
+    #[tracing::instrument(skip_all)]
     pub fn evaluate_unoptimized<'index>(
         self: &Arc<Self>,
         repo: &'index dyn Repo,
@@ -898,6 +933,25 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
         };
         Ok(RevsetExpression::bookmarks(expr))
     });
+    map.insert("archived_bookmarks", |diagnostics, function, _context| {
+        let ([], [opt_arg]) = function.expect_arguments()?;
+        let expr = if let Some(arg) = opt_arg {
+            expect_string_expression(diagnostics, arg)?
+        } else {
+            StringExpression::all()
+        };
+        Ok(RevsetExpression::archived_bookmarks(expr))
+    });
+    map.insert("archived", |_diagnostics, function, _context| {
+        function.expect_no_arguments()?;
+        // Built in rather than an alias so that the default log revset doesn't
+        // depend on user overrides of bookmarks() and the like.
+        let archived = RevsetExpression::archived_bookmarks(StringExpression::all());
+        let other_heads = RevsetExpression::visible_heads()
+            .union(&RevsetExpression::bookmarks(StringExpression::all()))
+            .minus(&archived);
+        Ok(archived.ancestors().minus(&other_heads.ancestors()))
+    });
     map.insert("remote_bookmarks", |diagnostics, function, context| {
         parse_remote_bookmarks_arguments(
             diagnostics,
@@ -928,6 +982,33 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
             )
         },
     );
+    map.insert("ahead_of", |diagnostics, function, context| {
+        let (bookmark_expr, remote_expr) =
+            parse_remote_bookmarks_patterns(diagnostics, function, context.default_ignored_remote)?;
+        Ok(RevsetExpression::tracked_remote_bookmark_ranges(
+            bookmark_expr,
+            remote_expr,
+            true,
+        ))
+    });
+    map.insert("behind", |diagnostics, function, context| {
+        let (bookmark_expr, remote_expr) =
+            parse_remote_bookmarks_patterns(diagnostics, function, context.default_ignored_remote)?;
+        Ok(RevsetExpression::tracked_remote_bookmark_ranges(
+            bookmark_expr,
+            remote_expr,
+            false,
+        ))
+    });
+    map.insert("unpushed", |diagnostics, function, context| {
+        let ([], [remote_opt_arg]) = function.expect_named_arguments(&["remote"])?;
+        let remote_expr =
+            parse_remote_pattern(diagnostics, remote_opt_arg, context.default_ignored_remote)?;
+        let pushed =
+            UserRevsetExpression::remote_bookmarks(StringExpression::all(), remote_expr, None)
+                .union(&RevsetExpression::root());
+        Ok(pushed.range(&RevsetExpression::bookmarks(StringExpression::all())))
+    });
     map.insert("tags", |diagnostics, function, _context| {
         let ([], [opt_arg]) = function.expect_arguments()?;
         let expr = if let Some(arg) = opt_arg {
@@ -937,6 +1018,15 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
         };
         Ok(RevsetExpression::tags(expr))
     });
+    map.insert("stashes", |diagnostics, function, _context| {
+        let ([], [opt_arg]) = function.expect_arguments()?;
+        let expr = if let Some(arg) = opt_arg {
+            expect_string_expression(diagnostics, arg)?
+        } else {
+            StringExpression::all()
+        };
+        Ok(RevsetExpression::stashes(expr))
+    });
     map.insert("git_refs", |_diagnostics, function, _context| {
         function.expect_no_arguments()?;
         Ok(RevsetExpression::git_refs())
@@ -1238,6 +1328,21 @@ fn parse_remote_bookmarks_arguments(
     remote_ref_state: Option<RemoteRefState>,
     default_ignored_remote: Option<&RemoteName>,
 ) -> Result<Arc<UserRevsetExpression>, RevsetParseError> {
+    let (bookmark_expr, remote_expr) =
+        parse_remote_bookmarks_patterns(diagnostics, function, default_ignored_remote)?;
+    Ok(RevsetExpression::remote_bookmarks(
+        bookmark_expr,
+        remote_expr,
+        remote_ref_state,
+    ))
+}
+
+/// Parses `([bookmark], [[remote=]remote])` arguments into patterns.
+fn parse_remote_bookmarks_patterns(
+    diagnostics: &mut RevsetDiagnostics,
+    function: &FunctionCallNode,
+    default_ignored_remote: Option<&RemoteName>,
+) -> Result<(StringExpression, StringExpression), RevsetParseError> {
     let ([], [bookmark_opt_arg, remote_opt_arg]) =
         function.expect_named_arguments(&["", "remote"])?;
     let bookmark_expr = if let Some(bookmark_arg) = bookmark_opt_arg {
@@ -1245,18 +1350,22 @@ fn parse_remote_bookmarks_arguments(
     } else {
         StringExpression::all()
     };
-    let remote_expr = if let Some(remote_arg) = remote_opt_arg {
-        expect_string_expression(diagnostics, remote_arg)?
+    let remote_expr = parse_remote_pattern(diagnostics, remote_opt_arg, default_ignored_remote)?;
+    Ok((bookmark_expr, remote_expr))
+}
+
+fn parse_remote_pattern(
+    diagnostics: &mut RevsetDiagnostics,
+    remote_opt_arg: Option<&ExpressionNode>,
+    default_ignored_remote: Option<&RemoteName>,
+) -> Result<StringExpression, RevsetParseError> {
+    if let Some(remote_arg) = remote_opt_arg {
+        expect_string_expression(diagnostics, remote_arg)
     } else if let Some(remote) = default_ignored_remote {
-        StringExpression::exact(remote).negated()
+        Ok(StringExpression::exact(remote).negated())
     } else {
-        StringExpression::all()
-    };
-    Ok(RevsetExpression::remote_bookmarks(
-        bookmark_expr,
-        remote_expr,
-        remote_ref_state,
-    ))
+        Ok(StringExpression::all())
+    }
 }
 
 /// Resolves function call by using the given function map.
@@ -2908,6 +3017,16 @@ fn resolve_commit_ref(
                 .collect();
             Ok(commit_ids)
         }
+        RevsetCommitRef::ArchivedBookmarks(expression) => {
+            let view = repo.view();
+            let commit_ids = view
+                .local_bookmarks_matching(&expression.to_matcher())
+                .filter(|(name, _)| view.is_bookmark_archived(name))
+                .flat_map(|(_, target)| target.added_ids())
+                .cloned()
+                .collect();
+            Ok(commit_ids)
+        }
         RevsetCommitRef::RemoteBookmarks {
             bookmark,
             remote,
@@ -2926,6 +3045,9 @@ fn resolve_commit_ref(
                 .collect();
             Ok(commit_ids)
         }
+        RevsetCommitRef::TrackedRemoteBookmarkRanges { .. } => {
+            unreachable!("ranges should be resolved as expression")
+        }
         RevsetCommitRef::Tags(expression) => {
             let commit_ids = repo
                 .view()
@@ -2935,6 +3057,15 @@ fn resolve_commit_ref(
                 .collect();
             Ok(commit_ids)
         }
+        RevsetCommitRef::Stashes(expression) => {
+            let commit_ids = repo
+                .view()
+                .stashes_matching(&expression.to_matcher())
+                .flat_map(|(_, target)| target.added_ids())
+                .cloned()
+                .collect();
+            Ok(commit_ids)
+        }
         RevsetCommitRef::GitRefs => {
             let mut commit_ids = vec![];
             for ref_target in repo.view().git_refs().values() {
@@ -2946,6 +3077,33 @@ fn resolve_commit_ref(
     }
 }
 
+/// Builds the union of ranges between tracked remote bookmarks and their local
+/// counterparts.
+fn resolve_tracked_remote_bookmark_ranges(
+    repo: &dyn Repo,
+    bookmark: &StringExpression,
+    remote: &StringExpression,
+    ahead: bool,
+) -> Arc<ResolvedRevsetExpression> {
+    let view = repo.view();
+    let ranges = view
+        .remote_bookmarks_matching(&bookmark.to_matcher(), &remote.to_matcher())
+        .filter(|(_, remote_ref)| remote_ref.is_tracked())
+        .map(|(symbol, remote_ref)| {
+            let local_ids = view.get_local_bookmark(symbol.name).added_ids().cloned();
+            let remote_ids = remote_ref.target.added_ids().cloned();
+            let local = ResolvedRevsetExpression::commits(local_ids.collect());
+            let remote = ResolvedRevsetExpression::commits(remote_ids.collect());
+            if ahead {
+                remote.range(&local)
+            } else {
+                local.range(&remote)
+            }
+        })
+        .collect_vec();
+    RevsetExpression::union_all(&ranges)
+}
+
 /// Resolves symbols and commit refs recursively.
 struct ExpressionSymbolResolver<'a, 'b> {
     base_repo: &'a dyn Repo,
@@ -3003,6 +3161,19 @@ impl ExpressionStateFolder<UserExpressionState, ResolvedExpressionState>
         &mut self,
         commit_ref: &RevsetCommitRef,
     ) -> Result<Arc<ResolvedRevsetExpression>, Self::Error> {
+        if let RevsetCommitRef::TrackedRemoteBookmarkRanges {
+            bookmark,
+            remote,
+            ahead,
+        } = commit_ref
+        {
+            return Ok(resolve_tracked_remote_bookmark_ranges(
+                self.repo(),
+                bookmark,
+                remote,
+                *ahead,
+            ));
+        }
         let commit_ids = resolve_commit_ref(self.repo(), commit_ref, self.symbol_resolver)?;
         Ok(RevsetExpression::commits(commit_ids))
     }
@@ -15,6 +15,7 @@
 #![expect(missing_docs)]
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -35,6 +36,7 @@ use crate::config::ConfigTable;
 use crate::config::ConfigValue;
 use crate::config::StackedConfig;
 use crate::config::ToConfigNamePath;
+use crate::file_util::expand_home_path;
 use crate::fmt_util::binary_prefix;
 use crate::ref_name::RemoteNameBuf;
 use crate::signing::SignBehavior;
@@ -62,6 +64,11 @@ struct UserSettingsData {
 #[derive(Debug, Clone)]
 pub struct RemoteSettings {
     pub auto_track_bookmarks: StringPattern,
+    /// SSH private key used to connect to the remote.
+    pub ssh_identity_file: Option<PathBuf>,
+    /// Jump hosts to connect to the remote through, as accepted by
+    /// `ssh -J`.
+    pub ssh_proxy_jump: Option<String>,
 }
 
 impl RemoteSettings {
@@ -74,21 +81,57 @@ impl RemoteSettings {
                 Ok((
                     name.into(),
                     Self {
-                        auto_track_bookmarks: settings.get_value_with(
-                            ["remotes", name, "auto-track-bookmarks"],
-                            |value| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
-                                Ok(StringPattern::parse(
-                                    value
-                                        .as_str()
-                                        .ok_or_else(|| "expected a string".to_string())?,
-                                )?)
-                            },
-                        )?,
+                        auto_track_bookmarks: settings
+                            .get_value_with(
+                                ["remotes", name, "auto-track-bookmarks"],
+                                |value| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
+                                    Ok(StringPattern::parse(
+                                        value
+                                            .as_str()
+                                            .ok_or_else(|| "expected a string".to_string())?,
+                                    )?)
+                                },
+                            )
+                            .optional()?
+                            // Matches no bookmarks
+                            .unwrap_or_else(|| StringPattern::exact("")),
+                        ssh_identity_file: settings
+                            .get_string(["remotes", name, "ssh-identity-file"])
+                            .optional()?
+                            .map(|path| expand_home_path(&path)),
+                        ssh_proxy_jump: settings
+                            .get_string(["remotes", name, "ssh-proxy-jump"])
+                            .optional()?,
                     },
                 ))
             })
             .try_collect()
     }
+
+    /// Returns the SSH command to connect to the remote with, or `None` if
+    /// the default command should be used. Hosts are still resolved through
+    /// the SSH configuration files.
+    pub fn ssh_command(&self) -> Option<String> {
+        if self.ssh_identity_file.is_none() && self.ssh_proxy_jump.is_none() {
+            return None;
+        }
+        let mut command = "ssh".to_owned();
+        if let Some(path) = &self.ssh_identity_file {
+            // Don't try the keys from the SSH agent before the configured one.
+            command.push_str(" -o IdentitiesOnly=yes -i ");
+            command.push_str(&shell_quote(&path.to_string_lossy()));
+        }
+        if let Some(jump) = &self.ssh_proxy_jump {
+            command.push_str(" -J ");
+            command.push_str(&shell_quote(jump));
+        }
+        Some(command)
+    }
+}
+
+/// Quotes `s` to be passed as a single argument in a shell command.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
 }
 
 /// Commit signing settings, describes how to and if to sign commits.
@@ -438,4 +481,27 @@ mod tests {
             Err("Integer out of range")
         );
     }
+
+    #[test]
+    fn remote_ssh_command() {
+        let mut settings = RemoteSettings {
+            auto_track_bookmarks: StringPattern::all(),
+            ssh_identity_file: None,
+            ssh_proxy_jump: None,
+        };
+        assert_eq!(settings.ssh_command(), None);
+
+        settings.ssh_identity_file = Some("/home/me/.ssh/id_work".into());
+        assert_eq!(
+            settings.ssh_command().unwrap(),
+            "ssh -o IdentitiesOnly=yes -i '/home/me/.ssh/id_work'"
+        );
+
+        settings.ssh_identity_file = Some("/home/me/it's here".into());
+        settings.ssh_proxy_jump = Some("bastion.example.com".to_owned());
+        assert_eq!(
+            settings.ssh_command().unwrap(),
+            r"ssh -o IdentitiesOnly=yes -i '/home/me/it'\''s here' -J 'bastion.example.com'"
+        );
+    }
 }
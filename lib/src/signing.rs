@@ -27,6 +27,8 @@ use crate::config::ConfigGetError;
 use crate::gpg_signing::GpgBackend;
 use crate::gpg_signing::GpgsmBackend;
 use crate::settings::UserSettings;
+use crate::sigstore_signing::SigstoreBackend;
+use crate::ssh_signing::SshAgentBackend;
 use crate::ssh_signing::SshBackend;
 use crate::store::COMMIT_CACHE_CAPACITY;
 #[cfg(feature = "testing")]
@@ -182,6 +184,12 @@ impl Signer {
             Box::new(GpgBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
             Box::new(GpgsmBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
             Box::new(SshBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?),
+            Box::new(
+                SshAgentBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?,
+            ),
+            Box::new(
+                SigstoreBackend::from_settings(settings).map_err(SignInitError::BackendConfig)?,
+            ),
             #[cfg(feature = "testing")]
             Box::new(TestSigningBackend),
         ];
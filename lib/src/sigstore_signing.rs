@@ -0,0 +1,220 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keyless signing with [Sigstore](https://www.sigstore.dev/).
+//!
+//! Signing and verification are delegated to
+//! [gitsign](https://github.com/sigstore/gitsign), which obtains a short-lived
+//! certificate from Fulcio and records the signature in the Rekor transparency
+//! log. gitsign speaks the same command-line protocol as `gpgsm`, and its
+//! signatures are PKCS #7 messages like the ones created by `gpgsm`. They are
+//! told apart by the Fulcio certificate embedded in the signature.
+
+use std::ffi::OsString;
+use std::fmt::Debug;
+use std::io::Write as _;
+use std::process::Command;
+use std::process::Stdio;
+use std::str;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+
+use crate::config::ConfigGetError;
+use crate::config::ConfigGetResultExt as _;
+use crate::gpg_signing::GpgError;
+use crate::gpg_signing::parse_gpg_verify_output;
+use crate::gpg_signing::run_sign_command;
+use crate::gpg_signing::run_verify_command;
+use crate::settings::UserSettings;
+use crate::signing::SignError;
+use crate::signing::SigningBackend;
+use crate::signing::Verification;
+
+const PEM_BEGIN: &str = "-----BEGIN SIGNED MESSAGE-----";
+const PEM_END: &str = "-----END SIGNED MESSAGE-----";
+
+/// DER encoding of the OID arc `1.3.6.1.4.1.57264` assigned to Sigstore.
+/// Certificates issued by Fulcio have extensions under this arc, such as the
+/// OIDC issuer of the signing identity.
+const SIGSTORE_OID_ARC: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xbf, 0x30];
+
+/// Returns true if the PEM-encoded PKCS #7 `signature` embeds a certificate
+/// issued by Fulcio.
+pub(crate) fn is_sigstore_signature(signature: &[u8]) -> bool {
+    let Some(der) = decode_pem(signature) else {
+        return false;
+    };
+    der.windows(SIGSTORE_OID_ARC.len())
+        .any(|window| window == SIGSTORE_OID_ARC)
+}
+
+fn decode_pem(signature: &[u8]) -> Option<Vec<u8>> {
+    let text = str::from_utf8(signature).ok()?;
+    let (_, rest) = text.split_once(PEM_BEGIN)?;
+    let (body, _) = rest.split_once(PEM_END)?;
+    let encoded: String = body.split_ascii_whitespace().collect();
+    BASE64_STANDARD.decode(encoded).ok()
+}
+
+/// Signing backend producing Sigstore signatures through `gitsign`.
+#[derive(Debug)]
+pub struct SigstoreBackend {
+    program: OsString,
+    /// Environment variables passed to the program, e.g. `GITSIGN_FULCIO_URL`.
+    env: Vec<(&'static str, String)>,
+    default_key: String,
+}
+
+impl SigstoreBackend {
+    /// Creates a backend running the given `gitsign` program.
+    pub fn new(program: OsString, default_key: String) -> Self {
+        Self {
+            program,
+            env: vec![],
+            default_key,
+        }
+    }
+
+    /// Creates a backend configured by `signing.backends.sigstore.*`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let program = settings.get_string("signing.backends.sigstore.program")?;
+        let default_key = settings.user_email().to_owned();
+        let mut backend = Self::new(program.into(), default_key);
+        for (name, var) in [
+            ("fulcio-url", "GITSIGN_FULCIO_URL"),
+            ("rekor-url", "GITSIGN_REKOR_URL"),
+            ("oidc-issuer", "GITSIGN_OIDC_ISSUER"),
+        ] {
+            let key = ["signing", "backends", "sigstore", name];
+            if let Some(value) = settings.get_string(key).optional()? {
+                backend.env.push((var, value));
+            }
+        }
+        Ok(backend)
+    }
+
+    fn create_command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        // Hide console window on Windows (https://stackoverflow.com/a/60958956)
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt as _;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .envs(self.env.iter().cloned());
+        command
+    }
+}
+
+impl SigningBackend for SigstoreBackend {
+    fn name(&self) -> &str {
+        "sigstore"
+    }
+
+    fn can_read(&self, signature: &[u8]) -> bool {
+        signature.starts_with(PEM_BEGIN.as_bytes()) && is_sigstore_signature(signature)
+    }
+
+    fn sign(&self, data: &[u8], key: Option<&str>) -> Result<Vec<u8>, SignError> {
+        // The signing identity comes from the OIDC login, so the key is only
+        // passed through for compatibility with the gpgsm protocol.
+        let key = key.unwrap_or(&self.default_key);
+        Ok(run_sign_command(
+            self.create_command().args(["--status-fd=2", "-bsau", key]),
+            data,
+        )?)
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<Verification, SignError> {
+        let mut signature_file = tempfile::Builder::new()
+            .prefix(".jj-sigstore-sig-tmp-")
+            .tempfile()
+            .map_err(GpgError::Io)?;
+        signature_file.write_all(signature).map_err(GpgError::Io)?;
+        signature_file.flush().map_err(GpgError::Io)?;
+
+        let sig_path = signature_file.into_temp_path();
+
+        let output = run_verify_command(
+            self.create_command()
+                .args(["--status-fd=1", "--verify"])
+                .arg(&sig_path)
+                .arg("-"),
+            data,
+        )?;
+
+        // Certificates are short-lived, so expiry doesn't invalidate a
+        // signature that was recorded in the transparency log.
+        parse_gpg_verify_output(&output, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpg_signing::GpgsmBackend;
+
+    fn encode_pem(der: &[u8]) -> Vec<u8> {
+        let encoded = BASE64_STANDARD.encode(der);
+        let lines = encoded.as_bytes().chunks(64);
+        let mut pem = format!("{PEM_BEGIN}\n");
+        for line in lines {
+            pem.push_str(str::from_utf8(line).unwrap());
+            pem.push('\n');
+        }
+        pem.push_str(PEM_END);
+        pem.push('\n');
+        pem.into_bytes()
+    }
+
+    // Start of a PKCS #7 signed-data message, as created by both gpgsm and
+    // gitsign
+    const PKCS7_SIGNED_DATA: &[u8] = &[
+        0x30, 0x80, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02,
+    ];
+
+    #[test]
+    fn test_can_read() {
+        let sigstore = SigstoreBackend::new("gitsign".into(), "someone@example.com".to_owned());
+        let gpgsm = GpgsmBackend::new("gpgsm".into(), false, "someone@example.com".to_owned());
+
+        // Fulcio certificate extension: 1.3.6.1.4.1.57264.1.1 (OIDC issuer)
+        let mut der = PKCS7_SIGNED_DATA.to_vec();
+        der.extend([0x06, 0x0a]);
+        der.extend(SIGSTORE_OID_ARC);
+        der.extend([0x01, 0x01]);
+        let signature = encode_pem(&der);
+        assert!(sigstore.can_read(&signature));
+        assert!(!gpgsm.can_read(&signature));
+
+        let signature = encode_pem(PKCS7_SIGNED_DATA);
+        assert!(!sigstore.can_read(&signature));
+        assert!(gpgsm.can_read(&signature));
+
+        let signature = b"-----BEGIN PGP SIGNATURE-----\n";
+        assert!(!sigstore.can_read(signature));
+        assert!(!gpgsm.can_read(signature));
+
+        // Malformed PEM isn't a Sigstore signature
+        let signature = format!("{PEM_BEGIN}\n!!!\n{PEM_END}\n");
+        assert!(!sigstore.can_read(signature.as_bytes()));
+        assert!(gpgsm.can_read(signature.as_bytes()));
+    }
+}
@@ -48,6 +48,7 @@ use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId;
 use crate::object_id::PrefixResolution;
 use crate::op_store;
+use crate::op_store::ArchivedBookmarks;
 use crate::op_store::OpStore;
 use crate::op_store::OpStoreError;
 use crate::op_store::OpStoreResult;
@@ -59,6 +60,7 @@ use crate::op_store::RemoteRef;
 use crate::op_store::RemoteRefState;
 use crate::op_store::RemoteView;
 use crate::op_store::RootOperationData;
+use crate::op_store::Stashes;
 use crate::op_store::TimestampRange;
 use crate::op_store::View;
 use crate::op_store::ViewId;
@@ -570,6 +572,23 @@ fn view_to_proto(view: &View) -> crate::protos::simple_op_store::View {
 
     let git_head = ref_target_to_proto(&view.git_head);
 
+    let archived_bookmarks = view
+        .archived_bookmarks
+        .0
+        .iter()
+        .map(|name| name.into())
+        .collect();
+
+    let stashes = view
+        .stashes
+        .0
+        .iter()
+        .map(|(name, target)| crate::protos::simple_op_store::Tag {
+            name: name.into(),
+            target: ref_target_to_proto(target),
+        })
+        .collect();
+
     #[expect(deprecated)]
     crate::protos::simple_op_store::View {
         head_ids,
@@ -583,6 +602,8 @@ fn view_to_proto(view: &View) -> crate::protos::simple_op_store::View {
         git_head,
         // New/loaded view should have been migrated to the latest format
         has_git_refs_migrated_to_remote_tags: true,
+        archived_bookmarks,
+        stashes,
     }
 }
 
@@ -669,6 +690,25 @@ fn view_from_proto(proto: crate::protos::simple_op_store::View) -> Result<View,
         RefTarget::absent()
     };
 
+    let archived_bookmarks = ArchivedBookmarks(
+        proto
+            .archived_bookmarks
+            .into_iter()
+            .map(RefNameBuf::from)
+            .collect(),
+    );
+
+    let stashes = Stashes(
+        proto
+            .stashes
+            .into_iter()
+            .map(|stash_proto| {
+                let name: RefNameBuf = stash_proto.name.into();
+                (name, ref_target_from_proto(stash_proto.target))
+            })
+            .collect(),
+    );
+
     Ok(View {
         head_ids,
         local_bookmarks,
@@ -677,6 +717,8 @@ fn view_from_proto(proto: crate::protos::simple_op_store::View) -> Result<View,
         git_refs,
         git_head,
         wc_commit_ids,
+        archived_bookmarks,
+        stashes,
     })
 }
 
@@ -999,6 +1041,9 @@ mod tests {
                 WorkspaceName::DEFAULT.to_owned() => default_wc_commit_id,
                 "test".into() => test_wc_commit_id,
             },
+            // Not populated so the view id stays compatible
+            archived_bookmarks: ArchivedBookmarks::default(),
+            stashes: Stashes::default(),
         }
     }
 
@@ -25,6 +25,7 @@
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io;
 use std::io::Read;
@@ -32,6 +33,7 @@ use std::io::Write as _;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::time::SystemTime;
 
 use blake2::Blake2b512;
 use blake2::Digest as _;
@@ -332,8 +334,10 @@ impl MutableTable {
     }
 
     fn save_in(self, store: &TableStore) -> TableStoreResult<Arc<ReadonlyTable>> {
-        if self.entries.is_empty() && self.parent_file.is_some() {
-            return Ok(self.parent_file.unwrap());
+        if self.entries.is_empty()
+            && let Some(parent_file) = self.parent_file
+        {
+            return Ok(parent_file);
         }
 
         let buf = self.maybe_squash_with_ancestors().serialize();
@@ -395,6 +399,8 @@ pub enum TableStoreError {
     },
     #[error("Failed to lock table store")]
     Lock(#[source] FileLockError),
+    #[error("Failed to remove unused table segments")]
+    Gc(#[source] io::Error),
 }
 
 pub type TableStoreResult<T> = Result<T, TableStoreError>;
@@ -544,6 +550,40 @@ impl TableStore {
         }
         Ok((merged_table, lock))
     }
+
+    /// Removes segment files that are no longer reachable from the heads,
+    /// except for the files modified after `keep_newer`.
+    ///
+    /// Segments become unreachable when they're squashed into a new segment.
+    pub fn gc(&self, keep_newer: SystemTime) -> TableStoreResult<()> {
+        let (head, _lock) = self.get_head_locked()?;
+        let reachable_names: HashSet<&str> =
+            std::iter::successors(Some(&head), |table| table.parent_file.as_ref())
+                .map(|table| table.name.as_str())
+                .collect();
+        for entry in self.dir.read_dir().map_err(TableStoreError::Gc)? {
+            let entry = entry.map_err(TableStoreError::Gc)?;
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            // Skip "heads" directory, "lock" file, temporary files, etc.
+            if hex_util::decode_hex(name).is_none() || reachable_names.contains(name) {
+                continue;
+            }
+            let mtime = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .map_err(TableStoreError::Gc)?;
+            if mtime > keep_newer {
+                continue;
+            }
+            tracing::trace!(name, "removing table segment");
+            std::fs::remove_file(entry.path()).map_err(TableStoreError::Gc)?;
+            self.cached_tables.write().unwrap().remove(name);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -737,4 +777,39 @@ mod tests {
         let table = store.get_head().unwrap();
         assert_eq!(table.get_value(b"abc"), Some(b"value".as_slice()));
     }
+
+    #[test]
+    fn stacked_table_gc() {
+        let temp_dir = new_temp_dir();
+        let store = TableStore::init(temp_dir.path().to_path_buf(), 3);
+        let count_segments = || {
+            temp_dir
+                .path()
+                .read_dir()
+                .unwrap()
+                .filter(|entry| entry.as_ref().unwrap().file_type().unwrap().is_file())
+                .filter(|entry| entry.as_ref().unwrap().file_name() != "lock")
+                .count()
+        };
+
+        // Each save squashes the previous segment since it has as many entries
+        let mut mut_table = store.get_head().unwrap().start_mutation();
+        for i in 0..3 {
+            mut_table.add_entry(format!("x{i}0").into_bytes(), b"value".to_vec());
+            let saved_table = store.save_table(mut_table).unwrap();
+            mut_table = MutableTable::incremental(saved_table);
+        }
+        let num_segments = count_segments();
+        assert!(num_segments > 1);
+
+        // New segments are kept
+        store.gc(SystemTime::UNIX_EPOCH).unwrap();
+        assert_eq!(count_segments(), num_segments);
+
+        store.gc(SystemTime::now()).unwrap();
+        assert_eq!(count_segments(), 1);
+        let table = store.get_head().unwrap();
+        assert_eq!(table.get_value(b"x00"), Some(b"value".as_slice()));
+        assert_eq!(table.get_value(b"x20"), Some(b"value".as_slice()));
+    }
 }
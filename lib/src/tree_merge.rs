@@ -40,6 +40,7 @@ use crate::merge::Merge;
 use crate::merge::MergedTreeVal;
 use crate::merge::MergedTreeValue;
 use crate::merge::SameChange;
+use crate::merge_driver::MergeDrivers;
 use crate::merged_tree::all_merged_tree_entries;
 use crate::object_id::ObjectId as _;
 use crate::repo_path::RepoPath;
@@ -56,6 +57,8 @@ pub struct MergeOptions {
     pub hunk_level: FileMergeHunkLevel,
     /// Whether to resolve conflict that makes the same change at all sides.
     pub same_change: SameChange,
+    /// Per-path merge drivers which override the default content merge.
+    pub drivers: MergeDrivers,
 }
 
 impl MergeOptions {
@@ -66,6 +69,7 @@ impl MergeOptions {
             // needed. It wouldn't be translated to FileMergeHunkLevel.
             hunk_level: settings.get("merge.hunk-level")?,
             same_change: settings.get("merge.same-change")?,
+            drivers: MergeDrivers::from_settings(settings)?,
         })
     }
 }
@@ -144,7 +148,7 @@ impl MergedTreeInput {
             let mut conflict_entries = self.conflicts.first_key_value().unwrap().1.map(|_| vec![]);
             for (basename, value) in self.conflicts {
                 assert_eq!(value.num_sides(), conflict_entries.num_sides());
-                for (entries, value) in conflict_entries.iter_mut().zip(value.into_iter()) {
+                for (entries, value) in conflict_entries.iter_mut().zip(value) {
                     if let Some(value) = value {
                         entries.push((basename.clone(), value));
                     }
@@ -488,7 +492,17 @@ async fn try_resolve_file_conflict(
             BackendResult::Ok(content)
         })
         .await?;
-    if let Some(merged_content) = files::try_merge(&contents, options) {
+    let merged_content = match options.drivers.find(filename) {
+        Some(driver) => driver
+            .merge(filename, &contents, options)
+            .unwrap_or_else(|err| {
+                // Leave the conflict to be resolved manually.
+                tracing::warn!(?err, ?filename, "merge driver failed");
+                None
+            }),
+        None => files::try_merge(&contents, options),
+    };
+    if let Some(merged_content) = merged_content {
         let id = store
             .write_file(filename, &mut merged_content.as_slice())
             .await?;
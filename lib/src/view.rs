@@ -178,6 +178,7 @@ impl View {
             self.data.local_bookmarks.insert(name.to_owned(), target);
         } else {
             self.data.local_bookmarks.remove(name);
+            self.data.archived_bookmarks.0.remove(name);
             for remote_view in self.data.remote_views.values_mut() {
                 let remote_refs = &mut remote_view.bookmarks;
                 if remote_refs.get(name).is_some_and(RemoteRef::is_absent) {
@@ -187,6 +188,31 @@ impl View {
         }
     }
 
+    /// Returns true if the local bookmark is archived.
+    pub fn is_bookmark_archived(&self, name: &RefName) -> bool {
+        self.data.archived_bookmarks.0.contains(name)
+    }
+
+    /// Iterates names of archived local bookmarks in lexicographical order.
+    pub fn archived_bookmarks(&self) -> impl Iterator<Item = &RefName> {
+        self.data
+            .archived_bookmarks
+            .0
+            .iter()
+            .map(|name| name.as_ref())
+    }
+
+    /// Marks the local bookmark as archived or not. Archived bookmarks are
+    /// hidden from the default listing, but are otherwise unchanged. Absent
+    /// bookmarks cannot be archived.
+    pub fn set_bookmark_archived(&mut self, name: &RefName, archived: bool) {
+        if archived && self.data.local_bookmarks.contains_key(name) {
+            self.data.archived_bookmarks.0.insert(name.to_owned());
+        } else {
+            self.data.archived_bookmarks.0.remove(name);
+        }
+    }
+
     /// Iterates over `(symbol, remote_ref)` for all remote bookmarks in
     /// lexicographical order.
     pub fn all_remote_bookmarks(&self) -> impl Iterator<Item = (RemoteRefSymbol<'_>, &RemoteRef)> {
@@ -393,6 +419,40 @@ impl View {
         }
     }
 
+    /// Iterates stash `(name, target)`s in lexicographical order.
+    pub fn stashes(&self) -> impl Iterator<Item = (&RefName, &RefTarget)> {
+        self.data
+            .stashes
+            .0
+            .iter()
+            .map(|(name, target)| (name.as_ref(), target))
+    }
+
+    pub fn get_stash(&self, name: &RefName) -> &RefTarget {
+        self.data.stashes.0.get(name).flatten()
+    }
+
+    /// Iterates stash `(name, target)`s matching the given pattern. Entries
+    /// are sorted by `name`.
+    pub fn stashes_matching(
+        &self,
+        matcher: &StringMatcher,
+    ) -> impl Iterator<Item = (&RefName, &RefTarget)> {
+        matcher
+            .filter_btree_map_as_deref(&self.data.stashes.0)
+            .map(|(name, target)| (name.as_ref(), target))
+    }
+
+    /// Sets stash to point to the given target. If the target is absent, the
+    /// stash will be removed. The stashed commit isn't added to the heads.
+    pub fn set_stash_target(&mut self, name: &RefName, target: RefTarget) {
+        if target.is_present() {
+            self.data.stashes.0.insert(name.to_owned(), target);
+        } else {
+            self.data.stashes.0.remove(name);
+        }
+    }
+
     /// Iterates over `(symbol, remote_ref)` for all remote tags in
     /// lexicographical order.
     pub fn all_remote_tags(&self) -> impl Iterator<Item = (RemoteRefSymbol<'_>, &RemoteRef)> {
@@ -529,6 +589,8 @@ impl View {
             git_refs,
             git_head,
             wc_commit_ids,
+            archived_bookmarks: _,
+            stashes,
         } = &self.data;
         itertools::chain!(
             head_ids,
@@ -541,7 +603,8 @@ impl View {
             }),
             git_refs.values().flat_map(ref_target_ids),
             ref_target_ids(git_head),
-            wc_commit_ids.values()
+            wc_commit_ids.values(),
+            stashes.0.values().flat_map(ref_target_ids)
         )
     }
 
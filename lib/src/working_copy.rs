@@ -30,6 +30,7 @@ use tracing::instrument;
 use crate::backend::BackendError;
 use crate::commit::Commit;
 use crate::dag_walk;
+use crate::gitattributes::GitAttributesError;
 use crate::gitignore::GitIgnoreError;
 use crate::gitignore::GitIgnoreFile;
 use crate::matchers::Matcher;
@@ -70,11 +71,28 @@ pub trait WorkingCopy: Any + Send {
     /// that all files should be checked out.
     fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError>;
 
+    /// How the sparse patterns are interpreted.
+    fn sparse_mode(&self) -> Result<SparseMode, WorkingCopyStateError> {
+        Ok(SparseMode::Prefix)
+    }
+
     /// Locks the working copy and returns an instance with methods for updating
     /// the working copy files and state.
     fn start_mutation(&self) -> Result<Box<dyn LockedWorkingCopy>, WorkingCopyStateError>;
 }
 
+/// How sparse patterns select the paths to check out.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SparseMode {
+    /// Each pattern selects the path and everything under it.
+    #[default]
+    Prefix,
+    /// Each pattern selects the directory and everything under it, and the
+    /// files directly in the root directory and in the ancestors of each
+    /// pattern are selected as well. This is Git's sparse-checkout cone mode.
+    Cone,
+}
+
 impl dyn WorkingCopy {
     /// Returns reference of the implementation type.
     pub fn downcast_ref<T: WorkingCopy>(&self) -> Option<&T> {
@@ -147,6 +165,23 @@ pub trait LockedWorkingCopy: Any + Send {
         new_sparse_patterns: Vec<RepoPathBuf>,
     ) -> Result<CheckoutStats, CheckoutError>;
 
+    /// See `WorkingCopy::sparse_mode()`
+    fn sparse_mode(&self) -> Result<SparseMode, WorkingCopyStateError> {
+        Ok(SparseMode::Prefix)
+    }
+
+    /// Updates the sparse patterns and how they are interpreted.
+    async fn set_sparse_patterns_with_mode(
+        &mut self,
+        new_sparse_patterns: Vec<RepoPathBuf>,
+        mode: SparseMode,
+    ) -> Result<CheckoutStats, CheckoutError> {
+        match mode {
+            SparseMode::Prefix => self.set_sparse_patterns(new_sparse_patterns).await,
+            SparseMode::Cone => Err(CheckoutError::UnsupportedSparseMode(mode)),
+        }
+    }
+
     /// Finish the modifications to the working copy by writing the updated
     /// states to disk. Returns the new (unlocked) working copy.
     async fn finish(
@@ -192,6 +227,9 @@ pub enum SnapshotError {
     /// Checking path with ignore patterns failed.
     #[error(transparent)]
     GitIgnoreError(#[from] GitIgnoreError),
+    /// Reading `.gitattributes` files failed.
+    #[error(transparent)]
+    GitAttributesError(#[from] GitAttributesError),
     /// Failed to load the working copy state.
     #[error(transparent)]
     WorkingCopyStateError(#[from] WorkingCopyStateError),
@@ -304,6 +342,9 @@ pub enum CheckoutError {
     /// Failed to load the working copy state.
     #[error(transparent)]
     WorkingCopyStateError(#[from] WorkingCopyStateError),
+    /// The working copy doesn't support the requested sparse mode.
+    #[error("Sparse mode {0:?} is not supported by this working copy")]
+    UnsupportedSparseMode(SparseMode),
     /// Some other error happened while checking out the working copy.
     #[error("{message}")]
     Other {
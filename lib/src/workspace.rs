@@ -61,6 +61,8 @@ use crate::working_copy::LockedWorkingCopy;
 use crate::working_copy::WorkingCopy;
 use crate::working_copy::WorkingCopyFactory;
 use crate::working_copy::WorkingCopyStateError;
+use crate::workspace_store::SimpleWorkspaceStore;
+use crate::workspace_store::WorkspaceStoreError;
 
 #[derive(Error, Debug)]
 pub enum WorkspaceInitError {
@@ -82,6 +84,8 @@ pub enum WorkspaceInitError {
     SignInit(#[from] SignInitError),
     #[error(transparent)]
     TransactionCommit(#[from] TransactionCommitError),
+    #[error(transparent)]
+    WorkspaceStore(#[from] WorkspaceStoreError),
 }
 
 #[derive(Error, Debug)]
@@ -321,6 +325,7 @@ impl Workspace {
             )?;
             let repo_loader = repo.loader().clone();
             let workspace = Self::new(workspace_root, repo_dir, working_copy, repo_loader)?;
+            workspace.record_location()?;
             Ok((workspace, repo))
         })()
         .inspect_err(|_err| {
@@ -376,9 +381,16 @@ impl Workspace {
             working_copy,
             repo.loader().clone(),
         )?;
+        workspace.record_location()?;
         Ok((workspace, repo))
     }
 
+    /// Records the workspace root in the repo's [`SimpleWorkspaceStore`].
+    fn record_location(&self) -> Result<(), WorkspaceStoreError> {
+        SimpleWorkspaceStore::load(self.repo_path())
+            .add(self.workspace_name(), self.workspace_root())
+    }
+
     pub fn load(
         user_settings: &UserSettings,
         workspace_path: &Path,
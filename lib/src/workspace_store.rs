@@ -0,0 +1,206 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records where the workspaces of a repo are located on disk.
+//!
+//! The set of workspaces is recorded in the view, so it's versioned with the
+//! operation log. Their paths are local to the machine, so they're kept in a
+//! separate file in the repo directory instead. An entry may therefore outlive
+//! its workspace, and workspaces created by older versions have no entry.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use prost::Message as _;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::file_util;
+use crate::file_util::BadPathEncoding;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::file_util::persist_temp_file;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
+use crate::ref_name::WorkspaceName;
+use crate::ref_name::WorkspaceNameBuf;
+
+/// Error that may occur when reading or updating the workspace store.
+#[derive(Debug, Error)]
+pub enum WorkspaceStoreError {
+    /// Failed to read or write the index file.
+    #[error(transparent)]
+    Path(#[from] PathError),
+    /// Failed to lock the index file.
+    #[error(transparent)]
+    Lock(#[from] FileLockError),
+    /// The workspace path can't be stored as bytes on this platform.
+    #[error("Workspace path could not be encoded")]
+    EncodePath(#[source] BadPathEncoding),
+}
+
+/// Workspace locations stored in `<repo>/workspace_store/index`.
+#[derive(Clone, Debug)]
+pub struct SimpleWorkspaceStore {
+    store_dir: PathBuf,
+}
+
+impl SimpleWorkspaceStore {
+    /// Returns the store of the repo at `repo_path`. The store directory is
+    /// created on the first update.
+    pub fn load(repo_path: &Path) -> Self {
+        Self {
+            store_dir: repo_path.join("workspace_store"),
+        }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.store_dir.join("index")
+    }
+
+    /// Returns the recorded root of the workspace `name`, if any.
+    pub fn get_workspace_path(
+        &self,
+        name: &WorkspaceName,
+    ) -> Result<Option<PathBuf>, WorkspaceStoreError> {
+        let mut paths = self.workspace_paths()?;
+        Ok(paths.remove(name))
+    }
+
+    /// Returns the recorded roots of all workspaces.
+    pub fn workspace_paths(
+        &self,
+    ) -> Result<BTreeMap<WorkspaceNameBuf, PathBuf>, WorkspaceStoreError> {
+        let index_path = self.index_path();
+        let data = match fs::read(&index_path).context(&index_path) {
+            Ok(data) => data,
+            Err(PathError { source: error, .. }) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(BTreeMap::new());
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let proto = crate::protos::workspace_store::Workspaces::decode(&*data)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            .context(&index_path)?;
+        let paths = proto
+            .workspaces
+            .into_iter()
+            // Entries that can't be decoded on this platform are ignored.
+            .filter_map(|workspace| {
+                let path = file_util::path_from_bytes(&workspace.path).ok()?;
+                Some((WorkspaceNameBuf::from(workspace.name), path.to_owned()))
+            })
+            .collect();
+        Ok(paths)
+    }
+
+    /// Records `path` as the root of the workspace `name`, replacing any
+    /// existing entry.
+    pub fn add(&self, name: &WorkspaceName, path: &Path) -> Result<(), WorkspaceStoreError> {
+        self.update(|paths| {
+            paths.insert(name.to_owned(), path.to_owned());
+        })
+    }
+
+    /// Removes the entries of the given workspaces.
+    pub fn forget(&self, names: &[&WorkspaceName]) -> Result<(), WorkspaceStoreError> {
+        self.update(|paths| {
+            for name in names {
+                paths.remove(*name);
+            }
+        })
+    }
+
+    /// Moves the entry of the workspace `old_name` to `new_name`.
+    pub fn rename(
+        &self,
+        old_name: &WorkspaceName,
+        new_name: &WorkspaceName,
+    ) -> Result<(), WorkspaceStoreError> {
+        self.update(|paths| {
+            if let Some(path) = paths.remove(old_name) {
+                paths.insert(new_name.to_owned(), path);
+            }
+        })
+    }
+
+    fn update(
+        &self,
+        f: impl FnOnce(&mut BTreeMap<WorkspaceNameBuf, PathBuf>),
+    ) -> Result<(), WorkspaceStoreError> {
+        file_util::create_or_reuse_dir(&self.store_dir).context(&self.store_dir)?;
+        let _lock = FileLock::lock(self.store_dir.join("lock"))?;
+        let mut paths = self.workspace_paths()?;
+        f(&mut paths);
+        let workspaces = paths
+            .iter()
+            .map(|(name, path)| {
+                let path =
+                    file_util::path_to_bytes(path).map_err(WorkspaceStoreError::EncodePath)?;
+                Ok(crate::protos::workspace_store::Workspace {
+                    name: name.as_str().to_owned(),
+                    path: path.to_vec(),
+                })
+            })
+            .collect::<Result<_, WorkspaceStoreError>>()?;
+        let proto = crate::protos::workspace_store::Workspaces { workspaces };
+        let mut temp_file = NamedTempFile::new_in(&self.store_dir).context(&self.store_dir)?;
+        temp_file
+            .as_file_mut()
+            .write_all(&proto.encode_to_vec())
+            .context(temp_file.path())?;
+        let index_path = self.index_path();
+        persist_temp_file(temp_file, &index_path).context(&index_path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_rename_forget() {
+        let temp_dir = crate::tests::new_temp_dir();
+        let store = SimpleWorkspaceStore::load(temp_dir.path());
+        let default = WorkspaceName::DEFAULT;
+        let second = WorkspaceName::new("second");
+        let third = WorkspaceName::new("third");
+        assert_eq!(store.get_workspace_path(default).unwrap(), None);
+
+        store.add(default, Path::new("/repo")).unwrap();
+        store.add(second, Path::new("/second")).unwrap();
+        assert_eq!(
+            store.get_workspace_path(second).unwrap(),
+            Some(PathBuf::from("/second"))
+        );
+
+        store.rename(second, third).unwrap();
+        assert_eq!(store.get_workspace_path(second).unwrap(), None);
+        assert_eq!(
+            store.get_workspace_path(third).unwrap(),
+            Some(PathBuf::from("/second"))
+        );
+
+        store.forget(&[default]).unwrap();
+        assert_eq!(
+            store.workspace_paths().unwrap(),
+            BTreeMap::from([(third.to_owned(), PathBuf::from("/second"))])
+        );
+    }
+}
@@ -2412,10 +2412,12 @@ fn materialize_conflict_string(
         .unwrap();
     let options = ConflictMaterializeOptions {
         marker_style,
+        marker_style_overrides: Default::default(),
         marker_len: None,
         merge: MergeOptions {
             hunk_level: FileMergeHunkLevel::Line,
             same_change: SameChange::Accept,
+            drivers: Default::default(),
         },
     };
     String::from_utf8(materialize_merge_result_to_bytes(&contents, &options).into()).unwrap()
@@ -583,3 +583,70 @@ fn test_eol_conversion_checkout(
     assert!(std::fs::exists(&file_disk_path).unwrap());
     std::fs::read(&file_disk_path).unwrap()
 }
+
+#[test]
+fn test_eol_conversion_gitattributes_snapshot() {
+    let user_settings =
+        base_user_settings_with_extra_configs("working-copy.eol-conversion = \"input\"\n");
+    let mut test_workspace =
+        TestWorkspace::init_with_backend_and_settings(TestRepoBackend::Git, &user_settings);
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let gitattributes_path = repo_path(".gitattributes");
+    let text_file_path = repo_path("dir/forced.dat");
+    let binary_file_path = repo_path("dir/raw.txt");
+    let other_file_path = repo_path("other.txt");
+    testutils::write_working_copy_file(
+        &workspace_root,
+        gitattributes_path,
+        "*.dat text\n*.txt text=auto\n",
+    );
+    testutils::write_working_copy_file(
+        &workspace_root,
+        repo_path("dir/.gitattributes"),
+        "raw.txt -text\n",
+    );
+    testutils::write_working_copy_file(&workspace_root, text_file_path, b"\0\r\n");
+    testutils::write_working_copy_file(&workspace_root, binary_file_path, CRLF_FILE_CONTENT);
+    testutils::write_working_copy_file(&workspace_root, other_file_path, CRLF_FILE_CONTENT);
+    let tree = test_workspace.snapshot().unwrap();
+
+    let read = |path| {
+        let value = tree.path_value(path).unwrap();
+        let Some(Some(jj_lib::backend::TreeValue::File { id, .. })) = value.as_resolved() else {
+            panic!("unexpected value at {path:?}: {value:?}");
+        };
+        testutils::read_file(tree.store(), path, id)
+    };
+    // "text" skips the binary detection
+    assert_eq!(read(text_file_path), b"\0\n");
+    // "-text" in the nested file overrides the root "text=auto"
+    assert_eq!(read(binary_file_path), CRLF_FILE_CONTENT);
+    assert_eq!(read(other_file_path), LF_FILE_CONTENT);
+}
+
+#[test_case("*.txt eol=crlf\n" => CRLF_FILE_CONTENT; "eol crlf attribute")]
+#[test_case("*.txt -text\n" => LF_FILE_CONTENT; "unset text attribute")]
+#[test_case("*.dat eol=crlf\n" => LF_FILE_CONTENT; "unmatched attribute")]
+fn test_eol_conversion_gitattributes_update(gitattributes: &str) -> Vec<u8> {
+    let user_settings =
+        base_user_settings_with_extra_configs("working-copy.eol-conversion = \"none\"\n");
+    let mut test_workspace =
+        TestWorkspace::init_with_backend_and_settings(TestRepoBackend::Git, &user_settings);
+    let file_repo_path = repo_path("test-eol-file.txt");
+    let file_disk_path = file_repo_path
+        .to_fs_path(test_workspace.workspace.workspace_root())
+        .unwrap();
+    let tree = testutils::create_tree(
+        &test_workspace.repo,
+        &[
+            (repo_path(".gitattributes"), gitattributes),
+            (file_repo_path, "aaa\nbbbb\nccccc\n"),
+        ],
+    );
+    let commit = commit_with_tree(test_workspace.repo.store(), tree);
+    test_workspace
+        .workspace
+        .check_out(test_workspace.repo.op_id().clone(), None, &commit)
+        .unwrap();
+    std::fs::read(&file_disk_path).unwrap()
+}
@@ -39,12 +39,14 @@ use jj_lib::config::ConfigLayer;
 use jj_lib::config::ConfigSource;
 use jj_lib::git;
 use jj_lib::git::FailedRefExportReason;
+use jj_lib::git::FetchDepth;
 use jj_lib::git::FetchTagsOverride;
 use jj_lib::git::GitBranchPushTargets;
 use jj_lib::git::GitFetch;
 use jj_lib::git::GitFetchError;
 use jj_lib::git::GitImportError;
 use jj_lib::git::GitPushError;
+use jj_lib::git::GitPushOptions;
 use jj_lib::git::GitPushStats;
 use jj_lib::git::GitRefKind;
 use jj_lib::git::GitRefUpdate;
@@ -53,6 +55,7 @@ use jj_lib::git::GitSettings;
 use jj_lib::git::IgnoredRefspec;
 use jj_lib::git::IgnoredRefspecs;
 use jj_lib::git::expand_default_fetch_refspecs;
+use jj_lib::git::expand_default_push_refspecs;
 use jj_lib::git::expand_fetch_refspecs;
 use jj_lib::git_backend::GitBackend;
 use jj_lib::hex_util;
@@ -72,12 +75,15 @@ use jj_lib::refs::BookmarkPushUpdate;
 use jj_lib::repo::MutableRepo;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::settings::RemoteSettings;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::Signer;
 use jj_lib::str_util::StringPattern;
+use jj_lib::working_copy::SparseMode;
 use jj_lib::workspace::Workspace;
 use maplit::btreemap;
+use maplit::hashmap;
 use maplit::hashset;
 use tempfile::TempDir;
 use test_case::test_case;
@@ -87,6 +93,7 @@ use testutils::base_user_config;
 use testutils::commit_transactions;
 use testutils::create_random_commit;
 use testutils::repo_path;
+use testutils::repo_path_buf;
 use testutils::write_random_commit;
 use testutils::write_random_commit_with_parents;
 
@@ -155,7 +162,7 @@ fn git_fetch(
         remote_name,
         fetch_refspecs,
         git::RemoteCallbacks::default(),
-        None,
+        &FetchDepth::Full,
         fetch_tags_override,
     )?;
     let default_branch = git_fetch.get_default_branch(remote_name)?;
@@ -3551,6 +3558,74 @@ fn test_expand_default_fetch_refspecs() {
     "#);
 }
 
+#[test]
+fn test_expand_default_push_refspecs() {
+    let mut test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let git_repo = get_git_repo(&test_repo.repo);
+    let config = git_repo.config_snapshot().clone();
+
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(
+            config
+                .meta()
+                .path
+                .as_ref()
+                .expect("failed to find config file"),
+        )
+        .expect("failed to open config file")
+        .write_all(
+            br#"
+            [remote "origin"]
+            url = /dev/null
+            # Valid
+            push = refs/heads/main:refs/heads/main
+            push = +refs/heads/foo*:refs/heads/foo*
+            push = ^refs/heads/fooqux
+            # Invalid
+            push = :
+            push = :refs/heads/deleted
+            push = refs/heads/renamed:refs/heads/mirror/renamed
+            push = refs/tags/wrong-src:refs/tags/wrong-src
+            push = ^refs/tags/wrong-negative
+            "#,
+        )
+        .expect("failed to update config file");
+
+    // Reload after Git configuration change.
+    test_repo.repo = test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    let git_repo = get_git_repo(&test_repo.repo);
+
+    let (IgnoredRefspecs(ignored_refspecs), expanded) =
+        expand_default_push_refspecs("origin".as_ref(), &git_repo)
+            .expect("failed to expand refspecs");
+
+    let mut warnings = String::new();
+    for IgnoredRefspec { refspec, reason } in ignored_refspecs {
+        warnings.push_str(reason);
+        warnings.push_str(": ");
+        warnings.push_str(&String::from_utf8_lossy(&refspec));
+        warnings.push('\n');
+    }
+    insta::assert_snapshot!(warnings, @r"
+    matching refspecs are not supported: :
+    delete refspecs are not supported: :refs/heads/deleted
+    renaming is not supported: refs/heads/renamed:refs/heads/mirror/renamed
+    only refs/heads/ is supported for refspec sources: refs/tags/wrong-src:refs/tags/wrong-src
+    only refs/heads/ is supported for negative refspecs: ^refs/tags/wrong-negative
+    ");
+
+    let is_allowed = |name: &str| expanded.is_allowed(name.as_ref());
+    assert!(is_allowed("main"));
+    assert!(is_allowed("foo"));
+    assert!(is_allowed("foobar"));
+    assert!(!is_allowed("fooqux"));
+    assert!(!is_allowed("renamed"));
+    assert!(!is_allowed("other"));
+}
+
 #[test]
 fn test_expand_default_fetch_refspecs_invalid_configuration() {
     let mut test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
@@ -4215,6 +4290,7 @@ fn test_push_updates_unexpectedly_moved_sideways_on_remote() {
             "origin".as_ref(),
             &targets,
             git::RemoteCallbacks::default(),
+            &GitPushOptions::default(),
         )
     };
 
@@ -4293,6 +4369,7 @@ fn test_push_updates_unexpectedly_moved_forward_on_remote() {
             "origin".as_ref(),
             &targets,
             git::RemoteCallbacks::default(),
+            &GitPushOptions::default(),
         )
     };
 
@@ -4357,6 +4434,7 @@ fn test_push_updates_unexpectedly_exists_on_remote() {
             "origin".as_ref(),
             &targets,
             git::RemoteCallbacks::default(),
+            &GitPushOptions::default(),
         )
     };
 
@@ -4393,6 +4471,7 @@ fn test_push_updates_success() {
             new_target: Some(setup.child_of_main_commit.id().clone()),
         }],
         git::RemoteCallbacks::default(),
+        &GitPushOptions::default(),
     );
     assert_eq!(
         result.unwrap(),
@@ -4433,6 +4512,7 @@ fn test_push_updates_no_such_remote() {
             new_target: Some(setup.child_of_main_commit.id().clone()),
         }],
         git::RemoteCallbacks::default(),
+        &GitPushOptions::default(),
     );
     assert!(matches!(result, Err(GitPushError::NoSuchRemote(_))));
 }
@@ -4453,6 +4533,7 @@ fn test_push_updates_invalid_remote() {
             new_target: Some(setup.child_of_main_commit.id().clone()),
         }],
         git::RemoteCallbacks::default(),
+        &GitPushOptions::default(),
     );
     assert!(matches!(result, Err(GitPushError::NoSuchRemote(_))));
 }
@@ -5020,9 +5101,204 @@ fn test_remote_add_with_tags_specification() {
     }
 }
 
+#[test]
+fn test_read_write_notes() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let mut tx = repo.start_transaction();
+    let commit1 = write_random_commit(tx.repo_mut());
+    let commit2 = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+    let store = repo.store();
+    let signature = testutils::user_settings().signature();
+    let notes_ref = git::DEFAULT_NOTES_REF;
+
+    assert!(git::read_notes(store, notes_ref).unwrap().is_empty());
+    git::write_note(store, notes_ref, commit1.id(), Some("one"), &signature).unwrap();
+    git::write_note(store, notes_ref, commit2.id(), Some("two\n"), &signature).unwrap();
+    assert_eq!(
+        git::read_notes(store, notes_ref).unwrap(),
+        hashmap! {
+            commit1.id().clone() => "one\n".to_owned(),
+            commit2.id().clone() => "two\n".to_owned(),
+        }
+    );
+    // Each change is a commit on top of the previous notes
+    let notes_commit = git_repo
+        .find_reference(notes_ref)
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(notes_commit.parent_ids().count(), 1);
+
+    git::write_note(store, notes_ref, commit1.id(), None, &signature).unwrap();
+    assert_eq!(
+        git::read_notes(store, notes_ref).unwrap(),
+        hashmap! { commit2.id().clone() => "two\n".to_owned() }
+    );
+
+    // Other notes refs are independent
+    assert!(
+        git::read_notes(store, "refs/notes/other")
+            .unwrap()
+            .is_empty()
+    );
+    assert_matches!(
+        git::read_notes(store, "refs/heads/main"),
+        Err(git::GitNotesError::InvalidRef(_))
+    );
+}
+
+#[test]
+fn test_read_notes_with_fanout() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let mut tx = repo.start_transaction();
+    let commit = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+
+    // Git splits the commit IDs into directories once there are many notes
+    let hex = commit.id().hex();
+    let blob_id = git_repo.write_blob(b"note\n").unwrap().detach();
+    let subtree_id = git_repo
+        .write_object(gix::objs::Tree {
+            entries: vec![gix::objs::tree::Entry {
+                mode: gix::object::tree::EntryKind::Blob.into(),
+                filename: hex[2..].into(),
+                oid: blob_id,
+            }],
+        })
+        .unwrap()
+        .detach();
+    let tree_id = git_repo
+        .write_object(gix::objs::Tree {
+            entries: vec![gix::objs::tree::Entry {
+                mode: gix::object::tree::EntryKind::Tree.into(),
+                filename: hex[..2].into(),
+                oid: subtree_id,
+            }],
+        })
+        .unwrap()
+        .detach();
+    let signature = gix::actor::Signature {
+        name: "Someone".into(),
+        email: "someone@example.com".into(),
+        time: gix::date::Time::new(0, 0),
+    };
+    git_repo
+        .commit_as(
+            signature.to_ref(&mut gix::date::parse::TimeBuf::default()),
+            signature.to_ref(&mut gix::date::parse::TimeBuf::default()),
+            git::DEFAULT_NOTES_REF,
+            "Notes added by 'git notes add'",
+            tree_id,
+            None::<gix::ObjectId>,
+        )
+        .unwrap();
+
+    assert_eq!(
+        git::read_notes(repo.store(), git::DEFAULT_NOTES_REF).unwrap(),
+        hashmap! { commit.id().clone() => "note\n".to_owned() }
+    );
+}
+
+#[test]
+fn test_sparse_checkout_round_trip() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let sparse_checkout_path = git_repo.path().join("info").join("sparse-checkout");
+    assert_eq!(git::read_sparse_checkout(repo.store()).unwrap(), None);
+
+    let dirs = vec![
+        repo_path_buf("a/b/c"),
+        repo_path_buf("a/b/c/d"),
+        repo_path_buf("a/e"),
+        repo_path_buf("f*"),
+    ];
+    git::write_sparse_checkout(repo.store(), &dirs, SparseMode::Cone).unwrap();
+    insta::assert_snapshot!(std::fs::read_to_string(&sparse_checkout_path).unwrap(), @r"
+    /*
+    !/*/
+    /a/
+    !/a/*/
+    /a/b/
+    !/a/b/*/
+    /a/b/c/
+    /a/e/
+    /f\*/
+    ");
+    assert_eq!(
+        git::read_sparse_checkout(repo.store()).unwrap(),
+        Some((
+            vec![
+                repo_path_buf("a/b/c"),
+                repo_path_buf("a/e"),
+                repo_path_buf("f*")
+            ],
+            SparseMode::Cone
+        ))
+    );
+    let config = get_git_repo(repo).config_snapshot().clone();
+    assert_eq!(
+        config.boolean("core.sparseCheckout").transpose().unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        config
+            .boolean("core.sparseCheckoutCone")
+            .transpose()
+            .unwrap(),
+        Some(true)
+    );
+
+    let prefixes = vec![repo_path_buf("a/b"), repo_path_buf("c")];
+    git::write_sparse_checkout(repo.store(), &prefixes, SparseMode::Prefix).unwrap();
+    insta::assert_snapshot!(std::fs::read_to_string(&sparse_checkout_path).unwrap(), @r"
+    /a/b
+    /c
+    ");
+    assert_eq!(
+        git::read_sparse_checkout(repo.store()).unwrap(),
+        Some((prefixes, SparseMode::Prefix))
+    );
+    let config = get_git_repo(repo).config_snapshot().clone();
+    assert_eq!(
+        config
+            .boolean("core.sparseCheckoutCone")
+            .transpose()
+            .unwrap(),
+        Some(false)
+    );
+
+    // Everything is included
+    git::write_sparse_checkout(repo.store(), &[RepoPathBuf::root()], SparseMode::Cone).unwrap();
+    assert_eq!(
+        git::read_sparse_checkout(repo.store()).unwrap(),
+        Some((vec![RepoPathBuf::root()], SparseMode::Cone))
+    );
+}
+
+#[test]
+fn test_read_sparse_checkout_unsupported_pattern() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let info_dir = get_git_repo(repo).path().join("info");
+    std::fs::create_dir_all(&info_dir).unwrap();
+    std::fs::write(info_dir.join("sparse-checkout"), "/a/\n*.txt\n").unwrap();
+    assert_matches!(
+        git::read_sparse_checkout(repo.store()),
+        Err(git::GitSparseCheckoutError::UnsupportedPattern(pattern)) if pattern == "*.txt"
+    );
+}
+
 fn auto_track_all() -> HashMap<RemoteNameBuf, RemoteSettings> {
     let settings = RemoteSettings {
         auto_track_bookmarks: StringPattern::parse("glob:*").unwrap(),
+        ssh_identity_file: None,
+        ssh_proxy_jump: None,
     };
     let remotes_used_in_tests = ["origin", "upstream"];
     remotes_used_in_tests
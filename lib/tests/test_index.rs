@@ -14,7 +14,9 @@
 
 use std::collections::HashSet;
 use std::fs;
+use std::slice;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use assert_matches::assert_matches;
 use itertools::Itertools as _;
@@ -822,6 +824,63 @@ fn test_read_legacy_operation_link_file() {
     assert!(op_links_dir.join(repo.op_id().hex()).exists());
 }
 
+#[test]
+fn test_index_store_gc() {
+    let test_repo = TestRepo::init();
+    let repo_0 = &test_repo.repo;
+    let index_dir = test_repo.repo_path().join("index");
+    let list_dir = |name: &str| -> HashSet<String> {
+        fs::read_dir(index_dir.join(name))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_owned())
+            .collect()
+    };
+
+    // Set up operation graph:
+    //
+    // B C
+    // |/
+    // A
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction();
+        write_random_commit(tx.repo_mut());
+        tx
+    };
+    let repo_a = random_tx(repo_0).commit("op A").unwrap();
+    let repo_b = random_tx(&repo_a).commit("op B").unwrap();
+    let segments_b = list_dir("segments");
+    let repo_c = random_tx(&repo_a).commit("op C").unwrap();
+    let segments_c = list_dir("segments");
+    let c_only_segments = segments_c.difference(&segments_b).cloned().collect_vec();
+    assert!(!c_only_segments.is_empty());
+
+    // C is no longer reachable, but kept by file modification time
+    let op_store = repo_0.op_store();
+    op_store
+        .gc(slice::from_ref(repo_b.op_id()), SystemTime::now())
+        .unwrap();
+    let index_store: &DefaultIndexStore = repo_0.index_store().downcast_ref().unwrap();
+    index_store
+        .gc(op_store.as_ref(), SystemTime::UNIX_EPOCH)
+        .unwrap();
+    assert!(list_dir("op_links").contains(&repo_c.op_id().hex()));
+    assert_eq!(list_dir("segments"), segments_c);
+
+    // Link and segments of C are removed
+    index_store
+        .gc(op_store.as_ref(), SystemTime::now())
+        .unwrap();
+    assert!(!list_dir("op_links").contains(&repo_c.op_id().hex()));
+    assert!(!list_dir("operations").contains(&repo_c.op_id().hex()));
+    assert!(list_dir("op_links").contains(&repo_b.op_id().hex()));
+    let segments = list_dir("segments");
+    assert!(c_only_segments.iter().all(|name| !segments.contains(name)));
+
+    // The index of B can still be loaded
+    let repo_b = repo_b.reload_at(repo_b.operation()).unwrap();
+    assert_eq!(as_readonly_index(&repo_b).num_commits(), 3);
+}
+
 #[test]
 fn test_changed_path_segments() {
     let test_repo = TestRepo::init();
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::fs::File;
+use std::num::NonZeroUsize;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt as _;
 use std::path::Path;
@@ -2041,6 +2042,63 @@ fn test_fsmonitor() {
     tree_state.save().unwrap();
 }
 
+#[test]
+fn test_check_out_with_jobs() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let workspace_root = test_repo.env.root().join("workspace");
+    let state_path = test_repo.env.root().join("state");
+    std::fs::create_dir(&workspace_root).unwrap();
+    std::fs::create_dir(&state_path).unwrap();
+    let tree_state_settings = TreeStateSettings {
+        jobs: Some(NonZeroUsize::new(4).unwrap()),
+        ..TreeStateSettings::try_from_user_settings(repo.settings()).unwrap()
+    };
+    let mut tree_state = TreeState::init(
+        repo.store().clone(),
+        workspace_root.clone(),
+        state_path,
+        &tree_state_settings,
+    )
+    .unwrap();
+
+    let paths = (0..20)
+        .flat_map(|i| (0..20).map(move |j| repo_path_buf(format!("dir{i}/file{j}"))))
+        .collect_vec();
+    let tree1 = create_tree_with(repo, |builder| {
+        for path in &paths {
+            builder.file(path, path.as_internal_file_string());
+        }
+    });
+    // Replace dir0 with a file, and empty dir1
+    let tree2 = create_tree_with(repo, |builder| {
+        builder.file(repo_path("dir0"), "file");
+        for path in &paths[40..] {
+            builder.file(path, path.as_internal_file_string());
+        }
+    });
+
+    let stats = tree_state.check_out(&tree1).unwrap();
+    assert_eq!(stats.added_files, 400);
+    for path in &paths {
+        let disk_path = path.to_fs_path_unchecked(&workspace_root);
+        assert_eq!(
+            std::fs::read_to_string(disk_path).unwrap(),
+            path.as_internal_file_string()
+        );
+    }
+    tree_state.snapshot(&empty_snapshot_options()).unwrap();
+    assert_tree_eq!(*tree_state.current_tree(), tree1);
+
+    let stats = tree_state.check_out(&tree2).unwrap();
+    assert_eq!(stats.added_files, 1);
+    assert_eq!(stats.removed_files, 40);
+    assert!(workspace_root.join("dir0").is_file());
+    assert!(!workspace_root.join("dir1").exists());
+    tree_state.snapshot(&empty_snapshot_options()).unwrap();
+    assert_tree_eq!(*tree_state.current_tree(), tree2);
+}
+
 #[test]
 fn test_snapshot_max_new_file_size() {
     let mut test_workspace = TestWorkspace::init();
@@ -2732,6 +2732,89 @@ fn test_evaluate_expression_remote_bookmarks() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_ahead_of_behind() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit_with_parents(mut_repo, &[&commit1]);
+    let commit3 = write_random_commit_with_parents(mut_repo, &[&commit2]);
+    let commit4 = write_random_commit_with_parents(mut_repo, &[&commit1]);
+
+    // Nothing to compare when there are no bookmarks
+    assert_eq!(resolve_commit_ids(mut_repo, "ahead_of()"), vec![]);
+    assert_eq!(resolve_commit_ids(mut_repo, "behind()"), vec![]);
+    assert_eq!(resolve_commit_ids(mut_repo, "unpushed()"), vec![]);
+
+    // "main" is ahead of its remote
+    mut_repo.set_local_bookmark_target("main".as_ref(), RefTarget::normal(commit3.id().clone()));
+    mut_repo.set_remote_bookmark(
+        remote_symbol("main", "origin"),
+        RemoteRef {
+            target: RefTarget::normal(commit1.id().clone()),
+            state: RemoteRefState::Tracked,
+        },
+    );
+    // "feature" is behind its remote
+    mut_repo.set_local_bookmark_target("feature".as_ref(), RefTarget::normal(commit1.id().clone()));
+    mut_repo.set_remote_bookmark(
+        remote_symbol("feature", "origin"),
+        RemoteRef {
+            target: RefTarget::normal(commit4.id().clone()),
+            state: RemoteRefState::Tracked,
+        },
+    );
+    // Untracked remote bookmarks aren't compared
+    mut_repo.set_local_bookmark_target("topic".as_ref(), RefTarget::normal(commit4.id().clone()));
+    mut_repo.set_remote_bookmark(
+        remote_symbol("topic", "origin"),
+        RemoteRef {
+            target: RefTarget::normal(commit1.id().clone()),
+            state: RemoteRefState::New,
+        },
+    );
+
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "ahead_of()"),
+        vec![commit3.id().clone(), commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "ahead_of(exact:main, exact:origin)"),
+        vec![commit3.id().clone(), commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "ahead_of(exact:feature)"),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "ahead_of(remote=exact:upstream)"),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "behind()"),
+        vec![commit4.id().clone()]
+    );
+    assert_eq!(resolve_commit_ids(mut_repo, "behind(exact:main)"), vec![]);
+
+    // Commits on any remote bookmark, tracked or not, are pushed
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "unpushed()"),
+        vec![commit3.id().clone(), commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "unpushed(remote=exact:upstream)"),
+        vec![
+            commit4.id().clone(),
+            commit3.id().clone(),
+            commit2.id().clone(),
+            commit1.id().clone(),
+        ]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_tags() {
     let test_repo = TestRepo::init();